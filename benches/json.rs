@@ -0,0 +1,42 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use livesplit_core::{
+    Layout, Timer, layout::LayoutState, run::parser::livesplit, settings::ImageCache, util::json,
+};
+use std::fs;
+
+criterion_main!(benches);
+criterion_group!(benches, serde_json_writer, json_to_vec);
+
+fn state() -> LayoutState {
+    let buf = fs::read_to_string("tests/run_files/Celeste - Any% (1.2.1.5).lss").unwrap();
+    let run = livesplit::parse(&buf).unwrap();
+
+    let mut timer = Timer::new(run).unwrap();
+    timer.start().unwrap();
+
+    Layout::default_layout().state(&mut ImageCache::new(), &timer.snapshot())
+}
+
+fn serde_json_writer(c: &mut Criterion) {
+    let state = state();
+    let mut buf = Vec::new();
+
+    c.bench_function("serde_json Writer", move |b| {
+        b.iter(|| {
+            buf.clear();
+            state.write_json(&mut buf).unwrap();
+        })
+    });
+}
+
+fn json_to_vec(c: &mut Criterion) {
+    let state = state();
+    let mut buf = Vec::new();
+
+    c.bench_function("JSON to Vec", move |b| {
+        b.iter(|| {
+            buf.clear();
+            json::to_vec(&state, &mut buf).unwrap();
+        })
+    });
+}
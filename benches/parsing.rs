@@ -1,10 +1,19 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use livesplit_core::run::parser::livesplit;
+use livesplit_core::run::{
+    parser::{binary, livesplit},
+    saver,
+};
 use std::fs;
 
 criterion_main!(benches);
-criterion_group!(benches, huge_game_icon, lots_of_icons, no_icons);
+criterion_group!(
+    benches,
+    huge_game_icon,
+    lots_of_icons,
+    no_icons,
+    binary_lots_of_icons
+);
 
 fn huge_game_icon(c: &mut Criterion) {
     let buf = fs::read_to_string("tests/run_files/livesplit1.6_gametime.lss").unwrap();
@@ -29,3 +38,16 @@ fn no_icons(c: &mut Criterion) {
         b.iter(|| livesplit::parse(&buf).unwrap())
     });
 }
+
+fn binary_lots_of_icons(c: &mut Criterion) {
+    let run = livesplit::parse(
+        &fs::read_to_string("tests/run_files/Celeste - Any% (1.2.1.5).lss").unwrap(),
+    )
+    .unwrap();
+    let mut buf = Vec::new();
+    saver::binary::save_run(&run, &mut buf);
+
+    c.bench_function("Parse Binary with lots of Icons", move |b| {
+        b.iter(|| binary::parse(&buf).unwrap())
+    });
+}
@@ -4,8 +4,10 @@ export type ComponentStateJson =
     { DetailedTimer: DetailedTimerComponentStateJson } |
     { Graph: GraphComponentStateJson } |
     { KeyValue: KeyValueComponentStateJson } |
+    { OverlayWidgets: OverlayWidgetsComponentStateJson } |
     { Separator: null } |
     { Splits: SplitsComponentStateJson } |
+    { Tabs: TabsComponentStateJson } |
     { Text: TextComponentStateJson } |
     { Timer: TimerComponentStateJson } |
     { Title: TitleComponentStateJson };
@@ -25,7 +27,12 @@ export type Gradient =
     "Transparent" |
     { Plain: Color } |
     { Vertical: Color[] } |
-    { Horizontal: Color[] };
+    { Horizontal: Color[] } |
+    { Angled: [Color, Color, number] } |
+    { Radial: [Color, Color, number[], number] } |
+    { Conic: [Color, Color, number[], number] } |
+    { HueCycling: [Color, Color, number] } |
+    { Rotating: [Color, Color, number] };
 
 /**
  * Describes an extended form of a gradient, specifically made for use with
@@ -84,6 +91,11 @@ export type Alignment = "Auto" | "Left" | "Center";
 export interface LayoutStateJson {
     /** The state objects for all of the components in the layout. */
     components: ComponentStateJson[],
+    /**
+     * Describes how each of the components is composited onto the layout.
+     * There is one appearance for each of the components.
+     */
+    component_appearances: ComponentAppearance[],
     /** The direction which the components are laid out in. */
     direction: LayoutDirection,
     /**
@@ -109,8 +121,80 @@ export interface LayoutStateJson {
     separators_color: Color,
     /** The text color to use for text that doesn't specify its own color. */
     text_color: Color,
+    /**
+     * The scale factors to apply when rendering the layout, based on the
+     * layout's scaling rules and the size it is shown at.
+     */
+    scaling: LayoutScaling,
+    /**
+     * The number of seconds that passed since the layout started being shown.
+     * Animated gradients are resolved based on this time.
+     */
+    animation_time: number,
 }
 
+/**
+ * The scale factors the scaling rules of a layout resolve to for the size the
+ * layout is currently shown at.
+ */
+export interface LayoutScaling {
+    /** The factor to scale all the text by. */
+    text_scale: number,
+    /**
+     * The factor to scale the height of the components by when they are laid
+     * out vertically. The components are centered within the additional space.
+     */
+    row_height_scale: number,
+}
+
+/**
+ * Describes how a component is composited onto the layout's background and the
+ * other components.
+ */
+export interface ComponentAppearance {
+    /**
+     * The opacity of the whole component, between 0 (fully transparent) and 1
+     * (fully opaque).
+     */
+    opacity: number,
+    /** How the colors of the component are combined with the colors below it. */
+    blend_mode: BlendMode,
+    /**
+     * An image that is shown behind the component. The component's own
+     * background is drawn on top of it.
+     */
+    background_image?: ComponentBackgroundImage,
+}
+
+/**
+ * An image that is shown behind a single component, such as art behind the
+ * title.
+ */
+export interface ComponentBackgroundImage {
+    /** The image ID to look up the actual image in an image cache. */
+    image: ImageId,
+    /** Describes how the image is fit into the area of the component. */
+    fit: ImageFit,
+}
+
+/**
+ * Describes how an image is fit into an area that may have a different aspect
+ * ratio than the image. `Fit` preserves the aspect ratio of the image and
+ * centers it, `Stretch` fills the whole area and `Tile` repeats the image side
+ * by side.
+ */
+export type ImageFit = "Fit" | "Stretch" | "Tile";
+
+/** Describes how the colors of a component are combined with the colors below it. */
+export type BlendMode =
+    "Normal" |
+    "Multiply" |
+    "Screen" |
+    "Overlay" |
+    "Darken" |
+    "Lighten" |
+    "Add";
+
 /**
  * Describes a Font to visualize text with. Depending on the platform a font
  * that matches the settings most closely is chosen. The settings may be ignored
@@ -139,6 +223,11 @@ export interface Font {
     weight: FontWeight,
     /** The stretch of the font to prefer selecting. */
     stretch: FontStretch,
+    /**
+     * The contents of a font file, encoded as Base64, to use instead of
+     * looking up the font among the fonts installed on the system.
+     */
+    data?: string,
 }
 
 /**
@@ -259,6 +348,16 @@ export enum Event {
     LoadingTimesSet = 16,
     /** A custom variable has been set. */
     CustomVariableSet = 17,
+    /** The most recent action has been undone. */
+    ActionUndone = 18,
+    /** The most recently undone action has been redone. */
+    ActionRedone = 19,
+    /** The offset of the current attempt has been adjusted. */
+    OffsetAdjusted = 20,
+    /** Time has been added to or removed from the current attempt. */
+    TimeAdjusted = 21,
+    /** The overlay widgets have been set. */
+    OverlayWidgetsSet = 22,
 }
 
 /** An error that occurred when a command was being processed. */
@@ -297,6 +396,19 @@ export enum CommandError {
     TimerPaused = -16,
     /** The runner decided to not reset the run. */
     RunnerDecidedAgainstReset = -17,
+    /** There is no action to undo. */
+    NothingToUndo = -18,
+    /** There is no action to redo. */
+    NothingToRedo = -19,
+    /** The index of the segment is out of bounds. */
+    SegmentIndexOutOfBounds = -20,
+    /**
+     * The split happened too soon after the previous split or the start of
+     * the attempt.
+     */
+    SplitCooldown = -21,
+    /** The game time is not initialized. */
+    GameTimeNotInitialized = -22,
 }
 
 /** The result of a command that was processed. */
@@ -512,6 +624,47 @@ export interface KeyValueComponentStateJson {
     updates_frequently: boolean,
 }
 
+/** The state object describes the information to visualize for this component. */
+export interface OverlayWidgetsComponentStateJson {
+    /** The background shown behind the component. */
+    background: Gradient,
+    /**
+     * The color of the labels. If `null` is specified, the color is taken from
+     * the layout.
+     */
+    label_color: Color | null,
+    /**
+     * The color of the values. If `null` is specified, the color is taken from
+     * the layout.
+     */
+    value_color: Color | null,
+    /** The color of the filled part of the progress bars. */
+    progress_color: Color,
+    /**
+     * Specifies whether to display the label of each widget and its value in
+     * two separate rows.
+     */
+    display_two_rows: boolean,
+    /** The state objects for all of the widgets to show. */
+    widgets: OverlayWidgetsComponentStateWidgetJson[],
+}
+
+/** The state object for a single widget of the Overlay Widgets Component. */
+export interface OverlayWidgetsComponentStateWidgetJson {
+    /** The label of the widget. */
+    label: string,
+    /**
+     * The value to show next to the label. For progress bars this is the
+     * progress formatted as a percentage.
+     */
+    value: string,
+    /**
+     * The fraction between 0 and 1 that the progress bar is filled by. This is
+     * `null` if the widget is not a progress bar.
+     */
+    progress: number | null,
+}
+
 /**
  * The state object describes the information to visualize for this component.
  * All the coordinates are in the range 0..1.
@@ -588,6 +741,23 @@ export interface GraphComponentStatePointJson {
     is_best_segment: boolean,
 }
 
+/** The state object describes the information to visualize for this component. */
+export interface TabsComponentStateJson {
+    /** The background shown behind the component. */
+    background: Gradient,
+    /** The names of all the tabs. */
+    tab_names: string[],
+    /** The index of the tab that is shown. */
+    selected_tab: number,
+    /**
+     * Specifies whether the name of the selected tab is shown above its
+     * components.
+     */
+    show_tab_names: boolean,
+    /** The state objects for all of the components of the selected tab. */
+    components: ComponentStateJson[],
+}
+
 /** The state object describes the information to visualize for this component. */
 export interface TextComponentStateJson {
     /** The background shown behind the component. */
@@ -675,13 +845,26 @@ export interface LayoutEditorStateJson {
     components: string[],
     /** Describes which actions are currently available. */
     buttons: LayoutEditorButtonsJson,
-    /** The index of the currently selected component. */
+    /**
+     * The index of the currently selected component. This is the component
+     * whose settings are shown.
+     */
     selected_component: number,
+    /**
+     * The indices of all the selected components in ascending order. This
+     * always includes the selected component whose settings are shown.
+     */
+    selected_components: number[],
     /**
      * A generic description of the settings available for the selected
      * component and their current values.
      */
     component_settings: SettingsDescriptionJson,
+    /**
+     * A generic description of the settings available for the appearance of
+     * the selected component and their current values.
+     */
+    component_appearance: SettingsDescriptionJson,
     /**
      * A generic description of the general settings available for the layout
      * and their current values.
@@ -689,6 +872,26 @@ export interface LayoutEditorStateJson {
     general_settings: SettingsDescriptionJson,
 }
 
+/**
+ * Describes which settings of a layout changed since the changes were last
+ * taken.
+ */
+export interface SettingsChangesJson {
+    /** Specifies whether the general settings of the layout changed. */
+    general_settings: boolean,
+    /**
+     * Specifies whether components were added, removed or moved. In that case
+     * the indices of the components may have changed as well, so all of them
+     * need to be considered changed.
+     */
+    components_rearranged: boolean,
+    /**
+     * The indices of the components whose settings or appearance changed, in
+     * ascending order. This is empty if the components got rearranged.
+     */
+    components: number[],
+}
+
 /**
  * Describes which actions are currently available. Depending on how many
  * components exist and which one is selected, only some actions can be executed
@@ -696,20 +899,24 @@ export interface LayoutEditorStateJson {
  */
 export interface LayoutEditorButtonsJson {
     /**
-     * Describes whether the currently selected component can be removed. If
-     * there's only one component in the layout, it can't be removed.
+     * Describes whether the currently selected components can be removed. If
+     * all the components of the layout are selected, they can't be removed.
      */
     can_remove: boolean,
     /**
-     * Describes whether the currently selected component can be moved up. If
-     * the first component is selected, it can't be moved.
+     * Describes whether the currently selected components can be moved up. If
+     * the first component is selected, they can't be moved.
      */
     can_move_up: boolean,
     /**
-     * Describes whether the currently selected component can be moved down. If
-     * the last component is selected, it can't be moved.
+     * Describes whether the currently selected components can be moved down.
+     * If the last component is selected, they can't be moved.
      */
     can_move_down: boolean,
+    /** Describes whether there is a change that can be undone. */
+    can_undo: boolean,
+    /** Describes whether there is an undone change that can be redone. */
+    can_redo: boolean,
 }
 
 /** A generic description of the settings available and their current values. */
@@ -719,16 +926,93 @@ export interface SettingsDescriptionJson {
      * values.
      */
     fields: SettingsDescriptionFieldJson[],
+    /**
+     * The groups the fields are organized in. Each group covers a contiguous
+     * range of fields and the groups are ordered by the fields they cover.
+     * Fields that are not covered by any group are meant to be shown without
+     * a heading. The groups don't affect the indices of the fields.
+     */
+    groups?: SettingsGroupJson[],
+}
+
+/**
+ * A group of fields that is meant to be shown under a common heading, such as
+ * all the settings that affect the colors of a component. Settings dialogs can
+ * show each group as a collapsible section.
+ */
+export interface SettingsGroupJson {
+    /**
+     * An identifier of the kind of group that stays the same even if the
+     * heading changes. This can be used to look up a translation of the
+     * heading. Groups that are repeated, such as the groups of the columns of
+     * the Splits Component, share the same key. This is null if the
+     * heading is not meant to be translated, such as when it is a name the
+     * user chose.
+     */
+    key: string | null,
+    /** The heading of the group. */
+    heading: string,
+    /** The index of the first field that is part of the group. */
+    start: number,
+    /** The index right after the last field that is part of the group. */
+    end: number,
+    /** Specifies whether the group should start out collapsed. */
+    collapsed: boolean,
 }
 
 /** A Field describes a single setting by its name and its current value. */
 export interface SettingsDescriptionFieldJson {
+    /**
+     * An identifier of the kind of setting that stays the same even if the
+     * name of the setting changes. This can be used to look up translations
+     * of the name and the tooltip. Settings that are repeated, such as the
+     * settings of each column of the Splits Component, share the same key, so
+     * only the index of a field identifies a specific setting.
+     */
+    key: string,
     /** The name of the setting. */
     text: string,
     /** The tooltip to show for the setting. */
     tooltip: string,
     /** The current value of the setting. */
     value: SettingsDescriptionValueJson,
+    /** The constraints the values of the setting need to satisfy. */
+    constraints?: SettingsConstraintsJson,
+    /**
+     * The value the setting has by default. This is missing if the default is
+     * not known, such as for settings that only exist because of the values of
+     * other settings.
+     */
+    default_value?: SettingsDescriptionValueJson,
+}
+
+/**
+ * Constraints restrict the values a setting accepts. They can be used to
+ * configure the widgets, such as the range of a number input or the file types
+ * shown by a file picker.
+ */
+export interface SettingsConstraintsJson {
+    /** The smallest number that is allowed. This applies to integer values. */
+    min: number | null,
+    /** The largest number that is allowed. This applies to integer values. */
+    max: number | null,
+    /**
+     * The largest number of characters that is allowed. This applies to string
+     * values.
+     */
+    max_length: number | null,
+    /**
+     * A regular expression that string values need to match in their
+     * entirety. This is only available if the `setting-patterns` feature is
+     * enabled.
+     */
+    pattern?: string | null,
+    /**
+     * The file extensions that are allowed, without the leading dot. This
+     * applies to string values that are paths to files. If the list is empty,
+     * any file is allowed.
+     */
+    file_extensions: string[],
 }
 
 /**
@@ -758,7 +1042,23 @@ export type SettingsDescriptionValueJson =
     { Font: Font | null } |
     { DeltaGradient: DeltaGradient } |
     { LayoutBackground: LayoutBackground } |
-    { CustomCombobox: CustomCombobox };
+    { ThemedColor: ThemedColor } |
+    { BlendMode: BlendMode } |
+    { ComponentBackgroundImage: ComponentBackgroundImage | null } |
+    { CustomCombobox: CustomCombobox } |
+    { List: SettingsDescriptionValueListJson };
+
+/**
+ * An ordered list of setting values, such as the names of the tabs of a Tabs
+ * component. Items can be added, removed and reordered by setting the whole
+ * list as the new value.
+ */
+export interface SettingsDescriptionValueListJson {
+    /** The values of the items of the list. */
+    items: SettingsDescriptionValueJson[],
+    /** The value a new item that gets added to the list starts out with. */
+    new_item: SettingsDescriptionValueJson,
+}
 
 /** Describes the kind of a column. */
 export type ColumnKind = "Time" | "Variable";
@@ -766,6 +1066,31 @@ export type ColumnKind = "Time" | "Variable";
 /** Represents the possible backgrounds for a timer. */
 export type DeltaGradient = Gradient | "DeltaPlain" | "DeltaVertical" | "DeltaHorizontal";
 
+/**
+ * Refers to one of the colors of the layout's theme by its meaning rather than
+ * by its actual value.
+ */
+export type ThemeColor =
+    "Background" |
+    "TextPrimary" |
+    "TextShadow" |
+    "Separators" |
+    "ThinSeparators" |
+    "BestSegment" |
+    "AheadGaining" |
+    "AheadLosing" |
+    "BehindGaining" |
+    "BehindLosing" |
+    "NotRunning" |
+    "PersonalBest" |
+    "Paused";
+
+/**
+ * A color that is either a fixed color or refers to one of the colors of the
+ * layout's theme or, by its name, to one of the colors of the layout's palette.
+ */
+export type ThemedColor = Color | ThemeColor | { palette: string };
+
 /** Describes the direction the components of a layout are laid out in. */
 export type LayoutDirection = "Vertical" | "Horizontal";
 
@@ -963,6 +1288,11 @@ export interface RunEditorButtonsJson {
      * moved.
      */
     can_move_down: boolean,
+    /**
+     * Describes whether the active segment can be merged with the segment
+     * below it. The last segment can't be merged.
+     */
+    can_merge_with_next: boolean,
 }
 
 /** Describes the current state of a segment. */
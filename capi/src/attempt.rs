@@ -2,10 +2,10 @@
 //! by a specific runner in the past. Every time a new attempt is started and
 //! then reset, an Attempt describing general information about it is created.
 
-use super::{output_time, output_time_span};
+use super::{Nullablec_char, output_str, output_time, output_time_span, str};
 use crate::{atomic_date_time::NullableOwnedAtomicDateTime, time_span::NullableTimeSpan};
 use livesplit_core::{Attempt, Time};
-use std::ptr;
+use std::{os::raw::c_char, ptr};
 
 /// type
 pub type OwnedAttempt = Box<Attempt>;
@@ -50,3 +50,22 @@ pub extern "C" fn Attempt_started(this: &Attempt) -> NullableOwnedAtomicDateTime
 pub extern "C" fn Attempt_ended(this: &Attempt) -> NullableOwnedAtomicDateTime {
     this.ended().map(Box::new)
 }
+
+/// Accesses the value the custom variable with the name specified had at the
+/// end of the attempt. <NULL> is returned if the value wasn't stored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Attempt_variable(
+    this: &Attempt,
+    name: *const c_char,
+) -> *const Nullablec_char {
+    // SAFETY: The caller guarantees that `name` is valid.
+    this.variable(unsafe { str(name) })
+        .map_or_else(ptr::null, output_str)
+}
+
+/// Returns whether the tag specified is attached to the attempt.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Attempt_has_tag(this: &Attempt, tag: *const c_char) -> bool {
+    // SAFETY: The caller guarantees that `tag` is valid.
+    this.has_tag(unsafe { str(tag) })
+}
@@ -29,6 +29,8 @@ impl AutoSplittingRuntime {
     pub fn load(&self, _: PathBuf, _: SharedTimer) -> Result<(), ()> {
         Err(())
     }
+
+    pub fn set_hot_reload(&self, _: bool) {}
 }
 
 /// type
@@ -60,6 +62,16 @@ pub extern "C" fn AutoSplittingRuntime_unload(this: &AutoSplittingRuntime) -> bo
     this.unload().is_ok()
 }
 
+/// Enables or disables hot reloading. While enabled, the auto splitter gets
+/// reloaded whenever the file it got loaded from changes.
+#[unsafe(no_mangle)]
+pub extern "C" fn AutoSplittingRuntime_set_hot_reload(
+    this: &AutoSplittingRuntime,
+    hot_reload: bool,
+) {
+    this.set_hot_reload(hot_reload);
+}
+
 /// drop
 #[unsafe(no_mangle)]
 pub extern "C" fn AutoSplittingRuntime_drop(this: OwnedAutoSplittingRuntime) {
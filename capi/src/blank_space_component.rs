@@ -5,7 +5,7 @@
 use super::{output_vec, Json};
 use crate::blank_space_component_state::OwnedBlankSpaceComponentState;
 use crate::component::OwnedComponent;
-use livesplit_core::component::blank_space::Component as BlankSpaceComponent;
+use livesplit_core::{component::blank_space::Component as BlankSpaceComponent, util::json};
 
 /// type
 pub type OwnedBlankSpaceComponent = Box<BlankSpaceComponent>;
@@ -35,7 +35,7 @@ pub extern "C" fn BlankSpaceComponent_into_generic(
 #[unsafe(no_mangle)]
 pub extern "C" fn BlankSpaceComponent_state_as_json(this: &mut BlankSpaceComponent) -> Json {
     output_vec(|o| {
-        this.state().write_json(o).unwrap();
+        json::to_vec(&this.state(), o).unwrap();
     })
 }
 
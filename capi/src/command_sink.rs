@@ -13,6 +13,7 @@ use std::{borrow::Cow, future::Future, ops::Deref, pin::Pin, sync::Arc};
 use livesplit_core::{
     TimeSpan, Timer, TimingMethod,
     event::{self, Result},
+    timing::OverlayWidget,
 };
 
 use crate::shared_timer::OwnedSharedTimer;
@@ -59,6 +60,7 @@ pub(crate) trait CommandSinkAndQuery: Send + Sync + 'static {
     fn dyn_resume_game_time(&self) -> Fut;
     fn dyn_set_loading_times(&self, time: TimeSpan) -> Fut;
     fn dyn_set_custom_variable(&self, name: Cow<str>, value: Cow<str>) -> Fut;
+    fn dyn_set_overlay_widgets(&self, widgets: Vec<OverlayWidget>) -> Fut;
 }
 
 type Fut = Pin<Box<dyn Future<Output = Result> + 'static>>;
@@ -134,6 +136,9 @@ where
     fn dyn_set_custom_variable(&self, name: Cow<str>, value: Cow<str>) -> Fut {
         Box::pin(self.set_custom_variable(name, value))
     }
+    fn dyn_set_overlay_widgets(&self, widgets: Vec<OverlayWidget>) -> Fut {
+        Box::pin(self.set_overlay_widgets(widgets))
+    }
 }
 
 impl event::CommandSink for CommandSink {
@@ -230,6 +235,13 @@ impl event::CommandSink for CommandSink {
     ) -> impl Future<Output = Result> + 'static {
         self.0.dyn_set_custom_variable(name, value)
     }
+
+    fn set_overlay_widgets(
+        &self,
+        widgets: Vec<OverlayWidget>,
+    ) -> impl Future<Output = Result> + 'static {
+        self.0.dyn_set_overlay_widgets(widgets)
+    }
 }
 
 impl event::TimerQuery for CommandSink {
@@ -1,11 +1,11 @@
 //! The Current Comparison Component is a component that shows the name of the
 //! comparison that is currently selected to be compared against.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::current_comparison::Component as CurrentComparisonComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedCurrentComparisonComponent = Box<CurrentComparisonComponent>;
@@ -38,7 +38,7 @@ pub extern "C" fn CurrentComparisonComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(timer).write_json(o).unwrap();
+        json::to_vec(&this.state(timer), o).unwrap();
     })
 }
 
@@ -2,11 +2,11 @@
 //! current attempt's final time, if the current attempt's pace matches the
 //! chosen comparison for the remainder of the run.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::current_pace::Component as CurrentPaceComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedCurrentPaceComponent = Box<CurrentPaceComponent>;
@@ -39,7 +39,7 @@ pub extern "C" fn CurrentPaceComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot()).write_json(o).unwrap();
+        json::to_vec(&this.state(&timer.snapshot()), o).unwrap();
     })
 }
 
@@ -1,9 +1,11 @@
 //! The Delta Component is a component that shows how far ahead or behind the
 //! current attempt is compared to the chosen comparison.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::{component::OwnedComponent, key_value_component_state::OwnedKeyValueComponentState};
-use livesplit_core::{component::delta::Component as DeltaComponent, GeneralLayoutSettings, Timer};
+use livesplit_core::{
+    GeneralLayoutSettings, Timer, component::delta::Component as DeltaComponent, util::json,
+};
 
 /// type
 pub type OwnedDeltaComponent = Box<DeltaComponent>;
@@ -35,9 +37,7 @@ pub extern "C" fn DeltaComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.state(&timer.snapshot(), layout_settings), o).unwrap();
     })
 }
 
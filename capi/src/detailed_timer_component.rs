@@ -3,13 +3,13 @@
 //! current segment. Other information, like segment times of up to two
 //! comparisons, the segment icon, and the segment's name, can also be shown.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::{
     component::OwnedComponent, detailed_timer_component_state::OwnedDetailedTimerComponentState,
 };
 use livesplit_core::{
-    component::detailed_timer::Component as DetailedTimerComponent, settings::ImageCache,
-    GeneralLayoutSettings, Timer,
+    GeneralLayoutSettings, Timer, component::detailed_timer::Component as DetailedTimerComponent,
+    settings::ImageCache, util::json,
 };
 
 /// type
@@ -45,9 +45,11 @@ pub extern "C" fn DetailedTimerComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(image_cache, &timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(
+            &this.state(image_cache, &timer.snapshot(), layout_settings),
+            o,
+        )
+        .unwrap();
     })
 }
 
@@ -6,7 +6,7 @@ use super::{output_vec, Json};
 use crate::component::OwnedComponent;
 use crate::graph_component_state::OwnedGraphComponentState;
 use livesplit_core::component::graph::Component as GraphComponent;
-use livesplit_core::{GeneralLayoutSettings, Timer};
+use livesplit_core::{GeneralLayoutSettings, Timer, util::json};
 
 /// type
 pub type OwnedGraphComponent = Box<GraphComponent>;
@@ -38,9 +38,7 @@ pub extern "C" fn GraphComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.state(&timer.snapshot(), layout_settings), o).unwrap();
     })
 }
 
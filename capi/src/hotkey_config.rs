@@ -3,7 +3,7 @@
 
 use super::{Json, get_file, output_vec, str};
 use crate::setting_value::OwnedSettingValue;
-use livesplit_core::HotkeyConfig;
+use livesplit_core::{HotkeyConfig, util::json};
 use std::io::{BufReader, Cursor};
 
 /// type
@@ -52,7 +52,7 @@ pub extern "C" fn HotkeyConfig_set_value(
 #[unsafe(no_mangle)]
 pub extern "C" fn HotkeyConfig_as_json(this: &HotkeyConfig) -> Json {
     output_vec(|o| {
-        this.write_json(o).unwrap();
+        json::to_vec(this, o).unwrap();
     })
 }
 
@@ -54,6 +54,46 @@ pub extern "C" fn HotkeySystem_activate(this: &mut HotkeySystem) -> bool {
     this.activate().is_ok()
 }
 
+/// Reports whether the application is currently focused. The hotkeys of
+/// actions that are only supposed to be used while the application is focused
+/// are ignored otherwise. By default the application is assumed to be focused.
+#[unsafe(no_mangle)]
+pub extern "C" fn HotkeySystem_set_focused(this: &mut HotkeySystem, is_focused: bool) {
+    this.set_focused(is_focused);
+}
+
+/// Starts capturing the next hotkey that gets pressed, so it can be used as a
+/// new binding. Until then, none of the hotkeys trigger their actions. Returns
+/// <FALSE> if the hotkeys can't be captured.
+#[unsafe(no_mangle)]
+pub extern "C" fn HotkeySystem_capture_next(this: &mut HotkeySystem) -> bool {
+    this.capture_next().is_ok()
+}
+
+/// Returns whether the Hotkey System is still waiting for a hotkey to be
+/// captured.
+#[unsafe(no_mangle)]
+pub extern "C" fn HotkeySystem_is_capturing(this: &HotkeySystem) -> bool {
+    this.is_capturing()
+}
+
+/// Returns the binding of the hotkey that got captured, if it got pressed
+/// already. The hotkey is only returned once. If no hotkey got captured, an
+/// empty string is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn HotkeySystem_take_captured(this: &mut HotkeySystem) -> *const c_char {
+    match this.take_captured() {
+        Some(captured) => output_str(captured.binding.to_string()),
+        None => output_str(""),
+    }
+}
+
+/// Cancels capturing the next hotkey.
+#[unsafe(no_mangle)]
+pub extern "C" fn HotkeySystem_cancel_capture(this: &mut HotkeySystem) -> bool {
+    this.cancel_capture().is_ok()
+}
+
 /// Returns the hotkey configuration currently in use by the Hotkey System.
 #[unsafe(no_mangle)]
 pub extern "C" fn HotkeySystem_config(this: &HotkeySystem) -> OwnedHotkeyConfig {
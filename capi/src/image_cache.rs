@@ -6,7 +6,9 @@
 
 use std::{ffi::c_char, ptr, str::FromStr};
 
-use livesplit_core::settings::{HasImageId, Image, ImageCache, ImageId};
+use livesplit_core::settings::{
+    CacheLimits, EvictionPolicy, HasImageId, Image, ImageCache, ImageId,
+};
 
 use crate::{output_str, slice, str};
 
@@ -87,11 +89,54 @@ pub unsafe extern "C" fn ImageCache_cache(
 }
 
 /// Runs the garbage collection of the cache. This removes images from the cache
-/// that have not been visited since the last garbage collection. Not every
-/// image that has not been visited is removed. There is a heuristic that keeps
-/// a certain amount of images in the cache regardless of whether they have been
-/// visited or not. Returns the amount of images that got collected.
+/// that have not been visited since the last garbage collection, according to
+/// the eviction policy. By default, not every image that has not been visited
+/// is removed. There is a heuristic that keeps a certain amount of images in
+/// the cache regardless of whether they have been visited or not. Afterwards
+/// the least recently used images are removed until the cache is within its
+/// limits. Returns the amount of images that got collected.
 #[unsafe(no_mangle)]
 pub extern "C" fn ImageCache_collect(this: &mut ImageCache) -> usize {
     this.collect()
 }
+
+/// Sets the limits the garbage collection keeps the cache within. A limit of 0
+/// for either the amount of images or the amount of bytes means that there is
+/// no limit. The eviction policy is one of `Adaptive`, `Unused` and
+/// `OverLimits`. If the eviction policy is invalid, <FALSE> is returned and the
+/// limits are not changed. The limits are only applied the next time `collect`
+/// is called.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ImageCache_set_limits(
+    this: &mut ImageCache,
+    max_images: usize,
+    max_bytes: usize,
+    eviction_policy: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `eviction_policy` is valid.
+    let eviction_policy = match unsafe { str(eviction_policy) } {
+        "Adaptive" => EvictionPolicy::Adaptive,
+        "Unused" => EvictionPolicy::Unused,
+        "OverLimits" => EvictionPolicy::OverLimits,
+        _ => return false,
+    };
+    this.set_limits(CacheLimits {
+        max_entries: Some(max_images).filter(|&max| max != 0),
+        max_bytes: Some(max_bytes).filter(|&max| max != 0),
+        eviction_policy,
+    });
+    true
+}
+
+/// Returns the amount of images that are currently in the cache.
+#[unsafe(no_mangle)]
+pub extern "C" fn ImageCache_image_count(this: &ImageCache) -> usize {
+    this.usage().entries
+}
+
+/// Returns the amount of memory in bytes the images in the cache currently
+/// use.
+#[unsafe(no_mangle)]
+pub extern "C" fn ImageCache_byte_len(this: &ImageCache) -> usize {
+    this.usage().bytes
+}
@@ -7,6 +7,7 @@ use livesplit_core::{
     Layout, Timer,
     layout::{LayoutSettings, LayoutState, parser},
     settings::ImageCache,
+    util::json,
 };
 use std::io::{BufReader, Cursor};
 
@@ -116,7 +117,7 @@ pub extern "C" fn Layout_update_state_as_json(
 ) -> Json {
     this.update_state(state, image_cache, &timer.snapshot());
     output_vec(|o| {
-        state.write_json(o).unwrap();
+        json::to_vec(state, o).unwrap();
     })
 }
 
@@ -129,9 +130,7 @@ pub extern "C" fn Layout_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(image_cache, &timer.snapshot())
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.state(image_cache, &timer.snapshot()), o).unwrap();
     })
 }
 
@@ -139,7 +138,25 @@ pub extern "C" fn Layout_state_as_json(
 #[unsafe(no_mangle)]
 pub extern "C" fn Layout_settings_as_json(this: &Layout) -> Json {
     output_vec(|o| {
-        this.settings().write_json(o).unwrap();
+        json::to_vec(&this.settings(), o).unwrap();
+    })
+}
+
+/// Accesses a counter that increases whenever any of the settings of the
+/// layout change. Comparing it to the value of the last frame is a cheap way to
+/// check whether anything changed at all.
+#[unsafe(no_mangle)]
+pub extern "C" fn Layout_settings_generation(this: &Layout) -> u64 {
+    this.settings_generation()
+}
+
+/// Takes the changes to the settings of the layout that happened since the
+/// changes were last taken and encodes them as JSON. A new layout starts out
+/// with everything being considered changed.
+#[unsafe(no_mangle)]
+pub extern "C" fn Layout_take_settings_changes_as_json(this: &mut Layout) -> Json {
+    output_vec(|o| {
+        json::to_vec(&this.take_settings_changes(), o).unwrap();
     })
 }
 
@@ -149,6 +166,15 @@ pub extern "C" fn Layout_push(this: &mut Layout, component: OwnedComponent) {
     this.push(*component);
 }
 
+/// Tells the layout the size in pixels it is currently shown at. This is used
+/// to apply the layout's scaling rules when calculating its state, so it should
+/// be called whenever the size of the window or overlay that shows the layout
+/// changes.
+#[unsafe(no_mangle)]
+pub extern "C" fn Layout_set_viewport_size(this: &mut Layout, width: f32, height: f32) {
+    this.set_viewport_size([width, height]);
+}
+
 /// Scrolls up all the components in the layout that can be scrolled up.
 #[unsafe(no_mangle)]
 pub extern "C" fn Layout_scroll_up(this: &mut Layout) {
@@ -160,3 +186,15 @@ pub extern "C" fn Layout_scroll_up(this: &mut Layout) {
 pub extern "C" fn Layout_scroll_down(this: &mut Layout) {
     this.scroll_down();
 }
+
+/// Shows the next tab of all the Tabs Components in the layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn Layout_next_tab(this: &mut Layout) {
+    this.next_tab();
+}
+
+/// Shows the previous tab of all the Tabs Components in the layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn Layout_previous_tab(this: &mut Layout) {
+    this.previous_tab();
+}
@@ -3,12 +3,18 @@
 //! operations are being applied. It provides the current state of the editor as
 //! state objects that can be visualized by any kind of User Interface.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec, str};
 use crate::{
     component::OwnedComponent, layout::OwnedLayout, layout_editor_state::OwnedLayoutEditorState,
     setting_value::OwnedSettingValue,
 };
-use livesplit_core::{layout::LayoutState, settings::ImageCache, LayoutEditor, Timer};
+use livesplit_core::{
+    LayoutEditor, Timer,
+    layout::LayoutState,
+    settings::{Color, ImageCache},
+    util::json,
+};
+use std::os::raw::c_char;
 
 /// type
 pub type OwnedLayoutEditor = Box<LayoutEditor>;
@@ -38,7 +44,7 @@ pub extern "C" fn LayoutEditor_state_as_json(
     image_cache: &mut ImageCache,
 ) -> Json {
     output_vec(|o| {
-        this.state(image_cache).write_json(o).unwrap();
+        json::to_vec(&this.state(image_cache), o).unwrap();
     })
 }
 
@@ -61,9 +67,7 @@ pub extern "C" fn LayoutEditor_layout_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.layout_state(image_cache, &timer.snapshot())
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.layout_state(image_cache, &timer.snapshot()), o).unwrap();
     })
 }
 
@@ -89,18 +93,36 @@ pub extern "C" fn LayoutEditor_update_layout_state_as_json(
 ) -> Json {
     this.update_layout_state(state, image_cache, &timer.snapshot());
     output_vec(|o| {
-        state.write_json(o).unwrap();
+        json::to_vec(state, o).unwrap();
     })
 }
 
 /// Selects the component with the given index in order to modify its
-/// settings. Only a single component is selected at any given time. You may
-/// not provide an invalid index.
+/// settings. All other components get deselected. You may not provide an
+/// invalid index.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_select(this: &mut LayoutEditor, index: usize) {
     this.select(index);
 }
 
+/// Adds the component with the given index to the selection or removes it from
+/// the selection if it is already selected. A component that gets added
+/// becomes the component whose settings are shown. The last selected component
+/// can't be deselected. You may not provide an invalid index.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_toggle_selection(this: &mut LayoutEditor, index: usize) {
+    this.toggle_selection(index);
+}
+
+/// Selects all the components between the selected component and the
+/// component with the given index, including both of them. The selected
+/// component stays the component whose settings are shown. You may not provide
+/// an invalid index.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_select_range(this: &mut LayoutEditor, index: usize) {
+    this.select_range(index);
+}
+
 /// Adds the component provided to the end of the layout. The newly added
 /// component becomes the selected component.
 #[unsafe(no_mangle)]
@@ -108,37 +130,42 @@ pub extern "C" fn LayoutEditor_add_component(this: &mut LayoutEditor, component:
     this.add_component(*component);
 }
 
-/// Removes the currently selected component, unless there's only one
-/// component in the layout. The next component becomes the selected
-/// component. If there's none, the previous component becomes the selected
-/// component instead.
+/// Removes all the selected components, unless that would leave no components
+/// in the layout. The component following the first removed component becomes
+/// the selected component. If there's none, the last component becomes the
+/// selected component instead.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_remove_component(this: &mut LayoutEditor) {
     this.remove_component();
 }
 
-/// Moves the selected component up, unless the first component is selected.
+/// Moves all the selected components up by one, unless the first component is
+/// selected.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_move_component_up(this: &mut LayoutEditor) {
     this.move_component_up();
 }
 
-/// Moves the selected component down, unless the last component is
-/// selected.
+/// Moves all the selected components down by one, unless the last component
+/// is selected.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_move_component_down(this: &mut LayoutEditor) {
     this.move_component_down();
 }
 
-/// Moves the selected component to the index provided. You may not provide
-/// an invalid index.
+/// Moves the selected components such that the selected component whose
+/// settings are shown ends up at the index provided. The other selected
+/// components keep their distance to it. If they would be moved past the start
+/// or the end of the layout, they stop there. You may not provide an invalid
+/// index.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_move_component(this: &mut LayoutEditor, dst_index: usize) {
     this.move_component(dst_index);
 }
 
-/// Duplicates the currently selected component. The copy gets placed right
-/// after the selected component and becomes the newly selected component.
+/// Duplicates all the selected components. The copies get placed right after
+/// the last selected component, in the same order as the originals, and become
+/// the newly selected components.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_duplicate_component(this: &mut LayoutEditor) {
     this.duplicate_component();
@@ -147,30 +174,128 @@ pub extern "C" fn LayoutEditor_duplicate_component(this: &mut LayoutEditor) {
 /// Sets a setting's value of the selected component by its setting index
 /// to the given value.
 ///
-/// This panics if the type of the value to be set is not compatible with
-/// the type of the setting's value. A panic can also occur if the index of
-/// the setting provided is out of bounds.
+/// <FALSE> is returned and the component stays unchanged if the index of the
+/// setting is out of bounds, if the type of the value is not compatible with
+/// the type of the setting's value or if the value doesn't satisfy the
+/// setting's constraints.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_set_component_settings_value(
     this: &mut LayoutEditor,
     index: usize,
     value: OwnedSettingValue,
+) -> bool {
+    this.set_component_settings_value(index, *value).is_ok()
+}
+
+/// Resets a setting of the selected component by its setting index to its
+/// default value. Settings that don't have a default value stay unchanged.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_reset_component_settings_value(
+    this: &mut LayoutEditor,
+    index: usize,
 ) {
-    this.set_component_settings_value(index, *value);
+    this.reset_component_settings_value(index);
+}
+
+/// Sets a setting's value of the appearance of all the selected components by
+/// its setting index to the given value.
+///
+/// <FALSE> is returned and the appearances stay unchanged if the index of the
+/// setting is out of bounds, if the type of the value is not compatible with
+/// the type of the setting's value or if the value doesn't satisfy the
+/// setting's constraints.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_set_component_appearance_value(
+    this: &mut LayoutEditor,
+    index: usize,
+    value: OwnedSettingValue,
+    image_cache: &ImageCache,
+) -> bool {
+    this.set_component_appearance_value(index, *value, image_cache)
+        .is_ok()
 }
 
 /// Sets a setting's value of the general settings by its setting index to
 /// the given value.
 ///
-/// This panics if the type of the value to be set is not compatible with
-/// the type of the setting's value. A panic can also occur if the index of
-/// the setting provided is out of bounds.
+/// <FALSE> is returned and the general settings stay unchanged if the index of
+/// the setting is out of bounds, if the type of the value is not compatible with
+/// the type of the setting's value or if the value doesn't satisfy the
+/// setting's constraints.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutEditor_set_general_settings_value(
     this: &mut LayoutEditor,
     index: usize,
     value: OwnedSettingValue,
     image_cache: &ImageCache,
+) -> bool {
+    this.set_general_settings_value(index, *value, image_cache)
+        .is_ok()
+}
+
+/// Resets a setting of the general settings by its setting index to its
+/// default value.
+///
+/// This panics if the index of the setting provided is out of bounds.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_reset_general_settings_value(this: &mut LayoutEditor, index: usize) {
+    this.reset_general_settings_value(index);
+}
+
+/// Sets the color of the layout's palette with the name provided as RGBA. If
+/// the palette doesn't contain a color with that name yet, it gets added.
+/// Every setting that refers to the color by its name changes along with it.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn LayoutEditor_set_palette_color(
+    this: &mut LayoutEditor,
+    name: *const c_char,
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
 ) {
-    this.set_general_settings_value(index, *value, image_cache);
+    // SAFETY: The caller guarantees that `name` is valid.
+    this.set_palette_color(unsafe { str(name) }, Color::rgba(r, g, b, a));
+}
+
+/// Removes the color with the name provided from the layout's palette.
+/// Settings that still refer to it become fully transparent. Returns whether
+/// the palette contained a color with that name.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn LayoutEditor_remove_palette_color(
+    this: &mut LayoutEditor,
+    name: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `name` is valid.
+    this.remove_palette_color(unsafe { str(name) })
+}
+
+/// Accesses a counter that increases whenever any of the settings of the
+/// layout change, including when a change gets undone or redone.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_settings_generation(this: &LayoutEditor) -> u64 {
+    this.settings_generation()
+}
+
+/// Takes the changes to the settings of the layout that happened since the
+/// changes were last taken and encodes them as JSON. Undoing or redoing a
+/// change considers everything changed.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_take_settings_changes_as_json(this: &mut LayoutEditor) -> Json {
+    output_vec(|o| {
+        json::to_vec(&this.take_settings_changes(), o).unwrap();
+    })
+}
+
+/// Undoes the last change made to the layout, including changes to the
+/// settings. The selection is restored to what it was before the change.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_undo(this: &mut LayoutEditor) {
+    this.undo();
+}
+
+/// Redoes the last change that got undone.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutEditor_redo(this: &mut LayoutEditor) {
+    this.redo();
 }
@@ -6,16 +6,19 @@
 //! - Using out of bounds indices.
 //! - Using the wrong getter function on the wrong type of component.
 
-use crate::{output_vec, Json};
+use crate::{Json, output_bytes, output_bytes_len, output_vec};
 use livesplit_core::{
     component::{
         blank_space::State as BlankSpaceComponentState,
         detailed_timer::State as DetailedTimerComponentState, graph::State as GraphComponentState,
-        key_value::State as KeyValueComponentState, separator::State as SeparatorComponentState,
-        splits::State as SplitsComponentState, text::State as TextComponentState,
+        key_value::State as KeyValueComponentState,
+        overlay_widgets::State as OverlayWidgetsComponentState,
+        separator::State as SeparatorComponentState, splits::State as SplitsComponentState,
+        tabs::State as TabsComponentState, text::State as TextComponentState,
         timer::State as TimerComponentState, title::State as TitleComponentState,
     },
     layout::{ComponentState, LayoutState},
+    util::json,
 };
 use std::os::raw::c_char;
 
@@ -40,7 +43,47 @@ pub extern "C" fn LayoutState_drop(this: OwnedLayoutState) {
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutState_as_json(this: &LayoutState) -> Json {
     output_vec(|o| {
-        this.write_json(o).unwrap();
+        json::to_vec(this, o).unwrap();
+    })
+}
+
+/// Encodes the layout state in a compact binary encoding that is a lot cheaper
+/// to produce and to consume than JSON. This is meant for passing the state to
+/// renderers running in a different process. The encoding is MessagePack with
+/// the same structure as the JSON, wrapped in an array whose first element is
+/// the version of the schema and whose second element is the state. The
+/// returned pointer stays valid until the next function returning a string or
+/// binary data is called. Use LayoutState_binary_len right afterwards to query
+/// the length of the encoded data.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_as_binary(this: &LayoutState) -> *const u8 {
+    output_bytes(|o| {
+        this.write_binary(o).unwrap();
+    })
+}
+
+/// Returns the length of the data most recently encoded with
+/// LayoutState_as_binary.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_binary_len() -> usize {
+    output_bytes_len()
+}
+
+/// Returns the version of the schema of the binary encoding of layout states.
+/// It is increased whenever the state changes in a way that existing decoders
+/// can't handle.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_binary_schema_version() -> u32 {
+    LayoutState::BINARY_SCHEMA_VERSION
+}
+
+/// Encodes the schemas of the state objects of all the kinds of components as a
+/// JSON array. Generic renderers can use this to adapt automatically to the
+/// fields the component states consist of.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_component_schemas_as_json() -> Json {
+    output_vec(|o| {
+        serde_json::to_writer(o, &ComponentState::schemas()).unwrap();
     })
 }
 
@@ -59,8 +102,10 @@ pub extern "C" fn LayoutState_component_type(this: &LayoutState, index: usize) -
         ComponentState::DetailedTimer(_) => "DetailedTimer\0",
         ComponentState::Graph(_) => "Graph\0",
         ComponentState::KeyValue(_) => "KeyValue\0",
+        ComponentState::OverlayWidgets(_) => "OverlayWidgets\0",
         ComponentState::Separator(_) => "Separator\0",
         ComponentState::Splits(_) => "Splits\0",
+        ComponentState::Tabs(_) => "Tabs\0",
         ComponentState::Text(_) => "Text\0",
         ComponentState::Timer(_) => "Timer\0",
         ComponentState::Title(_) => "Title\0",
@@ -117,6 +162,18 @@ pub extern "C" fn LayoutState_component_as_key_value(
     }
 }
 
+/// Gets the Overlay Widgets component state at the specified index.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_component_as_overlay_widgets(
+    this: &LayoutState,
+    index: usize,
+) -> &OverlayWidgetsComponentState {
+    match &this.components[index] {
+        ComponentState::OverlayWidgets(x) => x,
+        _ => panic!("wrong component state type"),
+    }
+}
+
 /// Gets the Separator component state at the specified index.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutState_component_as_separator(
@@ -141,6 +198,18 @@ pub extern "C" fn LayoutState_component_as_splits(
     }
 }
 
+/// Gets the Tabs component state at the specified index.
+#[unsafe(no_mangle)]
+pub extern "C" fn LayoutState_component_as_tabs(
+    this: &LayoutState,
+    index: usize,
+) -> &TabsComponentState {
+    match &this.components[index] {
+        ComponentState::Tabs(x) => x,
+        _ => panic!("wrong component state type"),
+    }
+}
+
 /// Gets the Text component state at the specified index.
 #[unsafe(no_mangle)]
 pub extern "C" fn LayoutState_component_as_text(
@@ -45,6 +45,8 @@ pub mod layout_editor;
 pub mod layout_editor_state;
 pub mod layout_state;
 pub mod linked_layout;
+pub mod overlay_widgets_component;
+pub mod overlay_widgets_component_state;
 pub mod parse_run_result;
 pub mod pb_chance_component;
 pub mod possible_time_save_component;
@@ -52,6 +54,7 @@ pub mod potential_clean_up;
 pub mod previous_segment_component;
 pub mod reset_chance_component;
 pub mod run;
+pub mod run_bundle;
 pub mod run_editor;
 pub mod run_metadata;
 pub mod run_metadata_custom_variable;
@@ -74,6 +77,9 @@ pub mod splits_component;
 pub mod splits_component_state;
 pub mod sum_of_best_cleaner;
 pub mod sum_of_best_component;
+pub mod svg_renderer;
+pub mod tabs_component;
+pub mod tabs_component_state;
 pub mod text_component;
 pub mod text_component_state;
 pub mod time;
@@ -155,6 +161,21 @@ where
     })
 }
 
+fn output_bytes<F>(f: F) -> *const u8
+where
+    F: FnOnce(&mut Vec<u8>),
+{
+    OUTPUT_VEC.with_borrow_mut(|output| {
+        output.clear();
+        f(output);
+        output.as_ptr()
+    })
+}
+
+fn output_bytes_len() -> usize {
+    OUTPUT_VEC.with_borrow(|output| output.len())
+}
+
 unsafe fn slice<T>(ptr: *const T, len: usize) -> &'static [T] {
     if len == 0 {
         &[]
@@ -0,0 +1,56 @@
+//! The Overlay Widgets Component shows the overlay widgets of the timer. These
+//! are usually provided by an auto splitter to display game specific
+//! information, such as the health of a boss or the seed of the random number
+//! generator.
+
+use super::{Json, output_vec};
+use crate::{
+    component::OwnedComponent, overlay_widgets_component_state::OwnedOverlayWidgetsComponentState,
+};
+use livesplit_core::{
+    Timer, component::overlay_widgets::Component as OverlayWidgetsComponent, util::json,
+};
+
+/// type
+pub type OwnedOverlayWidgetsComponent = Box<OverlayWidgetsComponent>;
+
+/// Creates a new Overlay Widgets Component.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponent_new() -> OwnedOverlayWidgetsComponent {
+    Box::new(OverlayWidgetsComponent::new())
+}
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponent_drop(this: OwnedOverlayWidgetsComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponent_into_generic(
+    this: OwnedOverlayWidgetsComponent,
+) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Encodes the component's state information as JSON.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponent_state_as_json(
+    this: &mut OverlayWidgetsComponent,
+    timer: &Timer,
+) -> Json {
+    output_vec(|o| {
+        json::to_vec(&this.state(timer), o).unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer provided.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponent_state(
+    this: &mut OverlayWidgetsComponent,
+    timer: &Timer,
+) -> OwnedOverlayWidgetsComponentState {
+    Box::new(this.state(timer))
+}
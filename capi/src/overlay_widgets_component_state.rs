@@ -0,0 +1,71 @@
+//! The state object describes the information to visualize for this component.
+
+use super::output_str;
+use livesplit_core::component::overlay_widgets::State as OverlayWidgetsComponentState;
+use std::os::raw::c_char;
+
+/// type
+pub type OwnedOverlayWidgetsComponentState = Box<OverlayWidgetsComponentState>;
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_drop(this: OwnedOverlayWidgetsComponentState) {
+    drop(this);
+}
+
+/// The number of widgets to visualize.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_len(this: &OverlayWidgetsComponentState) -> usize {
+    this.widgets.len()
+}
+
+/// The label of the widget with the index provided. You may not provide an out
+/// of bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_label(
+    this: &OverlayWidgetsComponentState,
+    index: usize,
+) -> *const c_char {
+    output_str(&this.widgets[index].label)
+}
+
+/// The value of the widget with the index provided. For progress bars this is
+/// the progress formatted as a percentage. You may not provide an out of bounds
+/// index.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_value(
+    this: &OverlayWidgetsComponentState,
+    index: usize,
+) -> *const c_char {
+    output_str(&this.widgets[index].value)
+}
+
+/// Specifies whether the widget with the index provided is a progress bar. You
+/// may not provide an out of bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_is_progress_bar(
+    this: &OverlayWidgetsComponentState,
+    index: usize,
+) -> bool {
+    this.widgets[index].progress.is_some()
+}
+
+/// The fraction between 0 and 1 that the progress bar of the widget with the
+/// index provided is filled by. This is 0 if the widget is not a progress bar.
+/// You may not provide an out of bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_progress(
+    this: &OverlayWidgetsComponentState,
+    index: usize,
+) -> f32 {
+    this.widgets[index].progress.unwrap_or_default()
+}
+
+/// Specifies whether to display the label of each widget and its value in two
+/// separate rows.
+#[unsafe(no_mangle)]
+pub extern "C" fn OverlayWidgetsComponentState_display_two_rows(
+    this: &OverlayWidgetsComponentState,
+) -> bool {
+    this.display_two_rows
+}
@@ -3,11 +3,11 @@
 //! of beating the Personal Best. During an attempt it actively changes based on
 //! how well the attempt is going.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::pb_chance::Component as PbChanceComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedPbChanceComponent = Box<PbChanceComponent>;
@@ -35,7 +35,7 @@ pub extern "C" fn PbChanceComponent_into_generic(this: OwnedPbChanceComponent) -
 #[unsafe(no_mangle)]
 pub extern "C" fn PbChanceComponent_state_as_json(this: &PbChanceComponent, timer: &Timer) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot()).write_json(o).unwrap();
+        json::to_vec(&this.state(&timer.snapshot()), o).unwrap();
     })
 }
 
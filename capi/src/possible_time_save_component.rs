@@ -3,11 +3,11 @@
 //! Segments. This component also allows showing the Total Possible Time Save
 //! for the remainder of the current attempt.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::possible_time_save::Component as PossibleTimeSaveComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedPossibleTimeSaveComponent = Box<PossibleTimeSaveComponent>;
@@ -40,7 +40,7 @@ pub extern "C" fn PossibleTimeSaveComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot()).write_json(o).unwrap();
+        json::to_vec(&this.state(&timer.snapshot()), o).unwrap();
     })
 }
 
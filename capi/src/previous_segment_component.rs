@@ -8,7 +8,7 @@ use super::{output_vec, Json};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::previous_segment::Component as PreviousSegmentComponent;
-use livesplit_core::{GeneralLayoutSettings, Timer};
+use livesplit_core::{GeneralLayoutSettings, Timer, util::json};
 
 /// type
 pub type OwnedPreviousSegmentComponent = Box<PreviousSegmentComponent>;
@@ -42,9 +42,7 @@ pub extern "C" fn PreviousSegmentComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.state(&timer.snapshot(), layout_settings), o).unwrap();
     })
 }
 
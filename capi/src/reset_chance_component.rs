@@ -5,8 +5,8 @@
 use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
-use livesplit_core::Timer;
 use livesplit_core::component::reset_chance::Component as ResetChanceComponent;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedResetChanceComponent = Box<ResetChanceComponent>;
@@ -39,7 +39,7 @@ pub extern "C" fn ResetChanceComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot()).write_json(o).unwrap();
+        json::to_vec(&this.state(&timer.snapshot()), o).unwrap();
     })
 }
 
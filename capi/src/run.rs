@@ -271,6 +271,30 @@ pub extern "C" fn Run_save_as_lss(this: &Run) -> *const c_char {
     })
 }
 
+/// Saves a Run as a WSplit splits file. Only the Real Time is saved.
+#[unsafe(no_mangle)]
+pub extern "C" fn Run_save_as_wsplit(this: &Run) -> *const c_char {
+    output_vec(|o| {
+        saver::wsplit::save_run(this, IoWrite(o)).unwrap();
+    })
+}
+
+/// Saves a Run as an Urn splits file. Only the Real Time is saved.
+#[unsafe(no_mangle)]
+pub extern "C" fn Run_save_as_urn(this: &Run) -> *const c_char {
+    output_vec(|o| {
+        saver::urn::save_run(this, IoWrite(o)).unwrap();
+    })
+}
+
+/// Saves a Run in the splits i/o exchange format.
+#[unsafe(no_mangle)]
+pub extern "C" fn Run_save_as_splits_io(this: &Run) -> *const c_char {
+    output_vec(|o| {
+        saver::splits_io::save_run(this, IoWrite(o)).unwrap();
+    })
+}
+
 /// Returns the amount of custom comparisons stored in this Run.
 #[unsafe(no_mangle)]
 pub extern "C" fn Run_custom_comparisons_len(this: &Run) -> usize {
@@ -0,0 +1,117 @@
+//! A Run Bundle holds multiple related Runs in a single file, such as the
+//! different categories of the same game. The game name and game icon are
+//! shared between all the runs. One of the runs is the active run, which is the
+//! one in use by the Timer.
+
+use super::{output_vec, str};
+use crate::{
+    run::{NullableOwnedRun, OwnedRun},
+    slice,
+};
+use livesplit_core::{
+    Run, SharedTimer, Timer,
+    run::{
+        RunBundle,
+        parser::livesplit,
+        saver::{self, livesplit::IoWrite},
+    },
+};
+use std::os::raw::c_char;
+
+/// type
+pub type OwnedRunBundle = Box<RunBundle>;
+/// type
+pub type NullableOwnedRunBundle = Option<OwnedRunBundle>;
+
+/// Creates a new empty Run Bundle.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_new() -> OwnedRunBundle {
+    Box::new(RunBundle::new())
+}
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_drop(this: OwnedRunBundle) {
+    drop(this);
+}
+
+/// Attempts to parse a LiveSplit run bundle file from an array. Returns <NULL>
+/// if the bundle couldn't be parsed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunBundle_parse(data: *const u8, length: usize) -> NullableOwnedRunBundle {
+    // SAFETY: The caller guarantees that `data` is valid for `length`.
+    let source = simdutf8::basic::from_utf8(unsafe { slice(data, length) }).ok()?;
+    livesplit::parse_bundle(source).ok().map(Box::new)
+}
+
+/// Saves the Run Bundle as a LiveSplit run bundle file. Store the Run in use
+/// by the Timer in the bundle first, in order to save the current state of the
+/// active run.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_save(this: &RunBundle) -> *const c_char {
+    output_vec(|o| {
+        saver::livesplit::save_bundle(this, IoWrite(o)).unwrap();
+    })
+}
+
+/// Adds a Run to the bundle. The Run receives the game name and game icon of
+/// the bundle.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_push(this: &mut RunBundle, run: OwnedRun) {
+    this.push(*run);
+}
+
+/// Removes the Run at the index provided from the bundle and returns it. The
+/// active Run can't be removed, so <NULL> is returned for its index or if there
+/// is no Run at the index.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_remove(this: &mut RunBundle, index: usize) -> NullableOwnedRun {
+    this.remove(index).map(Box::new)
+}
+
+/// Returns the amount of Runs in the bundle.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_len(this: &RunBundle) -> usize {
+    this.len()
+}
+
+/// Accesses the Run at the index provided. You may not provide an out of
+/// bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_run(this: &RunBundle, index: usize) -> &Run {
+    &this.runs()[index]
+}
+
+/// Returns the index of the active Run.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_active_index(this: &RunBundle) -> usize {
+    this.active_index()
+}
+
+/// Sets the game name of all the Runs in the bundle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunBundle_set_game_name(this: &mut RunBundle, game: *const c_char) {
+    // SAFETY: The caller guarantees that `game` is valid.
+    this.set_game_name(unsafe { str(game) });
+}
+
+/// Stores the Run in use by the Timer provided as the active Run of the
+/// bundle. If there is an attempt in progress, it is stored in the Run's
+/// history, without ending the attempt in the Timer.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_store_active_run(this: &mut RunBundle, timer: &Timer) {
+    this.store_active_run(timer);
+}
+
+/// Switches the active Run of the Shared Timer provided to the Run at the
+/// index provided. The Shared Timer is expected to use the current active Run,
+/// which is stored back into the bundle. Returns <FALSE> if the index is out of
+/// bounds or if there is an attempt in progress.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunBundle_switch(
+    this: &mut RunBundle,
+    index: usize,
+    timer: &SharedTimer,
+) -> bool {
+    this.switch_shared(index, timer).is_ok()
+}
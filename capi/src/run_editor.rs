@@ -3,16 +3,17 @@
 //! are being applied to the Run. It provides the current state of the editor as
 //! state objects that can be visualized by any kind of User Interface.
 
-use super::{Json, output_vec, str};
+use super::{Json, output_time_span, output_vec, str};
 use crate::{
     linked_layout::OwnedLinkedLayout, run::OwnedRun, slice,
-    sum_of_best_cleaner::OwnedSumOfBestCleaner,
+    sum_of_best_cleaner::OwnedSumOfBestCleaner, time_span::NullableTimeSpan,
 };
 use livesplit_core::{
-    Run, RunEditor, TimingMethod,
+    Run, RunEditor, TimeSpan, TimingMethod,
     settings::{Image, ImageCache},
+    util::json,
 };
-use std::os::raw::c_char;
+use std::{os::raw::c_char, ptr};
 
 /// type
 pub type OwnedRunEditor = Box<RunEditor>;
@@ -41,7 +42,7 @@ pub extern "C" fn RunEditor_close(this: OwnedRunEditor) -> OwnedRun {
 #[unsafe(no_mangle)]
 pub extern "C" fn RunEditor_state_as_json(this: &RunEditor, image_cache: &mut ImageCache) -> Json {
     output_vec(|o| {
-        this.state(image_cache).write_json(o).unwrap();
+        json::to_vec(&this.state(image_cache), o).unwrap();
     })
 }
 
@@ -280,6 +281,38 @@ pub extern "C" fn RunEditor_remove_segments(this: &mut RunEditor) {
     this.remove_segments();
 }
 
+/// Imports the segments from the start index up to, but not including, the end
+/// index of another Run below the active segment. The names, icons, best
+/// segment times and segment histories of the segments are copied. Returns
+/// <FALSE> if the range is empty or out of bounds.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_import_segments(
+    this: &mut RunEditor,
+    run: &Run,
+    start: usize,
+    end: usize,
+) -> bool {
+    this.import_segments(run, start..end).is_ok()
+}
+
+/// Moves the segment at the index provided to a new position, such as when
+/// dragging it to a different place in the list. The segment histories, best
+/// segments and comparisons are adjusted accordingly. The moved segment then
+/// becomes the only selected segment. You may not provide out of bounds
+/// indices.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_move_segment(this: &mut RunEditor, from: usize, to: usize) {
+    this.move_segment(from, to);
+}
+
+/// Merges the active segment with the segment below it. The segment histories
+/// of both segments are combined and the best segments are adjusted
+/// accordingly. The merged segment keeps the name of the segment below.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_merge_segment_with_next(this: &mut RunEditor) {
+    this.merge_segment_with_next();
+}
+
 /// Moves all the selected segments up, unless the first segment is
 /// selected. The run's information is automatically adjusted properly. The
 /// active segment stays the active segment.
@@ -336,6 +369,37 @@ pub unsafe extern "C" fn RunEditor_active_parse_and_set_split_time(
         .is_ok()
 }
 
+/// Accesses the segment time the attempt with the index provided has in the
+/// history of the active segment for the chosen timing method. This returns
+/// <NULL> if the attempt either skipped the segment or didn't reach it.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_active_segment_history_time(
+    this: &mut RunEditor,
+    attempt_index: i32,
+) -> *const NullableTimeSpan {
+    if let Some(time) = this.active_segment().segment_history_time(attempt_index) {
+        output_time_span(time)
+    } else {
+        ptr::null()
+    }
+}
+
+/// Parses a segment time from a string and sets it as the segment time the
+/// attempt with the index provided has in the history of the active segment
+/// for the chosen timing method. Returns <FALSE> if the time couldn't be parsed
+/// or the attempt didn't reach the segment.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_active_parse_and_set_segment_history_time(
+    this: &mut RunEditor,
+    attempt_index: i32,
+    time: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `time` is valid.
+    this.active_segment()
+        .parse_and_set_segment_history_time(attempt_index, unsafe { str(time) })
+        .is_ok()
+}
+
 /// Parses a segment time from a string and sets it for the active segment with
 /// the chosen timing method.
 #[unsafe(no_mangle)]
@@ -387,6 +451,31 @@ pub unsafe extern "C" fn RunEditor_add_comparison(
     this.add_comparison(unsafe { str(comparison) }).is_ok()
 }
 
+/// Adds a new custom comparison with manually authored split times for the
+/// chosen timing method. The split times are provided as a single string with
+/// one split time per line, where a line with just `-` means that the segment
+/// has no split time. Surrounding whitespace and empty lines are ignored.
+/// There needs to be a line for each segment and the split times need to be
+/// monotonic. The comparison can't be added if it starts with
+/// `[Race]` or it already exists.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_parse_and_add_manual_comparison(
+    this: &mut RunEditor,
+    comparison: *const c_char,
+    split_times: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `comparison` and `split_times` are
+    // valid.
+    this.parse_and_add_manual_comparison(unsafe { str(comparison) }, unsafe {
+        str(split_times)
+            .split('\n')
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| if line == "-" { "" } else { line })
+    })
+    .is_ok()
+}
+
 /// Imports the Personal Best from the provided run as a comparison. The
 /// comparison can't be added if its name starts with `[Race]` or it already
 /// exists.
@@ -479,6 +568,15 @@ pub extern "C" fn RunEditor_clear_history(this: &mut RunEditor) {
     this.clear_history();
 }
 
+/// Rebuilds the Personal Best split times and the Best Segment times purely
+/// from the Attempt History and the Segment Histories, discarding the values
+/// that are currently stored. This is useful for repairing splits files where
+/// these values got corrupted by other tools.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_rebuild_from_history(this: &mut RunEditor) {
+    this.rebuild_from_history();
+}
+
 /// Clears out the Attempt History, the Segment Histories, all the times,
 /// sets the Attempt Count to 0 and clears the speedrun.com run id
 /// association. All Custom Comparisons other than `Personal Best` are
@@ -488,6 +586,52 @@ pub extern "C" fn RunEditor_clear_times(this: &mut RunEditor) {
     this.clear_times();
 }
 
+/// Shifts all the split times of the selected timing method by the offset
+/// provided. Returns <FALSE> and changes nothing if any of the times would
+/// become negative.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_shift_times(this: &mut RunEditor, offset: &TimeSpan) -> bool {
+    this.shift_times(*offset).is_ok()
+}
+
+/// Scales all the times of the selected timing method by the factor provided.
+/// This is useful for correcting times that were recorded with an emulator
+/// running at the wrong speed. Returns <FALSE> and changes nothing if the
+/// factor isn't finite and positive or if any of the times would become too
+/// large to be represented.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_scale_times(this: &mut RunEditor, factor: f64) -> bool {
+    this.scale_times(factor).is_ok()
+}
+
+/// Removes all but the most recent attempts, such that at most the amount of
+/// attempts provided remain, along with their segment times. Best segments that
+/// were achieved in the removed attempts are kept. The amount of attempts that
+/// got removed is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_keep_last_attempts(this: &mut RunEditor, count: usize) -> usize {
+    this.keep_last_attempts(count)
+}
+
+/// Removes all the attempts that didn't finish the run, along with their
+/// segment times. Best segments that were achieved in the removed attempts are
+/// kept. The amount of attempts that got removed is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_remove_unfinished_attempts(this: &mut RunEditor) -> usize {
+    this.remove_unfinished_attempts()
+}
+
+/// Checks the Run for inconsistencies, such as negative segment times, segment
+/// history elements that don't belong to any attempt, decreasing split times
+/// and best segments that are slower than other known segment times, and
+/// repairs all of them. The amount of issues that got repaired is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn RunEditor_repair_sanity_issues(this: &mut RunEditor) -> usize {
+    let report = this.sanity_check();
+    this.repair_sanity_issues(&report);
+    report.issues.len()
+}
+
 /// Creates a Sum of Best Cleaner which allows you to interactively remove
 /// potential issues in the segment history that lead to an inaccurate Sum
 /// of Best. If you skip a split, whenever you will do the next split, the
@@ -500,3 +644,39 @@ pub extern "C" fn RunEditor_clean_sum_of_best(
 ) -> OwnedSumOfBestCleaner {
     Box::new(this.clean_sum_of_best())
 }
+
+/// Attaches the tag specified to the attempt with the index provided. Returns
+/// <FALSE> if there is no such attempt or the tag was already attached.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_add_attempt_tag(
+    this: &mut RunEditor,
+    attempt_index: i32,
+    tag: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `tag` is valid.
+    this.add_attempt_tag(attempt_index, unsafe { str(tag) })
+}
+
+/// Removes the tag specified from the attempt with the index provided. Returns
+/// <FALSE> if there is no such attempt or the tag wasn't attached.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_remove_attempt_tag(
+    this: &mut RunEditor,
+    attempt_index: i32,
+    tag: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `tag` is valid.
+    this.remove_attempt_tag(attempt_index, unsafe { str(tag) })
+}
+
+/// Sets whether attempts that have the tag specified attached are excluded
+/// from the comparisons. The comparisons are regenerated immediately.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn RunEditor_set_attempt_tag_excluded(
+    this: &mut RunEditor,
+    tag: *const c_char,
+    excluded: bool,
+) {
+    // SAFETY: The caller guarantees that `tag` is valid.
+    this.set_attempt_tag_excluded(unsafe { str(tag) }, excluded);
+}
@@ -2,11 +2,11 @@
 //! segment in a comparison of your choosing. If no comparison is specified it
 //! uses the timer's current comparison.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::segment_time::Component as SegmentTimeComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedSegmentTimeComponent = Box<SegmentTimeComponent>;
@@ -39,7 +39,7 @@ pub extern "C" fn SegmentTimeComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(timer).write_json(o).unwrap();
+        json::to_vec(&this.state(timer), o).unwrap();
     })
 }
 
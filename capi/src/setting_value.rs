@@ -1,17 +1,18 @@
 //! Describes a setting's value. Such a value can be of a variety of different
 //! types.
 
-use crate::{Json, output_vec, str};
+use crate::{Json, output_vec, slice, str};
 use livesplit_core::{
     TimingMethod,
     component::{
         splits::{ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith},
         timer::DeltaGradient,
     },
-    layout::LayoutDirection,
+    layout::{LayoutDirection, ThemeColor, ThemedColor},
     settings::{
-        Alignment, BackgroundImage, Color, ColumnKind, Font, FontStretch, FontStyle, FontWeight,
-        Gradient, ImageId, LayoutBackground, ListGradient, Value as SettingValue,
+        Alignment, BackgroundImage, BlendMode, Color, ColumnKind, ComponentBackgroundImage, Font,
+        FontData, FontStretch, FontStyle, FontWeight, Gradient, ImageFit, ImageId,
+        LayoutBackground, ListGradient, Value as SettingValue, ValueList,
     },
     timing::formatter::{Accuracy, DigitsFormat},
 };
@@ -170,6 +171,65 @@ pub extern "C" fn SettingValue_from_optional_empty_color() -> OwnedSettingValue
     Box::new(None::<Color>.into())
 }
 
+/// Creates a new setting value from the name of a color of the layout's theme.
+/// The value refers to the theme color, so it follows the layout whenever a
+/// different theme is applied. If it doesn't match a known theme color, <NULL>
+/// is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SettingValue_from_theme_color(
+    value: *const c_char,
+) -> NullableOwnedSettingValue {
+    // SAFETY: The caller guarantees that `value` is valid.
+    let value = unsafe { str(value) };
+    let value = match value {
+        "Background" => ThemeColor::Background,
+        "TextPrimary" => ThemeColor::TextPrimary,
+        "TextShadow" => ThemeColor::TextShadow,
+        "Separators" => ThemeColor::Separators,
+        "ThinSeparators" => ThemeColor::ThinSeparators,
+        "BestSegment" => ThemeColor::BestSegment,
+        "AheadGaining" => ThemeColor::AheadGaining,
+        "AheadLosing" => ThemeColor::AheadLosing,
+        "BehindGaining" => ThemeColor::BehindGaining,
+        "BehindLosing" => ThemeColor::BehindLosing,
+        "NotRunning" => ThemeColor::NotRunning,
+        "PersonalBest" => ThemeColor::PersonalBest,
+        "Paused" => ThemeColor::Paused,
+        _ => return None,
+    };
+    Some(Box::new(ThemedColor::Theme(value).into()))
+}
+
+/// Creates a new setting value that refers to the color of the layout's
+/// palette with the name provided.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SettingValue_from_palette_color(name: *const c_char) -> OwnedSettingValue {
+    // SAFETY: The caller guarantees that `name` is valid.
+    let name = unsafe { str(name) }.to_owned();
+    Box::new(ThemedColor::Palette { name }.into())
+}
+
+/// Creates a new setting value from the name of a blend mode. If it doesn't
+/// match a known blend mode, <NULL> is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SettingValue_from_blend_mode(
+    value: *const c_char,
+) -> NullableOwnedSettingValue {
+    // SAFETY: The caller guarantees that `value` is valid.
+    let value = unsafe { str(value) };
+    let value = match value {
+        "Normal" => BlendMode::Normal,
+        "Multiply" => BlendMode::Multiply,
+        "Screen" => BlendMode::Screen,
+        "Overlay" => BlendMode::Overlay,
+        "Darken" => BlendMode::Darken,
+        "Lighten" => BlendMode::Lighten,
+        "Add" => BlendMode::Add,
+        _ => return None,
+    };
+    Some(Box::new(value.into()))
+}
+
 /// Creates a new setting value that is a transparent gradient.
 #[unsafe(no_mangle)]
 pub extern "C" fn SettingValue_from_transparent_gradient() -> OwnedSettingValue {
@@ -206,6 +266,136 @@ pub extern "C" fn SettingValue_from_horizontal_gradient(
     Box::new(Gradient::Horizontal(Color::rgba(r1, g1, b1, a1), Color::rgba(r2, g2, b2, a2)).into())
 }
 
+/// Creates a new setting value from the angled gradient provided as two RGBA
+/// colors and the angle in degrees. An angle of 0° is the same as a vertical
+/// gradient and an angle of 90° is the same as a horizontal gradient.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_angled_gradient(
+    r1: f32,
+    g1: f32,
+    b1: f32,
+    a1: f32,
+    r2: f32,
+    g2: f32,
+    b2: f32,
+    a2: f32,
+    angle: f32,
+) -> OwnedSettingValue {
+    Box::new(
+        Gradient::Angled(
+            Color::rgba(r1, g1, b1, a1),
+            Color::rgba(r2, g2, b2, a2),
+            angle,
+        )
+        .into(),
+    )
+}
+
+/// Creates a new setting value from the radial gradient provided as the RGBA
+/// colors of the center and the edge, the position of the center and the
+/// radius. The position and the radius are relative to the size of the region.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_radial_gradient(
+    r1: f32,
+    g1: f32,
+    b1: f32,
+    a1: f32,
+    r2: f32,
+    g2: f32,
+    b2: f32,
+    a2: f32,
+    x: f32,
+    y: f32,
+    radius: f32,
+) -> OwnedSettingValue {
+    Box::new(
+        Gradient::Radial(
+            Color::rgba(r1, g1, b1, a1),
+            Color::rgba(r2, g2, b2, a2),
+            [x, y],
+            radius,
+        )
+        .into(),
+    )
+}
+
+/// Creates a new setting value from the conic gradient provided as two RGBA
+/// colors, the position of the center relative to the size of the region and
+/// the angle in degrees the gradient starts at. An angle of 0° starts at the
+/// top.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_conic_gradient(
+    r1: f32,
+    g1: f32,
+    b1: f32,
+    a1: f32,
+    r2: f32,
+    g2: f32,
+    b2: f32,
+    a2: f32,
+    x: f32,
+    y: f32,
+    angle: f32,
+) -> OwnedSettingValue {
+    Box::new(
+        Gradient::Conic(
+            Color::rgba(r1, g1, b1, a1),
+            Color::rgba(r2, g2, b2, a2),
+            [x, y],
+            angle,
+        )
+        .into(),
+    )
+}
+
+/// Creates a new setting value from the hue cycling gradient provided as two
+/// RGBA colors and the number of seconds a full cycle takes.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_hue_cycling_gradient(
+    r1: f32,
+    g1: f32,
+    b1: f32,
+    a1: f32,
+    r2: f32,
+    g2: f32,
+    b2: f32,
+    a2: f32,
+    period: f32,
+) -> OwnedSettingValue {
+    Box::new(
+        Gradient::HueCycling(
+            Color::rgba(r1, g1, b1, a1),
+            Color::rgba(r2, g2, b2, a2),
+            period,
+        )
+        .into(),
+    )
+}
+
+/// Creates a new setting value from the rotating gradient provided as two RGBA
+/// colors and the number of seconds a full rotation takes.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_rotating_gradient(
+    r1: f32,
+    g1: f32,
+    b1: f32,
+    a1: f32,
+    r2: f32,
+    g2: f32,
+    b2: f32,
+    a2: f32,
+    period: f32,
+) -> OwnedSettingValue {
+    Box::new(
+        Gradient::Rotating(
+            Color::rgba(r1, g1, b1, a1),
+            Color::rgba(r2, g2, b2, a2),
+            period,
+        )
+        .into(),
+    )
+}
+
 /// Creates a new setting value from the alternating gradient provided as two RGBA colors.
 #[unsafe(no_mangle)]
 pub extern "C" fn SettingValue_from_alternating_gradient(
@@ -372,11 +562,30 @@ pub unsafe extern "C" fn SettingValue_from_font(
                 "ultra-expanded" => FontStretch::UltraExpanded,
                 _ => return None,
             },
+            data: None,
         })
         .into(),
     ))
 }
 
+/// Embeds the contents of a font file into the font setting value, so the font
+/// doesn't need to be installed for the layout to look the same. Returns
+/// <FALSE> if the setting value is not a font or an empty font.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SettingValue_font_embed(
+    this: &mut SettingValue,
+    data: *const u8,
+    len: usize,
+) -> bool {
+    if let SettingValue::Font(Some(font)) = this {
+        // SAFETY: The caller guarantees that `data` is valid.
+        font.data = Some(FontData::new(unsafe { slice(data, len).into() }));
+        true
+    } else {
+        false
+    }
+}
+
 /// Creates a new empty setting value with the type `font`.
 #[unsafe(no_mangle)]
 pub extern "C" fn SettingValue_from_empty_font() -> OwnedSettingValue {
@@ -421,3 +630,56 @@ pub unsafe extern "C" fn SettingValue_from_background_image(
         .into(),
     ))
 }
+
+/// Creates a new setting value from the component background image with the
+/// image ID and the name of the way it is fit into the component provided. The
+/// fit can be `Fit`, `Stretch` or `Tile`. If the image ID or the fit is
+/// invalid, <NULL> is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SettingValue_from_component_background_image(
+    image_id: *const c_char,
+    fit: *const c_char,
+) -> NullableOwnedSettingValue {
+    // SAFETY: The caller guarantees that `fit` is valid.
+    let fit = match unsafe { str(fit) } {
+        "Fit" => ImageFit::Fit,
+        "Stretch" => ImageFit::Stretch,
+        "Tile" => ImageFit::Tile,
+        _ => return None,
+    };
+    Some(Box::new(
+        Some(ComponentBackgroundImage {
+            // SAFETY: The caller guarantees that `image_id` is valid.
+            image: ImageId::from_str(unsafe { str(image_id) }).ok()?,
+            fit,
+        })
+        .into(),
+    ))
+}
+
+/// Creates a new setting value that removes the background image of a
+/// component.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_empty_component_background_image() -> OwnedSettingValue {
+    Box::new(None::<ComponentBackgroundImage<ImageId>>.into())
+}
+
+/// Creates a new setting value that is an empty list. The setting value
+/// provided is the value a new item that gets added to the list starts out
+/// with. Use `SettingValue_list_push` to add items to the list.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_from_empty_list(new_item: OwnedSettingValue) -> OwnedSettingValue {
+    Box::new(ValueList::new(Vec::<SettingValue>::new(), *new_item).into())
+}
+
+/// Adds the setting value provided to the end of the list. Returns <FALSE> and
+/// drops the item if the setting value is not a list.
+#[unsafe(no_mangle)]
+pub extern "C" fn SettingValue_list_push(this: &mut SettingValue, item: OwnedSettingValue) -> bool {
+    if let SettingValue::List(list) = this {
+        list.items.push(*item);
+        true
+    } else {
+        false
+    }
+}
@@ -28,6 +28,18 @@ impl SoftwareRenderer {
         _: bool,
     ) {
     }
+
+    #[expect(warnings)]
+    fn render_bgra(
+        &mut self,
+        _: &LayoutState,
+        _: &ImageCache,
+        _: &mut [u8],
+        _: [u32; 2],
+        _: u32,
+        _: bool,
+    ) {
+    }
 }
 
 /// type
@@ -76,3 +88,31 @@ pub unsafe extern "C" fn SoftwareRenderer_render(
         force_redraw,
     );
 }
+
+/// Renders the layout state provided into the image buffer provided, just like
+/// SoftwareRenderer_render does. However the image has to be an array of BGRA8
+/// encoded pixels (blue, green, red, alpha with each channel being an u8)
+/// instead. The colors are premultiplied by their alpha. This is the format
+/// that most video capture and streaming software expects for overlays.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SoftwareRenderer_render_bgra(
+    this: &mut SoftwareRenderer,
+    layout_state: &LayoutState,
+    image_cache: &ImageCache,
+    data: *mut u8,
+    width: u32,
+    height: u32,
+    stride: u32,
+    force_redraw: bool,
+) {
+    this.render_bgra(
+        layout_state,
+        image_cache,
+        // SAFETY: The caller guarantees that `data` is valid for `stride *
+        // height * 4` bytes.
+        unsafe { slice_mut(data, stride as usize * height as usize * 4) },
+        [width, height],
+        stride,
+        force_redraw,
+    );
+}
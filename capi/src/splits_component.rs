@@ -4,11 +4,11 @@
 //! time. The list provides scrolling functionality, so not every segment needs
 //! to be shown all the time.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::{component::OwnedComponent, splits_component_state::OwnedSplitsComponentState};
 use livesplit_core::{
-    component::splits::Component as SplitsComponent, settings::ImageCache, GeneralLayoutSettings,
-    Timer,
+    GeneralLayoutSettings, Timer, component::splits::Component as SplitsComponent,
+    settings::ImageCache, util::json,
 };
 
 /// type
@@ -42,9 +42,11 @@ pub extern "C" fn SplitsComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(image_cache, &timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(
+            &this.state(image_cache, &timer.snapshot(), layout_settings),
+            o,
+        )
+        .unwrap();
     })
 }
 
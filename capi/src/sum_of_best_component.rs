@@ -6,11 +6,11 @@
 //! sum of their best segment times. The name is therefore a bit misleading, but
 //! sticks around for historical reasons.
 
-use super::{output_vec, Json};
+use super::{Json, output_vec};
 use crate::component::OwnedComponent;
 use crate::key_value_component_state::OwnedKeyValueComponentState;
 use livesplit_core::component::sum_of_best::Component as SumOfBestComponent;
-use livesplit_core::Timer;
+use livesplit_core::{Timer, util::json};
 
 /// type
 pub type OwnedSumOfBestComponent = Box<SumOfBestComponent>;
@@ -41,7 +41,7 @@ pub extern "C" fn SumOfBestComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(timer).write_json(o).unwrap();
+        json::to_vec(&this.state(timer), o).unwrap();
     })
 }
 
@@ -0,0 +1,65 @@
+//! The SVG renderer allows rendering layouts to vector images in the SVG
+//! format. The text is emitted as paths, so the images look the same
+//! everywhere, regardless of which fonts are installed. This is useful for
+//! previews, thumbnails and printing layouts at any size.
+
+use std::os::raw::c_char;
+
+use livesplit_core::{layout::LayoutState, settings::ImageCache};
+
+#[cfg(feature = "svg-rendering")]
+use livesplit_core::rendering::svg::Renderer as SvgRenderer;
+
+use crate::output_str;
+
+#[cfg(not(feature = "svg-rendering"))]
+/// dummy
+pub struct SvgRenderer;
+#[cfg(not(feature = "svg-rendering"))]
+impl SvgRenderer {
+    fn new() -> Self {
+        panic!("The SVG renderer is not compiled in.")
+    }
+
+    #[expect(warnings)]
+    fn render(
+        &mut self,
+        _: &mut String,
+        _: &LayoutState,
+        _: &ImageCache,
+        _: [f32; 2],
+    ) -> Result<Option<[f32; 2]>, std::fmt::Error> {
+        Ok(None)
+    }
+}
+
+/// type
+pub type OwnedSvgRenderer = Box<SvgRenderer>;
+
+/// Creates a new SVG renderer.
+#[unsafe(no_mangle)]
+pub extern "C" fn SvgRenderer_new() -> OwnedSvgRenderer {
+    Box::new(SvgRenderer::new())
+}
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn SvgRenderer_drop(this: OwnedSvgRenderer) {
+    drop(this);
+}
+
+/// Renders the layout state provided as an SVG document with the dimensions
+/// provided and returns it as a string.
+#[unsafe(no_mangle)]
+pub extern "C" fn SvgRenderer_render(
+    this: &mut SvgRenderer,
+    layout_state: &LayoutState,
+    image_cache: &ImageCache,
+    width: f32,
+    height: f32,
+) -> *const c_char {
+    let mut svg = String::new();
+    // Writing to a string can't fail.
+    let _ = this.render(&mut svg, layout_state, image_cache, [width, height]);
+    output_str(svg)
+}
@@ -0,0 +1,103 @@
+//! The Tabs Component is a container that holds multiple child layouts, called
+//! tabs, of which only one is shown at a time.
+
+use super::{Json, output_vec, str};
+use crate::{component::OwnedComponent, tabs_component_state::OwnedTabsComponentState};
+use livesplit_core::{
+    GeneralLayoutSettings, Timer,
+    component::tabs::{Component as TabsComponent, Tab},
+    settings::ImageCache,
+    util::json,
+};
+use std::os::raw::c_char;
+
+/// type
+pub type OwnedTabsComponent = Box<TabsComponent>;
+
+/// Creates a new Tabs Component without any tabs.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_new() -> OwnedTabsComponent {
+    Box::new(TabsComponent::new())
+}
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_drop(this: OwnedTabsComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_into_generic(this: OwnedTabsComponent) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Adds a new tab with the name provided and no components to the end of the
+/// list of tabs.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn TabsComponent_push_tab(this: &mut TabsComponent, name: *const c_char) {
+    // SAFETY: The caller guarantees that `name` is valid.
+    this.push_tab(Tab::new(unsafe { str(name) }.into()));
+}
+
+/// Adds a component to the end of the tab with the index provided. You may not
+/// provide an out of bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_push_component(
+    this: &mut TabsComponent,
+    tab_index: usize,
+    component: OwnedComponent,
+) {
+    this.tabs_mut()[tab_index].components.push(*component);
+}
+
+/// Shows the tab with the index provided. If there is no such tab, the last
+/// tab is shown instead.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_select_tab(this: &mut TabsComponent, index: usize) {
+    this.select_tab(index);
+}
+
+/// Shows the next tab. If the last tab is shown, the first tab is shown
+/// afterwards.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_next_tab(this: &mut TabsComponent) {
+    this.next_tab();
+}
+
+/// Shows the previous tab. If the first tab is shown, the last tab is shown
+/// afterwards.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_previous_tab(this: &mut TabsComponent) {
+    this.previous_tab();
+}
+
+/// Encodes the component's state information as JSON.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_state_as_json(
+    this: &mut TabsComponent,
+    image_cache: &mut ImageCache,
+    timer: &Timer,
+    layout_settings: &GeneralLayoutSettings,
+) -> Json {
+    output_vec(|o| {
+        json::to_vec(
+            &this.state(image_cache, &timer.snapshot(), layout_settings),
+            o,
+        )
+        .unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer and layout settings
+/// provided. Only the components of the selected tab are visited.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponent_state(
+    this: &mut TabsComponent,
+    image_cache: &mut ImageCache,
+    timer: &Timer,
+    layout_settings: &GeneralLayoutSettings,
+) -> OwnedTabsComponentState {
+    Box::new(this.state(image_cache, &timer.snapshot(), layout_settings))
+}
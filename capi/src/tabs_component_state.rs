@@ -0,0 +1,50 @@
+//! The state object describes the information to visualize for this component.
+
+use super::output_str;
+use livesplit_core::component::tabs::State as TabsComponentState;
+use std::os::raw::c_char;
+
+/// type
+pub type OwnedTabsComponentState = Box<TabsComponentState>;
+
+/// drop
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_drop(this: OwnedTabsComponentState) {
+    drop(this);
+}
+
+/// The number of tabs of the component.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_tab_count(this: &TabsComponentState) -> usize {
+    this.tab_names.len()
+}
+
+/// The name of the tab with the index provided. You may not provide an out of
+/// bounds index.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_tab_name(
+    this: &TabsComponentState,
+    index: usize,
+) -> *const c_char {
+    output_str(&this.tab_names[index])
+}
+
+/// The index of the tab that is shown.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_selected_tab(this: &TabsComponentState) -> u32 {
+    this.selected_tab
+}
+
+/// Specifies whether the name of the selected tab is shown above its
+/// components.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_show_tab_names(this: &TabsComponentState) -> bool {
+    this.show_tab_names
+}
+
+/// The number of components of the selected tab. Their states can be
+/// accessed through the component's JSON representation.
+#[unsafe(no_mangle)]
+pub extern "C" fn TabsComponentState_len(this: &TabsComponentState) -> usize {
+    this.components.len()
+}
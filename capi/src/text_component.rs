@@ -7,6 +7,7 @@ use crate::{component::OwnedComponent, text_component_state::OwnedTextComponentS
 use livesplit_core::{
     Timer,
     component::text::{Component as TextComponent, Text},
+    util::json,
 };
 use std::os::raw::c_char;
 
@@ -36,7 +37,7 @@ pub extern "C" fn TextComponent_into_generic(this: OwnedTextComponent) -> OwnedC
 #[unsafe(no_mangle)]
 pub extern "C" fn TextComponent_state_as_json(this: &TextComponent, timer: &Timer) -> Json {
     output_vec(|o| {
-        this.state(timer).write_json(o).unwrap();
+        json::to_vec(&this.state(timer), o).unwrap();
     })
 }
 
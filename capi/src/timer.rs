@@ -1,6 +1,6 @@
 //! A Timer provides all the capabilities necessary for doing speedrun attempts.
 
-use super::{output_str, output_time, output_time_span, output_vec, str};
+use super::{Json, output_str, output_time, output_time_span, output_vec, str};
 use crate::{
     run::{NullableOwnedRun, OwnedRun},
     shared_timer::OwnedSharedTimer,
@@ -9,8 +9,9 @@ use livesplit_core::{
     Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
     event::{Error, Event},
     run::saver::{self, livesplit::IoWrite},
+    timing::journal::Journal,
 };
-use std::os::raw::c_char;
+use std::{io::Cursor, os::raw::c_char};
 
 /// type
 pub type OwnedTimer = Box<Timer>;
@@ -122,6 +123,25 @@ pub extern "C" fn Timer_start(this: &mut Timer) -> i32 {
     convert(this.start())
 }
 
+/// Starts a practice attempt at the segment specified. The split times of all
+/// the segments before it are taken from the comparison provided. If the
+/// segment history is supposed to be fed, the segments completed during the
+/// attempt are stored in the segment history when the attempt gets reset.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_start_at_segment(
+    this: &mut Timer,
+    segment_index: usize,
+    comparison: *const c_char,
+    feed_segment_history: bool,
+) -> i32 {
+    // SAFETY: The caller guarantees that `comparison` is valid.
+    convert(this.start_at_segment(
+        segment_index,
+        unsafe { str(comparison) },
+        feed_segment_history,
+    ))
+}
+
 /// If an attempt is in progress, stores the current time as the time of the
 /// current split. The attempt ends if the last split time is stored.
 #[unsafe(no_mangle)]
@@ -151,6 +171,68 @@ pub extern "C" fn Timer_undo_split(this: &mut Timer) -> i32 {
     convert(this.undo_split())
 }
 
+/// Returns whether the timer is in practice mode. Attempts done in practice
+/// mode are not counted and neither stored in the attempt history nor in the
+/// segment history.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_is_practicing(this: &Timer) -> bool {
+    this.is_practicing()
+}
+
+/// Turns practice mode on or off. The mode can't be changed while an attempt
+/// is in progress, in which case `false` is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_set_practice_mode(this: &mut Timer, is_practicing: bool) -> bool {
+    this.set_practice_mode(is_practicing).is_ok()
+}
+
+/// Reverts the most recent split, skip, undone split, pause, resume, undone
+/// pauses or reset of the current attempt.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_undo(this: &mut Timer) -> i32 {
+    convert(this.undo())
+}
+
+/// Adjusts the offset of the current attempt retroactively, as if the timer was
+/// started earlier by the amount specified. The start of the attempt and all
+/// the split times recorded so far are shifted accordingly.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_adjust_offset(this: &mut Timer, amount: &TimeSpan) -> i32 {
+    convert(this.adjust_offset(*amount))
+}
+
+/// Adds time to or removes time from the current time of the attempt for the
+/// timing method specified. The split times recorded so far are not modified.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_adjust_time(
+    this: &mut Timer,
+    amount: &TimeSpan,
+    timing_method: TimingMethod,
+) -> i32 {
+    convert(this.adjust_time(*amount, timing_method))
+}
+
+/// Returns the minimum amount of time that needs to pass after the attempt was
+/// started or the previous split happened before another split is accepted.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_split_cooldown(this: &Timer) -> *const TimeSpan {
+    output_time_span(this.split_cooldown())
+}
+
+/// Sets the minimum amount of time that needs to pass after the attempt was
+/// started or the previous split happened before another split is accepted.
+/// Splits that happen too soon are rejected.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_set_split_cooldown(this: &mut Timer, cooldown: &TimeSpan) {
+    this.set_split_cooldown(*cooldown);
+}
+
+/// Applies the action that was most recently reverted with undo again.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_redo(this: &mut Timer) -> i32 {
+    convert(this.redo())
+}
+
 /// Checks whether the current attempt has new best segment times in any of the
 /// segments (for both TimingMethods) or a new Personal Best (for the current
 /// TimingMethod). This can be used to ask the user whether to update the splits
@@ -253,6 +335,46 @@ pub unsafe extern "C" fn Timer_set_current_comparison(
     convert(this.set_current_comparison(unsafe { str(comparison) }))
 }
 
+/// Adds a race comparison for the opponent specified. Its split times can be
+/// updated live with Timer_set_race_split_time. Race comparisons are not
+/// stored in the splits file. If there already is a race comparison for the
+/// opponent, <FALSE> is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_add_race_comparison(
+    this: &mut Timer,
+    opponent: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `opponent` is valid.
+    this.add_race_comparison(unsafe { str(opponent) }).is_ok()
+}
+
+/// Updates the split time of the opponent's race comparison for the segment
+/// specified. If there is no race comparison for the opponent or the segment
+/// index is out of bounds, <FALSE> is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_set_race_split_time(
+    this: &mut Timer,
+    opponent: *const c_char,
+    segment_index: usize,
+    time: &Time,
+) -> bool {
+    // SAFETY: The caller guarantees that `opponent` is valid.
+    this.set_race_split_time(unsafe { str(opponent) }, segment_index, *time)
+        .is_ok()
+}
+
+/// Removes the race comparison of the opponent specified. If there is no race
+/// comparison for the opponent, <FALSE> is returned.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_remove_race_comparison(
+    this: &mut Timer,
+    opponent: *const c_char,
+) -> bool {
+    // SAFETY: The caller guarantees that `opponent` is valid.
+    this.remove_race_comparison(unsafe { str(opponent) })
+        .is_ok()
+}
+
 /// Switches the current comparison to the next comparison in the list.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_switch_to_next_comparison(this: &mut Timer) {
@@ -279,10 +401,11 @@ pub extern "C" fn Timer_initialize_game_time(this: &mut Timer) -> i32 {
     convert(this.initialize_game_time())
 }
 
-/// Deinitializes Game Time for the current attempt.
+/// Deinitializes Game Time for the current attempt and records it in the
+/// event log of the attempt.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_deinitialize_game_time(this: &mut Timer) {
-    this.deinitialize_game_time();
+    this.deinitialize_game_time_logged();
 }
 
 /// Returns whether the Game Timer is currently paused. If the Game Timer is
@@ -342,6 +465,42 @@ pub unsafe extern "C" fn Timer_set_custom_variable(
     this.set_custom_variable(unsafe { str(name) }, unsafe { str(value) });
 }
 
+/// Encodes a journal of the current attempt as JSON, so it can be recovered
+/// after a crash. Frontends should store it periodically while an attempt is
+/// in progress. If there is no attempt in progress, `null` is returned.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_journal_as_json(this: &Timer) -> Json {
+    output_vec(|o| {
+        serde_json::to_writer(o, &this.journal()).unwrap();
+    })
+}
+
+/// Recovers the attempt stored in the journal provided as JSON, for example
+/// after the application crashed. The attempt continues as if it never got
+/// interrupted. <FALSE> is returned if the journal couldn't be parsed or
+/// recovered or if there is an attempt in progress.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn Timer_recover_from_json(this: &mut Timer, journal: Json) -> bool {
+    // SAFETY: The caller guarantees that `journal` is valid.
+    let journal = Cursor::new(unsafe { str(journal).as_bytes() });
+    Journal::from_json(journal).is_ok_and(|journal| this.recover(&journal).is_ok())
+}
+
+/// Sets whether the values of the custom variables at each split are stored in
+/// the attempt history when an attempt is reset. The values at the end of the
+/// attempt are always stored.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_set_records_split_variables(this: &mut Timer, records: bool) {
+    this.set_records_split_variables(records);
+}
+
+/// Sets whether the event log of an attempt is stored in the attempt history
+/// when the attempt is reset, so the attempt can be replayed later on.
+#[unsafe(no_mangle)]
+pub extern "C" fn Timer_set_stores_event_logs(this: &mut Timer, stores: bool) {
+    this.set_stores_event_logs(stores);
+}
+
 /// Returns the current Timer Phase.
 #[unsafe(no_mangle)]
 pub extern "C" fn Timer_current_phase(this: &Timer) -> TimerPhase {
@@ -4,7 +4,9 @@
 
 use super::{Json, output_vec};
 use crate::{component::OwnedComponent, timer_component_state::OwnedTimerComponentState};
-use livesplit_core::{GeneralLayoutSettings, Timer, component::timer::Component as TimerComponent};
+use livesplit_core::{
+    GeneralLayoutSettings, Timer, component::timer::Component as TimerComponent, util::json,
+};
 
 /// type
 pub type OwnedTimerComponent = Box<TimerComponent>;
@@ -36,9 +38,7 @@ pub extern "C" fn TimerComponent_state_as_json(
     layout_settings: &GeneralLayoutSettings,
 ) -> Json {
     output_vec(|o| {
-        this.state(&timer.snapshot(), layout_settings)
-            .write_json(o)
-            .unwrap();
+        json::to_vec(&this.state(&timer.snapshot(), layout_settings), o).unwrap();
     })
 }
 
@@ -4,7 +4,9 @@
 
 use super::{Json, output_vec};
 use crate::{component::OwnedComponent, title_component_state::OwnedTitleComponentState};
-use livesplit_core::{Timer, component::title::Component as TitleComponent, settings::ImageCache};
+use livesplit_core::{
+    Timer, component::title::Component as TitleComponent, settings::ImageCache, util::json,
+};
 
 /// type
 pub type OwnedTitleComponent = Box<TitleComponent>;
@@ -36,7 +38,7 @@ pub extern "C" fn TitleComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(image_cache, timer).write_json(o).unwrap();
+        json::to_vec(&this.state(image_cache, timer), o).unwrap();
     })
 }
 
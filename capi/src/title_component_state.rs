@@ -69,3 +69,10 @@ pub extern "C" fn TitleComponentState_shows_attempts(this: &TitleComponentState)
 pub extern "C" fn TitleComponentState_attempts(this: &TitleComponentState) -> u32 {
     this.attempts.unwrap_or_default()
 }
+
+/// Returns whether the timer is in practice mode, in which case a practice
+/// indicator is supposed to be shown instead of the attempt counts.
+#[unsafe(no_mangle)]
+pub extern "C" fn TitleComponentState_is_practicing(this: &TitleComponentState) -> bool {
+    this.is_practicing
+}
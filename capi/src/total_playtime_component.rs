@@ -3,7 +3,9 @@
 
 use super::{Json, output_vec};
 use crate::{component::OwnedComponent, key_value_component_state::OwnedKeyValueComponentState};
-use livesplit_core::{Timer, component::total_playtime::Component as TotalPlaytimeComponent};
+use livesplit_core::{
+    Timer, component::total_playtime::Component as TotalPlaytimeComponent, util::json,
+};
 
 /// type
 pub type OwnedTotalPlaytimeComponent = Box<TotalPlaytimeComponent>;
@@ -36,7 +38,7 @@ pub extern "C" fn TotalPlaytimeComponent_state_as_json(
     timer: &Timer,
 ) -> Json {
     output_vec(|o| {
-        this.state(timer).write_json(o).unwrap();
+        json::to_vec(&this.state(timer), o).unwrap();
     })
 }
 
@@ -7,6 +7,7 @@ use std::{borrow::Cow, cell::Cell, convert::TryFrom, future::Future, sync::Arc};
 use livesplit_core::{
     TimeSpan, Timer, TimingMethod,
     event::{CommandSink, Error, Event, Result, TimerQuery},
+    timing::OverlayWidget,
 };
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
@@ -41,6 +42,7 @@ pub struct WebCommandSink {
     resume_game_time: Option<Function>,
     set_loading_times: Option<Function>,
     set_custom_variable: Option<Function>,
+    set_overlay_widgets: Option<Function>,
 
     get_timer: Function,
     locked: Cell<bool>,
@@ -73,6 +75,7 @@ impl WebCommandSink {
             resume_game_time: get_func(&obj, "resumeGameTime"),
             set_loading_times: get_func(&obj, "setLoadingTimes"),
             set_custom_variable: get_func(&obj, "setCustomVariable"),
+            set_overlay_widgets: get_func(&obj, "setOverlayWidgets"),
 
             get_timer: get_func(&obj, "getTimer").unwrap(),
             locked: Cell::new(false),
@@ -316,6 +319,18 @@ impl CommandSink for WebCommandSink {
             .ok()
         }))
     }
+
+    fn set_overlay_widgets(
+        &self,
+        widgets: Vec<OverlayWidget>,
+    ) -> impl Future<Output = Result> + 'static {
+        debug_assert!(!self.locked.get());
+        handle_action_value(self.set_overlay_widgets.as_ref().and_then(|f| {
+            // The widgets are passed as JSON, just like the component states.
+            let widgets = serde_json::to_string(&widgets).ok()?;
+            f.call1(&self.obj, &JsValue::from_str(&widgets)).ok()
+        }))
+    }
 }
 
 /// type
@@ -39,6 +39,15 @@
 //! #[repr(transparent)]
 //! pub struct ProcessId(u64);
 //!
+//! /// A request to read `buf_len` bytes of memory at the address given into the
+//! /// buffer given.
+//! #[repr(C)]
+//! pub struct ReadRequest {
+//!     pub address: Address,
+//!     pub buf_ptr: *mut u8,
+//!     pub buf_len: usize,
+//! }
+//!
 //! #[repr(transparent)]
 //! pub struct SettingsMap(NonZeroU64);
 //!
@@ -131,6 +140,40 @@
 //!         value_ptr: *const u8,
 //!         value_len: usize,
 //!     );
+//!     /// Gets the value of a custom variable. This includes variables that are
+//!     /// not set by the auto splitter, such as the ones set by the user. The
+//!     /// pointer needs to point to valid UTF-8 encoded text with the given
+//!     /// length. The value is written as UTF-8 encoded text into the buffer.
+//!     /// The buffer needs to be large enough to hold the value. If it's not, the
+//!     /// function returns `false` and the length of the value is written into
+//!     /// `buf_len_ptr`. If the variable doesn't exist, `false` is returned and a
+//!     /// length of 0 is written into `buf_len_ptr`.
+//!     pub fn timer_get_variable(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         buf_ptr: *mut u8,
+//!         buf_len_ptr: *mut usize,
+//!     ) -> bool;
+//!     /// Starts watching a custom variable for changes. Once per tick the value
+//!     /// of each watched variable is compared against the value it had in the
+//!     /// previous tick. Changes made by the auto splitter itself are not
+//!     /// reported. The pointer needs to point to valid UTF-8 encoded text with
+//!     /// the given length.
+//!     pub fn timer_watch_variable(key_ptr: *const u8, key_len: usize);
+//!     /// Stops watching a custom variable for changes. The pointer needs to
+//!     /// point to valid UTF-8 encoded text with the given length.
+//!     pub fn timer_unwatch_variable(key_ptr: *const u8, key_len: usize);
+//!     /// Writes the name of the next watched custom variable that changed since
+//!     /// it was last returned by this function as UTF-8 encoded text into the
+//!     /// buffer. The buffer needs to be large enough to hold the name. If it's
+//!     /// not, the function returns `false` and the length of the name is written
+//!     /// into `buf_len_ptr`. The name is then returned again by the next call.
+//!     /// If none of the watched variables changed, `false` is returned and a
+//!     /// length of 0 is written into `buf_len_ptr`.
+//!     pub fn timer_next_changed_variable(
+//!         buf_ptr: *mut u8,
+//!         buf_len_ptr: *mut usize,
+//!     ) -> bool;
 //!
 //!     /// Sets the game time.
 //!     pub safe fn timer_set_game_time(secs: i64, nanos: i32);
@@ -179,6 +222,18 @@
 //!         buf_ptr: *mut u8,
 //!         buf_len: usize,
 //!     ) -> bool;
+//!     /// Reads memory from a process at multiple addresses at once. Each of the
+//!     /// requests given writes the memory at its address to its buffer. Reads
+//!     /// that are close to each other are combined, so this is cheaper than
+//!     /// calling `process_read` for each of them. Whether each of the reads
+//!     /// succeeded is written to the results, which need to have the same
+//!     /// length as the requests. Returns the amount of reads that succeeded.
+//!     pub fn process_read_batch(
+//!         process: AttachedProcess,
+//!         requests_ptr: *const ReadRequest,
+//!         requests_len: usize,
+//!         results_ptr: *mut bool,
+//!     ) -> usize;
 //!     /// Gets the address of a module in a process. The pointer needs to point to
 //!     /// valid UTF-8 encoded text with the given length.
 //!     pub fn process_get_module_address(
@@ -193,6 +248,22 @@
 //!         name_ptr: *const u8,
 //!         name_len: usize,
 //!     ) -> Option<NonZeroU64>;
+//!     /// Scans the memory of a module in a process for a signature and returns
+//!     /// the address of the first match. The pointers need to point to valid
+//!     /// UTF-8 encoded text with the given lengths. Each byte of the signature
+//!     /// is written as two hexadecimal digits and wildcards that match any byte
+//!     /// are written as `??`, such as `48 8B ?? ?? 05`. The bytes may be
+//!     /// separated by whitespace. The result is cached until the module gets
+//!     /// loaded again, so this is cheap to call repeatedly, even if the
+//!     /// signature isn't found. The auto splitter traps if the signature is
+//!     /// invalid.
+//!     pub fn process_scan_module(
+//!         process: AttachedProcess,
+//!         name_ptr: *const u8,
+//!         name_len: usize,
+//!         signature_ptr: *const u8,
+//!         signature_len: usize,
+//!     ) -> Option<NonZeroAddress>;
 //!     /// Stores the file system path of a module in a process in the buffer
 //!     /// given. The pointer to the module name needs to point to valid UTF-8
 //!     /// encoded text with the given length. The path is a path that is
@@ -543,11 +614,40 @@
 //!         buf_ptr: *mut u8,
 //!         buf_len_ptr: *mut usize,
 //!     ) -> bool;
+//!
+//!     /// Sets an overlay widget that displays a text next to a label. Overlay
+//!     /// widgets are meant to show information about the game, such as the
+//!     /// health of a boss, in the layout of the timer. The key identifies the
+//!     /// widget. If a widget with the same key already exists, it gets replaced
+//!     /// in place. Otherwise the widget gets added after all the other widgets.
+//!     /// All three strings need to be valid UTF-8.
+//!     pub fn overlay_set_text(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         label_ptr: *const u8,
+//!         label_len: usize,
+//!         text_ptr: *const u8,
+//!         text_len: usize,
+//!     );
+//!     /// Sets an overlay widget that displays a progress bar next to a label.
+//!     /// The progress is a fraction between 0 and 1 and gets clamped to that
+//!     /// range. The key identifies the widget just like with `overlay_set_text`.
+//!     pub fn overlay_set_progress(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         label_ptr: *const u8,
+//!         label_len: usize,
+//!         progress: f64,
+//!     );
+//!     /// Removes the overlay widget with the given key. Nothing happens if there
+//!     /// is no such widget.
+//!     pub fn overlay_remove(key_ptr: *const u8, key_len: usize);
 //! }
 //! ```
 //!
-//! On top of the runtime's API, there's also WASI 0.1 support. Considering WASI
-//! itself is still in preview, the API is subject to change. Auto splitters
+//! On top of the runtime's API, there's also WASI 0.1 support. Modules targeting
+//! the older `wasi_unstable` version of WASI are supported as well. Considering
+//! WASI itself is still in preview, the API is subject to change. Auto splitters
 //! using WASI may need to be recompiled in the future. Limitations of the WASI
 //! support:
 //!
@@ -562,6 +662,17 @@
 //! - There is no networking.
 //! - There is no threading.
 //! - Time and random numbers are available.
+//!
+//! # WebAssembly Components
+//!
+//! Auto splitters may also be provided as WebAssembly components. Those need to
+//! target the `auto-splitter` world of the `livesplit:auto-splitting` package,
+//! which is defined in `wit/auto-splitter.wit`. It provides the same API as the
+//! `env` module, but uses the types of the component model instead of pointers
+//! into the memory of the auto splitter, so any toolchain with support for the
+//! component model can generate the bindings. The component needs to export an
+//! `update` function, but no memory. WASI 0.2 is provided to components with the
+//! same limitations as WASI 0.1 is to modules.
 
 #![warn(
     clippy::complexity,
@@ -572,18 +683,20 @@
     clippy::undocumented_unsafe_blocks,
     missing_docs
 )]
-#![forbid(clippy::incompatible_msrv)]
+#![deny(clippy::incompatible_msrv)]
 
+pub mod overlay;
 mod process;
 mod runtime;
 pub mod settings;
+mod signature;
 mod timer;
 pub mod wasi_path;
 
 pub use process::Process;
 pub use runtime::{
     AutoSplitter, CompiledAutoSplitter, Config, CreationError, ExecutionGuard, InterruptHandle,
-    Runtime,
+    MemoryWatch, Runtime, TickStatistics,
 };
 pub use time;
 pub use timer::{LogLevel, Timer, TimerState};
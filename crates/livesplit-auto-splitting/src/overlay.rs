@@ -0,0 +1,99 @@
+//! Widgets that an auto splitter displays to the user.
+//!
+//! # Overview
+//!
+//! Unlike the settings [`Widgets`](crate::settings::Widget), these widgets are
+//! not meant to be interacted with. They allow the auto splitter to show
+//! information about the game, such as the health of a boss or the seed of the
+//! random number generator. Each widget is identified by a key. Setting a
+//! widget with a key that is already in use replaces the widget in place, so
+//! the widgets keep the order in which they were first set.
+
+use std::sync::Arc;
+
+/// A widget that displays information from the auto splitter.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Widget {
+    /// The unique identifier of the widget.
+    pub key: Arc<str>,
+    /// The label to show for the widget.
+    pub label: Arc<str>,
+    /// The value that the widget displays.
+    pub value: WidgetValue,
+}
+
+/// The value that a [`Widget`] displays.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum WidgetValue {
+    /// A text, shown next to the label.
+    Text(Arc<str>),
+    /// A progress bar that is filled by the given fraction. The fraction is
+    /// between 0 and 1.
+    Progress(f64),
+}
+
+pub(crate) fn set(widgets: &mut Arc<Vec<Widget>>, key: &str, label: &str, value: WidgetValue) {
+    let value = match value {
+        WidgetValue::Progress(progress) if progress.is_nan() => WidgetValue::Progress(0.0),
+        WidgetValue::Progress(progress) => WidgetValue::Progress(progress.clamp(0.0, 1.0)),
+        value => value,
+    };
+    match widgets.iter().position(|widget| &*widget.key == key) {
+        Some(index) => {
+            let widget = &widgets[index];
+            if &*widget.label == label && widget.value == value {
+                // The widgets only get cloned when something actually changes,
+                // so unchanged widgets aren't reported as changed.
+                return;
+            }
+            let widget = &mut Arc::make_mut(widgets)[index];
+            widget.label = label.into();
+            widget.value = value;
+        }
+        None => Arc::make_mut(widgets).push(Widget {
+            key: key.into(),
+            label: label.into(),
+            value,
+        }),
+    }
+}
+
+pub(crate) fn remove(widgets: &mut Arc<Vec<Widget>>, key: &str) {
+    if let Some(index) = widgets.iter().position(|widget| &*widget.key == key) {
+        Arc::make_mut(widgets).remove(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_remove() {
+        let mut widgets = Arc::new(Vec::new());
+        set(&mut widgets, "hp", "Boss HP", WidgetValue::Progress(1.5));
+        set(&mut widgets, "seed", "Seed", WidgetValue::Text("42".into()));
+        assert_eq!(widgets[0].value, WidgetValue::Progress(1.0));
+
+        let published = widgets.clone();
+        set(&mut widgets, "hp", "Boss HP", WidgetValue::Progress(1.0));
+        assert!(Arc::ptr_eq(&published, &widgets));
+
+        set(
+            &mut widgets,
+            "hp",
+            "Boss HP",
+            WidgetValue::Progress(f64::NAN),
+        );
+        assert!(!Arc::ptr_eq(&published, &widgets));
+        assert_eq!(widgets[0].value, WidgetValue::Progress(0.0));
+        assert_eq!(&*widgets[1].key, "seed");
+
+        remove(&mut widgets, "hp");
+        remove(&mut widgets, "unknown");
+        assert_eq!(widgets.len(), 1);
+        assert_eq!(&*widgets[0].key, "seed");
+    }
+}
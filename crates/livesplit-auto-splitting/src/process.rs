@@ -1,7 +1,9 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::{
+    collections::HashMap,
     io,
+    ops::Range,
     time::{Duration, Instant},
 };
 
@@ -9,7 +11,7 @@ use proc_maps::{MapRange, Pid};
 use read_process_memory::{CopyAddress, ProcessHandle};
 use snafu::{OptionExt, ResultExt, Snafu};
 
-use crate::{runtime::ProcessList, wasi_path};
+use crate::{runtime::ProcessList, signature::Signature, wasi_path};
 
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(false)))]
@@ -27,6 +29,15 @@ pub enum ModuleError {
 
 pub type Address = u64;
 
+/// Reads that are at most this many bytes apart get combined into a single read
+/// when reading memory in batches.
+const MAX_BATCH_GAP: u64 = 256;
+/// The size that a combined read may not exceed.
+const MAX_BATCH_LEN: u64 = 0x1000;
+/// The size of the chunks that memory gets read in when scanning it for a
+/// signature.
+const SCAN_CHUNK_LEN: usize = 0x10000;
+
 // FIXME: Temporary workaround until this is merged and released:
 // https://github.com/rbspy/read-process-memory/pull/21
 struct UnsafeSendSync<T>(T);
@@ -43,6 +54,17 @@ pub struct Process {
     next_memory_range_check: Instant,
     next_open_check: Instant,
     path: Option<Box<str>>,
+    /// The results of scanning the modules for signatures by the name of the
+    /// module.
+    signatures: HashMap<Box<str>, ModuleScans>,
+}
+
+/// The results of scanning a module for signatures, including the signatures
+/// that weren't found. They are dropped once the module gets loaded at a
+/// different address.
+struct ModuleScans {
+    module_address: Address,
+    results: HashMap<Signature, Option<Address>>,
 }
 
 impl std::fmt::Debug for Process {
@@ -82,6 +104,7 @@ impl Process {
             next_memory_range_check: now,
             next_open_check: now + Duration::from_secs(1),
             path,
+            signatures: HashMap::new(),
         })
     }
 
@@ -105,6 +128,7 @@ impl Process {
             next_memory_range_check: now,
             next_open_check: now + Duration::from_secs(1),
             path,
+            signatures: HashMap::new(),
         })
     }
 
@@ -160,6 +184,125 @@ impl Process {
         self.handle.0.copy_address(address as usize, buf)
     }
 
+    /// Reads the memory for each of the addresses given into the range of the
+    /// buffer that is paired with it. The ranges may overlap. The callback is
+    /// called with the index of each read once it is done, along with the
+    /// range of the buffer it read into and whether it succeeded. Returns
+    /// whether each of the reads succeeded.
+    pub(super) fn read_mem_batch(
+        &self,
+        reads: &[(Address, Range<usize>)],
+        buf: &mut [u8],
+        mut on_read: impl FnMut(usize, &[u8], bool),
+    ) -> Vec<bool> {
+        let lens = reads
+            .iter()
+            .map(|(address, range)| (*address, range.len()))
+            .collect::<Vec<_>>();
+
+        let mut results = vec![false; reads.len()];
+        let mut combined = Vec::new();
+
+        for (start, end, group) in batch_groups(&lens) {
+            if let [i] = *group {
+                let dst = &mut buf[reads[i].1.clone()];
+                results[i] = self.read_mem(start, dst).is_ok();
+                on_read(i, dst, results[i]);
+                continue;
+            }
+
+            // Groups of multiple reads never span more than `MAX_BATCH_LEN`
+            // bytes, so this buffer stays small.
+            combined.clear();
+            combined.resize((end - start) as usize, 0);
+            let combined_success = self.read_mem(start, &mut combined).is_ok();
+
+            for i in group {
+                let (address, ref range) = reads[i];
+                let dst = &mut buf[range.clone()];
+                results[i] = if combined_success {
+                    let combined_start = (address - start) as usize;
+                    dst.copy_from_slice(&combined[combined_start..][..dst.len()]);
+                    true
+                } else {
+                    // Only parts of the combined range may be readable, so the
+                    // reads may still succeed individually.
+                    self.read_mem(address, dst).is_ok()
+                };
+                on_read(i, dst, results[i]);
+            }
+        }
+
+        results
+    }
+
+    /// Scans the memory of the module given for the signature and returns the
+    /// address of the first match. The result is cached until the module gets
+    /// loaded at a different address.
+    pub(super) fn scan_module(
+        &mut self,
+        module: &str,
+        signature: &Signature,
+    ) -> Result<Option<Address>, ModuleError> {
+        let module_address = self.module_address(module)?;
+        if let Some(scans) = self.signatures.get(module)
+            && scans.module_address == module_address
+            && let Some(&address) = scans.results.get(signature)
+        {
+            return Ok(address);
+        }
+
+        let address = self.scan_module_uncached(module, signature);
+
+        let scans = self
+            .signatures
+            .entry(module.into())
+            .or_insert_with(|| ModuleScans {
+                module_address,
+                results: HashMap::new(),
+            });
+        if scans.module_address != module_address {
+            scans.module_address = module_address;
+            scans.results.clear();
+        }
+        scans.results.insert(signature.clone(), address);
+
+        Ok(address)
+    }
+
+    fn scan_module_uncached(&self, module: &str, signature: &Signature) -> Option<Address> {
+        let chunk_len = SCAN_CHUNK_LEN.max(2 * signature.len());
+        let mut buf = vec![0; chunk_len];
+
+        let ranges = self
+            .memory_ranges
+            .iter()
+            .filter(|m| m.is_read() && m.filename().is_some_and(|f| f.ends_with(module)));
+
+        for range in ranges {
+            let (mut address, end) = (
+                range.start() as Address,
+                (range.start() + range.size()) as Address,
+            );
+            while address < end {
+                let chunk = &mut buf[..chunk_len.min((end - address) as usize)];
+                if self.read_mem(address, chunk).is_ok()
+                    && let Some(offset) = signature.scan(chunk)
+                {
+                    return Some(address + offset as Address);
+                }
+                if address + chunk.len() as Address >= end {
+                    break;
+                }
+                // The chunks overlap, so signatures crossing the boundary
+                // between two chunks are found too.
+                address += (chunk.len() - (signature.len() - 1)) as Address;
+            }
+        }
+
+        None
+    }
+
     pub(super) fn get_memory_range_count(&mut self) -> Result<usize, ModuleError> {
         self.refresh_memory_ranges()?;
         Ok(self.memory_ranges.len())
@@ -237,3 +380,95 @@ impl Process {
         Ok(())
     }
 }
+
+/// Groups the reads that are close to each other, so each group can be read at
+/// once. Each group consists of the range of memory it covers and the indices
+/// of its reads.
+fn batch_groups(reads: &[(Address, usize)]) -> Vec<(Address, Address, Vec<usize>)> {
+    let mut order = (0..reads.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|&i| reads[i].0);
+
+    let mut groups = Vec::<(Address, Address, Vec<usize>)>::new();
+    for i in order {
+        let (address, len) = reads[i];
+        let end = address.saturating_add(len as u64);
+        if let Some((group_start, group_end, group)) = groups.last_mut()
+            && address <= group_end.saturating_add(MAX_BATCH_GAP)
+            && end.max(*group_end) - *group_start <= MAX_BATCH_LEN
+        {
+            *group_end = end.max(*group_end);
+            group.push(i);
+        } else {
+            groups.push((address, end, vec![i]));
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_nearby_reads() {
+        let groups = batch_groups(&[
+            (0x1100, 4),
+            (0x1000, 8),
+            (0x5000, 4),
+            (0x1004, 2),
+            (0x2000, 0x2000),
+            (0x1208, 8),
+        ]);
+        assert_eq!(
+            groups,
+            [
+                (0x1000, 0x1104, vec![1, 3, 0]),
+                (0x1208, 0x1210, vec![5]),
+                (0x2000, 0x4000, vec![4]),
+                (0x5000, 0x5004, vec![2]),
+            ],
+        );
+    }
+
+    #[test]
+    fn signatures_that_werent_found_are_cached() {
+        let mut process = Process::with_pid(std::process::id(), &mut ProcessList::new()).unwrap();
+        let exe = std::env::current_exe().unwrap();
+        let module = exe.file_name().unwrap().to_str().unwrap();
+        let signature = Signature::parse("4C 69 76 65 53 70 6C 69 74 ?? 00 FF 00 FF").unwrap();
+
+        assert_eq!(process.scan_module(module, &signature).unwrap(), None);
+        assert_eq!(process.signatures[module].results[&signature], None);
+
+        // Pretend the module got loaded at a different address before.
+        let scans = process.signatures.get_mut(module).unwrap();
+        let stale = Signature::parse("00").unwrap();
+        scans.results.insert(stale.clone(), Some(1));
+        scans.module_address += 1;
+
+        assert_eq!(process.scan_module(module, &signature).unwrap(), None);
+        let module_address = process.module_address(module).unwrap();
+        let scans = &process.signatures[module];
+        assert_eq!(scans.module_address, module_address);
+        assert!(!scans.results.contains_key(&stale));
+    }
+
+    #[test]
+    fn batched_reads_into_overlapping_buffers() {
+        let process = Process::with_pid(std::process::id(), &mut ProcessList::new()).unwrap();
+        let source = (0..=u8::MAX).cycle().take(16 << 20).collect::<Vec<u8>>();
+        let mut buf = vec![0; source.len()];
+
+        // If each of the reads got its own buffer, 4 GiB would be needed.
+        let reads = vec![(source.as_ptr() as Address, 0..buf.len()); 256];
+        let mut reads_done = 0;
+        let results = process.read_mem_batch(&reads, &mut buf, |_, read, success| {
+            assert!(success);
+            assert!(read == source);
+            reads_done += 1;
+        });
+
+        assert_eq!(reads_done, 256);
+        assert!(results.into_iter().all(|success| success));
+    }
+}
@@ -0,0 +1,751 @@
+use std::{
+    env::consts::{ARCH, OS},
+    sync::{Arc, atomic},
+};
+
+use anyhow::{Context as _, Result, bail, ensure, format_err};
+use slotmap::{Key, KeyData};
+use wasmtime::component::{HasSelf, Linker};
+use wasmtime_wasi::{WasiCtxView, WasiView};
+
+use crate::{
+    CreationError, Process, Timer,
+    overlay::{self, WidgetValue},
+    runtime::{Context, ProcessKey, SettingValueKey, SettingsListKey, SettingsMapKey},
+    settings,
+    signature::Signature,
+    timer::{self, LogLevel},
+};
+
+use self::livesplit::auto_splitting::{
+    overlay as overlay_api,
+    process::{self as process_api, MemoryRangeFlags},
+    runtime as runtime_api, setting_value as setting_value_api, settings_handles,
+    settings_list as settings_list_api, settings_map as settings_map_api, timer as timer_api,
+    user_settings as user_settings_api,
+};
+
+wasmtime::component::bindgen!({
+    world: "auto-splitter",
+    path: "wit",
+    imports: { default: trappable },
+});
+
+pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationError> {
+    AutoSplitter::add_to_linker::<_, HasSelf<_>>(linker, |context| context).map_err(|source| {
+        CreationError::LinkFunction {
+            source,
+            name: "livesplit:auto-splitting",
+        }
+    })?;
+    wasmtime_wasi::p2::add_to_linker_sync(linker).map_err(|source| CreationError::Wasi { source })
+}
+
+impl<T: Timer> WasiView for Context<T> {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        self.wasi.ctx()
+    }
+}
+
+impl<T> Context<T> {
+    fn process_mut(&mut self, process: u64) -> Result<&mut Process> {
+        self.processes
+            .get_mut(ProcessKey::from(KeyData::from_ffi(process)))
+            .ok_or_else(|| format_err!("Invalid process handle: {process}"))
+    }
+
+    fn settings_map(&self, settings_map: u64) -> Result<&settings::Map> {
+        self.settings_maps
+            .get(SettingsMapKey::from(KeyData::from_ffi(settings_map)))
+            .ok_or_else(|| format_err!("Invalid settings map handle: {settings_map}"))
+    }
+
+    fn settings_list(&self, settings_list: u64) -> Result<&settings::List> {
+        self.settings_lists
+            .get(SettingsListKey::from(KeyData::from_ffi(settings_list)))
+            .ok_or_else(|| format_err!("Invalid settings list handle: {settings_list}"))
+    }
+
+    fn setting_value(&self, setting_value: u64) -> Result<&settings::Value> {
+        self.setting_values
+            .get(SettingValueKey::from(KeyData::from_ffi(setting_value)))
+            .ok_or_else(|| format_err!("Invalid setting value handle: {setting_value}"))
+    }
+
+    fn insert_setting_value(&mut self, value: settings::Value) -> u64 {
+        self.setting_values.insert(value).data().as_ffi()
+    }
+
+    fn widget_mut(&mut self, key: &str) -> Result<&mut settings::Widget> {
+        Arc::make_mut(&mut self.settings_widgets)
+            .iter_mut()
+            .find(|s| &*s.key == key)
+            .context("There is no setting with the provided key.")
+    }
+}
+
+impl<T: Timer> timer_api::Host for Context<T> {
+    fn get_state(&mut self) -> Result<timer_api::TimerState> {
+        Ok(match self.timer.state() {
+            timer::TimerState::NotRunning => timer_api::TimerState::NotRunning,
+            timer::TimerState::Running => timer_api::TimerState::Running,
+            timer::TimerState::Paused => timer_api::TimerState::Paused,
+            timer::TimerState::Ended => timer_api::TimerState::Ended,
+        })
+    }
+
+    fn current_split_index(&mut self) -> Result<Option<u64>> {
+        Ok(self.timer.current_split_index().map(|i| i as u64))
+    }
+
+    fn segment_splitted(&mut self, index: u64) -> Result<Option<bool>> {
+        Ok(self.timer.segment_splitted(index as usize))
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.timer.start();
+        Ok(())
+    }
+
+    fn split(&mut self) -> Result<()> {
+        self.timer.split();
+        Ok(())
+    }
+
+    fn skip_split(&mut self) -> Result<()> {
+        self.timer.skip_split();
+        Ok(())
+    }
+
+    fn undo_split(&mut self) -> Result<()> {
+        self.timer.undo_split();
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.timer.reset();
+        Ok(())
+    }
+
+    fn set_variable(&mut self, key: String, value: String) -> Result<()> {
+        self.timer.set_variable(&key, &value);
+        self.watched_variables.set_by_auto_splitter(&key, &value);
+        Ok(())
+    }
+
+    fn get_variable(&mut self, key: String) -> Result<Option<String>> {
+        Ok(self.timer.variable(&key))
+    }
+
+    fn watch_variable(&mut self, key: String) -> Result<()> {
+        let value = self.timer.variable(&key);
+        self.watched_variables.watch(&key, value);
+        Ok(())
+    }
+
+    fn unwatch_variable(&mut self, key: String) -> Result<()> {
+        self.watched_variables.unwatch(&key);
+        Ok(())
+    }
+
+    fn next_changed_variable(&mut self) -> Result<Option<String>> {
+        let name = self.watched_variables.next_changed().map(str::to_owned);
+        if let Some(name) = &name {
+            self.watched_variables.mark_seen(name);
+        }
+        Ok(name)
+    }
+
+    fn set_game_time(&mut self, secs: i64, nanos: i32) -> Result<()> {
+        self.timer.set_game_time(time::Duration::new(secs, nanos));
+        Ok(())
+    }
+
+    fn pause_game_time(&mut self) -> Result<()> {
+        self.timer.pause_game_time();
+        Ok(())
+    }
+
+    fn resume_game_time(&mut self) -> Result<()> {
+        self.timer.resume_game_time();
+        Ok(())
+    }
+}
+
+impl<T: Timer> process_api::Host for Context<T> {
+    fn attach(&mut self, name: String) -> Result<Option<u64>> {
+        Ok(Process::with_name(&name, &mut self.process_list)
+            .ok()
+            .map(|p| self.insert_process(p)))
+    }
+
+    fn attach_by_pid(&mut self, pid: u64) -> Result<Option<u64>> {
+        Ok(pid
+            .try_into()
+            .ok()
+            .and_then(|pid| Process::with_pid(pid, &mut self.process_list).ok())
+            .map(|p| self.insert_process(p)))
+    }
+
+    fn detach(&mut self, process: u64) -> Result<()> {
+        self.processes
+            .remove(ProcessKey::from(KeyData::from_ffi(process)))
+            .ok_or_else(|| format_err!("Invalid process handle {process}"))?;
+        self.timer
+            .log_runtime(format_args!("Detached from a process."), LogLevel::Debug);
+        Ok(())
+    }
+
+    fn list_by_name(&mut self, name: String) -> Result<Option<Vec<u64>>> {
+        // Currently this can't fail, but that's only because `sysinfo` doesn't
+        // report any errors when listing the processes fails.
+        Ok(Some(
+            Process::list_pids_by_name(&name, &mut self.process_list)
+                .map(|pid| pid as u64)
+                .collect(),
+        ))
+    }
+
+    fn is_open(&mut self, process: u64) -> Result<bool> {
+        let proc = self
+            .processes
+            .get_mut(ProcessKey::from(KeyData::from_ffi(process)))
+            .ok_or_else(|| format_err!("Invalid process handle: {process}"))?;
+        Ok(proc.is_open(&mut self.process_list))
+    }
+
+    fn read(&mut self, process: u64, address: u64, len: u32) -> Result<Option<Vec<u8>>> {
+        let mut buf = vec![0; len as usize];
+        let success = self
+            .process_mut(process)?
+            .read_mem(address, &mut buf)
+            .is_ok();
+        self.record_read(address, &buf, success);
+        Ok(success.then_some(buf))
+    }
+
+    fn read_batch(&mut self, process: u64, reads: Vec<(u64, u32)>) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut len = 0usize;
+        let reads = reads
+            .into_iter()
+            .map(|(address, read_len)| {
+                let start = len;
+                len = len.saturating_add(read_len as usize);
+                (address, start..len)
+            })
+            .collect::<Vec<_>>();
+
+        // Unlike for modules, the host needs to allocate the memory that gets
+        // read. All of it needs to be returned into the memory of the auto
+        // splitter, so anything larger than that can't succeed anyway.
+        ensure!(
+            len <= self.max_memory_size,
+            "The reads don't fit into the memory of the auto splitter."
+        );
+        let mut buf = vec![0; len];
+        let results = self.read_mem_batch(process, &reads, &mut buf)?;
+
+        Ok(reads
+            .into_iter()
+            .zip(results)
+            .map(|((_, range), success)| success.then(|| buf[range].to_vec()))
+            .collect())
+    }
+
+    fn get_module_address(&mut self, process: u64, name: String) -> Result<Option<u64>> {
+        Ok(self.process_mut(process)?.module_address(&name).ok())
+    }
+
+    fn get_module_size(&mut self, process: u64, name: String) -> Result<Option<u64>> {
+        Ok(self.process_mut(process)?.module_size(&name).ok())
+    }
+
+    fn scan_module(
+        &mut self,
+        process: u64,
+        name: String,
+        signature: String,
+    ) -> Result<Option<u64>> {
+        let signature = Signature::parse(&signature).context("Invalid signature.")?;
+        Ok(self
+            .process_mut(process)?
+            .scan_module(&name, &signature)
+            .ok()
+            .flatten())
+    }
+
+    fn get_module_path(&mut self, process: u64, name: String) -> Result<Option<String>> {
+        Ok(self
+            .process_mut(process)?
+            .module_path(&name)
+            .ok()
+            .map(String::from))
+    }
+
+    fn get_path(&mut self, process: u64) -> Result<Option<String>> {
+        Ok(self.process_mut(process)?.path().map(String::from))
+    }
+
+    fn get_memory_range_count(&mut self, process: u64) -> Result<Option<u64>> {
+        Ok(self
+            .process_mut(process)?
+            .get_memory_range_count()
+            .ok()
+            .map(|count| count as u64))
+    }
+
+    fn get_memory_range_address(&mut self, process: u64, index: u64) -> Result<Option<u64>> {
+        Ok(self
+            .process_mut(process)?
+            .get_memory_range_address(index as usize)
+            .ok())
+    }
+
+    fn get_memory_range_size(&mut self, process: u64, index: u64) -> Result<Option<u64>> {
+        Ok(self
+            .process_mut(process)?
+            .get_memory_range_size(index as usize)
+            .ok())
+    }
+
+    fn get_memory_range_flags(
+        &mut self,
+        process: u64,
+        index: u64,
+    ) -> Result<Option<MemoryRangeFlags>> {
+        let Ok(bits) = self
+            .process_mut(process)?
+            .get_memory_range_flags(index as usize)
+        else {
+            return Ok(None);
+        };
+        let mut flags = MemoryRangeFlags::empty();
+        for (bit, flag) in [
+            (1 << 1, MemoryRangeFlags::READ),
+            (1 << 2, MemoryRangeFlags::WRITE),
+            (1 << 3, MemoryRangeFlags::EXECUTE),
+            (1 << 4, MemoryRangeFlags::PATH),
+        ] {
+            if bits & bit != 0 {
+                flags |= flag;
+            }
+        }
+        Ok(Some(flags))
+    }
+}
+
+impl<T: Timer> Context<T> {
+    fn insert_process(&mut self, process: Process) -> u64 {
+        self.timer.log_runtime(
+            format_args!(
+                "Attached to a new process: {}",
+                process.name().unwrap_or("<Unnamed Process>")
+            ),
+            LogLevel::Debug,
+        );
+        self.processes.insert(process).data().as_ffi()
+    }
+}
+
+impl<T: Timer> runtime_api::Host for Context<T> {
+    fn set_tick_rate(&mut self, ticks_per_second: f64) -> Result<()> {
+        self.timer.log_runtime(
+            format_args!("New Tick Rate: {ticks_per_second}"),
+            LogLevel::Debug,
+        );
+
+        ensure!(
+            ticks_per_second > 0.0,
+            "The tick rate needs to be larger than 0."
+        );
+        let duration = ticks_per_second.recip();
+
+        const MAX_DURATION: f64 = u64::MAX as f64;
+        ensure!(duration < MAX_DURATION, "The tick rate is too small.");
+
+        self.shared_data
+            .tick_rate
+            .store(duration.to_bits(), atomic::Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    fn print_message(&mut self, text: String) -> Result<()> {
+        self.timer.log_auto_splitter(format_args!("{text}"));
+        Ok(())
+    }
+
+    fn get_os(&mut self) -> Result<String> {
+        Ok(OS.into())
+    }
+
+    fn get_arch(&mut self) -> Result<String> {
+        Ok(ARCH.into())
+    }
+}
+
+impl<T: Timer> user_settings_api::Host for Context<T> {
+    fn add_bool(&mut self, key: String, description: String, default_value: bool) -> Result<bool> {
+        let key = Arc::<str>::from(key);
+        let value_in_map = match self.shared_data.get_settings_map().get(&key) {
+            Some(settings::Value::Bool(v)) => *v,
+            _ => default_value,
+        };
+        Arc::make_mut(&mut self.settings_widgets).push(settings::Widget {
+            key,
+            description: description.into(),
+            tooltip: None,
+            kind: settings::WidgetKind::Bool { default_value },
+        });
+        Ok(value_in_map)
+    }
+
+    fn add_title(&mut self, key: String, description: String, heading_level: u32) -> Result<()> {
+        Arc::make_mut(&mut self.settings_widgets).push(settings::Widget {
+            key: key.into(),
+            description: description.into(),
+            tooltip: None,
+            kind: settings::WidgetKind::Title { heading_level },
+        });
+        Ok(())
+    }
+
+    fn add_choice(
+        &mut self,
+        key: String,
+        description: String,
+        default_option_key: String,
+    ) -> Result<()> {
+        Arc::make_mut(&mut self.settings_widgets).push(settings::Widget {
+            key: key.into(),
+            description: description.into(),
+            tooltip: None,
+            kind: settings::WidgetKind::Choice {
+                default_option_key: default_option_key.into(),
+                options: Arc::new(Vec::new()),
+            },
+        });
+        Ok(())
+    }
+
+    fn add_choice_option(
+        &mut self,
+        key: String,
+        option_key: String,
+        option_description: String,
+    ) -> Result<bool> {
+        let setting = self.widget_mut(&key)?;
+        let settings::WidgetKind::Choice {
+            options,
+            default_option_key,
+        } = &mut setting.kind
+        else {
+            bail!("The setting is not a choice.");
+        };
+        let is_chosen = **default_option_key == *option_key;
+        Arc::make_mut(options).push(settings::ChoiceOption {
+            key: option_key.into(),
+            description: option_description.into(),
+        });
+        Ok(is_chosen)
+    }
+
+    fn add_file_select(&mut self, key: String, description: String) -> Result<()> {
+        Arc::make_mut(&mut self.settings_widgets).push(settings::Widget {
+            key: key.into(),
+            description: description.into(),
+            tooltip: None,
+            kind: settings::WidgetKind::FileSelect {
+                filters: Arc::new(Vec::new()),
+            },
+        });
+        Ok(())
+    }
+
+    fn add_file_select_name_filter(
+        &mut self,
+        key: String,
+        description: Option<String>,
+        pattern: String,
+    ) -> Result<()> {
+        let settings::WidgetKind::FileSelect { filters } = &mut self.widget_mut(&key)?.kind else {
+            bail!("The setting is not a file select.");
+        };
+        Arc::make_mut(filters).push(settings::FileFilter::Name {
+            description: description.map(Into::into),
+            pattern: pattern.into(),
+        });
+        Ok(())
+    }
+
+    fn add_file_select_mime_filter(&mut self, key: String, mime_type: String) -> Result<()> {
+        let settings::WidgetKind::FileSelect { filters } = &mut self.widget_mut(&key)?.kind else {
+            bail!("The setting is not a file select.");
+        };
+        Arc::make_mut(filters).push(settings::FileFilter::MimeType(mime_type.into()));
+        Ok(())
+    }
+
+    fn set_tooltip(&mut self, key: String, tooltip: String) -> Result<()> {
+        self.widget_mut(&key)?.tooltip = Some(tooltip.into());
+        Ok(())
+    }
+}
+
+impl<T: Timer> settings_handles::Host for Context<T> {}
+
+impl<T: Timer> settings_map_api::Host for Context<T> {
+    fn new(&mut self) -> Result<u64> {
+        Ok(self
+            .settings_maps
+            .insert(settings::Map::new())
+            .data()
+            .as_ffi())
+    }
+
+    fn free(&mut self, map: u64) -> Result<()> {
+        self.settings_maps
+            .remove(SettingsMapKey::from(KeyData::from_ffi(map)))
+            .ok_or_else(|| format_err!("Invalid settings map handle: {map}"))?;
+        Ok(())
+    }
+
+    fn load(&mut self) -> Result<u64> {
+        let settings_map = self.shared_data.get_settings_map();
+        Ok(self.settings_maps.insert(settings_map).data().as_ffi())
+    }
+
+    fn store(&mut self, map: u64) -> Result<()> {
+        let settings_map = self.settings_map(map)?.clone();
+        self.shared_data.set_settings_map(settings_map);
+        Ok(())
+    }
+
+    fn store_if_unchanged(&mut self, old_map: u64, new_map: u64) -> Result<bool> {
+        let old_map = self
+            .settings_map(old_map)
+            .context("Invalid old settings map handle.")?;
+        let new_map = self
+            .settings_map(new_map)
+            .context("Invalid new settings map handle.")?
+            .clone();
+        Ok(self
+            .shared_data
+            .set_settings_map_if_unchanged(old_map, new_map))
+    }
+
+    fn copy(&mut self, map: u64) -> Result<u64> {
+        let settings_map = self.settings_map(map)?.clone();
+        Ok(self.settings_maps.insert(settings_map).data().as_ffi())
+    }
+
+    fn insert(&mut self, map: u64, key: String, value: u64) -> Result<()> {
+        let value = self.setting_value(value)?.clone();
+        self.settings_maps
+            .get_mut(SettingsMapKey::from(KeyData::from_ffi(map)))
+            .ok_or_else(|| format_err!("Invalid settings map handle: {map}"))?
+            .insert(key.into(), value);
+        Ok(())
+    }
+
+    fn get(&mut self, map: u64, key: String) -> Result<Option<u64>> {
+        let value = self.settings_map(map)?.get(&key).cloned();
+        Ok(value.map(|value| self.insert_setting_value(value)))
+    }
+
+    fn len(&mut self, map: u64) -> Result<u64> {
+        Ok(self.settings_map(map)?.len() as u64)
+    }
+
+    fn get_key_by_index(&mut self, map: u64, index: u64) -> Result<Option<String>> {
+        Ok(self
+            .settings_map(map)?
+            .get_by_index(index.try_into().unwrap_or(usize::MAX))
+            .map(|(key, _)| key.into()))
+    }
+
+    fn get_value_by_index(&mut self, map: u64, index: u64) -> Result<Option<u64>> {
+        let value = self
+            .settings_map(map)?
+            .get_by_index(index.try_into().unwrap_or(usize::MAX))
+            .map(|(_, value)| value.clone());
+        Ok(value.map(|value| self.insert_setting_value(value)))
+    }
+}
+
+impl<T: Timer> settings_list_api::Host for Context<T> {
+    fn new(&mut self) -> Result<u64> {
+        Ok(self
+            .settings_lists
+            .insert(settings::List::new())
+            .data()
+            .as_ffi())
+    }
+
+    fn free(&mut self, list: u64) -> Result<()> {
+        self.settings_lists
+            .remove(SettingsListKey::from(KeyData::from_ffi(list)))
+            .ok_or_else(|| format_err!("Invalid settings list handle: {list}"))?;
+        Ok(())
+    }
+
+    fn copy(&mut self, list: u64) -> Result<u64> {
+        let settings_list = self.settings_list(list)?.clone();
+        Ok(self.settings_lists.insert(settings_list).data().as_ffi())
+    }
+
+    fn len(&mut self, list: u64) -> Result<u64> {
+        Ok(self.settings_list(list)?.len() as u64)
+    }
+
+    fn get(&mut self, list: u64, index: u64) -> Result<Option<u64>> {
+        let value = self
+            .settings_list(list)?
+            .get(index.try_into().unwrap_or(usize::MAX))
+            .cloned();
+        Ok(value.map(|value| self.insert_setting_value(value)))
+    }
+
+    fn push(&mut self, list: u64, value: u64) -> Result<()> {
+        let value = self.setting_value(value)?.clone();
+        self.settings_lists
+            .get_mut(SettingsListKey::from(KeyData::from_ffi(list)))
+            .ok_or_else(|| format_err!("Invalid settings list handle: {list}"))?
+            .push(value);
+        Ok(())
+    }
+
+    fn insert(&mut self, list: u64, index: u64, value: u64) -> Result<bool> {
+        let value = self.setting_value(value)?.clone();
+        Ok(self
+            .settings_lists
+            .get_mut(SettingsListKey::from(KeyData::from_ffi(list)))
+            .ok_or_else(|| format_err!("Invalid settings list handle: {list}"))?
+            .insert(index.try_into().unwrap_or(usize::MAX), value)
+            .is_ok())
+    }
+}
+
+impl<T: Timer> setting_value_api::Host for Context<T> {
+    fn new_map(&mut self, value: u64) -> Result<u64> {
+        let value = settings::Value::Map(self.settings_map(value)?.clone());
+        Ok(self.insert_setting_value(value))
+    }
+
+    fn new_list(&mut self, value: u64) -> Result<u64> {
+        let value = settings::Value::List(self.settings_list(value)?.clone());
+        Ok(self.insert_setting_value(value))
+    }
+
+    fn new_bool(&mut self, value: bool) -> Result<u64> {
+        Ok(self.insert_setting_value(settings::Value::Bool(value)))
+    }
+
+    fn new_s64(&mut self, value: i64) -> Result<u64> {
+        Ok(self.insert_setting_value(settings::Value::I64(value)))
+    }
+
+    fn new_f64(&mut self, value: f64) -> Result<u64> {
+        Ok(self.insert_setting_value(settings::Value::F64(value)))
+    }
+
+    fn new_string(&mut self, value: String) -> Result<u64> {
+        Ok(self.insert_setting_value(settings::Value::String(value.into())))
+    }
+
+    fn free(&mut self, value: u64) -> Result<()> {
+        self.setting_values
+            .remove(SettingValueKey::from(KeyData::from_ffi(value)))
+            .ok_or_else(|| format_err!("Invalid setting value handle: {value}"))?;
+        Ok(())
+    }
+
+    fn copy(&mut self, value: u64) -> Result<u64> {
+        let value = self.setting_value(value)?.clone();
+        Ok(self.insert_setting_value(value))
+    }
+
+    fn get_type(&mut self, value: u64) -> Result<setting_value_api::SettingValueType> {
+        use setting_value_api::SettingValueType;
+
+        Ok(match self.setting_value(value)? {
+            settings::Value::Map(_) => SettingValueType::Map,
+            settings::Value::List(_) => SettingValueType::List,
+            settings::Value::Bool(_) => SettingValueType::Bool,
+            settings::Value::I64(_) => SettingValueType::S64,
+            settings::Value::F64(_) => SettingValueType::F64,
+            settings::Value::String(_) => SettingValueType::String,
+        })
+    }
+
+    fn get_map(&mut self, value: u64) -> Result<Option<u64>> {
+        let settings::Value::Map(map) = self.setting_value(value)? else {
+            return Ok(None);
+        };
+        let map = map.clone();
+        Ok(Some(self.settings_maps.insert(map).data().as_ffi()))
+    }
+
+    fn get_list(&mut self, value: u64) -> Result<Option<u64>> {
+        let settings::Value::List(list) = self.setting_value(value)? else {
+            return Ok(None);
+        };
+        let list = list.clone();
+        Ok(Some(self.settings_lists.insert(list).data().as_ffi()))
+    }
+
+    fn get_bool(&mut self, value: u64) -> Result<Option<bool>> {
+        Ok(match self.setting_value(value)? {
+            settings::Value::Bool(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    fn get_s64(&mut self, value: u64) -> Result<Option<i64>> {
+        Ok(match self.setting_value(value)? {
+            settings::Value::I64(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    fn get_f64(&mut self, value: u64) -> Result<Option<f64>> {
+        Ok(match self.setting_value(value)? {
+            settings::Value::F64(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    fn get_string(&mut self, value: u64) -> Result<Option<String>> {
+        Ok(match self.setting_value(value)? {
+            settings::Value::String(value) => Some(value.to_string()),
+            _ => None,
+        })
+    }
+}
+
+impl<T: Timer> overlay_api::Host for Context<T> {
+    fn set_text(&mut self, key: String, label: String, text: String) -> Result<()> {
+        overlay::set(
+            &mut self.overlay_widgets,
+            &key,
+            &label,
+            WidgetValue::Text(text.into()),
+        );
+        Ok(())
+    }
+
+    fn set_progress(&mut self, key: String, label: String, progress: f64) -> Result<()> {
+        overlay::set(
+            &mut self.overlay_widgets,
+            &key,
+            &label,
+            WidgetValue::Progress(progress),
+        );
+        Ok(())
+    }
+
+    fn remove(&mut self, key: String) -> Result<()> {
+        overlay::remove(&mut self.overlay_widgets, &key);
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@ use crate::{CreationError, Timer};
 
 use super::Context;
 
+pub mod component;
+mod overlay;
 mod process;
 mod runtime;
 mod setting_value;
@@ -25,6 +27,7 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
     settings_map::bind(linker)?;
     settings_list::bind(linker)?;
     setting_value::bind(linker)?;
+    overlay::bind(linker)?;
     Ok(())
 }
 
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use wasmtime::{Caller, Linker};
+
+use crate::{
+    CreationError, Timer,
+    overlay::{self, WidgetValue},
+    runtime::Context,
+};
+
+use super::{get_str, memory_and_context};
+
+pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationError> {
+    linker
+        .func_wrap("env", "overlay_set_text", {
+            |mut caller: Caller<Context<T>>,
+             key_ptr: u32,
+             key_len: u32,
+             label_ptr: u32,
+             label_len: u32,
+             text_ptr: u32,
+             text_len: u32|
+             -> Result<()> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let key = get_str(memory, key_ptr, key_len)?;
+                let label = get_str(memory, label_ptr, label_len)?;
+                let text = get_str(memory, text_ptr, text_len)?;
+                overlay::set(
+                    &mut context.overlay_widgets,
+                    key,
+                    label,
+                    WidgetValue::Text(text.into()),
+                );
+                Ok(())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "overlay_set_text",
+        })?
+        .func_wrap("env", "overlay_set_progress", {
+            |mut caller: Caller<Context<T>>,
+             key_ptr: u32,
+             key_len: u32,
+             label_ptr: u32,
+             label_len: u32,
+             progress: f64|
+             -> Result<()> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let key = get_str(memory, key_ptr, key_len)?;
+                let label = get_str(memory, label_ptr, label_len)?;
+                overlay::set(
+                    &mut context.overlay_widgets,
+                    key,
+                    label,
+                    WidgetValue::Progress(progress),
+                );
+                Ok(())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "overlay_set_progress",
+        })?
+        .func_wrap("env", "overlay_remove", {
+            |mut caller: Caller<Context<T>>, key_ptr: u32, key_len: u32| -> Result<()> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let key = get_str(memory, key_ptr, key_len)?;
+                overlay::remove(&mut context.overlay_widgets, key);
+                Ok(())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "overlay_remove",
+        })?;
+    Ok(())
+}
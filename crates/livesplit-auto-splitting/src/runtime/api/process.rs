@@ -7,10 +7,13 @@ use wasmtime::{Caller, Linker};
 use crate::{
     CreationError, Process, Timer,
     runtime::{Context, ProcessKey},
+    signature::Signature,
     timer::LogLevel,
 };
 
-use super::{get_arr_mut, get_slice_mut, get_str, get_two_slice_mut, memory_and_context};
+use super::{
+    get_arr_mut, get_slice, get_slice_mut, get_str, get_two_slice_mut, memory_and_context,
+};
 
 pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationError> {
     linker
@@ -149,18 +152,69 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
              buf_ptr: u32,
              buf_len: u32| {
                 let (memory, context) = memory_and_context(&mut caller);
-                Ok(context
+                let buf = get_slice_mut(memory, buf_ptr, buf_len)?;
+                let success = context
                     .processes
                     .get(ProcessKey::from(KeyData::from_ffi(process)))
                     .ok_or_else(|| format_err!("Invalid process handle: {process}"))?
-                    .read_mem(address, get_slice_mut(memory, buf_ptr, buf_len)?)
-                    .is_ok() as u32)
+                    .read_mem(address, buf)
+                    .is_ok();
+                context.record_read(address, buf, success);
+                Ok(success as u32)
             }
         })
         .map_err(|source| CreationError::LinkFunction {
             source,
             name: "process_read",
         })?
+        .func_wrap("env", "process_read_batch", {
+            |mut caller: Caller<Context<T>>,
+             process: u64,
+             requests_ptr: u32,
+             requests_len: u32,
+             results_ptr: u32| {
+                let (memory, context) = memory_and_context(&mut caller);
+
+                // Each request consists of the address, followed by the
+                // pointer to and the length of the buffer. The memory is read
+                // straight into the buffers, so the lengths chosen by the auto
+                // splitter never determine the size of an allocation.
+                let reads = get_slice(
+                    memory,
+                    requests_ptr,
+                    requests_len
+                        .checked_mul(16)
+                        .context("The request length overflows the size of the address space.")?,
+                )?
+                .chunks_exact(16)
+                .map(|request| {
+                    let (address, buf) = request.split_at(8);
+                    let (buf_ptr, buf_len) = buf.split_at(4);
+                    let buf_ptr = u32::from_le_bytes(buf_ptr.try_into().unwrap());
+                    let buf_len = u32::from_le_bytes(buf_len.try_into().unwrap());
+                    get_slice(memory, buf_ptr, buf_len)?;
+                    Ok((
+                        u64::from_le_bytes(address.try_into().unwrap()),
+                        buf_ptr as usize..buf_ptr as usize + buf_len as usize,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+                get_slice(memory, results_ptr, requests_len)?;
+
+                let results = context.read_mem_batch(process, &reads, memory)?;
+
+                let results_bytes = get_slice_mut(memory, results_ptr, requests_len)?;
+                for (result_byte, &success) in results_bytes.iter_mut().zip(&results) {
+                    *result_byte = success as u8;
+                }
+
+                Ok(results.iter().filter(|&&success| success).count() as u32)
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "process_read_batch",
+        })?
         .func_wrap("env", "process_get_module_address", {
             |mut caller: Caller<Context<T>>, process: u64, ptr: u32, len: u32| {
                 let (memory, context) = memory_and_context(&mut caller);
@@ -193,6 +247,31 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
             source,
             name: "process_get_module_size",
         })?
+        .func_wrap("env", "process_scan_module", {
+            |mut caller: Caller<Context<T>>,
+             process: u64,
+             name_ptr: u32,
+             name_len: u32,
+             signature_ptr: u32,
+             signature_len: u32| {
+                let (memory, context) = memory_and_context(&mut caller);
+                let module_name = get_str(memory, name_ptr, name_len)?;
+                let signature = Signature::parse(get_str(memory, signature_ptr, signature_len)?)
+                    .context("Invalid signature.")?;
+                Ok(context
+                    .processes
+                    .get_mut(ProcessKey::from(KeyData::from_ffi(process)))
+                    .ok_or_else(|| format_err!("Invalid process handle: {process}"))?
+                    .scan_module(module_name, &signature)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "process_scan_module",
+        })?
         .func_wrap("env", "process_get_module_path", {
             |mut caller: Caller<Context<T>>,
              process: u64,
@@ -3,7 +3,7 @@ use wasmtime::{Caller, Linker};
 
 use crate::{CreationError, Timer, runtime::Context};
 
-use super::{get_str, memory_and_context};
+use super::{get_arr_mut, get_slice_mut, get_str, memory_and_context};
 
 pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationError> {
     linker
@@ -94,6 +94,7 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
                 let name = get_str(memory, name_ptr, name_len)?;
                 let value = get_str(memory, value_ptr, value_len)?;
                 context.timer.set_variable(name, value);
+                context.watched_variables.set_by_auto_splitter(name, value);
                 Ok(())
             }
         })
@@ -101,6 +102,64 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
             source,
             name: "timer_set_variable",
         })?
+        .func_wrap("env", "timer_get_variable", {
+            |mut caller: Caller<Context<T>>,
+             name_ptr: u32,
+             name_len: u32,
+             buf_ptr: u32,
+             buf_len_ptr: u32|
+             -> Result<u32> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let name = get_str(memory, name_ptr, name_len)?;
+                let value = context.timer.variable(name);
+                write_str(memory, value.as_deref(), buf_ptr, buf_len_ptr)
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "timer_get_variable",
+        })?
+        .func_wrap("env", "timer_watch_variable", {
+            |mut caller: Caller<Context<T>>, name_ptr: u32, name_len: u32| -> Result<()> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let name = get_str(memory, name_ptr, name_len)?;
+                let value = context.timer.variable(name);
+                context.watched_variables.watch(name, value);
+                Ok(())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "timer_watch_variable",
+        })?
+        .func_wrap("env", "timer_unwatch_variable", {
+            |mut caller: Caller<Context<T>>, name_ptr: u32, name_len: u32| -> Result<()> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let name = get_str(memory, name_ptr, name_len)?;
+                context.watched_variables.unwatch(name);
+                Ok(())
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "timer_unwatch_variable",
+        })?
+        .func_wrap("env", "timer_next_changed_variable", {
+            |mut caller: Caller<Context<T>>, buf_ptr: u32, buf_len_ptr: u32| -> Result<u32> {
+                let (memory, context) = memory_and_context(&mut caller);
+                let name = context.watched_variables.next_changed();
+                let written = write_str(memory, name, buf_ptr, buf_len_ptr)?;
+                if written != 0 {
+                    let name = name.unwrap().to_owned();
+                    context.watched_variables.mark_seen(&name);
+                }
+                Ok(written)
+            }
+        })
+        .map_err(|source| CreationError::LinkFunction {
+            source,
+            name: "timer_next_changed_variable",
+        })?
         .func_wrap("env", "timer_set_game_time", {
             |mut caller: Caller<Context<T>>, secs: i64, nanos: i32| {
                 caller
@@ -129,3 +188,21 @@ pub fn bind<T: Timer>(linker: &mut Linker<Context<T>>) -> Result<(), CreationErr
         })?;
     Ok(())
 }
+
+/// Writes the text into the buffer if it fits. The length that the buffer
+/// needs to have is always stored, or 0 if there is no text.
+fn write_str(memory: &mut [u8], text: Option<&str>, buf_ptr: u32, buf_len_ptr: u32) -> Result<u32> {
+    let len_bytes = get_arr_mut(memory, buf_len_ptr)?;
+    let Some(text) = text else {
+        *len_bytes = 0u32.to_le_bytes();
+        return Ok(0);
+    };
+    let len = u32::from_le_bytes(*len_bytes) as usize;
+    *len_bytes = (text.len() as u32).to_le_bytes();
+    if len < text.len() {
+        return Ok(0);
+    }
+    let buf = get_slice_mut(memory, buf_ptr, text.len() as _)?;
+    buf.copy_from_slice(text.as_bytes());
+    Ok(1)
+}
@@ -0,0 +1,105 @@
+use std::sync::{
+    Mutex,
+    atomic::{self, AtomicBool},
+};
+
+/// A range of memory of the processes that the auto splitter is attached to
+/// that is being watched. Whenever the auto splitter reads memory overlapping
+/// the range, the bytes it read get recorded. Which process the memory is read
+/// from is not considered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryWatch {
+    /// The address where the watched range starts.
+    pub address: u64,
+    /// The most recently read bytes of the watched range. The length of the
+    /// range is the length of this buffer. Bytes that the auto splitter didn't
+    /// read yet are zero.
+    pub value: Box<[u8]>,
+    /// The tick in which the auto splitter read any of the bytes of the range
+    /// most recently. This is [`None`] if it didn't read them yet.
+    pub last_read_tick: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct Debugger {
+    tracing: AtomicBool,
+    watching: AtomicBool,
+    watches: Mutex<Vec<MemoryWatch>>,
+}
+
+impl Debugger {
+    pub fn is_tracing(&self) -> bool {
+        self.tracing.load(atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_tracing(&self, tracing: bool) {
+        self.tracing.store(tracing, atomic::Ordering::Relaxed);
+    }
+
+    pub fn watch(&self, address: u64, len: usize) {
+        let mut watches = self.watches.lock().unwrap();
+        watches.retain(|watch| watch.address != address);
+        watches.push(MemoryWatch {
+            address,
+            value: vec![0; len].into(),
+            last_read_tick: None,
+        });
+        self.watching.store(true, atomic::Ordering::Relaxed);
+    }
+
+    pub fn unwatch(&self, address: u64) -> bool {
+        let mut watches = self.watches.lock().unwrap();
+        let len = watches.len();
+        watches.retain(|watch| watch.address != address);
+        self.watching
+            .store(!watches.is_empty(), atomic::Ordering::Relaxed);
+        watches.len() != len
+    }
+
+    pub fn watches(&self) -> Vec<MemoryWatch> {
+        self.watches.lock().unwrap().clone()
+    }
+
+    pub fn record_read(&self, tick: u64, address: u64, buf: &[u8]) {
+        if !self.watching.load(atomic::Ordering::Relaxed) {
+            return;
+        }
+        let end = address.saturating_add(buf.len() as u64);
+        for watch in &mut *self.watches.lock().unwrap() {
+            let watch_end = watch.address.saturating_add(watch.value.len() as u64);
+            let (start, stop) = (address.max(watch.address), end.min(watch_end));
+            if start >= stop {
+                continue;
+            }
+            let (src, dst) = ((start - address) as usize, (start - watch.address) as usize);
+            let len = (stop - start) as usize;
+            watch.value[dst..dst + len].copy_from_slice(&buf[src..src + len]);
+            watch.last_read_tick = Some(tick);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_overlapping_reads() {
+        let debugger = Debugger::default();
+        debugger.watch(0x1004, 4);
+
+        debugger.record_read(1, 0x1000, &[1, 2, 3, 4]);
+        assert_eq!(debugger.watches()[0].last_read_tick, None);
+
+        debugger.record_read(2, 0x1002, &[5, 6, 7, 8]);
+        debugger.record_read(3, 0x1006, &[9, 10, 11, 12]);
+        let watch = &debugger.watches()[0];
+        assert_eq!(*watch.value, [7, 8, 9, 10]);
+        assert_eq!(watch.last_read_tick, Some(3));
+
+        assert!(debugger.unwatch(0x1004));
+        assert!(!debugger.unwatch(0x1004));
+        assert!(debugger.watches().is_empty());
+    }
+}
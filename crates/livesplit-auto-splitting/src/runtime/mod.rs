@@ -1,19 +1,23 @@
 #![allow(clippy::unnecessary_cast)]
 
 use crate::{
+    overlay,
     process::Process,
     settings,
     timer::{LogLevel, Timer},
 };
 
-use anyhow::Result;
+use anyhow::{Result, format_err};
 use api::wasi::StdErr;
 use arc_swap::ArcSwap;
+use debugger::Debugger;
 use indexmap::IndexMap;
-use slotmap::SlotMap;
+use slotmap::{KeyData, SlotMap};
 use snafu::Snafu;
+use statistics::TickHistory;
 use std::{
-    path::Path,
+    ops::Range,
+    path::{Path, PathBuf},
     sync::{
         Arc, Mutex, MutexGuard,
         atomic::{self, AtomicU64},
@@ -21,12 +25,23 @@ use std::{
     time::{Duration, Instant},
 };
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System, UpdateKind};
+use variables::WatchedVariables;
 use wasmtime::{
-    Engine, Extern, Linker, Memory, Module, OptLevel, Store, TypedFunc, WasmBacktraceDetails,
+    CallHook, Engine, Extern, Linker, Memory, Module, OptLevel, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc, WasmBacktraceDetails,
+    component::{self, Component},
 };
 use wasmtime_wasi::preview1::WasiP1Ctx;
 
 mod api;
+mod debugger;
+mod statistics;
+mod variables;
+
+pub use debugger::MemoryWatch;
+pub use statistics::TickStatistics;
+
+const DEFAULT_TICK_RATE: f64 = 1.0 / 120.0;
 
 /// An error that is returned when the creation of a new runtime fails.
 #[derive(Debug, Snafu)]
@@ -75,6 +90,9 @@ pub enum CreationError {
         /// The underlying error.
         source: anyhow::Error,
     },
+    /// The auto splitter was compiled by a different runtime than the auto
+    /// splitter it is meant to replace.
+    DifferentRuntime,
 }
 
 slotmap::new_key_type! {
@@ -90,12 +108,110 @@ pub struct Context<T> {
     settings_lists: SlotMap<SettingsListKey, settings::List>,
     setting_values: SlotMap<SettingValueKey, settings::Value>,
     settings_widgets: Arc<Vec<settings::Widget>>,
+    overlay_widgets: Arc<Vec<overlay::Widget>>,
+    watched_variables: WatchedVariables,
     shared_data: Arc<SharedData>,
     timer: T,
     memory: Option<Memory>,
     process_list: ProcessList,
     wasi: WasiP1Ctx,
     stderr: StdErr,
+    limits: StoreLimits,
+    max_memory_size: usize,
+    tick: u64,
+    trace: TickTrace,
+}
+
+#[derive(Default)]
+struct TickTrace {
+    host_calls: u64,
+    reads: u64,
+    read_bytes: u64,
+}
+
+impl<T> Context<T> {
+    fn new(
+        timer: T,
+        shared_data: Arc<SharedData>,
+        process_list: ProcessList,
+        interpreter_script_path: Option<&Path>,
+        limits: Limits,
+        tick: u64,
+    ) -> Self {
+        let (wasi, stderr) = api::wasi::build(interpreter_script_path);
+
+        Self {
+            processes: SlotMap::with_key(),
+            settings_maps: SlotMap::with_key(),
+            settings_lists: SlotMap::with_key(),
+            setting_values: SlotMap::with_key(),
+            settings_widgets: Arc::new(Vec::new()),
+            overlay_widgets: Arc::new(Vec::new()),
+            watched_variables: WatchedVariables::default(),
+            shared_data,
+            timer,
+            memory: None,
+            process_list,
+            wasi,
+            stderr,
+            limits: limits.store_limits(),
+            max_memory_size: limits.max_memory_size(),
+            tick,
+            trace: TickTrace::default(),
+        }
+    }
+
+    fn into_store(self, engine: &Engine) -> Store<Self> {
+        let mut store = Store::new(engine, self);
+        store.limiter(|context| &mut context.limits);
+        store.set_epoch_deadline(1);
+        store
+    }
+
+    fn record_read(&mut self, address: u64, buf: &[u8], success: bool) {
+        self.trace
+            .record_read(&self.shared_data.debugger, self.tick, address, buf, success);
+    }
+
+    /// Reads the memory of the process in batches and records each of the
+    /// reads. See [`Process::read_mem_batch`] for more information.
+    fn read_mem_batch(
+        &mut self,
+        process: u64,
+        reads: &[(u64, Range<usize>)],
+        buf: &mut [u8],
+    ) -> Result<Vec<bool>> {
+        let Self {
+            processes,
+            shared_data,
+            tick,
+            trace,
+            ..
+        } = self;
+        let process = processes
+            .get(ProcessKey::from(KeyData::from_ffi(process)))
+            .ok_or_else(|| format_err!("Invalid process handle: {process}"))?;
+        Ok(process.read_mem_batch(reads, buf, |i, buf, success| {
+            trace.record_read(&shared_data.debugger, *tick, reads[i].0, buf, success);
+        }))
+    }
+}
+
+impl TickTrace {
+    fn record_read(
+        &mut self,
+        debugger: &Debugger,
+        tick: u64,
+        address: u64,
+        buf: &[u8],
+        success: bool,
+    ) {
+        self.reads += 1;
+        if success {
+            self.read_bytes += buf.len() as u64;
+            debugger.record_read(tick, address, buf);
+        }
+    }
 }
 
 /// A thread-safe handle used to interrupt the execution of the script.
@@ -114,7 +230,7 @@ pub struct ProcessList {
 }
 
 impl ProcessList {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             system: System::new_with_specifics(
                 RefreshKind::nothing().with_processes(multiple_processes()),
@@ -201,6 +317,15 @@ pub struct Config {
     /// occurs more details are printed in the backtrace. By default this is
     /// `true` if the feature `enhanced-backtrace` is enabled.
     pub backtrace_details: bool,
+    /// The amount of fuel that the auto splitter may consume within a single
+    /// tick. Executing a WebAssembly instruction roughly consumes one unit of
+    /// fuel. The auto splitter traps if it runs out of fuel. There is no limit
+    /// by default.
+    pub fuel_per_tick: Option<u64>,
+    /// The size in bytes that each memory of the auto splitter may grow to at
+    /// most. Attempts to grow the memory beyond this size fail. There is no
+    /// limit by default.
+    pub max_memory_size: Option<usize>,
 }
 
 impl Default for Config {
@@ -209,19 +334,62 @@ impl Default for Config {
             debug_info: cfg!(feature = "debugger-support"),
             optimize: true,
             backtrace_details: cfg!(feature = "enhanced-backtrace"),
+            fuel_per_tick: None,
+            max_memory_size: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Limits {
+    fuel_per_tick: Option<u64>,
+    max_memory_size: Option<usize>,
+}
+
+impl Limits {
+    fn store_limits(&self) -> StoreLimits {
+        let mut builder = StoreLimitsBuilder::new();
+        if let Some(size) = self.max_memory_size {
+            builder = builder.memory_size(size);
+        }
+        builder.build()
+    }
+
+    /// The size that the memory of the auto splitter may grow to at most. A
+    /// 32-bit memory can't be larger than 4 GiB.
+    fn max_memory_size(&self) -> usize {
+        self.max_memory_size.unwrap_or(u32::MAX as usize)
+    }
+
+    fn refuel<T>(&self, store: &mut Store<T>) -> Result<()> {
+        if let Some(fuel) = self.fuel_per_tick {
+            store.set_fuel(fuel)?;
         }
+        Ok(())
     }
 }
 
 struct SharedData {
     settings_map: ArcSwap<IndexMap<Arc<str>, settings::Value>>,
     tick_rate: AtomicU64,
+    tick_history: TickHistory,
+    debugger: Debugger,
 }
 
 struct ExclusiveData<T: 'static> {
     trapped: bool,
-    store: Store<Context<T>>,
-    update: TypedFunc<(), ()>,
+    tracing_hooked: bool,
+    // This is only ever `None` while the auto splitter gets instantiated again.
+    store: Option<Store<Context<T>>>,
+    update: Update,
+    code: Code,
+    limits: Limits,
+    interpreter_script_path: Option<PathBuf>,
+}
+
+enum Update {
+    Module(TypedFunc<(), ()>),
+    Component(api::component::AutoSplitter),
 }
 
 /// An instantiated auto splitter that is ready to be executed. You generally
@@ -236,6 +404,7 @@ pub struct AutoSplitter<T: 'static> {
     exclusive_data: Mutex<ExclusiveData<T>>,
     engine: Engine,
     settings_widgets: ArcSwap<Vec<settings::Widget>>,
+    overlay_widgets: ArcSwap<Vec<overlay::Widget>>,
     shared_data: Arc<SharedData>,
 }
 
@@ -244,6 +413,7 @@ pub struct AutoSplitter<T: 'static> {
 /// run at the same time. It can only be accessed by one thread at a time.
 pub struct ExecutionGuard<'runtime, T: Timer> {
     settings_widgets: &'runtime ArcSwap<Vec<settings::Widget>>,
+    overlay_widgets: &'runtime ArcSwap<Vec<overlay::Widget>>,
     data: MutexGuard<'runtime, ExclusiveData<T>>,
 }
 
@@ -255,43 +425,171 @@ impl<T: Timer> ExecutionGuard<'_, T> {
         if data.trapped {
             return Ok(());
         }
-        let result = data.update.call(&mut data.store, ());
+        let store = data.store.as_mut().unwrap();
+        data.limits.refuel(store)?;
+
+        let tracing = store.data().shared_data.debugger.is_tracing();
+        if tracing && !data.tracing_hooked {
+            store.call_hook(|mut store, hook| {
+                if let CallHook::CallingHost = hook {
+                    store.data_mut().trace.host_calls += 1;
+                }
+                Ok(())
+            });
+            data.tracing_hooked = true;
+        }
+        let context = store.data_mut();
+        context.tick += 1;
+        context.trace = TickTrace::default();
+        let timer = &context.timer;
+        context.watched_variables.poll(|name| timer.variable(name));
+        let fuel_before = store.get_fuel().ok();
+
+        let start = Instant::now();
+        let result = match &data.update {
+            Update::Module(update) => update.call(&mut *store, ()),
+            Update::Component(bindings) => bindings.call_update(&mut *store),
+        };
+        let duration = start.elapsed();
+        store.data().shared_data.tick_history.record(duration);
+
+        if tracing {
+            let fuel = fuel_before.zip(store.get_fuel().ok());
+            let context = store.data_mut();
+            let (tick, trace) = (context.tick, &context.trace);
+            let outcome = if result.is_ok() {
+                "finished"
+            } else {
+                "trapped"
+            };
+            match fuel {
+                Some((before, after)) => context.timer.log_runtime(
+                    format_args!(
+                        "Tick {tick} {outcome} after {duration:?} with {} runtime calls and {} bytes read in {} reads, consuming {} fuel.",
+                        trace.host_calls,
+                        trace.read_bytes,
+                        trace.reads,
+                        before - after,
+                    ),
+                    LogLevel::Trace,
+                ),
+                None => context.timer.log_runtime(
+                    format_args!(
+                        "Tick {tick} {outcome} after {duration:?} with {} runtime calls and {} bytes read in {} reads.",
+                        trace.host_calls, trace.read_bytes, trace.reads,
+                    ),
+                    LogLevel::Trace,
+                ),
+            }
+        }
 
         if result.is_ok() {
             self.settings_widgets
-                .store(data.store.data().settings_widgets.clone());
+                .store(store.data().settings_widgets.clone());
+            publish_overlay_widgets(self.overlay_widgets, store.data_mut());
         } else {
             data.trapped = true;
         }
 
-        let data = data.store.data_mut();
-        data.stderr.print_lines(&mut data.timer);
+        let context = store.data_mut();
+        context.stderr.print_lines(&mut context.timer);
 
         result
     }
 
+    /// Returns whether the auto splitter trapped. It doesn't run anymore in that
+    /// case, unless it gets [`restarted`](Self::restart).
+    pub fn has_trapped(&self) -> bool {
+        self.data.trapped
+    }
+
+    /// Restarts the auto splitter by instantiating it again. This allows an
+    /// auto splitter to recover after it trapped. The timer and the settings
+    /// map are kept, but all of its handles, such as the processes it attached
+    /// to, get released and it needs to declare its settings widgets again.
+    pub fn restart(&mut self) -> Result<(), CreationError> {
+        let code = self.data.code.clone();
+        self.reinstantiate(code)
+    }
+
+    /// Replaces the auto splitter with a different one, such as a newer
+    /// version of it that got compiled after its file changed. Just like when
+    /// [`restarting`](Self::restart) the auto splitter, the timer and the
+    /// settings map are kept, so the new auto splitter continues where the
+    /// previous one left off as far as possible. The tick statistics and the
+    /// debugging state are kept as well. The new auto splitter needs to be
+    /// compiled by the same [`Runtime`] as the current one. If the new auto
+    /// splitter fails to instantiate, the auto splitter is considered to have
+    /// trapped.
+    pub fn replace(&mut self, compiled: &CompiledAutoSplitter) -> Result<(), CreationError> {
+        if !Engine::same(compiled.code.engine(), self.data.code.engine()) {
+            return Err(CreationError::DifferentRuntime);
+        }
+        self.data.limits = compiled.limits;
+        self.reinstantiate(compiled.code.clone())
+    }
+
+    fn reinstantiate(&mut self, code: Code) -> Result<(), CreationError> {
+        let data = &mut *self.data;
+        data.trapped = true;
+        data.tracing_hooked = false;
+        data.code = code;
+
+        // The previous instance is dropped entirely, so its memory is freed.
+        let previous = data.store.take().unwrap().into_data();
+        previous
+            .shared_data
+            .tick_rate
+            .store(DEFAULT_TICK_RATE.to_bits(), atomic::Ordering::Relaxed);
+
+        let store = data.store.insert(
+            Context::new(
+                previous.timer,
+                previous.shared_data,
+                previous.process_list,
+                data.interpreter_script_path.as_deref(),
+                data.limits,
+                previous.tick,
+            )
+            .into_store(data.code.engine()),
+        );
+
+        let result = data.code.instantiate(store, data.limits);
+
+        self.settings_widgets
+            .store(store.data().settings_widgets.clone());
+        // The widgets of the previous instance are gone, so the timer needs to
+        // stop showing them.
+        publish_overlay_widgets(self.overlay_widgets, store.data_mut());
+
+        data.update = result?;
+        data.trapped = false;
+
+        Ok(())
+    }
+
     /// Accesses the memory of the WebAssembly module. This may be useful for
-    /// debugging purposes.
+    /// debugging purposes. The memory of WebAssembly components is not exposed,
+    /// so it is empty for them.
     pub fn memory(&self) -> &[u8] {
-        self.data
-            .store
+        let store = self.data.store();
+        store
             .data()
             .memory
             .as_ref()
-            .unwrap()
-            .data(&self.data.store)
+            .map_or(&[], |memory| memory.data(store))
     }
 
     /// Iterates over all the processes that the auto splitter is currently
     /// attached to. This may be useful for debugging purposes.
     pub fn attached_processes(&self) -> impl Iterator<Item = &Process> {
-        self.data.store.data().processes.values()
+        self.data.store().data().processes.values()
     }
 
     /// Returns the total amount of handles that are currently in use. This may
     /// be useful for debugging purposes to detect leaked handles.
     pub fn handles(&self) -> u64 {
-        let data = self.data.store.data();
+        let data = self.data.store().data();
         data.processes.len() as u64
             + data.settings_maps.len() as u64
             + data.settings_lists.len() as u64
@@ -299,6 +597,12 @@ impl<T: Timer> ExecutionGuard<'_, T> {
     }
 }
 
+impl<T> ExclusiveData<T> {
+    const fn store(&self) -> &Store<Context<T>> {
+        self.store.as_ref().unwrap()
+    }
+}
+
 impl SharedData {
     fn set_settings_map(&self, settings_map: settings::Map) {
         self.settings_map.store(settings_map.values);
@@ -320,11 +624,19 @@ impl SharedData {
 /// module to control a timer.
 pub struct Runtime {
     engine: Engine,
+    limits: Limits,
 }
 
 /// A compiled auto splitter that can be instantiated.
 pub struct CompiledAutoSplitter {
-    module: Module,
+    code: Code,
+    limits: Limits,
+}
+
+#[derive(Clone)]
+enum Code {
+    Module(Module),
+    Component(Component),
 }
 
 impl Runtime {
@@ -346,20 +658,41 @@ impl Runtime {
             })
             .wasm_function_references(true)
             .wasm_gc(true)
+            .wasm_component_model(true)
+            .consume_fuel(config.fuel_per_tick.is_some())
             .epoch_interruption(true);
 
         let engine = Engine::new(&engine_config)
             .map_err(|source| CreationError::EngineCreation { source })?;
 
-        Ok(Self { engine })
+        Ok(Self {
+            engine,
+            limits: Limits {
+                fuel_per_tick: config.fuel_per_tick,
+                max_memory_size: config.max_memory_size,
+            },
+        })
     }
 
     /// Compiles the given auto splitter that is provided as a WebAssembly
-    /// module.
+    /// module or a WebAssembly component.
     pub fn compile(&self, module: &[u8]) -> Result<CompiledAutoSplitter, CreationError> {
+        // Both start with the same magic number, but the layer that follows the
+        // version is 0 for modules and 1 for components.
+        let code = if module.get(6..8) == Some(&[1, 0]) {
+            Code::Component(
+                Component::from_binary(&self.engine, module)
+                    .map_err(|source| CreationError::ModuleLoading { source })?,
+            )
+        } else {
+            Code::Module(
+                Module::from_binary(&self.engine, module)
+                    .map_err(|source| CreationError::ModuleLoading { source })?,
+            )
+        };
         Ok(CompiledAutoSplitter {
-            module: Module::from_binary(&self.engine, module)
-                .map_err(|source| CreationError::ModuleLoading { source })?,
+            code,
+            limits: self.limits,
         })
     }
 }
@@ -372,92 +705,166 @@ impl CompiledAutoSplitter {
         settings_map: Option<settings::Map>,
         interpreter_script_path: Option<&Path>,
     ) -> Result<AutoSplitter<T>, CreationError> {
-        let engine = self.module.engine();
-
-        let settings_widgets = Arc::new(Vec::new());
+        let engine = self.code.engine();
 
         let shared_data = Arc::new(SharedData {
             settings_map: ArcSwap::new(settings_map.unwrap_or_default().values),
-            tick_rate: AtomicU64::new(f64::to_bits(1.0 / 120.0)),
+            tick_rate: AtomicU64::new(DEFAULT_TICK_RATE.to_bits()),
+            tick_history: TickHistory::default(),
+            debugger: Debugger::default(),
         });
 
-        let (wasi, stderr) = api::wasi::build(interpreter_script_path);
+        let mut store = Context::new(
+            timer,
+            shared_data.clone(),
+            ProcessList::new(),
+            interpreter_script_path,
+            self.limits,
+            0,
+        )
+        .into_store(engine);
 
-        let mut store = Store::new(
-            engine,
-            Context {
-                processes: SlotMap::with_key(),
-                settings_maps: SlotMap::with_key(),
-                settings_lists: SlotMap::with_key(),
-                setting_values: SlotMap::with_key(),
-                settings_widgets: settings_widgets.clone(),
-                shared_data: shared_data.clone(),
-                timer,
-                memory: None,
-                process_list: ProcessList::new(),
-                wasi,
-                stderr,
-            },
-        );
-
-        store.set_epoch_deadline(1);
+        let update = self.code.instantiate(&mut store, self.limits)?;
 
-        let mut linker = Linker::new(engine);
-        api::bind(&mut linker)?;
+        Ok(AutoSplitter {
+            exclusive_data: Mutex::new(ExclusiveData {
+                trapped: false,
+                tracing_hooked: false,
+                store: Some(store),
+                update,
+                code: self.code.clone(),
+                limits: self.limits,
+                interpreter_script_path: interpreter_script_path.map(Path::to_path_buf),
+            }),
+            engine: engine.clone(),
+            settings_widgets: ArcSwap::new(Arc::new(Vec::new())),
+            overlay_widgets: ArcSwap::new(Arc::new(Vec::new())),
+            shared_data,
+        })
+    }
+}
 
-        let uses_wasi = self
-            .module
-            .imports()
-            .any(|import| import.module() == "wasi_snapshot_preview1");
+fn publish_overlay_widgets<T: Timer>(
+    published: &ArcSwap<Vec<overlay::Widget>>,
+    context: &mut Context<T>,
+) {
+    let previous = published.load();
+    if Arc::ptr_eq(&previous, &context.overlay_widgets) {
+        return;
+    }
+    published.store(context.overlay_widgets.clone());
+    if **previous != *context.overlay_widgets {
+        context.timer.set_overlay_widgets(&context.overlay_widgets);
+    }
+}
 
-        if uses_wasi {
-            wasmtime_wasi::preview1::add_to_linker_sync(&mut linker, |ctx| &mut ctx.wasi)
-                .map_err(|source| CreationError::Wasi { source })?;
+impl Code {
+    fn engine(&self) -> &Engine {
+        match self {
+            Code::Module(module) => module.engine(),
+            Code::Component(component) => component.engine(),
         }
+    }
 
-        let instance = linker
-            .instantiate(&mut store, &self.module)
+    fn instantiate<T: Timer>(
+        &self,
+        store: &mut Store<Context<T>>,
+        limits: Limits,
+    ) -> Result<Update, CreationError> {
+        // The initialization of the auto splitter gets the same amount of fuel
+        // as a single tick.
+        limits
+            .refuel(store)
             .map_err(|source| CreationError::ModuleInstantiation { source })?;
 
-        let Some(Extern::Memory(mem)) = instance.get_export(&mut store, "memory") else {
-            return Err(CreationError::MissingMemory);
-        };
-        store.data_mut().memory = Some(mem);
+        match self {
+            Code::Module(module) => instantiate_module(module, store),
+            Code::Component(component) => instantiate_component(component, store),
+        }
+    }
+}
 
-        if uses_wasi
-            || self.module.get_export("_initialize").is_some()
-            || self.module.get_export("_start").is_some()
-        {
-            store.data_mut().timer.log_runtime(
-                format_args!("This auto splitter uses WASI. The API is subject to change, because WASI is still in preview. Auto splitters using WASI may need to be recompiled in the future."),
-                LogLevel::Warning,
-            );
+fn instantiate_module<T: Timer>(
+    module: &Module,
+    store: &mut Store<Context<T>>,
+) -> Result<Update, CreationError> {
+    let mut linker = Linker::new(module.engine());
+    api::bind(&mut linker)?;
 
-            // These may be different in future WASI versions.
-            if let Ok(func) = instance.get_typed_func::<(), ()>(&mut store, "_initialize") {
-                func.call(&mut store, ())
-                    .map_err(|source| CreationError::WasiStart { source })?;
-            } else if let Ok(func) = instance.get_typed_func::<(), ()>(&mut store, "_start") {
-                func.call(&mut store, ())
-                    .map_err(|source| CreationError::WasiStart { source })?;
-            }
-        }
+    let uses_wasi = module
+        .imports()
+        .any(|import| import.module() == "wasi_snapshot_preview1");
 
-        let update = instance
-            .get_typed_func(&mut store, "update")
-            .map_err(|source| CreationError::MissingUpdateFunction { source })?;
+    if uses_wasi {
+        wasmtime_wasi::preview1::add_to_linker_sync(&mut linker, |ctx| &mut ctx.wasi)
+            .map_err(|source| CreationError::Wasi { source })?;
+    }
 
-        Ok(AutoSplitter {
-            exclusive_data: Mutex::new(ExclusiveData {
-                trapped: false,
-                store,
-                update,
-            }),
-            engine: engine.clone(),
-            settings_widgets: ArcSwap::new(settings_widgets),
-            shared_data,
-        })
+    let uses_legacy_wasi = module
+        .imports()
+        .any(|import| import.module() == "wasi_unstable");
+
+    if uses_legacy_wasi {
+        wasmtime_wasi::preview0::add_to_linker_sync(&mut linker, |ctx| &mut ctx.wasi)
+            .map_err(|source| CreationError::Wasi { source })?;
     }
+
+    let instance = linker
+        .instantiate(&mut *store, module)
+        .map_err(|source| CreationError::ModuleInstantiation { source })?;
+
+    let Some(Extern::Memory(mem)) = instance.get_export(&mut *store, "memory") else {
+        return Err(CreationError::MissingMemory);
+    };
+    store.data_mut().memory = Some(mem);
+
+    if uses_wasi
+        || uses_legacy_wasi
+        || module.get_export("_initialize").is_some()
+        || module.get_export("_start").is_some()
+    {
+        store.data_mut().timer.log_runtime(
+            format_args!("This auto splitter uses WASI. The API is subject to change, because WASI is still in preview. Auto splitters using WASI may need to be recompiled in the future."),
+            LogLevel::Warning,
+        );
+
+        // These may be different in future WASI versions.
+        if let Ok(func) = instance.get_typed_func::<(), ()>(&mut *store, "_initialize") {
+            func.call(&mut *store, ())
+                .map_err(|source| CreationError::WasiStart { source })?;
+        } else if let Ok(func) = instance.get_typed_func::<(), ()>(&mut *store, "_start") {
+            func.call(&mut *store, ())
+                .map_err(|source| CreationError::WasiStart { source })?;
+        }
+    }
+
+    let update = instance
+        .get_typed_func(&mut *store, "update")
+        .map_err(|source| CreationError::MissingUpdateFunction { source })?;
+
+    Ok(Update::Module(update))
+}
+
+fn instantiate_component<T: Timer>(
+    component: &Component,
+    store: &mut Store<Context<T>>,
+) -> Result<Update, CreationError> {
+    let mut linker = component::Linker::new(component.engine());
+    api::component::bind(&mut linker)?;
+
+    let instance = linker
+        .instantiate(&mut *store, component)
+        .map_err(|source| CreationError::ModuleInstantiation { source })?;
+
+    let bindings = api::component::AutoSplitter::new(&mut *store, &instance)
+        .map_err(|source| CreationError::MissingUpdateFunction { source })?;
+
+    store.data_mut().timer.log_runtime(
+        format_args!("This auto splitter is a WebAssembly component. The component API is subject to change, so the auto splitter may need to be recompiled in the future."),
+        LogLevel::Warning,
+    );
+
+    Ok(Update::Component(bindings))
 }
 
 impl<T: Timer> AutoSplitter<T> {
@@ -479,6 +886,7 @@ impl<T: Timer> AutoSplitter<T> {
     pub fn lock(&self) -> ExecutionGuard<'_, T> {
         ExecutionGuard {
             settings_widgets: &self.settings_widgets,
+            overlay_widgets: &self.overlay_widgets,
             data: self.exclusive_data.lock().unwrap(),
         }
     }
@@ -495,10 +903,51 @@ impl<T: Timer> AutoSplitter<T> {
     pub fn try_lock(&self) -> Option<ExecutionGuard<'_, T>> {
         Some(ExecutionGuard {
             settings_widgets: &self.settings_widgets,
+            overlay_widgets: &self.overlay_widgets,
             data: self.exclusive_data.try_lock().ok()?,
         })
     }
 
+    /// Accesses statistics about how long the recent ticks of the auto splitter
+    /// took. This may be useful for figuring out whether an auto splitter is
+    /// too demanding.
+    pub fn tick_statistics(&self) -> TickStatistics {
+        self.shared_data.tick_history.statistics()
+    }
+
+    /// Enables or disables tracing of the ticks. While enabled, a trace message
+    /// is logged for every tick, describing how long it took, how often the
+    /// auto splitter called into the runtime and how much memory it read.
+    pub fn set_tracing(&self, tracing: bool) {
+        self.shared_data.debugger.set_tracing(tracing);
+    }
+
+    /// Returns whether tracing of the ticks is enabled.
+    pub fn is_tracing(&self) -> bool {
+        self.shared_data.debugger.is_tracing()
+    }
+
+    /// Starts watching the memory range of the given length at the given
+    /// address. This replaces any previous watch at the same address. Whenever
+    /// the auto splitter reads memory overlapping the range, the bytes it read
+    /// get recorded. They can be accessed via
+    /// [`memory_watches`](Self::memory_watches).
+    pub fn watch_memory(&self, address: u64, len: usize) {
+        self.shared_data.debugger.watch(address, len);
+    }
+
+    /// Stops watching the memory range at the given address. Returns [`false`]
+    /// if no memory range was watched at that address.
+    pub fn unwatch_memory(&self, address: u64) -> bool {
+        self.shared_data.debugger.unwatch(address)
+    }
+
+    /// Accesses all the memory ranges that are being watched, including the
+    /// bytes that the auto splitter read most recently.
+    pub fn memory_watches(&self) -> Vec<MemoryWatch> {
+        self.shared_data.debugger.watches()
+    }
+
     /// Returns the duration to wait until the next execution. The auto splitter
     /// can change this tick rate on every update. You should therefore call
     /// this function after every update to sleep for the correct amount of
@@ -543,4 +992,13 @@ impl<T: Timer> AutoSplitter<T> {
     pub fn settings_widgets(&self) -> Arc<Vec<settings::Widget>> {
         self.settings_widgets.load_full()
     }
+
+    /// Accesses all the overlay widgets that the auto splitter wants to
+    /// display. Just like the settings widgets, changes from within the auto
+    /// splitter can only be perceived once the auto splitter tick is complete.
+    /// The timer additionally gets notified through
+    /// [`Timer::set_overlay_widgets`] whenever they change.
+    pub fn overlay_widgets(&self) -> Arc<Vec<overlay::Widget>> {
+        self.overlay_widgets.load_full()
+    }
 }
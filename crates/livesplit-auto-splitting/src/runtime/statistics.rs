@@ -0,0 +1,101 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+/// The amount of the most recent ticks that the statistics are based on.
+const HISTORY_LEN: usize = 1024;
+
+/// Statistics about how long the `update` function of an auto splitter takes
+/// to run. The durations are based on the most recent ticks only, so they
+/// reflect the current behavior of the auto splitter.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TickStatistics {
+    /// The total amount of ticks that the auto splitter ran for.
+    pub ticks: u64,
+    /// The median duration of a tick.
+    pub median: Duration,
+    /// The duration that 95% of the ticks didn't exceed.
+    pub p95: Duration,
+    /// The duration that 99% of the ticks didn't exceed.
+    pub p99: Duration,
+    /// The duration of the longest tick.
+    pub max: Duration,
+}
+
+#[derive(Default)]
+pub struct TickHistory {
+    inner: Mutex<History>,
+}
+
+#[derive(Default)]
+struct History {
+    ticks: u64,
+    durations: VecDeque<Duration>,
+}
+
+impl TickHistory {
+    pub fn record(&self, duration: Duration) {
+        let history = &mut *self.inner.lock().unwrap();
+        history.ticks += 1;
+        if history.durations.len() == HISTORY_LEN {
+            history.durations.pop_front();
+        }
+        history.durations.push_back(duration);
+    }
+
+    pub fn statistics(&self) -> TickStatistics {
+        let (ticks, mut durations) = {
+            let history = self.inner.lock().unwrap();
+            (history.ticks, Vec::from(history.durations.clone()))
+        };
+        durations.sort_unstable();
+
+        // This uses the nearest-rank method.
+        let percentile = |percent: usize| {
+            durations
+                .get((durations.len() * percent).div_ceil(100).saturating_sub(1))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        TickStatistics {
+            ticks,
+            median: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            max: durations.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let history = TickHistory::default();
+        assert_eq!(history.statistics(), TickStatistics::default());
+
+        for millis in (1..=100).rev() {
+            history.record(Duration::from_millis(millis));
+        }
+        let statistics = history.statistics();
+        assert_eq!(statistics.ticks, 100);
+        assert_eq!(statistics.median, Duration::from_millis(50));
+        assert_eq!(statistics.p95, Duration::from_millis(95));
+        assert_eq!(statistics.p99, Duration::from_millis(99));
+        assert_eq!(statistics.max, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn only_considers_recent_ticks() {
+        let history = TickHistory::default();
+        history.record(Duration::from_secs(1));
+        for _ in 0..HISTORY_LEN {
+            history.record(Duration::from_millis(1));
+        }
+        let statistics = history.statistics();
+        assert_eq!(statistics.ticks, HISTORY_LEN as u64 + 1);
+        assert_eq!(statistics.max, Duration::from_millis(1));
+    }
+}
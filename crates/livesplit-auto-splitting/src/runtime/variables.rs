@@ -0,0 +1,104 @@
+use indexmap::IndexMap;
+
+/// The custom variables of the timer that the auto splitter watches for
+/// changes. The values are compared against the timer's values once per tick,
+/// so changes made by any source, such as the user or the frontend, are
+/// noticed.
+#[derive(Default)]
+pub struct WatchedVariables {
+    variables: IndexMap<Box<str>, Watched>,
+}
+
+struct Watched {
+    value: Option<String>,
+    changed: bool,
+}
+
+impl WatchedVariables {
+    /// Starts watching the variable. The current value is not reported as a
+    /// change, as the auto splitter can query it directly.
+    pub fn watch(&mut self, name: &str, value: Option<String>) {
+        if !self.variables.contains_key(name) {
+            self.variables.insert(
+                name.into(),
+                Watched {
+                    value,
+                    changed: false,
+                },
+            );
+        }
+    }
+
+    pub fn unwatch(&mut self, name: &str) {
+        self.variables.shift_remove(name);
+    }
+
+    /// Compares the watched variables against their current values and marks
+    /// the ones that changed since the last time.
+    pub fn poll(&mut self, mut current: impl FnMut(&str) -> Option<String>) {
+        for (name, watched) in &mut self.variables {
+            let value = current(name);
+            if value != watched.value {
+                watched.value = value;
+                watched.changed = true;
+            }
+        }
+    }
+
+    /// Updates the value of a variable that the auto splitter set itself, so
+    /// it doesn't get notified about its own change.
+    pub fn set_by_auto_splitter(&mut self, name: &str, value: &str) {
+        if let Some(watched) = self.variables.get_mut(name) {
+            watched.value = Some(value.into());
+        }
+    }
+
+    /// Returns the name of the first variable that changed, without marking it
+    /// as seen.
+    pub fn next_changed(&self) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|(_, watched)| watched.changed)
+            .map(|(name, _)| &**name)
+    }
+
+    pub fn mark_seen(&mut self, name: &str) {
+        if let Some(watched) = self.variables.get_mut(name) {
+            watched.changed = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_changes() {
+        let mut variables = WatchedVariables::default();
+        variables.watch("target", Some("1:00".into()));
+        variables.watch("route", None);
+        variables.poll(|name| (name == "target").then(|| "1:00".into()));
+        assert_eq!(variables.next_changed(), None);
+
+        variables.poll(|name| Some(format!("{name} changed")));
+        assert_eq!(variables.next_changed(), Some("target"));
+        variables.mark_seen("target");
+        assert_eq!(variables.next_changed(), Some("route"));
+        variables.mark_seen("route");
+        assert_eq!(variables.next_changed(), None);
+
+        variables.set_by_auto_splitter("route", "own value");
+        variables.poll(|name| {
+            Some(match name {
+                "route" => "own value".into(),
+                _ => format!("{name} changed"),
+            })
+        });
+        assert_eq!(variables.next_changed(), None);
+
+        variables.unwatch("target");
+        variables.poll(|_| None);
+        assert_eq!(variables.next_changed(), Some("route"));
+    }
+}
@@ -0,0 +1,116 @@
+use bstr::ByteSlice;
+
+/// A pattern of bytes to search for in memory, where some of the bytes may be
+/// wildcards that match any byte.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature {
+    bytes: Box<[u8]>,
+    mask: Box<[bool]>,
+}
+
+impl Signature {
+    /// Parses a signature where each byte is written as two hexadecimal digits
+    /// and wildcards are written as `??`. The bytes may be separated by
+    /// whitespace, in which case a wildcard may also be written as `?`, such
+    /// as `48 8B ? ?? 05`. Returns [`None`] if the signature is invalid or
+    /// empty.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (mut bytes, mut mask) = (Vec::new(), Vec::new());
+
+        for token in text.split_ascii_whitespace() {
+            if token == "?" {
+                bytes.push(0);
+                mask.push(false);
+                continue;
+            }
+            let token = token.as_bytes();
+            if token.len() % 2 != 0 {
+                return None;
+            }
+            for pair in token.chunks_exact(2) {
+                if pair == b"??" {
+                    bytes.push(0);
+                    mask.push(false);
+                } else {
+                    let [high, low] = [pair[0], pair[1]].map(|c| (c as char).to_digit(16));
+                    bytes.push((high? << 4 | low?) as u8);
+                    mask.push(true);
+                }
+            }
+        }
+
+        if bytes.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            bytes: bytes.into(),
+            mask: mask.into(),
+        })
+    }
+
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the offset of the first match of the signature in the memory
+    /// given.
+    pub fn scan(&self, memory: &[u8]) -> Option<usize> {
+        let last_start = memory.len().checked_sub(self.len())?;
+
+        // The first byte that isn't a wildcard is searched for quickly, so the
+        // signature only needs to be compared where it can match at all.
+        let Some(anchor) = self.mask.iter().position(|&mask| mask) else {
+            return Some(0);
+        };
+        let anchor_byte = self.bytes[anchor];
+
+        let mut start = 0;
+        while start <= last_start {
+            start += memory[start + anchor..=last_start + anchor].find_byte(anchor_byte)?;
+            let matches = memory[start..][..self.len()]
+                .iter()
+                .zip(self.bytes.iter().zip(&self.mask))
+                .all(|(&byte, (&expected, &mask))| !mask || byte == expected);
+            if matches {
+                return Some(start);
+            }
+            start += 1;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let signature = Signature::parse("48 8B ?? ? 05").unwrap();
+        assert_eq!(*signature.bytes, [0x48, 0x8B, 0, 0, 0x05]);
+        assert_eq!(*signature.mask, [true, true, false, false, true]);
+        assert_eq!(
+            Signature::parse("488b??05"),
+            Signature::parse("48 8B ?? 05")
+        );
+
+        assert_eq!(Signature::parse(""), None);
+        assert_eq!(Signature::parse("48 8"), None);
+        assert_eq!(Signature::parse("48 8G"), None);
+        assert_eq!(Signature::parse("48?"), None);
+    }
+
+    #[test]
+    fn scan() {
+        let memory = [0x00, 0x48, 0x8B, 0x48, 0x8B, 0x11, 0x05, 0x48];
+        let signature = Signature::parse("48 8B ?? 05").unwrap();
+        assert_eq!(signature.scan(&memory), Some(3));
+        assert_eq!(signature.scan(&memory[..6]), None);
+        assert_eq!(Signature::parse("?? 48").unwrap().scan(&memory), Some(0));
+        assert_eq!(Signature::parse("05 48").unwrap().scan(&memory), Some(6));
+        assert_eq!(Signature::parse("48 05").unwrap().scan(&memory), None);
+        assert_eq!(Signature::parse("?? ??").unwrap().scan(&memory[..1]), None);
+    }
+}
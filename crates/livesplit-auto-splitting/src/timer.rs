@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::overlay;
+
 /// Represents the state that a timer is in.
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
@@ -69,6 +71,20 @@ pub trait Timer: Send + 'static {
     /// Sets a custom key value pair. This may be arbitrary information that the
     /// auto splitter wants to provide for visualization.
     fn set_variable(&mut self, key: &str, value: &str);
+    /// Accesses the value of a custom variable. This includes variables that
+    /// are not set by the auto splitter, such as the ones set by the user.
+    /// Returns `None` if the variable doesn't exist, which is also what timers
+    /// that don't support reading variables return.
+    fn variable(&self, key: &str) -> Option<String> {
+        let _ = key;
+        None
+    }
+    /// Sets the overlay widgets that the auto splitter wants to display. This
+    /// is called with the full list of widgets whenever any of them changes.
+    /// Timers that can't display any widgets ignore them.
+    fn set_overlay_widgets(&mut self, widgets: &[overlay::Widget]) {
+        let _ = widgets;
+    }
     /// Logs a message from the auto splitter.
     fn log_auto_splitter(&mut self, message: fmt::Arguments);
     /// Logs a message from the runtime.
@@ -35,6 +35,11 @@ impl Timer for DummyTimer {
 
 #[track_caller]
 fn compile(crate_name: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
+    compile_for_target(crate_name, "wasm32-wasip1")
+}
+
+#[track_caller]
+fn compile_for_target(crate_name: &str, target: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
     let mut path = PathBuf::from("tests");
     path.push("test-cases");
     path.push(crate_name);
@@ -43,7 +48,7 @@ fn compile(crate_name: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
         .current_dir(&path)
         .arg("build")
         .arg("--target")
-        .arg("wasm32-wasip1")
+        .arg(target)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .output()
@@ -55,7 +60,7 @@ fn compile(crate_name: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
     }
 
     path.push("target");
-    path.push("wasm32-wasip1");
+    path.push(target);
     path.push("debug");
     let wasm_path = fs::read_dir(path)
         .unwrap()
@@ -69,8 +74,17 @@ fn compile(crate_name: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
         })
         .unwrap();
 
+    instantiate(&fs::read(wasm_path).unwrap())
+}
+
+#[track_caller]
+fn compile_wat(wat: &str) -> anyhow::Result<AutoSplitter<DummyTimer>> {
+    instantiate(&wat::parse_str(wat)?)
+}
+
+fn instantiate(wasm: &[u8]) -> anyhow::Result<AutoSplitter<DummyTimer>> {
     Ok(Runtime::new(Config::default())?
-        .compile(&fs::read(wasm_path).unwrap())?
+        .compile(wasm)?
         .instantiate(DummyTimer, None, None)?)
 }
 
@@ -158,6 +172,68 @@ fn infinite_loop() {
     assert!(runtime.lock().update().is_err());
 }
 
+#[test]
+fn legacy_wasi() {
+    let auto_splitter = compile_wat(
+        r#"(module
+            (import "wasi_unstable" "clock_time_get"
+                (func $clock_time_get (param i32 i64 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "update")
+                (drop (call $clock_time_get (i32.const 0) (i64.const 1) (i32.const 0)))))"#,
+    )
+    .unwrap();
+    auto_splitter.lock().update().unwrap();
+}
+
+#[test]
+fn legacy_wasi_rejects_unknown_imports() {
+    assert!(
+        compile_wat(
+            r#"(module
+                (import "wasi_unstable" "not_a_function" (func))
+                (memory (export "memory") 1)
+                (func (export "update")))"#,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn legacy_wasi_traps() {
+    let auto_splitter = compile_wat(
+        r#"(module
+            (import "wasi_unstable" "proc_exit" (func (param i32)))
+            (memory (export "memory") 1)
+            (func (export "update")
+                unreachable))"#,
+    )
+    .unwrap();
+    assert!(auto_splitter.lock().update().is_err());
+}
+
+#[test]
+fn component() {
+    let auto_splitter = compile_for_target("component", "wasm32-wasip2").unwrap();
+    auto_splitter.lock().update().unwrap();
+    assert!(auto_splitter.lock().update().is_err());
+}
+
+#[test]
+fn component_rejects_unknown_imports() {
+    assert!(
+        compile_wat(
+            r#"(component
+                (import "livesplit:auto-splitting/not-an-interface@0.1.0" (instance
+                    (export "f" (func))))
+                (core module $m (func (export "update")))
+                (core instance $i (instantiate $m))
+                (func (export "update") (canon lift (core func $i "update"))))"#,
+        )
+        .is_err()
+    );
+}
+
 // FIXME: Test Network
 
 // FIXME: Test heavy amounts of allocations
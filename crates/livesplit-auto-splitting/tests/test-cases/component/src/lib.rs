@@ -0,0 +1,26 @@
+use std::sync::atomic::{self, AtomicBool};
+
+use livesplit::auto_splitting::timer::{self, TimerState};
+
+wit_bindgen::generate!({
+    path: "../../../wit",
+    world: "auto-splitter",
+});
+
+static UPDATED: AtomicBool = AtomicBool::new(false);
+
+struct AutoSplitter;
+
+impl Guest for AutoSplitter {
+    fn update() {
+        // The second update traps, so the test can check that traps inside
+        // of components are reported.
+        if UPDATED.swap(true, atomic::Ordering::Relaxed) {
+            panic!("trap");
+        }
+        assert!(matches!(timer::get_state(), TimerState::NotRunning));
+        timer::set_variable("key", "value");
+    }
+}
+
+export!(AutoSplitter);
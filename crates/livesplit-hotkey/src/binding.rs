@@ -0,0 +1,299 @@
+use alloc::string::String;
+use core::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Hook, Hotkey, KeyCode};
+
+/// A binding is a short sequence of hotkeys that need to be pressed one after
+/// another, such as `Ctrl + KeyK, KeyS`. Each of the hotkeys can be a chord
+/// of modifiers and a key, such as `Ctrl + Shift + KeyR`. Most bindings only
+/// consist of a single hotkey. Sequences reduce the chance of accidentally
+/// triggering an action while playing, as a single key that is also used by
+/// the game is not enough to trigger it. The [`Trigger`] of the binding
+/// further specifies how the last hotkey needs to be pressed.
+///
+/// A binding is written as the hotkeys separated by commas, followed by the
+/// trigger in parentheses, unless the hotkey simply needs to be pressed, such
+/// as `Ctrl + KeyK, KeyR (Hold 500 ms)`.
+#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Binding {
+    // The hotkeys are always stored at the beginning of the array.
+    steps: [Option<Hotkey>; Binding::MAX_STEPS],
+    trigger: Trigger,
+}
+
+/// Specifies how the last hotkey of a [`Binding`] needs to be pressed for it to
+/// trigger its action.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Default)]
+pub enum Trigger {
+    /// The action is triggered as soon as the hotkey is pressed.
+    #[default]
+    Press,
+    /// The hotkey needs to be pressed twice, with the second press following
+    /// the first one within the amount of milliseconds specified.
+    DoubleTap {
+        /// The maximum amount of milliseconds between the two presses.
+        within_millis: u32,
+    },
+    /// The hotkey needs to be held down for the amount of milliseconds
+    /// specified. The action is triggered once the hotkey was held down for
+    /// that long, without waiting for it to be released. This is useful for
+    /// actions that shouldn't be triggered by accident, such as resetting.
+    Hold {
+        /// The amount of milliseconds the hotkey needs to be held down.
+        millis: u32,
+    },
+    /// The action is triggered once the hotkey that got pressed is released
+    /// again. Some communities time their runs from the moment the key is
+    /// released rather than pressed.
+    Release,
+}
+
+impl Trigger {
+    /// The double tap trigger that is suggested by default.
+    pub const DEFAULT_DOUBLE_TAP: Self = Self::DoubleTap { within_millis: 300 };
+    /// The hold trigger that is suggested by default.
+    pub const DEFAULT_HOLD: Self = Self::Hold { millis: 1000 };
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Press => f.write_str("Press"),
+            Self::DoubleTap { within_millis } => write!(f, "Double Tap {within_millis} ms"),
+            Self::Hold { millis } => write!(f, "Hold {millis} ms"),
+            Self::Release => f.write_str("Release"),
+        }
+    }
+}
+
+impl FromStr for Trigger {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let millis = |rest: &str| {
+            rest.trim()
+                .strip_suffix("ms")
+                .ok_or(())?
+                .trim_end()
+                .parse()
+                .map_err(drop)
+        };
+
+        if s == "Press" {
+            Ok(Self::Press)
+        } else if s == "Release" {
+            Ok(Self::Release)
+        } else if let Some(rest) = s.strip_prefix("Double Tap") {
+            Ok(Self::DoubleTap {
+                within_millis: millis(rest)?,
+            })
+        } else if let Some(rest) = s.strip_prefix("Hold") {
+            Ok(Self::Hold {
+                millis: millis(rest)?,
+            })
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl Binding {
+    /// The maximum amount of hotkeys a binding can consist of.
+    pub const MAX_STEPS: usize = 4;
+
+    /// Creates a binding from a sequence of hotkeys. [`None`] is returned if
+    /// there are no hotkeys or more than [`MAX_STEPS`](Self::MAX_STEPS).
+    pub fn sequence(hotkeys: &[Hotkey]) -> Option<Self> {
+        if hotkeys.is_empty() || hotkeys.len() > Self::MAX_STEPS {
+            return None;
+        }
+        let mut steps = [None; Self::MAX_STEPS];
+        for (step, &hotkey) in steps.iter_mut().zip(hotkeys) {
+            *step = Some(hotkey);
+        }
+        Some(Self {
+            steps,
+            trigger: Trigger::Press,
+        })
+    }
+
+    /// Returns the binding with its trigger changed to the one provided.
+    pub const fn with_trigger(mut self, trigger: Trigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Returns how the last hotkey needs to be pressed.
+    pub const fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    /// Returns the hotkeys that need to be pressed one after another.
+    pub fn steps(&self) -> impl Iterator<Item = Hotkey> + '_ {
+        self.steps.iter().map_while(|&step| step)
+    }
+
+    /// Returns the hotkey that needs to be pressed first.
+    pub fn first(&self) -> Hotkey {
+        self.steps[0].unwrap()
+    }
+
+    /// Returns [`true`] if the binding consists of a single hotkey rather than
+    /// a sequence of them.
+    pub fn is_single(&self) -> bool {
+        self.steps[1].is_none()
+    }
+
+    /// Returns all the presses of hotkeys that are necessary to trigger the
+    /// binding. This is the same as the [`steps`](Self::steps), unless the
+    /// last hotkey needs to be double tapped, in which case it is pressed
+    /// twice.
+    pub fn presses(&self) -> impl Iterator<Item = Hotkey> + '_ {
+        let last = self.steps().last();
+        self.steps()
+            .chain(last.filter(|_| matches!(self.trigger, Trigger::DoubleTap { .. })))
+    }
+
+    /// Returns [`true`] if all the presses of hotkeys of the other binding
+    /// are also the first presses of this binding, regardless of how long
+    /// the hotkeys are held down. A binding starts with itself.
+    pub fn starts_with(&self, other: &Binding) -> bool {
+        let mut presses = self.presses();
+        other.presses().all(|hotkey| presses.next() == Some(hotkey))
+    }
+
+    /// Returns [`true`] if the two bindings can't be used for different
+    /// actions at the same time. This is the case if they consist of the same
+    /// presses of hotkeys or if one of them is the beginning of the other
+    /// one, as it would be unclear whether to trigger the action of the
+    /// shorter binding or to wait for the rest of the longer one.
+    pub fn conflicts_with(&self, other: &Binding) -> bool {
+        self.starts_with(other) || other.starts_with(self)
+    }
+
+    /// Resolves the hotkeys of the binding according to the current keyboard
+    /// layout. This is formatted the same way as the binding itself, but
+    /// with the keys named the way they are labeled on the user's keyboard.
+    /// See [`Hotkey::resolve`] for more information.
+    pub fn resolve(&self, hook: &Hook) -> String {
+        let mut resolved = String::new();
+        for (index, hotkey) in self.steps().enumerate() {
+            if index != 0 {
+                resolved.push_str(", ");
+            }
+            resolved.push_str(&hotkey.resolve(hook));
+        }
+        if self.trigger != Trigger::Press {
+            let _ = write!(resolved, " ({})", self.trigger);
+        }
+        resolved
+    }
+}
+
+impl fmt::Debug for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, hotkey) in self.steps().enumerate() {
+            if index != 0 {
+                f.write_str(", ")?;
+            }
+            fmt::Display::fmt(&hotkey, f)?;
+        }
+        if self.trigger != Trigger::Press {
+            write!(f, " ({})", self.trigger)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Binding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, trigger) = match s.trim_end().strip_suffix(')') {
+            Some(rest) => {
+                let (s, trigger) = rest.rsplit_once('(').ok_or(())?;
+                (s, trigger.trim().parse()?)
+            }
+            None => (s, Trigger::Press),
+        };
+
+        let mut steps = [None; Self::MAX_STEPS];
+        let mut hotkeys = s.split(',');
+        for step in &mut steps {
+            if let Some(hotkey) = hotkeys.next() {
+                *step = Some(hotkey.trim().parse()?);
+            }
+        }
+        if hotkeys.next().is_some() {
+            return Err(());
+        }
+        Ok(Self { steps, trigger })
+    }
+}
+
+impl Serialize for Binding {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.is_single() && self.trigger == Trigger::Press {
+            self.first().serialize(serializer)
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BindingVisitor)
+    }
+}
+
+struct BindingVisitor;
+
+impl serde::de::Visitor<'_> for BindingVisitor {
+    type Value = Binding;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid binding")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Binding::from_str(v).map_err(|()| serde::de::Error::custom("invalid binding"))
+    }
+}
+
+impl From<Hotkey> for Binding {
+    fn from(hotkey: Hotkey) -> Self {
+        let mut steps = [None; Self::MAX_STEPS];
+        steps[0] = Some(hotkey);
+        Self {
+            steps,
+            trigger: Trigger::Press,
+        }
+    }
+}
+
+impl From<KeyCode> for Binding {
+    fn from(key_code: KeyCode) -> Self {
+        Hotkey::from(key_code).into()
+    }
+}
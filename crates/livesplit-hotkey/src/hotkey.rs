@@ -1,8 +1,9 @@
+use alloc::{format, string::String};
 use core::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{KeyCode, Modifiers};
+use crate::{Hook, KeyCode, Modifiers};
 
 /// A hotkey is a combination of a key code and a set of modifiers.
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
@@ -13,6 +14,22 @@ pub struct Hotkey {
     pub modifiers: Modifiers,
 }
 
+impl Hotkey {
+    /// Resolves the hotkey according to the current keyboard layout. Unlike
+    /// the [`Display`](fmt::Display) implementation, which names the physical
+    /// location of the key, this names the key the way it is labeled on the
+    /// user's keyboard, such as `Ctrl + Z` for [`KeyCode::KeyY`] on a German
+    /// keyboard.
+    pub fn resolve(self, hook: &Hook) -> String {
+        let key = self.key_code.resolve(hook);
+        if self.modifiers.is_empty() {
+            key.into_owned()
+        } else {
+            format!("{} + {key}", self.modifiers)
+        }
+    }
+}
+
 impl fmt::Debug for Hotkey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(self, f)
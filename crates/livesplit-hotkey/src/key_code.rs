@@ -34,6 +34,7 @@ use serde::{Deserialize, Serialize};
 /// additional values for Gamepad support and some browser specific values.
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
 #[non_exhaustive]
+#[repr(u8)]
 pub enum KeyCode {
     /// `Backtick` and `~` on a US keyboard. This is the `半角/全角/漢字`
     /// (`hankaku/zenkaku/kanji`) key on Japanese keyboards
@@ -1157,6 +1158,7 @@ pub enum KeyCode {
     /// USB HID:
     ///  - `AC View Toggle` `Consumer Page 0x232`
     ZoomToggle,
+    // Keep `ZoomToggle` as the last variant, as `KeyCode::all` relies on it.
 }
 
 impl fmt::Debug for KeyCode {
@@ -1841,6 +1843,32 @@ impl KeyCode {
         }
     }
 
+    /// Returns [`true`] if the key is one of the modifier keys, which can be
+    /// part of a [`Hotkey`] via its [`Modifiers`].
+    pub const fn is_modifier(self) -> bool {
+        use self::KeyCode::*;
+        matches!(
+            self,
+            ShiftLeft
+                | ShiftRight
+                | ControlLeft
+                | ControlRight
+                | AltLeft
+                | AltRight
+                | MetaLeft
+                | MetaRight
+        )
+    }
+
+    /// Iterates over all the key codes.
+    #[cfg(feature = "std")]
+    pub(crate) fn all() -> impl Iterator<Item = Self> {
+        // SAFETY: The variants don't specify their discriminants, so they are
+        // numbered consecutively starting from 0, with `ZoomToggle` being the
+        // last one. The enum is `repr(u8)`, so all of these values are valid.
+        (0..=Self::ZoomToggle as u8).map(|value| unsafe { core::mem::transmute::<u8, Self>(value) })
+    }
+
     /// Resolves the key according to the current keyboard layout.
     pub fn resolve(self, hook: &Hook) -> Cow<'static, str> {
         let class = self.classify();
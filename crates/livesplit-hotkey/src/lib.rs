@@ -13,6 +13,22 @@
 //! Linux and the web via wasm-bindgen. On unsupported platforms the crate still
 //! compiles but uses a stubbed out implementation instead that never receives
 //! any hotkeys.
+//!
+//! On Linux, the hotkeys are either read directly from the input devices via
+//! evdev, if the user is allowed to, or grabbed via X11. On Wayland, X11 only
+//! receives the keys while one of its windows is focused, so with the `portal`
+//! feature enabled, the XDG GlobalShortcuts desktop portal is used there
+//! instead, if the compositor supports it. The compositor may ask the user to
+//! confirm the shortcuts or let them choose different keys for them.
+//!
+//! Apart from keyboard keys, the buttons of gamepads can be used as hotkeys
+//! too, via the `Gamepad` [`KeyCode`] variants. On Windows this is supported
+//! for XInput controllers, on Linux through evdev and on the web through the
+//! Gamepad API. macOS doesn't support them yet.
+//!
+//! MIDI devices, such as stream decks and foot pedals, can be listened to with
+//! a [`MidiHook`]. Their notes and control changes are reported just like
+//! hotkeys. This is supported on Windows and Linux.
 
 extern crate alloc;
 
@@ -38,12 +54,16 @@ cfg_if::cfg_if! {
     }
 }
 
+mod binding;
 mod hotkey;
 mod key_code;
+mod midi;
 mod modifiers;
+#[cfg(feature = "std")]
+mod registry;
 use core::fmt;
 
-pub use self::{hotkey::*, key_code::*, modifiers::*};
+pub use self::{binding::*, hotkey::*, key_code::*, midi::*, modifiers::*};
 
 /// A hook allows you to listen to hotkeys.
 #[repr(transparent)]
@@ -67,6 +87,16 @@ pub enum ConsumePreference {
     MustNotConsume,
 }
 
+/// Describes what happened to a hotkey.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HotkeyEvent {
+    /// The hotkey got pressed.
+    Pressed,
+    /// The key of the hotkey got released after the hotkey got pressed. The
+    /// modifiers may have already been released before.
+    Released,
+}
+
 impl Hook {
     /// Creates a new hook without any preference of whether the hotkeys should
     /// be consumed or not.
@@ -81,9 +111,23 @@ impl Hook {
     }
 
     /// Registers a hotkey to listen to.
-    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
+    pub fn register<F>(&self, hotkey: Hotkey, mut callback: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
+    {
+        self.0.register(hotkey, move |event| {
+            if event == HotkeyEvent::Pressed {
+                callback();
+            }
+        })
+    }
+
+    /// Registers a hotkey to listen to. Unlike with [`register`](Self::register),
+    /// the callback is not only called when the hotkey gets pressed, but also
+    /// when its key gets released again.
+    pub fn register_with_release<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
+    where
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
         self.0.register(hotkey, callback)
     }
@@ -93,6 +137,25 @@ impl Hook {
         self.0.unregister(hotkey)
     }
 
+    /// Reports the next key that gets pressed to the callback, along with the
+    /// modifiers that are held down, instead of triggering any of the
+    /// registered hotkeys. The modifier keys on their own are not reported,
+    /// they only become part of the hotkey that gets captured. This allows
+    /// settings UIs to let the user press the hotkey they want to bind.
+    /// Capturing again replaces any capture that is still in progress.
+    pub fn capture<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        self.0.capture(callback)
+    }
+
+    /// Cancels the capture that is in progress, if there is any. The callback
+    /// of the capture is not going to be called anymore.
+    pub fn cancel_capture(&self) -> Result<()> {
+        self.0.cancel_capture()
+    }
+
     /// On the web you can use this to listen to keyboard events on an
     /// additional child window as well.
     #[cfg(all(target_family = "wasm", feature = "wasm-web"))]
@@ -114,6 +177,10 @@ pub enum Error {
     AlreadyRegistered,
     /// The hotkey to unregister was not registered.
     NotRegistered,
+    /// The MIDI device could not be opened.
+    MidiDevice,
+    /// The keys can't be captured on the current platform.
+    CaptureUnsupported,
     /// A platform specific error occurred.
     Platform(platform::Error),
 }
@@ -130,6 +197,8 @@ impl fmt::Display for Error {
             }
             Self::AlreadyRegistered => "The hotkey was already registered.",
             Self::NotRegistered => "The hotkey to unregister was not registered.",
+            Self::MidiDevice => "The MIDI device could not be opened.",
+            Self::CaptureUnsupported => "The keys can't be captured on the current platform.",
             Self::Platform(e) => return fmt::Display::fmt(e, f),
         })
     }
@@ -0,0 +1,1069 @@
+//! A minimal client for the D-Bus session bus. It only supports what is
+//! necessary for talking to the desktop portals: calling methods and
+//! receiving signals. The wire format is described here:
+//! https://dbus.freedesktop.org/doc/dbus-specification.html
+
+use std::{
+    collections::VecDeque,
+    env,
+    io::{self, Read, Write},
+    os::{
+        fd::{AsRawFd, RawFd},
+        linux::net::SocketAddrExt,
+        unix::net::{SocketAddr, UnixStream},
+    },
+    time::Duration,
+};
+
+use nix::unistd::getuid;
+
+const METHOD_CALL: u8 = 1;
+const ERROR: u8 = 3;
+pub const SIGNAL: u8 = 4;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_ERROR_NAME: u8 = 4;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+// Messages are not allowed to be larger than 128 MiB.
+const MAX_MESSAGE_LEN: usize = 128 << 20;
+
+// Arrays and structs may each be nested 32 levels deep. Variants don't count
+// towards that limit in the specification, but we need to limit them as well,
+// as each of them can bring its own signature.
+const MAX_DEPTH: usize = 64;
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A value of any of the D-Bus types. Some of the types are merged, as the
+/// exact type is not relevant to us.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Double(f64),
+    /// Strings, object paths and signatures.
+    String(String),
+    Array(Vec<Value>),
+    /// Structs and dictionary entries.
+    Struct(Vec<Value>),
+    Variant(Box<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            Self::Variant(value) => value.as_str(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Self::UInt(value) => Some(value),
+            Self::Int(value) => value.try_into().ok(),
+            Self::Variant(ref value) => value.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// Looks up the value of the key in a dictionary, such as `a{sv}`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let Self::Array(entries) = self else {
+            return None;
+        };
+        entries.iter().find_map(|entry| match entry {
+            Self::Struct(pair) if pair.len() == 2 && pair[0].as_str() == Some(key) => {
+                Some(&pair[1])
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Encodes values in the D-Bus wire format. The data is expected to start at
+/// an offset that is a multiple of 8 into the message, which is the case for
+/// both the header and the body.
+#[derive(Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn pad(&mut self, alignment: usize) {
+        let len = self.buf.len().next_multiple_of(alignment);
+        self.buf.resize(len, 0);
+    }
+
+    pub fn byte(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.pad(4);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a string or an object path.
+    pub fn str(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    pub fn signature(&mut self, value: &str) {
+        self.buf.push(value.len() as u8);
+        self.buf.extend_from_slice(value.as_bytes());
+        self.buf.push(0);
+    }
+
+    /// Writes the start of a struct or a dictionary entry.
+    pub fn begin_struct(&mut self) {
+        self.pad(8);
+    }
+
+    /// Writes an array. The alignment needs to be the alignment of the type
+    /// of its elements.
+    pub fn array(&mut self, alignment: usize, elements: impl FnOnce(&mut Self)) {
+        self.u32(0);
+        let len_pos = self.buf.len() - 4;
+        self.pad(alignment);
+        let start = self.buf.len();
+        elements(self);
+        let len = (self.buf.len() - start) as u32;
+        self.buf[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+    }
+
+    /// Writes a dictionary entry of the type `{sv}` with a string as the
+    /// value.
+    pub fn str_entry(&mut self, key: &str, value: &str) {
+        self.begin_struct();
+        self.str(key);
+        self.signature("s");
+        self.str(value);
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn align(&mut self, alignment: usize) -> io::Result<()> {
+        self.pos = self.pos.next_multiple_of(alignment);
+        if self.pos > self.data.len() {
+            return Err(invalid_data("Unexpected end of the message."));
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos.wrapping_add(len))
+            .ok_or_else(|| invalid_data("Unexpected end of the message."))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn fixed<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        self.align(N)?;
+        let mut bytes: [u8; N] = self.take(N)?.try_into().unwrap();
+        if self.big_endian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        self.fixed().map(u32::from_le_bytes)
+    }
+
+    fn string(&mut self, len: usize) -> io::Result<String> {
+        let bytes = self.take(len)?;
+        self.take(1)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| invalid_data("Invalid UTF-8 in a string."))
+    }
+
+    /// Reads a value of the single complete type at the start of the
+    /// signature and returns the rest of the signature. The depth is the
+    /// amount of containers the value is nested in.
+    fn value<'s>(&mut self, signature: &'s [u8], depth: usize) -> io::Result<(Value, &'s [u8])> {
+        let (&code, rest) = signature
+            .split_first()
+            .ok_or_else(|| invalid_data("Invalid signature."))?;
+        if depth > MAX_DEPTH {
+            return Err(invalid_data("The values are nested too deeply."));
+        }
+
+        let value = match code {
+            b'y' => Value::UInt(self.take(1)?[0].into()),
+            b'b' => Value::Bool(self.u32()? != 0),
+            b'n' => Value::Int(i16::from_le_bytes(self.fixed()?).into()),
+            b'q' => Value::UInt(u16::from_le_bytes(self.fixed()?).into()),
+            b'i' => Value::Int(i32::from_le_bytes(self.fixed()?).into()),
+            b'u' | b'h' => Value::UInt(self.u32()?.into()),
+            b'x' => Value::Int(i64::from_le_bytes(self.fixed()?)),
+            b't' => Value::UInt(u64::from_le_bytes(self.fixed()?)),
+            b'd' => Value::Double(f64::from_le_bytes(self.fixed()?)),
+            b's' | b'o' => {
+                let len = self.u32()? as usize;
+                Value::String(self.string(len)?)
+            }
+            b'g' => {
+                let len = self.take(1)?[0] as usize;
+                Value::String(self.string(len)?)
+            }
+            b'v' => {
+                let len = self.take(1)?[0] as usize;
+                let signature = self.take(len)?;
+                self.take(1)?;
+                let (value, rest) = self.value(signature, depth + 1)?;
+                if !rest.is_empty() {
+                    return Err(invalid_data("Invalid signature of a variant."));
+                }
+                Value::Variant(Box::new(value))
+            }
+            b'a' => {
+                let len = self.u32()? as usize;
+                let element_len = type_len(rest)?;
+                let (element, rest) = rest.split_at(element_len);
+                self.align(alignment(element[0]))?;
+                let end = self
+                    .pos
+                    .checked_add(len)
+                    .filter(|&end| end <= self.data.len())
+                    .ok_or_else(|| invalid_data("Unexpected end of the message."))?;
+                let mut elements = Vec::new();
+                while self.pos < end {
+                    let pos = self.pos;
+                    elements.push(self.value(element, depth + 1)?.0);
+                    // Every valid element takes up some space, so this would
+                    // otherwise never end.
+                    if self.pos == pos {
+                        return Err(invalid_data("Invalid element of an array."));
+                    }
+                }
+                return Ok((Value::Array(elements), rest));
+            }
+            b'(' | b'{' => {
+                self.align(8)?;
+                let close = if code == b'(' { b')' } else { b'}' };
+                if rest.first() == Some(&close) {
+                    return Err(invalid_data("Invalid signature."));
+                }
+                let mut fields = Vec::new();
+                let mut rest = rest;
+                while rest.first() != Some(&close) {
+                    let (value, remaining) = self.value(rest, depth + 1)?;
+                    fields.push(value);
+                    rest = remaining;
+                }
+                return Ok((Value::Struct(fields), &rest[1..]));
+            }
+            _ => return Err(invalid_data("Unsupported type in a signature.")),
+        };
+
+        Ok((value, rest))
+    }
+}
+
+const fn alignment(code: u8) -> usize {
+    match code {
+        b'n' | b'q' => 2,
+        b'b' | b'i' | b'u' | b'h' | b's' | b'o' | b'a' => 4,
+        b'x' | b't' | b'd' | b'(' | b'{' => 8,
+        _ => 1,
+    }
+}
+
+/// Returns the length of the single complete type at the start of the
+/// signature.
+fn type_len(signature: &[u8]) -> io::Result<usize> {
+    match signature.first() {
+        Some(b'a') => Ok(1 + type_len(&signature[1..])?),
+        Some(&open @ (b'(' | b'{')) => {
+            let close = if open == b'(' { b')' } else { b'}' };
+            // Structs need to have at least one field.
+            if signature.get(1) == Some(&close) {
+                return Err(invalid_data("Invalid signature."));
+            }
+            let mut len = 1;
+            while signature.get(len) != Some(&close) {
+                if len >= signature.len() {
+                    return Err(invalid_data("Invalid signature."));
+                }
+                len += type_len(&signature[len..])?;
+            }
+            Ok(len + 1)
+        }
+        Some(_) => Ok(1),
+        None => Err(invalid_data("Invalid signature.")),
+    }
+}
+
+/// A message that got received from the bus.
+pub struct Message {
+    pub kind: u8,
+    pub reply_serial: Option<u32>,
+    pub path: Option<String>,
+    pub interface: Option<String>,
+    pub member: Option<String>,
+    pub error_name: Option<String>,
+    pub body: Vec<Value>,
+}
+
+impl Message {
+    /// Returns the length of the message at the start of the data, if enough
+    /// of it is available to tell.
+    fn len(data: &[u8]) -> io::Result<Option<usize>> {
+        if data.len() < 16 {
+            return Ok(None);
+        }
+        let mut reader = Reader {
+            data,
+            pos: 4,
+            big_endian: data[0] == b'B',
+        };
+        let body_len = reader.u32()? as usize;
+        reader.u32()?;
+        let fields_len = reader.u32()? as usize;
+        let len = (16 + fields_len).next_multiple_of(8) + body_len;
+        if len > MAX_MESSAGE_LEN {
+            return Err(invalid_data("The message is too large."));
+        }
+        Ok(Some(len))
+    }
+
+    fn decode(data: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader {
+            data,
+            pos: 12,
+            big_endian: data[0] == b'B',
+        };
+        let (fields, _) = reader.value(b"a(yv)", 0)?;
+        reader.align(8)?;
+        let body_data = &data[reader.pos..];
+
+        let mut message = Self {
+            kind: data[1],
+            reply_serial: None,
+            path: None,
+            interface: None,
+            member: None,
+            error_name: None,
+            body: Vec::new(),
+        };
+        let mut signature = "";
+
+        let Value::Array(fields) = &fields else {
+            unreachable!()
+        };
+        for field in fields {
+            let Value::Struct(field) = field else {
+                unreachable!()
+            };
+            let (Some(code), value) = (field[0].as_u64(), &field[1]) else {
+                continue;
+            };
+            let string = || value.as_str().map(str::to_owned);
+            match code as u8 {
+                FIELD_PATH => message.path = string(),
+                FIELD_INTERFACE => message.interface = string(),
+                FIELD_MEMBER => message.member = string(),
+                FIELD_ERROR_NAME => message.error_name = string(),
+                FIELD_REPLY_SERIAL => {
+                    message.reply_serial = value.as_u64().map(|serial| serial as u32)
+                }
+                FIELD_SIGNATURE => signature = value.as_str().unwrap_or_default(),
+                _ => {}
+            }
+        }
+
+        let mut body = Reader {
+            data: body_data,
+            pos: 0,
+            big_endian: reader.big_endian,
+        };
+        let mut signature = signature.as_bytes();
+        while !signature.is_empty() {
+            let (value, rest) = body.value(signature, 0)?;
+            message.body.push(value);
+            signature = rest;
+        }
+
+        Ok(message)
+    }
+
+    /// Returns [`true`] if the message is the error reply to the method call
+    /// with the serial specified.
+    pub fn is_error_reply(&self, serial: u32) -> bool {
+        self.kind == ERROR && self.reply_serial == Some(serial)
+    }
+
+    /// Returns [`true`] if the message is the signal specified.
+    pub fn is_signal(&self, interface: &str, member: &str) -> bool {
+        self.kind == SIGNAL
+            && self.interface.as_deref() == Some(interface)
+            && self.member.as_deref() == Some(member)
+    }
+}
+
+/// A method to call on an object of another connection to the bus.
+pub struct MethodCall<'a> {
+    pub destination: &'a str,
+    pub path: &'a str,
+    pub interface: &'a str,
+    pub member: &'a str,
+    pub signature: &'a str,
+    pub body: Vec<u8>,
+}
+
+impl MethodCall<'_> {
+    fn encode(&self, serial: u32) -> Vec<u8> {
+        let mut message = Writer::default();
+        message.byte(b'l');
+        message.byte(METHOD_CALL);
+        message.byte(0);
+        message.byte(1);
+        message.u32(self.body.len() as u32);
+        message.u32(serial);
+        message.array(8, |fields| {
+            let mut field = |code, signature, value: &str| {
+                fields.begin_struct();
+                fields.byte(code);
+                fields.signature(signature);
+                if signature == "g" {
+                    fields.signature(value);
+                } else {
+                    fields.str(value);
+                }
+            };
+            field(FIELD_PATH, "o", self.path);
+            field(FIELD_INTERFACE, "s", self.interface);
+            field(FIELD_MEMBER, "s", self.member);
+            field(FIELD_DESTINATION, "s", self.destination);
+            if !self.signature.is_empty() {
+                field(FIELD_SIGNATURE, "g", self.signature);
+            }
+        });
+        message.pad(8);
+        message.buf.extend_from_slice(&self.body);
+        message.buf
+    }
+}
+
+/// A connection to the session bus.
+pub struct Connection {
+    stream: UnixStream,
+    serial: u32,
+    buffer: Vec<u8>,
+    pending: VecDeque<Message>,
+    unique_name: String,
+}
+
+impl AsRawFd for Connection {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+impl Connection {
+    /// Connects to the session bus. Until the connection is switched to non
+    /// blocking mode, waiting for a reply or a signal times out after the
+    /// duration specified.
+    pub fn session(timeout: Duration) -> io::Result<Self> {
+        let mut stream = connect()?;
+        stream.set_read_timeout(Some(timeout))?;
+
+        // We authenticate with the credentials of our process, which are
+        // transferred with the socket.
+        write!(stream, "\0AUTH EXTERNAL ")?;
+        for byte in getuid().as_raw().to_string().bytes() {
+            write!(stream, "{byte:02x}")?;
+        }
+        stream.write_all(b"\r\n")?;
+
+        // The server doesn't send anything after its reply until we send the
+        // first message, so we can't accidentally read too much here.
+        let mut line = Vec::new();
+        while !line.ends_with(b"\r\n") {
+            let mut byte = 0;
+            stream.read_exact(std::slice::from_mut(&mut byte))?;
+            line.push(byte);
+        }
+        if !line.starts_with(b"OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Failed authenticating with the session bus.",
+            ));
+        }
+        stream.write_all(b"BEGIN\r\n")?;
+
+        let mut connection = Self {
+            stream,
+            serial: 0,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            unique_name: String::new(),
+        };
+
+        let reply = connection.call(&MethodCall {
+            destination: "org.freedesktop.DBus",
+            path: "/org/freedesktop/DBus",
+            interface: "org.freedesktop.DBus",
+            member: "Hello",
+            signature: "",
+            body: Vec::new(),
+        })?;
+        connection.unique_name = reply
+            .body
+            .first()
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_data("The bus didn't assign us a name."))?
+            .to_owned();
+
+        Ok(connection)
+    }
+
+    /// The name the bus assigned to this connection.
+    pub fn unique_name(&self) -> &str {
+        &self.unique_name
+    }
+
+    /// Switches the connection to non blocking mode or back. In blocking mode
+    /// waiting times out after the duration the connection was created with.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.stream.set_nonblocking(nonblocking)
+    }
+
+    /// Sends the method call without waiting for the reply.
+    pub fn send(&mut self, call: &MethodCall<'_>) -> io::Result<u32> {
+        self.serial += 1;
+        self.stream.write_all(&call.encode(self.serial))?;
+        Ok(self.serial)
+    }
+
+    /// Calls the method and waits for its reply. Any other messages that
+    /// arrive in the meantime are kept for later.
+    pub fn call(&mut self, call: &MethodCall<'_>) -> io::Result<Message> {
+        let serial = self.send(call)?;
+        loop {
+            let message = self.read_message()?.ok_or(io::ErrorKind::TimedOut)?;
+            if message.reply_serial == Some(serial) {
+                if message.kind == ERROR {
+                    return Err(io::Error::other(
+                        message.error_name.unwrap_or_else(|| "Unknown error".into()),
+                    ));
+                }
+                return Ok(message);
+            }
+            self.pending.push_back(message);
+        }
+    }
+
+    /// Waits for the signal to be emitted by the object at the path. Any other
+    /// messages that arrive in the meantime are kept for later.
+    pub fn wait_for_signal(
+        &mut self,
+        path: &str,
+        interface: &str,
+        member: &str,
+    ) -> io::Result<Message> {
+        let is_match = |message: &Message| {
+            message.is_signal(interface, member) && message.path.as_deref() == Some(path)
+        };
+        if let Some(index) = self.pending.iter().position(is_match) {
+            return Ok(self.pending.remove(index).unwrap());
+        }
+        loop {
+            let message = self.read_message()?.ok_or(io::ErrorKind::TimedOut)?;
+            if is_match(&message) {
+                return Ok(message);
+            }
+            self.pending.push_back(message);
+        }
+    }
+
+    /// Receives the next message. In non blocking mode [`None`] is returned
+    /// once there are no more messages available.
+    pub fn receive(&mut self) -> io::Result<Option<Message>> {
+        if let Some(message) = self.pending.pop_front() {
+            return Ok(Some(message));
+        }
+        self.read_message()
+    }
+
+    fn read_message(&mut self) -> io::Result<Option<Message>> {
+        loop {
+            if let Some(len) = Message::len(&self.buffer)?
+                && self.buffer.len() >= len
+            {
+                let message = Message::decode(&self.buffer[..len]);
+                self.buffer.drain(..len);
+                return message.map(Some);
+            }
+
+            let mut chunk = [0; 4096];
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(len) => self.buffer.extend_from_slice(&chunk[..len]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Connects to the first of the addresses of the session bus that works.
+fn connect() -> io::Result<UnixStream> {
+    let Some(addresses) = env::var_os("DBUS_SESSION_BUS_ADDRESS") else {
+        let runtime_dir = env::var_os("XDG_RUNTIME_DIR").ok_or(io::ErrorKind::NotFound)?;
+        return UnixStream::connect(std::path::Path::new(&runtime_dir).join("bus"));
+    };
+    let addresses = addresses
+        .into_string()
+        .map_err(|_| invalid_data("Invalid address of the session bus."))?;
+
+    let mut result = Err(io::ErrorKind::NotFound.into());
+    for address in addresses.split(';') {
+        let Some(options) = address.strip_prefix("unix:") else {
+            continue;
+        };
+        for option in options.split(',') {
+            let Some((key, value)) = option.split_once('=') else {
+                continue;
+            };
+            let value = unescape(value);
+            result = match key {
+                "path" => UnixStream::connect(String::from_utf8_lossy(&value).as_ref()),
+                "abstract" => SocketAddr::from_abstract_name(&value)
+                    .and_then(|address| UnixStream::connect_addr(&address)),
+                _ => continue,
+            };
+            if result.is_ok() {
+                return result;
+            }
+        }
+    }
+    result
+}
+
+/// Undoes the percent encoding of the values of the addresses.
+fn unescape(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, remaining)) = rest.split_first() {
+        rest = remaining;
+        if byte == b'%'
+            && let Some(hex) = rest.get(..2)
+            && let Ok(hex) = std::str::from_utf8(hex)
+            && let Ok(decoded) = u8::from_str_radix(hex, 16)
+        {
+            bytes.push(decoded);
+            rest = &rest[2..];
+        } else {
+            bytes.push(byte);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_calls_can_be_decoded() {
+        let mut body = Writer::default();
+        body.str("/session");
+        body.array(8, |shortcuts| {
+            shortcuts.begin_struct();
+            shortcuts.str("Ctrl + KeyA");
+            shortcuts.array(8, |options| {
+                options.str_entry("description", "Split");
+                options.str_entry("preferred_trigger", "CTRL+a");
+            });
+        });
+        body.str("");
+        body.array(8, |_| {});
+
+        let data = MethodCall {
+            destination: "org.freedesktop.portal.Desktop",
+            path: "/org/freedesktop/portal/desktop",
+            interface: "org.freedesktop.portal.GlobalShortcuts",
+            member: "BindShortcuts",
+            signature: "oa(sa{sv})sa{sv}",
+            body: body.into_inner(),
+        }
+        .encode(7);
+
+        assert_eq!(Message::len(&data).unwrap(), Some(data.len()));
+        let message = Message::decode(&data).unwrap();
+        assert_eq!(message.kind, METHOD_CALL);
+        assert_eq!(message.member.as_deref(), Some("BindShortcuts"));
+
+        let string = |s: &str| Value::String(s.into());
+        let entry = |key: &str, value: &str| {
+            Value::Struct(vec![string(key), Value::Variant(Box::new(string(value)))])
+        };
+        let options = Value::Array(vec![
+            entry("description", "Split"),
+            entry("preferred_trigger", "CTRL+a"),
+        ]);
+        assert_eq!(
+            options.get("preferred_trigger").and_then(Value::as_str),
+            Some("CTRL+a")
+        );
+        assert_eq!(
+            message.body,
+            [
+                string("/session"),
+                Value::Array(vec![Value::Struct(vec![string("Ctrl + KeyA"), options])]),
+                string(""),
+                Value::Array(vec![]),
+            ],
+        );
+    }
+
+    fn message_with_body(signature: &str, body: Writer) -> Vec<u8> {
+        MethodCall {
+            destination: "org.freedesktop.portal.Desktop",
+            path: "/org/freedesktop/portal/desktop",
+            interface: "org.freedesktop.portal.GlobalShortcuts",
+            member: "BindShortcuts",
+            signature,
+            body: body.into_inner(),
+        }
+        .encode(7)
+    }
+
+    #[test]
+    fn empty_structs_are_rejected() {
+        let mut body = Writer::default();
+        body.u32(8);
+        body.u32(0);
+        body.u32(0);
+        assert!(Message::decode(&message_with_body("a()", body)).is_err());
+
+        let mut body = Writer::default();
+        body.signature("()");
+        body.u32(0);
+        assert!(Message::decode(&message_with_body("v", body)).is_err());
+    }
+
+    #[test]
+    fn deeply_nested_variants_are_rejected() {
+        let mut body = Writer::default();
+        for _ in 0..=MAX_DEPTH {
+            body.signature("v");
+        }
+        body.signature("s");
+        body.str("");
+        assert!(Message::decode(&message_with_body("v", body)).is_err());
+    }
+
+    /// A xorshift generator, so that the randomized tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    /// An encoder for values of any type in either byte order. The writer of
+    /// the connection only encodes what the portal needs.
+    struct Encoder {
+        buf: Vec<u8>,
+        big_endian: bool,
+    }
+
+    impl Encoder {
+        fn pad(&mut self, alignment: usize) {
+            let len = self.buf.len().next_multiple_of(alignment);
+            self.buf.resize(len, 0);
+        }
+
+        fn fixed<const N: usize>(&mut self, mut bytes: [u8; N]) {
+            self.pad(N);
+            if self.big_endian {
+                bytes.reverse();
+            }
+            self.buf.extend_from_slice(&bytes);
+        }
+
+        fn u32(&mut self, value: u32) {
+            self.fixed(value.to_le_bytes());
+        }
+
+        fn str(&mut self, value: &str) {
+            self.u32(value.len() as u32);
+            self.buf.extend_from_slice(value.as_bytes());
+            self.buf.push(0);
+        }
+
+        fn signature(&mut self, value: &str) {
+            self.buf.push(value.len() as u8);
+            self.buf.extend_from_slice(value.as_bytes());
+            self.buf.push(0);
+        }
+
+        fn array(&mut self, alignment: usize, elements: impl FnOnce(&mut Self)) {
+            self.u32(0);
+            let len_pos = self.buf.len() - 4;
+            self.pad(alignment);
+            let start = self.buf.len();
+            elements(self);
+            let mut len = ((self.buf.len() - start) as u32).to_le_bytes();
+            if self.big_endian {
+                len.reverse();
+            }
+            self.buf[len_pos..len_pos + 4].copy_from_slice(&len);
+        }
+    }
+
+    const BASIC_TYPES: &[u8] = b"ybnqiuxtdsogh";
+
+    fn random_basic_type(rng: &mut Rng) -> char {
+        BASIC_TYPES[rng.below(BASIC_TYPES.len() as u64) as usize] as char
+    }
+
+    /// Appends a random single complete type to the signature.
+    fn random_type(rng: &mut Rng, depth: usize, signature: &mut String) {
+        match if depth < 3 { rng.below(8) } else { 0 } {
+            0..=3 => signature.push(random_basic_type(rng)),
+            4 => signature.push('v'),
+            5 => {
+                signature.push('a');
+                random_type(rng, depth + 1, signature);
+            }
+            6 => {
+                signature.push_str("a{");
+                signature.push(random_basic_type(rng));
+                random_type(rng, depth + 1, signature);
+                signature.push('}');
+            }
+            _ => {
+                signature.push('(');
+                for _ in 0..=rng.below(3) {
+                    random_type(rng, depth + 1, signature);
+                }
+                signature.push(')');
+            }
+        }
+    }
+
+    fn random_string(rng: &mut Rng) -> String {
+        const CHARS: &[char] = &['a', 'Z', '0', '/', ' ', 'ä', '🎮'];
+        (0..rng.below(8))
+            .map(|_| CHARS[rng.below(CHARS.len() as u64) as usize])
+            .collect()
+    }
+
+    /// Encodes a random value of the single complete type at the start of the
+    /// signature and returns it together with the rest of the signature.
+    fn random_value<'s>(
+        rng: &mut Rng,
+        encoder: &mut Encoder,
+        signature: &'s [u8],
+        depth: usize,
+    ) -> (Value, &'s [u8]) {
+        let (&code, rest) = signature.split_first().unwrap();
+        let value = match code {
+            b'y' => {
+                let value = rng.next() as u8;
+                encoder.buf.push(value);
+                Value::UInt(value.into())
+            }
+            b'b' => {
+                let value = rng.below(2) == 1;
+                encoder.u32(value as u32);
+                Value::Bool(value)
+            }
+            b'n' => {
+                let value = rng.next() as i16;
+                encoder.fixed(value.to_le_bytes());
+                Value::Int(value.into())
+            }
+            b'q' => {
+                let value = rng.next() as u16;
+                encoder.fixed(value.to_le_bytes());
+                Value::UInt(value.into())
+            }
+            b'i' => {
+                let value = rng.next() as i32;
+                encoder.fixed(value.to_le_bytes());
+                Value::Int(value.into())
+            }
+            b'u' | b'h' => {
+                let value = rng.next() as u32;
+                encoder.u32(value);
+                Value::UInt(value.into())
+            }
+            b'x' => {
+                let value = rng.next() as i64;
+                encoder.fixed(value.to_le_bytes());
+                Value::Int(value)
+            }
+            b't' => {
+                let value = rng.next();
+                encoder.fixed(value.to_le_bytes());
+                Value::UInt(value)
+            }
+            b'd' => {
+                let value = rng.next() as i64 as f64 / 7.0;
+                encoder.fixed(value.to_le_bytes());
+                Value::Double(value)
+            }
+            b's' | b'o' => {
+                let value = random_string(rng);
+                encoder.str(&value);
+                Value::String(value)
+            }
+            b'g' => {
+                let mut value = String::new();
+                random_type(rng, depth, &mut value);
+                encoder.signature(&value);
+                Value::String(value)
+            }
+            b'v' => {
+                let mut signature = String::new();
+                random_type(rng, depth + 1, &mut signature);
+                encoder.signature(&signature);
+                let (value, _) = random_value(rng, encoder, signature.as_bytes(), depth + 1);
+                Value::Variant(Box::new(value))
+            }
+            b'a' => {
+                let (element, rest) = rest.split_at(type_len(rest).unwrap());
+                let mut elements = Vec::new();
+                encoder.array(alignment(element[0]), |encoder| {
+                    for _ in 0..rng.below(4) {
+                        elements.push(random_value(rng, encoder, element, depth + 1).0);
+                    }
+                });
+                return (Value::Array(elements), rest);
+            }
+            b'(' | b'{' => {
+                encoder.pad(8);
+                let close = if code == b'(' { b')' } else { b'}' };
+                let mut fields = Vec::new();
+                let mut rest = rest;
+                while rest[0] != close {
+                    let (value, remaining) = random_value(rng, encoder, rest, depth + 1);
+                    fields.push(value);
+                    rest = remaining;
+                }
+                return (Value::Struct(fields), &rest[1..]);
+            }
+            _ => unreachable!(),
+        };
+        (value, rest)
+    }
+
+    /// Encodes a signal with a random body and returns it together with the
+    /// values of the body.
+    fn random_message(rng: &mut Rng, big_endian: bool) -> (Vec<u8>, Vec<Value>) {
+        let mut signature = String::new();
+        for _ in 0..rng.below(4) {
+            random_type(rng, 0, &mut signature);
+        }
+
+        let mut body = Encoder {
+            buf: Vec::new(),
+            big_endian,
+        };
+        let mut values = Vec::new();
+        let mut rest = signature.as_bytes();
+        while !rest.is_empty() {
+            let (value, remaining) = random_value(rng, &mut body, rest, 0);
+            values.push(value);
+            rest = remaining;
+        }
+
+        let mut message = Encoder {
+            buf: Vec::new(),
+            big_endian,
+        };
+        let endianness = if big_endian { b'B' } else { b'l' };
+        message.buf.extend([endianness, SIGNAL, 0, 1]);
+        message.u32(body.buf.len() as u32);
+        message.u32(rng.next() as u32 | 1);
+        message.array(8, |fields| {
+            fields.pad(8);
+            fields.buf.extend([FIELD_MEMBER, 1, b's', 0]);
+            fields.str("Activated");
+            fields.pad(8);
+            fields.buf.extend([FIELD_SIGNATURE, 1, b'g', 0]);
+            fields.signature(&signature);
+        });
+        message.pad(8);
+        message.buf.extend(body.buf);
+
+        (message.buf, values)
+    }
+
+    #[test]
+    fn random_messages_roundtrip() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for i in 0..2000 {
+            let (data, body) = random_message(&mut rng, i % 2 == 1);
+            assert_eq!(Message::len(&data).unwrap(), Some(data.len()));
+            let message = Message::decode(&data).unwrap();
+            assert_eq!(message.kind, SIGNAL);
+            assert_eq!(message.member.as_deref(), Some("Activated"));
+            assert_eq!(message.body, body);
+        }
+    }
+
+    #[test]
+    fn corrupted_messages_are_handled() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for i in 0..20000 {
+            let (mut data, _) = random_message(&mut rng, i % 2 == 1);
+            for _ in 0..=rng.below(4) {
+                let index = rng.below(data.len() as u64) as usize;
+                data[index] = rng.next() as u8;
+            }
+            if rng.below(4) == 0 {
+                data.truncate(rng.below(data.len() as u64) as usize);
+            }
+
+            // This mirrors how the connection reads messages. Decoding may
+            // fail, but it must not panic.
+            if let Ok(Some(len)) = Message::len(&data)
+                && let Some(data) = data.get(..len)
+            {
+                let _ = Message::decode(data);
+            }
+        }
+    }
+
+    #[test]
+    fn addresses_are_unescaped() {
+        assert_eq!(unescape("/tmp/dbus-%41b%2c"), b"/tmp/dbus-Ab,");
+        assert_eq!(unescape("100%"), b"100%");
+    }
+}
@@ -1,11 +1,14 @@
-use std::{collections::hash_map::HashMap, os::unix::prelude::AsRawFd, ptr, thread};
+use std::{os::unix::prelude::AsRawFd, ptr, thread};
 
 use evdev::{Device, EventType, KeyCode as Key};
 use mio::{Events, Interest, Poll, Token, Waker, unix::SourceFd};
 use x11_dl::xlib::{_XDisplay, Xlib};
 
 use super::{Error, Hook, Message, x11_impl};
-use crate::{KeyCode, Modifiers, Result};
+use crate::{
+    KeyCode, Modifiers, Result,
+    registry::{self, Registry},
+};
 
 // Low numbered tokens are allocated to devices.
 const PING_TOKEN: Token = Token(usize::MAX);
@@ -235,6 +238,19 @@ pub const fn code_for(key: KeyCode) -> Option<Key> {
     })
 }
 
+impl registry::Key for Key {
+    fn key_code(self) -> Option<KeyCode> {
+        KeyCode::all().find(|&key_code| code_for(key_code) == Some(self))
+    }
+}
+
+/// Returns whether the device is a gamepad rather than a keyboard.
+pub fn is_gamepad(device: &Device) -> bool {
+    device
+        .supported_keys()
+        .is_some_and(|keys| keys.contains(Key::BTN_SOUTH))
+}
+
 pub fn new() -> Result<Hook> {
     let (sender, receiver) = crossbeam_channel::unbounded();
     let mut poll = Poll::new().map_err(|_| Error::EPoll)?;
@@ -254,7 +270,7 @@ pub fn new() -> Result<Hook> {
     let join_handle = thread::spawn(move || -> Result<()> {
         let mut result = Ok(());
         let mut events = Events::with_capacity(1024);
-        let mut hotkeys: HashMap<(Key, Modifiers), Box<dyn FnMut() + Send>> = HashMap::new();
+        let mut hotkeys = Registry::new();
         let mut modifiers = Modifiers::empty();
 
         let (mut xlib, mut display) = (None, None);
@@ -275,9 +291,7 @@ pub fn new() -> Result<Hook> {
                             const PRESSED: i32 = 1;
                             match ev.value() {
                                 PRESSED => {
-                                    if let Some(callback) = hotkeys.get_mut(&(k, modifiers)) {
-                                        callback();
-                                    }
+                                    hotkeys.press(k, modifiers);
                                     match k {
                                         Key::KEY_LEFTALT | Key::KEY_RIGHTALT => {
                                             modifiers.insert(Modifiers::ALT);
@@ -294,21 +308,24 @@ pub fn new() -> Result<Hook> {
                                         _ => {}
                                     }
                                 }
-                                RELEASED => match k {
-                                    Key::KEY_LEFTALT | Key::KEY_RIGHTALT => {
-                                        modifiers.remove(Modifiers::ALT);
-                                    }
-                                    Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => {
-                                        modifiers.remove(Modifiers::CONTROL);
-                                    }
-                                    Key::KEY_LEFTMETA | Key::KEY_RIGHTMETA => {
-                                        modifiers.remove(Modifiers::META);
-                                    }
-                                    Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => {
-                                        modifiers.remove(Modifiers::SHIFT);
+                                RELEASED => {
+                                    hotkeys.release(k);
+                                    match k {
+                                        Key::KEY_LEFTALT | Key::KEY_RIGHTALT => {
+                                            modifiers.remove(Modifiers::ALT);
+                                        }
+                                        Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => {
+                                            modifiers.remove(Modifiers::CONTROL);
+                                        }
+                                        Key::KEY_LEFTMETA | Key::KEY_RIGHTMETA => {
+                                            modifiers.remove(Modifiers::META);
+                                        }
+                                        Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => {
+                                            modifiers.remove(Modifiers::SHIFT);
+                                        }
+                                        _ => {}
                                     }
-                                    _ => {}
-                                },
+                                }
                                 _ => {} // Ignore repeating
                             }
                         }
@@ -317,25 +334,27 @@ pub fn new() -> Result<Hook> {
                     for message in receiver.try_iter() {
                         match message {
                             Message::Register(key, callback, promise) => {
-                                promise.set(
-                                    if code_for(key.key_code)
-                                        .and_then(|k| hotkeys.insert((k, key.modifiers), callback))
-                                        .is_some()
-                                    {
-                                        Err(crate::Error::AlreadyRegistered)
-                                    } else {
-                                        Ok(())
-                                    },
-                                );
+                                promise.set(if let Some(k) = code_for(key.key_code) {
+                                    hotkeys.register(k, key.modifiers, callback)
+                                } else {
+                                    Ok(())
+                                });
                             }
                             Message::Unregister(key, promise) => promise.set(
                                 code_for(key.key_code)
-                                    .and_then(|k| hotkeys.remove(&(k, key.modifiers)).map(drop))
-                                    .ok_or(crate::Error::NotRegistered),
+                                    .ok_or(crate::Error::NotRegistered)
+                                    .and_then(|k| hotkeys.unregister(k, key.modifiers)),
                             ),
                             Message::Resolve(key_code, promise) => {
                                 promise.set(resolve(&mut xlib, &mut display, key_code))
                             }
+                            Message::Capture(callback, promise) => {
+                                match callback {
+                                    Some(callback) => hotkeys.capture(callback),
+                                    None => hotkeys.cancel_capture(),
+                                }
+                                promise.set(Ok(()));
+                            }
                             Message::End => {
                                 break 'event_loop;
                             }
@@ -361,7 +380,7 @@ pub fn new() -> Result<Hook> {
     })
 }
 
-fn resolve(
+pub fn resolve(
     xlib: &mut Option<Xlib>,
     display: &mut Option<*mut _XDisplay>,
     key_code: KeyCode,
@@ -1,12 +1,21 @@
+#[cfg(feature = "portal")]
+use std::env;
 use std::{fmt, thread::JoinHandle};
 
-use crate::{ConsumePreference, Hotkey, KeyCode, Result};
+use crate::{
+    ConsumePreference, Hotkey, HotkeyEvent, KeyCode, Result,
+    registry::{Callback, CaptureCallback},
+};
 use crossbeam_channel::Sender;
 use mio::Waker;
 use nix::unistd::{Group, getgroups};
 use promising_future::{Promise, future_promise};
 
+#[cfg(feature = "portal")]
+mod dbus;
 mod evdev_impl;
+#[cfg(feature = "portal")]
+mod portal_impl;
 mod x11_impl;
 
 #[derive(Debug, Copy, Clone)]
@@ -16,6 +25,7 @@ pub enum Error {
     EPoll,
     NoXLib,
     OpenXServerConnection,
+    Portal,
     ThreadStopped,
 }
 
@@ -32,19 +42,19 @@ impl fmt::Display for Error {
             Self::EPoll => "Failed polling the event file descriptors.",
             Self::NoXLib => "Failed dynamically linking to X11.",
             Self::OpenXServerConnection => "Failed opening a connection to the X11 server.",
+            Self::Portal => "Failed communicating with the global shortcuts portal.",
             Self::ThreadStopped => "The background thread stopped unexpectedly.",
         })
     }
 }
 
 enum Message {
-    Register(
-        Hotkey,
-        Box<dyn FnMut() + Send + 'static>,
-        Promise<Result<()>>,
-    ),
+    Register(Hotkey, Callback, Promise<Result<()>>),
     Unregister(Hotkey, Promise<Result<()>>),
     Resolve(KeyCode, Promise<Option<char>>),
+    /// Starts capturing the next key, or cancels the capture if there is no
+    /// callback.
+    Capture(Option<CaptureCallback>, Promise<Result<()>>),
     End,
 }
 
@@ -72,6 +82,25 @@ fn can_use_evdev() -> Option<()> {
 
 impl Hook {
     pub fn new(consume: ConsumePreference) -> Result<Self> {
+        // On Wayland, X11 only receives the keys while one of its windows is
+        // focused, so we need to ask the compositor for global shortcuts via
+        // the portal instead. The compositor consumes the shortcuts, so evdev
+        // is still preferred if the keys shouldn't be consumed. If the portal
+        // isn't available, we fall back to the other backends.
+        #[cfg(feature = "portal")]
+        if env::var_os("WAYLAND_DISPLAY").is_some()
+            && match consume {
+                ConsumePreference::PreferConsume | ConsumePreference::MustConsume => true,
+                ConsumePreference::NoPreference | ConsumePreference::PreferNoConsume => {
+                    can_use_evdev().is_none()
+                }
+                ConsumePreference::MustNotConsume => false,
+            }
+            && let Ok(portal) = portal_impl::new()
+        {
+            return Ok(portal);
+        }
+
         if matches!(consume, ConsumePreference::PreferConsume)
             && let Ok(x11) = x11_impl::new()
         {
@@ -92,7 +121,7 @@ impl Hook {
 
     pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
-        F: FnMut() + Send + 'static,
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
         let (future, promise) = future_promise();
 
@@ -117,6 +146,29 @@ impl Hook {
         future.value().ok_or(Error::ThreadStopped)?
     }
 
+    pub fn capture<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        self.send_capture(Some(Box::new(callback)))
+    }
+
+    pub fn cancel_capture(&self) -> Result<()> {
+        self.send_capture(None)
+    }
+
+    fn send_capture(&self, callback: Option<CaptureCallback>) -> Result<()> {
+        let (future, promise) = future_promise();
+
+        self.sender
+            .send(Message::Capture(callback, promise))
+            .map_err(|_| Error::ThreadStopped)?;
+
+        self.waker.wake().map_err(|_| Error::ThreadStopped)?;
+
+        future.value().ok_or(Error::ThreadStopped)?
+    }
+
     pub fn try_resolve(&self, key_code: KeyCode) -> Option<String> {
         let (future, promise) = future_promise();
 
@@ -0,0 +1,457 @@
+use std::{
+    os::unix::prelude::AsRawFd,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use mio::{Events, Interest, Poll, Token, Waker, unix::SourceFd};
+
+use super::{
+    Error, Hook, Message,
+    dbus::{Connection, MethodCall, Value, Writer},
+    evdev_impl,
+};
+use crate::{Hotkey, KeyCode, Modifiers, Result, registry::Registry};
+
+const DBUS_TOKEN: Token = Token(0);
+const PING_TOKEN: Token = Token(1);
+
+const PORTAL: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const GLOBAL_SHORTCUTS: &str = "org.freedesktop.portal.GlobalShortcuts";
+const REQUEST: &str = "org.freedesktop.portal.Request";
+
+// Setting up the session doesn't involve the user, so the portal should
+// respond quickly. If it doesn't, we rather fall back to the other backends.
+const SETUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Binding the shortcuts may show a dialog to the user, so the hotkeys that get
+// registered in quick succession are bound all at once.
+const BIND_DELAY: Duration = Duration::from_millis(100);
+
+/// Returns the XKB name of the key's symbol on a US keyboard, which is how
+/// the portal expects the keys of the triggers to be named. The compositor
+/// may resolve them according to the actual keyboard layout, but as the user
+/// can choose different triggers anyway, this is good enough.
+fn key_name(key_code: KeyCode) -> Option<&'static str> {
+    use self::KeyCode::*;
+    let name = key_code.name();
+    Some(match key_code {
+        KeyA | KeyB | KeyC | KeyD | KeyE | KeyF | KeyG | KeyH | KeyI | KeyJ | KeyK | KeyL
+        | KeyM | KeyN | KeyO | KeyP | KeyQ | KeyR | KeyS | KeyT | KeyU | KeyV | KeyW | KeyX
+        | KeyY | KeyZ => {
+            const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+            let index = (name.as_bytes()[3] - b'A') as usize;
+            &LETTERS[index..index + 1]
+        }
+        Digit0 | Digit1 | Digit2 | Digit3 | Digit4 | Digit5 | Digit6 | Digit7 | Digit8 | Digit9 => {
+            &name[5..]
+        }
+        F1 | F2 | F3 | F4 | F5 | F6 | F7 | F8 | F9 | F10 | F11 | F12 | F13 | F14 | F15 | F16
+        | F17 | F18 | F19 | F20 | F21 | F22 | F23 | F24 => name,
+        Numpad0 => "KP_0",
+        Numpad1 => "KP_1",
+        Numpad2 => "KP_2",
+        Numpad3 => "KP_3",
+        Numpad4 => "KP_4",
+        Numpad5 => "KP_5",
+        Numpad6 => "KP_6",
+        Numpad7 => "KP_7",
+        Numpad8 => "KP_8",
+        Numpad9 => "KP_9",
+        NumpadAdd => "KP_Add",
+        NumpadSubtract => "KP_Subtract",
+        NumpadMultiply => "KP_Multiply",
+        NumpadDivide => "KP_Divide",
+        NumpadDecimal => "KP_Decimal",
+        NumpadEnter => "KP_Enter",
+        Escape => "Escape",
+        Tab => "Tab",
+        Space => "space",
+        Enter => "Return",
+        Backspace => "BackSpace",
+        Insert => "Insert",
+        Delete => "Delete",
+        Home => "Home",
+        End => "End",
+        PageUp => "Page_Up",
+        PageDown => "Page_Down",
+        ArrowUp => "Up",
+        ArrowDown => "Down",
+        ArrowLeft => "Left",
+        ArrowRight => "Right",
+        Pause => "Pause",
+        PrintScreen => "Print",
+        ScrollLock => "Scroll_Lock",
+        Minus => "minus",
+        Equal => "equal",
+        Comma => "comma",
+        Period => "period",
+        Slash => "slash",
+        Semicolon => "semicolon",
+        Quote => "apostrophe",
+        Backquote => "grave",
+        BracketLeft => "bracketleft",
+        BracketRight => "bracketright",
+        Backslash => "backslash",
+        _ => return None,
+    })
+}
+
+/// Describes the hotkey in the format of the XDG shortcuts specification,
+/// such as `CTRL+SHIFT+a`.
+fn preferred_trigger(hotkey: Hotkey) -> Option<String> {
+    let mut trigger = String::new();
+    for (modifier, name) in [
+        (Modifiers::CONTROL, "CTRL+"),
+        (Modifiers::ALT, "ALT+"),
+        (Modifiers::SHIFT, "SHIFT+"),
+        (Modifiers::META, "LOGO+"),
+    ] {
+        if hotkey.modifiers.contains(modifier) {
+            trigger.push_str(name);
+        }
+    }
+    trigger.push_str(key_name(hotkey.key_code)?);
+    Some(trigger)
+}
+
+/// The path of the request object the portal creates for the token.
+fn request_path(connection: &Connection, token: &str) -> String {
+    let sender = connection.unique_name().trim_start_matches(':');
+    format!("{PORTAL_PATH}/request/{}/{token}", sender.replace('.', "_"))
+}
+
+fn add_match(connection: &mut Connection, rule: &str) -> std::io::Result<()> {
+    let mut body = Writer::default();
+    body.str(rule);
+    connection.call(&MethodCall {
+        destination: "org.freedesktop.DBus",
+        path: "/org/freedesktop/DBus",
+        interface: "org.freedesktop.DBus",
+        member: "AddMatch",
+        signature: "s",
+        body: body.into_inner(),
+    })?;
+    Ok(())
+}
+
+/// Checks whether the portal supports global shortcuts at all and subscribes
+/// to the signals of the portal.
+fn subscribe(connection: &mut Connection) -> std::io::Result<()> {
+    let mut body = Writer::default();
+    body.str(GLOBAL_SHORTCUTS);
+    body.str("version");
+    connection.call(&MethodCall {
+        destination: PORTAL,
+        path: PORTAL_PATH,
+        interface: "org.freedesktop.DBus.Properties",
+        member: "Get",
+        signature: "ss",
+        body: body.into_inner(),
+    })?;
+
+    // We subscribe before making any requests, so we can't miss any of the
+    // responses.
+    add_match(
+        connection,
+        &format!("type='signal',sender='{PORTAL}',interface='{REQUEST}',member='Response'"),
+    )?;
+    add_match(
+        connection,
+        &format!("type='signal',sender='{PORTAL}',interface='{GLOBAL_SHORTCUTS}'"),
+    )
+}
+
+/// Creates a new session and waits for the portal to respond, so the
+/// connection needs to be in blocking mode.
+fn create_session(connection: &mut Connection, request: &mut u32) -> std::io::Result<String> {
+    *request += 1;
+    let token = format!("livesplit_session_{request}");
+    let mut body = Writer::default();
+    body.array(8, |options| {
+        options.str_entry("handle_token", &token);
+        options.str_entry("session_handle_token", &token);
+    });
+    connection.call(&MethodCall {
+        destination: PORTAL,
+        path: PORTAL_PATH,
+        interface: GLOBAL_SHORTCUTS,
+        member: "CreateSession",
+        signature: "a{sv}",
+        body: body.into_inner(),
+    })?;
+
+    let path = request_path(connection, &token);
+    let response = connection.wait_for_signal(&path, REQUEST, "Response")?;
+    match &response.body[..] {
+        [code, results] if code.as_u64() == Some(0) => results
+            .get("session_handle")
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .ok_or_else(|| std::io::ErrorKind::InvalidData.into()),
+        _ => Err(std::io::ErrorKind::PermissionDenied.into()),
+    }
+}
+
+/// Closing a session removes its shortcuts again.
+fn close_session(connection: &mut Connection, session: &str) -> std::io::Result<()> {
+    connection.send(&MethodCall {
+        destination: PORTAL,
+        path: session,
+        interface: "org.freedesktop.portal.Session",
+        member: "Close",
+        signature: "",
+        body: Vec::new(),
+    })?;
+    Ok(())
+}
+
+/// A request for binding the shortcuts that the portal didn't respond to yet.
+struct PendingBind {
+    /// The serial of the method call, which an error gets sent in reply to.
+    serial: u32,
+    /// The path of the request object, which emits the response.
+    path: String,
+}
+
+/// Asks the portal to bind the shortcuts to the session. This may show a
+/// dialog to the user, so the response isn't waited for.
+fn bind_shortcuts(
+    connection: &mut Connection,
+    session: &str,
+    hotkeys: &[Hotkey],
+    request: &mut u32,
+) -> std::io::Result<PendingBind> {
+    *request += 1;
+    let token = format!("livesplit_bind_{request}");
+
+    let mut body = Writer::default();
+    body.str(session);
+    body.array(8, |shortcuts| {
+        for &hotkey in hotkeys {
+            let id = hotkey.to_string();
+            shortcuts.begin_struct();
+            shortcuts.str(&id);
+            shortcuts.array(8, |options| {
+                options.str_entry("description", &id);
+                if let Some(trigger) = preferred_trigger(hotkey) {
+                    options.str_entry("preferred_trigger", &trigger);
+                }
+            });
+        }
+    });
+    body.str("");
+    body.array(8, |options| options.str_entry("handle_token", &token));
+
+    let serial = connection.send(&MethodCall {
+        destination: PORTAL,
+        path: PORTAL_PATH,
+        interface: GLOBAL_SHORTCUTS,
+        member: "BindShortcuts",
+        signature: "oa(sa{sv})sa{sv}",
+        body: body.into_inner(),
+    })?;
+    Ok(PendingBind {
+        serial,
+        path: request_path(connection, &token),
+    })
+}
+
+/// The session that the shortcuts are bound to.
+struct Session {
+    path: String,
+    is_bound: bool,
+}
+
+impl Session {
+    /// Binds the hotkeys to the session. The portal only allows binding the
+    /// shortcuts of a session once, so if that already happened, the session
+    /// gets replaced by a new one.
+    fn bind(
+        &mut self,
+        connection: &mut Connection,
+        hotkeys: &[Hotkey],
+        request: &mut u32,
+    ) -> std::io::Result<PendingBind> {
+        if self.is_bound {
+            close_session(connection, &self.path)?;
+            connection.set_nonblocking(false)?;
+            let path = create_session(connection, request);
+            connection.set_nonblocking(true)?;
+            self.path = path?;
+        }
+        self.is_bound = true;
+        bind_shortcuts(connection, &self.path, hotkeys, request)
+    }
+}
+
+pub fn new() -> Result<Hook> {
+    let mut connection = Connection::session(SETUP_TIMEOUT).map_err(|_| Error::Portal)?;
+    let mut request = 0;
+    subscribe(&mut connection).map_err(|_| Error::Portal)?;
+    let mut session = Session {
+        path: create_session(&mut connection, &mut request).map_err(|_| Error::Portal)?,
+        is_bound: false,
+    };
+    connection
+        .set_nonblocking(true)
+        .map_err(|_| Error::Portal)?;
+
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let mut poll = Poll::new().map_err(|_| Error::EPoll)?;
+    let waker = Waker::new(poll.registry(), PING_TOKEN).map_err(|_| Error::EPoll)?;
+    poll.registry()
+        .register(
+            &mut SourceFd(&connection.as_raw_fd()),
+            DBUS_TOKEN,
+            Interest::READABLE,
+        )
+        .map_err(|_| Error::EPoll)?;
+
+    let join_handle = thread::spawn(move || -> Result<()> {
+        let mut result = Ok(());
+        let mut events = Events::with_capacity(1024);
+        let mut hotkeys = Registry::new();
+        let mut bound = Vec::<Hotkey>::new();
+        let mut bind_at = None::<Instant>;
+        let mut pending_bind = None::<PendingBind>;
+
+        let (mut xlib, mut display) = (None, None);
+
+        'event_loop: loop {
+            let timeout = bind_at.map(|at| at.saturating_duration_since(Instant::now()));
+            if poll.poll(&mut events, timeout).is_err() {
+                result = Err(Error::EPoll);
+                break 'event_loop;
+            }
+
+            let mut has_messages = events.iter().any(|e| e.token() == DBUS_TOKEN);
+            let has_pings = events.iter().any(|e| e.token() == PING_TOKEN);
+
+            if bind_at.is_some_and(|at| at <= Instant::now()) {
+                bind_at = None;
+                match session.bind(&mut connection, &bound, &mut request) {
+                    Ok(bind) => pending_bind = Some(bind),
+                    Err(_) => {
+                        result = Err(Error::Portal);
+                        break 'event_loop;
+                    }
+                }
+                // Creating the session may have received messages already,
+                // which don't cause any further events.
+                has_messages = true;
+            }
+
+            if has_messages {
+                loop {
+                    let message = match connection.receive() {
+                        Ok(Some(message)) => message,
+                        Ok(None) => break,
+                        Err(_) => {
+                            result = Err(Error::Portal);
+                            break 'event_loop;
+                        }
+                    };
+
+                    if let Some(bind) = &pending_bind {
+                        if message.is_error_reply(bind.serial) {
+                            result = Err(Error::Portal);
+                            break 'event_loop;
+                        }
+                        if message.is_signal(REQUEST, "Response")
+                            && message.path.as_deref() == Some(&bind.path)
+                        {
+                            // Anything but a success means that the shortcuts
+                            // didn't get bound, for example because the user
+                            // cancelled the dialog.
+                            if message.body.first().and_then(Value::as_u64) != Some(0) {
+                                result = Err(Error::Portal);
+                                break 'event_loop;
+                            }
+                            pending_bind = None;
+                            continue;
+                        }
+                    }
+
+                    let is_activated = message.is_signal(GLOBAL_SHORTCUTS, "Activated");
+                    if !is_activated && !message.is_signal(GLOBAL_SHORTCUTS, "Deactivated") {
+                        continue;
+                    }
+                    let [path, id, ..] = &message.body[..] else {
+                        continue;
+                    };
+                    if path.as_str() != Some(&session.path) {
+                        continue;
+                    }
+                    let Some(hotkey) = id.as_str().and_then(|id| Hotkey::from_str(id).ok()) else {
+                        continue;
+                    };
+                    if is_activated {
+                        hotkeys.press(hotkey.key_code, hotkey.modifiers);
+                    } else {
+                        hotkeys.release(hotkey.key_code);
+                    }
+                }
+            }
+
+            if has_pings {
+                for message in receiver.try_iter() {
+                    match message {
+                        Message::Register(hotkey, callback, promise) => {
+                            let result =
+                                hotkeys.register(hotkey.key_code, hotkey.modifiers, callback);
+                            if result.is_ok() {
+                                bound.push(hotkey);
+                                bind_at = Some(Instant::now() + BIND_DELAY);
+                            }
+                            promise.set(result);
+                        }
+                        Message::Unregister(hotkey, promise) => {
+                            let result = hotkeys.unregister(hotkey.key_code, hotkey.modifiers);
+                            if result.is_ok() {
+                                bound.retain(|&h| h != hotkey);
+                                bind_at = Some(Instant::now() + BIND_DELAY);
+                            }
+                            promise.set(result);
+                        }
+                        Message::Resolve(key_code, promise) => {
+                            // XWayland may still tell us about the keyboard
+                            // layout.
+                            promise.set(evdev_impl::resolve(&mut xlib, &mut display, key_code))
+                        }
+                        Message::Capture(callback, promise) => {
+                            // The compositor only tells us about the shortcuts
+                            // it bound for us, so we never get to see any
+                            // other keys.
+                            promise.set(match callback {
+                                Some(_) => Err(crate::Error::CaptureUnsupported),
+                                None => Ok(()),
+                            });
+                        }
+                        Message::End => {
+                            break 'event_loop;
+                        }
+                    }
+                }
+            }
+        }
+
+        let _ = close_session(&mut connection, &session.path);
+
+        if let Some(xlib) = xlib
+            && let Some(display) = display
+        {
+            unsafe { (xlib.XCloseDisplay)(display) };
+        }
+
+        result.map_err(Into::into)
+    });
+
+    Ok(Hook {
+        sender,
+        waker,
+        join_handle: Some(join_handle),
+    })
+}
@@ -1,18 +1,25 @@
 use std::{
-    collections::HashMap,
     mem::MaybeUninit,
-    os::raw::{c_int, c_uint},
+    os::{
+        raw::{c_int, c_uint},
+        unix::prelude::AsRawFd,
+    },
     ptr, thread,
 };
 
+use evdev::{Device, EventType};
 use mio::{Events, Interest, Poll, Token, Waker, unix::SourceFd};
 use x11_dl::xlib::{
-    _XDisplay, AnyKey, AnyModifier, ControlMask, Display, GrabModeAsync, KeyPress, LockMask,
-    Mod1Mask, Mod2Mask, Mod3Mask, Mod4Mask, ShiftMask, XErrorEvent, XKeyEvent, Xlib,
+    _XDisplay, AnyKey, AnyModifier, ControlMask, CurrentTime, Display, GrabModeAsync, GrabSuccess,
+    KeyPress, KeyRelease, LockMask, Mod1Mask, Mod2Mask, Mod3Mask, Mod4Mask, ShiftMask, XErrorEvent,
+    XKeyEvent, Xlib,
 };
 
-use super::{Error, Hook, Message};
-use crate::{KeyCode, Modifiers, Result};
+use super::{Error, Hook, Message, evdev_impl};
+use crate::{
+    KeyCode, KeyCodeClass, Modifiers, Result,
+    registry::{self, Registry},
+};
 
 unsafe fn ungrab_all(xlib: &Xlib, display: *mut Display) {
     unsafe {
@@ -112,8 +119,44 @@ const fn code_for(key: KeyCode) -> Option<c_uint> {
     }
 }
 
+impl registry::Key for c_uint {
+    fn key_code(self) -> Option<KeyCode> {
+        KeyCode::all().find(|&key_code| code_for(key_code) == Some(self))
+    }
+}
+
+// While capturing, the whole keyboard is grabbed, so we get to see all the
+// keys, not just the ones of the registered hotkeys.
+unsafe fn grab_keyboard(xlib: &Xlib, display: *mut Display, grab: bool) -> bool {
+    unsafe {
+        let window = (xlib.XDefaultRootWindow)(display);
+        let grabbed = if grab {
+            (xlib.XGrabKeyboard)(
+                display,
+                window,
+                false as _,
+                GrabModeAsync,
+                GrabModeAsync,
+                CurrentTime,
+            ) == GrabSuccess
+        } else {
+            (xlib.XUngrabKeyboard)(display, CurrentTime);
+            false
+        };
+        (xlib.XFlush)(display);
+        grabbed
+    }
+}
+
 const X_TOKEN: Token = Token(0);
 const PING_TOKEN: Token = Token(1);
+// Higher numbered tokens are allocated to gamepads.
+const FIRST_GAMEPAD_TOKEN: usize = 2;
+
+// X11 doesn't know about gamepads, so they can't be grabbed.
+const fn is_grabbable(key_code: KeyCode) -> bool {
+    !matches!(key_code.classify(), KeyCodeClass::Gamepad)
+}
 
 pub fn new() -> Result<Hook> {
     unsafe {
@@ -140,6 +183,25 @@ pub fn new() -> Result<Hook> {
             )
             .map_err(|_| Error::EPoll)?;
 
+        // X11 doesn't report the buttons of gamepads, so we read them from
+        // evdev instead. Unlike keyboards, gamepads are usually accessible to
+        // the user that is logged in, even if they are not part of the `input`
+        // group.
+        let mut gamepads: Vec<Device> = evdev::enumerate()
+            .map(|(_, d)| d)
+            .filter(evdev_impl::is_gamepad)
+            .collect();
+
+        for (i, device) in gamepads.iter().enumerate() {
+            poll.registry()
+                .register(
+                    &mut SourceFd(&device.as_raw_fd()),
+                    Token(FIRST_GAMEPAD_TOKEN + i),
+                    Interest::READABLE,
+                )
+                .map_err(|_| Error::EPoll)?;
+        }
+
         struct XData(Xlib, *mut Display);
         unsafe impl Send for XData {}
         let xdata = XData(xlib, display);
@@ -150,12 +212,18 @@ pub fn new() -> Result<Hook> {
 
             let mut result = Ok(());
             let mut events = Events::with_capacity(1024);
-            let mut hotkeys = HashMap::new();
+            let mut hotkeys = Registry::new();
+            let mut keyboard_grabbed = false;
 
             // For some reason we need to call this once for any KeyGrabs to
             // actually do anything.
             (xlib.XKeysymToKeycode)(display, 0);
 
+            // Without this, holding down a key results in alternating release
+            // and press events, which would look like the key got tapped
+            // repeatedly.
+            (xlib.XkbSetDetectableAutoRepeat)(display, 1, ptr::null_mut());
+
             'event_loop: loop {
                 if poll.poll(&mut events, None).is_err() {
                     result = Err(Error::EPoll);
@@ -168,24 +236,19 @@ pub fn new() -> Result<Hook> {
                             match message {
                                 Message::Register(key, callback, promise) => {
                                     promise.set(if let Some(code) = code_for(key.key_code) {
-                                        if hotkeys.insert((code, key.modifiers), callback).is_some()
-                                        {
-                                            Err(crate::Error::AlreadyRegistered)
-                                        } else {
+                                        let res = hotkeys.register(code, key.modifiers, callback);
+                                        if res.is_ok() && is_grabbable(key.key_code) {
                                             grab_key(&xlib, display, code, key.modifiers, false);
-                                            Ok(())
                                         }
+                                        res
                                     } else {
                                         Ok(())
                                     });
                                 }
                                 Message::Unregister(key, promise) => {
                                     let res = if let Some(code) = code_for(key.key_code) {
-                                        let res = hotkeys
-                                            .remove(&(code, key.modifiers))
-                                            .map(drop)
-                                            .ok_or(crate::Error::NotRegistered);
-                                        if res.is_ok() {
+                                        let res = hotkeys.unregister(code, key.modifiers);
+                                        if res.is_ok() && is_grabbable(key.key_code) {
                                             grab_key(&xlib, display, code, key.modifiers, true);
                                         }
                                         res
@@ -197,6 +260,25 @@ pub fn new() -> Result<Hook> {
                                 Message::Resolve(key_code, promise) => {
                                     promise.set(resolve(&xlib, display, key_code))
                                 }
+                                Message::Capture(callback, promise) => {
+                                    match callback {
+                                        Some(callback) => {
+                                            hotkeys.capture(callback);
+                                            if !keyboard_grabbed {
+                                                keyboard_grabbed =
+                                                    grab_keyboard(&xlib, display, true);
+                                            }
+                                        }
+                                        None => {
+                                            hotkeys.cancel_capture();
+                                            if keyboard_grabbed {
+                                                keyboard_grabbed =
+                                                    grab_keyboard(&xlib, display, false);
+                                            }
+                                        }
+                                    }
+                                    promise.set(Ok(()));
+                                }
                                 Message::End => {
                                     break 'event_loop;
                                 }
@@ -225,13 +307,54 @@ pub fn new() -> Result<Hook> {
                                         modifiers.insert(Modifiers::META);
                                     }
 
-                                    if let Some(callback) =
-                                        hotkeys.get_mut(&(event.keycode, modifiers))
-                                    {
-                                        callback();
+                                    hotkeys.press(event.keycode, modifiers);
+                                    if keyboard_grabbed && !hotkeys.is_capturing() {
+                                        keyboard_grabbed = grab_keyboard(&xlib, display, false);
+                                    }
+                                } else if event.get_type() == KeyRelease {
+                                    let event: &XKeyEvent = event.as_ref();
+                                    hotkeys.release(event.keycode);
+                                }
+                            }
+                        }
+                    } else if let Some(device) = mio_event
+                        .token()
+                        .0
+                        .checked_sub(FIRST_GAMEPAD_TOKEN)
+                        .and_then(|i| gamepads.get_mut(i))
+                    {
+                        let fd = device.as_raw_fd();
+                        match device.fetch_events() {
+                            Ok(events) => {
+                                for ev in events {
+                                    if ev.event_type() != EventType::KEY {
+                                        continue;
+                                    }
+                                    // The gamepad buttons use the same offset
+                                    // as the keys. They are outside the range
+                                    // of X11 key codes, so they can't clash.
+                                    let code = ev.code() as c_uint + 8;
+                                    const RELEASED: i32 = 0;
+                                    const PRESSED: i32 = 1;
+                                    match ev.value() {
+                                        PRESSED => {
+                                            hotkeys.press(code, Modifiers::empty());
+                                            if keyboard_grabbed && !hotkeys.is_capturing() {
+                                                keyboard_grabbed =
+                                                    grab_keyboard(&xlib, display, false);
+                                            }
+                                        }
+                                        RELEASED => {
+                                            hotkeys.release(code);
+                                        }
+                                        _ => {}
                                     }
                                 }
                             }
+                            Err(_) => {
+                                // The gamepad most likely got disconnected.
+                                poll.registry().deregister(&mut SourceFd(&fd)).ok();
+                            }
                         }
                     }
                 }
@@ -20,10 +20,11 @@ use self::{
         EventRef, EventTapLocation, EventTapOptions, EventTapPlacement, EventTapProxy, EventType,
     },
 };
-use crate::{ConsumePreference, Hotkey, KeyCode, Modifiers, Result};
+use crate::{
+    ConsumePreference, Hotkey, HotkeyEvent, KeyCode, Modifiers, Result, registry::Registry,
+};
 use core::ptr::null_mut;
 use std::{
-    collections::{HashMap, hash_map::Entry},
     ffi::c_void,
     fmt,
     sync::{Arc, Mutex, mpsc::channel},
@@ -75,7 +76,7 @@ unsafe impl Send for RunLoop {}
 unsafe impl Sync for RunLoop {}
 
 struct State {
-    hotkeys: Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>,
+    hotkeys: Mutex<Registry<KeyCode>>,
 }
 
 /// A hook allows you to listen to hotkeys.
@@ -104,7 +105,7 @@ impl Hook {
         );
 
         let state = Arc::new(State {
-            hotkeys: Mutex::new(HashMap::new()),
+            hotkeys: Mutex::new(Registry::new()),
         });
         let thread_state = state.clone();
 
@@ -131,7 +132,7 @@ impl Hook {
                 } else {
                     EventTapOptions::LISTEN_ONLY
                 },
-                EventMask::KEY_DOWN,
+                EventMask::KEY_DOWN | EventMask::KEY_UP,
                 Some(callback),
                 state_ptr as *mut c_void,
             );
@@ -175,24 +176,37 @@ impl Hook {
 
     pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
-        F: FnMut() + Send + 'static,
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
-        if let Entry::Vacant(vacant) = self.state.hotkeys.lock().unwrap().entry(hotkey) {
-            vacant.insert(Box::new(callback));
-            Ok(())
-        } else {
-            Err(crate::Error::AlreadyRegistered)
-        }
+        self.state.hotkeys.lock().unwrap().register(
+            hotkey.key_code,
+            hotkey.modifiers,
+            Box::new(callback),
+        )
     }
 
     pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
-        let _ = self
-            .state
+        self.state
             .hotkeys
             .lock()
             .unwrap()
-            .remove(&hotkey)
-            .ok_or(crate::Error::NotRegistered)?;
+            .unregister(hotkey.key_code, hotkey.modifiers)
+    }
+
+    pub fn capture<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        self.state
+            .hotkeys
+            .lock()
+            .unwrap()
+            .capture(Box::new(callback));
+        Ok(())
+    }
+
+    pub fn cancel_capture(&self) -> Result<()> {
+        self.state.hotkeys.lock().unwrap().cancel_capture();
         Ok(())
     }
 
@@ -304,7 +318,7 @@ impl Hook {
 
 unsafe extern "C" fn callback(
     _: EventTapProxy,
-    _: EventType,
+    event_type: EventType,
     event: EventRef,
     user_info: *mut c_void,
 ) -> EventRef {
@@ -480,14 +494,14 @@ unsafe extern "C" fn callback(
         modifiers.insert(Modifiers::META);
     }
 
-    if let Some(callback) = state
-        .hotkeys
-        .lock()
-        .unwrap()
-        .get_mut(&key_code.with_modifiers(modifiers))
-    {
-        callback();
+    let mut hotkeys = state.hotkeys.lock().unwrap();
+    let handled = if event_type == EventType::KEY_UP {
+        hotkeys.release(key_code)
+    } else {
+        hotkeys.press(key_code, modifiers)
+    };
 
+    if handled {
         // If we handled the event and the hook is consuming, we should return
         // null so the system deletes the event. If the hook is not consuming
         // the return value will be ignored, so return null anyway.
@@ -0,0 +1,121 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Read},
+    os::unix::{fs::OpenOptionsExt, prelude::AsRawFd},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{self, AtomicBool},
+    },
+    thread::{self, JoinHandle},
+};
+
+use mio::{Events, Interest, Poll, Token, Waker, unix::SourceFd};
+use nix::fcntl::OFlag;
+
+use super::Handler;
+use crate::{Error, Result};
+
+const DEVICE_TOKEN: Token = Token(0);
+const PING_TOKEN: Token = Token(1);
+
+// ALSA exposes each MIDI port as a raw MIDI file called
+// `/dev/snd/midiC<card>D<device>`. The numbers of the cards depend on the
+// order in which they got connected, so the ports are named after the ids of
+// the cards instead.
+fn ports() -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir("/dev/snd") else {
+        return Vec::new();
+    };
+    let mut ports: Vec<_> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let file_name = entry.file_name();
+            let (card, device) = file_name.to_str()?.strip_prefix("midiC")?.split_once('D')?;
+            let (card, device): (u32, u32) = (card.parse().ok()?, device.parse().ok()?);
+            let id = fs::read_to_string(format!("/proc/asound/card{card}/id")).ok()?;
+            let id = id.trim();
+            let name = if device == 0 {
+                id.to_owned()
+            } else {
+                format!("{id} {device}")
+            };
+            Some((name, entry.path()))
+        })
+        .collect();
+    ports.sort();
+    ports
+}
+
+pub fn devices() -> Vec<String> {
+    ports().into_iter().map(|(name, _)| name).collect()
+}
+
+pub struct Device {
+    waker: Waker,
+    stopped: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        self.stopped.store(true, atomic::Ordering::Relaxed);
+        self.waker.wake().ok();
+        if let Some(handle) = self.join_handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+pub fn open(name: &str, mut handler: Handler) -> Result<Device> {
+    let (_, path) = ports()
+        .into_iter()
+        .find(|(port, _)| port == name)
+        .ok_or(Error::MidiDevice)?;
+
+    let mut file: File = OpenOptions::new()
+        .read(true)
+        .custom_flags(OFlag::O_NONBLOCK.bits())
+        .open(path)
+        .map_err(|_| Error::MidiDevice)?;
+
+    let mut poll = Poll::new().map_err(|_| Error::MidiDevice)?;
+    let waker = Waker::new(poll.registry(), PING_TOKEN).map_err(|_| Error::MidiDevice)?;
+    poll.registry()
+        .register(
+            &mut SourceFd(&file.as_raw_fd()),
+            DEVICE_TOKEN,
+            Interest::READABLE,
+        )
+        .map_err(|_| Error::MidiDevice)?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let thread_stopped = stopped.clone();
+
+    let join_handle = thread::spawn(move || {
+        let mut events = Events::with_capacity(16);
+        let mut buf = [0; 256];
+
+        while !thread_stopped.load(atomic::Ordering::Relaxed) {
+            if poll.poll(&mut events, None).is_err() {
+                return;
+            }
+            loop {
+                match file.read(&mut buf) {
+                    Ok(0) => return,
+                    Ok(len) => handler(&buf[..len]),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    // The device most likely got disconnected.
+                    Err(_) => return,
+                }
+            }
+        }
+    });
+
+    Ok(Device {
+        waker,
+        stopped,
+        join_handle: Some(join_handle),
+    })
+}
@@ -0,0 +1,67 @@
+use std::sync::{Arc, Mutex};
+
+use super::{Handler, MidiMessage, Parser, platform};
+use crate::{HotkeyEvent, Modifiers, Result, registry::Registry};
+
+/// A MIDI hook listens to the messages of a single MIDI device, similar to how
+/// a [`Hook`](crate::Hook) listens to hotkeys.
+pub struct MidiHook {
+    messages: Arc<Mutex<Registry<MidiMessage>>>,
+    _device: platform::Device,
+}
+
+impl MidiHook {
+    /// Starts listening to the MIDI device with the name provided. The names
+    /// of the devices that are available can be queried with
+    /// [`midi_devices`].
+    pub fn new(device: &str) -> Result<Self> {
+        let messages = Arc::new(Mutex::new(Registry::new()));
+
+        let mut parser = Parser::default();
+        let registry = messages.clone();
+        let handler: Handler = Box::new(move |bytes| {
+            for &byte in bytes {
+                if let Some((message, event)) = parser.push(byte) {
+                    let mut registry = registry.lock().unwrap();
+                    match event {
+                        HotkeyEvent::Pressed => registry.press(message, Modifiers::empty()),
+                        HotkeyEvent::Released => registry.release(message),
+                    };
+                }
+            }
+        });
+
+        Ok(Self {
+            messages,
+            _device: platform::open(device, handler)?,
+        })
+    }
+
+    /// Registers a MIDI message to listen to. The callback is called both when
+    /// the message presses its note or controller and when it releases it
+    /// again.
+    pub fn register<F>(&self, message: MidiMessage, callback: F) -> Result<()>
+    where
+        F: FnMut(HotkeyEvent) + Send + 'static,
+    {
+        self.messages
+            .lock()
+            .unwrap()
+            .register(message, Modifiers::empty(), Box::new(callback))
+    }
+
+    /// Unregisters a previously registered MIDI message.
+    pub fn unregister(&self, message: MidiMessage) -> Result<()> {
+        self.messages
+            .lock()
+            .unwrap()
+            .unregister(message, Modifiers::empty())
+    }
+}
+
+/// Returns the names of the MIDI devices that are currently connected. The
+/// names stay the same when the devices get reconnected, so they can be
+/// stored in a configuration.
+pub fn midi_devices() -> Vec<String> {
+    platform::devices()
+}
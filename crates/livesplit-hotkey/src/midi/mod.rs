@@ -0,0 +1,263 @@
+use core::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::HotkeyEvent;
+
+cfg_if::cfg_if! {
+    if #[cfg(not(feature = "std"))] {
+    } else if #[cfg(windows)] {
+        mod winmm;
+        use self::winmm as platform;
+    } else if #[cfg(target_os = "linux")] {
+        mod alsa;
+        use self::alsa as platform;
+    } else {
+        mod other;
+        use self::other as platform;
+    }
+}
+
+#[cfg(feature = "std")]
+mod hook;
+#[cfg(feature = "std")]
+pub use self::hook::*;
+
+/// Receives the raw bytes sent by a MIDI device.
+#[cfg(feature = "std")]
+type Handler = Box<dyn FnMut(&[u8]) + Send + 'static>;
+
+/// A MIDI message that can be used as a trigger. The channels are numbered
+/// from 0 to 15, even though they are usually presented to the user as 1 to
+/// 16.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MidiMessage {
+    /// A note getting played. The note on message presses it and the note off
+    /// message releases it again.
+    Note {
+        /// The channel the note is played on.
+        channel: u8,
+        /// The number of the note, with 60 being the middle C.
+        note: u8,
+    },
+    /// A controller changing its value, such as a sustain pedal. Values of 64
+    /// and above count as pressed, lower values count as released.
+    ControlChange {
+        /// The channel of the controller.
+        channel: u8,
+        /// The number of the controller.
+        controller: u8,
+    },
+}
+
+impl fmt::Display for MidiMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Note { channel, note } => write!(f, "Note {note} Channel {}", channel + 1),
+            Self::ControlChange {
+                channel,
+                controller,
+            } => write!(f, "CC {controller} Channel {}", channel + 1),
+        }
+    }
+}
+
+impl FromStr for MidiMessage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let kind = words.next().ok_or(())?;
+        let number: u8 = words.next().ok_or(())?.parse().map_err(drop)?;
+        if words.next() != Some("Channel") {
+            return Err(());
+        }
+        let channel: u8 = words.next().ok_or(())?.parse().map_err(drop)?;
+        if words.next().is_some() || number > 127 || !(1..=16).contains(&channel) {
+            return Err(());
+        }
+        let channel = channel - 1;
+
+        match kind {
+            "Note" => Ok(Self::Note {
+                channel,
+                note: number,
+            }),
+            "CC" => Ok(Self::ControlChange {
+                channel,
+                controller: number,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Serialize for MidiMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MidiMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(MidiMessageVisitor)
+    }
+}
+
+struct MidiMessageVisitor;
+
+impl serde::de::Visitor<'_> for MidiMessageVisitor {
+    type Value = MidiMessage;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid MIDI message")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        MidiMessage::from_str(v).map_err(|()| serde::de::Error::custom("invalid MIDI message"))
+    }
+}
+
+/// Turns a stream of MIDI bytes into the messages that can be used as
+/// triggers. Running status is supported and all the other messages are
+/// skipped.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Parser {
+    status: u8,
+    data: [u8; 2],
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl Parser {
+    fn push(&mut self, byte: u8) -> Option<(MidiMessage, HotkeyEvent)> {
+        // Real-time messages can appear anywhere, even in between the data
+        // bytes of other messages.
+        if byte >= 0xF8 {
+            return None;
+        }
+        if byte >= 0x80 {
+            // System messages cancel the running status, so the data bytes
+            // that follow them are skipped.
+            self.status = if byte < 0xF0 { byte } else { 0 };
+            self.len = 0;
+            return None;
+        }
+        if self.status == 0 {
+            return None;
+        }
+
+        self.data[self.len] = byte;
+        self.len += 1;
+
+        let (kind, channel) = (self.status & 0xF0, self.status & 0x0F);
+        let needed = if matches!(kind, 0xC0 | 0xD0) { 1 } else { 2 };
+        if self.len < needed {
+            return None;
+        }
+        self.len = 0;
+
+        let [number, value] = self.data;
+        match kind {
+            0x80 => Some((
+                MidiMessage::Note {
+                    channel,
+                    note: number,
+                },
+                HotkeyEvent::Released,
+            )),
+            // A note on message with a velocity of 0 is a note off message.
+            0x90 => Some((
+                MidiMessage::Note {
+                    channel,
+                    note: number,
+                },
+                if value != 0 {
+                    HotkeyEvent::Pressed
+                } else {
+                    HotkeyEvent::Released
+                },
+            )),
+            0xB0 => Some((
+                MidiMessage::ControlChange {
+                    channel,
+                    controller: number,
+                },
+                if value >= 64 {
+                    HotkeyEvent::Pressed
+                } else {
+                    HotkeyEvent::Released
+                },
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> Vec<(MidiMessage, HotkeyEvent)> {
+        let mut parser = Parser::default();
+        bytes.iter().filter_map(|&b| parser.push(b)).collect()
+    }
+
+    #[test]
+    fn parses_notes_with_running_status() {
+        let note = |note| MidiMessage::Note { channel: 2, note };
+        assert_eq!(
+            parse(&[0x92, 60, 100, 62, 90, 0xF8, 60, 0, 0x82, 62, 64]),
+            [
+                (note(60), HotkeyEvent::Pressed),
+                (note(62), HotkeyEvent::Pressed),
+                (note(60), HotkeyEvent::Released),
+                (note(62), HotkeyEvent::Released),
+            ],
+        );
+    }
+
+    #[test]
+    fn skips_other_messages() {
+        let pedal = MidiMessage::ControlChange {
+            channel: 0,
+            controller: 64,
+        };
+        assert_eq!(
+            parse(&[0xC0, 5, 0xF0, 0x41, 0x10, 0xF7, 0xB0, 64, 127, 64, 0]),
+            [
+                (pedal, HotkeyEvent::Pressed),
+                (pedal, HotkeyEvent::Released),
+            ],
+        );
+    }
+
+    #[test]
+    fn messages_round_trip_as_strings() {
+        for message in [
+            MidiMessage::Note {
+                channel: 0,
+                note: 60,
+            },
+            MidiMessage::ControlChange {
+                channel: 15,
+                controller: 64,
+            },
+        ] {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+        assert_eq!("Note 60 Channel 0".parse::<MidiMessage>(), Err(()));
+        assert_eq!("CC 128 Channel 1".parse::<MidiMessage>(), Err(()));
+    }
+}
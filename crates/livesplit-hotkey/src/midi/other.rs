@@ -0,0 +1,12 @@
+use super::Handler;
+use crate::{Error, Result};
+
+pub struct Device;
+
+pub fn open(_: &str, _: Handler) -> Result<Device> {
+    Err(Error::MidiDevice)
+}
+
+pub fn devices() -> Vec<String> {
+    Vec::new()
+}
@@ -0,0 +1,121 @@
+use std::{mem, ptr};
+
+use windows_sys::Win32::Media::{
+    Audio::{
+        CALLBACK_FUNCTION, HMIDIIN, MIDIINCAPSW, midiInClose, midiInGetDevCapsW, midiInGetNumDevs,
+        midiInOpen, midiInReset, midiInStart,
+    },
+    MM_MIM_DATA, MMSYSERR_NOERROR,
+};
+
+use super::Handler;
+use crate::{Error, Result};
+
+fn device_name(index: u32) -> Option<String> {
+    let mut caps = MIDIINCAPSW::default();
+    // SAFETY: The capabilities are a valid pointer and the size matches.
+    let result = unsafe {
+        midiInGetDevCapsW(
+            index as usize,
+            &mut caps,
+            mem::size_of::<MIDIINCAPSW>() as u32,
+        )
+    };
+    if result != MMSYSERR_NOERROR {
+        return None;
+    }
+    let len = caps
+        .szPname
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(caps.szPname.len());
+    Some(String::from_utf16_lossy(&caps.szPname[..len]))
+}
+
+pub fn devices() -> Vec<String> {
+    // SAFETY: Always safe to call.
+    let count = unsafe { midiInGetNumDevs() };
+    (0..count).filter_map(device_name).collect()
+}
+
+pub struct Device {
+    handle: HMIDIIN,
+    handler: *mut Handler,
+}
+
+// SAFETY: The handle and the handler are only accessed by the callback and
+// when the device gets dropped, which stops the callback.
+unsafe impl Send for Device {}
+unsafe impl Sync for Device {}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        // SAFETY: The handle is valid and once it is closed, the callback is
+        // not called anymore, so the handler can be freed.
+        unsafe {
+            midiInReset(self.handle);
+            midiInClose(self.handle);
+            drop(Box::from_raw(self.handler));
+        }
+    }
+}
+
+unsafe extern "system" fn callback(
+    _: HMIDIIN,
+    message: u32,
+    instance: usize,
+    param: usize,
+    _: usize,
+) {
+    if message == MM_MIM_DATA {
+        // SAFETY: The instance is the handler, which lives until the device
+        // got closed.
+        let handler = unsafe { &mut *(instance as *mut Handler) };
+
+        // The message is packed into the parameter, with the status byte in
+        // the lowest byte. Unused data bytes are not guaranteed to be zero.
+        let [status, first, second, _] = (param as u32).to_le_bytes();
+        let len = if matches!(status & 0xF0, 0xC0 | 0xD0) {
+            2
+        } else {
+            3
+        };
+        handler(&[status, first, second][..len]);
+    }
+}
+
+pub fn open(name: &str, handler: Handler) -> Result<Device> {
+    // SAFETY: Always safe to call.
+    let count = unsafe { midiInGetNumDevs() };
+    let index = (0..count)
+        .find(|&index| device_name(index).as_deref() == Some(name))
+        .ok_or(Error::MidiDevice)?;
+
+    let handler = Box::into_raw(Box::new(handler));
+    let mut handle = ptr::null_mut();
+
+    // SAFETY: The callback matches the signature expected and the handler
+    // stays valid until the device gets closed. If opening or starting the
+    // device fails, the callback doesn't get called anymore, so the handler
+    // can be freed.
+    unsafe {
+        if midiInOpen(
+            &mut handle,
+            index,
+            callback as usize,
+            handler as usize,
+            CALLBACK_FUNCTION,
+        ) != MMSYSERR_NOERROR
+        {
+            drop(Box::from_raw(handler));
+            return Err(Error::MidiDevice);
+        }
+        if midiInStart(handle) != MMSYSERR_NOERROR {
+            midiInClose(handle);
+            drop(Box::from_raw(handler));
+            return Err(Error::MidiDevice);
+        }
+    }
+
+    Ok(Device { handle, handler })
+}
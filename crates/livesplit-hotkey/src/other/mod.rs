@@ -1,4 +1,4 @@
-use crate::{ConsumePreference, Hotkey, KeyCode, Result};
+use crate::{ConsumePreference, Hotkey, HotkeyEvent, KeyCode, Result};
 use alloc::{fmt, string::String};
 
 #[derive(Debug)]
@@ -23,7 +23,7 @@ impl Hook {
     #[inline]
     pub fn register<F>(&self, _: Hotkey, _: F) -> Result<()>
     where
-        F: FnMut() + Send + 'static,
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
         Ok(())
     }
@@ -33,6 +33,19 @@ impl Hook {
         Ok(())
     }
 
+    #[inline]
+    pub fn capture<F>(&self, _: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn cancel_capture(&self) -> Result<()> {
+        Ok(())
+    }
+
     #[inline]
     pub fn try_resolve(&self, _key_code: KeyCode) -> Option<String> {
         None
@@ -0,0 +1,158 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Hotkey, HotkeyEvent, KeyCode, MidiMessage, Modifiers, Result};
+
+pub type Callback = Box<dyn FnMut(HotkeyEvent) + Send + 'static>;
+pub type CaptureCallback = Box<dyn FnOnce(Hotkey) + Send + 'static>;
+
+/// A key as identified by the platform.
+pub trait Key: Copy + Eq + Hash {
+    /// Returns the key code of the key, if there is one.
+    fn key_code(self) -> Option<KeyCode>;
+}
+
+impl Key for KeyCode {
+    fn key_code(self) -> Option<KeyCode> {
+        Some(self)
+    }
+}
+
+impl Key for MidiMessage {
+    fn key_code(self) -> Option<KeyCode> {
+        None
+    }
+}
+
+/// Keeps track of the registered hotkeys and which of them are currently
+/// being held down. The keys are identified by whatever the platform uses to
+/// identify them. The release of a key is reported to the hotkey that got
+/// pressed with it, even if the modifiers changed in the meantime. Instead of
+/// triggering the hotkeys, the next key that gets pressed can also be
+/// captured.
+pub struct Registry<K> {
+    callbacks: HashMap<(K, Modifiers), Callback>,
+    pressed: HashMap<K, Modifiers>,
+    capture: Option<CaptureCallback>,
+}
+
+impl<K: Key> Registry<K> {
+    pub fn new() -> Self {
+        Self {
+            callbacks: HashMap::new(),
+            pressed: HashMap::new(),
+            capture: None,
+        }
+    }
+
+    /// Reports the next key that gets pressed, along with the modifiers that
+    /// are held down, to the callback instead of triggering the hotkeys. The
+    /// modifier keys on their own are not reported. This replaces any capture
+    /// that is still in progress.
+    pub fn capture(&mut self, callback: CaptureCallback) {
+        self.capture = Some(callback);
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capture = None;
+    }
+
+    pub const fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    pub fn register(&mut self, key: K, modifiers: Modifiers, callback: Callback) -> Result<()> {
+        if self.callbacks.contains_key(&(key, modifiers)) {
+            return Err(crate::Error::AlreadyRegistered);
+        }
+        self.callbacks.insert((key, modifiers), callback);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, key: K, modifiers: Modifiers) -> Result<()> {
+        self.callbacks
+            .remove(&(key, modifiers))
+            .map(drop)
+            .ok_or(crate::Error::NotRegistered)
+    }
+
+    /// Reports that the key got pressed while the modifiers were held down.
+    /// Returns whether a registered hotkey got pressed or the key got
+    /// captured. Repeated presses of a key that is already held down are
+    /// ignored.
+    pub fn press(&mut self, key: K, modifiers: Modifiers) -> bool {
+        if let Some(&modifiers) = self.pressed.get(&key) {
+            return self.callbacks.contains_key(&(key, modifiers));
+        }
+        if self.capture.is_some() {
+            if let Some(key_code) = key.key_code().filter(|key_code| !key_code.is_modifier())
+                && let Some(capture) = self.capture.take()
+            {
+                capture(key_code.with_modifiers(modifiers));
+                return true;
+            }
+            return false;
+        }
+        if let Some(callback) = self.callbacks.get_mut(&(key, modifiers)) {
+            callback(HotkeyEvent::Pressed);
+            self.pressed.insert(key, modifiers);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reports that the key got released. Returns whether the key belongs to
+    /// a registered hotkey that got pressed before.
+    pub fn release(&mut self, key: K) -> bool {
+        if let Some(modifiers) = self.pressed.remove(&key)
+            && let Some(callback) = self.callbacks.get_mut(&(key, modifiers))
+        {
+            callback(HotkeyEvent::Released);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn captures_the_next_key_with_its_modifiers() {
+        let mut registry = Registry::new();
+        let presses = Arc::new(Mutex::new(0));
+        let thread_presses = presses.clone();
+        registry
+            .register(
+                KeyCode::KeyA,
+                Modifiers::empty(),
+                Box::new(move |_| *thread_presses.lock().unwrap() += 1),
+            )
+            .unwrap();
+
+        let captured = Arc::new(Mutex::new(None));
+        let thread_captured = captured.clone();
+        registry.capture(Box::new(move |hotkey| {
+            *thread_captured.lock().unwrap() = Some(hotkey)
+        }));
+
+        assert!(!registry.press(KeyCode::ControlLeft, Modifiers::empty()));
+        assert!(registry.is_capturing());
+        assert!(registry.press(KeyCode::KeyA, Modifiers::CONTROL));
+        assert!(!registry.is_capturing());
+        assert_eq!(
+            *captured.lock().unwrap(),
+            Some(KeyCode::KeyA.with_modifiers(Modifiers::CONTROL)),
+        );
+        assert_eq!(*presses.lock().unwrap(), 0);
+
+        // The captured key isn't held down as a hotkey.
+        assert!(!registry.release(KeyCode::KeyA));
+        assert!(registry.press(KeyCode::KeyA, Modifiers::empty()));
+        assert_eq!(*presses.lock().unwrap(), 1);
+    }
+}
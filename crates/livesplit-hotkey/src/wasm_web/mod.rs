@@ -1,11 +1,12 @@
-use crate::{ConsumePreference, Hotkey, KeyCode, Modifiers, Result};
+use crate::{
+    ConsumePreference, Hotkey, HotkeyEvent, KeyCode, Modifiers, Result, registry::Registry,
+};
 use js_sys::{Function, Promise, Reflect};
 use wasm_bindgen::{JsCast, prelude::*};
 use web_sys::{Event, Gamepad, GamepadButton, KeyboardEvent, window};
 
 use std::{
     cell::{Cell, RefCell},
-    collections::hash_map::{Entry, HashMap},
     fmt,
     rc::Rc,
     sync::{Arc, Mutex},
@@ -26,8 +27,9 @@ impl fmt::Display for Error {
 }
 
 pub struct Hook {
-    hotkeys: Arc<Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>>,
+    hotkeys: Arc<Mutex<Registry<KeyCode>>>,
     keyboard_callback: Closure<dyn FnMut(MaybeKeyboardEvent)>,
+    keyboard_release_callback: Closure<dyn FnMut(MaybeKeyboardEvent)>,
     gamepad_callback: Closure<dyn FnMut()>,
     interval_id: Cell<Option<i32>>,
     keyboard_layout_resolver: Rc<RefCell<Option<(JsValue, Function)>>>,
@@ -41,6 +43,10 @@ impl Drop for Hook {
                 "keydown",
                 self.keyboard_callback.as_ref().unchecked_ref(),
             );
+            let _ = window.remove_event_listener_with_callback(
+                "keyup",
+                self.keyboard_release_callback.as_ref().unchecked_ref(),
+            );
             if let Some(interval_id) = self.interval_id.get() {
                 window.clear_interval_with_handle(interval_id);
             }
@@ -79,10 +85,7 @@ impl Hook {
             ConsumePreference::PreferConsume | ConsumePreference::MustConsume
         );
 
-        let hotkeys = Arc::new(Mutex::new(HashMap::<
-            Hotkey,
-            Box<dyn FnMut() + Send + 'static>,
-        >::new()));
+        let hotkeys = Arc::new(Mutex::new(Registry::new()));
 
         let window = window().ok_or(crate::Error::Platform(Error::FailedToCreateHook))?;
 
@@ -121,15 +124,8 @@ impl Hook {
                         modifiers.insert(Modifiers::META);
                     }
 
-                    if let Some(callback) = hotkey_map
-                        .lock()
-                        .unwrap()
-                        .get_mut(&code.with_modifiers(modifiers))
-                    {
-                        callback();
-                        if prevent_default {
-                            event.prevent_default();
-                        }
+                    if hotkey_map.lock().unwrap().press(code, modifiers) && prevent_default {
+                        event.prevent_default();
                     }
                 }
             }
@@ -139,6 +135,29 @@ impl Hook {
             .add_event_listener_with_callback("keydown", keyboard_callback.as_ref().unchecked_ref())
             .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))?;
 
+        let hotkey_map = hotkeys.clone();
+        let keyboard_release_callback = Closure::wrap(Box::new(move |event: MaybeKeyboardEvent| {
+            // See the `keydown` handler for why this check is necessary.
+            if event.repeat().is_some() {
+                let event = event.unchecked_into::<KeyboardEvent>();
+
+                if let Ok(code) = event.code().parse::<KeyCode>()
+                    && hotkey_map.lock().unwrap().release(code)
+                    && prevent_default
+                {
+                    event.prevent_default();
+                }
+            }
+        })
+            as Box<dyn FnMut(MaybeKeyboardEvent)>);
+
+        window
+            .add_event_listener_with_callback(
+                "keyup",
+                keyboard_release_callback.as_ref().unchecked_ref(),
+            )
+            .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))?;
+
         let hotkey_map = hotkeys.clone();
 
         let mut states = Vec::new();
@@ -190,12 +209,10 @@ impl Hook {
                         {
                             if let Ok(button) = button.dyn_into::<GamepadButton>() {
                                 let pressed = button.pressed();
-                                if pressed
-                                    && !*state
-                                    && let Some(callback) =
-                                        hotkey_map.lock().unwrap().get_mut(&code.into())
-                                {
-                                    callback();
+                                if pressed && !*state {
+                                    hotkey_map.lock().unwrap().press(code, Modifiers::empty());
+                                } else if !pressed && *state {
+                                    hotkey_map.lock().unwrap().release(code);
                                 }
                                 *state = pressed;
                             }
@@ -208,6 +225,7 @@ impl Hook {
         Ok(Hook {
             hotkeys,
             keyboard_callback,
+            keyboard_release_callback,
             gamepad_callback,
             interval_id: Cell::new(None),
             keyboard_layout_resolver,
@@ -217,32 +235,52 @@ impl Hook {
 
     pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
-        F: FnMut() + Send + 'static,
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
-        if let Entry::Vacant(vacant) = self.hotkeys.lock().unwrap().entry(hotkey) {
-            if GAMEPAD_BUTTONS.contains(&hotkey.key_code) && self.interval_id.get().is_none() {
-                let interval_id = window()
-                    .ok_or(crate::Error::Platform(Error::FailedToCreateHook))?
-                    .set_interval_with_callback_and_timeout_and_arguments_0(
-                        self.gamepad_callback.as_ref().unchecked_ref(),
-                        1000 / 60,
-                    )
-                    .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))?;
-                self.interval_id.set(Some(interval_id));
-            }
-            vacant.insert(Box::new(callback));
-            Ok(())
-        } else {
-            Err(crate::Error::AlreadyRegistered)
+        self.hotkeys.lock().unwrap().register(
+            hotkey.key_code,
+            hotkey.modifiers,
+            Box::new(callback),
+        )?;
+        if GAMEPAD_BUTTONS.contains(&hotkey.key_code) {
+            self.poll_gamepads()?;
         }
+        Ok(())
     }
 
-    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
-        if self.hotkeys.lock().unwrap().remove(&hotkey).is_some() {
-            Ok(())
-        } else {
-            Err(crate::Error::NotRegistered)
+    fn poll_gamepads(&self) -> Result<()> {
+        if self.interval_id.get().is_none() {
+            let interval_id = window()
+                .ok_or(crate::Error::Platform(Error::FailedToCreateHook))?
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    self.gamepad_callback.as_ref().unchecked_ref(),
+                    1000 / 60,
+                )
+                .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))?;
+            self.interval_id.set(Some(interval_id));
         }
+        Ok(())
+    }
+
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
+        self.hotkeys
+            .lock()
+            .unwrap()
+            .unregister(hotkey.key_code, hotkey.modifiers)
+    }
+
+    pub fn capture<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        self.hotkeys.lock().unwrap().capture(Box::new(callback));
+        // The buttons of the gamepads can be captured as well.
+        self.poll_gamepads()
+    }
+
+    pub fn cancel_capture(&self) -> Result<()> {
+        self.hotkeys.lock().unwrap().cancel_capture();
+        Ok(())
     }
 
     pub fn try_resolve(&self, key_code: KeyCode) -> Option<String> {
@@ -261,6 +299,12 @@ impl Hook {
                 "keydown",
                 self.keyboard_callback.as_ref().unchecked_ref(),
             )
+            .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))?;
+        window
+            .add_event_listener_with_callback(
+                "keyup",
+                self.keyboard_release_callback.as_ref().unchecked_ref(),
+            )
             .map_err(|_| crate::Error::Platform(Error::FailedToCreateHook))
     }
 }
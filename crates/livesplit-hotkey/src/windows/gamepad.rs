@@ -0,0 +1,138 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{self, AtomicBool},
+        mpsc::Sender,
+    },
+    thread,
+    time::Duration,
+};
+
+use windows_sys::Win32::{
+    Foundation::ERROR_SUCCESS,
+    UI::Input::XboxController::{
+        XINPUT_GAMEPAD_A, XINPUT_GAMEPAD_B, XINPUT_GAMEPAD_BACK, XINPUT_GAMEPAD_BUTTON_FLAGS,
+        XINPUT_GAMEPAD_DPAD_DOWN, XINPUT_GAMEPAD_DPAD_LEFT, XINPUT_GAMEPAD_DPAD_RIGHT,
+        XINPUT_GAMEPAD_DPAD_UP, XINPUT_GAMEPAD_LEFT_SHOULDER, XINPUT_GAMEPAD_LEFT_THUMB,
+        XINPUT_GAMEPAD_RIGHT_SHOULDER, XINPUT_GAMEPAD_RIGHT_THUMB, XINPUT_GAMEPAD_START,
+        XINPUT_GAMEPAD_TRIGGER_THRESHOLD, XINPUT_GAMEPAD_X, XINPUT_GAMEPAD_Y, XINPUT_STATE,
+        XInputGetState, XUSER_MAX_COUNT,
+    },
+};
+
+use crate::{Hotkey, HotkeyEvent, KeyCode};
+
+// XInput doesn't notify us about any changes, so we need to poll the
+// controllers. This is the same rate the browsers use.
+const POLL_INTERVAL: Duration = Duration::from_millis(1000 / 60);
+
+// Querying a controller that is not connected is surprisingly expensive, so we
+// only check every second whether one got connected.
+const RECONNECT_INTERVAL: u32 = 60;
+
+// The buttons are mapped to the "Standard Gamepad" defined here:
+// https://w3c.github.io/gamepad/#dfn-standard-gamepad
+// The triggers are analog, so they are handled separately. The guide button is
+// not reported by XInput.
+const BUTTONS: [(XINPUT_GAMEPAD_BUTTON_FLAGS, KeyCode); 14] = [
+    (XINPUT_GAMEPAD_A, KeyCode::Gamepad0),
+    (XINPUT_GAMEPAD_B, KeyCode::Gamepad1),
+    (XINPUT_GAMEPAD_X, KeyCode::Gamepad2),
+    (XINPUT_GAMEPAD_Y, KeyCode::Gamepad3),
+    (XINPUT_GAMEPAD_LEFT_SHOULDER, KeyCode::Gamepad4),
+    (XINPUT_GAMEPAD_RIGHT_SHOULDER, KeyCode::Gamepad5),
+    (XINPUT_GAMEPAD_BACK, KeyCode::Gamepad8),
+    (XINPUT_GAMEPAD_START, KeyCode::Gamepad9),
+    (XINPUT_GAMEPAD_LEFT_THUMB, KeyCode::Gamepad10),
+    (XINPUT_GAMEPAD_RIGHT_THUMB, KeyCode::Gamepad11),
+    (XINPUT_GAMEPAD_DPAD_UP, KeyCode::Gamepad12),
+    (XINPUT_GAMEPAD_DPAD_DOWN, KeyCode::Gamepad13),
+    (XINPUT_GAMEPAD_DPAD_LEFT, KeyCode::Gamepad14),
+    (XINPUT_GAMEPAD_DPAD_RIGHT, KeyCode::Gamepad15),
+];
+
+/// Polls the XInput controllers on a background thread until it gets dropped.
+pub struct Poller {
+    stopped: Arc<AtomicBool>,
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stopped.store(true, atomic::Ordering::Relaxed);
+    }
+}
+
+impl Poller {
+    pub fn new(events: Sender<(Hotkey, HotkeyEvent)>) -> Self {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+
+        thread::spawn(move || {
+            let mut connected = [true; XUSER_MAX_COUNT as usize];
+            let mut previous = 0u32;
+            let mut tick = 0u32;
+
+            while !thread_stopped.load(atomic::Ordering::Relaxed) {
+                // The buttons of all the controllers are combined, so pressing
+                // the same button on two controllers only counts once.
+                let mut pressed = 0u32;
+
+                for (index, connected) in connected.iter_mut().enumerate() {
+                    if !*connected && tick % RECONNECT_INTERVAL != 0 {
+                        continue;
+                    }
+                    let mut state = XINPUT_STATE::default();
+                    // SAFETY: The index is within the bounds of the controllers
+                    // and the state is a valid pointer.
+                    *connected =
+                        unsafe { XInputGetState(index as u32, &mut state) } == ERROR_SUCCESS;
+                    if !*connected {
+                        continue;
+                    }
+
+                    let gamepad = state.Gamepad;
+                    for (flag, key_code) in BUTTONS {
+                        if gamepad.wButtons & flag != 0 {
+                            pressed |= bit(key_code);
+                        }
+                    }
+                    if u16::from(gamepad.bLeftTrigger) > XINPUT_GAMEPAD_TRIGGER_THRESHOLD {
+                        pressed |= bit(KeyCode::Gamepad6);
+                    }
+                    if u16::from(gamepad.bRightTrigger) > XINPUT_GAMEPAD_TRIGGER_THRESHOLD {
+                        pressed |= bit(KeyCode::Gamepad7);
+                    }
+                }
+
+                let changed = pressed ^ previous;
+                for key_code in BUTTONS
+                    .map(|(_, key_code)| key_code)
+                    .into_iter()
+                    .chain([KeyCode::Gamepad6, KeyCode::Gamepad7])
+                {
+                    if changed & bit(key_code) == 0 {
+                        continue;
+                    }
+                    let event = if pressed & bit(key_code) != 0 {
+                        HotkeyEvent::Pressed
+                    } else {
+                        HotkeyEvent::Released
+                    };
+                    if events.send((key_code.into(), event)).is_err() {
+                        return;
+                    }
+                }
+
+                previous = pressed;
+                tick = tick.wrapping_add(1);
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        Self { stopped }
+    }
+}
+
+const fn bit(key_code: KeyCode) -> u32 {
+    1 << (key_code as u8 - KeyCode::Gamepad0 as u8)
+}
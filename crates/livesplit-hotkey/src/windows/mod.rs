@@ -1,7 +1,9 @@
-use crate::{ConsumePreference, Hotkey, KeyCode, Modifiers, Result};
+use crate::{
+    ConsumePreference, Hotkey, HotkeyEvent, KeyCode, KeyCodeClass, Modifiers, Result,
+    registry::Registry,
+};
 use std::{
     cell::RefCell,
-    collections::hash_map::{Entry, HashMap},
     fmt, mem, ptr,
     sync::{
         Arc, Mutex,
@@ -25,6 +27,8 @@ use windows_sys::Win32::{
     },
 };
 
+mod gamepad;
+
 const MSG_EXIT: u32 = 0x400;
 
 #[derive(Debug)]
@@ -45,11 +49,11 @@ impl fmt::Display for Error {
     }
 }
 
-type Callback = Box<dyn FnMut() + Send + 'static>;
-
 pub struct Hook {
     thread_id: u32,
-    hotkeys: Arc<Mutex<HashMap<Hotkey, Callback>>>,
+    hotkeys: Arc<Mutex<Registry<KeyCode>>>,
+    events: Sender<(Hotkey, HotkeyEvent)>,
+    gamepad_poller: Mutex<Option<gamepad::Poller>>,
 }
 
 impl Drop for Hook {
@@ -62,7 +66,7 @@ impl Drop for Hook {
 
 struct State {
     hook: HHOOK,
-    events: Sender<Hotkey>,
+    events: Sender<(Hotkey, HotkeyEvent)>,
     modifiers: Modifiers,
     // FIXME: Use variant count when it's stable.
     // https://github.com/rust-lang/rust/issues/73662
@@ -294,10 +298,13 @@ unsafe extern "system" fn callback_proc(code: i32, wparam: WPARAM, lparam: LPARA
 
                         state
                             .events
-                            .send(Hotkey {
-                                key_code,
-                                modifiers: state.modifiers,
-                            })
+                            .send((
+                                Hotkey {
+                                    key_code,
+                                    modifiers: state.modifiers,
+                                },
+                                HotkeyEvent::Pressed,
+                            ))
                             .expect("Callback Thread disconnected");
 
                         match key_code {
@@ -347,6 +354,17 @@ unsafe extern "system" fn callback_proc(code: i32, wparam: WPARAM, lparam: LPARA
                     let (idx, bit) = key_idx(key_code);
                     state.key_state[idx as usize] &= !bit;
 
+                    state
+                        .events
+                        .send((
+                            Hotkey {
+                                key_code,
+                                modifiers: state.modifiers,
+                            },
+                            HotkeyEvent::Released,
+                        ))
+                        .expect("Callback Thread disconnected");
+
                     match key_code {
                         KeyCode::AltLeft | KeyCode::AltRight => {
                             state.modifiers.remove(Modifiers::ALT);
@@ -386,14 +404,13 @@ impl Hook {
             return Err(crate::Error::UnmatchedPreference);
         }
 
-        let hotkeys = Arc::new(Mutex::new(HashMap::<
-            Hotkey,
-            Box<dyn FnMut() + Send + 'static>,
-        >::new()));
+        let hotkeys = Arc::new(Mutex::new(Registry::new()));
 
         let (initialized_tx, initialized_rx) = channel();
         let (events_tx, events_rx) = channel();
 
+        let events = events_tx.clone();
+
         thread::spawn(move || {
             let mut hook = ptr::null_mut();
 
@@ -448,10 +465,12 @@ impl Hook {
         let hotkey_map = hotkeys.clone();
 
         thread::spawn(move || {
-            while let Ok(key) = events_rx.recv() {
-                if let Some(callback) = hotkey_map.lock().unwrap().get_mut(&key) {
-                    callback();
-                }
+            while let Ok((key, event)) = events_rx.recv() {
+                let mut hotkeys = hotkey_map.lock().unwrap();
+                match event {
+                    HotkeyEvent::Pressed => hotkeys.press(key.key_code, key.modifiers),
+                    HotkeyEvent::Released => hotkeys.release(key.key_code),
+                };
             }
         });
 
@@ -459,27 +478,60 @@ impl Hook {
             .recv()
             .map_err(|_| crate::Error::Platform(Error::ThreadStopped))??;
 
-        Ok(Hook { thread_id, hotkeys })
+        Ok(Hook {
+            thread_id,
+            hotkeys,
+            events,
+            gamepad_poller: Mutex::new(None),
+        })
     }
 
     pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
-        F: FnMut() + Send + 'static,
+        F: FnMut(HotkeyEvent) + Send + 'static,
     {
-        if let Entry::Vacant(vacant) = self.hotkeys.lock().unwrap().entry(hotkey) {
-            vacant.insert(Box::new(callback));
-            Ok(())
-        } else {
-            Err(crate::Error::AlreadyRegistered)
+        self.hotkeys.lock().unwrap().register(
+            hotkey.key_code,
+            hotkey.modifiers,
+            Box::new(callback),
+        )?;
+
+        // The controllers are only polled once there is a hotkey for them.
+        if matches!(hotkey.key_code.classify(), KeyCodeClass::Gamepad) {
+            self.gamepad_poller
+                .lock()
+                .unwrap()
+                .get_or_insert_with(|| gamepad::Poller::new(self.events.clone()));
         }
+
+        Ok(())
     }
 
     pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
-        if self.hotkeys.lock().unwrap().remove(&hotkey).is_some() {
-            Ok(())
-        } else {
-            Err(crate::Error::NotRegistered)
-        }
+        self.hotkeys
+            .lock()
+            .unwrap()
+            .unregister(hotkey.key_code, hotkey.modifiers)
+    }
+
+    pub fn capture<F>(&self, callback: F) -> Result<()>
+    where
+        F: FnOnce(Hotkey) + Send + 'static,
+    {
+        self.hotkeys.lock().unwrap().capture(Box::new(callback));
+
+        // The buttons of the controllers can be captured as well.
+        self.gamepad_poller
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| gamepad::Poller::new(self.events.clone()));
+
+        Ok(())
+    }
+
+    pub fn cancel_capture(&self) -> Result<()> {
+        self.hotkeys.lock().unwrap().cancel_capture();
+        Ok(())
     }
 
     pub fn try_resolve(&self, key_code: KeyCode) -> Option<String> {
@@ -0,0 +1,241 @@
+//! Evaluates the health of each comparison stored in a [`Run`]. This includes
+//! how stale the comparison is, how many segments lack data, whether another
+//! comparison dominates it and whether the stored times of a Comparison
+//! Generator are outdated. Based on this, removals and regenerations are
+//! suggested, which can be applied through the
+//! [`RunEditor`](crate::RunEditor).
+
+use crate::{Run, TimeSpan, TimingMethod, comparison::personal_best, platform::prelude::*};
+
+/// A suggestion on how to deal with a comparison.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Suggestion {
+    /// The custom comparison doesn't provide any value anymore and should be
+    /// removed.
+    Remove,
+    /// The times stored for the Comparison Generator's comparison are outdated
+    /// and the comparison should be regenerated.
+    Regenerate,
+}
+
+/// Describes the health of a single comparison.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComparisonHealth {
+    /// The name of the comparison.
+    pub name: String,
+    /// Whether the comparison is provided by a Comparison Generator, as
+    /// opposed to being a custom comparison.
+    pub is_generated: bool,
+    /// The number of segments that don't have a time stored for the
+    /// comparison.
+    pub missing_segments: usize,
+    /// Describes how stale the comparison is. This is the number of finished
+    /// attempts in the attempt history that were faster than the comparison's
+    /// final time.
+    pub beaten_by_attempts: u32,
+    /// Whether the times stored for the comparison differ from what its
+    /// Comparison Generator would generate right now. This is always `false`
+    /// for custom comparisons.
+    pub is_outdated: bool,
+    /// The name of another comparison that is at least as fast as this
+    /// comparison at every split and faster at one or more of them. Only the
+    /// Personal Best and the other custom comparisons are considered, as most
+    /// Comparison Generators are meant to be faster than the runner's actual
+    /// times.
+    pub dominated_by: Option<String>,
+    /// The suggested action for the comparison, if there is any.
+    pub suggestion: Option<Suggestion>,
+}
+
+/// A report about the health of all the comparisons of a [`Run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The timing method the report was created for.
+    pub method: TimingMethod,
+    /// The health of each comparison, in the same order as the comparisons of
+    /// the run.
+    pub comparisons: Vec<ComparisonHealth>,
+}
+
+impl Report {
+    /// Returns an iterator over all the comparisons that have a suggested
+    /// action.
+    pub fn suggestions(&self) -> impl Iterator<Item = &ComparisonHealth> + '_ {
+        self.comparisons.iter().filter(|c| c.suggestion.is_some())
+    }
+}
+
+/// Evaluates the health of all the comparisons of the run for the timing
+/// method specified.
+pub fn calculate(run: &Run, method: TimingMethod) -> Report {
+    let mut regenerated = run.segments().to_vec();
+    for generator in run.comparison_generators() {
+        generator
+            .clone()
+            .generate(&mut regenerated, run.attempt_history());
+    }
+
+    let split_times = |comparison: &str| {
+        run.segments()
+            .iter()
+            .map(|s| s.comparison(comparison)[method])
+            .collect::<Vec<_>>()
+    };
+
+    let custom_split_times = run
+        .custom_comparisons()
+        .iter()
+        .map(|c| (c.as_str(), split_times(c)))
+        .collect::<Vec<_>>();
+
+    let mut comparisons = Vec::new();
+
+    for (name, times) in &custom_split_times {
+        let dominated_by = custom_split_times
+            .iter()
+            .find(|(other, other_times)| other != name && dominates(other_times, times))
+            .map(|(other, _)| String::from(*other));
+
+        let missing_segments = times.iter().filter(|t| t.is_none()).count();
+
+        let suggestion = if *name != personal_best::NAME
+            && (missing_segments == times.len() || dominated_by.is_some())
+        {
+            Some(Suggestion::Remove)
+        } else {
+            None
+        };
+
+        comparisons.push(ComparisonHealth {
+            name: String::from(*name),
+            is_generated: false,
+            missing_segments,
+            beaten_by_attempts: beaten_by_attempts(run, times, method),
+            is_outdated: false,
+            dominated_by,
+            suggestion,
+        });
+    }
+
+    for generator in run.comparison_generators() {
+        let name = generator.name();
+        let times = split_times(name);
+
+        let is_outdated = run
+            .segments()
+            .iter()
+            .zip(&regenerated)
+            .any(|(stored, fresh)| stored.comparison(name) != fresh.comparison(name));
+
+        comparisons.push(ComparisonHealth {
+            name: String::from(name),
+            is_generated: true,
+            missing_segments: times.iter().filter(|t| t.is_none()).count(),
+            beaten_by_attempts: beaten_by_attempts(run, &times, method),
+            is_outdated,
+            dominated_by: None,
+            suggestion: is_outdated.then_some(Suggestion::Regenerate),
+        });
+    }
+
+    Report {
+        method,
+        comparisons,
+    }
+}
+
+fn beaten_by_attempts(run: &Run, split_times: &[Option<TimeSpan>], method: TimingMethod) -> u32 {
+    let Some(Some(final_time)) = split_times.last() else {
+        return 0;
+    };
+    run.attempt_history()
+        .iter()
+        .filter(|a| a.time()[method].is_some_and(|t| t < *final_time))
+        .count() as u32
+}
+
+fn dominates(dominating: &[Option<TimeSpan>], dominated: &[Option<TimeSpan>]) -> bool {
+    let mut is_faster_somewhere = false;
+
+    for (dominating, dominated) in dominating.iter().zip(dominated) {
+        let Some(dominated) = dominated else {
+            continue;
+        };
+        let Some(dominating) = dominating else {
+            return false;
+        };
+        if dominating > dominated {
+            return false;
+        }
+        is_faster_somewhere |= dominating < dominated;
+    }
+
+    is_faster_somewhere
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Time,
+        comparison::{average_segments, best_segments},
+        util::tests_helper::*,
+    };
+
+    fn find<'a>(report: &'a Report, name: &str) -> &'a ComparisonHealth {
+        report.comparisons.iter().find(|c| c.name == name).unwrap()
+    }
+
+    fn set_comparison(run: &mut Run, name: &str, times: &[Option<f64>]) {
+        run.add_custom_comparison(name).unwrap();
+        for (segment, time) in run.segments_mut().iter_mut().zip(times) {
+            *segment.comparison_mut(name) = Time::new().with_game_time(time.map(span));
+        }
+    }
+
+    #[test]
+    fn report() {
+        let mut timer = create_timer(&["A", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+        run_with_splits(&mut timer, &[8.0, 18.0]);
+        let mut run = timer.into_run(true);
+
+        set_comparison(&mut run, "Old PB", &[Some(12.0), Some(25.0)]);
+        set_comparison(&mut run, "Empty", &[None, None]);
+        set_comparison(&mut run, "Goal", &[Some(7.0), Some(19.0)]);
+
+        let report = calculate(&run, TimingMethod::GameTime);
+
+        let pb = find(&report, personal_best::NAME);
+        assert_eq!(pb.missing_segments, 0);
+        assert_eq!(pb.beaten_by_attempts, 0);
+        assert_eq!(pb.suggestion, None);
+
+        let old_pb = find(&report, "Old PB");
+        assert_eq!(old_pb.beaten_by_attempts, 2);
+        assert_eq!(old_pb.dominated_by.as_deref(), Some(personal_best::NAME));
+        assert_eq!(old_pb.suggestion, Some(Suggestion::Remove));
+
+        let empty = find(&report, "Empty");
+        assert_eq!(empty.missing_segments, 2);
+        assert_eq!(empty.suggestion, Some(Suggestion::Remove));
+
+        let goal = find(&report, "Goal");
+        assert_eq!(goal.dominated_by, None);
+        assert_eq!(goal.suggestion, None);
+
+        let best_segments = find(&report, best_segments::NAME);
+        assert!(best_segments.is_generated);
+        assert!(!best_segments.is_outdated);
+        assert_eq!(best_segments.suggestion, None);
+
+        for segment in run.segments_mut() {
+            segment.comparisons_mut().remove(average_segments::NAME);
+        }
+        let report = calculate(&run, TimingMethod::GameTime);
+        let average = find(&report, average_segments::NAME);
+        assert!(average.is_outdated);
+        assert_eq!(average.missing_segments, 2);
+        assert_eq!(average.suggestion, Some(Suggestion::Regenerate));
+    }
+}
@@ -8,7 +8,9 @@ use crate::{TimeSpan, TimerPhase, analysis, timing::Snapshot};
 /// provided. If there's no active attempt, the final time of the comparison is
 /// returned instead.
 pub fn calculate(timer: &Snapshot, comparison: &str) -> (Option<TimeSpan>, bool) {
-    let timing_method = timer.current_timing_method();
+    let timing_method = timer
+        .run()
+        .resolve_timing_method(comparison, timer.current_timing_method());
     let last_segment = timer.run().segments().last().unwrap();
     let phase = timer.current_phase();
 
@@ -12,7 +12,9 @@ use crate::{TimeSpan, TimerPhase, analysis, timing::Snapshot};
 /// the moment. This may be the case when the current attempt is slower than the
 /// comparison at the current split.
 pub fn calculate(timer: &Snapshot, comparison: &str) -> (Option<TimeSpan>, bool) {
-    let timing_method = timer.current_timing_method();
+    let timing_method = timer
+        .run()
+        .resolve_timing_method(comparison, timer.current_timing_method());
     let last_segment = timer.run().segments().last().unwrap();
 
     let mut use_live_delta = false;
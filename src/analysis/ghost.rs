@@ -0,0 +1,222 @@
+//! Provides functionality to calculate the position of a simulated "ghost"
+//! runner that is racing the current attempt. The ghost follows the split
+//! times of either a comparison or a specific attempt from the attempt history.
+//! Renderers can use this to draw a ghost marker or a second timer racing the
+//! runner.
+
+use crate::{Run, TimeSpan, TimingMethod, platform::prelude::*, timing::Snapshot};
+
+/// The source of the split times the ghost follows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Source<'a> {
+    /// The ghost follows the split times of the comparison with this name.
+    Comparison(&'a str),
+    /// The ghost follows the split times of the attempt with this index in
+    /// the attempt history.
+    Attempt(i32),
+}
+
+/// The position of the ghost at a specific point in time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ghost {
+    /// The index of the segment the ghost is currently on. This is `None` if
+    /// the ghost doesn't have any further split times, either because it
+    /// finished the run or because its attempt was reset at that point.
+    pub current_split_index: Option<usize>,
+    /// The time the ghost's timer is showing. Once the ghost stops, this
+    /// stays on the ghost's last split time.
+    pub time: TimeSpan,
+    /// How far the ghost is through its current segment, from `0.0` at the
+    /// start of the segment to `1.0` at its end. This is `1.0` if the ghost
+    /// doesn't have a current segment.
+    pub segment_progress: f64,
+    /// Whether the ghost finished the run.
+    pub is_finished: bool,
+}
+
+/// Calculates the split times the ghost follows for the timing method
+/// specified. Segments that the ghost skipped have no split time. If the
+/// ghost's attempt was reset, all the segments after the reset have no split
+/// time either.
+pub fn split_times(run: &Run, source: Source<'_>, method: TimingMethod) -> Vec<Option<TimeSpan>> {
+    match source {
+        Source::Comparison(comparison) => run
+            .segments()
+            .iter()
+            .map(|segment| segment.comparison(comparison)[method])
+            .collect(),
+        Source::Attempt(attempt) => {
+            let mut total = TimeSpan::zero();
+            let mut is_reset = false;
+            run.segments()
+                .iter()
+                .map(|segment| {
+                    if is_reset {
+                        return None;
+                    }
+                    let Some(segment_time) = segment.segment_history().get(attempt) else {
+                        is_reset = true;
+                        return None;
+                    };
+                    total += segment_time[method]?;
+                    Some(total)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Calculates the position of the ghost at the time the snapshot was taken.
+/// The ghost starts at the same time as the active attempt, so both race each
+/// other. If there is no active attempt, the ghost is waiting at the start. If
+/// the snapshot doesn't have a time for the timing method specified, `None` is
+/// returned.
+pub fn calculate(timer: &Snapshot, source: Source<'_>, method: TimingMethod) -> Option<Ghost> {
+    let current_time = timer.current_time()[method]?;
+    Some(at_time(
+        &split_times(timer.run(), source, method),
+        current_time,
+    ))
+}
+
+/// Calculates the position of a ghost following the split times provided at
+/// the time specified. The split times can be calculated with
+/// [`split_times`]. Frontends that query the ghost every frame should cache
+/// the split times and use this function, instead of using [`calculate`].
+pub fn at_time(split_times: &[Option<TimeSpan>], time: TimeSpan) -> Ghost {
+    let mut segment_start = TimeSpan::zero();
+
+    for (index, split_time) in split_times.iter().enumerate() {
+        let Some(split_time) = *split_time else {
+            continue;
+        };
+
+        if time < split_time {
+            let segment_duration = (split_time - segment_start).total_seconds();
+            let segment_progress = if segment_duration > 0.0 {
+                ((time - segment_start).total_seconds() / segment_duration).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            return Ghost {
+                current_split_index: Some(index),
+                time,
+                segment_progress,
+                is_finished: false,
+            };
+        }
+
+        segment_start = split_time;
+    }
+
+    let is_finished = split_times.last().is_some_and(Option::is_some);
+
+    Ghost {
+        current_split_index: None,
+        time: if split_times.iter().any(Option::is_some) {
+            segment_start
+        } else {
+            time
+        },
+        segment_progress: 1.0,
+        is_finished,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Timer, comparison::personal_best, util::tests_helper::*};
+
+    fn timer() -> Timer {
+        let mut timer = create_timer(&["A", "B", "C"]);
+        run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+        run_with_splits_opt(&mut timer, &[Some(5.0), None, Some(40.0)]);
+        start_run(&mut timer);
+        timer.split().unwrap();
+        timer.reset(true).unwrap();
+        timer
+    }
+
+    #[test]
+    fn split_times_of_comparison() {
+        let timer = timer();
+        assert_eq!(
+            split_times(
+                timer.run(),
+                Source::Comparison(personal_best::NAME),
+                TimingMethod::GameTime,
+            ),
+            [Some(span(10.0)), Some(span(20.0)), Some(span(30.0))],
+        );
+    }
+
+    #[test]
+    fn split_times_of_attempts() {
+        let timer = timer();
+        let run = timer.run();
+        assert_eq!(
+            split_times(run, Source::Attempt(2), TimingMethod::GameTime),
+            [Some(span(5.0)), None, Some(span(40.0))],
+        );
+        assert_eq!(
+            split_times(run, Source::Attempt(3), TimingMethod::GameTime),
+            [Some(span(0.0)), None, None],
+        );
+    }
+
+    #[test]
+    fn position() {
+        let split_times = [Some(span(10.0)), None, Some(span(40.0))];
+
+        let ghost = at_time(&split_times, span(-2.0));
+        assert_eq!(ghost.current_split_index, Some(0));
+        assert_eq!(ghost.segment_progress, 0.0);
+
+        let ghost = at_time(&split_times, span(5.0));
+        assert_eq!(ghost.current_split_index, Some(0));
+        assert_eq!(ghost.time, span(5.0));
+        assert_eq!(ghost.segment_progress, 0.5);
+
+        let ghost = at_time(&split_times, span(25.0));
+        assert_eq!(ghost.current_split_index, Some(2));
+        assert_eq!(ghost.segment_progress, 0.5);
+
+        let ghost = at_time(&split_times, span(50.0));
+        assert_eq!(ghost.current_split_index, None);
+        assert_eq!(ghost.time, span(40.0));
+        assert!(ghost.is_finished);
+    }
+
+    #[test]
+    fn reset_ghost_stops() {
+        let ghost = at_time(&[Some(span(10.0)), None, None], span(15.0));
+        assert_eq!(ghost.current_split_index, None);
+        assert_eq!(ghost.time, span(10.0));
+        assert!(!ghost.is_finished);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut timer = timer();
+        start_run(&mut timer);
+        timer.set_game_time(span(15.0)).unwrap();
+
+        let ghost = timer
+            .snapshot()
+            .ghost(
+                Source::Comparison(personal_best::NAME),
+                TimingMethod::GameTime,
+            )
+            .unwrap();
+        assert_eq!(ghost.current_split_index, Some(1));
+        assert_eq!(ghost.time, span(15.0));
+
+        let ghost = timer
+            .snapshot()
+            .ghost(Source::Attempt(2), TimingMethod::GameTime)
+            .unwrap();
+        assert_eq!(ghost.current_split_index, Some(2));
+    }
+}
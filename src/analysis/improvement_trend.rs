@@ -0,0 +1,197 @@
+//! Provides functionality to calculate how the finished times of a [`Run`]
+//! develop over its attempt history. This includes a rolling median of the
+//! finished times, which smooths out outliers, and a linear regression over
+//! the dates the attempts were finished at, so frontends can show how much
+//! time the runner improves by per week.
+//!
+//! Attempts that were reset are not considered, as they don't have a finished
+//! time, and neither are attempts that are excluded through their tags. The
+//! regression is based on the actual dates of the attempts, so gaps in the
+//! attempt history, such as breaks from the game, are properly accounted for.
+
+use crate::{AtomicDateTime, Run, TimeSpan, TimingMethod, platform::prelude::*};
+
+const SECONDS_PER_WEEK: f64 = 7.0 * 24.0 * 60.0 * 60.0;
+
+/// The settings that determine how the trend is calculated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Settings {
+    /// The number of finished attempts the rolling median is calculated over.
+    /// A window of 1 means that the finished times are not smoothed at all.
+    pub window: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { window: 5 }
+    }
+}
+
+/// A single finished attempt as part of the trend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Point {
+    /// The index of the attempt in the attempt history.
+    pub attempt_index: usize,
+    /// The point in time the attempt ended at, if it is known.
+    pub ended: Option<AtomicDateTime>,
+    /// The finished time of the attempt.
+    pub time: TimeSpan,
+    /// The median of the finished times of this attempt and the finished
+    /// attempts right before it, as specified by the window of the settings.
+    pub rolling_median: TimeSpan,
+}
+
+/// The trend of the finished times of a [`Run`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trend {
+    /// All the finished attempts in the order they were played in.
+    pub points: Vec<Point>,
+    /// The amount of time the finished times change by per week, based on a
+    /// linear regression over the dates the attempts were finished at. A
+    /// negative value means that the runner is improving. This is `None` if
+    /// there are fewer than two finished attempts with different dates.
+    pub change_per_week: Option<TimeSpan>,
+}
+
+impl Trend {
+    /// Returns the rolling median of the most recent finished attempt. This is
+    /// the runner's current typical finished time.
+    pub fn current_median(&self) -> Option<TimeSpan> {
+        Some(self.points.last()?.rolling_median)
+    }
+}
+
+/// Calculates the trend of the finished times of the run for the timing
+/// method specified.
+pub fn calculate(run: &Run, method: TimingMethod, settings: &Settings) -> Trend {
+    let window = settings.window.max(1);
+    let mut buf = Vec::with_capacity(window);
+    let mut points: Vec<Point> = Vec::new();
+
+    for (attempt_index, attempt) in run.attempt_history().iter().enumerate() {
+        let Some(time) = attempt.time()[method] else {
+            continue;
+        };
+        if run.has_excluded_tag(attempt) {
+            continue;
+        }
+
+        buf.clear();
+        buf.push(time);
+        buf.extend(points.iter().rev().take(window - 1).map(|p| p.time));
+        buf.sort_unstable();
+
+        let rolling_median = if buf.len() % 2 == 0 {
+            let (a, b) = (buf[buf.len() / 2 - 1], buf[buf.len() / 2]);
+            TimeSpan::from_seconds(0.5 * (a.total_seconds() + b.total_seconds()))
+        } else {
+            buf[buf.len() / 2]
+        };
+
+        points.push(Point {
+            attempt_index,
+            ended: attempt.ended(),
+            time,
+            rolling_median,
+        });
+    }
+
+    let change_per_week = regression_slope(&points).map(|slope| {
+        // The slope is in seconds of finished time per second of real world
+        // time.
+        TimeSpan::from_seconds(slope * SECONDS_PER_WEEK)
+    });
+
+    Trend {
+        points,
+        change_per_week,
+    }
+}
+
+fn regression_slope(points: &[Point]) -> Option<f64> {
+    let first = points.iter().find_map(|p| p.ended)?;
+
+    let (mut n, mut sum_x, mut sum_y, mut sum_xx, mut sum_xy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for point in points {
+        let Some(ended) = point.ended else {
+            continue;
+        };
+        let x = (ended - first).total_seconds();
+        let y = point.time.total_seconds();
+        n += 1.0;
+        sum_x += x;
+        sum_y += y;
+        sum_xx += x * x;
+        sum_xy += x * y;
+    }
+
+    let denominator = n * sum_xx - sum_x * sum_x;
+    if n < 2.0 || denominator <= 0.0 {
+        return None;
+    }
+
+    Some((n * sum_xy - sum_x * sum_y) / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DateTime, Time};
+
+    fn day(day: i64) -> Option<AtomicDateTime> {
+        Some(AtomicDateTime::new(
+            DateTime::from_unix_timestamp(1_600_000_000 + day * 24 * 60 * 60).unwrap(),
+            false,
+        ))
+    }
+
+    fn finished(seconds: f64) -> Time {
+        Time::new()
+            .with_real_time(Some(TimeSpan::from_seconds(seconds)))
+            .with_game_time(Some(TimeSpan::from_seconds(seconds - 10.0)))
+    }
+
+    fn run() -> Run {
+        let mut run = Run::new();
+        run.add_attempt(finished(1000.0), day(0), day(0), None);
+        // A reset attempt
+        run.add_attempt(Time::new(), day(1), day(1), None);
+        run.add_attempt(finished(990.0), day(7), day(7), None);
+        // A long break
+        run.add_attempt(finished(960.0), day(28), day(28), None);
+        run
+    }
+
+    #[test]
+    fn improvement_per_week() {
+        let trend = calculate(&run(), TimingMethod::RealTime, &Settings::default());
+        assert_eq!(trend.points.len(), 3);
+        assert_eq!(trend.points[1].attempt_index, 2);
+
+        let change = trend.change_per_week.unwrap().total_seconds();
+        assert!((change + 10.0).abs() < 1e-6, "{change}");
+    }
+
+    #[test]
+    fn rolling_median() {
+        let trend = calculate(&run(), TimingMethod::GameTime, &Settings { window: 2 });
+        let medians = trend
+            .points
+            .iter()
+            .map(|p| p.rolling_median.total_seconds())
+            .collect::<Vec<_>>();
+        assert_eq!(medians, [990.0, 985.0, 965.0]);
+        assert_eq!(trend.current_median(), Some(TimeSpan::from_seconds(965.0)));
+    }
+
+    #[test]
+    fn no_dates() {
+        let mut run = Run::new();
+        run.add_attempt(finished(1000.0), None, None, None);
+        run.add_attempt(finished(990.0), None, None, None);
+        let trend = calculate(&run, TimingMethod::RealTime, &Settings::default());
+        assert_eq!(trend.points.len(), 2);
+        assert_eq!(trend.change_per_week, None);
+    }
+}
@@ -1,11 +1,18 @@
 //! The analysis module provides a variety of functions for calculating
 //! information about a [`Run`](crate::run::Run).
 
+pub mod comparison_health;
 pub mod current_pace;
 pub mod delta;
+pub mod ghost;
+pub mod improvement_trend;
 pub mod pb_chance;
 pub mod possible_time_save;
 pub mod reset_chance;
+pub mod sanity_check;
+pub mod segment_correlation;
+pub mod sessions;
+pub mod simulation;
 mod skill_curve;
 pub mod state_helper;
 pub mod sum_of_segments;
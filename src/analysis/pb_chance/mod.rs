@@ -35,7 +35,11 @@ fn calculate(segments: &[Segment], method: TimingMethod, offset: TimeSpan) -> f6
 /// calculated. The value is being reported as a floating point number in the
 /// range from 0 (0%) to 1 (100%).
 pub fn for_run(run: &Run, method: TimingMethod) -> f64 {
-    calculate(run.segments(), method, TimeSpan::zero())
+    calculate(
+        &run.segments_without_excluded_attempts(),
+        method,
+        TimeSpan::zero(),
+    )
 }
 
 /// Calculates the PB chance for a [`Timer`](crate::timing::Timer). The chance
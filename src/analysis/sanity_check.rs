@@ -0,0 +1,238 @@
+//! Checks a [`Run`] for inconsistencies, such as negative segment times,
+//! segment history elements that don't belong to any attempt, decreasing
+//! split times and best segments that are slower than a segment time in the
+//! history. Each issue found can be repaired through the
+//! [`RunEditor`](crate::RunEditor).
+
+use crate::{Run, TimeSpan, TimingMethod, platform::prelude::*};
+use hashbrown::HashSet;
+
+/// An inconsistency found in a [`Run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// A segment time stored in a segment's history is negative. It is
+    /// repaired by removing the time from the history element.
+    NegativeSegmentTime {
+        /// The index of the segment.
+        segment_index: usize,
+        /// The index of the attempt the segment time belongs to.
+        attempt_index: i32,
+        /// The timing method of the segment time.
+        method: TimingMethod,
+    },
+    /// The best segment time of a segment is negative. It is repaired by
+    /// removing the best segment time.
+    NegativeBestSegmentTime {
+        /// The index of the segment.
+        segment_index: usize,
+        /// The timing method of the best segment time.
+        method: TimingMethod,
+    },
+    /// The segment histories contain elements for an attempt that is not
+    /// part of the attempt history. It is repaired by removing these
+    /// elements from all the segment histories.
+    UnattachedSegmentHistory {
+        /// The index of the attempt that doesn't exist.
+        attempt_index: i32,
+    },
+    /// A split time of a custom comparison is earlier than a split time of a
+    /// previous segment. It is repaired by removing the split time.
+    NonMonotonicSplitTime {
+        /// The name of the comparison.
+        comparison: String,
+        /// The index of the segment.
+        segment_index: usize,
+        /// The timing method of the split time.
+        method: TimingMethod,
+    },
+    /// The best segment time of a segment is slower than a segment time
+    /// stored in its history or the segment time of the Personal Best. It is
+    /// repaired by using the faster segment time as the best segment time.
+    BestSegmentNotBest {
+        /// The index of the segment.
+        segment_index: usize,
+        /// The timing method of the best segment time.
+        method: TimingMethod,
+        /// The fastest segment time that is known for the segment.
+        fastest: TimeSpan,
+    },
+}
+
+/// A report about all the inconsistencies found in a [`Run`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The issues that were found.
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    /// Returns `true` if no issues were found.
+    pub const fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Checks the run for inconsistencies for all the timing methods.
+pub fn check(run: &Run) -> Report {
+    let mut issues = Vec::new();
+
+    for method in TimingMethod::all() {
+        for (segment_index, segment) in run.segments().iter().enumerate() {
+            for &(attempt_index, time) in segment.segment_history() {
+                if time[method].is_some_and(|t| t < TimeSpan::zero()) {
+                    issues.push(Issue::NegativeSegmentTime {
+                        segment_index,
+                        attempt_index,
+                        method,
+                    });
+                }
+            }
+
+            if segment.best_segment_time()[method].is_some_and(|t| t < TimeSpan::zero()) {
+                issues.push(Issue::NegativeBestSegmentTime {
+                    segment_index,
+                    method,
+                });
+            }
+        }
+
+        for comparison in run.custom_comparisons() {
+            let mut previous = TimeSpan::zero();
+            for (segment_index, segment) in run.segments().iter().enumerate() {
+                if let Some(time) = segment.comparison(comparison)[method] {
+                    if time < previous {
+                        issues.push(Issue::NonMonotonicSplitTime {
+                            comparison: comparison.clone(),
+                            segment_index,
+                            method,
+                        });
+                    } else {
+                        previous = time;
+                    }
+                }
+            }
+        }
+
+        let mut previous_pb_time = Some(TimeSpan::zero());
+        for (segment_index, segment) in run.segments().iter().enumerate() {
+            let pb_time = segment.personal_best_split_time()[method];
+            let pb_segment = catch! { pb_time? - previous_pb_time? };
+            previous_pb_time = pb_time;
+
+            let Some(best_segment) = segment.best_segment_time()[method] else {
+                continue;
+            };
+
+            let fastest = segment
+                .segment_history()
+                .iter()
+                .filter_map(|(_, time)| time[method])
+                .chain(pb_segment)
+                .filter(|&t| t >= TimeSpan::zero())
+                .min();
+
+            if let Some(fastest) = fastest
+                && fastest < best_segment
+            {
+                issues.push(Issue::BestSegmentNotBest {
+                    segment_index,
+                    method,
+                    fastest,
+                });
+            }
+        }
+    }
+
+    let attempts: HashSet<i32> = run.attempt_history().iter().map(|a| a.index()).collect();
+    let mut unattached = HashSet::new();
+    for segment in run.segments() {
+        for &(attempt_index, _) in segment.segment_history().iter_actual_runs() {
+            if !attempts.contains(&attempt_index) {
+                unattached.insert(attempt_index);
+            }
+        }
+    }
+    let mut unattached: Vec<i32> = unattached.into_iter().collect();
+    unattached.sort_unstable();
+    issues.extend(
+        unattached
+            .into_iter()
+            .map(|attempt_index| Issue::UnattachedSegmentHistory { attempt_index }),
+    );
+
+    Report { issues }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Time, comparison::personal_best, util::tests_helper::*};
+
+    fn run() -> Run {
+        let mut timer = create_timer(&["A", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+        run_with_splits(&mut timer, &[12.0, 21.0]);
+        timer.into_run(true)
+    }
+
+    #[test]
+    fn consistent_run_has_no_issues() {
+        assert!(check(&run()).is_empty());
+    }
+
+    #[test]
+    fn finds_negative_times() {
+        let mut run = run();
+        run.segment_mut(1)
+            .segment_history_mut()
+            .get_mut(2)
+            .unwrap()
+            .game_time = Some(span(-1.0));
+        run.segment_mut(0).best_segment_time_mut().game_time = Some(span(-2.0));
+
+        let report = check(&run);
+        assert!(report.issues.contains(&Issue::NegativeSegmentTime {
+            segment_index: 1,
+            attempt_index: 2,
+            method: TimingMethod::GameTime,
+        }));
+        assert!(report.issues.contains(&Issue::NegativeBestSegmentTime {
+            segment_index: 0,
+            method: TimingMethod::GameTime,
+        }));
+    }
+
+    #[test]
+    fn finds_unattached_segment_history() {
+        let mut run = run();
+        run.segment_mut(0)
+            .segment_history_mut()
+            .insert(7, Time::new().with_game_time(Some(span(11.0))));
+
+        assert_eq!(
+            check(&run).issues,
+            [Issue::UnattachedSegmentHistory { attempt_index: 7 }]
+        );
+    }
+
+    #[test]
+    fn finds_non_monotonic_split_times_and_slow_best_segments() {
+        let mut run = run();
+        run.segment_mut(1)
+            .comparison_mut(personal_best::NAME)
+            .game_time = Some(span(5.0));
+        run.segment_mut(1).best_segment_time_mut().game_time = Some(span(10.0));
+
+        let report = check(&run);
+        assert!(report.issues.contains(&Issue::NonMonotonicSplitTime {
+            comparison: personal_best::NAME.into(),
+            segment_index: 1,
+            method: TimingMethod::GameTime,
+        }));
+        assert!(report.issues.contains(&Issue::BestSegmentNotBest {
+            segment_index: 1,
+            method: TimingMethod::GameTime,
+            fastest: span(9.0),
+        }));
+    }
+}
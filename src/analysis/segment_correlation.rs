@@ -0,0 +1,233 @@
+//! Provides functionality to calculate how the segment times of the different
+//! segments of a [`Run`] correlate with each other across attempts. A strong
+//! positive correlation between two segments means that a slow time on one of
+//! them tends to come with a slow time on the other, which may for example be
+//! caused by RNG that carries over from one segment to the next.
+//!
+//! The correlations are calculated as the Pearson correlation coefficient and
+//! are provided as a matrix that frontends can visualize. The segment
+//! histories are walked in place, so apart from the matrix itself, no memory
+//! is allocated.
+
+use crate::{
+    Run, Segment, TimingMethod,
+    platform::{math::f64::sqrt, prelude::*},
+};
+use core::iter::Peekable;
+
+/// The minimum number of attempts that need to have a segment time for both
+/// segments for their correlation to be calculated.
+pub const MIN_SAMPLES: usize = 3;
+
+/// A square matrix that stores the correlation between every pair of segments
+/// of a [`Run`]. Each correlation is a value from -1 to 1. A value close to 1
+/// means that slow times on one segment tend to come with slow times on the
+/// other segment, while a value close to -1 means that a slow time on one
+/// segment tends to come with a fast time on the other segment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CorrelationMatrix {
+    len: usize,
+    values: Vec<Option<f64>>,
+}
+
+impl CorrelationMatrix {
+    /// Creates a new empty correlation matrix. Use [`update`](Self::update) to
+    /// calculate the correlations for a [`Run`].
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            values: Vec::new(),
+        }
+    }
+
+    /// Recalculates all the correlations for the [`Run`] and timing method
+    /// provided. The memory of the matrix is reused.
+    pub fn update(&mut self, run: &Run, method: TimingMethod) {
+        let segments = run.segments_without_excluded_attempts();
+        self.len = segments.len();
+        self.values.clear();
+        self.values.resize(self.len * self.len, None);
+
+        for a in 0..self.len {
+            self.values[a * self.len + a] = Some(1.0);
+            for b in a + 1..self.len {
+                let correlation = correlate(&segments, a, b, method);
+                self.values[a * self.len + b] = correlation;
+                self.values[b * self.len + a] = correlation;
+            }
+        }
+    }
+
+    /// Returns the number of segments, which is both the number of rows and
+    /// the number of columns of the matrix.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the matrix doesn't contain any segments.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the correlation between the two segments specified by their
+    /// indices. If there are fewer than [`MIN_SAMPLES`] attempts with segment
+    /// times for both segments or if either of the segments always had the
+    /// same segment time, there is no correlation and `None` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the indices is out of bounds.
+    pub fn get(&self, a: usize, b: usize) -> Option<f64> {
+        assert!(a < self.len && b < self.len);
+        self.values[a * self.len + b]
+    }
+
+    /// Returns the correlations of the segment specified by its index with
+    /// all the segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn row(&self, index: usize) -> &[Option<f64>] {
+        &self.values[index * self.len..][..self.len]
+    }
+
+    /// Returns the pair of different segments with the strongest positive
+    /// correlation along with their correlation.
+    pub fn strongest(&self) -> Option<(usize, usize, f64)> {
+        (0..self.len)
+            .flat_map(|a| (a + 1..self.len).map(move |b| (a, b)))
+            .filter_map(|(a, b)| Some((a, b, self.get(a, b)?)))
+            .max_by(|(_, _, x), (_, _, y)| x.total_cmp(y))
+    }
+}
+
+/// Calculates the correlations between all the segments of the [`Run`] for
+/// the timing method specified.
+pub fn calculate(run: &Run, method: TimingMethod) -> CorrelationMatrix {
+    let mut matrix = CorrelationMatrix::new();
+    matrix.update(run, method);
+    matrix
+}
+
+/// Returns an iterator over the attempt indices and segment times of the
+/// segment. Only segment times of actual attempts that cover exactly this
+/// segment are yielded, so combined segment times of skipped segments are
+/// ignored.
+fn segment_times(
+    segments: &[Segment],
+    index: usize,
+    method: TimingMethod,
+) -> impl Iterator<Item = (i32, f64)> + '_ {
+    let previous = index.checked_sub(1).map(|i| &segments[i]);
+    segments[index]
+        .segment_history()
+        .iter_actual_runs()
+        .filter_map(move |&(id, time)| {
+            let time = time[method]?;
+            if let Some(previous) = previous
+                && previous.segment_history().get(id)?[method].is_none()
+            {
+                return None;
+            }
+            Some((id, time.total_seconds()))
+        })
+}
+
+fn correlate(segments: &[Segment], a: usize, b: usize, method: TimingMethod) -> Option<f64> {
+    let mut a_times = segment_times(segments, a, method).peekable();
+    let mut b_times = segment_times(segments, b, method).peekable();
+
+    let (mut n, mut sum_a, mut sum_b) = (0usize, 0.0, 0.0);
+    let (mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0);
+
+    while let Some((x, y)) = next_pair(&mut a_times, &mut b_times) {
+        n += 1;
+        sum_a += x;
+        sum_b += y;
+        sum_aa += x * x;
+        sum_bb += y * y;
+        sum_ab += x * y;
+    }
+
+    if n < MIN_SAMPLES {
+        return None;
+    }
+
+    let n = n as f64;
+    let covariance = n * sum_ab - sum_a * sum_b;
+    let variance_a = n * sum_aa - sum_a * sum_a;
+    let variance_b = n * sum_bb - sum_b * sum_b;
+
+    if variance_a <= 0.0 || variance_b <= 0.0 {
+        return None;
+    }
+
+    Some((covariance / sqrt(variance_a * variance_b)).clamp(-1.0, 1.0))
+}
+
+/// Walks both sorted segment histories in lockstep and returns the next pair
+/// of segment times that belong to the same attempt.
+fn next_pair<A, B>(a: &mut Peekable<A>, b: &mut Peekable<B>) -> Option<(f64, f64)>
+where
+    A: Iterator<Item = (i32, f64)>,
+    B: Iterator<Item = (i32, f64)>,
+{
+    loop {
+        let (a_id, a_time) = *a.peek()?;
+        let (b_id, b_time) = *b.peek()?;
+        match a_id.cmp(&b_id) {
+            core::cmp::Ordering::Less => {
+                a.next();
+            }
+            core::cmp::Ordering::Greater => {
+                b.next();
+            }
+            core::cmp::Ordering::Equal => {
+                a.next();
+                b.next();
+                return Some((a_time, b_time));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::tests_helper::*;
+
+    #[test]
+    fn correlated_segments() {
+        let mut timer = create_timer(&["A", "B", "C"]);
+        // B is slow whenever A is slow, C is random.
+        run_with_splits(&mut timer, &[10.0, 20.0, 25.0]);
+        run_with_splits(&mut timer, &[12.0, 24.0, 34.0]);
+        run_with_splits(&mut timer, &[11.0, 22.0, 24.0]);
+        run_with_splits(&mut timer, &[15.0, 30.0, 37.0]);
+        let matrix = calculate(timer.run(), TimingMethod::GameTime);
+
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.get(0, 0), Some(1.0));
+        assert!(matrix.get(0, 1).unwrap() > 0.99);
+        assert_eq!(matrix.get(0, 1), matrix.get(1, 0));
+        assert!(matrix.get(0, 2).unwrap() < 0.99);
+        assert_eq!(matrix.row(1)[0], matrix.get(1, 0));
+
+        let (a, b, _) = matrix.strongest().unwrap();
+        assert_eq!((a, b), (0, 1));
+    }
+
+    #[test]
+    fn not_enough_samples() {
+        let mut timer = create_timer(&["A", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+        run_with_splits(&mut timer, &[12.0, 24.0]);
+        run_with_splits_opt(&mut timer, &[None, Some(30.0)]);
+
+        let mut matrix = CorrelationMatrix::new();
+        matrix.update(timer.run(), TimingMethod::GameTime);
+        assert_eq!(matrix.get(0, 1), None);
+        assert_eq!(matrix.strongest(), None);
+    }
+}
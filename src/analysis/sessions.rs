@@ -0,0 +1,196 @@
+//! Provides functionality to group the attempt history of a [`Run`] into play
+//! sessions. A new session is considered to have started whenever the gap
+//! between two consecutive attempts exceeds a configurable threshold. For each
+//! session, aggregates such as the number of attempts, the number of completed
+//! attempts and the total playtime are calculated.
+
+use crate::{AtomicDateTime, Run, TimeSpan, platform::prelude::*};
+
+/// The settings that determine how attempts are grouped into sessions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// The maximum amount of time that may pass between the end of an attempt
+    /// and the start of the next attempt for both of them to still be
+    /// considered part of the same session.
+    pub max_gap: TimeSpan,
+    /// The minimum number of attempts a session needs to consist of. Sessions
+    /// with fewer attempts, such as a single attempt to check whether the game
+    /// still works, are not reported.
+    pub min_attempts: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            max_gap: TimeSpan::from_seconds(2.0 * 60.0 * 60.0),
+            min_attempts: 1,
+        }
+    }
+}
+
+/// A play session is a group of attempts that were played in close succession.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    /// The point in time the first attempt of the session was started at.
+    pub started: AtomicDateTime,
+    /// The point in time the last attempt of the session ended at.
+    pub ended: AtomicDateTime,
+    /// The index of the first attempt of the session in the attempt history.
+    pub first_attempt: usize,
+    /// The index of the last attempt of the session in the attempt history.
+    pub last_attempt: usize,
+    /// The number of attempts that were started during the session.
+    pub attempts: u32,
+    /// The number of attempts that were completed during the session.
+    pub completed_attempts: u32,
+    /// The time actually spent playing during the session. This is the sum of
+    /// the durations of all the attempts without the time they were paused
+    /// for. The time between the attempts is not included.
+    pub playtime: TimeSpan,
+}
+
+impl Session {
+    /// Returns the amount of time that passed from the start of the first
+    /// attempt to the end of the last attempt of the session.
+    pub fn duration(&self) -> TimeSpan {
+        self.ended - self.started
+    }
+}
+
+/// Groups the attempt history of the run into play sessions. Attempts that
+/// don't store when they were started and ended can't be associated with any
+/// session and are therefore skipped. The sessions are returned in
+/// chronological order.
+pub fn calculate(run: &Run, settings: &Settings) -> Vec<Session> {
+    let history = run.attempt_history();
+
+    let mut attempts = history
+        .iter()
+        .enumerate()
+        .filter_map(|(index, attempt)| Some((index, attempt.started()?, attempt.ended()?)))
+        .collect::<Vec<_>>();
+    attempts.sort_by_key(|&(_, started, _)| started.time);
+
+    let mut sessions: Vec<Session> = Vec::new();
+
+    for (index, started, ended) in attempts {
+        let attempt = &history[index];
+
+        let playtime = (ended - started) - attempt.pause_time().unwrap_or_default();
+        let completed = attempt.time().real_time.is_some() as u32;
+
+        if let Some(session) = sessions.last_mut()
+            && started - session.ended <= settings.max_gap
+        {
+            if ended.time > session.ended.time {
+                session.ended = ended;
+                session.last_attempt = index;
+            }
+            session.attempts += 1;
+            session.completed_attempts += completed;
+            session.playtime += playtime;
+            continue;
+        }
+
+        if let Some(session) = sessions.last()
+            && session.attempts < settings.min_attempts
+        {
+            sessions.pop();
+        }
+
+        sessions.push(Session {
+            started,
+            ended,
+            first_attempt: index,
+            last_attempt: index,
+            attempts: 1,
+            completed_attempts: completed,
+            playtime,
+        });
+    }
+
+    if let Some(session) = sessions.last()
+        && session.attempts < settings.min_attempts
+    {
+        sessions.pop();
+    }
+
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DateTime, Time};
+
+    fn at(minutes: i64) -> Option<AtomicDateTime> {
+        Some(AtomicDateTime::new(
+            DateTime::from_unix_timestamp(1_600_000_000 + minutes * 60).unwrap(),
+            false,
+        ))
+    }
+
+    fn minutes(minutes: f64) -> TimeSpan {
+        TimeSpan::from_seconds(minutes * 60.0)
+    }
+
+    fn run() -> Run {
+        let mut run = Run::new();
+        let finished = Time::new().with_real_time(Some(minutes(20.0)));
+
+        // First session
+        run.add_attempt(Time::new(), at(0), at(5), None);
+        run.add_attempt(finished, at(12), at(30), None);
+        run.add_attempt(Time::new(), at(35), at(45), Some(minutes(2.0)));
+
+        // An attempt without any dates
+        run.add_attempt(Time::new(), None, None, None);
+
+        // Second session, a day later
+        run.add_attempt(finished, at(1440), at(1460), None);
+
+        run
+    }
+
+    #[test]
+    fn groups_attempts_by_gap() {
+        let sessions = calculate(&run(), &Settings::default());
+        assert_eq!(sessions.len(), 2);
+
+        let first = sessions[0];
+        assert_eq!(first.first_attempt, 0);
+        assert_eq!(first.last_attempt, 2);
+        assert_eq!(first.attempts, 3);
+        assert_eq!(first.completed_attempts, 1);
+        assert_eq!(first.playtime, minutes(31.0));
+        assert_eq!(first.duration(), minutes(45.0));
+
+        let second = sessions[1];
+        assert_eq!(second.first_attempt, 4);
+        assert_eq!(second.attempts, 1);
+        assert_eq!(second.completed_attempts, 1);
+        assert_eq!(second.playtime, minutes(20.0));
+    }
+
+    #[test]
+    fn smaller_gap_splits_sessions() {
+        let settings = Settings {
+            max_gap: minutes(6.0),
+            ..Default::default()
+        };
+        let sessions = calculate(&run(), &settings);
+        let attempts = sessions.iter().map(|s| s.attempts).collect::<Vec<_>>();
+        assert_eq!(attempts, [1, 2, 1]);
+    }
+
+    #[test]
+    fn small_sessions_are_filtered_out() {
+        let settings = Settings {
+            min_attempts: 2,
+            ..Default::default()
+        };
+        let sessions = calculate(&run(), &settings);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].attempts, 3);
+    }
+}
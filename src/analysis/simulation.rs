@@ -0,0 +1,317 @@
+//! Provides a Monte Carlo simulator that samples the segment histories of a
+//! [`Run`] to estimate the distribution of its finish times. The distribution
+//! can be used to determine the chance of beating the Personal Best or any
+//! other goal time.
+//!
+//! The simulator is driven by a seedable random number generator, so the same
+//! seed always yields the same results. The amount of work is controlled by a
+//! budget, which is the number of runs to simulate per call to
+//! [`simulate`](Simulator::simulate). This allows spreading the simulation
+//! across multiple frames on platforms where blocking for a long time is not
+//! an option, such as the web.
+
+use crate::{
+    Run, Segment, TimeSpan, TimingMethod,
+    platform::{math::f64::round, prelude::*},
+    timing::Snapshot,
+};
+
+/// A Monte Carlo simulator for the finish times of a [`Run`]. Each simulated
+/// run consists of a randomly chosen segment time from the segment history of
+/// every remaining segment.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    segment_times: Vec<Vec<TimeSpan>>,
+    offset: TimeSpan,
+    personal_best: Option<TimeSpan>,
+    rng: Rng,
+    finish_times: Vec<TimeSpan>,
+}
+
+impl Simulator {
+    /// Creates a new simulator for the segments provided. The simulated
+    /// finish times are offset by the offset specified, which is the time
+    /// that already passed before the first of the segments. Only segment
+    /// times that cover exactly a single segment are sampled, so times of
+    /// segments that were combined because the previous segment was skipped
+    /// are ignored.
+    pub fn new(segments: &[Segment], method: TimingMethod, offset: TimeSpan, seed: u64) -> Self {
+        let segment_times = segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                segment
+                    .segment_history()
+                    .iter()
+                    .filter(|&&(id, _)| {
+                        index
+                            .checked_sub(1)
+                            .and_then(|prev| segments[prev].segment_history().get(id))
+                            .is_none_or(|time| time[method].is_some())
+                    })
+                    .filter_map(|(_, time)| time[method])
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            segment_times,
+            offset,
+            personal_best: segments
+                .last()
+                .and_then(|s| s.personal_best_split_time()[method]),
+            rng: Rng::new(seed),
+            finish_times: Vec::new(),
+        }
+    }
+
+    /// Creates a new simulator for the whole [`Run`]. No information about an
+    /// active attempt is used.
+    pub fn for_run(run: &Run, method: TimingMethod, seed: u64) -> Self {
+        Self::new(
+            &run.segments_without_excluded_attempts(),
+            method,
+            TimeSpan::zero(),
+            seed,
+        )
+    }
+
+    /// Creates a new simulator for the remainder of the current attempt of
+    /// the [`Timer`](crate::timing::Timer). Only the segments after the last
+    /// split that has a split time are simulated, starting from that split
+    /// time. If there is no attempt in progress, this is the same as
+    /// simulating the whole run.
+    pub fn for_timer(timer: &Snapshot, seed: u64) -> Self {
+        let method = timer.current_timing_method();
+        let segments = timer.run().segments();
+
+        let (index, offset) = if timer.current_phase().is_not_running() {
+            (0, TimeSpan::zero())
+        } else {
+            segments
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(i, s)| Some((i + 1, s.split_time()[method]?)))
+                .unwrap_or_default()
+        };
+
+        let mut simulator = Self::new(&segments[index..], method, offset, seed);
+        simulator.personal_best = segments
+            .last()
+            .and_then(|s| s.personal_best_split_time()[method]);
+        simulator
+    }
+
+    /// Simulates as many runs as the budget specifies. This can be called
+    /// repeatedly to refine the results. If any of the segments doesn't have
+    /// a single segment time to sample from, no runs can be simulated.
+    pub fn simulate(&mut self, budget: usize) {
+        if self.segment_times.iter().any(Vec::is_empty) {
+            return;
+        }
+
+        self.finish_times.reserve(budget);
+
+        for _ in 0..budget {
+            let mut time = self.offset;
+            for times in &self.segment_times {
+                time += times[self.rng.below(times.len())];
+            }
+            self.finish_times.push(time);
+        }
+
+        self.finish_times.sort_unstable();
+    }
+
+    /// Returns the number of runs that were simulated so far.
+    pub const fn simulated_runs(&self) -> usize {
+        self.finish_times.len()
+    }
+
+    /// Returns the finish times of all the runs simulated so far, sorted from
+    /// the fastest to the slowest.
+    pub fn finish_times(&self) -> &[TimeSpan] {
+        &self.finish_times
+    }
+
+    /// Returns the finish time at the percentile specified, which is a value
+    /// from 0 (the fastest simulated run) to 1 (the slowest simulated run).
+    /// If no runs were simulated yet, `None` is returned.
+    pub fn percentile(&self, percentile: f64) -> Option<TimeSpan> {
+        let last = self.finish_times.len().checked_sub(1)?;
+        let index = round(percentile.clamp(0.0, 1.0) * last as f64) as usize;
+        Some(self.finish_times[index])
+    }
+
+    /// Returns the average finish time of the runs simulated so far. If no
+    /// runs were simulated yet, `None` is returned.
+    pub fn mean(&self) -> Option<TimeSpan> {
+        if self.finish_times.is_empty() {
+            return None;
+        }
+        let total = self
+            .finish_times
+            .iter()
+            .map(TimeSpan::total_seconds)
+            .sum::<f64>();
+        Some(TimeSpan::from_seconds(
+            total / self.finish_times.len() as f64,
+        ))
+    }
+
+    /// Returns the chance of finishing faster than the goal time specified,
+    /// as a floating point number in the range from 0 (0%) to 1 (100%). If no
+    /// runs were simulated yet, `None` is returned.
+    pub fn goal_chance(&self, goal: TimeSpan) -> Option<f64> {
+        if self.finish_times.is_empty() {
+            return None;
+        }
+        let faster = self.finish_times.partition_point(|&t| t < goal);
+        Some(faster as f64 / self.finish_times.len() as f64)
+    }
+
+    /// Returns the chance of beating the Personal Best, as a floating point
+    /// number in the range from 0 (0%) to 1 (100%). If there is no Personal
+    /// Best, it's always a 100% chance. If no runs were simulated yet, `None`
+    /// is returned.
+    pub fn pb_chance(&self) -> Option<f64> {
+        match self.personal_best {
+            Some(pb) => self.goal_chance(pb),
+            None => (!self.finish_times.is_empty()).then_some(1.0),
+        }
+    }
+
+    /// Groups the finish times of the runs simulated so far into the number
+    /// of equally sized buckets specified. Each bucket is described by the
+    /// time it starts at and the number of simulated runs that fall into it.
+    /// The buckets are sorted from the fastest to the slowest.
+    pub fn histogram(&self, buckets: usize) -> Vec<(TimeSpan, usize)> {
+        let (Some(&min), Some(&max)) = (self.finish_times.first(), self.finish_times.last()) else {
+            return Vec::new();
+        };
+        if buckets == 0 {
+            return Vec::new();
+        }
+
+        let min_secs = min.total_seconds();
+        let width = (max.total_seconds() - min_secs) / buckets as f64;
+
+        let mut histogram = (0..buckets)
+            .map(|i| (TimeSpan::from_seconds(min_secs + width * i as f64), 0))
+            .collect::<Vec<_>>();
+
+        for time in &self.finish_times {
+            let index = if width > 0.0 {
+                (((time.total_seconds() - min_secs) / width) as usize).min(buckets - 1)
+            } else {
+                0
+            };
+            histogram[index].1 += 1;
+        }
+
+        histogram
+    }
+}
+
+/// A small SplitMix64 based random number generator. It's fast, has a tiny
+/// state and produces the same sequence on every platform for the same seed.
+#[derive(Copy, Clone, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in the range `0..n`.
+    const fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::tests_helper::*;
+
+    fn run() -> Run {
+        let mut timer = create_timer(&["A", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+        run_with_splits(&mut timer, &[12.0, 20.0]);
+        run_with_splits_opt(&mut timer, &[None, Some(25.0)]);
+        timer.into_run(true)
+    }
+
+    #[test]
+    fn simulates_all_combinations() {
+        let mut simulator = Simulator::for_run(&run(), TimingMethod::GameTime, 0);
+        simulator.simulate(1000);
+        assert_eq!(simulator.simulated_runs(), 1000);
+
+        // The combined segment of the last attempt is not sampled.
+        let possible = [span(18.0), span(20.0), span(22.0)];
+        assert!(
+            simulator
+                .finish_times()
+                .iter()
+                .all(|t| possible.contains(t))
+        );
+        assert_eq!(simulator.percentile(0.0), Some(span(18.0)));
+        assert_eq!(simulator.percentile(1.0), Some(span(22.0)));
+
+        let pb_chance = simulator.pb_chance().unwrap();
+        assert!(pb_chance > 0.15 && pb_chance < 0.35);
+        assert_eq!(simulator.goal_chance(span(30.0)), Some(1.0));
+        assert_eq!(simulator.goal_chance(span(18.0)), Some(0.0));
+
+        let histogram = simulator.histogram(2);
+        assert_eq!(histogram[0].0, span(18.0));
+        assert_eq!(histogram[0].1 + histogram[1].1, 1000);
+    }
+
+    #[test]
+    fn is_reproducible() {
+        let run = run();
+        let mut a = Simulator::for_run(&run, TimingMethod::GameTime, 42);
+        let mut b = Simulator::for_run(&run, TimingMethod::GameTime, 42);
+        a.simulate(100);
+        b.simulate(50);
+        b.simulate(50);
+        assert_eq!(a.mean(), b.mean());
+        assert_eq!(a.finish_times(), b.finish_times());
+    }
+
+    #[test]
+    fn no_data() {
+        let mut simulator = Simulator::for_run(&create_run(&["A", "B"]), TimingMethod::GameTime, 0);
+        simulator.simulate(100);
+        assert_eq!(simulator.simulated_runs(), 0);
+        assert_eq!(simulator.pb_chance(), None);
+        assert_eq!(simulator.percentile(0.5), None);
+    }
+
+    #[test]
+    fn timer() {
+        let mut timer = create_timer(&["A", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+        run_with_splits(&mut timer, &[12.0, 20.0]);
+        start_run(&mut timer);
+        timer.set_game_time(span(5.0)).unwrap();
+        timer.split().unwrap();
+
+        let mut simulator = Simulator::for_timer(&timer.snapshot(), 0);
+        simulator.simulate(100);
+        assert_eq!(simulator.percentile(0.0), Some(span(13.0)));
+        assert_eq!(simulator.percentile(1.0), Some(span(15.0)));
+        assert_eq!(simulator.pb_chance(), Some(1.0));
+    }
+}
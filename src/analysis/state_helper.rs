@@ -6,6 +6,9 @@ use crate::{
 };
 
 /// Gets the last non-live delta in the [`Run`] starting from `segment_index`.
+/// If the comparison is bound to a timing method, that timing method is used
+/// instead of the one provided. This applies to all the helper functions that
+/// take a comparison.
 ///
 /// - `run`: The current [`Run`].
 /// - `segment_index`: The split number to start checking deltas from.
@@ -19,6 +22,7 @@ pub fn last_delta(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = run.resolve_timing_method(comparison, method);
     find_previous_non_empty_split_and_comparison_time(
         &run.segments()[..=segment_index],
         comparison,
@@ -75,6 +79,7 @@ pub fn comparison_combined_segment_time(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = run.resolve_timing_method(comparison, method);
     if comparison == best_segments::NAME {
         return run.segment(segment_index).best_segment_time()[method];
     }
@@ -105,6 +110,7 @@ pub fn comparison_single_segment_time(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = run.resolve_timing_method(comparison, method);
     if comparison == best_segments::NAME {
         return run.segment(segment_index).best_segment_time()[method];
     }
@@ -216,6 +222,7 @@ pub fn previous_segment_delta(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = timer.run().resolve_timing_method(comparison, method);
     segment_delta(
         timer.run(),
         segment_index,
@@ -241,6 +248,7 @@ pub fn live_segment_delta(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = timer.run().resolve_timing_method(comparison, method);
     segment_delta(
         timer.run(),
         segment_index,
@@ -265,6 +273,7 @@ pub fn check_live_delta(
     comparison: &str,
     method: TimingMethod,
 ) -> Option<TimeSpan> {
+    let method = timer.run().resolve_timing_method(comparison, method);
     if timer.current_phase() == TimerPhase::Running || timer.current_phase() == TimerPhase::Paused {
         let current_split = timer
             .current_split()
@@ -0,0 +1,28 @@
+use crate::{
+    Run, Segment, Time, TimingMethod, analysis::state_helper::last_delta, util::tests_helper::span,
+};
+
+#[test]
+fn deltas_respect_the_bound_timing_method() {
+    let mut run = Run::new();
+    let mut segment = Segment::new("A");
+    *segment.comparison_mut("Game Time PB") = Time::new()
+        .with_real_time(Some(span(12.0)))
+        .with_game_time(Some(span(8.0)));
+    segment.set_split_time(
+        Time::new()
+            .with_real_time(Some(span(15.0)))
+            .with_game_time(Some(span(10.0))),
+    );
+    run.push_segment(segment);
+
+    let delta = last_delta(&run, 0, "Game Time PB", TimingMethod::RealTime);
+    assert_eq!(delta, Some(span(3.0)));
+
+    run.set_bound_timing_method("Game Time PB", Some(TimingMethod::GameTime));
+    let delta = last_delta(&run, 0, "Game Time PB", TimingMethod::RealTime);
+    assert_eq!(delta, Some(span(2.0)));
+
+    run.set_bound_timing_method("Game Time PB", None);
+    assert!(run.timing_method_bindings().is_empty());
+}
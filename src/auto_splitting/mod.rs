@@ -39,6 +39,15 @@
 //! #[repr(transparent)]
 //! pub struct ProcessId(u64);
 //!
+//! /// A request to read `buf_len` bytes of memory at the address given into the
+//! /// buffer given.
+//! #[repr(C)]
+//! pub struct ReadRequest {
+//!     pub address: Address,
+//!     pub buf_ptr: *mut u8,
+//!     pub buf_len: usize,
+//! }
+//!
 //! #[repr(transparent)]
 //! pub struct SettingsMap(NonZeroU64);
 //!
@@ -131,6 +140,40 @@
 //!         value_ptr: *const u8,
 //!         value_len: usize,
 //!     );
+//!     /// Gets the value of a custom variable. This includes variables that are
+//!     /// not set by the auto splitter, such as the ones set by the user. The
+//!     /// pointer needs to point to valid UTF-8 encoded text with the given
+//!     /// length. The value is written as UTF-8 encoded text into the buffer.
+//!     /// The buffer needs to be large enough to hold the value. If it's not, the
+//!     /// function returns `false` and the length of the value is written into
+//!     /// `buf_len_ptr`. If the variable doesn't exist, `false` is returned and a
+//!     /// length of 0 is written into `buf_len_ptr`.
+//!     pub fn timer_get_variable(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         buf_ptr: *mut u8,
+//!         buf_len_ptr: *mut usize,
+//!     ) -> bool;
+//!     /// Starts watching a custom variable for changes. Once per tick the value
+//!     /// of each watched variable is compared against the value it had in the
+//!     /// previous tick. Changes made by the auto splitter itself are not
+//!     /// reported. The pointer needs to point to valid UTF-8 encoded text with
+//!     /// the given length.
+//!     pub fn timer_watch_variable(key_ptr: *const u8, key_len: usize);
+//!     /// Stops watching a custom variable for changes. The pointer needs to
+//!     /// point to valid UTF-8 encoded text with the given length.
+//!     pub fn timer_unwatch_variable(key_ptr: *const u8, key_len: usize);
+//!     /// Writes the name of the next watched custom variable that changed since
+//!     /// it was last returned by this function as UTF-8 encoded text into the
+//!     /// buffer. The buffer needs to be large enough to hold the name. If it's
+//!     /// not, the function returns `false` and the length of the name is written
+//!     /// into `buf_len_ptr`. The name is then returned again by the next call.
+//!     /// If none of the watched variables changed, `false` is returned and a
+//!     /// length of 0 is written into `buf_len_ptr`.
+//!     pub fn timer_next_changed_variable(
+//!         buf_ptr: *mut u8,
+//!         buf_len_ptr: *mut usize,
+//!     ) -> bool;
 //!
 //!     /// Sets the game time.
 //!     pub safe fn timer_set_game_time(secs: i64, nanos: i32);
@@ -179,6 +222,18 @@
 //!         buf_ptr: *mut u8,
 //!         buf_len: usize,
 //!     ) -> bool;
+//!     /// Reads memory from a process at multiple addresses at once. Each of the
+//!     /// requests given writes the memory at its address to its buffer. Reads
+//!     /// that are close to each other are combined, so this is cheaper than
+//!     /// calling `process_read` for each of them. Whether each of the reads
+//!     /// succeeded is written to the results, which need to have the same
+//!     /// length as the requests. Returns the amount of reads that succeeded.
+//!     pub fn process_read_batch(
+//!         process: AttachedProcess,
+//!         requests_ptr: *const ReadRequest,
+//!         requests_len: usize,
+//!         results_ptr: *mut bool,
+//!     ) -> usize;
 //!     /// Gets the address of a module in a process. The pointer needs to point to
 //!     /// valid UTF-8 encoded text with the given length.
 //!     pub fn process_get_module_address(
@@ -193,6 +248,22 @@
 //!         name_ptr: *const u8,
 //!         name_len: usize,
 //!     ) -> Option<NonZeroU64>;
+//!     /// Scans the memory of a module in a process for a signature and returns
+//!     /// the address of the first match. The pointers need to point to valid
+//!     /// UTF-8 encoded text with the given lengths. Each byte of the signature
+//!     /// is written as two hexadecimal digits and wildcards that match any byte
+//!     /// are written as `??`, such as `48 8B ?? ?? 05`. The bytes may be
+//!     /// separated by whitespace. The result is cached until the module gets
+//!     /// loaded again, so this is cheap to call repeatedly, even if the
+//!     /// signature isn't found. The auto splitter traps if the signature is
+//!     /// invalid.
+//!     pub fn process_scan_module(
+//!         process: AttachedProcess,
+//!         name_ptr: *const u8,
+//!         name_len: usize,
+//!         signature_ptr: *const u8,
+//!         signature_len: usize,
+//!     ) -> Option<NonZeroAddress>;
 //!     /// Stores the file system path of a module in a process in the buffer
 //!     /// given. The pointer to the module name needs to point to valid UTF-8
 //!     /// encoded text with the given length. The path is a path that is
@@ -543,11 +614,40 @@
 //!         buf_ptr: *mut u8,
 //!         buf_len_ptr: *mut usize,
 //!     ) -> bool;
+//!
+//!     /// Sets an overlay widget that displays a text next to a label. Overlay
+//!     /// widgets are meant to show information about the game, such as the
+//!     /// health of a boss, in the layout of the timer. The key identifies the
+//!     /// widget. If a widget with the same key already exists, it gets replaced
+//!     /// in place. Otherwise the widget gets added after all the other widgets.
+//!     /// All three strings need to be valid UTF-8.
+//!     pub fn overlay_set_text(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         label_ptr: *const u8,
+//!         label_len: usize,
+//!         text_ptr: *const u8,
+//!         text_len: usize,
+//!     );
+//!     /// Sets an overlay widget that displays a progress bar next to a label.
+//!     /// The progress is a fraction between 0 and 1 and gets clamped to that
+//!     /// range. The key identifies the widget just like with `overlay_set_text`.
+//!     pub fn overlay_set_progress(
+//!         key_ptr: *const u8,
+//!         key_len: usize,
+//!         label_ptr: *const u8,
+//!         label_len: usize,
+//!         progress: f64,
+//!     );
+//!     /// Removes the overlay widget with the given key. Nothing happens if there
+//!     /// is no such widget.
+//!     pub fn overlay_remove(key_ptr: *const u8, key_len: usize);
 //! }
 //! ```
 //!
-//! On top of the runtime's API, there's also WASI 0.1 support. Considering WASI
-//! itself is still in preview, the API is subject to change. Auto splitters
+//! On top of the runtime's API, there's also WASI 0.1 support. Modules targeting
+//! the older `wasi_unstable` version of WASI are supported as well. Considering
+//! WASI itself is still in preview, the API is subject to change. Auto splitters
 //! using WASI may need to be recompiled in the future. Limitations of the WASI
 //! support:
 //!
@@ -562,33 +662,56 @@
 //! - There is no networking.
 //! - There is no threading.
 //! - Time and random numbers are available.
+//!
+//! # WebAssembly Components
+//!
+//! Auto splitters may also be provided as WebAssembly components. Those need to
+//! target the `auto-splitter` world of the `livesplit:auto-splitting` package,
+//! which is defined in `crates/livesplit-auto-splitting/wit/auto-splitter.wit`.
+//! It provides the same API as the `env` module, but uses the types of the
+//! component model instead of pointers into the memory of the auto splitter, so
+//! any toolchain with support for the component model can generate the
+//! bindings. The component needs to export an `update` function, but no memory.
+//! WASI 0.2 is provided to components with the same limitations as WASI 0.1 is
+//! to modules.
 
 use crate::{
     event::{self, TimerQuery},
     platform::Arc,
-    timing::TimerPhase,
+    timing::{OverlayWidget, OverlayWidgetValue, TimerPhase},
 };
 use arc_swap::ArcSwapOption;
 use livesplit_auto_splitting::{
-    AutoSplitter, CompiledAutoSplitter, Config, CreationError, LogLevel,
-    Timer as AutoSplitTimer, TimerState,
+    AutoSplitter, CompiledAutoSplitter, CreationError, LogLevel, Timer as AutoSplitTimer,
+    TimerState, overlay,
 };
-pub use livesplit_auto_splitting::{settings, wasi_path};
+pub use livesplit_auto_splitting::{Config, MemoryWatch, TickStatistics, settings, wasi_path};
 use snafu::Snafu;
 use std::{
     fmt, fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    ptr,
     sync::{
         Condvar, Mutex, RwLock,
+        atomic::{self, AtomicBool},
         mpsc::{self, Receiver, RecvTimeoutError, Sender},
     },
     thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
+/// How often the file of the auto splitter is checked for changes while hot
+/// reloading is enabled.
+const HOT_RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
 /// An error that the [`Runtime`] can return.
 #[derive(Debug, Snafu)]
 pub enum Error {
+    /// Failed creating the runtime.
+    CreationFailed {
+        /// The underlying error.
+        source: CreationError,
+    },
     /// The runtime thread unexpectedly stopped.
     ThreadStopped,
     /// Failed loading the auto splitter.
@@ -609,33 +732,114 @@ pub enum Error {
     SettingNotFound,
 }
 
+/// Configures how the [`Runtime`] deals with an auto splitter that misbehaves.
+/// An auto splitter misbehaves if it traps, exceeds one of the limits of its
+/// [`Config`] or doesn't finish a tick in time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Watchdog {
+    /// The time a single tick may take before the watchdog interrupts the auto
+    /// splitter. This is 5 seconds by default.
+    pub timeout: Duration,
+    /// What happens to an auto splitter that misbehaves.
+    pub action: WatchdogAction,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            action: WatchdogAction::Unload,
+        }
+    }
+}
+
+/// What happens to an auto splitter that misbehaves.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// The auto splitter gets unloaded.
+    #[default]
+    Unload,
+    /// The auto splitter stays loaded, but doesn't run anymore until it gets
+    /// [reloaded](Runtime::reload) or its file changes while
+    /// [hot reloading](Runtime::set_hot_reload) is enabled. Its settings can
+    /// still be accessed in the meantime.
+    Suspend,
+    /// The auto splitter gets restarted. It keeps its settings, but loses all
+    /// of its other state. If it misbehaves again before completing a single
+    /// tick, it gets suspended instead.
+    Restart,
+}
+
 /// An auto splitter runtime that allows using an auto splitter provided as a
 /// WebAssembly module to control a timer.
 pub struct Runtime<T: event::CommandSink + TimerQuery + Send + 'static> {
     shared_state: Arc<SharedState<T>>,
-    changed_sender: Sender<()>,
-    runtime: livesplit_auto_splitting::Runtime,
-    compiled_auto_splitter: RwLock<Option<CompiledAutoSplitter>>,
+    message_sender: Sender<Message>,
+    hot_reload: Mutex<Option<Sender<()>>>,
 }
 
-struct SharedState<T: 'static> {
+struct SharedState<T: event::CommandSink + TimerQuery + Send + 'static> {
     auto_splitter: ArcSwapOption<AutoSplitter<Timer<T>>>,
+    runtime: livesplit_auto_splitting::Runtime,
+    compiled_auto_splitter: RwLock<Option<CompiledAutoSplitter>>,
+    file: Mutex<Option<LoadedFile>>,
+    suspended: AtomicBool,
+    paused: AtomicBool,
+    watchdog: Watchdog,
     watchdog_state: Mutex<WatchdogState>,
     watchdog_state_update: Condvar,
 }
 
+struct LoadedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+enum Message {
+    /// The auto splitter got loaded, replaced or unloaded.
+    Changed,
+    /// The execution of the auto splitter got resumed.
+    Resumed,
+    /// A single tick is supposed to be executed while paused.
+    Step,
+}
+
 enum WatchdogState {
     Unloaded,
     Shutdown,
     Tick(Instant),
 }
 
-impl<T> SharedState<T> {
+impl<T: event::CommandSink + TimerQuery + Send> SharedState<T> {
     fn update_watchdog(&self, watchdog_state: WatchdogState) -> Result<(), ()> {
         *self.watchdog_state.lock().map_err(drop)? = watchdog_state;
         self.watchdog_state_update.notify_one();
         Ok(())
     }
+
+    fn compile(&self, path: &Path) -> Result<CompiledAutoSplitter, Error> {
+        let data = fs::read(path).map_err(|e| Error::ReadFileFailed { source: e })?;
+
+        self.runtime
+            .compile(&data)
+            .map_err(|e| Error::LoadFailed { source: e })
+    }
+
+    /// Returns the path of the file of the auto splitter if it changed since
+    /// it got compiled.
+    fn changed_file(&self) -> Option<PathBuf> {
+        let mut file = self.file.lock().unwrap();
+        let file = file.as_mut()?;
+        let modified = fs::metadata(&file.path).and_then(|m| m.modified()).ok()?;
+        if file.modified == Some(modified) {
+            return None;
+        }
+        // Even if reloading fails, it shouldn't be attempted again until the
+        // file changes again.
+        file.modified = Some(modified);
+        Some(file.path.clone())
+    }
 }
 
 impl<T: event::CommandSink + TimerQuery + Send + 'static> Drop for Runtime<T> {
@@ -658,9 +862,26 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
     /// Starts the runtime. Doesn't actually load an auto splitter until
     /// [`load`][Runtime::load] is called.
     pub fn new() -> Self {
-        let (changed_sender, changed_receiver) = mpsc::channel();
+        // TODO: unwrap?
+        Self::with_config(Config::default(), Watchdog::default()).unwrap()
+    }
+
+    /// Starts the runtime with the given configuration, which allows limiting
+    /// the resources that the auto splitters may use. Doesn't actually load an
+    /// auto splitter until [`load`][Runtime::load] is called.
+    pub fn with_config(config: Config, watchdog: Watchdog) -> Result<Self, Error> {
+        let runtime = livesplit_auto_splitting::Runtime::new(config)
+            .map_err(|source| Error::CreationFailed { source })?;
+
+        let (message_sender, message_receiver) = mpsc::channel();
         let shared_state = Arc::new(SharedState {
             auto_splitter: ArcSwapOption::from(None),
+            runtime,
+            compiled_auto_splitter: RwLock::new(None),
+            file: Mutex::new(None),
+            suspended: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            watchdog,
             watchdog_state: Mutex::new(WatchdogState::Unloaded),
             watchdog_state_update: Condvar::new(),
         });
@@ -670,7 +891,7 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
             .spawn({
                 let shared_state = shared_state.clone();
                 move || {
-                    run(shared_state, changed_receiver);
+                    run(shared_state, message_receiver);
                 }
             })
             .unwrap();
@@ -680,30 +901,25 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
             .spawn({
                 let shared_state = shared_state.clone();
                 move || {
-                    watchdog(shared_state);
+                    self::watchdog(shared_state);
                 }
             })
             .unwrap();
 
-        Self {
+        Ok(Self {
             shared_state,
-            changed_sender,
-            // TODO: unwrap?
-            runtime: livesplit_auto_splitting::Runtime::new(Config::default()).unwrap(),
-            compiled_auto_splitter: RwLock::new(None),
-        }
+            message_sender,
+            hot_reload: Mutex::new(None),
+        })
     }
 
     /// Attempts to load a wasm file containing an auto splitter module.
     pub fn load(&self, path: PathBuf, timer: T) -> Result<(), Error> {
-        let data = fs::read(path).map_err(|e| Error::ReadFileFailed { source: e })?;
-
-        let compiled_auto_splitter = self
-            .runtime
-            .compile(&data)
-            .map_err(|e| Error::LoadFailed { source: e })?;
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let compiled_auto_splitter = self.shared_state.compile(&path)?;
         self.instantiate(&compiled_auto_splitter, timer)?;
-        *self.compiled_auto_splitter.write().unwrap() = Some(compiled_auto_splitter);
+        *self.shared_state.compiled_auto_splitter.write().unwrap() = Some(compiled_auto_splitter);
+        *self.shared_state.file.lock().unwrap() = Some(LoadedFile { path, modified });
         Ok(())
     }
 
@@ -715,15 +931,15 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
     ) -> Result<(), Error> {
         let settings_map = timer.get_timer().run().auto_splitter_settings_map_load();
         let auto_splitter = compiled_auto_splitter
-            .instantiate(Timer(timer), settings_map, None)
+            .instantiate(Timer(timer, Vec::new()), settings_map, None)
             .map_err(|e| Error::LoadFailed { source: e })?;
 
         self.shared_state
             .auto_splitter
             .store(Some(Arc::new(auto_splitter)));
 
-        self.changed_sender
-            .send(())
+        self.message_sender
+            .send(Message::Changed)
             .map_err(|_| Error::ThreadStopped)
     }
 
@@ -733,20 +949,169 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
     pub fn unload(&self) -> Result<(), Error> {
         self.shared_state.auto_splitter.store(None);
 
-        self.changed_sender
-            .send(())
+        self.message_sender
+            .send(Message::Changed)
             .map_err(|_| Error::ThreadStopped)
     }
 
     /// Reloads the auto splitter without re-compiling.
     pub fn reload(&self, timer: T) -> Result<(), Error> {
         self.unload()?;
-        if let Some(compiled_auto_splitter) = self.compiled_auto_splitter.read().unwrap().as_ref() {
+        if let Some(compiled_auto_splitter) = self
+            .shared_state
+            .compiled_auto_splitter
+            .read()
+            .unwrap()
+            .as_ref()
+        {
             self.instantiate(compiled_auto_splitter, timer)?;
         }
         Ok(())
     }
 
+    /// Returns whether the auto splitter got suspended, because it misbehaved.
+    /// It doesn't run until it gets [reloaded](Self::reload) or its file
+    /// changes while [hot reloading](Self::set_hot_reload) is enabled.
+    pub fn is_suspended(&self) -> bool {
+        self.shared_state.suspended.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Enables or disables hot reloading. While enabled, the file that the
+    /// auto splitter got [loaded](Self::load) from is watched for changes.
+    /// Whenever it changes, the auto splitter gets compiled again and replaces
+    /// the loaded auto splitter. The timer, the settings and the debugging
+    /// state carry over to the new auto splitter, but it needs to attach to
+    /// the processes again. If the new auto splitter fails to compile, the
+    /// previous one keeps running. If it compiles, but fails to start, the
+    /// auto splitter gets suspended until its file changes again. This is
+    /// disabled by default.
+    pub fn set_hot_reload(&self, hot_reload: bool) {
+        let mut sender = self.hot_reload.lock().unwrap();
+        if !hot_reload {
+            // Dropping the sender stops the thread.
+            *sender = None;
+        } else if sender.is_none() {
+            let (hot_reload_sender, hot_reload_receiver) = mpsc::channel();
+            thread::Builder::new()
+                .name("Auto Splitting Hot Reload".into())
+                .spawn({
+                    let shared_state = self.shared_state.clone();
+                    let message_sender = self.message_sender.clone();
+                    move || {
+                        self::hot_reload(shared_state, message_sender, hot_reload_receiver);
+                    }
+                })
+                .unwrap();
+            *sender = Some(hot_reload_sender);
+        }
+    }
+
+    /// Returns whether [hot reloading](Self::set_hot_reload) is enabled.
+    pub fn is_hot_reload_enabled(&self) -> bool {
+        self.hot_reload.lock().unwrap().is_some()
+    }
+
+    /// Pauses the execution of the auto splitter. It doesn't run until it gets
+    /// [resumed](Self::resume), but single ticks can still be executed via
+    /// [`step`](Self::step). The auto splitter stays paused when it gets
+    /// replaced.
+    pub fn pause(&self) {
+        self.shared_state
+            .paused
+            .store(true, atomic::Ordering::Relaxed);
+    }
+
+    /// Resumes the execution of the auto splitter after it got
+    /// [paused](Self::pause).
+    pub fn resume(&self) -> Result<(), Error> {
+        self.shared_state
+            .paused
+            .store(false, atomic::Ordering::Relaxed);
+        self.message_sender
+            .send(Message::Resumed)
+            .map_err(|_| Error::ThreadStopped)
+    }
+
+    /// Executes a single tick of the auto splitter while it is
+    /// [paused](Self::pause). This does nothing if it isn't paused.
+    pub fn step(&self) -> Result<(), Error> {
+        self.message_sender
+            .send(Message::Step)
+            .map_err(|_| Error::ThreadStopped)
+    }
+
+    /// Returns whether the execution of the auto splitter is
+    /// [paused](Self::pause).
+    pub fn is_paused(&self) -> bool {
+        self.shared_state.paused.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Enables or disables tracing the ticks of the auto splitter. While
+    /// enabled, a trace message is logged for every tick, describing how long
+    /// it took, how often the auto splitter called into the runtime and how
+    /// much memory it read. Returns [`None`] if there is no auto splitter
+    /// loaded.
+    pub fn set_tracing(&self, tracing: bool) -> Option<()> {
+        self.shared_state
+            .auto_splitter
+            .load()
+            .as_ref()?
+            .set_tracing(tracing);
+        Some(())
+    }
+
+    /// Starts watching the memory range of the given length at the given
+    /// address. Whenever the auto splitter reads memory overlapping the range,
+    /// the bytes it read get recorded. They can be accessed via
+    /// [`memory_watches`](Self::memory_watches). Returns [`None`] if there is
+    /// no auto splitter loaded.
+    pub fn watch_memory(&self, address: u64, len: usize) -> Option<()> {
+        self.shared_state
+            .auto_splitter
+            .load()
+            .as_ref()?
+            .watch_memory(address, len);
+        Some(())
+    }
+
+    /// Stops watching the memory range at the given address. Returns whether
+    /// a memory range was watched at that address or [`None`] if there is no
+    /// auto splitter loaded.
+    pub fn unwatch_memory(&self, address: u64) -> Option<bool> {
+        Some(
+            self.shared_state
+                .auto_splitter
+                .load()
+                .as_ref()?
+                .unwatch_memory(address),
+        )
+    }
+
+    /// Accesses all the memory ranges that are being watched, including the
+    /// bytes that the auto splitter read most recently. Returns [`None`] if
+    /// there is no auto splitter loaded.
+    pub fn memory_watches(&self) -> Option<Vec<MemoryWatch>> {
+        Some(
+            self.shared_state
+                .auto_splitter
+                .load()
+                .as_ref()?
+                .memory_watches(),
+        )
+    }
+
+    /// Accesses statistics about how long the recent ticks of the auto splitter
+    /// took. Returns [`None`] if there is no auto splitter loaded.
+    pub fn tick_statistics(&self) -> Option<TickStatistics> {
+        Some(
+            self.shared_state
+                .auto_splitter
+                .load()
+                .as_ref()?
+                .tick_statistics(),
+        )
+    }
+
     /// Accesses a copy of the currently stored settings. The auto splitter can
     /// change these at any time. If you intend to make modifications to the
     /// settings, you need to set them again via
@@ -811,8 +1176,21 @@ impl<T: event::CommandSink + TimerQuery + Send + 'static> Runtime<T> {
 }
 
 // This newtype is required because [`SharedTimer`](crate::timing::SharedTimer)
-// is an Arc<RwLock<T>>, so we can't implement the trait directly on it.
-struct Timer<E>(E);
+// is an Arc<RwLock<T>>, so we can't implement the trait directly on it. It also
+// remembers the overlay widgets that it set last, so it can remove them from
+// the timer once the auto splitter is gone.
+struct Timer<E: event::CommandSink + TimerQuery + Send + 'static>(E, Vec<OverlayWidget>);
+
+impl<E: event::CommandSink + TimerQuery + Send + 'static> Drop for Timer<E> {
+    fn drop(&mut self) {
+        // The widgets may already have been replaced by someone else, such as
+        // an auto splitter that got loaded in the meantime.
+        let is_unchanged = !self.1.is_empty() && self.0.get_timer().overlay_widgets() == self.1;
+        if is_unchanged {
+            drop(self.0.set_overlay_widgets(Vec::new()));
+        }
+    }
+}
 
 impl<E: event::CommandSink + TimerQuery + Send + 'static> AutoSplitTimer for Timer<E> {
     fn state(&self) -> TimerState {
@@ -879,6 +1257,36 @@ impl<E: event::CommandSink + TimerQuery + Send + 'static> AutoSplitTimer for Tim
         drop(self.0.set_custom_variable(name.into(), value.into()));
     }
 
+    fn variable(&self, name: &str) -> Option<String> {
+        self.0
+            .get_timer()
+            .run()
+            .metadata()
+            .custom_variable_value(name)
+            .map(Into::into)
+    }
+
+    fn set_overlay_widgets(&mut self, widgets: &[overlay::Widget]) {
+        let widgets: Vec<_> = widgets
+            .iter()
+            .filter_map(|widget| {
+                let value = match &widget.value {
+                    overlay::WidgetValue::Text(text) => OverlayWidgetValue::Text(text.to_string()),
+                    &overlay::WidgetValue::Progress(progress) => {
+                        OverlayWidgetValue::Progress(progress)
+                    }
+                    _ => return None,
+                };
+                Some(OverlayWidget {
+                    label: widget.label.to_string(),
+                    value,
+                })
+            })
+            .collect();
+        self.1.clone_from(&widgets);
+        drop(self.0.set_overlay_widgets(widgets));
+    }
+
     fn log_auto_splitter(&mut self, message: fmt::Arguments) {
         log::info!(target: "Auto Splitter", "{message}");
     }
@@ -897,7 +1305,7 @@ impl<E: event::CommandSink + TimerQuery + Send + 'static> AutoSplitTimer for Tim
 
 fn run<T: event::CommandSink + TimerQuery + Send>(
     shared_state: Arc<SharedState<T>>,
-    changed_receiver: Receiver<()>,
+    message_receiver: Receiver<Message>,
 ) {
     'back_to_not_having_an_auto_splitter: loop {
         if shared_state
@@ -908,13 +1316,17 @@ fn run<T: event::CommandSink + TimerQuery + Send>(
         }
 
         while shared_state.auto_splitter.load().is_none() {
-            if changed_receiver.recv().is_err() {
+            if message_receiver.recv().is_err() {
                 return;
             }
         }
 
         log::info!(target: "Auto Splitter", "Loaded auto splitter");
+        shared_state
+            .suspended
+            .store(false, atomic::Ordering::Relaxed);
         let mut next_tick = Instant::now();
+        let mut restarted = false;
 
         if shared_state
             .update_watchdog(WatchdogState::Tick(next_tick))
@@ -924,8 +1336,22 @@ fn run<T: event::CommandSink + TimerQuery + Send>(
         }
 
         loop {
-            let result =
-                changed_receiver.recv_timeout(next_tick.saturating_duration_since(Instant::now()));
+            let paused = shared_state.paused.load(atomic::Ordering::Relaxed);
+            let result = if paused {
+                // The watchdog must not interrupt the auto splitter for not
+                // running while it's paused.
+                if shared_state
+                    .update_watchdog(WatchdogState::Unloaded)
+                    .is_err()
+                {
+                    return;
+                }
+                message_receiver
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                message_receiver.recv_timeout(next_tick.saturating_duration_since(Instant::now()))
+            };
 
             let Some(auto_splitter) = &*shared_state.auto_splitter.load() else {
                 log::info!(target: "Auto Splitter", "Unloaded");
@@ -934,19 +1360,38 @@ fn run<T: event::CommandSink + TimerQuery + Send>(
             let auto_splitter = &**auto_splitter;
 
             match result {
-                Ok(()) => {
+                Ok(Message::Changed) => {
                     log::info!(target: "Auto Splitter", "Replaced auto splitter");
-                    next_tick = Instant::now();
-                    if shared_state
-                        .update_watchdog(WatchdogState::Tick(next_tick))
-                        .is_err()
-                    {
-                        return;
+                    shared_state
+                        .suspended
+                        .store(false, atomic::Ordering::Relaxed);
+                    restarted = false;
+                    if paused {
+                        continue;
+                    }
+                }
+                Ok(Message::Resumed) => {}
+                Ok(Message::Step) => {
+                    if !paused {
+                        continue;
                     }
                 }
                 Err(RecvTimeoutError::Disconnected) => return,
                 Err(RecvTimeoutError::Timeout) => {
                     // Actually the default happy path.
+                    if shared_state.paused.load(atomic::Ordering::Relaxed) {
+                        continue;
+                    }
+                }
+            }
+
+            if !matches!(result, Err(RecvTimeoutError::Timeout)) {
+                next_tick = Instant::now();
+                if shared_state
+                    .update_watchdog(WatchdogState::Tick(next_tick))
+                    .is_err()
+                {
+                    return;
                 }
             }
 
@@ -954,10 +1399,68 @@ fn run<T: event::CommandSink + TimerQuery + Send>(
             // released early.
             let result = auto_splitter.lock().update();
 
-            if let Err(e) = result {
-                shared_state.auto_splitter.store(None);
-                log::error!(target: "Auto Splitter", "Unloaded, because the script trapped: {:?}", e);
-                continue 'back_to_not_having_an_auto_splitter;
+            match result {
+                Ok(()) => restarted = false,
+                Err(e) => {
+                    if !shared_state
+                        .auto_splitter
+                        .load()
+                        .as_deref()
+                        .is_some_and(|current| ptr::eq(current, auto_splitter))
+                    {
+                        // The auto splitter got interrupted, because it got
+                        // replaced or unloaded, which the next iteration
+                        // handles.
+                        continue;
+                    }
+                    let action = match shared_state.watchdog.action {
+                        WatchdogAction::Restart if restarted => WatchdogAction::Suspend,
+                        action => action,
+                    };
+                    match action {
+                        WatchdogAction::Unload => {
+                            shared_state.auto_splitter.store(None);
+                            log::error!(target: "Auto Splitter", "Unloaded, because the script trapped: {:?}", e);
+                            continue 'back_to_not_having_an_auto_splitter;
+                        }
+                        WatchdogAction::Suspend => {
+                            shared_state
+                                .suspended
+                                .store(true, atomic::Ordering::Relaxed);
+                            log::error!(target: "Auto Splitter", "Suspended, because the script trapped: {:?}", e);
+                            if shared_state
+                                .update_watchdog(WatchdogState::Unloaded)
+                                .is_err()
+                            {
+                                return;
+                            }
+                            // The auto splitter only runs again once it got
+                            // replaced.
+                            loop {
+                                match message_receiver.recv() {
+                                    Ok(Message::Changed) => {
+                                        continue 'back_to_not_having_an_auto_splitter;
+                                    }
+                                    Ok(_) => {}
+                                    Err(_) => return,
+                                }
+                            }
+                        }
+                        WatchdogAction::Restart => {
+                            log::error!(target: "Auto Splitter", "Restarting, because the script trapped: {:?}", e);
+                            if let Err(e) = auto_splitter.lock().restart() {
+                                shared_state.auto_splitter.store(None);
+                                log::error!(target: "Auto Splitter", "Unloaded, because restarting failed: {:?}", e);
+                                continue 'back_to_not_having_an_auto_splitter;
+                            }
+                            restarted = true;
+                            // The tick may have taken long enough for the
+                            // watchdog to interrupt the restarted auto splitter
+                            // right away otherwise.
+                            next_tick = Instant::now();
+                        }
+                    }
+                }
             }
 
             next_tick = next_tick
@@ -974,8 +1477,48 @@ fn run<T: event::CommandSink + TimerQuery + Send>(
     }
 }
 
+fn hot_reload<T: event::CommandSink + TimerQuery + Send>(
+    shared_state: Arc<SharedState<T>>,
+    message_sender: Sender<Message>,
+    receiver: Receiver<()>,
+) {
+    while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(HOT_RELOAD_INTERVAL) {
+        let Some(path) = shared_state.changed_file() else {
+            continue;
+        };
+
+        log::info!(target: "Auto Splitter", "Reloading, because {} changed", path.display());
+        let compiled_auto_splitter = match shared_state.compile(&path) {
+            Ok(compiled_auto_splitter) => compiled_auto_splitter,
+            Err(e) => {
+                log::error!(target: "Auto Splitter", "Failed hot reloading: {:?}", e);
+                continue;
+            }
+        };
+
+        let auto_splitter = shared_state.auto_splitter.load_full();
+        let result = auto_splitter
+            .map(|auto_splitter| auto_splitter.lock().replace(&compiled_auto_splitter));
+        *shared_state.compiled_auto_splitter.write().unwrap() = Some(compiled_auto_splitter);
+
+        // Only future reloads use the new auto splitter if none is loaded.
+        let Some(result) = result else {
+            continue;
+        };
+
+        if let Err(e) = result {
+            shared_state
+                .suspended
+                .store(true, atomic::Ordering::Relaxed);
+            log::error!(target: "Auto Splitter", "Suspended, because hot reloading failed: {:?}", e);
+        } else if message_sender.send(Message::Changed).is_err() {
+            return;
+        }
+    }
+}
+
 fn watchdog<T: event::CommandSink + TimerQuery + Send>(shared_state: Arc<SharedState<T>>) {
-    const TIMEOUT: Duration = Duration::from_secs(5);
+    let timeout = shared_state.watchdog.timeout;
     let mut has_timed_out = false;
 
     let Ok(mut state) = shared_state.watchdog_state.lock() else {
@@ -990,7 +1533,7 @@ fn watchdog<T: event::CommandSink + TimerQuery + Send>(shared_state: Arc<SharedS
             },
             WatchdogState::Shutdown => return,
             WatchdogState::Tick(next_tick) => {
-                let timeout_instant = next_tick.checked_add(TIMEOUT).unwrap_or(next_tick);
+                let timeout_instant = next_tick.checked_add(timeout).unwrap_or(next_tick);
                 let timeout_duration = timeout_instant.saturating_duration_since(Instant::now());
 
                 let Ok((new_state, result)) = shared_state
@@ -1002,7 +1545,7 @@ fn watchdog<T: event::CommandSink + TimerQuery + Send>(shared_state: Arc<SharedS
 
                 if result.timed_out() {
                     if !has_timed_out {
-                        log::error!(target: "Auto Splitter", "timeout, no update in {} seconds", TIMEOUT.as_secs_f32());
+                        log::error!(target: "Auto Splitter", "timeout, no update in {} seconds", timeout.as_secs_f32());
                         has_timed_out = true;
                     }
                     if let Some(auto_splitter) = &*shared_state.auto_splitter.load() {
@@ -1017,3 +1560,121 @@ fn watchdog<T: event::CommandSink + TimerQuery + Send>(shared_state: Arc<SharedS
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SharedTimer, util::tests_helper::create_timer};
+    use std::{env, fs::File};
+
+    // Starts the timer if it isn't running yet.
+    const STARTS_TIMER: &str = r#"(module
+        (import "env" "timer_get_state" (func $get_state (result i32)))
+        (import "env" "timer_start" (func $start))
+        (memory (export "memory") 1)
+        (func (export "update")
+            (if (i32.eqz (call $get_state)) (then (call $start)))))"#;
+
+    // Splits whenever the timer is running.
+    const SPLITS_TIMER: &str = r#"(module
+        (import "env" "timer_get_state" (func $get_state (result i32)))
+        (import "env" "timer_split" (func $split))
+        (memory (export "memory") 1)
+        (func (export "update")
+            (if (i32.eq (call $get_state) (i32.const 1)) (then (call $split)))))"#;
+
+    struct ScriptFile(PathBuf);
+
+    impl ScriptFile {
+        fn new(name: &str, wat: &str) -> Self {
+            let path = env::temp_dir().join(format!("{name}-{}.wasm", std::process::id()));
+            let file = Self(path);
+            file.write(&wat::parse_str(wat).unwrap(), SystemTime::UNIX_EPOCH);
+            file
+        }
+
+        // The modification time is set explicitly, so the change is detected
+        // regardless of the resolution of the file system's timestamps.
+        fn write(&self, contents: &[u8], modified: SystemTime) {
+            fs::write(&self.0, contents).unwrap();
+            File::options()
+                .write(true)
+                .open(&self.0)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+    }
+
+    impl Drop for ScriptFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[track_caller]
+    fn wait_until(mut condition: impl FnMut() -> bool) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(start.elapsed() < Duration::from_secs(10), "Timed out");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn phase(timer: &SharedTimer) -> TimerPhase {
+        timer.read().unwrap().current_phase()
+    }
+
+    #[test]
+    fn hot_reload_keeps_the_settings_and_the_timer() {
+        let timer = create_timer(&["A", "B"]).into_shared();
+        let file = ScriptFile::new("hot_reload_keeps_the_settings_and_the_timer", STARTS_TIMER);
+
+        let runtime = Runtime::new();
+        runtime.load(file.0.clone(), timer.clone()).unwrap();
+        runtime.set_hot_reload(true);
+        wait_until(|| phase(&timer) == TimerPhase::Running);
+
+        let mut settings = settings::Map::new();
+        settings.insert("key".into(), settings::Value::Bool(true));
+        runtime.set_settings_map(settings).unwrap();
+
+        file.write(&wat::parse_str(SPLITS_TIMER).unwrap(), SystemTime::now());
+
+        // The new auto splitter only splits if it sees that the previous one
+        // started the timer.
+        wait_until(|| phase(&timer) == TimerPhase::Ended);
+        assert!(!runtime.is_suspended());
+        assert!(matches!(
+            runtime.settings_map().unwrap().get("key"),
+            Some(settings::Value::Bool(true)),
+        ));
+    }
+
+    #[test]
+    fn failed_hot_reload_keeps_the_previous_auto_splitter() {
+        let timer = create_timer(&["A", "B"]).into_shared();
+        let file = ScriptFile::new(
+            "failed_hot_reload_keeps_the_previous_auto_splitter",
+            STARTS_TIMER,
+        );
+
+        let runtime = Runtime::new();
+        runtime.load(file.0.clone(), timer.clone()).unwrap();
+        runtime.set_hot_reload(true);
+        wait_until(|| phase(&timer) == TimerPhase::Running);
+
+        let modified = SystemTime::now();
+        file.write(b"not an auto splitter", modified);
+        wait_until(|| {
+            let file = runtime.shared_state.file.lock().unwrap();
+            file.as_ref().unwrap().modified == Some(modified)
+        });
+
+        // The previous auto splitter still runs, so it starts the timer again.
+        timer.write().unwrap().reset(true).unwrap();
+        wait_until(|| phase(&timer) == TimerPhase::Running);
+        assert!(!runtime.is_suspended());
+        assert!(runtime.settings_map().is_some());
+    }
+}
@@ -2,7 +2,8 @@
 //! [`Run`](crate::Run). The Average Segments are calculated through a
 //! weighted arithmetic mean that gives more recent segments a larger
 //! weight so that the Average Segments are more suited to represent
-//! the current performance of a runner.
+//! the current performance of a runner. How quickly older segments stop
+//! mattering can be configured per [`Run`](crate::Run).
 
 use super::ComparisonGenerator;
 use crate::{Attempt, Segment, TimeSpan, TimingMethod};
@@ -12,8 +13,10 @@ use crate::{Attempt, Segment, TimeSpan, TimingMethod};
 /// arithmetic mean that gives more recent segments a larger weight so that
 /// the Average Segments are more suited to represent the current performance
 /// of a runner.
-#[derive(Copy, Clone, Debug)]
-pub struct AverageSegments;
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AverageSegments {
+    weight: f64,
+}
 
 /// The short name of this comparison. Suitable for situations where not a lot
 /// of space for text is available.
@@ -21,9 +24,48 @@ pub const SHORT_NAME: &str = "Average";
 /// The name of this comparison.
 pub const NAME: &str = "Average Segments";
 
-const WEIGHT: f64 = 0.75;
+/// The weight that is used by default.
+pub const DEFAULT_WEIGHT: f64 = 0.75;
+
+impl Default for AverageSegments {
+    fn default() -> Self {
+        Self::new(DEFAULT_WEIGHT)
+    }
+}
+
+impl AverageSegments {
+    /// Creates a new Comparison Generator for the Average Segments that uses
+    /// the weight provided. The weight is the factor the weight of a segment
+    /// time gets multiplied by for each more recent segment time. A weight of
+    /// 1 means that all the segment times are weighted equally, while smaller
+    /// weights cause older segment times to stop mattering more quickly.
+    /// Weights larger than 1 are clamped to 1, while weights that are not
+    /// finite or not positive are replaced by the [`DEFAULT_WEIGHT`].
+    pub const fn new(weight: f64) -> Self {
+        let weight = if !weight.is_finite() || weight <= 0.0 {
+            DEFAULT_WEIGHT
+        } else if weight > 1.0 {
+            1.0
+        } else {
+            weight
+        };
+        Self { weight }
+    }
+
+    /// Creates a new Comparison Generator for the Average Segments that
+    /// weights all the segment times equally.
+    pub const fn unweighted() -> Self {
+        Self::new(1.0)
+    }
+
+    /// Returns the factor the weight of a segment time gets multiplied by for
+    /// each more recent segment time.
+    pub const fn weight(&self) -> f64 {
+        self.weight
+    }
+}
 
-fn generate(segments: &mut [Segment], method: TimingMethod) {
+fn generate(segments: &mut [Segment], weight: f64, method: TimingMethod) {
     let mut accumulated = Some(TimeSpan::zero());
 
     let mut previous_segment: Option<&Segment> = None;
@@ -43,7 +85,7 @@ fn generate(segments: &mut [Segment], method: TimingMethod) {
                     if !skip {
                         total_weights += current_weight;
                         total_time += current_weight * time.total_seconds();
-                        current_weight *= WEIGHT;
+                        current_weight *= weight;
                     }
                 }
             }
@@ -65,7 +107,7 @@ impl ComparisonGenerator for AverageSegments {
     }
 
     fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
-        generate(segments, TimingMethod::RealTime);
-        generate(segments, TimingMethod::GameTime);
+        generate(segments, self.weight, TimingMethod::RealTime);
+        generate(segments, self.weight, TimingMethod::GameTime);
     }
 }
@@ -1,7 +1,9 @@
 //! Defines the Comparison Generator for the Best Split Times. The Best Split
 //! Times represent the best pace that the runner was ever on up to each split
 //! in the run. The Best Split Times are calculated by taking the best split
-//! time for each individual split from all of the runner's attempts.
+//! time for each individual split from all of the runner's attempts. This
+//! includes attempts that were reset before they were finished, so the
+//! comparison shows the furthest the runner has ever been ahead at each split.
 
 use super::ComparisonGenerator;
 use crate::{Attempt, Segment, TimeSpan, TimingMethod};
@@ -9,7 +11,8 @@ use crate::{Attempt, Segment, TimeSpan, TimingMethod};
 /// The Comparison Generator for the Best Split Times. The Best Split Times
 /// represent the best pace that the runner was ever on up to each split in the
 /// run. The Best Split Times are calculated by taking the best split time for
-/// each individual split from all of the runner's attempts.
+/// each individual split from all of the runner's attempts. This includes
+/// attempts that were reset before they were finished.
 #[derive(Copy, Clone, Debug)]
 pub struct BestSplitTimes;
 
@@ -0,0 +1,127 @@
+//! Defines the Comparison Generator for calculating a comparison based on only
+//! the last few attempts of a [`Run`](crate::Run). For each segment, only the
+//! most recent segment times are considered, so an old segment history that no
+//! longer reflects the current performance of a runner doesn't influence the
+//! comparison at all. The amount of attempts and whether their average or
+//! median is used can be configured per [`Run`](crate::Run).
+
+use super::ComparisonGenerator;
+use crate::{Attempt, Segment, TimeSpan, TimingMethod, platform::prelude::*};
+
+/// The short name of this comparison. Suitable for situations where not a lot
+/// of space for text is available.
+pub const SHORT_NAME: &str = "Recent";
+/// The name of this comparison.
+pub const NAME: &str = "Last Attempts";
+
+/// The amount of attempts that are considered by default.
+pub const DEFAULT_ATTEMPTS: u32 = 10;
+
+/// Determines how the segment times of the last attempts are combined into a
+/// single segment time.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Statistic {
+    /// The arithmetic mean of the segment times is used.
+    #[default]
+    Average,
+    /// The median of the segment times is used. If there's an even amount of
+    /// segment times, the mean of the two middle segment times is used.
+    Median,
+}
+
+/// The Comparison Generator for calculating a comparison based on only the
+/// last few attempts of a [`Run`](crate::Run). For each segment, only the most
+/// recent segment times are considered. Attempts where a segment was skipped
+/// don't count towards the amount of attempts of that segment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LastAttempts {
+    /// The amount of most recent segment times considered for each segment.
+    pub attempts: u32,
+    /// How the segment times get combined.
+    pub statistic: Statistic,
+}
+
+impl Default for LastAttempts {
+    fn default() -> Self {
+        Self {
+            attempts: DEFAULT_ATTEMPTS,
+            statistic: Statistic::default(),
+        }
+    }
+}
+
+impl LastAttempts {
+    /// Creates a new Comparison Generator that considers the amount of
+    /// attempts specified and combines them using the statistic provided.
+    pub const fn new(attempts: u32, statistic: Statistic) -> Self {
+        Self {
+            attempts,
+            statistic,
+        }
+    }
+
+    fn generate(&self, segments: &mut [Segment], times: &mut Vec<TimeSpan>, method: TimingMethod) {
+        let mut accumulated = Some(TimeSpan::zero());
+
+        let mut previous_segment: Option<&Segment> = None;
+        for segment in segments {
+            if let Some(accumulated_val) = &mut accumulated {
+                times.clear();
+
+                for &(id, time) in segment.segment_history().iter_actual_runs().rev() {
+                    if times.len() >= self.attempts as usize {
+                        break;
+                    }
+                    if let Some(time) = time[method] {
+                        // Skip all the combined segments
+                        let skip = catch! {
+                            previous_segment?.segment_history().get(id)?[method].is_none()
+                        }
+                        .unwrap_or(false);
+
+                        if !skip {
+                            times.push(time);
+                        }
+                    }
+                }
+
+                if times.is_empty() {
+                    accumulated = None;
+                } else {
+                    *accumulated_val += match self.statistic {
+                        Statistic::Average => {
+                            let total = times.iter().map(|t| t.total_seconds()).sum::<f64>();
+                            TimeSpan::from_seconds(total / times.len() as f64)
+                        }
+                        Statistic::Median => {
+                            times.sort_unstable();
+                            let middle = times.len() / 2;
+                            if times.len().is_multiple_of(2) {
+                                TimeSpan::from_seconds(
+                                    0.5 * (times[middle - 1].total_seconds()
+                                        + times[middle].total_seconds()),
+                                )
+                            } else {
+                                times[middle]
+                            }
+                        }
+                    };
+                }
+            }
+            segment.comparison_mut(NAME)[method] = accumulated;
+            previous_segment = Some(&*segment);
+        }
+    }
+}
+
+impl ComparisonGenerator for LastAttempts {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
+        let times = &mut Vec::new();
+        LastAttempts::generate(self, segments, times, TimingMethod::RealTime);
+        LastAttempts::generate(self, segments, times, TimingMethod::GameTime);
+    }
+}
@@ -11,15 +11,17 @@ pub mod balanced_pb;
 pub mod best_segments;
 pub mod best_split_times;
 pub mod goal;
+pub mod last_attempts;
 pub mod latest_run;
 pub mod median_segments;
 pub mod none;
+pub mod registry;
 pub mod worst_segments;
 
 pub use self::{
     average_segments::AverageSegments, balanced_pb::BalancedPB, best_segments::BestSegments,
-    best_split_times::BestSplitTimes, latest_run::LatestRun, median_segments::MedianSegments,
-    none::None, worst_segments::WorstSegments,
+    best_split_times::BestSplitTimes, last_attempts::LastAttempts, latest_run::LatestRun,
+    median_segments::MedianSegments, none::None, worst_segments::WorstSegments,
 };
 
 use crate::{platform::prelude::*, Attempt, Segment, Timer};
@@ -61,6 +63,20 @@ pub trait ComparisonGenerator: Debug + Sync + Send + ComparisonGeneratorClone {
     /// provided, in case the comparison generator requires information from the
     /// previous attempts.
     fn generate(&mut self, segments: &mut [Segment], attempts: &[Attempt]);
+    /// Serializes the settings of the Comparison Generator, so they can be
+    /// stored in the splits file. The settings are provided to the
+    /// [`ComparisonGeneratorFactory`](registry::ComparisonGeneratorFactory)
+    /// when the Comparison Generator is restored. By default the Comparison
+    /// Generator doesn't have any settings.
+    fn settings(&self) -> Option<String> {
+        Option::None
+    }
+    /// Returns `true` if this is an [`Unknown`](registry::Unknown) Comparison
+    /// Generator that still needs to be resolved through a
+    /// [`Registry`](registry::Registry).
+    fn is_unknown(&self) -> bool {
+        false
+    }
 }
 
 /// Provides the ability to clone a Comparison Generator, even when it is stored
@@ -91,7 +107,7 @@ pub fn default_generators() -> Vec<Box<dyn ComparisonGenerator>> {
     vec![
         Box::new(BestSegments),
         Box::new(BestSplitTimes),
-        Box::new(AverageSegments),
+        Box::new(AverageSegments::default()),
         Box::new(MedianSegments),
         Box::new(WorstSegments),
         Box::new(BalancedPB),
@@ -109,6 +125,7 @@ pub fn try_shorten(comparison: &str) -> Option<&'static str> {
         world_record::NAME => world_record::SHORT_NAME,
         average_segments::NAME => average_segments::SHORT_NAME,
         median_segments::NAME => median_segments::SHORT_NAME,
+        last_attempts::NAME => last_attempts::SHORT_NAME,
         balanced_pb::NAME => balanced_pb::SHORT_NAME,
         best_segments::NAME => best_segments::SHORT_NAME,
         best_split_times::NAME => best_split_times::SHORT_NAME,
@@ -0,0 +1,174 @@
+//! Provides the registration of Comparison Generators, so applications can
+//! provide their own [`ComparisonGenerator`] implementations. The Comparison
+//! Generators of a [`Run`](crate::Run) are stored in the splits file by their
+//! name and their settings. When the splits file is opened, the Comparison
+//! Generators are recreated by looking up their names in a [`Registry`].
+//! Comparison Generators that are not registered are kept around as
+//! [`Unknown`] generators, so they don't get lost when the splits file is
+//! opened by an application that doesn't know about them.
+
+use super::{
+    AverageSegments, BalancedPB, BestSegments, BestSplitTimes, ComparisonGenerator, LastAttempts,
+    LatestRun, MedianSegments, None, WorstSegments, average_segments, balanced_pb, best_segments,
+    best_split_times, last_attempts, latest_run, median_segments, none, worst_segments,
+};
+use crate::{Attempt, Segment, platform::prelude::*};
+
+/// A factory that creates a [`ComparisonGenerator`] from its settings. Register
+/// it in a [`Registry`] to allow the Comparison Generator to be restored from
+/// a splits file.
+pub trait ComparisonGeneratorFactory: Send + Sync {
+    /// The name of the comparison the created Comparison Generators generate.
+    fn name(&self) -> &str;
+    /// Creates a new Comparison Generator from the settings that were
+    /// provided by [`ComparisonGenerator::settings`] when the splits file was
+    /// saved. If the settings are invalid, `None` may be returned, in which
+    /// case the Comparison Generator is kept as an [`Unknown`] generator.
+    fn create(&self, settings: Option<&str>) -> Option<Box<dyn ComparisonGenerator>>;
+}
+
+struct Builtin {
+    name: &'static str,
+    create: fn() -> Box<dyn ComparisonGenerator>,
+}
+
+impl ComparisonGeneratorFactory for Builtin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn create(&self, _: Option<&str>) -> Option<Box<dyn ComparisonGenerator>> {
+        Some((self.create)())
+    }
+}
+
+/// A registry of all the Comparison Generators that can be restored from a
+/// splits file. By default it contains all the Comparison Generators that are
+/// provided by this crate.
+pub struct Registry {
+    factories: Vec<Box<dyn ComparisonGeneratorFactory>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(Builtin {
+            name: best_segments::NAME,
+            create: || Box::new(BestSegments),
+        });
+        registry.register(Builtin {
+            name: best_split_times::NAME,
+            create: || Box::new(BestSplitTimes),
+        });
+        registry.register(Builtin {
+            name: average_segments::NAME,
+            create: || Box::new(AverageSegments::default()),
+        });
+        registry.register(Builtin {
+            name: median_segments::NAME,
+            create: || Box::new(MedianSegments),
+        });
+        registry.register(Builtin {
+            name: last_attempts::NAME,
+            create: || Box::new(LastAttempts::default()),
+        });
+        registry.register(Builtin {
+            name: worst_segments::NAME,
+            create: || Box::new(WorstSegments),
+        });
+        registry.register(Builtin {
+            name: balanced_pb::NAME,
+            create: || Box::new(BalancedPB),
+        });
+        registry.register(Builtin {
+            name: latest_run::NAME,
+            create: || Box::new(LatestRun),
+        });
+        registry.register(Builtin {
+            name: none::NAME,
+            create: || Box::new(None),
+        });
+        registry
+    }
+}
+
+impl Registry {
+    /// Creates a new empty registry. Use [`Registry::default`] to create a
+    /// registry that contains all the Comparison Generators provided by this
+    /// crate.
+    pub const fn new() -> Self {
+        Self {
+            factories: Vec::new(),
+        }
+    }
+
+    /// Registers a factory for a Comparison Generator. A factory that was
+    /// registered before with the same name is replaced.
+    pub fn register(&mut self, factory: impl ComparisonGeneratorFactory + 'static) {
+        self.factories.retain(|f| f.name() != factory.name());
+        self.factories.push(Box::new(factory));
+    }
+
+    /// Creates the Comparison Generator with the name and settings provided.
+    /// If there is no factory registered for the name or the settings are
+    /// invalid, an [`Unknown`] generator is created instead.
+    pub fn create(&self, name: &str, settings: Option<&str>) -> Box<dyn ComparisonGenerator> {
+        self.factories
+            .iter()
+            .find(|f| f.name() == name)
+            .and_then(|f| f.create(settings))
+            .unwrap_or_else(|| Box::new(Unknown::new(name, settings)))
+    }
+
+    /// Replaces all the [`Unknown`] generators in the list provided with the
+    /// Comparison Generators registered for their names.
+    pub fn resolve(&self, generators: &mut [Box<dyn ComparisonGenerator>]) {
+        for generator in generators {
+            if generator.is_unknown() {
+                let settings = generator.settings();
+                *generator = self.create(generator.name(), settings.as_deref());
+            }
+        }
+    }
+}
+
+/// A Comparison Generator that is stored in a splits file, but that no
+/// factory is registered for. It keeps the name and the settings around, so
+/// they are stored again when the splits file is saved. As it doesn't know
+/// how to generate the comparison, the comparison doesn't have any times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unknown {
+    name: String,
+    settings: Option<String>,
+}
+
+impl Unknown {
+    /// Creates a new unknown Comparison Generator with the name and settings
+    /// provided.
+    pub fn new(name: &str, settings: Option<&str>) -> Self {
+        Self {
+            name: name.into(),
+            settings: settings.map(Into::into),
+        }
+    }
+}
+
+impl ComparisonGenerator for Unknown {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
+        for segment in segments {
+            segment.comparisons_mut().remove(&self.name);
+        }
+    }
+
+    fn settings(&self) -> Option<String> {
+        self.settings.clone()
+    }
+
+    fn is_unknown(&self) -> bool {
+        true
+    }
+}
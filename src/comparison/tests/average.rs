@@ -12,7 +12,7 @@ fn test() {
 
     run.comparison_generators_mut().clear();
     run.comparison_generators_mut()
-        .push(Box::new(AverageSegments));
+        .push(Box::new(AverageSegments::default()));
 
     let mut timer = Timer::new(run).unwrap();
 
@@ -47,3 +47,50 @@ fn test() {
         assert!(current_average > last_average);
     }
 }
+
+#[test]
+fn unweighted() {
+    let s = TimeSpan::from_seconds;
+
+    let mut run = Run::new();
+    run.push_segment(Segment::new("First"));
+    run.set_average_segments_comparison(AverageSegments::unweighted());
+
+    let mut timer = Timer::new(run).unwrap();
+
+    run_with_splits(&mut timer, &[1.0]);
+    run_with_splits(&mut timer, &[2.0]);
+    run_with_splits(&mut timer, &[6.0]);
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(3.0)));
+}
+
+#[test]
+fn only_latest() {
+    let s = TimeSpan::from_seconds;
+
+    let mut run = Run::new();
+    run.push_segment(Segment::new("First"));
+    run.set_average_segments_comparison(AverageSegments::new(f64::MIN_POSITIVE));
+
+    let mut timer = Timer::new(run).unwrap();
+
+    run_with_splits(&mut timer, &[1.0]);
+    run_with_splits(&mut timer, &[2.0]);
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(2.0)));
+}
+
+#[test]
+fn invalid_weights() {
+    use crate::comparison::average_segments::DEFAULT_WEIGHT;
+
+    assert_eq!(AverageSegments::new(0.5).weight(), 0.5);
+    assert_eq!(AverageSegments::new(2.0).weight(), 1.0);
+    assert_eq!(AverageSegments::new(f64::INFINITY).weight(), DEFAULT_WEIGHT);
+    assert_eq!(AverageSegments::new(f64::NAN).weight(), DEFAULT_WEIGHT);
+    assert_eq!(AverageSegments::new(0.0).weight(), DEFAULT_WEIGHT);
+    assert_eq!(AverageSegments::new(-0.5).weight(), DEFAULT_WEIGHT);
+}
@@ -0,0 +1,24 @@
+use crate::comparison::best_split_times::NAME;
+use crate::util::tests_helper::{
+    create_timer, make_progress_run_with_splits_opt, run_with_splits, span, start_run,
+};
+
+#[test]
+fn includes_reset_attempts() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+
+    run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[Some(8.0), Some(15.0)]);
+    timer.reset(true).unwrap();
+
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[None, Some(14.0)]);
+    timer.reset(true).unwrap();
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(span(8.0)));
+    assert_eq!(run.segment(1).comparison(NAME).game_time, Some(span(14.0)));
+    assert_eq!(run.segment(2).comparison(NAME).game_time, Some(span(30.0)));
+}
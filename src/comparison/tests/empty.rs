@@ -6,7 +6,7 @@ fn test<T: ComparisonGenerator>(mut generator: T) {
 
 #[test]
 fn average_segments() {
-    test(comparison::AverageSegments);
+    test(comparison::AverageSegments::default());
 }
 
 #[test]
@@ -24,6 +24,11 @@ fn best_split_times() {
     test(comparison::BestSplitTimes);
 }
 
+#[test]
+fn last_attempts() {
+    test(comparison::LastAttempts::default());
+}
+
 #[test]
 fn latest_run() {
     test(comparison::LatestRun);
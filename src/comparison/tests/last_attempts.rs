@@ -0,0 +1,82 @@
+use crate::comparison::last_attempts::{LastAttempts, NAME, Statistic};
+use crate::util::tests_helper::{run_with_splits, run_with_splits_opt};
+use crate::{Run, Segment, TimeSpan, Timer};
+
+fn timer(statistic: Statistic) -> Timer {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("First"));
+    run.push_segment(Segment::new("Second"));
+    run.set_last_attempts_comparison(LastAttempts::new(3, statistic));
+    run.set_last_attempts_comparison_enabled(true);
+    Timer::new(run).unwrap()
+}
+
+#[test]
+fn average() {
+    let s = TimeSpan::from_seconds;
+    let mut timer = timer(Statistic::Average);
+
+    run_with_splits(&mut timer, &[100.0, 200.0]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[20.0, 40.0]);
+
+    let run = timer.run();
+    assert_eq!(
+        run.segment(0).comparison(NAME).game_time,
+        Some(s(130.0 / 3.0))
+    );
+
+    run_with_splits(&mut timer, &[30.0, 60.0]);
+
+    // The oldest attempt is not considered anymore.
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(20.0)));
+    assert_eq!(run.segment(1).comparison(NAME).game_time, Some(s(40.0)));
+}
+
+#[test]
+fn median() {
+    let s = TimeSpan::from_seconds;
+    let mut timer = timer(Statistic::Median);
+
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[100.0, 110.0]);
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(55.0)));
+
+    run_with_splits(&mut timer, &[20.0, 25.0]);
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(20.0)));
+    assert_eq!(run.segment(1).comparison(NAME).game_time, Some(s(30.0)));
+}
+
+#[test]
+fn skipped_segments_dont_count() {
+    let s = TimeSpan::from_seconds;
+    let mut timer = timer(Statistic::Average);
+
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[12.0, 22.0]);
+    run_with_splits(&mut timer, &[14.0, 24.0]);
+    run_with_splits_opt(&mut timer, &[None, Some(5.0)]);
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).comparison(NAME).game_time, Some(s(12.0)));
+    assert_eq!(run.segment(1).comparison(NAME).game_time, Some(s(22.0)));
+}
+
+#[test]
+fn is_opt_in() {
+    let mut run = Run::new();
+    assert!(!run.is_last_attempts_comparison_enabled());
+    assert!(run.comparisons().all(|c| c != NAME));
+
+    run.set_last_attempts_comparison_enabled(true);
+    run.set_last_attempts_comparison_enabled(true);
+    assert_eq!(run.comparisons().filter(|&c| c == NAME).count(), 1);
+
+    run.set_last_attempts_comparison_enabled(false);
+    assert!(!run.is_last_attempts_comparison_enabled());
+}
@@ -1,4 +1,7 @@
 mod average;
 mod balanced_pb;
+mod best_split_times;
 mod empty;
+mod last_attempts;
 mod median;
+mod registry;
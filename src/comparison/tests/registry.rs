@@ -0,0 +1,93 @@
+use crate::comparison::{
+    ComparisonGenerator,
+    registry::{ComparisonGeneratorFactory, Registry},
+};
+use crate::run::{parser, saver};
+use crate::{Attempt, Run, Segment, TimeSpan};
+
+#[derive(Clone, Debug)]
+struct Fixed(f64);
+
+impl ComparisonGenerator for Fixed {
+    fn name(&self) -> &str {
+        "Fixed"
+    }
+
+    fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
+        for segment in segments {
+            segment.comparison_mut("Fixed").real_time = Some(TimeSpan::from_seconds(self.0));
+        }
+    }
+
+    fn settings(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+struct FixedFactory;
+
+impl ComparisonGeneratorFactory for FixedFactory {
+    fn name(&self) -> &str {
+        "Fixed"
+    }
+
+    fn create(&self, settings: Option<&str>) -> Option<Box<dyn ComparisonGenerator>> {
+        Some(Box::new(Fixed(settings?.parse().ok()?)))
+    }
+}
+
+fn save(run: &Run) -> String {
+    let mut buf = String::new();
+    saver::livesplit::save_run(run, &mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn default_generators_are_not_stored() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    assert!(!save(&run).contains("ComparisonGenerators"));
+}
+
+#[test]
+fn unknown_generators_survive_a_roundtrip() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.comparison_generators_mut().push(Box::new(Fixed(5.0)));
+    run.regenerate_comparisons();
+
+    let run = parser::livesplit::parse(&save(&run)).unwrap();
+    let fixed = run.comparison_generators().last().unwrap();
+    assert!(fixed.is_unknown());
+    assert_eq!(fixed.settings().as_deref(), Some("5"));
+    assert_eq!(run.segment(0).comparison("Fixed").real_time, None);
+
+    // Saving it again without knowing the generator keeps it around.
+    let mut run = parser::livesplit::parse(&save(&run)).unwrap();
+    assert_eq!(run.comparisons().filter(|&c| c == "Fixed").count(), 1);
+
+    let mut registry = Registry::default();
+    registry.register(FixedFactory);
+    run.resolve_comparison_generators(&registry);
+
+    assert!(!run.comparison_generators().last().unwrap().is_unknown());
+    assert_eq!(
+        run.segment(0).comparison("Fixed").real_time,
+        Some(TimeSpan::from_seconds(5.0)),
+    );
+}
+
+#[test]
+fn removed_default_generators_stay_removed() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.comparison_generators_mut()
+        .retain(|g| g.name() != crate::comparison::worst_segments::NAME);
+
+    let run = parser::livesplit::parse(&save(&run)).unwrap();
+    assert!(
+        !run.comparisons()
+            .any(|c| c == crate::comparison::worst_segments::NAME)
+    );
+    assert!(run.comparison_generators().iter().all(|g| !g.is_unknown()));
+}
@@ -5,7 +5,7 @@
 
 use crate::{
     platform::prelude::*,
-    settings::{Field, Gradient, SettingsDescription, Value},
+    settings::{Constraints, Field, Gradient, SettingsDescription, Value},
 };
 use serde_derive::{Deserialize, Serialize};
 
@@ -101,15 +101,18 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "size",
                 "Size".into(),
                 "The size of the component.".into(),
                 u64::from(self.settings.size).into(),
-            ),
+            )
+            .with_constraints(Constraints::range(0, u32::MAX as i64)),
         ])
     }
 
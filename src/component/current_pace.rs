@@ -172,31 +172,37 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "comparison",
                 "Comparison".into(),
                 "The comparison to predict the final time from. If not specified, the current comparison is used.".into(),
                 self.settings.comparison_override.clone().into(),
             ),
             Field::new(
+                "display_2_rows",
                 "Display 2 Rows".into(),
                 "Specifies whether to display the name of the component and the predicted time in two separate rows.".into(),
                 self.settings.display_two_rows.into(),
             ),
             Field::new(
+                "label_color",
                 "Label Color".into(),
                 "The color of the component's name. If not specified, the color is taken from the layout.".into(),
                 self.settings.label_color.into(),
             ),
             Field::new(
+                "value_color",
                 "Value Color".into(),
                 "The color of the predicted time. If not specified, the color is taken from the layout.".into(),
                 self.settings.value_color.into(),
             ),
             Field::new(
+                "accuracy",
                 "Accuracy".into(),
                 "The accuracy of the predicted time shown.".into(),
                 self.settings.accuracy.into(),
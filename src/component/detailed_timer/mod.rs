@@ -10,7 +10,9 @@ use crate::{
     analysis::comparison_single_segment_time,
     comparison::{self, best_segments, none},
     platform::prelude::*,
-    settings::{Color, Field, Gradient, Image, ImageCache, ImageId, SettingsDescription, Value},
+    settings::{
+        Color, Constraints, Field, Gradient, Image, ImageCache, ImageId, SettingsDescription, Value,
+    },
     timing::{
         Snapshot,
         formatter::{Accuracy, DigitsFormat, SegmentTime, TimeFormatter},
@@ -355,61 +357,75 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "timing_method",
                 "Timing Method".into(),
                 "Specifies the timing method to use. If not specified, the current timing method is used.".into(),
                 self.settings.timer.timing_method.into(),
             ),
             Field::new(
+                "comparison_1",
                 "Comparison 1".into(),
                 "The first comparison to show the segment time of. If not specified, the current comparison is used.".into(),
                 self.settings.comparison1.clone().into(),
             ),
             Field::new(
+                "comparison_2",
                 "Comparison 2".into(),
                 "The second comparison to show the segment time of. If not specified, the current comparison is used, unless the first comparison is also None. This is not shown if the second comparison is hidden.".into(),
                 self.settings.comparison2.clone().into(),
             ),
             Field::new(
+                "hide_second_comparison",
                 "Hide Second Comparison".into(),
                 "Specifies whether to only show a single comparison.".into(),
                 self.settings.hide_second_comparison.into(),
             ),
             Field::new(
+                "timer_height",
                 "Timer Height".into(),
                 "The height of the run timer.".into(),
                 u64::from(self.settings.timer.height).into(),
-            ),
+            )
+            .with_constraints(Constraints::range(1, u32::MAX as i64)),
             Field::new(
+                "segment_timer_height",
                 "Segment Timer Height".into(),
                 "The height of the segment timer.".into(),
                 u64::from(self.settings.segment_timer.height).into(),
-            ),
+            )
+            .with_constraints(Constraints::range(1, u32::MAX as i64)),
             Field::new(
+                "timer_color",
                 "Timer Color".into(),
                 "Instead of automatically determining the color for the main timer based on a how well the current attempt is doing, a specific color to always be used can be provided instead.".into(),
                 self.settings.timer.color_override.into(),
             ),
             Field::new(
+                "show_timer_gradient",
                 "Show Timer Gradient".into(),
                 "The main timer automatically turns its color into a vertical gradient if this setting is activated. Otherwise, the actual color is used instead of a gradient.".into(),
                 self.settings.timer.show_gradient.into(),
             ),
             Field::new(
+                "timer_digits_format",
                 "Timer Digits Format".into(),
                 "Specifies how many digits to show for the main timer. If the duration is lower than the digits to be shown, zeros are shown instead.".into(),
                 self.settings.timer.digits_format.into(),
             ),
             Field::new(
+                "timer_accuracy",
                 "Timer Accuracy".into(),
                 "The accuracy of the time shown for the main timer.".into(),
                 self.settings.timer.accuracy.into(),
             ),
             Field::new(
+                "segment_timer_color",
                 "Segment Timer Color".into(),
                 "Changes the color of the segment timer to a color different from the default color.".into(),
                 self.settings
@@ -419,46 +435,55 @@ impl Component {
                     .into(),
             ),
             Field::new(
+                "show_segment_timer_gradient",
                 "Show Segment Timer Gradient".into(),
                 "The segment timer automatically turns its color into a vertical gradient if this setting is activated. Otherwise, the actual color is used instead of a gradient.".into(),
                 self.settings.segment_timer.show_gradient.into(),
             ),
             Field::new(
+                "segment_timer_digits_format",
                 "Segment Timer Digits Format".into(),
                 "Specifies how many digits to show for the segment timer. If the duration is lower than the digits to be shown, zeros are shown instead.".into(),
                 self.settings.segment_timer.digits_format.into(),
             ),
             Field::new(
+                "segment_timer_accuracy",
                 "Segment Timer Accuracy".into(),
                 "The accuracy of the time shown for the segment timer.".into(),
                 self.settings.segment_timer.accuracy.into(),
             ),
             Field::new(
+                "comparison_names_color",
                 "Comparison Names Color".into(),
                 "The color of the comparison names if they are shown. If no color is specified, the color is taken from the layout.".into(),
                 self.settings.comparison_names_color.into(),
             ),
             Field::new(
+                "comparison_times_color",
                 "Comparison Times Color".into(),
                 "The color of the comparison times if they are shown. If no color is specified, the color is taken from the layout.".into(),
                 self.settings.comparison_times_color.into(),
             ),
             Field::new(
+                "comparison_times_accuracy",
                 "Comparison Times Accuracy".into(),
                 "The accuracy of the comparison times.".into(),
                 self.settings.comparison_times_accuracy.into(),
             ),
             Field::new(
+                "show_segment_name",
                 "Show Segment Name".into(),
                 "Specifies whether the segment name should be shown.".into(),
                 self.settings.show_segment_name.into(),
             ),
             Field::new(
+                "segment_name_color",
                 "Segment Name Color".into(),
                 "The color of the segment name if it's shown. If no color is specified, the color is taken from the layout.".into(),
                 self.settings.segment_name_color.into(),
             ),
             Field::new(
+                "display_icon",
                 "Display Icon".into(),
                 "Specifies whether the segment icon should be shown.".into(),
                 self.settings.display_icon.into(),
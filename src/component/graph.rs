@@ -12,8 +12,9 @@
 
 use crate::{
     GeneralLayoutSettings, TimeSpan, Timer, TimerPhase, analysis, comparison,
+    layout::{ThemeColor, ThemedColor},
     platform::prelude::*,
-    settings::{Color, Field, SettingsDescription, Value},
+    settings::{Color, Constraints, Field, SettingsDescription, Value},
     timing::Snapshot,
 };
 use alloc::borrow::Cow;
@@ -51,22 +52,22 @@ pub struct Settings {
     pub flip_graph: bool,
     /// The background color for the chart region containing the times that are
     /// behind the comparison.
-    pub behind_background_color: Color,
+    pub behind_background_color: ThemedColor,
     /// The background color for the chart region containing the times that are
     /// ahead of the comparison.
-    pub ahead_background_color: Color,
+    pub ahead_background_color: ThemedColor,
     /// The color of the chart's grid lines.
-    pub grid_lines_color: Color,
+    pub grid_lines_color: ThemedColor,
     /// The color of the lines connecting the graph's points.
-    pub graph_lines_color: Color,
+    pub graph_lines_color: ThemedColor,
     /// The color of the region enclosed by the x-axis and the graph. The
     /// partial fill color is only used for live changes. More specifically,
     /// this color is used in the interval from the last split time to the
     /// current time.
-    pub partial_fill_color: Color,
+    pub partial_fill_color: ThemedColor,
     /// The color of the region enclosed by the x-axis and the graph, excluding
     /// the graph segment with live changes.
-    pub complete_fill_color: Color,
+    pub complete_fill_color: ThemedColor,
     /// The height of the chart.
     pub height: u32,
 }
@@ -143,12 +144,22 @@ impl Default for Settings {
             show_best_segments: false,
             live_graph: true,
             flip_graph: false,
-            behind_background_color: Color::rgba(115.0 / 255.0, 40.0 / 255.0, 40.0 / 255.0, 1.0),
-            ahead_background_color: Color::rgba(40.0 / 255.0, 115.0 / 255.0, 52.0 / 255.0, 1.0),
-            grid_lines_color: Color::rgba(0.0, 0.0, 0.0, 0.15),
-            graph_lines_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
-            partial_fill_color: Color::rgba(1.0, 1.0, 1.0, 0.25),
-            complete_fill_color: Color::rgba(1.0, 1.0, 1.0, 0.4),
+            behind_background_color: ThemedColor::Custom(Color::rgba(
+                115.0 / 255.0,
+                40.0 / 255.0,
+                40.0 / 255.0,
+                1.0,
+            )),
+            ahead_background_color: ThemedColor::Custom(Color::rgba(
+                40.0 / 255.0,
+                115.0 / 255.0,
+                52.0 / 255.0,
+                1.0,
+            )),
+            grid_lines_color: ThemedColor::Custom(Color::rgba(0.0, 0.0, 0.0, 0.15)),
+            graph_lines_color: ThemedColor::Theme(ThemeColor::TextPrimary),
+            partial_fill_color: ThemedColor::Custom(Color::rgba(1.0, 1.0, 1.0, 0.25)),
+            complete_fill_color: ThemedColor::Custom(Color::rgba(1.0, 1.0, 1.0, 0.4)),
             height: 80,
         }
     }
@@ -254,7 +265,7 @@ impl Component {
 
         let grid_lines = calculate_grid_lines(&draw_info, x_axis);
         update_grid_line_vecs(state, grid_lines);
-        self.copy_settings_to_state(state);
+        self.copy_settings_to_state(state, layout_settings);
         state.best_segment_color = layout_settings.best_segment_color;
         state.middle = x_axis;
         state.is_live_delta_active = draw_info.is_live_delta_active;
@@ -277,59 +288,71 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "comparison",
                 "Comparison".into(),
                 "The comparison to use for the graph. If not specified, the current comparison is used.".into(),
                 self.settings.comparison_override.clone().into(),
             ),
             Field::new(
+                "height",
                 "Height".into(),
                 "The height of the chart.".into(),
                 u64::from(self.settings.height).into(),
-            ),
+            )
+            .with_constraints(Constraints::range(1, u32::MAX as i64)),
             Field::new(
+                "show_best_segments",
                 "Show Best Segments".into(),
                 "Specifies whether to color the best segments with the layout's best segment color.".into(),
                 self.settings.show_best_segments.into(),
             ),
             Field::new(
+                "live_graph",
                 "Live Graph".into(),
                 "Specifies whether the graph should automatically refresh all the time. If this is deactivated, changes to the graph only happen whenever the current segment changes.".into(),
                 self.settings.live_graph.into(),
             ),
             Field::new(
+                "flip_graph",
                 "Flip Graph".into(),
                 "Specifies whether the chart should be flipped vertically. If not enabled, split times which are ahead of the comparison are displayed below the x-axis and times which are behind are above it. Enabling this settings flips it.".into(),
                 self.settings.flip_graph.into(),
             ),
             Field::new(
+                "behind_background_color",
                 "Behind Background Color".into(),
                 "The background color for the chart region containing the times that are behind the comparison.".into(),
-                self.settings.behind_background_color.into(),
+                self.settings.behind_background_color.clone().into(),
             ),
             Field::new(
+                "ahead_background_color",
                 "Ahead Background Color".into(),
                 "The background color for the chart region containing the times that are ahead of the comparison.".into(),
-                self.settings.ahead_background_color.into(),
+                self.settings.ahead_background_color.clone().into(),
             ),
             Field::new(
+                "grid_lines_color",
                 "Grid Lines Color".into(),
                 "The color of the chart's grid lines.".into(),
-                self.settings.grid_lines_color.into(),
+                self.settings.grid_lines_color.clone().into(),
             ),
             Field::new(
+                "graph_lines_color",
                 "Graph Lines Color".into(),
                 "The color of the lines connecting the graph's points.".into(),
-                self.settings.graph_lines_color.into(),
+                self.settings.graph_lines_color.clone().into(),
             ),
             Field::new(
+                "partial_fill_color",
                 "Partial Fill Color".into(),
                 "The color of the region enclosed by the x-axis and the graph. The partial fill color is only used for live changes. More specifically, this color is used in the interval from the last split time to the current time.".into(),
-                self.settings.partial_fill_color.into(),
+                self.settings.partial_fill_color.clone().into(),
             ),
             Field::new(
+                "complete_fill_color",
                 "Complete Fill Color".into(),
                 "The color of the region enclosed by the x-axis and the graph, excluding the graph segment with live changes.".into(),
-                self.settings.complete_fill_color.into(),
+                self.settings.complete_fill_color.clone().into(),
             ),
         ])
     }
@@ -387,25 +410,21 @@ impl Component {
         Some(x_axis)
     }
 
-    const fn copy_settings_to_state(&self, state: &mut State) {
+    fn copy_settings_to_state(&self, state: &mut State, layout_settings: &GeneralLayoutSettings) {
         let settings = &self.settings;
+        let behind_background_color = settings.behind_background_color.resolve(layout_settings);
+        let ahead_background_color = settings.ahead_background_color.resolve(layout_settings);
         (state.top_background_color, state.bottom_background_color) = if settings.flip_graph {
-            (
-                settings.ahead_background_color,
-                settings.behind_background_color,
-            )
+            (ahead_background_color, behind_background_color)
         } else {
-            (
-                settings.behind_background_color,
-                settings.ahead_background_color,
-            )
+            (behind_background_color, ahead_background_color)
         };
 
         state.is_flipped = settings.flip_graph;
-        state.grid_lines_color = settings.grid_lines_color;
-        state.graph_lines_color = settings.graph_lines_color;
-        state.partial_fill_color = settings.partial_fill_color;
-        state.complete_fill_color = settings.complete_fill_color;
+        state.grid_lines_color = settings.grid_lines_color.resolve(layout_settings);
+        state.graph_lines_color = settings.graph_lines_color.resolve(layout_settings);
+        state.partial_fill_color = settings.partial_fill_color.resolve(layout_settings);
+        state.complete_fill_color = settings.complete_fill_color.resolve(layout_settings);
         state.height = settings.height;
     }
 }
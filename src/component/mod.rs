@@ -9,7 +9,9 @@ pub mod current_pace;
 pub mod delta;
 pub mod detailed_timer;
 pub mod graph;
+pub mod overlay_widgets;
 pub mod pb_chance;
+pub mod placeholder;
 pub mod possible_time_save;
 pub mod previous_segment;
 pub mod reset_chance;
@@ -17,6 +19,7 @@ pub mod segment_time;
 pub mod separator;
 pub mod splits;
 pub mod sum_of_best;
+pub mod tabs;
 pub mod text;
 pub mod timer;
 pub mod title;
@@ -30,7 +33,9 @@ pub use current_pace::Component as CurrentPace;
 pub use delta::Component as Delta;
 pub use detailed_timer::Component as DetailedTimer;
 pub use graph::Component as Graph;
+pub use overlay_widgets::Component as OverlayWidgets;
 pub use pb_chance::Component as PbChance;
+pub use placeholder::Component as Placeholder;
 pub use possible_time_save::Component as PossibleTimeSave;
 pub use previous_segment::Component as PreviousSegment;
 pub use reset_chance::Component as ResetChance;
@@ -38,6 +43,7 @@ pub use segment_time::Component as SegmentTime;
 pub use separator::Component as Separator;
 pub use splits::Component as Splits;
 pub use sum_of_best::Component as SumOfBest;
+pub use tabs::Component as Tabs;
 pub use text::Component as Text;
 pub use timer::Component as Timer;
 pub use title::Component as Title;
@@ -0,0 +1,218 @@
+//! Provides the Overlay Widgets Component and relevant types for using it. The
+//! Overlay Widgets Component shows the overlay widgets of the timer. These are
+//! usually provided by an auto splitter to display game specific information,
+//! such as the health of a boss or the seed of the random number generator.
+
+use super::key_value;
+use crate::{
+    Timer,
+    platform::prelude::*,
+    settings::{Color, Field, Gradient, SettingsDescription, Value},
+    timing::OverlayWidgetValue,
+    util::{Clear, ClearVec},
+};
+use core::fmt::Write;
+use serde_derive::{Deserialize, Serialize};
+
+/// The Overlay Widgets Component shows the overlay widgets of the timer. These
+/// are usually provided by an auto splitter to display game specific
+/// information, such as the health of a boss or the seed of the random number
+/// generator.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// Specifies whether to display the label of each widget and its value in
+    /// two separate rows.
+    pub display_two_rows: bool,
+    /// The color of the labels. If `None` is specified, the color is taken from
+    /// the layout.
+    pub label_color: Option<Color>,
+    /// The color of the values. If `None` is specified, the color is taken from
+    /// the layout.
+    pub value_color: Option<Color>,
+    /// The color of the filled part of the progress bars.
+    pub progress_color: Color,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            background: key_value::DEFAULT_GRADIENT,
+            display_two_rows: false,
+            label_color: None,
+            value_color: None,
+            progress_color: Color::rgba(0.16, 0.52, 0.86, 0.5),
+        }
+    }
+}
+
+/// The state object describes the information to visualize for this component.
+#[derive(Default, Serialize, Deserialize)]
+pub struct State {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The color of the labels. If `None` is specified, the color is taken
+    /// from the layout.
+    pub label_color: Option<Color>,
+    /// The color of the values. If `None` is specified, the color is taken
+    /// from the layout.
+    pub value_color: Option<Color>,
+    /// The color of the filled part of the progress bars.
+    pub progress_color: Color,
+    /// Specifies whether to display the label of each widget and its value in
+    /// two separate rows.
+    pub display_two_rows: bool,
+    /// The state objects for all of the widgets to show.
+    pub widgets: ClearVec<WidgetState>,
+}
+
+/// The state object for a single widget.
+#[derive(Default, Serialize, Deserialize)]
+pub struct WidgetState {
+    /// The label of the widget.
+    pub label: String,
+    /// The value to show next to the label. For progress bars this is the
+    /// progress formatted as a percentage.
+    pub value: String,
+    /// The fraction between 0 and 1 that the progress bar is filled by. This is
+    /// `None` if the widget is not a progress bar.
+    pub progress: Option<f32>,
+}
+
+impl Clear for WidgetState {
+    fn clear(&mut self) {
+        self.label.clear();
+        self.value.clear();
+        self.progress = None;
+    }
+}
+
+#[cfg(feature = "std")]
+impl State {
+    /// Encodes the state object's information as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+impl Component {
+    /// Creates a new Overlay Widgets Component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new Overlay Widgets Component with the given settings.
+    pub const fn with_settings(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        "Overlay Widgets"
+    }
+
+    /// Updates the component's state based on the timer provided.
+    pub fn update_state(&self, state: &mut State, timer: &Timer) {
+        state.background = self.settings.background;
+        state.label_color = self.settings.label_color;
+        state.value_color = self.settings.value_color;
+        state.progress_color = self.settings.progress_color;
+        state.display_two_rows = self.settings.display_two_rows;
+
+        state.widgets.clear();
+        for widget in timer.overlay_widgets() {
+            let state = state.widgets.push_with(Default::default);
+            state.label.push_str(&widget.label);
+            match &widget.value {
+                OverlayWidgetValue::Text(text) => state.value.push_str(text),
+                &OverlayWidgetValue::Progress(progress) => {
+                    let _ = write!(state.value, "{:.0}%", 100.0 * progress);
+                    state.progress = Some(progress as f32);
+                }
+            }
+        }
+    }
+
+    /// Calculates the component's state based on the timer provided.
+    pub fn state(&self, timer: &Timer) -> State {
+        let mut state = Default::default();
+        self.update_state(&mut state, timer);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new(
+                "background",
+                "Background".into(),
+                "The background shown behind the component.".into(),
+                self.settings.background.into(),
+            ),
+            Field::new(
+                "display_2_rows",
+                "Display 2 Rows".into(),
+                "Specifies whether to display the label of each widget and its value in two separate rows.".into(),
+                self.settings.display_two_rows.into(),
+            ),
+            Field::new(
+                "label_color",
+                "Label Color".into(),
+                "The color of the labels of the widgets. If not specified, the color is taken from the layout.".into(),
+                self.settings.label_color.into(),
+            ),
+            Field::new(
+                "value_color",
+                "Value Color".into(),
+                "The color of the values of the widgets. If not specified, the color is taken from the layout.".into(),
+                self.settings.value_color.into(),
+            ),
+            Field::new(
+                "progress_color",
+                "Progress Bar Color".into(),
+                "The color of the filled part of the progress bars.".into(),
+                self.settings.progress_color.into(),
+            ),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.settings.background = value.into(),
+            1 => self.settings.display_two_rows = value.into(),
+            2 => self.settings.label_color = value.into(),
+            3 => self.settings.value_color = value.into(),
+            4 => self.settings.progress_color = value.into(),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+}
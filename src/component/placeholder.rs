@@ -0,0 +1,104 @@
+//! Provides the Placeholder Component and relevant types for using it. The
+//! Placeholder Component stands in for a component of a layout of the original
+//! LiveSplit that livesplit-core doesn't support. It doesn't show anything, but
+//! it keeps the component's settings around, so that saving the layout as a
+//! layout of the original LiveSplit again doesn't lose the component.
+
+use super::blank_space;
+use crate::{
+    platform::prelude::*,
+    settings::{Gradient, SettingsDescription, Value},
+};
+use alloc::borrow::Cow;
+use serde_derive::{Deserialize, Serialize};
+
+/// The Placeholder Component stands in for a component of a layout of the
+/// original LiveSplit that livesplit-core doesn't support. It doesn't show
+/// anything.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The path of the component in the original LiveSplit, such as
+    /// `LiveSplit.AutoSplitter.dll`.
+    pub path: String,
+    /// The settings of the component the way they are stored in the layout
+    /// file of the original LiveSplit. This is the already escaped XML content
+    /// of the component's `Settings` element.
+    pub xml_settings: String,
+}
+
+impl Component {
+    /// Creates a new Placeholder Component for the component of the original
+    /// LiveSplit with the path provided.
+    pub const fn new(path: String) -> Self {
+        Self::with_settings(Settings {
+            path,
+            xml_settings: String::new(),
+        })
+    }
+
+    /// Creates a new Placeholder Component with the given settings.
+    pub const fn with_settings(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component. This is the name of the component
+    /// in the original LiveSplit.
+    pub fn name(&self) -> Cow<'_, str> {
+        let name = self.settings.path.trim_end_matches(".dll");
+        let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+        if name.is_empty() {
+            "Placeholder".into()
+        } else {
+            format!("{name} (Unsupported)").into()
+        }
+    }
+
+    /// Updates the component's state. As the component doesn't show anything,
+    /// it is an empty Blank Space.
+    pub const fn update_state(&self, state: &mut blank_space::State) {
+        state.background = Gradient::Transparent;
+        state.size = 0;
+    }
+
+    /// Calculates the component's state. As the component doesn't show
+    /// anything, it is an empty Blank Space.
+    pub const fn state(&self) -> blank_space::State {
+        blank_space::State {
+            background: Gradient::Transparent,
+            size: 0,
+        }
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values. As the settings can only be
+    /// interpreted by the original LiveSplit, there are none.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::default()
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, _index: usize, _value: Value) {}
+}
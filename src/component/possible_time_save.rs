@@ -172,36 +172,43 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "comparison",
                 "Comparison".into(),
                 "The comparison to calculate the possible time save for. If not specified, the current comparison is used.".into(),
                 self.settings.comparison_override.clone().into(),
             ),
             Field::new(
+                "display_2_rows",
                 "Display 2 Rows".into(),
                 "Specifies whether to display the name of the component and the possible time save in two separate rows.".into(),
                 self.settings.display_two_rows.into(),
             ),
             Field::new(
+                "show_total_possible_time_save",
                 "Show Total Possible Time Save".into(),
                 "Specifies whether to show the total possible time save for the remainder of the current attempt, instead of the possible time save for the current segment.".into(),
                 self.settings.total_possible_time_save.into(),
             ),
             Field::new(
+                "label_color",
                 "Label Color".into(),
                 "The color of the component's name. If not specified, the color is taken from the layout.".into(),
                 self.settings.label_color.into(),
             ),
             Field::new(
+                "value_color",
                 "Value Color".into(),
                 "The color of the possible time save. If not specified, the color is taken from the layout.".into(),
                 self.settings.value_color.into(),
             ),
             Field::new(
+                "accuracy",
                 "Accuracy".into(),
                 "The accuracy of the possible time save shown.".into(),
                 self.settings.accuracy.into(),
@@ -163,34 +163,40 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "display_2_rows",
                 "Display 2 Rows".into(),
                 "Specifies whether to display the name of the component and the reset chance in two separate rows."
                     .into(),
                 self.settings.display_two_rows.into(),
             ),
             Field::new(
+                "label_color",
                 "Label Color".into(),
                 "The color of the component's name. If not specified, the color is taken from the layout."
                     .into(),
                 self.settings.label_color.into(),
             ),
             Field::new(
+                "value_color",
                 "Value Color".into(),
                 "The color of the PB chance. If not specified, the color is taken from the layout."
                     .into(),
                 self.settings.value_color.into(),
             ),
             Field::new(
+                "show_successes",
                 "Show Successes".into(),
                 "Instead of showing the reset chance, show the success chance for the current split.".into(),
                 self.settings.show_successes.into(),
             ),
             Field::new(
+                "show_attempt_details",
                 "Show Attempt Details".into(),
                 "In addition to showing the reset chance, show the attempt counts used for the calculation.".into(),
                 self.settings.show_attempt_details.into(),
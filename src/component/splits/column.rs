@@ -7,7 +7,7 @@ use crate::{
     settings::{Color, SemanticColor},
     timing::{
         Snapshot,
-        formatter::{Delta, Regular, SegmentTime, TimeFormatter},
+        formatter::{Delta, Frames, Regular, SegmentTime, TimeFormatter},
     },
     util::Clear,
 };
@@ -57,6 +57,10 @@ pub struct TimeColumn {
     /// of the Timer is used for showing the time. Otherwise the Timing Method
     /// provided is used.
     pub timing_method: Option<TimingMethod>,
+    /// Specifies whether the times are shown as frame counts. This only has an
+    /// effect if the [`Run`](crate::Run) has a frame rate. Otherwise the times
+    /// are shown as usual.
+    pub show_frames: bool,
 }
 
 /// A column that shows a variable.
@@ -147,12 +151,13 @@ impl Default for TimeColumn {
             update_trigger: ColumnUpdateTrigger::Contextual,
             comparison_override: None,
             timing_method: None,
+            show_frames: false,
         }
     }
 }
 
 /// Describes the state of a single segment's column to visualize.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnState {
     /// The value shown in the column.
     pub value: String,
@@ -287,7 +292,25 @@ fn update_time_column(
 
     state.value.clear();
 
-    if !is_empty {
+    let frame_rate = timer
+        .run()
+        .frame_rate()
+        .filter(|_| column_settings.show_frames);
+
+    if !is_empty && let Some(frame_rate) = frame_rate {
+        let _ = match formatter {
+            ColumnFormatter::Delta => write!(
+                state.value,
+                "{}",
+                Frames::delta(frame_rate).format(column_value)
+            ),
+            ColumnFormatter::Time | ColumnFormatter::SegmentTime => write!(
+                state.value,
+                "{}",
+                Frames::new(frame_rate).format(column_value)
+            ),
+        };
+    } else if !is_empty {
         let _ = match formatter {
             ColumnFormatter::Time => write!(
                 state.value,
@@ -28,7 +28,7 @@ pub use column::{
 };
 
 const SETTINGS_BEFORE_COLUMNS: usize = 15;
-const SETTINGS_PER_TIME_COLUMN: usize = 6;
+const SETTINGS_PER_TIME_COLUMN: usize = 7;
 const SETTINGS_PER_VARIABLE_COLUMN: usize = 2;
 
 /// The Splits Component is the main component for visualizing all the split
@@ -103,7 +103,7 @@ pub struct Settings {
 }
 
 /// The state object that describes a single segment's information to visualize.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SplitState {
     /// The icon of the segment. The associated image can be looked up in the
     /// image cache. The image may be the empty image. This indicates that there
@@ -122,6 +122,21 @@ pub struct SplitState {
     /// there can be a scrolling window, showing only a subset of segments. Each
     /// index is guaranteed to be unique.
     pub index: usize,
+    /// The opacity the segment is shown with. This is only below 1 while the
+    /// segment fades in or out as part of an
+    /// [`Animator`](crate::layout::Animator) transition.
+    #[serde(default = "full_opacity")]
+    pub opacity: f32,
+    /// The offset in rows (or columns in horizontal layouts) from the position
+    /// of the segment in the list that the segment is shown at. This is only
+    /// non-zero while the segment moves to its new position as part of an
+    /// [`Animator`](crate::layout::Animator) transition.
+    #[serde(default)]
+    pub offset: f32,
+}
+
+const fn full_opacity() -> f32 {
+    1.0
 }
 
 impl Clear for SplitState {
@@ -129,6 +144,8 @@ impl Clear for SplitState {
         self.icon = *ImageId::EMPTY;
         self.name.clear();
         self.columns.clear();
+        self.opacity = 1.0;
+        self.offset = 0.0;
     }
 }
 
@@ -194,6 +211,7 @@ impl Default for Settings {
                         update_trigger: ColumnUpdateTrigger::OnEndingSegment,
                         comparison_override: None,
                         timing_method: None,
+                        show_frames: false,
                     }),
                 },
                 ColumnSettings {
@@ -204,6 +222,7 @@ impl Default for Settings {
                         update_trigger: ColumnUpdateTrigger::Contextual,
                         comparison_override: None,
                         timing_method: None,
+                        show_frames: false,
                     }),
                 },
             ],
@@ -353,6 +372,8 @@ impl Component {
                 columns: ClearVec::new(),
                 is_current_split: false,
                 index: 0,
+                opacity: 1.0,
+                offset: 0.0,
             });
 
             let icon = segment.icon();
@@ -392,6 +413,8 @@ impl Component {
                     columns: ClearVec::new(),
                     is_current_split: false,
                     index: 0,
+                    opacity: 1.0,
+                    offset: 0.0,
                 });
                 state.is_current_split = false;
                 state.index = (usize::MAX ^ 1) - 2 * i;
@@ -426,82 +449,101 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         let mut settings = SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component. You can choose for the colors to be alternating. In that case each row alternates between the two colors chosen.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "total_rows",
                 "Total Rows".into(),
                 "The total number of rows of segments to show in the list. If set to 0, all the segments are shown. If set to a number lower than the total number of segments, only a certain window of all the segments is shown. This window can scroll up or down.".into(),
                 Value::UInt(self.settings.visual_split_count as _),
             ),
             Field::new(
+                "upcoming_segments",
                 "Upcoming Segments".into(),
                 "If there's more segments than rows that are shown, the window showing the segments automatically scrolls up and down when the current segment changes. This number determines the minimum number of future segments to be shown in this scrolling window.".into(),
                 Value::UInt(self.settings.split_preview_count as _),
             ),
             Field::new(
+                "show_thin_separators",
                 "Show Thin Separators".into(),
                 "Specifies whether thin separators should be shown between the individual segment rows.".into(),
                 self.settings.show_thin_separators.into(),
             ),
             Field::new(
+                "show_separator_before_last_split",
                 "Show Separator Before Last Split".into(),
                 "If the last segment is to always be shown, this determines whether to show a more pronounced separator in front of the last segment, if it is not directly adjacent to the segment shown right before it in the scrolling window.".into(),
                 self.settings.separator_last_split.into(),
             ),
             Field::new(
+                "always_show_last_split",
                 "Always Show Last Split".into(),
                 "If not every segment is shown in the scrolling window of segments, then this option determines whether the final segment should always be shown, as it contains the total duration of the chosen comparison. This can be valuable information, as it is often the runner's Personal Best.".into(),
                 self.settings.always_show_last_split.into(),
             ),
             Field::new(
+                "fill_with_blank_space",
                 "Fill with Blank Space".into(),
                 "If there's not enough segments to fill the list, this option allows filling the remaining rows with blank space in order to always show the number of total rows specified in the settings. Otherwise, the number of total rows shown is reduced to the actual number of segments.".into(),
                 self.settings.fill_with_blank_space.into(),
             ),
             Field::new(
+                "show_times_below_segment_name",
                 "Show Times Below Segment Name".into(),
                 "Specifies whether to show the times below the segment name. Otherwise the times are shown next to the segment name.".into(),
                 self.settings.display_two_rows.into(),
             ),
             Field::new(
+                "current_segment_gradient",
                 "Current Segment Gradient".into(),
                 "The gradient to show behind the current segment as an indicator of it being the current segment.".into(),
                 self.settings.current_split_gradient.into(),
             ),
             Field::new(
+                "split_time_accuracy",
                 "Split Time Accuracy".into(),
                 "Specifies the accuracy to use for visualizing columns that contain split times.".into(),
                 self.settings.split_time_accuracy.into(),
             ),
             Field::new(
+                "segment_time_accuracy",
                 "Segment Time Accuracy".into(),
                 "Specifies the accuracy to use for visualizing columns that contain segment times.".into(),
                 self.settings.segment_time_accuracy.into(),
             ),
             Field::new(
+                "delta_time_accuracy",
                 "Delta Time Accuracy".into(),
                 "Specifies the accuracy to use for visualizing columns that contain the amount of time you are ahead or behind.".into(),
                 self.settings.delta_time_accuracy.into(),
             ),
             Field::new(
+                "drop_delta_decimals_when_showing_minutes",
                 "Drop Delta Decimals When Showing Minutes".into(),
                 "Specifies if the decimals should not be shown anymore when a column that contains the amount of time you are ahead or behind is over a minute.".into(),
                 self.settings.delta_drop_decimals.into(),
             ),
             Field::new(
+                "show_column_labels",
                 "Show Column Labels".into(),
                 "Specifies whether to show the names of the columns at the top of the list.".into(),
                 self.settings.show_column_labels.into(),
             ),
             Field::new(
+                "columns",
                 "Columns".into(),
                 "The number of columns to show in each row. Each column can be configured to show different information. The columns are defined from right to left.".into(),
                 Value::UInt(self.settings.columns.len() as _),
             ),
         ]);
 
+        settings.add_group("segments", "Segments", 0..9, false);
+        settings.add_group("accuracy", "Accuracy", 9..13, true);
+        settings.add_group("columns", "Columns", 13..SETTINGS_BEFORE_COLUMNS, false);
+
         settings.fields.reserve_exact(
             self.settings
                 .columns
@@ -514,9 +556,12 @@ impl Component {
         );
 
         for column in &self.settings.columns {
+            let start = settings.fields.len();
+
             settings
                 .fields
                 .push(Field::new(
+                    "column_name",
                     "Column Name".into(),
                     "The name of the column. This is shown at the top of the list if the option to show column labels is enabled.".into(),
                     column.name.clone().into(),
@@ -525,11 +570,13 @@ impl Component {
             match &column.kind {
                 ColumnKind::Variable(column) => {
                     settings.fields.push(Field::new(
+                        "column_type",
                         "Column Type".into(),
                         "The type of information this column displays. This can be a time or a custom variable that you have stored in your splits.".into(),
                         settings::ColumnKind::Variable.into(),
                     ));
                     settings.fields.push(Field::new(
+                        "variable_name",
                         "Variable Name".into(),
                         "The name of the custom variable that this column displays.".into(),
                         column.variable_name.clone().into(),
@@ -537,6 +584,7 @@ impl Component {
                 }
                 ColumnKind::Time(column) => {
                     settings.fields.push(Field::new(
+                        "column_type",
                         "Column Type".into(),
                         "The type of information this column displays. This can be a time or a custom variable that you have stored in your splits.".into(),
                         settings::ColumnKind::Time.into(),
@@ -544,6 +592,7 @@ impl Component {
                     settings
                         .fields
                         .push(Field::new(
+                            "start_with",
                             "Start With".into(),
                             "The value that this column starts with for each segment. The Update Trigger determines when this time is replaced.".into(),
                             column.start_with.into(),
@@ -551,27 +600,44 @@ impl Component {
                     settings
                         .fields
                         .push(Field::new(
+                            "update_with",
                             "Update With".into(),
                             "Once a certain condition is met, which is usually being on the segment or having already completed the segment, the time gets updated with the value specified here.".into(),
                             column.update_with.into(),
                     ));
                     settings.fields.push(Field::new(
+                        "update_trigger",
                         "Update Trigger".into(),
                         "The condition that needs to be met for the time to get updated with the value specified in the Update With field. Before this condition is met, the time is the value specified in the Start With field.".into(),
                         column.update_trigger.into(),
                     ));
                     settings.fields.push(Field::new(
+                        "comparison",
                         "Comparison".into(),
                         "The comparison that is being compared against for this column. If not specified, the current comparison is used.".into(),
                         column.comparison_override.clone().into(),
                     ));
                     settings.fields.push(Field::new(
+                        "timing_method",
                         "Timing Method".into(),
                         "Specifies the timing method to use for this column. If not specified, the current timing method is used.".into(),
                         column.timing_method.into(),
                     ));
+                    settings.fields.push(Field::new(
+                        "show_as_frames",
+                        "Show As Frames".into(),
+                        "Specifies whether the times in this column are shown as frame counts. This only has an effect if a frame rate is set for the splits.".into(),
+                        column.show_frames.into(),
+                    ));
                 }
             }
+
+            let fields = start..settings.fields.len();
+            if column.name.is_empty() {
+                settings.add_group("column", "Column", fields, true);
+            } else {
+                settings.add_untranslated_group(column.name.clone(), fields, true);
+            }
         }
 
         settings
@@ -633,17 +699,18 @@ impl Component {
                             index -= 1;
                         }
                         ColumnKind::Time(column) => {
-                            if index < 5 {
+                            if index < 6 {
                                 match index {
                                     0 => column.start_with = value.into(),
                                     1 => column.update_with = value.into(),
                                     2 => column.update_trigger = value.into(),
                                     3 => column.comparison_override = value.into(),
-                                    _ => column.timing_method = value.into(),
+                                    4 => column.timing_method = value.into(),
+                                    _ => column.show_frames = value.into(),
                                 }
                                 return;
                             }
-                            index -= 5;
+                            index -= 6;
                         }
                     }
                 }
@@ -3,10 +3,11 @@ use super::{
     State,
 };
 use crate::{
-    component::splits::{ColumnKind, TimeColumn},
-    settings::ImageCache,
     Run, Segment, TimeSpan, Timer, TimingMethod,
+    component::splits::{ColumnKind, TimeColumn},
+    settings::{ImageCache, Localizer},
 };
+use alloc::borrow::Cow;
 
 pub mod column;
 
@@ -118,6 +119,39 @@ fn negative_segment_times() {
     assert_eq!(state.splits[0].columns[0].value, "−1.00");
 }
 
+#[test]
+fn frame_count_columns() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new(""));
+    run.set_frame_rate(Some(60.0));
+    let mut timer = Timer::new(run).unwrap();
+    let layout_settings = Default::default();
+    let mut component = Component::with_settings(Settings {
+        columns: vec![ColumnSettings {
+            kind: ColumnKind::Time(TimeColumn {
+                start_with: ColumnStartWith::Empty,
+                update_with: ColumnUpdateWith::SegmentTime,
+                update_trigger: ColumnUpdateTrigger::OnStartingSegment,
+                show_frames: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+
+    timer.start().unwrap();
+    timer.set_current_timing_method(TimingMethod::GameTime);
+    timer.initialize_game_time().unwrap();
+    timer.pause_game_time().unwrap();
+    timer.set_game_time(TimeSpan::from_seconds(1.5)).unwrap();
+
+    let mut image_cache = ImageCache::new();
+
+    let state = component.state(&mut image_cache, &timer.snapshot(), &layout_settings);
+    assert_eq!(state.splits[0].columns[0].value, "90");
+}
+
 #[test]
 fn unique_split_indices() {
     let mut run = Run::new();
@@ -146,3 +180,60 @@ fn unique_split_indices() {
 
     assert!(indices.windows(2).all(|pair| pair[0] != pair[1]));
 }
+
+#[test]
+fn settings_are_grouped_per_column() {
+    let component = Component::new();
+    let description = component.settings_description();
+
+    let headings = description
+        .groups
+        .iter()
+        .map(|group| &*group.heading)
+        .collect::<Vec<_>>();
+    assert_eq!(headings, ["Segments", "Accuracy", "Columns", "Time", "+/−"]);
+    assert_eq!(
+        description.groups.last().unwrap().end,
+        description.fields.len()
+    );
+    assert!(
+        description
+            .groups
+            .windows(2)
+            .all(|pair| pair[0].end == pair[1].start)
+    );
+    assert_eq!(description.group_of(15).unwrap().heading, "Time");
+}
+
+struct German;
+
+impl Localizer for German {
+    fn name(&self, key: &str) -> Option<Cow<'static, str>> {
+        Some(match key {
+            "total_rows" => "Zeilen insgesamt".into(),
+            "accuracy" => "Genauigkeit".into(),
+            "column_name" => "Spaltenname".into(),
+            _ => return None,
+        })
+    }
+
+    fn tooltip(&self, key: &str) -> Option<Cow<'static, str>> {
+        (key == "total_rows").then_some("Die Anzahl der Zeilen.".into())
+    }
+}
+
+#[test]
+fn settings_can_be_localized() {
+    let mut description = Component::new().settings_description();
+    description.localize(&German);
+
+    assert_eq!(description.fields[1].key, "total_rows");
+    assert_eq!(description.fields[1].text, "Zeilen insgesamt");
+    assert_eq!(description.fields[1].tooltip, "Die Anzahl der Zeilen.");
+    assert_eq!(description.fields[0].text, "Background");
+    assert_eq!(description.fields[15].text, "Spaltenname");
+    assert_eq!(description.groups[1].heading, "Genauigkeit");
+    // The names of the columns are chosen by the user.
+    assert_eq!(description.groups[3].key, None);
+    assert_eq!(description.groups[3].heading, "Time");
+}
@@ -0,0 +1,395 @@
+//! Provides the Tabs Component and relevant types for using it. The Tabs
+//! Component is a container that holds multiple child layouts, called tabs, of
+//! which only one is shown at a time. This allows switching between, for
+//! example, a page showing the splits and a page showing statistics about the
+//! run.
+
+use crate::{
+    layout::{Component as LayoutComponent, ComponentSettings, ComponentState, GeneralSettings},
+    platform::prelude::*,
+    settings::{Constraints, Field, Gradient, ImageCache, SettingsDescription, Value, ValueList},
+    timing::Snapshot,
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// The Tabs Component is a container that holds multiple child layouts, called
+/// tabs, of which only one is shown at a time.
+#[derive(Clone)]
+pub struct Component {
+    tabs: Vec<Tab>,
+    selected_tab: usize,
+    background: Gradient,
+    show_tab_names: bool,
+}
+
+/// A tab of the Tabs Component. Each tab is a child layout with its own list
+/// of components.
+#[derive(Clone)]
+pub struct Tab {
+    /// The name of the tab.
+    pub name: String,
+    /// The components shown while the tab is selected.
+    pub components: Vec<LayoutComponent>,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The tabs of the component.
+    pub tabs: Vec<TabSettings>,
+    /// The index of the tab that is shown.
+    pub selected_tab: usize,
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// Specifies whether the name of the selected tab is shown above its
+    /// components.
+    pub show_tab_names: bool,
+}
+
+/// The Settings of a single tab of the component.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TabSettings {
+    /// The name of the tab.
+    pub name: String,
+    /// The settings of the components of the tab.
+    pub components: Vec<ComponentSettings>,
+}
+
+/// The state object describes the information to visualize for this component.
+#[derive(Default, Serialize, Deserialize)]
+pub struct State {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The names of all the tabs.
+    pub tab_names: Vec<String>,
+    /// The index of the tab that is shown.
+    pub selected_tab: u32,
+    /// Specifies whether the name of the selected tab is shown above its
+    /// components.
+    pub show_tab_names: bool,
+    /// The state objects for all of the components of the selected tab.
+    pub components: Vec<ComponentState>,
+}
+
+#[cfg(feature = "std")]
+impl State {
+    /// Encodes the state object's information as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tabs: Vec::new(),
+            selected_tab: 0,
+            background: Gradient::Transparent,
+            show_tab_names: true,
+        }
+    }
+}
+
+impl Default for Component {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tab {
+    /// Creates a new tab with the name provided and no components.
+    pub const fn new(name: String) -> Self {
+        Self {
+            name,
+            components: Vec::new(),
+        }
+    }
+}
+
+impl Component {
+    /// Creates a new Tabs Component without any tabs.
+    pub const fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            selected_tab: 0,
+            background: Gradient::Transparent,
+            show_tab_names: true,
+        }
+    }
+
+    /// Creates a new Tabs Component with the given settings.
+    pub fn with_settings(settings: Settings) -> Self {
+        let mut component = Self {
+            tabs: settings
+                .tabs
+                .into_iter()
+                .map(|tab| Tab {
+                    name: tab.name,
+                    components: tab.components.into_iter().map(Into::into).collect(),
+                })
+                .collect(),
+            selected_tab: 0,
+            background: settings.background,
+            show_tab_names: settings.show_tab_names,
+        };
+        component.select_tab(settings.selected_tab);
+        component
+    }
+
+    /// Accesses the settings of the component. As the settings include the
+    /// settings of all the components of all the tabs, they are collected
+    /// into a new value.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            tabs: self
+                .tabs
+                .iter()
+                .map(|tab| TabSettings {
+                    name: tab.name.clone(),
+                    components: tab
+                        .components
+                        .iter()
+                        .map(LayoutComponent::settings)
+                        .collect(),
+                })
+                .collect(),
+            selected_tab: self.selected_tab,
+            background: self.background,
+            show_tab_names: self.show_tab_names,
+        }
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        "Tabs"
+    }
+
+    /// Accesses all the tabs of the component.
+    pub fn tabs(&self) -> &[Tab] {
+        &self.tabs
+    }
+
+    /// Grants mutable access to all the tabs of the component.
+    pub const fn tabs_mut(&mut self) -> &mut Vec<Tab> {
+        &mut self.tabs
+    }
+
+    /// Adds a new tab to the end of the list of tabs.
+    pub fn push_tab(&mut self, tab: Tab) {
+        self.tabs.push(tab);
+    }
+
+    /// Accesses the tab that is currently shown. There is none if the
+    /// component doesn't have any tabs.
+    pub fn selected_tab(&self) -> Option<&Tab> {
+        self.tabs.get(self.selected_tab)
+    }
+
+    /// Returns the index of the tab that is currently shown.
+    pub const fn selected_tab_index(&self) -> usize {
+        self.selected_tab
+    }
+
+    /// Shows the tab with the index provided. If there is no such tab, the
+    /// last tab is shown instead.
+    pub const fn select_tab(&mut self, index: usize) {
+        self.selected_tab = if index < self.tabs.len() {
+            index
+        } else {
+            self.tabs.len().saturating_sub(1)
+        };
+    }
+
+    /// Shows the next tab. If the last tab is shown, the first tab is shown
+    /// afterwards.
+    pub const fn next_tab(&mut self) {
+        self.selected_tab += 1;
+        if self.selected_tab >= self.tabs.len() {
+            self.selected_tab = 0;
+        }
+    }
+
+    /// Shows the previous tab. If the first tab is shown, the last tab is shown
+    /// afterwards.
+    pub const fn previous_tab(&mut self) {
+        if self.selected_tab == 0 {
+            self.selected_tab = self.tabs.len();
+        }
+        self.selected_tab = self.selected_tab.saturating_sub(1);
+    }
+
+    /// Scrolls up all the components of the selected tab that can be scrolled
+    /// up.
+    pub fn scroll_up(&mut self) {
+        if let Some(tab) = self.tabs.get_mut(self.selected_tab) {
+            for component in &mut tab.components {
+                component.scroll_up();
+            }
+        }
+    }
+
+    /// Scrolls down all the components of the selected tab that can be
+    /// scrolled down.
+    pub fn scroll_down(&mut self) {
+        if let Some(tab) = self.tabs.get_mut(self.selected_tab) {
+            for component in &mut tab.components {
+                component.scroll_down();
+            }
+        }
+    }
+
+    /// Updates the component's state based on the timer and layout settings
+    /// provided. Only the components of the selected tab are updated.
+    pub fn update_state(
+        &mut self,
+        state: &mut State,
+        image_cache: &mut ImageCache,
+        timer: &Snapshot,
+        layout_settings: &GeneralSettings,
+    ) {
+        state.background = self.background;
+        state.selected_tab = self.selected_tab as u32;
+        state.show_tab_names = self.show_tab_names;
+
+        state.tab_names.truncate(self.tabs.len());
+        let mut tabs = self.tabs.iter();
+        for (name, tab) in state.tab_names.iter_mut().zip(tabs.by_ref()) {
+            name.clone_from(&tab.name);
+        }
+        state.tab_names.extend(tabs.map(|tab| tab.name.clone()));
+
+        let Some(tab) = self.tabs.get_mut(self.selected_tab) else {
+            state.components.clear();
+            return;
+        };
+
+        state.components.truncate(tab.components.len());
+        let mut components = tab.components.iter_mut();
+        for (state, component) in state.components.iter_mut().zip(components.by_ref()) {
+            component.update_state(state, image_cache, timer, layout_settings);
+        }
+        state.components.extend(
+            components.map(|component| component.state(image_cache, timer, layout_settings)),
+        );
+    }
+
+    /// Calculates the component's state based on the timer and layout settings
+    /// provided. Only the components of the selected tab are visited.
+    pub fn state(
+        &mut self,
+        image_cache: &mut ImageCache,
+        timer: &Snapshot,
+        layout_settings: &GeneralSettings,
+    ) -> State {
+        let mut state = State::default();
+        self.update_state(&mut state, image_cache, timer, layout_settings);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values. The components of the tabs are not
+    /// part of the description.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new(
+                "background",
+                "Background".into(),
+                "The background shown behind the component.".into(),
+                self.background.into(),
+            ),
+            Field::new(
+                "show_tab_names",
+                "Show Tab Names".into(),
+                "Specifies whether the name of the selected tab is shown above its components."
+                    .into(),
+                self.show_tab_names.into(),
+            ),
+            Field::new(
+                "selected_tab",
+                "Selected Tab".into(),
+                "The number of the tab that is shown, starting at 1.".into(),
+                Value::UInt(self.selected_tab as u64 + 1),
+            )
+            .with_constraints(Constraints::range(1, self.tabs.len().max(1) as i64)),
+            Field::new(
+                "tab_names",
+                "Tab Names".into(),
+                "The names of the tabs in order. Adding a name adds a new empty tab, removing a \
+                name removes its tab and reordering the names reorders the tabs along with their \
+                components."
+                    .into(),
+                ValueList::new(
+                    self.tabs.iter().map(|tab| tab.name.clone()),
+                    String::new().into(),
+                )
+                .into(),
+            ),
+        ])
+    }
+
+    /// Sets the names of the tabs. Tabs are matched up with the names by their
+    /// current name first, so reordering or removing names reorders or
+    /// removes the tabs along with their components. Any remaining name is
+    /// then used to rename the unmatched tab at the same position, or adds a
+    /// new empty tab if there is none.
+    fn set_tab_names(&mut self, names: Vec<String>) {
+        let mut old_tabs: Vec<Option<Tab>> = self.tabs.drain(..).map(Some).collect();
+
+        let matched: Vec<Option<(usize, Tab)>> = names
+            .iter()
+            .map(|name| {
+                let index = old_tabs
+                    .iter()
+                    .position(|tab| tab.as_ref().is_some_and(|tab| tab.name == *name))?;
+                Some((index, old_tabs[index].take()?))
+            })
+            .collect();
+
+        let mut selected_tab = self.selected_tab;
+        for (index, (name, matched)) in names.into_iter().zip(matched).enumerate() {
+            let matched = matched.or_else(|| Some((index, old_tabs.get_mut(index)?.take()?)));
+            let tab = match matched {
+                Some((old_index, mut tab)) => {
+                    if old_index == self.selected_tab {
+                        selected_tab = index;
+                    }
+                    tab.name = name;
+                    tab
+                }
+                None => Tab::new(name),
+            };
+            self.tabs.push(tab);
+        }
+        self.select_tab(selected_tab);
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.background = value.into(),
+            1 => self.show_tab_names = value.into(),
+            2 => self.select_tab((value.into_uint().unwrap() as usize).saturating_sub(1)),
+            3 => self.set_tab_names(
+                ValueList::from(value)
+                    .items
+                    .into_iter()
+                    .filter_map(|name| match name {
+                        Value::String(name) => Some(name),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+}
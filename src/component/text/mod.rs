@@ -275,11 +275,12 @@ impl Component {
             second,
             is_variable,
             is_split,
-            (left_color, left_color_text),
-            (right_color, right_color_text),
+            (left_color_key, left_color, left_color_text),
+            (right_color_key, right_color, right_color_text),
         ) = match &self.settings.text {
             Text::Center(text) => (
                 Field::new(
+                    "text",
                     "Text".into(),
                     "Specifies the text to display in the center.".into(),
                     text.to_string().into(),
@@ -287,27 +288,38 @@ impl Component {
                 None,
                 false,
                 false,
-                ("Text Color", "The color of the text."),
-                ("", ""),
+                ("text_color", "Text Color", "The color of the text."),
+                ("", "", ""),
             ),
             Text::Split(left, right) => (
                 Field::new(
+                    "left",
                     "Left".into(),
                     "Specifies the text to display on the left.".into(),
                     left.to_string().into(),
                 ),
                 Some(Field::new(
+                    "right",
                     "Right".into(),
                     "Specifies the text to display on the right.".into(),
                     right.to_string().into(),
                 )),
                 false,
                 true,
-                ("Left Color", "The color of the text on the left."),
-                ("Right Color", "The color of the text on the right."),
+                (
+                    "left_color",
+                    "Left Color",
+                    "The color of the text on the left.",
+                ),
+                (
+                    "right_color",
+                    "Right Color",
+                    "The color of the text on the right.",
+                ),
             ),
             Text::Variable(var_name, is_split) => (
                 Field::new(
+                    "variable",
                     "Variable".into(),
                     "Specifies the name of the custom variable to display.".into(),
                     var_name.to_string().into(),
@@ -316,32 +328,48 @@ impl Component {
                 true,
                 *is_split,
                 if *is_split {
-                    ("Name Color", "The color of the variable name.")
+                    (
+                        "name_color",
+                        "Name Color",
+                        "The color of the variable name.",
+                    )
                 } else {
-                    ("Value Color", "The color of the variable value.")
+                    (
+                        "value_color",
+                        "Value Color",
+                        "The color of the variable value.",
+                    )
                 },
-                ("Value Color", "The color of the variable value."),
+                (
+                    "value_color",
+                    "Value Color",
+                    "The color of the variable value.",
+                ),
             ),
         };
 
         let mut fields = vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "use_variable",
                 "Use Variable".into(),
                 "Specifies whether to use a custom variable to display a dynamic value. Custom variables can be specified in the splits editor and provided automatically by auto splitters.".into(),
                 is_variable.into(),
             ),
             Field::new(
+                "split",
                 "Split".into(),
                 "Specifies whether to split the text into a left and right part. If this is not the case then only a single centered text is displayed.".into(),
                 is_split.into(),
             ),
             first,
             Field::new(
+                left_color_key,
                 left_color.into(),
                 left_color_text.into(),
                 self.settings.left_center_color.into(),
@@ -354,11 +382,13 @@ impl Component {
 
         if is_split {
             fields.push(Field::new(
+                right_color_key,
                 right_color.into(),
                 right_color_text.into(),
                 self.settings.right_color.into(),
             ));
             fields.push(Field::new(
+                "display_2_rows",
                 "Display 2 Rows".into(),
                 "Specifies whether to display the left and right text in two separate rows.".into(),
                 self.settings.display_two_rows.into(),
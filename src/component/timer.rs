@@ -7,7 +7,7 @@ use crate::{
     GeneralLayoutSettings, TimeSpan, TimerPhase, TimingMethod,
     analysis::split_color,
     platform::prelude::*,
-    settings::{Color, Field, Gradient, SemanticColor, SettingsDescription, Value},
+    settings::{Color, Constraints, Field, Gradient, SemanticColor, SettingsDescription, Value},
     timing::{
         Snapshot,
         formatter::{Accuracy, DigitsFormat, TimeFormatter, timer as formatter},
@@ -314,41 +314,50 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component. It is also possible to apply the color associated with the time ahead or behind as the background color.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "segment_timer",
                 "Segment Timer".into(),
                 "Specifies whether to show how much time has passed since the start of the current segment, rather than how much time has passed since the start of the current attempt.".into(),
                 self.settings.is_segment_timer.into(),
             ),
             Field::new(
+                "timing_method",
                 "Timing Method".into(),
                 "Specifies the timing method to use. If not specified, the current timing method is used.".into(),
                 self.settings.timing_method.into(),
             ),
             Field::new(
+                "height",
                 "Height".into(),
                 "The height of the timer.".into(),
                 u64::from(self.settings.height).into(),
-            ),
+            )
+            .with_constraints(Constraints::range(1, u32::MAX as i64)),
             Field::new(
+                "text_color",
                 "Text Color".into(),
                 "The color of the time shown. If not specified, the color is automatically chosen based on how well the current attempt is going. Those colors can be specified in the general settings for the layout.".into(),
                 self.settings.color_override.into(),
             ),
             Field::new(
+                "show_gradient",
                 "Show Gradient".into(),
                 "Determines whether to display the timer's color as a gradient.".into(),
                 self.settings.show_gradient.into(),
             ),
             Field::new(
+                "digits_format",
                 "Digits Format".into(),
                 "Specifies how many digits to show. If the duration is lower than the digits to be shown, zeros are shown instead.".into(),
                 self.settings.digits_format.into(),
             ),
             Field::new(
+                "accuracy",
                 "Accuracy".into(),
                 "The accuracy of the time shown.".into(),
                 self.settings.accuracy.into(),
@@ -100,6 +100,10 @@ pub struct State {
     /// The amount of total attempts. If `None` is specified, the amount of
     /// total attempts isn't supposed to be shown.
     pub attempts: Option<u32>,
+    /// Specifies whether the timer is in practice mode. If this is the case,
+    /// a practice indicator is supposed to be shown instead of the attempt
+    /// counts.
+    pub is_practicing: bool,
 }
 
 impl Default for Settings {
@@ -177,7 +181,7 @@ impl Component {
                 .filter(|a| a.time().real_time.is_some())
                 .count() as u32;
 
-            if timer.current_phase() == TimerPhase::Ended {
+            if timer.current_phase() == TimerPhase::Ended && !timer.is_practicing() {
                 count += 1;
             }
 
@@ -336,6 +340,7 @@ impl Component {
         state.text_color = self.settings.text_color;
         state.finished_runs = finished_runs;
         state.attempts = attempts;
+        state.is_practicing = timer.is_practicing();
         state.is_centered = is_centered;
     }
 
@@ -355,69 +360,81 @@ impl Component {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the component.".into(),
                 self.settings.background.into(),
             ),
             Field::new(
+                "text_color",
                 "Text Color".into(),
                 "The color of the title text. If no color is specified, the color is taken from the layout."
                     .into(),
                 self.settings.text_color.into(),
             ),
             Field::new(
+                "show_game_name",
                 "Show Game Name".into(),
                 "Specifies whether the game name should be part of the title that is being shown."
                     .into(),
                 self.settings.show_game_name.into(),
             ),
             Field::new(
+                "show_category_name",
                 "Show Category Name".into(),
                 "Specifies whether the category name should be part of the title that is being shown."
                     .into(),
                 self.settings.show_category_name.into(),
             ),
             Field::new(
+                "show_finished_runs_count",
                 "Show Finished Runs Count".into(),
                 "Specifies whether the number of successfully finished attempts should be shown."
                     .into(),
                 self.settings.show_finished_runs_count.into(),
             ),
             Field::new(
+                "show_attempt_count",
                 "Show Attempt Count".into(),
                 "Specifies whether the total number of attempts should be shown.".into(),
                 self.settings.show_attempt_count.into(),
             ),
             Field::new(
+                "text_alignment",
                 "Text Alignment".into(),
                 "Specifies the alignment of the title.".into(),
                 self.settings.text_alignment.into(),
             ),
             Field::new(
+                "display_text_as_single_line",
                 "Display Text as Single Line".into(),
                 "Specifies if the title should be shown as a single line, instead of being separated into one line for the game name and one for the category name."
                     .into(),
                 self.settings.display_as_single_line.into(),
             ),
             Field::new(
+                "display_game_icon",
                 "Display Game Icon".into(),
                 "Specifies whether the game's icon should be shown, if there is a game icon stored in the splits."
                     .into(),
                 self.settings.display_game_icon.into(),
             ),
             Field::new(
+                "show_region",
                 "Show Region".into(),
                 "The category name can be extended with additional information. This extends it with the game's region, if it is provided in the variables tab of the splits editor."
                     .into(),
                 self.settings.show_region.into(),
             ),
             Field::new(
+                "show_platform",
                 "Show Platform".into(),
                 "The category name can be extended with additional information. This extends it with the platform the game is being played on, if it is provided in the variables tab of the splits editor."
                     .into(),
                 self.settings.show_platform.into(),
             ),
             Field::new(
+                "show_variables",
                 "Show Variables".into(),
                 "The category name can be extended with additional information. This extends it with additional variables provided in the variables tab of the splits editor. This refers to speedrun.com variables, not custom variables."
                     .into(),
@@ -42,3 +42,26 @@ fn finished_runs_and_attempt_count() {
     );
     assert_eq!(component.state(&mut image_cache, &timer).attempts, Some(1));
 }
+
+#[test]
+fn practice_attempts_are_not_counted() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new(""));
+    let mut timer = Timer::new(run).unwrap();
+    timer.set_practice_mode(true).unwrap();
+
+    let component = Component::with_settings(Settings {
+        show_finished_runs_count: true,
+        show_attempt_count: true,
+        ..Default::default()
+    });
+
+    let mut image_cache = ImageCache::new();
+
+    timer.start().unwrap();
+    timer.split().unwrap();
+    let state = component.state(&mut image_cache, &timer);
+    assert!(state.is_practicing);
+    assert_eq!(state.finished_runs, Some(0));
+    assert_eq!(state.attempts, Some(0));
+}
@@ -13,7 +13,7 @@ use core::{future::Future, ops::Deref};
 
 use alloc::{borrow::Cow, sync::Arc};
 
-use crate::{TimeSpan, Timer, TimingMethod};
+use crate::{TimeSpan, Timer, TimingMethod, platform::prelude::*, timing::OverlayWidget};
 
 /// An event informs you about a change in the timer.
 #[derive(
@@ -59,6 +59,16 @@ pub enum Event {
     LoadingTimesSet = 16,
     /// A custom variable has been set.
     CustomVariableSet = 17,
+    /// The most recent action has been undone.
+    ActionUndone = 18,
+    /// The most recently undone action has been redone.
+    ActionRedone = 19,
+    /// The offset of the current attempt has been adjusted.
+    OffsetAdjusted = 20,
+    /// Time has been added to or removed from the current attempt.
+    TimeAdjusted = 21,
+    /// The overlay widgets have been set.
+    OverlayWidgetsSet = 22,
     /// An unknown event occurred.
     #[serde(other)]
     Unknown,
@@ -85,6 +95,11 @@ impl From<u32> for Event {
             15 => Event::GameTimeResumed,
             16 => Event::LoadingTimesSet,
             17 => Event::CustomVariableSet,
+            18 => Event::ActionUndone,
+            19 => Event::ActionRedone,
+            20 => Event::OffsetAdjusted,
+            21 => Event::TimeAdjusted,
+            22 => Event::OverlayWidgetsSet,
             _ => Event::Unknown,
         }
     }
@@ -139,6 +154,17 @@ pub enum Error {
     TimerPaused = 15,
     /// The runner decided to not reset the run.
     RunnerDecidedAgainstReset = 16,
+    /// There is no action to undo.
+    NothingToUndo = 17,
+    /// There is no action to redo.
+    NothingToRedo = 18,
+    /// The index of the segment is out of bounds.
+    SegmentIndexOutOfBounds = 19,
+    /// The split happened too soon after the previous split or the start of
+    /// the attempt.
+    SplitCooldown = 20,
+    /// The game time is not initialized.
+    GameTimeNotInitialized = 21,
     /// An unknown error occurred.
     #[serde(other)]
     Unknown,
@@ -164,6 +190,11 @@ impl From<u32> for Error {
             14 => Error::CouldNotParseTime,
             15 => Error::TimerPaused,
             16 => Error::RunnerDecidedAgainstReset,
+            17 => Error::NothingToUndo,
+            18 => Error::NothingToRedo,
+            19 => Error::SegmentIndexOutOfBounds,
+            20 => Error::SplitCooldown,
+            21 => Error::GameTimeNotInitialized,
             _ => Error::Unknown,
         }
     }
@@ -276,6 +307,16 @@ pub trait CommandSink {
         name: Cow<str>,
         value: Cow<str>,
     ) -> impl Future<Output = Result> + 'static;
+    /// Sets the overlay widgets that an auto splitter wants to display,
+    /// replacing all the previous ones. Command sinks that can't display any
+    /// widgets don't need to implement this, as it is not supported by default.
+    fn set_overlay_widgets(
+        &self,
+        widgets: Vec<OverlayWidget>,
+    ) -> impl Future<Output = Result> + 'static {
+        drop(widgets);
+        async { Err(Error::Unsupported) }
+    }
 }
 
 /// This trait provides functionality for querying information from the timer.
@@ -405,6 +446,14 @@ impl CommandSink for crate::SharedTimer {
         self.write().unwrap().set_custom_variable(name, value);
         async { Ok(Event::CustomVariableSet) }
     }
+
+    fn set_overlay_widgets(
+        &self,
+        widgets: Vec<OverlayWidget>,
+    ) -> impl Future<Output = Result> + 'static {
+        self.write().unwrap().set_overlay_widgets(widgets);
+        async { Ok(Event::OverlayWidgetsSet) }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -509,6 +558,13 @@ impl<T: CommandSink + ?Sized> CommandSink for Arc<T> {
     ) -> impl Future<Output = Result> + 'static {
         CommandSink::set_custom_variable(&**self, name, value)
     }
+
+    fn set_overlay_widgets(
+        &self,
+        widgets: Vec<OverlayWidget>,
+    ) -> impl Future<Output = Result> + 'static {
+        CommandSink::set_overlay_widgets(&**self, widgets)
+    }
 }
 
 impl<T: TimerQuery + ?Sized> TimerQuery for Arc<T> {
@@ -1,36 +1,337 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
 use crate::{
-    hotkey::Hotkey,
+    hotkey::{Binding, MidiMessage},
     platform::prelude::*,
     settings::{Field, SettingsDescription, Value},
 };
 use serde_derive::{Deserialize, Serialize};
 
+/// The number of actions that can be bound in each hotkey profile.
+const ACTIONS: usize = 13;
+
+// The indices of the settings, in the order they are described by
+// `HotkeyConfig::settings_description`. The bindings, the MIDI triggers and
+// the scopes each start with the setting of the first action.
+pub(crate) const BINDINGS_INDEX: usize = 0;
+const MIDI_DEVICE_INDEX: usize = BINDINGS_INDEX + ACTIONS;
+const MIDI_TRIGGERS_INDEX: usize = MIDI_DEVICE_INDEX + 1;
+pub(crate) const CYCLE_PROFILE_INDEX: usize = MIDI_TRIGGERS_INDEX + ACTIONS;
+const SCOPES_INDEX: usize = CYCLE_PROFILE_INDEX + 1;
+const SETTINGS_LEN: usize = SCOPES_INDEX + ACTIONS;
+
 /// The configuration to use for a [`HotkeySystem`](crate::HotkeySystem). It describes which [`Hotkey`](livesplit_hotkey::Hotkey) to use as hotkeys for the different actions.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// Instead of a single hotkey, an action can also be bound to a short sequence
+/// of hotkeys, as described by the [`Binding`]. The
+/// [`Trigger`](livesplit_hotkey::Trigger) of a binding allows requiring its
+/// hotkey to be double tapped or held down, which is useful to prevent
+/// accidentally resetting, for example. It can also delay the action until
+/// the hotkey gets released. The actions can additionally be
+/// triggered by the MIDI messages of a MIDI device, such as a foot pedal.
+///
+/// Additional named [`HotkeyProfile`]s can be stored in the configuration,
+/// which the [`HotkeySystem`](crate::HotkeySystem) can switch between at
+/// runtime, for example one per game or per keyboard layout.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HotkeyConfig {
     /// The key to use for splitting and starting a new attempt.
-    pub split: Option<Hotkey>,
+    pub split: Option<Binding>,
+    /// The key to use for resetting the current attempt.
+    pub reset: Option<Binding>,
+    /// The key to use for undoing the last split.
+    pub undo: Option<Binding>,
+    /// The key to use for skipping the current split.
+    pub skip: Option<Binding>,
+    /// The key to use for pausing the current attempt. It can also be used for
+    /// starting a new attempt.
+    pub pause: Option<Binding>,
+    /// The key to use for removing all the pause times from the current time.
+    pub undo_all_pauses: Option<Binding>,
+    /// The key to use for switching to the previous comparison.
+    pub previous_comparison: Option<Binding>,
+    /// The key to use for switching to the next comparison.
+    pub next_comparison: Option<Binding>,
+    /// The key to use for toggling between the `Real Time` and `Game Time`
+    /// timing methods.
+    pub toggle_timing_method: Option<Binding>,
+    /// The key to use for switching to the previous layout of the
+    /// [`LayoutManager`](crate::layout::LayoutManager).
+    pub previous_layout: Option<Binding>,
+    /// The key to use for switching to the next layout of the
+    /// [`LayoutManager`](crate::layout::LayoutManager).
+    pub next_layout: Option<Binding>,
+    /// The key to use for showing the previous tab of the
+    /// [`Tabs Components`](crate::component::tabs) in the current layout.
+    pub previous_tab: Option<Binding>,
+    /// The key to use for showing the next tab of the
+    /// [`Tabs Components`](crate::component::tabs) in the current layout.
+    pub next_tab: Option<Binding>,
+    /// The name of the MIDI device to listen to for the MIDI triggers. The
+    /// names of the devices that are available can be queried with
+    /// [`midi_devices`](livesplit_hotkey::midi_devices).
+    pub midi_device: Option<String>,
+    /// The MIDI messages to use as triggers for the actions, in addition to
+    /// their hotkeys.
+    pub midi: MidiTriggers,
+    /// The key to use for switching to the next hotkey profile. It is used
+    /// regardless of which profile is active.
+    pub cycle_profile: Option<Binding>,
+    /// The additional hotkey profiles. The hotkeys of the configuration itself
+    /// form the first profile, the profiles stored here follow after.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<HotkeyProfile>,
+    /// Whether the actions are triggered regardless of which application is
+    /// focused or only while the application using the
+    /// [`HotkeySystem`](crate::HotkeySystem) is focused.
+    pub scopes: HotkeyScopes,
+}
+
+/// Describes the binding of a [`HotkeyConfig`] that a new binding conflicts
+/// with. See [`HotkeyConfig::find_conflict`] for more information.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct HotkeyConflict {
+    /// The index of the hotkey profile the conflicting binding belongs to.
+    /// The hotkeys of the configuration itself are the profile at index 0,
+    /// the profiles stored in the configuration follow after.
+    pub profile: usize,
+    /// The index of the setting of the conflicting binding, as used by
+    /// [`HotkeyConfig::settings_description`].
+    pub index: usize,
+}
+
+/// A named set of hotkeys that is part of a [`HotkeyConfig`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct HotkeyProfile {
+    /// The name of the profile.
+    pub name: String,
+    /// The hotkeys of the profile.
+    pub hotkeys: ProfileBindings,
+}
+
+/// The bindings of a [`HotkeyProfile`]. These are the parts of a
+/// [`HotkeyConfig`] that differ between the profiles. The MIDI device and the
+/// hotkey for cycling through the profiles are shared by all of them.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileBindings {
+    /// The key to use for splitting and starting a new attempt.
+    pub split: Option<Binding>,
     /// The key to use for resetting the current attempt.
-    pub reset: Option<Hotkey>,
+    pub reset: Option<Binding>,
     /// The key to use for undoing the last split.
-    pub undo: Option<Hotkey>,
+    pub undo: Option<Binding>,
     /// The key to use for skipping the current split.
-    pub skip: Option<Hotkey>,
+    pub skip: Option<Binding>,
     /// The key to use for pausing the current attempt. It can also be used for
     /// starting a new attempt.
-    pub pause: Option<Hotkey>,
+    pub pause: Option<Binding>,
     /// The key to use for removing all the pause times from the current time.
-    pub undo_all_pauses: Option<Hotkey>,
+    pub undo_all_pauses: Option<Binding>,
     /// The key to use for switching to the previous comparison.
-    pub previous_comparison: Option<Hotkey>,
+    pub previous_comparison: Option<Binding>,
     /// The key to use for switching to the next comparison.
-    pub next_comparison: Option<Hotkey>,
+    pub next_comparison: Option<Binding>,
     /// The key to use for toggling between the `Real Time` and `Game Time`
     /// timing methods.
-    pub toggle_timing_method: Option<Hotkey>,
+    pub toggle_timing_method: Option<Binding>,
+    /// The key to use for switching to the previous layout.
+    pub previous_layout: Option<Binding>,
+    /// The key to use for switching to the next layout.
+    pub next_layout: Option<Binding>,
+    /// The key to use for showing the previous tab of the current layout.
+    pub previous_tab: Option<Binding>,
+    /// The key to use for showing the next tab of the current layout.
+    pub next_tab: Option<Binding>,
+    /// The MIDI messages to use as triggers for the actions, in addition to
+    /// their hotkeys.
+    pub midi: MidiTriggers,
+    /// The scopes of the hotkeys for the actions.
+    pub scopes: HotkeyScopes,
+}
+
+impl ProfileBindings {
+    pub(crate) const fn bindings(&self) -> [Option<Binding>; ACTIONS] {
+        [
+            self.split,
+            self.reset,
+            self.undo,
+            self.skip,
+            self.pause,
+            self.undo_all_pauses,
+            self.previous_comparison,
+            self.next_comparison,
+            self.toggle_timing_method,
+            self.previous_layout,
+            self.next_layout,
+            self.previous_tab,
+            self.next_tab,
+        ]
+    }
+}
+
+/// The MIDI messages to use as triggers for the actions of a [`HotkeyConfig`].
+/// A MIDI message triggers its action when it presses its note or controller.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MidiTriggers {
+    /// The MIDI message to use for splitting and starting a new attempt.
+    pub split: Option<MidiMessage>,
+    /// The MIDI message to use for resetting the current attempt.
+    pub reset: Option<MidiMessage>,
+    /// The MIDI message to use for undoing the last split.
+    pub undo: Option<MidiMessage>,
+    /// The MIDI message to use for skipping the current split.
+    pub skip: Option<MidiMessage>,
+    /// The MIDI message to use for pausing the current attempt. It can also be
+    /// used for starting a new attempt.
+    pub pause: Option<MidiMessage>,
+    /// The MIDI message to use for removing all the pause times from the
+    /// current time.
+    pub undo_all_pauses: Option<MidiMessage>,
+    /// The MIDI message to use for switching to the previous comparison.
+    pub previous_comparison: Option<MidiMessage>,
+    /// The MIDI message to use for switching to the next comparison.
+    pub next_comparison: Option<MidiMessage>,
+    /// The MIDI message to use for toggling between the `Real Time` and
+    /// `Game Time` timing methods.
+    pub toggle_timing_method: Option<MidiMessage>,
+    /// The MIDI message to use for switching to the previous layout.
+    pub previous_layout: Option<MidiMessage>,
+    /// The MIDI message to use for switching to the next layout.
+    pub next_layout: Option<MidiMessage>,
+    /// The MIDI message to use for showing the previous tab of the current
+    /// layout.
+    pub previous_tab: Option<MidiMessage>,
+    /// The MIDI message to use for showing the next tab of the current layout.
+    pub next_tab: Option<MidiMessage>,
+}
+
+impl MidiTriggers {
+    pub(crate) const fn as_array(&self) -> [Option<MidiMessage>; ACTIONS] {
+        [
+            self.split,
+            self.reset,
+            self.undo,
+            self.skip,
+            self.pause,
+            self.undo_all_pauses,
+            self.previous_comparison,
+            self.next_comparison,
+            self.toggle_timing_method,
+            self.previous_layout,
+            self.next_layout,
+            self.previous_tab,
+            self.next_tab,
+        ]
+    }
+
+    /// Returns [`true`] if the same MIDI message is used for multiple actions.
+    pub(crate) fn has_conflicts(&self) -> bool {
+        let triggers = self.as_array();
+        triggers
+            .iter()
+            .enumerate()
+            .any(|(index, trigger)| trigger.is_some() && triggers[index + 1..].contains(trigger))
+    }
+}
+
+/// Specifies when the hotkeys of an action are used.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum HotkeyScope {
+    /// The hotkeys are used regardless of which application is focused, so
+    /// the runner can control the timer while playing the game.
+    #[default]
+    Global,
+    /// The hotkeys are only used while the application using the
+    /// [`HotkeySystem`](crate::HotkeySystem) is focused, as reported by
+    /// [`HotkeySystem::set_focused`](crate::HotkeySystem::set_focused). This
+    /// prevents accidentally triggering the action while typing in other
+    /// applications, such as a chat.
+    Focused,
+}
+
+impl HotkeyScope {
+    /// Returns [`true`] if the hotkeys are only used while the application is
+    /// focused.
+    pub const fn is_focused(self) -> bool {
+        matches!(self, Self::Focused)
+    }
+
+    const fn from_focused(focused: bool) -> Self {
+        if focused { Self::Focused } else { Self::Global }
+    }
+}
+
+/// The scopes of the hotkeys for the actions of a [`HotkeyConfig`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeyScopes {
+    /// The scope of the hotkeys for splitting and starting a new attempt.
+    pub split: HotkeyScope,
+    /// The scope of the hotkeys for resetting the current attempt.
+    pub reset: HotkeyScope,
+    /// The scope of the hotkeys for undoing the last split.
+    pub undo: HotkeyScope,
+    /// The scope of the hotkeys for skipping the current split.
+    pub skip: HotkeyScope,
+    /// The scope of the hotkeys for pausing the current attempt.
+    pub pause: HotkeyScope,
+    /// The scope of the hotkeys for removing all the pause times from the current time.
+    pub undo_all_pauses: HotkeyScope,
+    /// The scope of the hotkeys for switching to the previous comparison.
+    pub previous_comparison: HotkeyScope,
+    /// The scope of the hotkeys for switching to the next comparison.
+    pub next_comparison: HotkeyScope,
+    /// The scope of the hotkeys for toggling between the `Real Time` and `Game Time` timing methods.
+    pub toggle_timing_method: HotkeyScope,
+    /// The scope of the hotkeys for switching to the previous layout.
+    pub previous_layout: HotkeyScope,
+    /// The scope of the hotkeys for switching to the next layout.
+    pub next_layout: HotkeyScope,
+    /// The scope of the hotkeys for showing the previous tab of the current layout.
+    pub previous_tab: HotkeyScope,
+    /// The scope of the hotkeys for showing the next tab of the current layout.
+    pub next_tab: HotkeyScope,
+}
+
+impl HotkeyScopes {
+    pub(crate) const fn as_array(&self) -> [HotkeyScope; ACTIONS] {
+        [
+            self.split,
+            self.reset,
+            self.undo,
+            self.skip,
+            self.pause,
+            self.undo_all_pauses,
+            self.previous_comparison,
+            self.next_comparison,
+            self.toggle_timing_method,
+            self.previous_layout,
+            self.next_layout,
+            self.previous_tab,
+            self.next_tab,
+        ]
+    }
+
+    const fn set(&mut self, index: usize, scope: HotkeyScope) {
+        match index {
+            0 => self.split = scope,
+            1 => self.reset = scope,
+            2 => self.undo = scope,
+            3 => self.skip = scope,
+            4 => self.pause = scope,
+            5 => self.undo_all_pauses = scope,
+            6 => self.previous_comparison = scope,
+            7 => self.next_comparison = scope,
+            8 => self.toggle_timing_method = scope,
+            9 => self.previous_layout = scope,
+            10 => self.next_layout = scope,
+            11 => self.previous_tab = scope,
+            12 => self.next_tab = scope,
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
 }
 
 impl Default for HotkeyConfig {
@@ -46,6 +347,15 @@ impl Default for HotkeyConfig {
             previous_comparison: Some(Numpad4.into()),
             next_comparison: Some(Numpad6.into()),
             toggle_timing_method: None,
+            previous_layout: None,
+            next_layout: None,
+            previous_tab: None,
+            next_tab: None,
+            midi_device: None,
+            midi: MidiTriggers::default(),
+            cycle_profile: None,
+            profiles: Vec::new(),
+            scopes: HotkeyScopes::default(),
         }
     }
 }
@@ -56,50 +366,251 @@ impl HotkeyConfig {
     pub fn settings_description(&self) -> SettingsDescription {
         SettingsDescription::with_fields(vec![
             Field::new(
+                "start_split",
                 "Start / Split".into(),
                 "The hotkey to use for splitting and starting a new attempt.".into(),
                 self.split.into(),
             ),
             Field::new(
+                "reset",
                 "Reset".into(),
                 "The hotkey to use for resetting the current attempt.".into(),
                 self.reset.into(),
             ),
             Field::new(
+                "undo_split",
                 "Undo Split".into(),
                 "The hotkey to use for undoing the last split.".into(),
                 self.undo.into(),
             ),
             Field::new(
+                "skip_split",
                 "Skip Split".into(),
                 "The hotkey to use for skipping the current split.".into(),
                 self.skip.into(),
             ),
             Field::new(
+                "pause",
                 "Pause".into(),
                 "The hotkey to use for pausing the current attempt. It can also be used for starting a new attempt.".into(),
                 self.pause.into(),
             ),
             Field::new(
+                "undo_all_pauses",
                 "Undo All Pauses".into(),
                 "The hotkey to use for removing all the pause times from the current time. This is useful in case you accidentally paused and want to undo it.".into(),
                 self.undo_all_pauses.into(),
             ),
             Field::new(
+                "previous_comparison",
                 "Previous Comparison".into(),
                 "The hotkey to use for switching to the previous comparison.".into(),
                 self.previous_comparison.into(),
             ),
             Field::new(
+                "next_comparison",
                 "Next Comparison".into(),
                 "The hotkey to use for switching to the next comparison.".into(),
                 self.next_comparison.into(),
             ),
             Field::new(
+                "toggle_timing_method",
                 "Toggle Timing Method".into(),
                 r#"The hotkey to use for toggling between the "Real Time" and "Game Time" timing methods."#.into(),
                 self.toggle_timing_method.into(),
             ),
+            Field::new(
+                "previous_layout",
+                "Previous Layout".into(),
+                "The hotkey to use for switching to the previous layout.".into(),
+                self.previous_layout.into(),
+            ),
+            Field::new(
+                "next_layout",
+                "Next Layout".into(),
+                "The hotkey to use for switching to the next layout.".into(),
+                self.next_layout.into(),
+            ),
+            Field::new(
+                "previous_tab",
+                "Previous Tab".into(),
+                "The hotkey to use for showing the previous tab of the tabbed regions of the layout.".into(),
+                self.previous_tab.into(),
+            ),
+            Field::new(
+                "next_tab",
+                "Next Tab".into(),
+                "The hotkey to use for showing the next tab of the tabbed regions of the layout.".into(),
+                self.next_tab.into(),
+            ),
+            Field::new(
+                "midi_device",
+                "MIDI Device".into(),
+                "The MIDI device to listen to for the MIDI triggers, such as a foot pedal or a stream deck.".into(),
+                self.midi_device.clone().into(),
+            ),
+            Field::new(
+                "start_split_midi",
+                "Start / Split (MIDI)".into(),
+                "The MIDI message to use for splitting and starting a new attempt.".into(),
+                self.midi.split.into(),
+            ),
+            Field::new(
+                "reset_midi",
+                "Reset (MIDI)".into(),
+                "The MIDI message to use for resetting the current attempt.".into(),
+                self.midi.reset.into(),
+            ),
+            Field::new(
+                "undo_split_midi",
+                "Undo Split (MIDI)".into(),
+                "The MIDI message to use for undoing the last split.".into(),
+                self.midi.undo.into(),
+            ),
+            Field::new(
+                "skip_split_midi",
+                "Skip Split (MIDI)".into(),
+                "The MIDI message to use for skipping the current split.".into(),
+                self.midi.skip.into(),
+            ),
+            Field::new(
+                "pause_midi",
+                "Pause (MIDI)".into(),
+                "The MIDI message to use for pausing the current attempt. It can also be used for starting a new attempt.".into(),
+                self.midi.pause.into(),
+            ),
+            Field::new(
+                "undo_all_pauses_midi",
+                "Undo All Pauses (MIDI)".into(),
+                "The MIDI message to use for removing all the pause times from the current time.".into(),
+                self.midi.undo_all_pauses.into(),
+            ),
+            Field::new(
+                "previous_comparison_midi",
+                "Previous Comparison (MIDI)".into(),
+                "The MIDI message to use for switching to the previous comparison.".into(),
+                self.midi.previous_comparison.into(),
+            ),
+            Field::new(
+                "next_comparison_midi",
+                "Next Comparison (MIDI)".into(),
+                "The MIDI message to use for switching to the next comparison.".into(),
+                self.midi.next_comparison.into(),
+            ),
+            Field::new(
+                "toggle_timing_method_midi",
+                "Toggle Timing Method (MIDI)".into(),
+                r#"The MIDI message to use for toggling between the "Real Time" and "Game Time" timing methods."#.into(),
+                self.midi.toggle_timing_method.into(),
+            ),
+            Field::new(
+                "previous_layout_midi",
+                "Previous Layout (MIDI)".into(),
+                "The MIDI message to use for switching to the previous layout.".into(),
+                self.midi.previous_layout.into(),
+            ),
+            Field::new(
+                "next_layout_midi",
+                "Next Layout (MIDI)".into(),
+                "The MIDI message to use for switching to the next layout.".into(),
+                self.midi.next_layout.into(),
+            ),
+            Field::new(
+                "previous_tab_midi",
+                "Previous Tab (MIDI)".into(),
+                "The MIDI message to use for showing the previous tab of the tabbed regions of the layout.".into(),
+                self.midi.previous_tab.into(),
+            ),
+            Field::new(
+                "next_tab_midi",
+                "Next Tab (MIDI)".into(),
+                "The MIDI message to use for showing the next tab of the tabbed regions of the layout.".into(),
+                self.midi.next_tab.into(),
+            ),
+            Field::new(
+                "cycle_profile",
+                "Cycle Profile".into(),
+                "The hotkey to use for switching to the next hotkey profile.".into(),
+                self.cycle_profile.into(),
+            ),
+            Field::new(
+                "start_split_focused_only",
+                "Start / Split (Only When Focused)".into(),
+                "Whether the hotkeys for splitting and starting a new attempt are only used while the application is focused.".into(),
+                self.scopes.split.is_focused().into(),
+            ),
+            Field::new(
+                "reset_focused_only",
+                "Reset (Only When Focused)".into(),
+                "Whether the hotkeys for resetting the current attempt are only used while the application is focused.".into(),
+                self.scopes.reset.is_focused().into(),
+            ),
+            Field::new(
+                "undo_split_focused_only",
+                "Undo Split (Only When Focused)".into(),
+                "Whether the hotkeys for undoing the last split are only used while the application is focused.".into(),
+                self.scopes.undo.is_focused().into(),
+            ),
+            Field::new(
+                "skip_split_focused_only",
+                "Skip Split (Only When Focused)".into(),
+                "Whether the hotkeys for skipping the current split are only used while the application is focused.".into(),
+                self.scopes.skip.is_focused().into(),
+            ),
+            Field::new(
+                "pause_focused_only",
+                "Pause (Only When Focused)".into(),
+                "Whether the hotkeys for pausing the current attempt are only used while the application is focused.".into(),
+                self.scopes.pause.is_focused().into(),
+            ),
+            Field::new(
+                "undo_all_pauses_focused_only",
+                "Undo All Pauses (Only When Focused)".into(),
+                "Whether the hotkeys for removing all the pause times from the current time are only used while the application is focused.".into(),
+                self.scopes.undo_all_pauses.is_focused().into(),
+            ),
+            Field::new(
+                "previous_comparison_focused_only",
+                "Previous Comparison (Only When Focused)".into(),
+                "Whether the hotkeys for switching to the previous comparison are only used while the application is focused.".into(),
+                self.scopes.previous_comparison.is_focused().into(),
+            ),
+            Field::new(
+                "next_comparison_focused_only",
+                "Next Comparison (Only When Focused)".into(),
+                "Whether the hotkeys for switching to the next comparison are only used while the application is focused.".into(),
+                self.scopes.next_comparison.is_focused().into(),
+            ),
+            Field::new(
+                "toggle_timing_method_focused_only",
+                "Toggle Timing Method (Only When Focused)".into(),
+                "Whether the hotkeys for toggling the timing method are only used while the application is focused.".into(),
+                self.scopes.toggle_timing_method.is_focused().into(),
+            ),
+            Field::new(
+                "previous_layout_focused_only",
+                "Previous Layout (Only When Focused)".into(),
+                "Whether the hotkeys for switching to the previous layout are only used while the application is focused.".into(),
+                self.scopes.previous_layout.is_focused().into(),
+            ),
+            Field::new(
+                "next_layout_focused_only",
+                "Next Layout (Only When Focused)".into(),
+                "Whether the hotkeys for switching to the next layout are only used while the application is focused.".into(),
+                self.scopes.next_layout.is_focused().into(),
+            ),
+            Field::new(
+                "previous_tab_focused_only",
+                "Previous Tab (Only When Focused)".into(),
+                "Whether the hotkeys for showing the previous tab are only used while the application is focused.".into(),
+                self.scopes.previous_tab.is_focused().into(),
+            ),
+            Field::new(
+                "next_tab_focused_only",
+                "Next Tab (Only When Focused)".into(),
+                "Whether the hotkeys for showing the next tab are only used while the application is focused.".into(),
+                self.scopes.next_tab.is_focused().into(),
+            ),
         ])
     }
 
@@ -107,37 +618,136 @@ impl HotkeyConfig {
     ///
     /// # Errors
     ///
-    /// An error is returned if a hotkey is already in use by a different
-    /// action.
+    /// An error is returned if the index of the setting is out of bounds or if
+    /// the type of the value is not compatible with the type of the setting's
+    /// value. An error is also returned if the binding conflicts with the
+    /// binding of a different action, meaning that they are either the same or
+    /// one of them is the beginning of the other. The same goes for MIDI
+    /// messages that are already used for a different action. The hotkey for
+    /// cycling through the profiles must not conflict with the bindings of any
+    /// of the profiles.
+    pub fn set_value(&mut self, index: usize, value: Value) -> Result<(), ()> {
+        self.settings_description()
+            .validate(index, &value)
+            .map_err(drop)?;
+
+        match index {
+            BINDINGS_INDEX..MIDI_DEVICE_INDEX => {
+                self.set_binding(index - BINDINGS_INDEX, value.into())
+            }
+            MIDI_DEVICE_INDEX => {
+                self.midi_device = value.into();
+                Ok(())
+            }
+            MIDI_TRIGGERS_INDEX..CYCLE_PROFILE_INDEX => {
+                self.set_midi_trigger(index - MIDI_TRIGGERS_INDEX, value.into())
+            }
+            CYCLE_PROFILE_INDEX => self.set_cycle_profile(value.into()),
+            SCOPES_INDEX..SETTINGS_LEN => {
+                self.scopes.set(
+                    index - SCOPES_INDEX,
+                    HotkeyScope::from_focused(value.into()),
+                );
+                Ok(())
+            }
+            _ => Err(()),
+        }
+    }
+
+    /// Checks whether the binding could be used for the setting at the index
+    /// specified, without actually changing the configuration. This is meant
+    /// for settings UIs that want to validate a binding before committing it.
+    /// If it conflicts with the binding of another setting, meaning that they
+    /// are either the same or one of them is the beginning of the other, that
+    /// setting is returned. The binding of the setting itself is ignored. The
+    /// hotkey for cycling through the profiles is checked against the bindings
+    /// of all the profiles.
     ///
     /// # Panics
     ///
-    /// This panics if the type of the value to be set is not compatible with
-    /// the type of the setting's value. A panic can also occur if the index of
-    /// the setting provided is out of bounds.
-    pub fn set_value(&mut self, index: usize, value: Value) -> Result<(), ()> {
-        let value: Option<Hotkey> = value.into();
-
-        if value.is_some() {
-            let any = [
-                self.split,
-                self.reset,
-                self.undo,
-                self.skip,
-                self.pause,
-                self.undo_all_pauses,
-                self.previous_comparison,
-                self.next_comparison,
-                self.toggle_timing_method,
-            ]
+    /// This panics if the index doesn't belong to a setting of a binding.
+    pub fn find_conflict(&self, index: usize, binding: Binding) -> Option<HotkeyConflict> {
+        assert!(
+            matches!(
+                index,
+                BINDINGS_INDEX..MIDI_DEVICE_INDEX | CYCLE_PROFILE_INDEX
+            ),
+            "Unsupported Setting Index"
+        );
+
+        let own = self
+            .bindings()
             .into_iter()
             .enumerate()
-            .filter(|&(i, _)| i != index)
-            .any(|(_, v)| v == value);
+            .map(|(index, other)| (BINDINGS_INDEX + index, other))
+            .chain([(CYCLE_PROFILE_INDEX, self.cycle_profile)])
+            .map(|(index, other)| (0, index, other));
 
-            if any {
-                return Err(());
-            }
+        // Only the hotkey for cycling through the profiles is shared between
+        // the profiles.
+        let profiles = self
+            .profiles
+            .iter()
+            .enumerate()
+            .filter(|_| index == CYCLE_PROFILE_INDEX)
+            .flat_map(|(profile, config)| {
+                config
+                    .hotkeys
+                    .bindings()
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, other)| (profile + 1, BINDINGS_INDEX + index, other))
+            });
+
+        own.chain(profiles)
+            .find(|&(profile, other_index, other)| {
+                (profile, other_index) != (0, index)
+                    && other.is_some_and(|other| binding.conflicts_with(&other))
+            })
+            .map(|(profile, index, _)| HotkeyConflict { profile, index })
+    }
+
+    pub(crate) const fn bindings(&self) -> [Option<Binding>; ACTIONS] {
+        self.profile_bindings().bindings()
+    }
+
+    /// Returns the bindings of the configuration itself, which form the first
+    /// hotkey profile.
+    pub const fn profile_bindings(&self) -> ProfileBindings {
+        ProfileBindings {
+            split: self.split,
+            reset: self.reset,
+            undo: self.undo,
+            skip: self.skip,
+            pause: self.pause,
+            undo_all_pauses: self.undo_all_pauses,
+            previous_comparison: self.previous_comparison,
+            next_comparison: self.next_comparison,
+            toggle_timing_method: self.toggle_timing_method,
+            previous_layout: self.previous_layout,
+            next_layout: self.next_layout,
+            previous_tab: self.previous_tab,
+            next_tab: self.next_tab,
+            midi: self.midi,
+            scopes: self.scopes,
+        }
+    }
+
+    fn set_cycle_profile(&mut self, value: Option<Binding>) -> Result<(), ()> {
+        if let Some(value) = value
+            && self.find_conflict(CYCLE_PROFILE_INDEX, value).is_some()
+        {
+            return Err(());
+        }
+        self.cycle_profile = value;
+        Ok(())
+    }
+
+    fn set_binding(&mut self, index: usize, value: Option<Binding>) -> Result<(), ()> {
+        if let Some(value) = value
+            && self.find_conflict(BINDINGS_INDEX + index, value).is_some()
+        {
+            return Err(());
         }
 
         match index {
@@ -150,6 +760,43 @@ impl HotkeyConfig {
             6 => self.previous_comparison = value,
             7 => self.next_comparison = value,
             8 => self.toggle_timing_method = value,
+            9 => self.previous_layout = value,
+            10 => self.next_layout = value,
+            11 => self.previous_tab = value,
+            12 => self.next_tab = value,
+            _ => panic!("Unsupported Setting Index"),
+        }
+
+        Ok(())
+    }
+
+    fn set_midi_trigger(&mut self, index: usize, value: Option<MidiMessage>) -> Result<(), ()> {
+        if value.is_some()
+            && self
+                .midi
+                .as_array()
+                .into_iter()
+                .enumerate()
+                .any(|(i, v)| i != index && v == value)
+        {
+            return Err(());
+        }
+
+        let midi = &mut self.midi;
+        match index {
+            0 => midi.split = value,
+            1 => midi.reset = value,
+            2 => midi.undo = value,
+            3 => midi.skip = value,
+            4 => midi.pause = value,
+            5 => midi.undo_all_pauses = value,
+            6 => midi.previous_comparison = value,
+            7 => midi.next_comparison = value,
+            8 => midi.toggle_timing_method = value,
+            9 => midi.previous_layout = value,
+            10 => midi.next_layout = value,
+            11 => midi.previous_tab = value,
+            12 => midi.next_tab = value,
             _ => panic!("Unsupported Setting Index"),
         }
 
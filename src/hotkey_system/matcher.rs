@@ -0,0 +1,318 @@
+use super::Action;
+use crate::{
+    hotkey::{Binding, Hotkey, KeyCode, Trigger},
+    platform::Duration,
+};
+
+/// How long the matcher waits for the next hotkey of a sequence before it
+/// starts over.
+pub const SEQUENCE_TIMEOUT: Duration = Duration::seconds(1);
+
+/// Describes what needs to happen after a hotkey got pressed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing needs to happen.
+    None,
+    /// The action needs to be triggered.
+    Trigger(Action),
+    /// A hotkey that needs to be held down got pressed. Once the duration
+    /// passed, [`Matcher::hold_elapsed`] needs to be called to trigger the
+    /// action if it is still held down.
+    Hold(Duration),
+}
+
+/// Keeps track of the hotkeys that got pressed to figure out which of the
+/// bindings got completed. Bindings that conflict with each other are not
+/// supported, so the binding that gets completed is always unambiguous. All
+/// the points in time are relative to an arbitrary, but fixed point in time.
+pub struct Matcher {
+    bindings: Vec<(Binding, Action)>,
+    pressed: Vec<(Hotkey, Duration)>,
+    hold: Option<Hold>,
+    release: Option<(KeyCode, Action)>,
+}
+
+struct Hold {
+    key_code: KeyCode,
+    action: Action,
+    until: Duration,
+}
+
+impl Matcher {
+    pub const fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pressed: Vec::new(),
+            hold: None,
+            release: None,
+        }
+    }
+
+    /// Replaces the bindings to match. Any sequence that is currently in
+    /// progress is canceled.
+    pub fn set_bindings(&mut self, bindings: Vec<(Binding, Action)>) {
+        self.bindings = bindings;
+        self.pressed.clear();
+        self.hold = None;
+        self.release = None;
+    }
+
+    /// Registers that the hotkey got pressed at the given point in time.
+    pub fn press(&mut self, hotkey: Hotkey, now: Duration) -> Outcome {
+        if self
+            .pressed
+            .last()
+            .is_some_and(|&(_, last_press)| now - last_press > SEQUENCE_TIMEOUT)
+        {
+            self.pressed.clear();
+        }
+        self.pressed.push((hotkey, now));
+
+        loop {
+            if let Some(&(binding, action)) = self
+                .bindings
+                .iter()
+                .find(|(binding, _)| self.completes(binding))
+            {
+                self.pressed.clear();
+                return match binding.trigger() {
+                    Trigger::Hold { millis } => {
+                        let duration = Duration::milliseconds(millis.into());
+                        self.hold = Some(Hold {
+                            key_code: hotkey.key_code,
+                            action,
+                            until: now + duration,
+                        });
+                        Outcome::Hold(duration)
+                    }
+                    Trigger::Release => {
+                        self.release = Some((hotkey.key_code, action));
+                        Outcome::None
+                    }
+                    _ => Outcome::Trigger(action),
+                };
+            }
+
+            let len = self.pressed.len();
+            if self.bindings.iter().any(|(binding, _)| {
+                binding.presses().count() > len
+                    && binding
+                        .presses()
+                        .take(len)
+                        .eq(self.pressed.iter().map(|&(hotkey, _)| hotkey))
+            }) {
+                return Outcome::None;
+            }
+
+            // The hotkey doesn't continue the sequence, but it may start a new
+            // one.
+            if len == 1 {
+                self.pressed.clear();
+                return Outcome::None;
+            }
+            self.pressed.drain(..len - 1);
+        }
+    }
+
+    /// Registers that the key got released at the given point in time.
+    /// Returns the action to trigger if the key belongs to a hotkey that was
+    /// held down for long enough, but the action wasn't triggered yet, or to
+    /// a hotkey that triggers its action when released.
+    pub fn release(&mut self, key_code: KeyCode, now: Duration) -> Option<Action> {
+        if let Some((_, action)) = self.release.take_if(|&mut (key, _)| key == key_code) {
+            return Some(action);
+        }
+        if self
+            .hold
+            .as_ref()
+            .is_some_and(|hold| hold.key_code == key_code)
+        {
+            let hold = self.hold.take()?;
+            if now >= hold.until {
+                return Some(hold.action);
+            }
+        }
+        None
+    }
+
+    /// Returns the action to trigger if a hotkey has been held down for long
+    /// enough.
+    pub fn hold_elapsed(&mut self, now: Duration) -> Option<Action> {
+        if self.hold.as_ref().is_some_and(|hold| now >= hold.until) {
+            return self.hold.take().map(|hold| hold.action);
+        }
+        None
+    }
+
+    fn completes(&self, binding: &Binding) -> bool {
+        if !binding
+            .presses()
+            .eq(self.pressed.iter().map(|&(hotkey, _)| hotkey))
+        {
+            return false;
+        }
+        match binding.trigger() {
+            Trigger::DoubleTap { within_millis } => {
+                let [.., (_, first), (_, second)] = self.pressed[..] else {
+                    return false;
+                };
+                second - first <= Duration::milliseconds(within_millis.into())
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hotkey::Modifiers;
+
+    fn matcher() -> Matcher {
+        let mut matcher = Matcher::new();
+        matcher.set_bindings(vec![
+            (KeyCode::Numpad1.into(), Action::Split),
+            (
+                KeyCode::KeyR
+                    .with_modifiers(Modifiers::CONTROL | Modifiers::SHIFT)
+                    .into(),
+                Action::Reset,
+            ),
+            (
+                Binding::sequence(&[
+                    KeyCode::KeyK.with_modifiers(Modifiers::CONTROL),
+                    KeyCode::KeyU.into(),
+                ])
+                .unwrap(),
+                Action::Undo,
+            ),
+            (
+                Binding::from(KeyCode::Numpad5)
+                    .with_trigger(Trigger::DoubleTap { within_millis: 300 }),
+                Action::Pause,
+            ),
+            (
+                Binding::from(KeyCode::Numpad3).with_trigger(Trigger::Hold { millis: 1000 }),
+                Action::UndoAllPauses,
+            ),
+            (
+                Binding::from(KeyCode::Numpad7).with_trigger(Trigger::Release),
+                Action::Skip,
+            ),
+        ]);
+        matcher
+    }
+
+    fn ms(millis: i64) -> Duration {
+        Duration::milliseconds(millis)
+    }
+
+    #[test]
+    fn single_hotkeys_trigger_immediately() {
+        let mut matcher = matcher();
+        let now = Duration::ZERO;
+        assert_eq!(
+            matcher.press(KeyCode::Numpad1.into(), now),
+            Outcome::Trigger(Action::Split),
+        );
+        assert_eq!(
+            matcher.press(
+                KeyCode::KeyR.with_modifiers(Modifiers::CONTROL | Modifiers::SHIFT),
+                now
+            ),
+            Outcome::Trigger(Action::Reset),
+        );
+        assert_eq!(matcher.press(KeyCode::KeyR.into(), now), Outcome::None);
+    }
+
+    #[test]
+    fn sequences_trigger_once_completed() {
+        let mut matcher = matcher();
+        let now = Duration::ZERO;
+        let ctrl_k = KeyCode::KeyK.with_modifiers(Modifiers::CONTROL);
+
+        assert_eq!(matcher.press(ctrl_k, now), Outcome::None);
+        assert_eq!(
+            matcher.press(KeyCode::KeyU.into(), now),
+            Outcome::Trigger(Action::Undo),
+        );
+        assert_eq!(matcher.press(KeyCode::KeyU.into(), now), Outcome::None);
+
+        // A hotkey that doesn't continue the sequence starts over.
+        assert_eq!(matcher.press(ctrl_k, now), Outcome::None);
+        assert_eq!(
+            matcher.press(KeyCode::Numpad1.into(), now),
+            Outcome::Trigger(Action::Split),
+        );
+        assert_eq!(matcher.press(KeyCode::KeyU.into(), now), Outcome::None);
+
+        assert_eq!(matcher.press(ctrl_k, now), Outcome::None);
+        assert_eq!(matcher.press(ctrl_k, now), Outcome::None);
+        assert_eq!(
+            matcher.press(KeyCode::KeyU.into(), now),
+            Outcome::Trigger(Action::Undo),
+        );
+    }
+
+    #[test]
+    fn sequences_time_out() {
+        let mut matcher = matcher();
+        let ctrl_k = KeyCode::KeyK.with_modifiers(Modifiers::CONTROL);
+
+        assert_eq!(matcher.press(ctrl_k, ms(0)), Outcome::None);
+        assert_eq!(
+            matcher.press(KeyCode::KeyU.into(), SEQUENCE_TIMEOUT + ms(1)),
+            Outcome::None,
+        );
+    }
+
+    #[test]
+    fn double_taps_need_to_be_quick() {
+        let mut matcher = matcher();
+        let numpad5 = KeyCode::Numpad5.into();
+
+        assert_eq!(matcher.press(numpad5, ms(0)), Outcome::None);
+        assert_eq!(matcher.press(numpad5, ms(400)), Outcome::None);
+        assert_eq!(
+            matcher.press(numpad5, ms(600)),
+            Outcome::Trigger(Action::Pause),
+        );
+        assert_eq!(matcher.press(numpad5, ms(700)), Outcome::None);
+    }
+
+    #[test]
+    fn holds_trigger_once_held_long_enough() {
+        let mut matcher = matcher();
+        let numpad3 = KeyCode::Numpad3.into();
+
+        assert_eq!(matcher.press(numpad3, ms(0)), Outcome::Hold(ms(1000)));
+        assert_eq!(matcher.hold_elapsed(ms(500)), None);
+        assert_eq!(matcher.release(KeyCode::Numpad3, ms(600)), None);
+        assert_eq!(matcher.hold_elapsed(ms(1000)), None);
+
+        assert_eq!(matcher.press(numpad3, ms(2000)), Outcome::Hold(ms(1000)));
+        assert_eq!(matcher.hold_elapsed(ms(3000)), Some(Action::UndoAllPauses));
+        assert_eq!(matcher.release(KeyCode::Numpad3, ms(3500)), None);
+
+        // Even if the hold didn't get reported, releasing the key triggers the
+        // action.
+        assert_eq!(matcher.press(numpad3, ms(4000)), Outcome::Hold(ms(1000)));
+        assert_eq!(
+            matcher.release(KeyCode::Numpad3, ms(5500)),
+            Some(Action::UndoAllPauses),
+        );
+    }
+
+    #[test]
+    fn releases_trigger_once_released() {
+        let mut matcher = matcher();
+
+        assert_eq!(matcher.press(KeyCode::Numpad7.into(), ms(0)), Outcome::None);
+        assert_eq!(matcher.release(KeyCode::Numpad1, ms(100)), None);
+        assert_eq!(
+            matcher.release(KeyCode::Numpad7, ms(200)),
+            Some(Action::Skip),
+        );
+        assert_eq!(matcher.release(KeyCode::Numpad7, ms(300)), None);
+    }
+}
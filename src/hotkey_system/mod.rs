@@ -0,0 +1,1115 @@
+use alloc::borrow::Cow;
+use core::iter;
+use std::sync::{Arc, Mutex, RwLockWriteGuard};
+#[cfg(not(target_family = "wasm"))]
+use std::sync::{
+    Weak,
+    mpsc::{self, RecvTimeoutError},
+};
+
+use crate::{
+    HotkeyConfig, HotkeyConflict, ProfileBindings, event,
+    hotkey::{
+        Binding, ConsumePreference, Error, Hook, Hotkey, HotkeyEvent, KeyCode, MidiHook,
+        MidiMessage,
+    },
+    hotkey_config::{BINDINGS_INDEX, CYCLE_PROFILE_INDEX},
+    layout::{LayoutManager, SharedLayoutManager},
+    platform::{Duration, Instant},
+};
+
+mod matcher;
+
+use self::matcher::{Matcher, Outcome};
+
+pub use crate::hotkey::Result;
+
+// This enum might be better situated in hotkey_config, but the last method should stay in this file
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Action {
+    Split,
+    /// The key to use for resetting the current attempt.
+    Reset,
+    /// The key to use for undoing the last split.
+    Undo,
+    /// The key to use for skipping the current split.
+    Skip,
+    /// The key to use for pausing the current attempt and starting a new
+    /// attempt.
+    Pause,
+    /// The key to use for removing all the pause times from the current time.
+    UndoAllPauses,
+    /// The key to use for switching to the previous comparison.
+    PreviousComparison,
+    /// The key to use for switching to the next comparison.
+    NextComparison,
+    /// The key to use for toggling between the `Real Time` and `Game Time`
+    /// timing methods.
+    ToggleTimingMethod,
+    /// The key to use for switching to the previous layout.
+    PreviousLayout,
+    /// The key to use for switching to the next layout.
+    NextLayout,
+    /// The key to use for showing the previous tab of the current layout.
+    PreviousTab,
+    /// The key to use for showing the next tab of the current layout.
+    NextTab,
+    /// The key to use for switching to the next hotkey profile. It is not part
+    /// of [`Action::ALL`], as the dispatcher handles it itself.
+    CycleProfile,
+}
+
+impl Action {
+    const ALL: [Action; 13] = [
+        Action::Split,
+        Action::Reset,
+        Action::Undo,
+        Action::Skip,
+        Action::Pause,
+        Action::UndoAllPauses,
+        Action::PreviousComparison,
+        Action::NextComparison,
+        Action::ToggleTimingMethod,
+        Action::PreviousLayout,
+        Action::NextLayout,
+        Action::PreviousTab,
+        Action::NextTab,
+    ];
+
+    const fn set_hotkey(self, config: &mut HotkeyConfig, hotkey: Option<Binding>) {
+        match self {
+            Action::Split => config.split = hotkey,
+            Action::Reset => config.reset = hotkey,
+            Action::Undo => config.undo = hotkey,
+            Action::Skip => config.skip = hotkey,
+            Action::Pause => config.pause = hotkey,
+            Action::UndoAllPauses => config.undo_all_pauses = hotkey,
+            Action::PreviousComparison => config.previous_comparison = hotkey,
+            Action::NextComparison => config.next_comparison = hotkey,
+            Action::ToggleTimingMethod => config.toggle_timing_method = hotkey,
+            Action::PreviousLayout => config.previous_layout = hotkey,
+            Action::NextLayout => config.next_layout = hotkey,
+            Action::PreviousTab => config.previous_tab = hotkey,
+            Action::NextTab => config.next_tab = hotkey,
+            Action::CycleProfile => config.cycle_profile = hotkey,
+        }
+    }
+
+    const fn get_hotkey(self, config: &HotkeyConfig) -> Option<Binding> {
+        match self {
+            Action::Split => config.split,
+            Action::Reset => config.reset,
+            Action::Undo => config.undo,
+            Action::Skip => config.skip,
+            Action::Pause => config.pause,
+            Action::UndoAllPauses => config.undo_all_pauses,
+            Action::PreviousComparison => config.previous_comparison,
+            Action::NextComparison => config.next_comparison,
+            Action::ToggleTimingMethod => config.toggle_timing_method,
+            Action::PreviousLayout => config.previous_layout,
+            Action::NextLayout => config.next_layout,
+            Action::PreviousTab => config.previous_tab,
+            Action::NextTab => config.next_tab,
+            Action::CycleProfile => config.cycle_profile,
+        }
+    }
+
+    fn callback<S: event::CommandSink + Send + 'static>(
+        self,
+        command_sink: S,
+        layout_manager: Option<SharedLayoutManager>,
+    ) -> Callback {
+        match self {
+            Action::Split => Box::new(move || {
+                drop(command_sink.split_or_start());
+            }),
+            Action::Reset => Box::new(move || {
+                drop(command_sink.reset(None));
+            }),
+            Action::Undo => Box::new(move || {
+                drop(command_sink.undo_split());
+            }),
+            Action::Skip => Box::new(move || {
+                drop(command_sink.skip_split());
+            }),
+            Action::Pause => Box::new(move || {
+                drop(command_sink.toggle_pause_or_start());
+            }),
+            Action::UndoAllPauses => Box::new(move || {
+                drop(command_sink.undo_all_pauses());
+            }),
+            Action::PreviousComparison => Box::new(move || {
+                drop(command_sink.switch_to_previous_comparison());
+            }),
+            Action::NextComparison => Box::new(move || {
+                drop(command_sink.switch_to_next_comparison());
+            }),
+            Action::ToggleTimingMethod => Box::new(move || {
+                drop(command_sink.toggle_timing_method());
+            }),
+            Action::PreviousLayout => Box::new(move || {
+                if let Some(mut layout_manager) = write(&layout_manager) {
+                    layout_manager.previous();
+                }
+            }),
+            Action::NextLayout => Box::new(move || {
+                if let Some(mut layout_manager) = write(&layout_manager) {
+                    layout_manager.next();
+                }
+            }),
+            Action::PreviousTab => Box::new(move || {
+                if let Some(mut layout_manager) = write(&layout_manager) {
+                    layout_manager.current_mut().previous_tab();
+                }
+            }),
+            Action::NextTab => Box::new(move || {
+                if let Some(mut layout_manager) = write(&layout_manager) {
+                    layout_manager.current_mut().next_tab();
+                }
+            }),
+            Action::CycleProfile => Box::new(|| {}),
+        }
+    }
+}
+
+/// Locks the Layout Manager for one of the actions, if one is attached. Just
+/// like a failing timer command, a poisoned lock turns the action into a
+/// no-op instead of taking down the thread that dispatches the hotkeys.
+fn write(
+    layout_manager: &Option<SharedLayoutManager>,
+) -> Option<RwLockWriteGuard<'_, LayoutManager>> {
+    layout_manager.as_ref()?.write().ok()
+}
+
+type Callback = Box<dyn FnMut() + Send + 'static>;
+
+/// The callbacks are invoked after the dispatcher got unlocked, as they lock
+/// the timer and the layout manager. A frontend may hold either of them while
+/// calling into the Hotkey System, which locks the dispatcher.
+type SharedCallback = Arc<Mutex<Callback>>;
+
+/// A binding of an action in one of the hotkey profiles, as enumerated by
+/// [`HotkeySystem::bindings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundAction {
+    /// The index of the hotkey profile the binding belongs to. The hotkeys of
+    /// the configuration itself are the profile at index 0, the profiles
+    /// stored in the configuration follow after.
+    pub profile: usize,
+    /// The index of the setting of the binding, as used by
+    /// [`HotkeyConfig::settings_description`].
+    pub index: usize,
+    /// The binding of the action.
+    pub binding: Binding,
+    /// The binding resolved according to the current keyboard layout, so it
+    /// can be shown to the user.
+    pub name: String,
+}
+
+/// A hotkey that got captured by [`HotkeySystem::capture_next`], as a
+/// candidate for a new binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedHotkey {
+    /// The binding consisting of the hotkey that got pressed, including the
+    /// modifiers that were held down.
+    pub binding: Binding,
+    /// The binding resolved according to the current keyboard layout, so it
+    /// can be shown to the user.
+    pub name: String,
+}
+
+/// The state of capturing the next hotkey that gets pressed.
+enum Capture {
+    Idle,
+    Capturing,
+    Captured(Hotkey),
+}
+
+/// The bindings and MIDI triggers of a single hotkey profile.
+struct Profile {
+    bindings: Vec<(Binding, Action)>,
+    midi: Vec<(MidiMessage, Action)>,
+    focused_only: Vec<Action>,
+}
+
+impl Profile {
+    fn new(bindings: &ProfileBindings, cycle_profile: Option<Binding>) -> Self {
+        Self {
+            bindings: iter::zip(bindings.bindings(), Action::ALL)
+                .filter_map(|(binding, action)| Some((binding?, action)))
+                .chain(cycle_profile.map(|binding| (binding, Action::CycleProfile)))
+                .collect(),
+            midi: iter::zip(bindings.midi.as_array(), Action::ALL)
+                .filter_map(|(message, action)| Some((message?, action)))
+                .collect(),
+            focused_only: iter::zip(bindings.scopes.as_array(), Action::ALL)
+                .filter(|(scope, _)| scope.is_focused())
+                .map(|(_, action)| action)
+                .collect(),
+        }
+    }
+}
+
+/// Dispatches the hotkeys that got pressed to the actions whose bindings they
+/// complete. Only the bindings of the active profile are matched. The hotkeys
+/// of all the profiles are registered at all times, so switching the profile
+/// doesn't require touching the hook, which may be the one calling us.
+struct Dispatcher {
+    matcher: Matcher,
+    callbacks: Vec<(Action, SharedCallback)>,
+    profiles: Vec<Profile>,
+    active_profile: usize,
+    is_focused: bool,
+    capture: Capture,
+    hold_timer: HoldTimer,
+    start: Instant,
+}
+
+impl Dispatcher {
+    fn now(&self) -> Duration {
+        Instant::now() - self.start
+    }
+
+    fn set_profiles(&mut self, profiles: Vec<Profile>) {
+        self.profiles = profiles;
+        let index = if self.active_profile < self.profiles.len() {
+            self.active_profile
+        } else {
+            0
+        };
+        self.select_profile(index);
+    }
+
+    fn select_profile(&mut self, index: usize) {
+        self.active_profile = index;
+        let bindings = self
+            .profiles
+            .get(index)
+            .map(|profile| profile.bindings.clone())
+            .unwrap_or_default();
+        self.matcher.set_bindings(bindings);
+    }
+
+    fn next_profile(&mut self) {
+        let index = self.active_profile + 1;
+        self.select_profile(if index < self.profiles.len() {
+            index
+        } else {
+            0
+        });
+    }
+
+    /// Triggers the action and returns the callback that needs to be invoked
+    /// for it, once the dispatcher is unlocked again.
+    fn trigger(&mut self, action: Action) -> Option<SharedCallback> {
+        // While capturing, the hook doesn't report any hotkeys, but MIDI
+        // messages and hotkeys that were held down before are still coming
+        // in. None of them are meant to control the timer.
+        if let Capture::Capturing = self.capture {
+            return None;
+        }
+
+        if !self.is_focused
+            && self
+                .profiles
+                .get(self.active_profile)
+                .is_some_and(|profile| profile.focused_only.contains(&action))
+        {
+            return None;
+        }
+
+        if action == Action::CycleProfile {
+            self.next_profile();
+            None
+        } else {
+            self.callbacks
+                .iter()
+                .find(|(a, _)| *a == action)
+                .map(|(_, callback)| callback.clone())
+        }
+    }
+
+    fn trigger_midi(&mut self, message: MidiMessage) -> Option<SharedCallback> {
+        let &(_, action) = self
+            .profiles
+            .get(self.active_profile)?
+            .midi
+            .iter()
+            .find(|(m, _)| *m == message)?;
+        self.trigger(action)
+    }
+}
+
+fn invoke(callback: Option<SharedCallback>) {
+    if let Some(callback) = callback {
+        (callback.lock().unwrap())();
+    }
+}
+
+fn dispatch(dispatcher: &Arc<Mutex<Dispatcher>>, hotkey: Hotkey, event: HotkeyEvent) {
+    let callback = {
+        let mut guard = dispatcher.lock().unwrap();
+        let now = guard.now();
+        match event {
+            HotkeyEvent::Pressed => match guard.matcher.press(hotkey, now) {
+                Outcome::None => None,
+                Outcome::Trigger(action) => guard.trigger(action),
+                Outcome::Hold(duration) => {
+                    let start = guard.start;
+                    guard
+                        .hold_timer
+                        .wait_until(dispatcher, start, now + duration);
+                    None
+                }
+            },
+            HotkeyEvent::Released => guard
+                .matcher
+                .release(hotkey.key_code, now)
+                .and_then(|action| guard.trigger(action)),
+        }
+    };
+    invoke(callback);
+}
+
+/// Waits for hotkeys to be held down for long enough on a single thread that
+/// is shared by all the holds. The matcher only keeps track of a single hold
+/// at a time, so only the latest deadline needs to be waited for. A hold that
+/// ended early is simply ignored by the matcher once the deadline is reached.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Default)]
+struct HoldTimer {
+    deadlines: Option<mpsc::Sender<Duration>>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl HoldTimer {
+    /// Checks whether the hold elapsed once the deadline, relative to the start
+    /// of the dispatcher, is reached. The thread gets started on first use.
+    fn wait_until(
+        &mut self,
+        dispatcher: &Arc<Mutex<Dispatcher>>,
+        start: Instant,
+        deadline: Duration,
+    ) {
+        if let Some(deadlines) = &self.deadlines
+            && deadlines.send(deadline).is_ok()
+        {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(deadline);
+        self.deadlines = Some(sender);
+
+        // The thread only holds on to the dispatcher weakly. Dropping the
+        // dispatcher drops the sender, which stops the thread.
+        let dispatcher = Arc::downgrade(dispatcher);
+        std::thread::spawn(move || wait_for_holds(&dispatcher, start, &receiver));
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn wait_for_holds(
+    dispatcher: &Weak<Mutex<Dispatcher>>,
+    start: Instant,
+    deadlines: &mpsc::Receiver<Duration>,
+) {
+    let mut deadline: Option<Duration> = None;
+    loop {
+        let result = match deadline {
+            Some(deadline) => {
+                // We wait a little longer, as the clock used for waiting may
+                // not exactly agree with ours.
+                let timeout = deadline + Duration::milliseconds(1) - (Instant::now() - start);
+                deadlines.recv_timeout(timeout.try_into().unwrap_or_default())
+            }
+            None => deadlines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match result {
+            Ok(new_deadline) => deadline = Some(new_deadline),
+            Err(RecvTimeoutError::Timeout) => {
+                deadline = None;
+                let Some(dispatcher) = dispatcher.upgrade() else {
+                    return;
+                };
+                let callback = {
+                    let mut dispatcher = dispatcher.lock().unwrap();
+                    let now = dispatcher.now();
+                    dispatcher
+                        .matcher
+                        .hold_elapsed(now)
+                        .and_then(|action| dispatcher.trigger(action))
+                };
+                invoke(callback);
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+// There are no threads we could wait on, so the action is only triggered once
+// the hotkey gets released after it was held down for long enough.
+#[cfg(target_family = "wasm")]
+#[derive(Default)]
+struct HoldTimer;
+
+#[cfg(target_family = "wasm")]
+impl HoldTimer {
+    fn wait_until(&mut self, _: &Arc<Mutex<Dispatcher>>, _: Instant, _: Duration) {}
+}
+
+/// With a `HotkeySystem` the runner can use hotkeys on their keyboard to control
+/// the Timer. The hotkeys are global, so the application doesn't need to be in
+/// focus. The behavior of the hotkeys depends on the platform and is stubbed
+/// out on platforms that don't support hotkeys. You can turn off a `HotkeySystem`
+/// temporarily. By default the `HotkeySystem` is activated. If a
+/// [`LayoutManager`](crate::layout::LayoutManager) is attached, the hotkeys
+/// can also be used to switch between its layouts.
+///
+/// Actions can be bound to short sequences of hotkeys, such as
+/// `Ctrl + KeyK, KeyS`. The hotkeys of a sequence need to be pressed within a
+/// second of each other. Bindings can also require their last hotkey to be
+/// double tapped or held down, or trigger their action only once it gets
+/// released, as specified by their [`Trigger`](crate::hotkey::Trigger).
+///
+/// If the configuration specifies a MIDI device, its MIDI messages can
+/// trigger the actions as well. If the device can't be opened, for example
+/// because it is not connected, only the hotkeys are used until the
+/// configuration gets applied again.
+///
+/// The hotkeys of an action can be restricted to only be used while the
+/// application is focused, as specified by its
+/// [`HotkeyScope`](crate::HotkeyScope). The Hotkey System doesn't know about
+/// the windows of the application, so the application needs to report
+/// whether it is focused via [`set_focused`](Self::set_focused).
+///
+/// The hotkeys of the configuration form the first hotkey profile, followed
+/// by the [`HotkeyProfile`](crate::HotkeyProfile)s stored in the
+/// configuration. Only the hotkeys of the active profile trigger their
+/// actions. The profiles can be switched at runtime, either through the API
+/// or with the hotkey for cycling through them.
+///
+/// To let the user choose a new binding by pressing it, the next hotkey that
+/// gets pressed can be captured via [`capture_next`](Self::capture_next).
+pub struct HotkeySystem<S> {
+    config: HotkeyConfig,
+    hook: Hook,
+    midi: Option<(String, MidiHook)>,
+    command_sink: S,
+    layout_manager: Option<SharedLayoutManager>,
+    dispatcher: Arc<Mutex<Dispatcher>>,
+    registered: Vec<Hotkey>,
+    is_active: bool,
+}
+
+impl<S: event::CommandSink + Clone + Send + 'static> HotkeySystem<S> {
+    /// Creates a new Hotkey System for a Timer with the default hotkeys.
+    pub fn new(command_sink: S) -> Result<Self> {
+        Self::with_config(command_sink, Default::default())
+    }
+
+    /// Creates a new Hotkey System for a Timer with a custom configuration for
+    /// the hotkeys.
+    pub fn with_config(command_sink: S, config: HotkeyConfig) -> Result<Self> {
+        if has_conflicts(&config) {
+            return Err(Error::AlreadyRegistered);
+        }
+        let mut hotkey_system = Self {
+            config,
+            hook: Hook::with_consume_preference(ConsumePreference::PreferNoConsume)?,
+            midi: None,
+            command_sink,
+            layout_manager: None,
+            dispatcher: Arc::new(Mutex::new(Dispatcher {
+                matcher: Matcher::new(),
+                callbacks: Vec::new(),
+                profiles: Vec::new(),
+                active_profile: 0,
+                is_focused: true,
+                capture: Capture::Idle,
+                hold_timer: HoldTimer::default(),
+                start: Instant::now(),
+            })),
+            registered: Vec::new(),
+            is_active: false,
+        };
+        hotkey_system.activate()?;
+        Ok(hotkey_system)
+    }
+
+    // Registers all the hotkeys that are part of the bindings of the actions
+    // in any of the profiles with the hook and unregisters the ones that are no
+    // longer in use. The hotkeys are forwarded to the dispatcher, which keeps
+    // track of the sequences and the active profile.
+    fn update(&mut self) -> Result<()> {
+        let profiles: Vec<Profile> = iter::once(self.config.profile_bindings())
+            .chain(self.config.profiles.iter().map(|profile| profile.hotkeys))
+            .map(|bindings| Profile::new(&bindings, self.config.cycle_profile))
+            .collect();
+
+        let mut hotkeys = Vec::<Hotkey>::new();
+        if self.is_active {
+            for hotkey in profiles
+                .iter()
+                .flat_map(|profile| &profile.bindings)
+                .flat_map(|(binding, _)| binding.steps())
+            {
+                if !hotkeys.contains(&hotkey) {
+                    hotkeys.push(hotkey);
+                }
+            }
+        }
+
+        while let Some(index) = self
+            .registered
+            .iter()
+            .position(|hotkey| !hotkeys.contains(hotkey))
+        {
+            self.hook.unregister(self.registered[index])?;
+            self.registered.swap_remove(index);
+        }
+
+        for hotkey in hotkeys {
+            if !self.registered.contains(&hotkey) {
+                let dispatcher = self.dispatcher.clone();
+                self.hook.register_with_release(hotkey, move |event| {
+                    dispatch(&dispatcher, hotkey, event);
+                })?;
+                self.registered.push(hotkey);
+            }
+        }
+
+        self.update_midi(&profiles);
+
+        let callbacks = Action::ALL
+            .into_iter()
+            .map(|action| {
+                (
+                    action,
+                    Arc::new(Mutex::new(action.callback(
+                        self.command_sink.clone(),
+                        self.layout_manager.clone(),
+                    ))),
+                )
+            })
+            .collect();
+
+        let mut dispatcher = self.dispatcher.lock().unwrap();
+        dispatcher.callbacks = callbacks;
+        dispatcher.set_profiles(profiles);
+
+        Ok(())
+    }
+
+    // Opens the MIDI device of the configuration and registers the MIDI
+    // triggers of all the profiles with it. The device is reopened every time,
+    // so the triggers work again once a device got reconnected.
+    fn update_midi(&mut self, profiles: &[Profile]) {
+        self.midi = None;
+
+        let Some(device) = self
+            .config
+            .midi_device
+            .as_deref()
+            .filter(|_| self.is_active)
+        else {
+            return;
+        };
+        let Ok(hook) = MidiHook::new(device) else {
+            return;
+        };
+
+        let mut messages = Vec::<MidiMessage>::new();
+        for &(message, _) in profiles.iter().flat_map(|profile| &profile.midi) {
+            if !messages.contains(&message) {
+                messages.push(message);
+            }
+        }
+
+        for message in messages {
+            let dispatcher = self.dispatcher.clone();
+            // The messages are unique, so this can't fail.
+            let _ = hook.register(message, move |event| {
+                if event == HotkeyEvent::Pressed {
+                    let callback = dispatcher.lock().unwrap().trigger_midi(message);
+                    invoke(callback);
+                }
+            });
+        }
+
+        self.midi = Some((device.to_owned(), hook));
+    }
+
+    fn set_hotkey(&mut self, action: Action, hotkey: Option<Binding>) -> Result<()> {
+        if action.get_hotkey(&self.config) == hotkey {
+            return Ok(());
+        }
+        let mut config = self.config.clone();
+        action.set_hotkey(&mut config, hotkey);
+        self.set_config(config)
+    }
+
+    /// Sets the key to use for splitting and starting a new attempt.
+    pub fn set_split(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::Split, hotkey)
+    }
+
+    /// Sets the key to use for resetting the current attempt.
+    pub fn set_reset(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::Reset, hotkey)
+    }
+
+    /// Sets the key to use for pausing the current attempt and starting a new
+    /// attempt.
+    pub fn set_pause(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::Pause, hotkey)
+    }
+
+    /// Sets the key to use for skipping the current split.
+    pub fn set_skip(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::Skip, hotkey)
+    }
+
+    /// Sets the key to use for undoing the last split.
+    pub fn set_undo(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::Undo, hotkey)
+    }
+
+    /// Sets the key to use for switching to the previous comparison.
+    pub fn set_previous_comparison(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::PreviousComparison, hotkey)
+    }
+
+    /// Sets the key to use for switching to the next comparison.
+    pub fn set_next_comparison(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::NextComparison, hotkey)
+    }
+
+    /// Sets the key to use for removing all the pause times from the current
+    /// time.
+    pub fn set_undo_all_pauses(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::UndoAllPauses, hotkey)
+    }
+
+    /// Sets the key to use for toggling between the `Real Time` and `Game Time`
+    /// timing methods.
+    pub fn set_toggle_timing_method(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::ToggleTimingMethod, hotkey)
+    }
+
+    /// Sets the key to use for switching to the previous layout.
+    pub fn set_previous_layout(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::PreviousLayout, hotkey)
+    }
+
+    /// Sets the key to use for switching to the next layout.
+    pub fn set_next_layout(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::NextLayout, hotkey)
+    }
+
+    /// Sets the key to use for showing the previous tab of the current layout.
+    pub fn set_previous_tab(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::PreviousTab, hotkey)
+    }
+
+    /// Sets the key to use for showing the next tab of the current layout.
+    pub fn set_next_tab(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::NextTab, hotkey)
+    }
+
+    /// Sets the key to use for switching to the next hotkey profile.
+    pub fn set_cycle_profile(&mut self, hotkey: Option<Binding>) -> Result<()> {
+        self.set_hotkey(Action::CycleProfile, hotkey)
+    }
+
+    /// Returns the index of the hotkey profile that is currently active. The
+    /// hotkeys of the configuration itself are the profile at index 0, the
+    /// profiles stored in the configuration follow after.
+    pub fn active_profile(&self) -> usize {
+        self.dispatcher.lock().unwrap().active_profile
+    }
+
+    /// Switches to the hotkey profile at the index specified. Any sequence of
+    /// hotkeys that is currently in progress is canceled.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn select_profile(&mut self, index: usize) {
+        assert!(
+            index <= self.config.profiles.len(),
+            "Index out of bounds for hotkey profiles."
+        );
+        self.dispatcher.lock().unwrap().select_profile(index);
+    }
+
+    /// Switches to the next hotkey profile. If the last profile is active, the
+    /// first profile is selected.
+    pub fn next_profile(&mut self) {
+        self.dispatcher.lock().unwrap().next_profile();
+    }
+
+    /// Attaches a Layout Manager to the Hotkey System, so that the hotkeys for
+    /// switching between layouts select the previous and next layout of the
+    /// Layout Manager and the hotkeys for switching between tabs affect its
+    /// current layout. Passing [`None`] detaches the Layout Manager, which
+    /// turns those hotkeys into no-ops.
+    pub fn set_layout_manager(
+        &mut self,
+        layout_manager: Option<SharedLayoutManager>,
+    ) -> Result<()> {
+        self.layout_manager = layout_manager;
+        self.update()
+    }
+
+    /// Accesses the Layout Manager attached to the Hotkey System, if there is
+    /// one.
+    pub const fn layout_manager(&self) -> Option<&SharedLayoutManager> {
+        self.layout_manager.as_ref()
+    }
+
+    /// Deactivates the Hotkey System. No hotkeys will go through until it gets
+    /// activated again. If it's already deactivated, nothing happens.
+    pub fn deactivate(&mut self) -> Result<()> {
+        if self.is_active {
+            self.is_active = false;
+            self.update()?;
+        }
+        Ok(())
+    }
+
+    /// Activates a previously deactivated Hotkey System. If it's already
+    /// active, nothing happens.
+    pub fn activate(&mut self) -> Result<()> {
+        if !self.is_active {
+            self.is_active = true;
+            self.update()?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the Hotkey System is active, false otherwise.
+    pub const fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Reports whether the application is currently focused. The hotkeys of
+    /// actions that are only supposed to be used while the application is
+    /// focused are ignored otherwise. By default the application is assumed
+    /// to be focused.
+    pub fn set_focused(&mut self, is_focused: bool) {
+        self.dispatcher.lock().unwrap().is_focused = is_focused;
+    }
+
+    /// Returns whether the application was last reported to be focused.
+    pub fn is_focused(&self) -> bool {
+        self.dispatcher.lock().unwrap().is_focused
+    }
+
+    /// Starts capturing the next hotkey that gets pressed, so it can be used as
+    /// a new binding. Until then, neither the hotkeys nor the MIDI messages
+    /// trigger their actions. The modifier keys on their own are not captured,
+    /// they only become part of the hotkey that gets captured. Use
+    /// [`take_captured`](Self::take_captured) to retrieve the hotkey once it
+    /// got pressed. This works even while the Hotkey System is deactivated.
+    pub fn capture_next(&mut self) -> Result<()> {
+        self.dispatcher.lock().unwrap().capture = Capture::Capturing;
+        // The dispatcher must not be locked while talking to the hook, as the
+        // hook may call into it in the meantime.
+        let dispatcher = self.dispatcher.clone();
+        let result = self.hook.capture(move |hotkey| {
+            let mut dispatcher = dispatcher.lock().unwrap();
+            if let Capture::Capturing = dispatcher.capture {
+                dispatcher.capture = Capture::Captured(hotkey);
+            }
+        });
+        if result.is_err() {
+            self.dispatcher.lock().unwrap().capture = Capture::Idle;
+        }
+        result
+    }
+
+    /// Returns whether the Hotkey System is still waiting for a hotkey to be
+    /// captured.
+    pub fn is_capturing(&self) -> bool {
+        matches!(self.dispatcher.lock().unwrap().capture, Capture::Capturing)
+    }
+
+    /// Returns the hotkey that got captured since
+    /// [`capture_next`](Self::capture_next) got called, if it got pressed
+    /// already. The hotkey is only returned once.
+    pub fn take_captured(&mut self) -> Option<CapturedHotkey> {
+        let hotkey = {
+            let mut dispatcher = self.dispatcher.lock().unwrap();
+            let Capture::Captured(hotkey) = dispatcher.capture else {
+                return None;
+            };
+            dispatcher.capture = Capture::Idle;
+            hotkey
+        };
+        let binding = Binding::from(hotkey);
+        Some(CapturedHotkey {
+            binding,
+            name: binding.resolve(&self.hook),
+        })
+    }
+
+    /// Cancels capturing the next hotkey. Any hotkey that got captured but
+    /// wasn't taken yet is discarded.
+    pub fn cancel_capture(&mut self) -> Result<()> {
+        self.dispatcher.lock().unwrap().capture = Capture::Idle;
+        self.hook.cancel_capture()
+    }
+
+    /// Returns the hotkey configuration currently in use by the Hotkey System.
+    pub fn config(&self) -> HotkeyConfig {
+        self.config.clone()
+    }
+
+    /// Returns the name of the MIDI device the Hotkey System is listening to,
+    /// if it was able to open the one specified in the configuration.
+    pub fn midi_device(&self) -> Option<&str> {
+        self.midi.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Applies a new hotkey configuration to the Hotkey System. Each hotkey is
+    /// changed to the one specified in the configuration. This operation fails
+    /// without changing anything if you provide a hotkey configuration where
+    /// the binding of an action conflicts with the binding of another action,
+    /// meaning that they are either the same or one of them is the beginning
+    /// of the other, or where the same MIDI message is used for multiple
+    /// actions. The same applies to each of the hotkey profiles. The hotkey
+    /// for cycling through the profiles must not conflict with any of them.
+    /// The active profile stays selected, unless it no longer exists.
+    pub fn set_config(&mut self, config: HotkeyConfig) -> Result<()> {
+        if has_conflicts(&config) {
+            return Err(Error::AlreadyRegistered);
+        }
+        self.config = config;
+        self.update()
+    }
+
+    /// Resolves the key according to the current keyboard layout.
+    pub fn resolve(&self, key_code: KeyCode) -> Cow<'static, str> {
+        key_code.resolve(&self.hook)
+    }
+
+    /// Resolves all the hotkeys of the binding according to the current
+    /// keyboard layout.
+    pub fn resolve_binding(&self, binding: &Binding) -> String {
+        binding.resolve(&self.hook)
+    }
+
+    /// Returns all the hotkeys that are currently registered. These are the
+    /// hotkeys that are part of the bindings of any of the hotkey profiles.
+    /// While the Hotkey System is deactivated, no hotkeys are registered.
+    pub fn bound_hotkeys(&self) -> &[Hotkey] {
+        &self.registered
+    }
+
+    /// Enumerates the bindings of all the actions in all the hotkey profiles,
+    /// with their hotkeys resolved according to the current keyboard layout.
+    /// The hotkey for cycling through the profiles is listed as part of the
+    /// first profile.
+    pub fn bindings(&self) -> Vec<BoundAction> {
+        iter::once(self.config.profile_bindings())
+            .chain(self.config.profiles.iter().map(|profile| profile.hotkeys))
+            .enumerate()
+            .flat_map(|(profile, bindings)| {
+                bindings
+                    .bindings()
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(move |(index, binding)| {
+                        Some((profile, BINDINGS_INDEX + index, binding?))
+                    })
+            })
+            .chain(
+                self.config
+                    .cycle_profile
+                    .map(|binding| (0, CYCLE_PROFILE_INDEX, binding)),
+            )
+            .map(|(profile, index, binding)| BoundAction {
+                profile,
+                index,
+                binding,
+                name: binding.resolve(&self.hook),
+            })
+            .collect()
+    }
+
+    /// Checks whether the binding could be used for the setting of the hotkey
+    /// configuration at the index specified, without actually changing it.
+    /// See [`HotkeyConfig::find_conflict`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index doesn't belong to a setting of a binding.
+    pub fn find_conflict(&self, index: usize, binding: Binding) -> Option<HotkeyConflict> {
+        self.config.find_conflict(index, binding)
+    }
+
+    /// On the web you can use this to listen to keyboard events on an
+    /// additional child window as well.
+    #[cfg(all(target_family = "wasm", feature = "wasm-web"))]
+    pub fn add_window(&self, window: web_sys::Window) -> Result<()> {
+        self.hook.add_window(window)
+    }
+}
+
+fn has_conflicts(config: &HotkeyConfig) -> bool {
+    iter::once(config.profile_bindings())
+        .chain(config.profiles.iter().map(|profile| profile.hotkeys))
+        .any(|profile| {
+            if profile.midi.has_conflicts() {
+                return true;
+            }
+
+            let bindings: Vec<Binding> = profile
+                .bindings()
+                .into_iter()
+                .flatten()
+                .chain(config.cycle_profile)
+                .collect();
+
+            bindings.iter().enumerate().any(|(index, binding)| {
+                bindings[index + 1..]
+                    .iter()
+                    .any(|other| binding.conflicts_with(other))
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hotkey::Trigger, util::tests_helper::create_timer};
+
+    #[test]
+    fn callbacks_are_invoked_without_the_dispatcher_locked() {
+        let dispatcher = Arc::new(Mutex::new(Dispatcher {
+            matcher: Matcher::new(),
+            callbacks: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: 0,
+            is_focused: true,
+            capture: Capture::Idle,
+            hold_timer: HoldTimer::default(),
+            start: Instant::now(),
+        }));
+
+        let was_unlocked = Arc::new(Mutex::new(None));
+        let callback: Callback = Box::new({
+            let dispatcher = dispatcher.clone();
+            let was_unlocked = was_unlocked.clone();
+            move || *was_unlocked.lock().unwrap() = Some(dispatcher.try_lock().is_ok())
+        });
+
+        {
+            let mut dispatcher = dispatcher.lock().unwrap();
+            dispatcher.callbacks = vec![(Action::Split, Arc::new(Mutex::new(callback)))];
+            dispatcher.set_profiles(vec![Profile {
+                bindings: vec![(KeyCode::Numpad1.into(), Action::Split)],
+                midi: Vec::new(),
+                focused_only: Vec::new(),
+            }]);
+        }
+
+        dispatch(&dispatcher, KeyCode::Numpad1.into(), HotkeyEvent::Pressed);
+        assert_eq!(*was_unlocked.lock().unwrap(), Some(true));
+    }
+
+    #[test]
+    fn holds_are_waited_for_on_a_shared_thread() {
+        let triggered = Arc::new(Mutex::new(0));
+        let callback: Callback = Box::new({
+            let triggered = triggered.clone();
+            move || *triggered.lock().unwrap() += 1
+        });
+        let dispatcher = Arc::new(Mutex::new(Dispatcher {
+            matcher: Matcher::new(),
+            callbacks: vec![(Action::Split, Arc::new(Mutex::new(callback)))],
+            profiles: Vec::new(),
+            active_profile: 0,
+            is_focused: true,
+            capture: Capture::Idle,
+            hold_timer: HoldTimer::default(),
+            start: Instant::now(),
+        }));
+        dispatcher.lock().unwrap().set_profiles(vec![Profile {
+            bindings: vec![(
+                Binding::from(KeyCode::Numpad3).with_trigger(Trigger::Hold { millis: 50 }),
+                Action::Split,
+            )],
+            midi: Vec::new(),
+            focused_only: Vec::new(),
+        }]);
+
+        // Releasing the hotkey early doesn't trigger the action.
+        dispatch(&dispatcher, KeyCode::Numpad3.into(), HotkeyEvent::Pressed);
+        dispatch(&dispatcher, KeyCode::Numpad3.into(), HotkeyEvent::Released);
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert_eq!(*triggered.lock().unwrap(), 0);
+
+        dispatch(&dispatcher, KeyCode::Numpad3.into(), HotkeyEvent::Pressed);
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert_eq!(*triggered.lock().unwrap(), 1);
+
+        // The thread doesn't keep the dispatcher alive.
+        assert_eq!(Arc::strong_count(&dispatcher), 1);
+    }
+
+    #[test]
+    fn nothing_is_triggered_while_capturing() {
+        let mut dispatcher = Dispatcher {
+            matcher: Matcher::new(),
+            callbacks: vec![(Action::Split, Arc::new(Mutex::new(Box::new(|| {}))))],
+            profiles: Vec::new(),
+            active_profile: 0,
+            is_focused: true,
+            capture: Capture::Capturing,
+            hold_timer: HoldTimer::default(),
+            start: Instant::now(),
+        };
+        let message = MidiMessage::Note {
+            channel: 0,
+            note: 60,
+        };
+        dispatcher.set_profiles(vec![Profile {
+            bindings: Vec::new(),
+            midi: vec![(message, Action::Split)],
+            focused_only: Vec::new(),
+        }]);
+
+        assert!(dispatcher.trigger_midi(message).is_none());
+        assert!(dispatcher.trigger(Action::Split).is_none());
+
+        dispatcher.capture = Capture::Idle;
+        assert!(dispatcher.trigger_midi(message).is_some());
+    }
+
+    #[test]
+    fn layout_actions_ignore_a_poisoned_layout_manager() {
+        let timer = create_timer(&["A"]).into_shared();
+        let layout_manager = LayoutManager::default().into_shared();
+        let _ = std::thread::spawn({
+            let layout_manager = layout_manager.clone();
+            move || {
+                let _guard = layout_manager.write().unwrap();
+                panic!("Poisoning the lock");
+            }
+        })
+        .join();
+        assert!(layout_manager.is_poisoned());
+
+        for action in [
+            Action::PreviousLayout,
+            Action::NextLayout,
+            Action::PreviousTab,
+            Action::NextTab,
+        ] {
+            action.callback(timer.clone(), Some(layout_manager.clone()))();
+        }
+    }
+}
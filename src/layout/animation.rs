@@ -0,0 +1,328 @@
+use super::{ComponentState, LayoutState};
+use crate::{
+    component::{
+        key_value,
+        splits::{self, SplitState},
+        timer,
+    },
+    platform::prelude::*,
+    settings::Color,
+};
+
+/// An `Animator` smoothly transitions between consecutive [`LayoutState`]s.
+/// Every state that gets passed to it is modified in place to represent how
+/// far along the transitions towards it are. Segments that appear in or
+/// disappear from the splits fade in and out, the splits smoothly scroll to
+/// their new positions and colors, such as those of deltas, blend into their
+/// new values. The transitions are driven by the state's
+/// [`animation_time`](LayoutState::animation_time), so the states need to be
+/// updated continuously while a transition is happening.
+#[derive(Clone)]
+pub struct Animator {
+    duration: f64,
+    components: Vec<ComponentAnimation>,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Self::new(0.3)
+    }
+}
+
+impl Animator {
+    /// Creates a new animator where each transition takes the duration
+    /// provided in seconds.
+    pub const fn new(duration: f64) -> Self {
+        Self {
+            duration,
+            components: Vec::new(),
+        }
+    }
+
+    /// Returns the duration of each transition in seconds.
+    pub const fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Sets the duration of each transition in seconds.
+    pub const fn set_duration(&mut self, duration: f64) {
+        self.duration = duration;
+    }
+
+    /// Modifies the state provided to represent how far along the transitions
+    /// from the previous states towards it are. The first state the animator
+    /// sees is not modified.
+    pub fn animate(&mut self, state: &mut LayoutState) {
+        let (time, duration, text_color) = (state.animation_time, self.duration, state.text_color);
+
+        self.components
+            .resize_with(state.components.len(), || ComponentAnimation::None);
+
+        for (component, animation) in state.components.iter_mut().zip(&mut self.components) {
+            match (component, animation) {
+                (ComponentState::KeyValue(state), ComponentAnimation::KeyValue(value_color)) => {
+                    animate_key_value(state, value_color, text_color, time, duration);
+                }
+                (ComponentState::Splits(state), ComponentAnimation::Splits(splits)) => {
+                    splits.animate(state, time, duration);
+                }
+                (ComponentState::Timer(state), ComponentAnimation::Timer(colors)) => {
+                    animate_timer(state, colors, time, duration);
+                }
+                (component, animation) => {
+                    // The component at this index changed, so there is nothing
+                    // to transition from.
+                    *animation = match component {
+                        ComponentState::KeyValue(state) => ComponentAnimation::KeyValue(
+                            Tween::settled(state.value_color.unwrap_or(text_color)),
+                        ),
+                        ComponentState::Splits(state) => {
+                            ComponentAnimation::Splits(SplitsAnimation::new(state))
+                        }
+                        ComponentState::Timer(state) => ComponentAnimation::Timer([
+                            Tween::settled(state.top_color),
+                            Tween::settled(state.bottom_color),
+                        ]),
+                        _ => ComponentAnimation::None,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum ComponentAnimation {
+    None,
+    KeyValue(Tween<Color>),
+    Splits(SplitsAnimation),
+    Timer([Tween<Color>; 2]),
+}
+
+fn animate_key_value(
+    state: &mut key_value::State,
+    value_color: &mut Tween<Color>,
+    text_color: Color,
+    time: f64,
+    duration: f64,
+) {
+    let target = state.value_color.unwrap_or(text_color);
+    let color = value_color.set(target, time, duration);
+    if color != target {
+        state.value_color = Some(color);
+    }
+}
+
+fn animate_timer(
+    state: &mut timer::State,
+    [top_color, bottom_color]: &mut [Tween<Color>; 2],
+    time: f64,
+    duration: f64,
+) {
+    state.top_color = top_color.set(state.top_color, time, duration);
+    state.bottom_color = bottom_color.set(state.bottom_color, time, duration);
+}
+
+#[derive(Clone)]
+struct SplitsAnimation {
+    rows: Vec<Row>,
+}
+
+#[derive(Clone)]
+struct Row {
+    state: SplitState,
+    position: Tween<f32>,
+    opacity: Tween<f32>,
+    column_colors: Vec<Tween<Color>>,
+    leaving: bool,
+}
+
+impl SplitsAnimation {
+    fn new(state: &splits::State) -> Self {
+        Self {
+            rows: state
+                .splits
+                .iter()
+                .enumerate()
+                .map(|(position, split)| Row {
+                    state: split.clone(),
+                    position: Tween::settled(position as f32),
+                    opacity: Tween::settled(1.0),
+                    column_colors: split
+                        .columns
+                        .iter()
+                        .map(|column| Tween::settled(column.visual_color))
+                        .collect(),
+                    leaving: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn animate(&mut self, state: &mut splits::State, time: f64, duration: f64) {
+        // When the list scrolled, the segments that are shown both before and
+        // after moved by the same amount. Segments that newly appear are
+        // assumed to come from where they would have been before the list
+        // scrolled, while the ones that disappear keep moving along.
+        let shift = state
+            .splits
+            .iter()
+            .enumerate()
+            .find_map(|(position, split)| {
+                let row = self
+                    .rows
+                    .iter()
+                    .find(|row| !row.leaving && row.state.index == split.index)?;
+                Some(row.position.to - position as f32)
+            })
+            .unwrap_or_default();
+
+        for row in &mut self.rows {
+            if !state
+                .splits
+                .iter()
+                .any(|split| split.index == row.state.index)
+            {
+                if !row.leaving || shift != 0.0 {
+                    row.position.set(row.position.to - shift, time, duration);
+                }
+                row.opacity.set(0.0, time, duration);
+                row.leaving = true;
+            }
+        }
+
+        for (position, split) in state.splits.iter().enumerate() {
+            let position = position as f32;
+            let row = match self
+                .rows
+                .iter_mut()
+                .find(|row| row.state.index == split.index)
+            {
+                Some(row) => {
+                    row.position.set(position, time, duration);
+                    row.opacity.set(1.0, time, duration);
+                    row.state.clone_from(split);
+                    row.leaving = false;
+                    row
+                }
+                None => {
+                    self.rows.push(Row {
+                        state: split.clone(),
+                        position: Tween {
+                            from: position + shift,
+                            to: position,
+                            start: time,
+                        },
+                        opacity: Tween {
+                            from: 0.0,
+                            to: 1.0,
+                            start: time,
+                        },
+                        column_colors: Vec::new(),
+                        leaving: false,
+                    });
+                    self.rows.last_mut().unwrap()
+                }
+            };
+
+            row.column_colors.truncate(split.columns.len());
+            for (index, column) in split.columns.iter().enumerate() {
+                match row.column_colors.get_mut(index) {
+                    Some(color) => {
+                        color.set(column.visual_color, time, duration);
+                    }
+                    None => row.column_colors.push(Tween::settled(column.visual_color)),
+                }
+            }
+        }
+
+        self.rows
+            .retain(|row| !row.leaving || row.opacity.value(time, duration) > 0.0);
+        self.rows
+            .sort_by(|a, b| a.position.to.total_cmp(&b.position.to));
+
+        state.splits = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let mut split = row.state.clone();
+                split.opacity = row.opacity.value(time, duration);
+                split.offset = row.position.value(time, duration) - index as f32;
+                for (column, color) in split.columns.iter_mut().zip(&row.column_colors) {
+                    column.visual_color = color.value(time, duration);
+                }
+                split
+            })
+            .collect();
+    }
+}
+
+/// A value that transitions from one value to another, starting at a specific
+/// point in time.
+#[derive(Copy, Clone)]
+struct Tween<T> {
+    from: T,
+    to: T,
+    start: f64,
+}
+
+impl<T: Lerp> Tween<T> {
+    const fn settled(value: T) -> Self {
+        Self {
+            from: value,
+            to: value,
+            start: f64::NEG_INFINITY,
+        }
+    }
+
+    fn value(&self, time: f64, duration: f64) -> T {
+        let progress = if duration > 0.0 {
+            (time - self.start) / duration
+        } else {
+            1.0
+        };
+        if progress >= 1.0 {
+            return self.to;
+        }
+        let progress = progress.max(0.0);
+        // Cubic ease out, so the transitions start quickly and then slowly
+        // settle on the new value.
+        let remaining = 1.0 - progress;
+        let progress = 1.0 - remaining * remaining * remaining;
+        T::lerp(self.from, self.to, progress as f32)
+    }
+
+    /// Starts transitioning towards the target from wherever the current
+    /// transition is at, unless it already is the target. Returns the current
+    /// value.
+    fn set(&mut self, target: T, time: f64, duration: f64) -> T {
+        if target != self.to {
+            self.from = self.value(time, duration);
+            self.to = target;
+            self.start = time;
+        }
+        self.value(time, duration)
+    }
+}
+
+trait Lerp: Copy + PartialEq {
+    fn lerp(from: Self, to: Self, progress: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(from: Self, to: Self, progress: f32) -> Self {
+        from + (to - from) * progress
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(from: Self, to: Self, progress: f32) -> Self {
+        Color::rgba(
+            f32::lerp(from.red, to.red, progress),
+            f32::lerp(from.green, to.green, progress),
+            f32::lerp(from.blue, to.blue, progress),
+            f32::lerp(from.alpha, to.alpha, progress),
+        )
+    }
+}
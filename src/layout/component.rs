@@ -1,12 +1,13 @@
 use super::{ComponentSettings, ComponentState, GeneralSettings};
 use crate::{
     component::{
-        blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-        possible_time_save, previous_segment, reset_chance, segment_time, separator, splits,
-        sum_of_best, text, timer, title, total_playtime,
+        blank_space, current_comparison, current_pace, delta, detailed_timer, graph,
+        overlay_widgets, pb_chance, placeholder, possible_time_save, previous_segment,
+        reset_chance, segment_time, separator, splits, sum_of_best, tabs, text, timer, title,
+        total_playtime,
     },
     platform::prelude::*,
-    settings::{ImageCache, SettingsDescription, Value},
+    settings::{ImageCache, SettingsDescription, ValidationError, Value},
     timing::Snapshot,
 };
 use alloc::borrow::Cow;
@@ -27,8 +28,12 @@ pub enum Component {
     DetailedTimer(Box<detailed_timer::Component>),
     /// The Graph Component.
     Graph(graph::Component),
+    /// The Overlay Widgets Component.
+    OverlayWidgets(overlay_widgets::Component),
     /// The PB Chance Component.
     PbChance(pb_chance::Component),
+    /// The Placeholder Component.
+    Placeholder(placeholder::Component),
     /// The Possible Time Save Component.
     PossibleTimeSave(possible_time_save::Component),
     /// The Previous Segment Component.
@@ -43,6 +48,8 @@ pub enum Component {
     Splits(splits::Component),
     /// The Sum of Best Component.
     SumOfBest(sum_of_best::Component),
+    /// The Tabs Component.
+    Tabs(tabs::Component),
     /// The Text Component.
     Text(text::Component),
     /// The Timer Component.
@@ -89,12 +96,24 @@ impl From<graph::Component> for Component {
     }
 }
 
+impl From<overlay_widgets::Component> for Component {
+    fn from(component: overlay_widgets::Component) -> Self {
+        Self::OverlayWidgets(component)
+    }
+}
+
 impl From<pb_chance::Component> for Component {
     fn from(component: pb_chance::Component) -> Self {
         Self::PbChance(component)
     }
 }
 
+impl From<placeholder::Component> for Component {
+    fn from(component: placeholder::Component) -> Self {
+        Self::Placeholder(component)
+    }
+}
+
 impl From<possible_time_save::Component> for Component {
     fn from(component: possible_time_save::Component) -> Self {
         Self::PossibleTimeSave(component)
@@ -137,6 +156,12 @@ impl From<sum_of_best::Component> for Component {
     }
 }
 
+impl From<tabs::Component> for Component {
+    fn from(component: tabs::Component) -> Self {
+        Self::Tabs(component)
+    }
+}
+
 impl From<text::Component> for Component {
     fn from(component: text::Component) -> Self {
         Self::Text(component)
@@ -195,9 +220,15 @@ impl Component {
             (ComponentState::Graph(state), Component::Graph(component)) => {
                 component.update_state(state, timer, layout_settings)
             }
+            (ComponentState::OverlayWidgets(state), Component::OverlayWidgets(component)) => {
+                component.update_state(state, timer)
+            }
             (ComponentState::KeyValue(state), Component::PbChance(component)) => {
                 component.update_state(state, timer)
             }
+            (ComponentState::BlankSpace(state), Component::Placeholder(component)) => {
+                component.update_state(state)
+            }
             (ComponentState::KeyValue(state), Component::PossibleTimeSave(component)) => {
                 component.update_state(state, timer)
             }
@@ -216,6 +247,9 @@ impl Component {
             (ComponentState::KeyValue(state), Component::SumOfBest(component)) => {
                 component.update_state(state, timer)
             }
+            (ComponentState::Tabs(state), Component::Tabs(component)) => {
+                component.update_state(state, image_cache, timer, layout_settings)
+            }
             (ComponentState::Text(state), Component::Text(component)) => {
                 component.update_state(state, timer)
             }
@@ -261,7 +295,11 @@ impl Component {
             Component::Graph(component) => {
                 ComponentState::Graph(component.state(timer, layout_settings))
             }
+            Component::OverlayWidgets(component) => {
+                ComponentState::OverlayWidgets(component.state(timer))
+            }
             Component::PbChance(component) => ComponentState::KeyValue(component.state(timer)),
+            Component::Placeholder(component) => ComponentState::BlankSpace(component.state()),
             Component::PossibleTimeSave(component) => {
                 ComponentState::KeyValue(component.state(timer))
             }
@@ -275,6 +313,9 @@ impl Component {
                 ComponentState::Splits(component.state(image_cache, timer, layout_settings))
             }
             Component::SumOfBest(component) => ComponentState::KeyValue(component.state(timer)),
+            Component::Tabs(component) => {
+                ComponentState::Tabs(component.state(image_cache, timer, layout_settings))
+            }
             Component::Text(component) => ComponentState::Text(component.state(timer)),
             Component::Timer(component) => {
                 ComponentState::Timer(component.state(timer, layout_settings))
@@ -306,9 +347,15 @@ impl Component {
                 ComponentSettings::DetailedTimer(Box::new(component.settings().clone()))
             }
             Component::Graph(component) => ComponentSettings::Graph(component.settings().clone()),
+            Component::OverlayWidgets(component) => {
+                ComponentSettings::OverlayWidgets(component.settings().clone())
+            }
             Component::PbChance(component) => {
                 ComponentSettings::PbChance(component.settings().clone())
             }
+            Component::Placeholder(component) => {
+                ComponentSettings::Placeholder(component.settings().clone())
+            }
             Component::PossibleTimeSave(component) => {
                 ComponentSettings::PossibleTimeSave(component.settings().clone())
             }
@@ -326,6 +373,7 @@ impl Component {
             Component::SumOfBest(component) => {
                 ComponentSettings::SumOfBest(component.settings().clone())
             }
+            Component::Tabs(component) => ComponentSettings::Tabs(component.settings()),
             Component::Text(component) => ComponentSettings::Text(component.settings().clone()),
             Component::Timer(component) => ComponentSettings::Timer(component.settings().clone()),
             Component::Title(component) => ComponentSettings::Title(component.settings().clone()),
@@ -344,7 +392,9 @@ impl Component {
             Component::Delta(component) => component.name(),
             Component::DetailedTimer(component) => component.name().into(),
             Component::Graph(component) => component.name(),
+            Component::OverlayWidgets(component) => component.name().into(),
             Component::PbChance(component) => component.name().into(),
+            Component::Placeholder(component) => component.name(),
             Component::PossibleTimeSave(component) => component.name(),
             Component::PreviousSegment(component) => component.name(),
             Component::ResetChance(component) => component.name().into(),
@@ -352,6 +402,7 @@ impl Component {
             Component::Separator(component) => component.name().into(),
             Component::Splits(component) => component.name().into(),
             Component::SumOfBest(component) => component.name().into(),
+            Component::Tabs(component) => component.name().into(),
             Component::Text(component) => component.name(),
             Component::Timer(component) => component.name().into(),
             Component::Title(component) => component.name().into(),
@@ -361,17 +412,37 @@ impl Component {
 
     /// Tells the component to scroll up. This may be interpreted differently
     /// based on the kind of component. Most components will ignore this.
-    pub const fn scroll_up(&mut self) {
-        if let Component::Splits(component) = self {
-            component.scroll_up();
+    pub fn scroll_up(&mut self) {
+        match self {
+            Component::Splits(component) => component.scroll_up(),
+            Component::Tabs(component) => component.scroll_up(),
+            _ => {}
         }
     }
 
     /// Tells the component to scroll down. This may be interpreted differently
     /// based on the kind of component. Most components will ignore this.
-    pub const fn scroll_down(&mut self) {
-        if let Component::Splits(component) = self {
-            component.scroll_down();
+    pub fn scroll_down(&mut self) {
+        match self {
+            Component::Splits(component) => component.scroll_down(),
+            Component::Tabs(component) => component.scroll_down(),
+            _ => {}
+        }
+    }
+
+    /// Tells the component to show its next tab. Only the Tabs Component
+    /// reacts to this.
+    pub const fn next_tab(&mut self) {
+        if let Component::Tabs(component) = self {
+            component.next_tab();
+        }
+    }
+
+    /// Tells the component to show its previous tab. Only the Tabs Component
+    /// reacts to this.
+    pub const fn previous_tab(&mut self) {
+        if let Component::Tabs(component) = self {
+            component.previous_tab();
         }
     }
 
@@ -380,6 +451,56 @@ impl Component {
     /// type they are and what value they currently have. This provides a user
     /// interface independent way of changing the settings.
     pub fn settings_description(&self) -> SettingsDescription {
+        self.settings_description_without_defaults().with_defaults(
+            self.with_default_settings()
+                .settings_description_without_defaults(),
+        )
+    }
+
+    /// Changes a setting of the component based on its Settings Description
+    /// index to its default value. Settings that don't have a default value,
+    /// such as lists, stay unchanged.
+    pub fn set_value_to_default(&mut self, index: usize) {
+        if let Some(value) = self
+            .settings_description()
+            .fields
+            .into_iter()
+            .nth(index)
+            .and_then(|field| field.default_value)
+        {
+            self.set_value_unchecked(index, value);
+        }
+    }
+
+    // Creates the same kind of component with its settings in their default
+    // state.
+    fn with_default_settings(&self) -> Component {
+        match self {
+            Component::BlankSpace(_) => blank_space::Component::default().into(),
+            Component::CurrentComparison(_) => current_comparison::Component::default().into(),
+            Component::CurrentPace(_) => current_pace::Component::default().into(),
+            Component::Delta(_) => delta::Component::default().into(),
+            Component::DetailedTimer(_) => Box::<detailed_timer::Component>::default().into(),
+            Component::Graph(_) => graph::Component::default().into(),
+            Component::OverlayWidgets(_) => overlay_widgets::Component::default().into(),
+            Component::PbChance(_) => pb_chance::Component::default().into(),
+            Component::Placeholder(_) => placeholder::Component::default().into(),
+            Component::PossibleTimeSave(_) => possible_time_save::Component::default().into(),
+            Component::PreviousSegment(_) => previous_segment::Component::default().into(),
+            Component::ResetChance(_) => reset_chance::Component::default().into(),
+            Component::SegmentTime(_) => segment_time::Component::default().into(),
+            Component::Separator(_) => separator::Component.into(),
+            Component::Splits(_) => splits::Component::default().into(),
+            Component::SumOfBest(_) => sum_of_best::Component::default().into(),
+            Component::Tabs(_) => tabs::Component::default().into(),
+            Component::Text(_) => text::Component::default().into(),
+            Component::Timer(_) => timer::Component::default().into(),
+            Component::Title(_) => title::Component::default().into(),
+            Component::TotalPlaytime(_) => total_playtime::Component::default().into(),
+        }
+    }
+
+    fn settings_description_without_defaults(&self) -> SettingsDescription {
         match self {
             Component::BlankSpace(component) => component.settings_description(),
             Component::CurrentComparison(component) => component.settings_description(),
@@ -387,7 +508,9 @@ impl Component {
             Component::Delta(component) => component.settings_description(),
             Component::DetailedTimer(component) => component.settings_description(),
             Component::Graph(component) => component.settings_description(),
+            Component::OverlayWidgets(component) => component.settings_description(),
             Component::PbChance(component) => component.settings_description(),
+            Component::Placeholder(component) => component.settings_description(),
             Component::PossibleTimeSave(component) => component.settings_description(),
             Component::PreviousSegment(component) => component.settings_description(),
             Component::ResetChance(component) => component.settings_description(),
@@ -395,6 +518,7 @@ impl Component {
             Component::Separator(component) => component.settings_description(),
             Component::Splits(component) => component.settings_description(),
             Component::SumOfBest(component) => component.settings_description(),
+            Component::Tabs(component) => component.settings_description(),
             Component::Text(component) => component.settings_description(),
             Component::Timer(component) => component.settings_description(),
             Component::Title(component) => component.settings_description(),
@@ -402,15 +526,39 @@ impl Component {
         }
     }
 
+    /// Checks whether the value provided can be set for the setting with the
+    /// Settings Description index provided.
+    ///
+    /// # Errors
+    ///
+    /// The value is rejected if the index doesn't match any setting provided by
+    /// the Settings Description of this component, if the type of the value is
+    /// not compatible with the type of the setting's value or if the value
+    /// doesn't satisfy the setting's constraints.
+    pub fn validate_value(&self, index: usize, value: &Value) -> Result<(), ValidationError> {
+        self.settings_description_without_defaults()
+            .validate(index, value)
+    }
+
     /// Changes a setting of the component based on its Settings Description
     /// index.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This may panic if the index doesn't match any setting provided by the
-    /// Settings Description of this component. Additionally, the value needs to
-    /// have a compatible type.
-    pub fn set_value(&mut self, index: usize, value: Value) {
+    /// The value is rejected and the component stays unchanged if the index
+    /// doesn't match any setting provided by the Settings Description of this
+    /// component, if the type of the value is not compatible with the type of
+    /// the setting's value or if the value doesn't satisfy the setting's
+    /// constraints.
+    pub fn set_value(&mut self, index: usize, value: Value) -> Result<(), ValidationError> {
+        self.validate_value(index, &value)?;
+        self.set_value_unchecked(index, value);
+        Ok(())
+    }
+
+    // Changes a setting without checking the value first. The value needs to be
+    // validated already.
+    pub(super) fn set_value_unchecked(&mut self, index: usize, value: Value) {
         match self {
             Component::BlankSpace(component) => component.set_value(index, value),
             Component::CurrentComparison(component) => component.set_value(index, value),
@@ -418,7 +566,9 @@ impl Component {
             Component::Delta(component) => component.set_value(index, value),
             Component::DetailedTimer(component) => component.set_value(index, value),
             Component::Graph(component) => component.set_value(index, value),
+            Component::OverlayWidgets(component) => component.set_value(index, value),
             Component::PbChance(component) => component.set_value(index, value),
+            Component::Placeholder(component) => component.set_value(index, value),
             Component::ResetChance(component) => component.set_value(index, value),
             Component::PossibleTimeSave(component) => component.set_value(index, value),
             Component::PreviousSegment(component) => component.set_value(index, value),
@@ -426,6 +576,7 @@ impl Component {
             Component::Separator(component) => component.set_value(index, value),
             Component::Splits(component) => component.set_value(index, value),
             Component::SumOfBest(component) => component.set_value(index, value),
+            Component::Tabs(component) => component.set_value(index, value),
             Component::Text(component) => component.set_value(index, value),
             Component::Timer(component) => component.set_value(index, value),
             Component::Title(component) => component.set_value(index, value),
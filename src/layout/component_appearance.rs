@@ -0,0 +1,120 @@
+use crate::{
+    platform::prelude::*,
+    settings::{
+        BlendMode, ComponentBackgroundImage, Constraints, Field, Image, ImageCache, ImageId,
+        SettingsDescription, Value,
+    },
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// Describes how a component of a [`Layout`](super::Layout) is composited onto
+/// the layout's background and the other components. This allows components
+/// to be subtly layered over a background image, for example.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComponentAppearance<I = Image> {
+    /// The opacity of the whole component, between 0 (fully transparent) and 1
+    /// (fully opaque).
+    pub opacity: f32,
+    /// How the colors of the component are combined with the colors below it.
+    pub blend_mode: BlendMode,
+    /// An image that is shown behind the component. The component's own
+    /// background is drawn on top of it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<ComponentBackgroundImage<I>>,
+}
+
+impl<I> Default for ComponentAppearance<I> {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            background_image: None,
+        }
+    }
+}
+
+impl<I> ComponentAppearance<I> {
+    /// Returns `true` if the component is drawn as is, i.e. fully opaque, with
+    /// the normal blend mode and without a background image.
+    pub fn is_default(&self) -> bool {
+        self.is_composited_normally() && self.background_image.is_none()
+    }
+
+    /// Returns `true` if the component is fully opaque and uses the normal
+    /// blend mode, so it doesn't need to be composited separately.
+    pub fn is_composited_normally(&self) -> bool {
+        self.opacity >= 1.0 && self.blend_mode == BlendMode::Normal
+    }
+}
+
+impl ComponentAppearance<Image> {
+    /// Caches the background image in the image cache provided and returns a
+    /// [`ComponentAppearance`] which refers to the cached image by its
+    /// [`ImageId`]. The image gets marked as visited regardless of whether it
+    /// was already in the cache or not.
+    pub fn cache(&self, image_cache: &mut ImageCache) -> ComponentAppearance<ImageId> {
+        ComponentAppearance {
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            background_image: self
+                .background_image
+                .as_ref()
+                .map(|image| image.cache(image_cache)),
+        }
+    }
+
+    /// Accesses a generic description of the settings available for the
+    /// appearance of a component and their current values. The
+    /// [`ImageCache`] is updated with the background image. The image is
+    /// marked as visited in the [`ImageCache`]. You still need to manually run
+    /// [`ImageCache::collect`] to ensure unused images are removed from the
+    /// cache.
+    pub fn settings_description(&self, image_cache: &mut ImageCache) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new(
+                "opacity",
+                "Opacity".into(),
+                "The opacity of the whole component in percent. Lower values let the background show through the component.".into(),
+                Value::UInt((self.opacity.clamp(0.0, 1.0) * 100.0 + 0.5) as u64),
+            )
+            .with_constraints(Constraints::range(0, 100)),
+            Field::new(
+                "blend_mode",
+                "Blend Mode".into(),
+                "Specifies how the colors of the component are combined with the colors below it.".into(),
+                self.blend_mode.into(),
+            ),
+            Field::new(
+                "background_image",
+                "Background Image".into(),
+                "An optional image to show behind the component, such as art behind the title. The component's own background is drawn on top of it, so it needs to be transparent for the image to be fully visible.".into(),
+                self.background_image
+                    .as_ref()
+                    .map(|image| image.cache(image_cache))
+                    .into(),
+            ),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value, image_cache: &ImageCache) {
+        match index {
+            0 => self.opacity = value.into_uint().unwrap().min(100) as f32 / 100.0,
+            1 => self.blend_mode = value.into(),
+            2 => {
+                self.background_image = value
+                    .into_component_background_image()
+                    .unwrap()
+                    .map(|image| image.from_cache(image_cache));
+            }
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+}
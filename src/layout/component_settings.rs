@@ -1,9 +1,10 @@
 use super::Component;
 use crate::{
     component::{
-        blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-        possible_time_save, previous_segment, reset_chance, segment_time, separator, splits,
-        sum_of_best, text, timer, title, total_playtime,
+        blank_space, current_comparison, current_pace, delta, detailed_timer, graph,
+        overlay_widgets, pb_chance, placeholder, possible_time_save, previous_segment,
+        reset_chance, segment_time, separator, splits, sum_of_best, tabs, text, timer, title,
+        total_playtime,
     },
     platform::prelude::*,
 };
@@ -24,8 +25,12 @@ pub enum ComponentSettings {
     DetailedTimer(Box<detailed_timer::Settings>),
     /// The Settings for the Graph Component.
     Graph(graph::Settings),
+    /// The Settings for the Overlay Widgets Component.
+    OverlayWidgets(overlay_widgets::Settings),
     /// The Settings for the PB Chance Component.
     PbChance(pb_chance::Settings),
+    /// The Settings for the Placeholder Component.
+    Placeholder(placeholder::Settings),
     /// The Settings for the Possible Time Save Component.
     PossibleTimeSave(possible_time_save::Settings),
     /// The Settings for the Previous Segment Component.
@@ -40,6 +45,8 @@ pub enum ComponentSettings {
     Splits(splits::Settings),
     /// The Settings for the Sum Of Best Component.
     SumOfBest(sum_of_best::Settings),
+    /// The Settings for the Tabs Component.
+    Tabs(tabs::Settings),
     /// The Settings for the Text Component.
     Text(text::Settings),
     /// The Settings for the Timer Component.
@@ -71,9 +78,15 @@ impl From<ComponentSettings> for Component {
             ComponentSettings::Graph(settings) => {
                 Component::Graph(graph::Component::with_settings(settings))
             }
+            ComponentSettings::OverlayWidgets(settings) => {
+                Component::OverlayWidgets(overlay_widgets::Component::with_settings(settings))
+            }
             ComponentSettings::PbChance(settings) => {
                 Component::PbChance(pb_chance::Component::with_settings(settings))
             }
+            ComponentSettings::Placeholder(settings) => {
+                Component::Placeholder(placeholder::Component::with_settings(settings))
+            }
             ComponentSettings::PossibleTimeSave(settings) => {
                 Component::PossibleTimeSave(possible_time_save::Component::with_settings(settings))
             }
@@ -93,6 +106,9 @@ impl From<ComponentSettings> for Component {
             ComponentSettings::SumOfBest(settings) => {
                 Component::SumOfBest(sum_of_best::Component::with_settings(settings))
             }
+            ComponentSettings::Tabs(settings) => {
+                Component::Tabs(tabs::Component::with_settings(settings))
+            }
             ComponentSettings::Text(settings) => {
                 Component::Text(text::Component::with_settings(settings))
             }
@@ -2,7 +2,8 @@ use serde_derive::{Deserialize, Serialize};
 
 use crate::{
     component::{
-        blank_space, detailed_timer, graph, key_value, separator, splits, text, timer, title,
+        blank_space, detailed_timer, graph, key_value, overlay_widgets, separator, splits, tabs,
+        text, timer, title,
     },
     platform::prelude::*,
 };
@@ -18,10 +19,14 @@ pub enum ComponentState {
     Graph(graph::State),
     /// The state object for a key value based component.
     KeyValue(key_value::State),
+    /// The state object for the Overlay Widgets Component.
+    OverlayWidgets(overlay_widgets::State),
     /// The state object for the Separator Component.
     Separator(separator::State),
     /// The state object for the Splits Component.
     Splits(splits::State),
+    /// The state object for the Tabs Component.
+    Tabs(tabs::State),
     /// The state object for the Text Component.
     Text(text::State),
     /// The state object for the Timer Component.
@@ -4,12 +4,13 @@
 //! editor as state objects that can be visualized by any kind of User
 //! Interface.
 
-use super::{Component, Layout, LayoutState};
+use super::{Component, Layout, LayoutState, SettingsChanges, Theme};
 use crate::{
-    settings::{ImageCache, Value},
+    platform::prelude::*,
+    settings::{Color, ImageCache, ValidationError, Value},
     timing::Snapshot,
 };
-use core::result::Result as StdResult;
+use core::{mem, result::Result as StdResult};
 
 mod state;
 
@@ -22,8 +23,34 @@ pub use self::state::{Buttons as ButtonsState, State};
 pub struct Editor {
     layout: Layout,
     selected_component: usize,
+    // All the selected components in ascending order. This always includes
+    // the selected component whose settings are shown.
+    selected_components: Vec<usize>,
+    undo_stack: Vec<Checkpoint>,
+    redo_stack: Vec<Checkpoint>,
+    last_changed_setting: Option<ChangedSetting>,
 }
 
+// The state of the layout and the selection before a change was applied, so
+// that the change can be undone.
+struct Checkpoint {
+    layout: Layout,
+    selected_component: usize,
+    selected_components: Vec<usize>,
+}
+
+// Identifies a setting by where it is located. Consecutive changes to the same
+// setting, such as typing into a text box, are undone all at once.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ChangedSetting {
+    Component(usize, usize),
+    Appearance(usize),
+    General(usize),
+}
+
+// The maximum number of changes that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+
 /// Describes an Error that occurred while opening the Layout Editor.
 #[derive(Debug, snafu::Snafu)]
 pub enum Error {
@@ -39,13 +66,17 @@ impl Editor {
     /// Creates a new Layout Editor that modifies the Layout provided. Creation
     /// of the Layout Editor fails when a Layout with no components is provided.
     pub fn new(layout: Layout) -> Result<Self> {
-        if layout.components.is_empty() {
+        if layout.components().is_empty() {
             return Err(Error::EmptyLayout);
         }
 
         Ok(Self {
             layout,
             selected_component: 0,
+            selected_components: vec![0],
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_changed_setting: None,
         })
     }
 
@@ -84,127 +115,400 @@ impl Editor {
     }
 
     /// Selects the component with the given index in order to modify its
-    /// settings. Only a single component is selected at any given time. You may
-    /// not provide an invalid index.
-    pub const fn select(&mut self, index: usize) {
-        if index < self.layout.components.len() {
+    /// settings. All other components get deselected. You may not provide an
+    /// invalid index.
+    pub fn select(&mut self, index: usize) {
+        if index < self.layout.components().len() {
             self.selected_component = index;
+            self.selected_components.clear();
+            self.selected_components.push(index);
+            self.last_changed_setting = None;
         }
     }
 
+    /// Adds the component with the given index to the selection or removes it
+    /// from the selection if it is already selected. A component that gets
+    /// added becomes the component whose settings are shown. The last selected
+    /// component can't be deselected. You may not provide an invalid index.
+    pub fn toggle_selection(&mut self, index: usize) {
+        if index >= self.layout.components().len() {
+            return;
+        }
+        match self.selected_components.binary_search(&index) {
+            Ok(position) => {
+                if self.selected_components.len() > 1 {
+                    self.selected_components.remove(position);
+                    if self.selected_component == index {
+                        self.selected_component = self.selected_components
+                            [position.min(self.selected_components.len() - 1)];
+                    }
+                }
+            }
+            Err(position) => {
+                self.selected_components.insert(position, index);
+                self.selected_component = index;
+            }
+        }
+        self.last_changed_setting = None;
+    }
+
+    /// Selects all the components between the selected component and the
+    /// component with the given index, including both of them. The selected
+    /// component stays the component whose settings are shown. You may not
+    /// provide an invalid index.
+    pub fn select_range(&mut self, index: usize) {
+        if index < self.layout.components().len() {
+            let (start, end) = if index < self.selected_component {
+                (index, self.selected_component)
+            } else {
+                (self.selected_component, index)
+            };
+            self.selected_components.clear();
+            self.selected_components.extend(start..=end);
+            self.last_changed_setting = None;
+        }
+    }
+
+    /// Accesses the indices of all the selected components in ascending order.
+    /// This always includes the selected component whose settings are shown.
+    pub fn selected_components(&self) -> &[usize] {
+        &self.selected_components
+    }
+
     /// Adds the component provided to the end of the layout. The newly added
     /// component becomes the selected component.
     pub fn add_component<C: Into<Component>>(&mut self, component: C) {
-        self.selected_component = self.layout.components.len();
+        self.save_undo_point(None);
+        let index = self.layout.components().len();
         self.layout.push(component);
+        self.select(index);
     }
 
-    /// Checks if the currently selected component can be removed. If there's
-    /// only one component in the layout, it can't be removed.
+    /// Checks if the selected components can be removed. There always needs to
+    /// be at least one component remaining in the layout.
     pub const fn can_remove_component(&self) -> bool {
         // We need to ensure there's always at least one component.
-        self.layout.components.len() > 1
+        self.selected_components.len() < self.layout.components().len()
     }
 
-    /// Removes the currently selected component, unless there's only one
-    /// component in the layout. The next component becomes the selected
-    /// component. If there's none, the previous component becomes the selected
-    /// component instead.
+    /// Removes all the selected components, unless that would leave no
+    /// components in the layout. The component following the first removed
+    /// component becomes the selected component. If there's none, the last
+    /// component becomes the selected component instead.
     pub fn remove_component(&mut self) {
         if self.can_remove_component() {
-            self.layout.components.remove(self.selected_component);
-            if self.selected_component >= self.layout.components.len() {
-                self.selected_component = self.layout.components.len() - 1;
+            self.save_undo_point(None);
+            let selection = mem::take(&mut self.selected_components);
+            for &index in selection.iter().rev() {
+                self.layout.remove(index);
             }
+            self.select(selection[0].min(self.layout.components().len() - 1));
         }
     }
 
-    /// Checks if the currently selected component can be moved up. If the first
-    /// component is selected, it can't be moved up.
-    pub const fn can_move_component_up(&self) -> bool {
-        self.selected_component > 0
+    /// Checks if the selected components can be moved up. If the first
+    /// component is selected, they can't be moved up.
+    pub fn can_move_component_up(&self) -> bool {
+        self.selected_components[0] > 0
     }
 
-    /// Moves the selected component up, unless the first component is selected.
+    /// Moves all the selected components up by one, unless the first component
+    /// is selected.
     pub fn move_component_up(&mut self) {
         if self.can_move_component_up() {
-            self.layout
-                .components
-                .swap(self.selected_component, self.selected_component - 1);
-            self.selected_component -= 1;
+            self.save_undo_point(None);
+            self.shift_selection_up();
         }
     }
 
-    /// Checks if the currently selected component can be moved down. If the
-    /// last component is selected, it can't be moved down.
-    pub const fn can_move_component_down(&self) -> bool {
-        self.selected_component < self.layout.components.len() - 1
+    /// Checks if the selected components can be moved down. If the last
+    /// component is selected, they can't be moved down.
+    pub fn can_move_component_down(&self) -> bool {
+        self.selected_components[self.selected_components.len() - 1]
+            < self.layout.components().len() - 1
     }
 
-    /// Moves the selected component down, unless the last component is
-    /// selected.
+    /// Moves all the selected components down by one, unless the last
+    /// component is selected.
     pub fn move_component_down(&mut self) {
         if self.can_move_component_down() {
-            self.layout
-                .components
-                .swap(self.selected_component, self.selected_component + 1);
-            self.selected_component += 1;
+            self.save_undo_point(None);
+            self.shift_selection_down();
         }
     }
 
-    /// Moves the selected component to the index provided. You may not provide
-    /// an invalid index.
+    /// Moves the selected components such that the selected component whose
+    /// settings are shown ends up at the index provided. The other selected
+    /// components keep their distance to it. If they would be moved past the
+    /// start or the end of the layout, they stop there. You may not provide an
+    /// invalid index.
     pub fn move_component(&mut self, dst_index: usize) {
-        if dst_index < self.layout.components.len() {
-            while self.selected_component > dst_index {
-                self.move_component_up();
+        if dst_index < self.layout.components().len() && dst_index != self.selected_component {
+            self.save_undo_point(None);
+            while self.selected_component > dst_index && self.can_move_component_up() {
+                self.shift_selection_up();
             }
-            while self.selected_component < dst_index {
-                self.move_component_down();
+            while self.selected_component < dst_index && self.can_move_component_down() {
+                self.shift_selection_down();
             }
         }
     }
 
-    /// Duplicates the currently selected component. The copy gets placed right
-    /// after the selected component and becomes the newly selected component.
+    fn shift_selection_up(&mut self) {
+        for index in &mut self.selected_components {
+            self.layout.swap(*index, *index - 1);
+            *index -= 1;
+        }
+        self.selected_component -= 1;
+    }
+
+    fn shift_selection_down(&mut self) {
+        for index in self.selected_components.iter_mut().rev() {
+            self.layout.swap(*index, *index + 1);
+            *index += 1;
+        }
+        self.selected_component += 1;
+    }
+
+    /// Duplicates all the selected components. The copies get placed right
+    /// after the last selected component, in the same order as the originals,
+    /// and become the newly selected components.
     pub fn duplicate_component(&mut self) {
-        let index = self.selected_component;
-        let new_index = index + 1;
+        self.save_undo_point(None);
+
+        let selection = mem::take(&mut self.selected_components);
+        let new_index = selection[selection.len() - 1] + 1;
 
-        let component = self.layout.components[index].clone();
-        self.layout.components.insert(new_index, component);
+        for (offset, &index) in selection.iter().enumerate() {
+            let component = self.layout.components()[index].clone();
+            self.layout.insert(new_index + offset, component);
+            *self.layout.component_appearance_mut(new_index + offset) =
+                self.layout.component_appearance(index);
+        }
 
-        self.selected_component = new_index;
+        let offset = selection
+            .iter()
+            .position(|&index| index == self.selected_component)
+            .unwrap_or_default();
+        self.selected_component = new_index + offset;
+        self.selected_components = (new_index..new_index + selection.len()).collect();
     }
 
     /// Sets a setting's value of the selected component by its setting index
     /// to the given value.
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// The value is rejected and the component stays unchanged if the index of
+    /// the setting is out of bounds, if the type of the value is not compatible
+    /// with the type of the setting's value or if the value doesn't satisfy the
+    /// setting's constraints.
+    pub fn set_component_settings_value(
+        &mut self,
+        index: usize,
+        value: Value,
+    ) -> StdResult<(), ValidationError> {
+        self.layout.components()[self.selected_component].validate_value(index, &value)?;
+
+        self.save_undo_point(Some(ChangedSetting::Component(
+            self.selected_component,
+            index,
+        )));
+        self.layout
+            .component_mut(self.selected_component)
+            .set_value_unchecked(index, value);
+        Ok(())
+    }
+
+    /// Resets a setting of the selected component by its setting index to its
+    /// default value. Settings that don't have a default value stay unchanged.
+    pub fn reset_component_settings_value(&mut self, index: usize) {
+        self.save_undo_point(None);
+        self.layout
+            .component_mut(self.selected_component)
+            .set_value_to_default(index);
+    }
+
+    /// Sets a setting's value of the appearance of all the selected components
+    /// by its setting index to the given value.
+    ///
+    /// # Errors
     ///
-    /// This panics if the type of the value to be set is not compatible with
-    /// the type of the setting's value. A panic can also occur if the index of
-    /// the setting provided is out of bounds.
-    pub fn set_component_settings_value(&mut self, index: usize, value: Value) {
-        self.layout.components[self.selected_component].set_value(index, value);
+    /// The value is rejected and the appearances stay unchanged if the index of
+    /// the setting is out of bounds, if the type of the value is not compatible
+    /// with the type of the setting's value or if the value doesn't satisfy the
+    /// setting's constraints.
+    pub fn set_component_appearance_value(
+        &mut self,
+        index: usize,
+        value: Value,
+        image_cache: &ImageCache,
+    ) -> StdResult<(), ValidationError> {
+        // Only the types and constraints of the settings are of interest, so
+        // the images don't need to end up in the actual image cache.
+        self.layout
+            .component_appearance(self.selected_component)
+            .settings_description(&mut ImageCache::new())
+            .validate(index, &value)?;
+
+        self.save_undo_point(Some(ChangedSetting::Appearance(index)));
+        for &component in &self.selected_components {
+            self.layout.component_appearance_mut(component).set_value(
+                index,
+                value.clone(),
+                image_cache,
+            );
+        }
+        Ok(())
     }
 
     /// Sets a setting's value of the general settings by its setting index to
     /// the given value.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This panics if the type of the value to be set is not compatible with
-    /// the type of the setting's value. A panic can also occur if the index of
-    /// the setting provided is out of bounds.
+    /// The value is rejected and the general settings stay unchanged if the
+    /// index of the setting is out of bounds, if the type of the value is not
+    /// compatible with the type of the setting's value or if the value doesn't
+    /// satisfy the setting's constraints.
     pub fn set_general_settings_value(
         &mut self,
         index: usize,
         value: Value,
         image_cache: &ImageCache,
-    ) {
+    ) -> StdResult<(), ValidationError> {
+        // Only the types and constraints of the settings are of interest, so
+        // the images don't need to end up in the actual image cache.
+        self.layout
+            .general_settings()
+            .settings_description(&mut ImageCache::new())
+            .validate(index, &value)?;
+
+        self.save_undo_point(Some(ChangedSetting::General(index)));
         self.layout
             .general_settings_mut()
             .set_value(index, value, image_cache);
+        Ok(())
+    }
+
+    /// Resets a setting of the general settings by its setting index to its
+    /// default value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index of the setting provided is out of bounds.
+    pub fn reset_general_settings_value(&mut self, index: usize) {
+        self.save_undo_point(None);
+        self.layout
+            .general_settings_mut()
+            .set_value_to_default(index);
+    }
+
+    /// Sets the color of the layout's palette with the name provided. If the
+    /// palette doesn't contain a color with that name yet, it gets added.
+    /// Every setting that refers to the color by its name changes along with
+    /// it.
+    pub fn set_palette_color(&mut self, name: &str, color: Color) {
+        self.save_undo_point(None);
+        self.layout
+            .general_settings_mut()
+            .set_palette_color(name, color);
+    }
+
+    /// Removes the color with the name provided from the layout's palette.
+    /// Settings that still refer to it become fully transparent. Returns
+    /// whether the palette contained a color with that name.
+    pub fn remove_palette_color(&mut self, name: &str) -> bool {
+        if self.layout.general_settings().palette_color(name).is_none() {
+            return false;
+        }
+        self.save_undo_point(None);
+        self.layout
+            .general_settings_mut()
+            .remove_palette_color(name)
+    }
+
+    /// Applies the theme provided to the layout, replacing the background and
+    /// all of the layout's colors with the colors of the theme.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        self.save_undo_point(None);
+        self.layout.general_settings_mut().apply_theme(theme);
+    }
+
+    /// Accesses a counter that increases whenever any of the settings of the
+    /// layout change, including when a change gets undone or redone.
+    pub const fn settings_generation(&self) -> u64 {
+        self.layout.settings_generation()
+    }
+
+    /// Takes the changes to the settings of the layout that happened since
+    /// the changes were last taken. Undoing or redoing a change considers
+    /// everything changed.
+    pub fn take_settings_changes(&mut self) -> SettingsChanges {
+        self.layout.take_settings_changes()
+    }
+
+    /// Checks if there is a change that can be undone.
+    pub const fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Undoes the last change made to the layout, including changes to the
+    /// settings. The selection is restored to what it was before the change.
+    pub fn undo(&mut self) {
+        if let Some(checkpoint) = self.undo_stack.pop() {
+            let current = self.restore(checkpoint);
+            self.redo_stack.push(current);
+        }
+    }
+
+    /// Checks if there is an undone change that can be redone.
+    pub const fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Redoes the last change that got undone.
+    pub fn redo(&mut self) {
+        if let Some(checkpoint) = self.redo_stack.pop() {
+            let current = self.restore(checkpoint);
+            self.undo_stack.push(current);
+        }
+    }
+
+    // Remembers the current state before a change gets applied. If the same
+    // setting as last time is changed, the existing checkpoint is kept.
+    fn save_undo_point(&mut self, setting: Option<ChangedSetting>) {
+        if setting.is_some() && setting == self.last_changed_setting {
+            return;
+        }
+        self.last_changed_setting = setting;
+        self.redo_stack.clear();
+        if self.undo_stack.len() >= MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(Checkpoint {
+            layout: self.layout.clone(),
+            selected_component: self.selected_component,
+            selected_components: self.selected_components.clone(),
+        });
+    }
+
+    // Replaces the current state with the checkpoint and returns the state
+    // that got replaced.
+    fn restore(&mut self, checkpoint: Checkpoint) -> Checkpoint {
+        self.last_changed_setting = None;
+        let layout = mem::replace(&mut self.layout, checkpoint.layout);
+        self.layout.changes.replaced(layout.settings_generation());
+        Checkpoint {
+            layout,
+            selected_component: mem::replace(
+                &mut self.selected_component,
+                checkpoint.selected_component,
+            ),
+            selected_components: mem::replace(
+                &mut self.selected_components,
+                checkpoint.selected_components,
+            ),
+        }
     }
 }
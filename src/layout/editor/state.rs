@@ -13,11 +13,18 @@ pub struct State {
     pub components: Vec<String>,
     /// Describes which actions are currently available.
     pub buttons: Buttons,
-    /// The index of the currently selected component.
+    /// The index of the currently selected component. This is the component
+    /// whose settings are shown.
     pub selected_component: u32,
+    /// The indices of all the selected components in ascending order. This
+    /// always includes the selected component whose settings are shown.
+    pub selected_components: Vec<u32>,
     /// A generic description of the settings available for the selected
     /// component and their current values.
     pub component_settings: SettingsDescription,
+    /// A generic description of the settings available for the appearance of
+    /// the selected component and their current values.
+    pub component_appearance: SettingsDescription,
     /// A generic description of the general settings available for the layout
     /// and their current values.
     pub general_settings: SettingsDescription,
@@ -28,15 +35,19 @@ pub struct State {
 /// executed successfully.
 #[derive(Serialize, Deserialize)]
 pub struct Buttons {
-    /// Describes whether the currently selected component can be removed. If
-    /// there's only one component in the layout, it can't be removed.
+    /// Describes whether the currently selected components can be removed. If
+    /// all the components of the layout are selected, they can't be removed.
     pub can_remove: bool,
-    /// Describes whether the currently selected component can be moved up. If
-    /// the first component is selected, it can't be moved.
+    /// Describes whether the currently selected components can be moved up. If
+    /// the first component is selected, they can't be moved.
     pub can_move_up: bool,
-    /// Describes whether the currently selected component can be moved down. If
-    /// the last component is selected, it can't be moved.
+    /// Describes whether the currently selected components can be moved down.
+    /// If the last component is selected, they can't be moved.
     pub can_move_down: bool,
+    /// Describes whether there is a change that can be undone.
+    pub can_undo: bool,
+    /// Describes whether there is an undone change that can be redone.
+    pub can_redo: bool,
 }
 
 #[cfg(feature = "std")]
@@ -59,7 +70,7 @@ impl Editor {
     pub fn state(&self, image_cache: &mut ImageCache) -> State {
         let components = self
             .layout
-            .components
+            .components()
             .iter()
             .map(|c| c.name().into_owned())
             .collect();
@@ -68,14 +79,25 @@ impl Editor {
             can_remove: self.can_remove_component(),
             can_move_up: self.can_move_component_up(),
             can_move_down: self.can_move_component_down(),
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
         };
 
         State {
             components,
             buttons,
             selected_component: self.selected_component as u32,
-            component_settings: self.layout.components[self.selected_component]
+            selected_components: self
+                .selected_components
+                .iter()
+                .map(|&index| index as u32)
+                .collect(),
+            component_settings: self.layout.components()[self.selected_component]
                 .settings_description(),
+            component_appearance: self
+                .layout
+                .component_appearance(self.selected_component)
+                .settings_description(image_cache),
             general_settings: self
                 .layout
                 .general_settings()
@@ -1,8 +1,9 @@
-use super::LayoutDirection;
+use super::{LayoutDirection, PaletteColor, Theme};
 use crate::{
     platform::prelude::*,
     settings::{
-        Color, Field, Font, Gradient, ImageCache, LayoutBackground, SettingsDescription, Value,
+        Color, Constraints, Field, Font, Gradient, ImageCache, LayoutBackground,
+        SettingsDescription, Value,
     },
 };
 use serde_derive::{Deserialize, Serialize};
@@ -52,35 +53,92 @@ pub struct GeneralSettings {
     pub separators_color: Color,
     /// The text color to use for text that doesn't specify its own color.
     pub text_color: Color,
+    /// The named colors of the layout's palette. Components can refer to them
+    /// by their names, so a color only needs to be changed in a single place.
+    pub palette: Vec<PaletteColor>,
 }
 
+/// The number of settings that are always available. The colors of the
+/// palette follow after them.
+const FIXED_SETTINGS: usize = 17;
+
 impl Default for GeneralSettings {
     fn default() -> Self {
+        let theme = Theme::dark();
         Self {
             direction: LayoutDirection::Vertical,
             timer_font: None,
             times_font: None,
             text_font: None,
-            text_shadow: Some(Color::hsla(0.0, 0.0, 0.0, 0.5)),
-            background: LayoutBackground::Gradient(Gradient::Plain(Color::hsla(
-                0.0, 0.0, 0.06, 1.0,
-            ))),
-            best_segment_color: Color::hsla(50.0, 1.0, 0.5, 1.0),
-            ahead_gaining_time_color: Color::hsla(136.0, 1.0, 0.4, 1.0),
-            ahead_losing_time_color: Color::hsla(136.0, 0.55, 0.6, 1.0),
-            behind_gaining_time_color: Color::hsla(0.0, 0.55, 0.6, 1.0),
-            behind_losing_time_color: Color::hsla(0.0, 1.0, 0.4, 1.0),
-            not_running_color: Color::hsla(0.0, 0.0, 0.67, 1.0),
-            personal_best_color: Color::hsla(203.0, 1.0, 0.54, 1.0),
-            paused_color: Color::hsla(0.0, 0.0, 0.48, 1.0),
-            thin_separators_color: Color::hsla(0.0, 0.0, 1.0, 0.09),
-            separators_color: Color::hsla(0.0, 0.0, 1.0, 0.35),
-            text_color: Color::hsla(0.0, 0.0, 1.0, 1.0),
+            text_shadow: theme.text_shadow,
+            background: LayoutBackground::Gradient(Gradient::Plain(theme.background)),
+            best_segment_color: theme.best_segment,
+            ahead_gaining_time_color: theme.ahead_gaining,
+            ahead_losing_time_color: theme.ahead_losing,
+            behind_gaining_time_color: theme.behind_gaining,
+            behind_losing_time_color: theme.behind_losing,
+            not_running_color: theme.not_running,
+            personal_best_color: theme.personal_best,
+            paused_color: theme.paused,
+            thin_separators_color: theme.thin_separators,
+            separators_color: theme.separators,
+            text_color: theme.text_primary,
+            palette: Vec::new(),
         }
     }
 }
 
 impl GeneralSettings {
+    /// Applies the theme provided to the layout. This replaces the background
+    /// and all the colors of the layout with the colors of the theme. Fonts,
+    /// the layout direction and the palette stay as they are.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        self.background = LayoutBackground::Gradient(Gradient::Plain(theme.background));
+        self.text_color = theme.text_primary;
+        self.text_shadow = theme.text_shadow;
+        self.separators_color = theme.separators;
+        self.thin_separators_color = theme.thin_separators;
+        self.best_segment_color = theme.best_segment;
+        self.ahead_gaining_time_color = theme.ahead_gaining;
+        self.ahead_losing_time_color = theme.ahead_losing;
+        self.behind_gaining_time_color = theme.behind_gaining;
+        self.behind_losing_time_color = theme.behind_losing;
+        self.not_running_color = theme.not_running;
+        self.personal_best_color = theme.personal_best;
+        self.paused_color = theme.paused;
+    }
+
+    /// Looks up the color of the palette with the name provided.
+    pub fn palette_color(&self, name: &str) -> Option<Color> {
+        self.palette
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.color)
+    }
+
+    /// Sets the color of the palette with the name provided. If the palette
+    /// doesn't contain a color with that name yet, it gets added to the end of
+    /// the palette.
+    pub fn set_palette_color(&mut self, name: &str, color: Color) {
+        if let Some(entry) = self.palette.iter_mut().find(|entry| entry.name == name) {
+            entry.color = color;
+        } else {
+            self.palette.push(PaletteColor {
+                name: name.into(),
+                color,
+            });
+        }
+    }
+
+    /// Removes the color with the name provided from the palette. Components
+    /// that still refer to it become fully transparent. Returns whether the
+    /// palette contained a color with that name.
+    pub fn remove_palette_color(&mut self, name: &str) -> bool {
+        let len = self.palette.len();
+        self.palette.retain(|entry| entry.name != name);
+        self.palette.len() != len
+    }
+
     /// Accesses a generic description of the general settings available for the
     /// layout and their current values. The [`ImageCache`] is updated with all
     /// the images that are part of the state. The images are marked as visited
@@ -88,100 +146,163 @@ impl GeneralSettings {
     /// [`ImageCache::collect`] to ensure unused images are removed from the
     /// cache.
     pub fn settings_description(&self, image_cache: &mut ImageCache) -> SettingsDescription {
-        SettingsDescription::with_fields(vec![
+        self.settings_description_without_defaults(image_cache)
+            .with_defaults(Self::default().settings_description_without_defaults(image_cache))
+    }
+
+    /// Sets a setting's value by its index to its default value. The colors
+    /// of the palette don't have a default value, so they stay as they are.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index of the setting provided is out of bounds.
+    pub fn set_value_to_default(&mut self, index: usize) {
+        if index >= FIXED_SETTINGS {
+            assert!(index - FIXED_SETTINGS < self.palette.len());
+            return;
+        }
+        // The default settings don't contain any images, so nothing needs to
+        // be looked up in the actual image cache.
+        let mut image_cache = ImageCache::new();
+        let value = Self::default()
+            .settings_description_without_defaults(&mut image_cache)
+            .fields
+            .swap_remove(index)
+            .value;
+        self.set_value(index, value, &image_cache);
+    }
+
+    fn settings_description_without_defaults(
+        &self,
+        image_cache: &mut ImageCache,
+    ) -> SettingsDescription {
+        let mut settings = SettingsDescription::with_fields(vec![
             Field::new(
+                "layout_direction",
                 "Layout Direction".into(),
                 "The direction in which the components are laid out.".into(),
                 self.direction.into(),
             ),
             Field::new(
+                "custom_timer_font",
                 "Custom Timer Font".into(),
                 "Allows you to specify a custom font for the timer. If this is not set, the default font is used."
                     .into(),
                 self.timer_font.clone().into(),
             ),
             Field::new(
+                "custom_times_font",
                 "Custom Times Font".into(),
                 "Allows you to specify a custom font for the times. If this is not set, the default font is used."
                     .into(),
                 self.times_font.clone().into(),
             ),
             Field::new(
+                "custom_text_font",
                 "Custom Text Font".into(),
                 "Allows you to specify a custom font for the text. If this is not set, the default font is used."
                     .into(),
                 self.text_font.clone().into(),
             ),
             Field::new(
+                "text_shadow",
                 "Text Shadow".into(),
                 "Allows you to optionally specify a color for text shadows.".into(),
                 self.text_shadow.into(),
             ),
             Field::new(
+                "background",
                 "Background".into(),
                 "The background shown behind the entire layout.".into(),
                 self.background.cache(image_cache).into(),
             ),
             Field::new(
+                "best_segment",
                 "Best Segment".into(),
                 "The color to use for when you achieve a new best segment.".into(),
                 self.best_segment_color.into(),
             ),
             Field::new(
+                "ahead_gaining_time",
                 "Ahead (Gaining Time)".into(),
                 "The color to use for when you are ahead of the comparison and are gaining even more time."
                     .into(),
                 self.ahead_gaining_time_color.into(),
             ),
             Field::new(
+                "ahead_losing_time",
                 "Ahead (Losing Time)".into(),
                 "The color to use for when you are ahead of the comparison, but are losing time."
                     .into(),
                 self.ahead_losing_time_color.into(),
             ),
             Field::new(
+                "behind_gaining_time",
                 "Behind (Gaining Time)".into(),
                 "The color to use for when you are behind the comparison, but are gaining back time."
                     .into(),
                 self.behind_gaining_time_color.into(),
             ),
             Field::new(
+                "behind_losing_time",
                 "Behind (Losing Time)".into(),
                 "The color to use for when you are behind the comparison and are losing even more time."
                     .into(),
                 self.behind_losing_time_color.into(),
             ),
             Field::new(
+                "not_running",
                 "Not Running".into(),
                 "The color to use for when there is no active attempt.".into(),
                 self.not_running_color.into(),
             ),
             Field::new(
+                "personal_best",
                 "Personal Best".into(),
                 "The color to use for when you achieve a new Personal Best.".into(),
                 self.personal_best_color.into(),
             ),
             Field::new(
+                "paused",
                 "Paused".into(),
                 "The color to use for when the timer is paused.".into(),
                 self.paused_color.into(),
             ),
             Field::new(
+                "thin_separators",
                 "Thin Separators".into(),
                 "The color of thin separators.".into(),
                 self.thin_separators_color.into(),
             ),
             Field::new(
+                "separators",
                 "Separators".into(),
                 "The color of normal separators.".into(),
                 self.separators_color.into(),
             ),
             Field::new(
+                "text",
                 "Text".into(),
                 "The color to use for text that doesn't specify its own color.".into(),
                 self.text_color.into(),
             ),
-        ])
+        ]);
+
+        if !self.palette.is_empty() {
+            settings.fields.extend(self.palette.iter().map(|entry| Field {
+                key: format!("palette.{}", entry.name).into(),
+                text: entry.name.clone().into(),
+                tooltip: "A color of the layout's palette. Changing it changes the color of every setting that refers to it."
+                    .into(),
+                value: entry.color.into(),
+                constraints: Constraints::NONE,
+                default_value: None,
+            }));
+            let fields = FIXED_SETTINGS..settings.fields.len();
+            settings.add_group("palette", "Palette", fields, false);
+        }
+
+        settings
     }
 
     /// Sets a setting's value by its index to the given value.
@@ -210,7 +331,7 @@ impl GeneralSettings {
             14 => self.thin_separators_color = value.into(),
             15 => self.separators_color = value.into(),
             16 => self.text_color = value.into(),
-            _ => panic!("Unsupported Setting Index"),
+            _ => self.palette[index - FIXED_SETTINGS].color = value.into(),
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{ComponentSettings, GeneralSettings};
+use super::{ComponentAppearance, ComponentSettings, GeneralSettings, ScalingRules};
 use crate::platform::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 
@@ -10,6 +10,14 @@ pub struct LayoutSettings {
     pub components: Vec<ComponentSettings>,
     /// The general settings of the layout that apply to all components.
     pub general: GeneralSettings,
+    /// The appearances of the components by their index. Components without an
+    /// appearance use the default appearance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub component_appearances: Vec<ComponentAppearance>,
+    /// The rules that describe how the layout adapts to the size it is shown
+    /// at. If there are none, the layout is simply stretched to fit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scaling_rules: Option<ScalingRules>,
 }
 
 #[cfg(feature = "std")]
@@ -1,9 +1,13 @@
+use core::fmt;
+use serde::de::{self, IgnoredAny, SeqAccess, Visitor};
 use serde_derive::{Deserialize, Serialize};
+use snafu::ResultExt;
 
-use super::{ComponentState, LayoutDirection};
+use super::{ComponentAppearance, ComponentState, LayoutDirection, LayoutScaling};
 use crate::{
     platform::prelude::*,
     settings::{Color, Font, ImageId, LayoutBackground},
+    util::msgpack,
 };
 
 /// The state object describes the information to visualize for the layout.
@@ -11,6 +15,9 @@ use crate::{
 pub struct LayoutState {
     /// The state objects for all of the components in the layout.
     pub components: Vec<ComponentState>,
+    /// Describes how each of the components is composited onto the layout.
+    /// There is one appearance for each of the components.
+    pub component_appearances: Vec<ComponentAppearance<ImageId>>,
     /// The direction which the components are laid out in.
     pub direction: LayoutDirection,
     /// The font to use for the timer text. `None` means a default font should
@@ -32,6 +39,15 @@ pub struct LayoutState {
     pub separators_color: Color,
     /// The text color to use for text that doesn't specify its own color.
     pub text_color: Color,
+    /// The scale factors to apply when rendering the layout, based on the
+    /// layout's scaling rules and the size it is shown at.
+    #[serde(default)]
+    pub scaling: LayoutScaling,
+    /// The number of seconds that passed since the layout started being
+    /// shown. Animated gradients are resolved based on this time with
+    /// [`Gradient::at`](crate::settings::Gradient::at).
+    #[serde(default)]
+    pub animation_time: f64,
 }
 
 #[cfg(feature = "std")]
@@ -44,3 +60,84 @@ impl LayoutState {
         serde_json::to_writer(writer, self)
     }
 }
+
+/// The error that occurs when decoding the binary encoding of a
+/// [`LayoutState`] fails.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum BinaryError {
+    /// The state was encoded with a version of the schema that is not
+    /// supported.
+    #[snafu(display("The schema version {version} is not supported."))]
+    UnsupportedVersion {
+        /// The version of the schema the state was encoded with.
+        version: u32,
+    },
+    /// The data is not a valid encoding of a state.
+    Decode {
+        /// The underlying error.
+        source: msgpack::Error,
+    },
+}
+
+impl LayoutState {
+    /// The version of the schema of the binary encoding of the state. It is
+    /// increased whenever the state changes in a way that existing decoders
+    /// can't handle, such as a field being removed or changing its type. New
+    /// fields and components may be added without increasing the version, so
+    /// decoders should ignore anything they don't know about.
+    pub const BINARY_SCHEMA_VERSION: u32 = 1;
+
+    /// Encodes the state object's information in a compact binary encoding
+    /// and appends it to the buffer provided. This is meant for passing the
+    /// state to renderers running in a different process, as it is a lot
+    /// cheaper to produce and to consume than JSON. The encoding is
+    /// [MessagePack](https://msgpack.org) with the same structure as the JSON,
+    /// wrapped in an array of two elements, the first of which is the
+    /// [`BINARY_SCHEMA_VERSION`](Self::BINARY_SCHEMA_VERSION) and the second
+    /// of which is the state itself.
+    pub fn write_binary(&self, buf: &mut Vec<u8>) -> Result<(), msgpack::Error> {
+        msgpack::to_vec(&(Self::BINARY_SCHEMA_VERSION, self), buf)
+    }
+
+    /// Decodes a state object from its binary encoding, as produced by
+    /// [`write_binary`](Self::write_binary).
+    pub fn from_binary(data: &[u8]) -> Result<Self, BinaryError> {
+        let Versioned(state) = msgpack::from_slice(data).context(Decode)?;
+        state.map_err(|version| BinaryError::UnsupportedVersion { version })
+    }
+}
+
+/// The binary encoding of a state, which is only decoded if its schema version
+/// is supported. Otherwise the version is kept.
+struct Versioned(Result<LayoutState, u32>);
+
+impl<'de> serde::Deserialize<'de> for Versioned {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(2, VersionedVisitor)
+    }
+}
+
+struct VersionedVisitor;
+
+impl<'de> Visitor<'de> for VersionedVisitor {
+    type Value = Versioned;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a schema version followed by a layout state")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Versioned, A::Error> {
+        let version: u32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if version != LayoutState::BINARY_SCHEMA_VERSION {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            return Ok(Versioned(Err(version)));
+        }
+        let state = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Versioned(Ok(state)))
+    }
+}
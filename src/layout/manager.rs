@@ -0,0 +1,132 @@
+use super::Layout;
+use crate::platform::prelude::*;
+
+/// A `SharedLayoutManager` is a wrapper around the [`LayoutManager`] that can
+/// be shared across multiple threads with multiple owners. This is what the
+/// [`HotkeySystem`](crate::HotkeySystem) uses to switch between the layouts.
+#[cfg(feature = "std")]
+pub type SharedLayoutManager = alloc::sync::Arc<std::sync::RwLock<LayoutManager>>;
+
+/// The Layout Manager holds multiple layouts and keeps track of which one of
+/// them is currently in use. This allows the runner to switch between the
+/// layouts, even in the middle of an attempt. There is always at least one
+/// layout in the Layout Manager.
+#[derive(Clone)]
+pub struct LayoutManager {
+    layouts: Vec<Layout>,
+    current: usize,
+}
+
+impl Default for LayoutManager {
+    fn default() -> Self {
+        Self::new(Layout::default_layout())
+    }
+}
+
+impl LayoutManager {
+    /// Creates a new Layout Manager that holds a single layout, which is
+    /// selected.
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layouts: vec![layout],
+            current: 0,
+        }
+    }
+
+    /// Creates a new Layout Manager from a list of layouts. The first layout
+    /// is selected. Returns [`None`] if there are no layouts.
+    pub fn from_layouts(layouts: Vec<Layout>) -> Option<Self> {
+        if layouts.is_empty() {
+            None
+        } else {
+            Some(Self {
+                layouts,
+                current: 0,
+            })
+        }
+    }
+
+    /// Consumes the Layout Manager and creates a Shared Layout Manager that
+    /// can be shared across multiple threads with multiple owners.
+    #[cfg(feature = "std")]
+    pub fn into_shared(self) -> SharedLayoutManager {
+        alloc::sync::Arc::new(std::sync::RwLock::new(self))
+    }
+
+    /// Accesses all the layouts of the Layout Manager. There is always at least
+    /// one layout.
+    pub fn layouts(&self) -> &[Layout] {
+        &self.layouts
+    }
+
+    /// Accesses the layout that is currently selected.
+    pub fn current(&self) -> &Layout {
+        &self.layouts[self.current]
+    }
+
+    /// Grants mutable access to the layout that is currently selected.
+    pub fn current_mut(&mut self) -> &mut Layout {
+        &mut self.layouts[self.current]
+    }
+
+    /// Returns the index of the layout that is currently selected.
+    pub const fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Adds a layout to the end of the list of layouts. The selection doesn't
+    /// change.
+    pub fn push(&mut self, layout: Layout) {
+        self.layouts.push(layout);
+    }
+
+    /// Removes the layout at the index provided and returns it. The layout
+    /// that is selected stays selected, unless it is the one being removed,
+    /// in which case the layout before it gets selected. If the first layout
+    /// is removed while it is selected, the next one gets selected. The last
+    /// remaining layout can't be removed, so [`None`] is returned in that
+    /// case.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<Layout> {
+        assert!(index < self.layouts.len(), "Index out of bounds");
+        if self.layouts.len() == 1 {
+            return None;
+        }
+        let layout = self.layouts.remove(index);
+        if index < self.current || (index == self.current && index > 0) {
+            self.current -= 1;
+        }
+        Some(layout)
+    }
+
+    /// Selects the layout at the index provided.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn select(&mut self, index: usize) {
+        assert!(index < self.layouts.len(), "Index out of bounds");
+        self.current = index;
+    }
+
+    /// Selects the next layout. If the last layout is selected, the first
+    /// layout gets selected.
+    pub const fn next(&mut self) {
+        self.current += 1;
+        if self.current == self.layouts.len() {
+            self.current = 0;
+        }
+    }
+
+    /// Selects the previous layout. If the first layout is selected, the last
+    /// layout gets selected.
+    pub const fn previous(&mut self) {
+        if self.current == 0 {
+            self.current = self.layouts.len();
+        }
+        self.current -= 1;
+    }
+}
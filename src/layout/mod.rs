@@ -2,7 +2,9 @@
 //! [`Layout`]. A [`Layout`] allows you to combine multiple components together
 //! to visualize a variety of information the runner is interested in.
 
+mod animation;
 mod component;
+mod component_appearance;
 mod component_settings;
 mod component_state;
 pub mod editor;
@@ -10,28 +12,57 @@ mod general_settings;
 mod layout_direction;
 mod layout_settings;
 mod layout_state;
+mod manager;
 pub mod parser;
+pub mod saver;
+mod scaling_rules;
+mod settings_changes;
+mod state_schema;
+mod theme;
 
 pub use self::{
-    component::Component, component_settings::ComponentSettings, component_state::ComponentState,
-    editor::Editor, general_settings::GeneralSettings, layout_direction::LayoutDirection,
-    layout_settings::LayoutSettings, layout_state::LayoutState,
+    animation::Animator,
+    component::Component,
+    component_appearance::ComponentAppearance,
+    component_settings::ComponentSettings,
+    component_state::ComponentState,
+    editor::Editor,
+    general_settings::GeneralSettings,
+    layout_direction::LayoutDirection,
+    layout_settings::LayoutSettings,
+    layout_state::{BinaryError, LayoutState},
+    manager::LayoutManager,
+    scaling_rules::{LayoutScaling, ScalingBreakpoint, ScalingRules},
+    settings_changes::SettingsChanges,
+    state_schema::{ComponentStateSchema, FieldSchema, TypeSchema},
+    theme::{PaletteColor, Theme, ThemeColor, ThemedColor},
 };
 
+#[cfg(feature = "std")]
+pub use self::manager::SharedLayoutManager;
+
+use self::settings_changes::ChangeTracker;
 use crate::{
     component::{previous_segment, splits, timer, title},
     platform::prelude::*,
     settings::ImageCache,
-    timing::Snapshot,
+    timing::{Snapshot, TimeStamp},
 };
 
 /// A Layout allows you to combine multiple components together to visualize a
 /// variety of information the runner is interested in.
 #[derive(Clone, Default)]
 pub struct Layout {
-    /// All of the layout's components.
-    pub components: Vec<Component>,
+    components: Vec<Component>,
     settings: GeneralSettings,
+    // The appearances of the components by their index. There is always
+    // exactly one for each component, so the components are only ever added,
+    // removed or moved together with their appearance.
+    appearances: Vec<ComponentAppearance>,
+    scaling_rules: Option<ScalingRules>,
+    viewport_size: Option<[f32; 2]>,
+    animation_start: Option<TimeStamp>,
+    changes: ChangeTracker,
 }
 
 impl Layout {
@@ -45,26 +76,34 @@ impl Layout {
     /// are provided by this and how they are configured may change in the
     /// future.
     pub fn default_layout() -> Self {
-        Self {
-            components: vec![
-                title::Component::new().into(),
-                splits::Component::new().into(),
-                timer::Component::new().into(),
-                previous_segment::Component::new().into(),
-            ],
-            settings: GeneralSettings::default(),
-        }
+        let mut layout = Self::new();
+        layout.push(title::Component::new());
+        layout.push(splits::Component::new());
+        layout.push(timer::Component::new());
+        layout.push(previous_segment::Component::new());
+        layout
     }
 
     /// Creates a new layout from the layout settings of the whole layout.
     pub fn from_settings(layout_settings: LayoutSettings) -> Self {
+        let components: Vec<Component> = layout_settings
+            .components
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        // The appearances may be missing for some or all of the components.
+        let mut appearances = layout_settings.component_appearances;
+        appearances.resize(components.len(), ComponentAppearance::default());
+
         Self {
-            components: layout_settings
-                .components
-                .into_iter()
-                .map(Into::into)
-                .collect(),
+            components,
             settings: layout_settings.general,
+            appearances,
+            scaling_rules: layout_settings.scaling_rules,
+            viewport_size: None,
+            animation_start: None,
+            changes: ChangeTracker::default(),
         }
     }
 
@@ -75,20 +114,132 @@ impl Layout {
     }
 
     /// Grants mutable access to the general settings of the layout that apply
-    /// to all components.
+    /// to all components. The general settings are considered changed.
     pub const fn general_settings_mut(&mut self) -> &mut GeneralSettings {
+        self.changes.general_settings();
         &mut self.settings
     }
 
-    /// Adds a new component to the end of the layout.
+    /// Accesses the appearance of the component at the index provided.
+    pub fn component_appearance(&self, index: usize) -> ComponentAppearance {
+        self.appearances.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Grants mutable access to the appearance of the component at the index
+    /// provided. The component is considered changed.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn component_appearance_mut(&mut self, index: usize) -> &mut ComponentAppearance {
+        assert!(index < self.components.len(), "Index out of bounds");
+        self.changes.component(index);
+        &mut self.appearances[index]
+    }
+
+    /// Accesses the rules that describe how the layout adapts to the size it is
+    /// shown at. There are none if the layout is simply stretched to fit.
+    pub const fn scaling_rules(&self) -> Option<&ScalingRules> {
+        self.scaling_rules.as_ref()
+    }
+
+    /// Sets the rules that describe how the layout adapts to the size it is
+    /// shown at. If there are none, the layout is simply stretched to fit.
+    pub fn set_scaling_rules(&mut self, scaling_rules: Option<ScalingRules>) {
+        self.changes.general_settings();
+        self.scaling_rules = scaling_rules;
+    }
+
+    /// Tells the layout the size in pixels it is currently shown at. This is
+    /// used to apply the layout's [`ScalingRules`] when calculating its state,
+    /// so it should be called whenever the size of the window or overlay that
+    /// shows the layout changes.
+    pub const fn set_viewport_size(&mut self, size: [f32; 2]) {
+        self.viewport_size = Some(size);
+    }
+
+    /// Accesses all of the layout's components.
+    pub const fn components(&self) -> &[Component] {
+        self.components.as_slice()
+    }
+
+    /// Grants mutable access to the component at the index provided. The
+    /// component is considered changed.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn component_mut(&mut self, index: usize) -> &mut Component {
+        assert!(index < self.components.len(), "Index out of bounds");
+        self.changes.component(index);
+        &mut self.components[index]
+    }
+
+    /// Adds a new component to the end of the layout. It uses the default
+    /// appearance.
     pub fn push<C: Into<Component>>(&mut self, component: C) {
         self.components.push(component.into());
+        self.appearances.push(ComponentAppearance::default());
+        self.changes.components_rearranged();
+    }
+
+    /// Inserts a new component at the index provided, shifting all the
+    /// components after it to the right. It uses the default appearance.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is larger than the amount of components.
+    pub fn insert<C: Into<Component>>(&mut self, index: usize, component: C) {
+        self.components.insert(index, component.into());
+        self.appearances
+            .insert(index, ComponentAppearance::default());
+        self.changes.components_rearranged();
+    }
+
+    /// Removes the component at the index provided together with its
+    /// appearance, shifting all the components after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Component {
+        self.appearances.remove(index);
+        self.changes.components_rearranged();
+        self.components.remove(index)
+    }
+
+    /// Swaps the components at the indices provided together with their
+    /// appearances.
+    ///
+    /// # Panics
+    ///
+    /// This panics if either of the indices is out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.components.swap(a, b);
+        self.appearances.swap(a, b);
+        self.changes.components_rearranged();
+    }
+
+    /// Accesses a counter that increases whenever any of the settings of the
+    /// layout change. Comparing it to the value of the last frame is a cheap
+    /// way to check whether anything changed at all.
+    pub const fn settings_generation(&self) -> u64 {
+        self.changes.generation()
+    }
+
+    /// Takes the changes to the settings of the layout that happened since
+    /// the changes were last taken. A new layout starts out with everything
+    /// being considered changed.
+    pub fn take_settings_changes(&mut self) -> SettingsChanges {
+        self.changes.take()
     }
 
     /// Updates the layout's state based on the timer provided. You can use this
-    /// to visualize all of the components of a layout. The [`ImageCache`] is
-    /// updated with all the images that are part of the state. The images are
-    /// marked as visited in the [`ImageCache`]. You still need to manually run
+    /// to visualize all of the components of a layout. All the components are
+    /// updated based on the same snapshot of the timer, so the times they show
+    /// are always consistent with each other. The [`ImageCache`] is updated
+    /// with all the images that are part of the state. The images are marked as
+    /// visited in the [`ImageCache`]. You still need to manually run
     /// [`ImageCache::collect`] to ensure unused images are removed from the
     /// cache.
     pub fn update_state(
@@ -110,6 +261,52 @@ impl Layout {
             .components
             .extend(components.map(|c| c.state(image_cache, timer, settings)));
 
+        self.update_general_state(state, image_cache);
+    }
+
+    /// Updates only the states of the components with the indices provided,
+    /// based on the timer provided. The states of all the other components are
+    /// kept as they are. This is useful for embedders that show the components
+    /// in separate windows, such as the timer on one monitor and the splits on
+    /// another. Updating each window's components from the same snapshot keeps
+    /// them consistent with each other, without having to take a snapshot for
+    /// each window. If the state doesn't contain the states of exactly the
+    /// components of the layout, such as when it's a new state or components
+    /// were added or removed, all the components are updated instead. The
+    /// [`ImageCache`] is updated with all the images that are part of the
+    /// updated states. Indices that are out of bounds are skipped.
+    ///
+    /// There is no separate `update_state_batched` function for updating all
+    /// the components in a batch, as [`update_state`](Self::update_state)
+    /// already updates all of them from the same snapshot.
+    pub fn update_state_subset(
+        &mut self,
+        state: &mut LayoutState,
+        image_cache: &mut ImageCache,
+        timer: &Snapshot,
+        indices: &[usize],
+    ) {
+        if state.components.len() != self.components.len() {
+            return self.update_state(state, image_cache, timer);
+        }
+
+        let settings = &self.settings;
+
+        for &index in indices {
+            if let (Some(component), Some(state)) = (
+                self.components.get_mut(index),
+                state.components.get_mut(index),
+            ) {
+                component.update_state(state, image_cache, timer, settings);
+            }
+        }
+
+        self.update_general_state(state, image_cache);
+    }
+
+    fn update_general_state(&mut self, state: &mut LayoutState, image_cache: &mut ImageCache) {
+        let settings = &self.settings;
+
         state.timer_font.clone_from(&settings.timer_font);
         state.times_font.clone_from(&settings.times_font);
         state.text_font.clone_from(&settings.text_font);
@@ -120,6 +317,21 @@ impl Layout {
         state.text_color = settings.text_color;
         state.direction = settings.direction;
         state.text_shadow = settings.text_shadow;
+        state.scaling = match (&self.scaling_rules, self.viewport_size) {
+            (Some(rules), Some(size)) => rules.resolve(size),
+            _ => LayoutScaling::default(),
+        };
+
+        let now = TimeStamp::now();
+        let animation_start = *self.animation_start.get_or_insert(now);
+        state.animation_time = (now - animation_start).total_seconds();
+
+        state.component_appearances.clear();
+        state.component_appearances.extend(
+            self.appearances
+                .iter()
+                .map(|appearance| appearance.cache(image_cache)),
+        );
     }
 
     /// Calculates the layout's state based on the timer provided. You can use
@@ -136,9 +348,17 @@ impl Layout {
 
     /// Accesses the settings of the layout.
     pub fn settings(&self) -> LayoutSettings {
+        let component_appearances = if self.appearances.iter().all(|a| a.is_default()) {
+            Vec::new()
+        } else {
+            self.appearances.clone()
+        };
+
         LayoutSettings {
             components: self.components.iter().map(Component::settings).collect(),
             general: self.settings.clone(),
+            component_appearances,
+            scaling_rules: self.scaling_rules.clone(),
         }
     }
 
@@ -155,4 +375,76 @@ impl Layout {
             component.scroll_down();
         }
     }
+
+    /// Shows the next tab of all the Tabs Components in the layout.
+    pub fn next_tab(&mut self) {
+        for component in &mut self.components {
+            component.next_tab();
+        }
+    }
+
+    /// Shows the previous tab of all the Tabs Components in the layout.
+    pub fn previous_tab(&mut self) {
+        for component in &mut self.components {
+            component.previous_tab();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::timer,
+        util::tests_helper::{create_timer, span, start_run},
+    };
+
+    fn time(state: &LayoutState, index: usize) -> &str {
+        match &state.components[index] {
+            ComponentState::Timer(state) => &state.time,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn updating_a_subset_of_components() {
+        let mut layout = Layout::new();
+        layout.push(timer::Component::new());
+        layout.push(timer::Component::new());
+
+        let mut image_cache = ImageCache::new();
+        let mut timer = create_timer(&["A"]);
+        let mut state = LayoutState::default();
+
+        // The state doesn't have any component states yet, so all of them get
+        // created.
+        layout.update_state_subset(&mut state, &mut image_cache, &timer.snapshot(), &[1]);
+        assert_eq!(state.components.len(), 2);
+        assert_eq!(time(&state, 0), "0");
+
+        start_run(&mut timer);
+        timer.set_game_time(span(5.0)).unwrap();
+
+        layout.update_state_subset(&mut state, &mut image_cache, &timer.snapshot(), &[1]);
+        assert_eq!(time(&state, 0), "0");
+        assert_eq!(time(&state, 1), "5");
+    }
+
+    #[test]
+    fn out_of_bounds_indices_are_skipped() {
+        let mut layout = Layout::new();
+        layout.push(timer::Component::new());
+
+        let mut image_cache = ImageCache::new();
+        let mut timer = create_timer(&["A"]);
+        let mut state = LayoutState::default();
+        layout.update_state(&mut state, &mut image_cache, &timer.snapshot());
+
+        start_run(&mut timer);
+        timer.set_game_time(span(5.0)).unwrap();
+
+        layout.update_state_subset(&mut state, &mut image_cache, &timer.snapshot(), &[3, 0]);
+        assert_eq!(state.components.len(), 1);
+        assert_eq!(time(&state, 0), "5");
+    }
 }
@@ -12,26 +12,26 @@ pub fn settings(reader: &mut Reader, component: &mut Component) -> Result<()> {
     parse_children(reader, |reader, tag, _| {
         match tag.name() {
             "Height" => text_parsed(reader, |v| settings.height = translate_size(v)),
-            "BehindGraphColor" => color(reader, |c| settings.behind_background_color = c),
-            "AheadGraphColor" => color(reader, |c| settings.ahead_background_color = c),
-            "GridlinesColor" => color(reader, |c| settings.grid_lines_color = c),
+            "BehindGraphColor" => color(reader, |c| settings.behind_background_color = c.into()),
+            "AheadGraphColor" => color(reader, |c| settings.ahead_background_color = c.into()),
+            "GridlinesColor" => color(reader, |c| settings.grid_lines_color = c.into()),
             "PartialFillColorAhead" => {
                 // Version >= 1.2
-                color(reader, |c| settings.partial_fill_color = c)
+                color(reader, |c| settings.partial_fill_color = c.into())
             }
             "CompleteFillColorAhead" => {
                 // Version >= 1.2
-                color(reader, |c| settings.complete_fill_color = c)
+                color(reader, |c| settings.complete_fill_color = c.into())
             }
             "PartialFillColor" => {
                 // Version < 1.2
-                color(reader, |c| settings.partial_fill_color = c)
+                color(reader, |c| settings.partial_fill_color = c.into())
             }
             "CompleteFillColor" => {
                 // Version < 1.2
-                color(reader, |c| settings.complete_fill_color = c)
+                color(reader, |c| settings.complete_fill_color = c.into())
             }
-            "GraphColor" => color(reader, |c| settings.graph_lines_color = c),
+            "GraphColor" => color(reader, |c| settings.graph_lines_color = c.into()),
             "LiveGraph" => parse_bool(reader, |b| settings.live_graph = b),
             "FlipGraph" => parse_bool(reader, |b| settings.flip_graph = b),
             "Comparison" => comparison_override(reader, |v| settings.comparison_override = v),
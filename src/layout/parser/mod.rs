@@ -2,7 +2,7 @@
 
 use super::{Component, Layout, LayoutDirection};
 use crate::{
-    component::{separator, timer::DeltaGradient},
+    component::{placeholder, separator, timer::DeltaGradient},
     platform::{math::f32::stable_powf, prelude::*},
     settings::{
         Alignment, BackgroundImage, Color, Font, FontStretch, FontStyle, FontWeight, Gradient,
@@ -13,9 +13,9 @@ use crate::{
         formatter::{Accuracy, DigitsFormat},
     },
     util::xml::{
-        Reader,
+        Reader, Text,
         helper::{
-            Error as XmlError, end_tag, image, parse_base, parse_children, text,
+            Error as XmlError, end_tag, image, parse_base, parse_children, reencode_children, text,
             text_as_escaped_string_err, text_parsed,
         },
     },
@@ -46,7 +46,7 @@ mod font_resolving;
 // 1.0 units high in component space.
 // 24 pixels high in LiveSplit One's pixel coordinate space.
 // ~30.5 pixels high in the original LiveSplit's pixel coordinate space.
-const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
+pub(super) const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
 
 fn translate_size(v: u32) -> u32 {
     (v as f32 * PIXEL_SPACE_RATIO + 0.5) as u32
@@ -310,26 +310,32 @@ where
         let number = u32::from_str_radix(text, 16)?;
         let [a, r, g, b] = number.to_be_bytes();
         let mut color = Color::rgba8(r, g, b, a);
-        let [r, g, b, a] = color.to_array();
-
-        // Adjust alpha based on the lightness of the color. The formula is
-        // based on two sRGB curves measured for white on top of a black
-        // background and for black on top of a white background. We interpolate
-        // between the two curves based on the lightness of the color. The
-        // problem is that we only have the foreground color, so based on the
-        // actual background color, this may be wrong. Therefore this is only a
-        // heuristic. We often have white on dark grey, instead of white on
-        // black. Because of that, we use 1.75 as the exponent denominator for
-        // the white on black case instead of the usual 2.2 for sRGB.
-        let lightness = (r + g + b) * (1.0 / 3.0);
-        color.alpha = (1.0 - lightness) * (1.0 - stable_powf(1.0 - a, 1.0 / 2.2))
-            + lightness * stable_powf(a, 1.0 / 1.75);
+        color.alpha = adjusted_alpha(color);
 
         func(color);
         Ok(())
     })
 }
 
+/// Adjusts the alpha of a color stored in a layout file of the original
+/// LiveSplit to the alpha that livesplit-core uses for it.
+pub(super) fn adjusted_alpha(color: Color) -> f32 {
+    let [r, g, b, a] = color.to_array();
+
+    // Adjust alpha based on the lightness of the color. The formula is
+    // based on two sRGB curves measured for white on top of a black
+    // background and for black on top of a white background. We interpolate
+    // between the two curves based on the lightness of the color. The
+    // problem is that we only have the foreground color, so based on the
+    // actual background color, this may be wrong. Therefore this is only a
+    // heuristic. We often have white on dark grey, instead of white on
+    // black. Because of that, we use 1.75 as the exponent denominator for
+    // the white on black case instead of the usual 2.2 for sRGB.
+    let lightness = (r + g + b) * (1.0 / 3.0);
+    (1.0 - lightness) * (1.0 - stable_powf(1.0 - a, 1.0 / 2.2))
+        + lightness * stable_powf(a, 1.0 / 1.75)
+}
+
 fn percentage<F>(reader: &mut Reader, func: F) -> Result<()>
 where
     F: FnOnce(f32),
@@ -537,6 +543,7 @@ where
             style,
             weight,
             stretch,
+            data: None,
         });
         Ok(())
     })
@@ -659,7 +666,9 @@ where
                     "LiveSplit.Timer.dll" => timer::Component::new().into(),
                     "LiveSplit.Title.dll" => title::Component::new().into(),
                     "LiveSplit.TotalPlaytime.dll" => total_playtime::Component::new().into(),
-                    _ => return Ok(()),
+                    // Components that aren't supported are kept around, so
+                    // they don't get lost when the layout is saved again.
+                    _ => placeholder::Component::new(Text::new_escaped(text).unescape_str()).into(),
                 });
                 Ok(())
             }),
@@ -675,11 +684,17 @@ where
                         Component::DetailedTimer(c) => detailed_timer::settings(reader, c),
                         Component::Graph(c) => graph::settings(reader, c),
                         Component::PbChance(c) => pb_chance::settings(reader, c),
+                        Component::Placeholder(c) => {
+                            reencode_children(reader, &mut c.settings_mut().xml_settings)
+                                .map_err(Into::into)
+                        }
                         Component::ResetChance(c) => reset_chance::settings(reader, c),
                         Component::PossibleTimeSave(c) => possible_time_save::settings(reader, c),
                         Component::PreviousSegment(c) => previous_segment::settings(reader, c),
                         Component::SegmentTime(_) => end_tag(reader),
                         Component::Separator(_) => end_tag(reader),
+                        Component::Tabs(_) => end_tag(reader),
+                        Component::OverlayWidgets(_) => end_tag(reader),
                         Component::Splits(c) => splits::settings(reader, c),
                         Component::SumOfBest(c) => sum_of_best::settings(reader, c),
                         Component::Text(c) => text::settings(reader, c),
@@ -812,7 +827,11 @@ fn parse_general_settings(layout: &mut Layout, reader: &mut Reader) -> Result<()
 
 /// Attempts to parse a layout file of the original LiveSplit. They are only
 /// parsed on a best effort basis, so if something isn't supported by
-/// livesplit-core, then it will be parsed without that option.
+/// livesplit-core, then it will be parsed without that option. Components that
+/// aren't supported at all are kept as a
+/// [`Placeholder`](crate::component::placeholder) that remembers their
+/// settings, so that they are still part of the layout when it is saved as a
+/// layout of the original LiveSplit again.
 pub fn parse(source: &str) -> Result<Layout> {
     let reader = &mut Reader::new(source);
 
@@ -838,7 +857,7 @@ pub fn parse(source: &str) -> Result<Layout> {
         })
     })?;
 
-    if layout.components.is_empty() {
+    if layout.components().is_empty() {
         Err(Error::Empty)
     } else {
         Ok(layout)
@@ -149,6 +149,7 @@ pub fn settings(reader: &mut Reader, component: &mut Component) -> Result<()> {
                                         update_trigger: ColumnUpdateTrigger::OnEndingSegment,
                                         comparison_override: comparison_override.clone(),
                                         timing_method: None,
+                                        show_frames: false,
                                     }),
                                 });
                                 settings.columns.push(ColumnSettings {
@@ -159,6 +160,7 @@ pub fn settings(reader: &mut Reader, component: &mut Component) -> Result<()> {
                                         update_trigger: ColumnUpdateTrigger::Contextual,
                                         comparison_override,
                                         timing_method: None,
+                                        show_frames: false,
                                     }),
                                 });
                             }
@@ -0,0 +1,12 @@
+use super::{background, display_tag, gradient_parts, translate_size, version};
+use crate::{component::blank_space::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.7")?;
+    display_tag(writer, "SpaceHeight", translate_size(settings.size))?;
+    display_tag(writer, "SpaceWidth", translate_size(settings.size))?;
+    background(writer, gradient_parts(settings.background))
+}
@@ -0,0 +1,86 @@
+use super::{
+    accuracy, background, bool_tag, color, comparison_override, delta_gradient_parts, display_tag,
+    timer_format, timing_method_override, translate_size, version,
+};
+use crate::{component::detailed_timer::Component, settings::Color, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+    let default_color = Color::rgba8(0xAA, 0xAA, 0xAA, 0xFF);
+
+    let total_height = settings.timer.height + settings.segment_timer.height;
+    let segment_timer_ratio = if total_height != 0 {
+        (100.0 * settings.segment_timer.height as f32 / total_height as f32 + 0.5) as u32
+    } else {
+        40
+    };
+
+    version(writer, "1.5")?;
+    display_tag(writer, "Height", translate_size(total_height))?;
+    display_tag(writer, "Width", 200)?;
+    display_tag(writer, "SegmentTimerSizeRatio", segment_timer_ratio)?;
+    bool_tag(writer, "TimerShowGradient", settings.timer.show_gradient)?;
+    bool_tag(
+        writer,
+        "OverrideTimerColors",
+        settings.timer.color_override.is_some(),
+    )?;
+    bool_tag(
+        writer,
+        "SegmentTimerShowGradient",
+        settings.segment_timer.show_gradient,
+    )?;
+    timer_format(
+        writer,
+        "TimerFormat",
+        settings.timer.digits_format,
+        settings.timer.accuracy,
+    )?;
+    timer_format(
+        writer,
+        "SegmentTimerFormat",
+        settings.segment_timer.digits_format,
+        settings.segment_timer.accuracy,
+    )?;
+    accuracy(
+        writer,
+        "SegmentTimesAccuracy",
+        settings.comparison_times_accuracy,
+    )?;
+    color(
+        writer,
+        "TimerColor",
+        settings.timer.color_override.unwrap_or(default_color),
+    )?;
+    color(
+        writer,
+        "SegmentTimerColor",
+        settings
+            .segment_timer
+            .color_override
+            .unwrap_or(default_color),
+    )?;
+    color(
+        writer,
+        "SegmentLabelsColor",
+        settings.comparison_names_color.unwrap_or_else(Color::white),
+    )?;
+    color(
+        writer,
+        "SegmentTimesColor",
+        settings.comparison_times_color.unwrap_or_else(Color::white),
+    )?;
+    bool_tag(writer, "DisplayIcon", settings.display_icon)?;
+    bool_tag(writer, "ShowSplitName", settings.show_segment_name)?;
+    color(
+        writer,
+        "SplitNameColor",
+        settings.segment_name_color.unwrap_or_else(Color::white),
+    )?;
+    background(writer, delta_gradient_parts(settings.background))?;
+    comparison_override(writer, "Comparison", settings.comparison1.as_deref())?;
+    comparison_override(writer, "Comparison2", settings.comparison2.as_deref())?;
+    bool_tag(writer, "HideComparison", settings.hide_second_comparison)?;
+    timing_method_override(writer, "TimingMethod", settings.timer.timing_method)
+}
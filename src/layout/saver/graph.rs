@@ -0,0 +1,63 @@
+use super::{bool_tag, color, comparison_override, display_tag, translate_size, version};
+use crate::{component::graph::Component, layout::GeneralSettings, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    component: &Component,
+    layout_settings: &GeneralSettings,
+) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.5")?;
+    display_tag(writer, "Height", translate_size(settings.height))?;
+    display_tag(writer, "Width", 180)?;
+    color(
+        writer,
+        "BehindGraphColor",
+        settings.behind_background_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "AheadGraphColor",
+        settings.ahead_background_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "GridlinesColor",
+        settings.grid_lines_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "PartialFillColorBehind",
+        settings.partial_fill_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "CompleteFillColorBehind",
+        settings.complete_fill_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "PartialFillColorAhead",
+        settings.partial_fill_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "CompleteFillColorAhead",
+        settings.complete_fill_color.resolve(layout_settings),
+    )?;
+    color(
+        writer,
+        "GraphColor",
+        settings.graph_lines_color.resolve(layout_settings),
+    )?;
+    bool_tag(writer, "LiveGraph", settings.live_graph)?;
+    bool_tag(writer, "FlipGraph", settings.flip_graph)?;
+    comparison_override(
+        writer,
+        "Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool_tag(writer, "ShowBestSegments", settings.show_best_segments)
+}
@@ -0,0 +1,635 @@
+//! Provides the saver for layout files of the original LiveSplit (*.lsl). It is
+//! the inverse of the [`parser`](super::parser). The settings of the components
+//! are mapped back to the settings of their counterparts in the original
+//! LiveSplit where possible. Settings that can't be represented, as well as
+//! components that the original LiveSplit doesn't have, such as the Segment
+//! Time Component, are left out.
+//!
+//! # Examples
+//!
+//! Using the saver to save a layout as a layout file of the original LiveSplit.
+//!
+//! ```no_run
+//! use livesplit_core::layout::{saver, Layout};
+//! use livesplit_core::run::saver::livesplit::IoWrite;
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! let layout = Layout::default_layout();
+//!
+//! // Create the layout file.
+//! let file = File::create("path/to/layout_file.lsl");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the layout as a layout file of the original LiveSplit.
+//! saver::save_layout(&layout, IoWrite(writer)).expect("Couldn't save the layout file");
+//! ```
+
+use super::{
+    Component, GeneralSettings, Layout, LayoutDirection,
+    parser::{PIXEL_SPACE_RATIO, adjusted_alpha},
+};
+use crate::{
+    component::{placeholder, timer::DeltaGradient},
+    platform::prelude::*,
+    run::saver::livesplit::{LSS_IMAGE_HEADER, image},
+    settings::{
+        Color, Font, FontStretch, FontStyle, FontWeight, Gradient, LayoutBackground, ListGradient,
+    },
+    timing::{
+        TimingMethod,
+        formatter::{Accuracy, DigitsFormat},
+    },
+    util::xml::{DisplayAlreadyEscaped, NO_ATTRIBUTES, Text, Writer},
+};
+use alloc::borrow::Cow;
+use core::{fmt, mem::MaybeUninit};
+
+mod blank_space;
+mod current_comparison;
+mod current_pace;
+mod delta;
+mod detailed_timer;
+mod graph;
+mod pb_chance;
+mod possible_time_save;
+mod previous_segment;
+mod reset_chance;
+mod splits;
+mod sum_of_best;
+mod text;
+mod timer;
+mod title;
+mod total_playtime;
+
+const LAYOUT_VERSION: &str = "1.6.1";
+
+/// The beginning of every serialized `System.Drawing.Font`. It covers the
+/// record header and the class definition, up to the object id of the font's
+/// name. This is the prefix that the parser skips as well.
+const FONT_HEADER: &str = "AAEAAAD/////AQAAAAAAAAAMAgAAAFFTeXN0ZW0uRHJhd2luZywgVmVyc2lvbj00LjAuMC4wLCBDdWx0dXJlPW5ldXRyYWwsIFB1YmxpY0tleVRva2VuPWIwM2Y1ZjdmMTFkNTBhM2EFAQAAABNTeXN0ZW0uRHJhd2luZy5Gb250BAAAAAROYW1lBFNpemUFU3R5bGUEVW5pdAEABAQLGFN5c3RlbS5EcmF3aW5nLkZvbnRTdHlsZQIAAAAbU3lzdGVtLkRyYXdpbmcuR3JhcGhpY3NVbml0AgAAAAIAAAAGAwAA";
+const FONT_STYLE_RECORD: &[u8] =
+    b"\x05\xFC\xFF\xFF\xFF\x18System.Drawing.FontStyle\x01\x00\x00\x00\x07value__\x00\x08\x02\x00\x00\x00";
+const GRAPHICS_UNIT_RECORD: &[u8] =
+    b"\x05\xFB\xFF\xFF\xFF\x1BSystem.Drawing.GraphicsUnit\x01\x00\x00\x00\x07value__\x00\x08\x02\x00\x00\x00";
+
+/// The font the original LiveSplit uses for one of the layout wide fonts, if
+/// livesplit-core is meant to use its default font.
+struct DefaultFont {
+    family: &'static str,
+    bold: bool,
+    size: f32,
+    /// The `System.Drawing.GraphicsUnit` of the size.
+    unit: u8,
+}
+
+const TIMER_FONT: DefaultFont = DefaultFont {
+    family: "Calibri",
+    bold: true,
+    size: 43.75,
+    unit: 2,
+};
+
+const TIMES_FONT: DefaultFont = DefaultFont {
+    family: "Segoe UI",
+    bold: true,
+    size: 12.0,
+    unit: 3,
+};
+
+const TEXT_FONT: DefaultFont = DefaultFont {
+    family: "Segoe UI",
+    bold: false,
+    size: 12.0,
+    unit: 3,
+};
+
+const fn bool(value: bool) -> Text<'static> {
+    Text::new_escaped(if value { "True" } else { "False" })
+}
+
+fn translate_size(v: u32) -> u32 {
+    (v as f32 / PIXEL_SPACE_RATIO + 0.5) as u32
+}
+
+fn version<W: fmt::Write>(writer: &mut Writer<W>, version: &str) -> fmt::Result {
+    writer.tag_with_text_content("Version", NO_ATTRIBUTES, Text::new_escaped(version))
+}
+
+fn bool_tag<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, value: bool) -> fmt::Result {
+    writer.tag_with_text_content(tag, NO_ATTRIBUTES, bool(value))
+}
+
+fn display_tag<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    value: impl fmt::Display,
+) -> fmt::Result {
+    writer.tag_with_text_content(tag, NO_ATTRIBUTES, DisplayAlreadyEscaped(value))
+}
+
+fn color<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, color: Color) -> fmt::Result {
+    let [r, g, b, _] = color.to_rgba8();
+    let alpha = |a| adjusted_alpha(Color::rgba8(r, g, b, a));
+
+    // The parser adjusts the alpha based on the lightness of the color. The
+    // adjustment is monotonic, so we can look for the alpha that results in
+    // the closest adjusted alpha by bisection.
+    let (mut low, mut high) = (0u8, u8::MAX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if alpha(mid) < color.alpha {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    let a = if low > 0 && color.alpha - alpha(low - 1) < alpha(low) - color.alpha {
+        low - 1
+    } else {
+        low
+    };
+
+    display_tag(writer, tag, format_args!("{a:02X}{r:02X}{g:02X}{b:02X}"))
+}
+
+/// Writes a color that is only used if the override flag is set. If there is
+/// no color, the flag is not set and white is written in its place.
+fn override_color<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    override_tag: &str,
+    value: Option<Color>,
+) -> fmt::Result {
+    color(writer, tag, value.unwrap_or_else(Color::white))?;
+    bool_tag(writer, override_tag, value.is_some())
+}
+
+fn gradient_parts(gradient: Gradient) -> (&'static str, Color, Color) {
+    match gradient {
+        Gradient::Transparent => ("Plain", Color::transparent(), Color::transparent()),
+        Gradient::Plain(c) => ("Plain", c, Color::transparent()),
+        Gradient::Vertical(top, bottom) => ("Vertical", top, bottom),
+        Gradient::Horizontal(left, right) => ("Horizontal", left, right),
+        Gradient::Angled(start, end, angle) => {
+            // The original LiveSplit only supports vertical and horizontal
+            // gradients, so we save the closest of the two.
+            if (45.0..135.0).contains(&(angle % 180.0).abs()) {
+                ("Horizontal", start, end)
+            } else {
+                ("Vertical", start, end)
+            }
+        }
+        // Most of a radial gradient is close to its edge color, which makes it
+        // the best plain color to fall back to. Conic gradients sweep from one
+        // color to the other, just like a linear gradient.
+        Gradient::Radial(_, edge, ..) => ("Plain", edge, Color::transparent()),
+        Gradient::Conic(start, end, ..) => ("Vertical", start, end),
+        // Animations aren't supported by the original LiveSplit either, so the
+        // gradients are saved without them.
+        Gradient::HueCycling(top, bottom, _) => ("Vertical", top, bottom),
+        Gradient::Rotating(start, end, _) => ("Vertical", start, end),
+    }
+}
+
+fn delta_gradient_parts(gradient: DeltaGradient) -> (&'static str, Color, Color) {
+    match gradient {
+        DeltaGradient::Gradient(gradient) => gradient_parts(gradient),
+        DeltaGradient::DeltaPlain => (
+            "PlainWithDeltaColor",
+            Color::transparent(),
+            Color::transparent(),
+        ),
+        DeltaGradient::DeltaVertical => (
+            "VerticalWithDeltaColor",
+            Color::transparent(),
+            Color::transparent(),
+        ),
+        DeltaGradient::DeltaHorizontal => (
+            "HorizontalWithDeltaColor",
+            Color::transparent(),
+            Color::transparent(),
+        ),
+    }
+}
+
+fn list_gradient_parts(gradient: ListGradient) -> (&'static str, Color, Color) {
+    match gradient {
+        ListGradient::Same(gradient) => gradient_parts(gradient),
+        ListGradient::Alternating(even, odd) => ("Alternating", even, odd),
+    }
+}
+
+fn gradient_with_tags<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    [tag_color1, tag_color2, tag_kind]: [&str; 3],
+    (kind, first, second): (&str, Color, Color),
+) -> fmt::Result {
+    color(writer, tag_color1, first)?;
+    color(writer, tag_color2, second)?;
+    writer.tag_with_text_content(tag_kind, NO_ATTRIBUTES, Text::new_escaped(kind))
+}
+
+fn background<W: fmt::Write>(writer: &mut Writer<W>, parts: (&str, Color, Color)) -> fmt::Result {
+    gradient_with_tags(
+        writer,
+        ["BackgroundColor", "BackgroundColor2", "BackgroundGradient"],
+        parts,
+    )
+}
+
+fn accuracy<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, accuracy: Accuracy) -> fmt::Result {
+    writer.tag_with_text_content(
+        tag,
+        NO_ATTRIBUTES,
+        Text::new_escaped(match accuracy {
+            Accuracy::Seconds => "Seconds",
+            Accuracy::Tenths => "Tenths",
+            // The original LiveSplit doesn't support showing milliseconds.
+            Accuracy::Hundredths | Accuracy::Milliseconds => "Hundredths",
+        }),
+    )
+}
+
+fn timer_format<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    digits_format: DigitsFormat,
+    accuracy: Accuracy,
+) -> fmt::Result {
+    let digits_format = match digits_format {
+        DigitsFormat::SingleDigitSeconds | DigitsFormat::DoubleDigitSeconds => "1",
+        DigitsFormat::SingleDigitMinutes | DigitsFormat::DoubleDigitMinutes => "00:01",
+        DigitsFormat::SingleDigitHours => "0:00:01",
+        DigitsFormat::DoubleDigitHours => "00:00:01",
+    };
+    let accuracy = match accuracy {
+        Accuracy::Seconds => "",
+        Accuracy::Tenths => ".2",
+        Accuracy::Hundredths | Accuracy::Milliseconds => ".23",
+    };
+    display_tag(writer, tag, format_args!("{digits_format}{accuracy}"))
+}
+
+fn comparison_override<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    comparison: Option<&str>,
+) -> fmt::Result {
+    writer.tag_with_text_content(
+        tag,
+        NO_ATTRIBUTES,
+        comparison.unwrap_or("Current Comparison"),
+    )
+}
+
+fn timing_method_override<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    timing_method: Option<TimingMethod>,
+) -> fmt::Result {
+    writer.tag_with_text_content(
+        tag,
+        NO_ATTRIBUTES,
+        Text::new_escaped(match timing_method {
+            None => "Current Timing Method",
+            Some(TimingMethod::RealTime) => "Real Time",
+            Some(TimingMethod::GameTime) => "Game Time",
+        }),
+    )
+}
+
+/// Writes a font as a serialized `System.Drawing.Font`. The original LiveSplit
+/// identifies fonts by their GDI name, so the styling that can't be expressed
+/// through the bold and italic flags is appended to the family name, the same
+/// way the parser splits it off again.
+fn font<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    font: Option<&Font>,
+    default: &DefaultFont,
+    buf: &mut Vec<u8>,
+    base64_buf: &mut Vec<MaybeUninit<u8>>,
+) -> fmt::Result {
+    let mut name = String::new();
+    let (bold, italic) = match font {
+        Some(font) => {
+            name.push_str(&font.family);
+            let weight = match font.weight {
+                FontWeight::Thin => "Thin",
+                FontWeight::ExtraLight => "ExtraLight",
+                FontWeight::Light => "Light",
+                FontWeight::SemiLight => "SemiLight",
+                FontWeight::Normal | FontWeight::Bold => "",
+                FontWeight::Medium => "Medium",
+                FontWeight::SemiBold => "SemiBold",
+                FontWeight::ExtraBold => "ExtraBold",
+                FontWeight::Black => "Black",
+                FontWeight::ExtraBlack => "ExtraBlack",
+            };
+            let stretch = match font.stretch {
+                FontStretch::UltraCondensed => "UltraCondensed",
+                FontStretch::ExtraCondensed => "ExtraCondensed",
+                FontStretch::Condensed => "Condensed",
+                FontStretch::SemiCondensed => "SemiCondensed",
+                FontStretch::Normal => "",
+                FontStretch::SemiExpanded => "SemiExpanded",
+                FontStretch::Expanded => "Expanded",
+                FontStretch::ExtraExpanded => "ExtraExpanded",
+                FontStretch::UltraExpanded => "UltraExpanded",
+            };
+            for token in [weight, stretch] {
+                if !token.is_empty() {
+                    name.push(' ');
+                    name.push_str(token);
+                }
+            }
+            (
+                font.weight == FontWeight::Bold,
+                font.style != FontStyle::Normal,
+            )
+        }
+        None => {
+            name.push_str(default.family);
+            (default.bold, false)
+        }
+    };
+
+    // The header ends right before the last byte of the name's object id,
+    // which is where we continue, so the rest can be encoded separately.
+    buf.clear();
+    buf.push(0);
+    let mut len = name.len();
+    while len >= 0x80 {
+        buf.push(len as u8 | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(name.as_bytes());
+    buf.extend_from_slice(&default.size.to_le_bytes());
+    buf.extend_from_slice(FONT_STYLE_RECORD);
+    buf.extend_from_slice(&u32::from((bold as u8) | ((italic as u8) << 1)).to_le_bytes());
+    buf.extend_from_slice(GRAPHICS_UNIT_RECORD);
+    buf.extend_from_slice(&u32::from(default.unit).to_le_bytes());
+    buf.push(0xB);
+
+    base64_buf.resize(
+        base64_simd::STANDARD.encoded_length(buf.len()),
+        MaybeUninit::uninit(),
+    );
+    let encoded =
+        base64_simd::STANDARD.encode_as_str(buf, base64_simd::Out::from_uninit_slice(base64_buf));
+
+    writer.tag_with_content(tag, NO_ATTRIBUTES, |writer| {
+        writer.cdata(DisplayAlreadyEscaped(format_args!(
+            "{FONT_HEADER}{encoded}"
+        )))
+    })
+}
+
+fn general_settings<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    settings: &GeneralSettings,
+) -> fmt::Result {
+    let (background_type, first, second) = match &settings.background {
+        LayoutBackground::Gradient(gradient) => match gradient_parts(*gradient) {
+            ("Vertical", top, bottom) => ("VerticalGradient", top, bottom),
+            ("Horizontal", left, right) => ("HorizontalGradient", left, right),
+            (_, plain, _) => ("SolidColor", plain, Color::transparent()),
+        },
+        LayoutBackground::Image(_) => ("Image", Color::transparent(), Color::transparent()),
+    };
+
+    let mut buf = Vec::new();
+    let mut base64_buf = Vec::new();
+
+    color(writer, "TextColor", settings.text_color)?;
+    color(writer, "BackgroundColor", first)?;
+    color(writer, "BackgroundColor2", second)?;
+    color(
+        writer,
+        "ThinSeparatorsColor",
+        settings.thin_separators_color,
+    )?;
+    color(writer, "SeparatorsColor", settings.separators_color)?;
+    color(writer, "PersonalBestColor", settings.personal_best_color)?;
+    color(
+        writer,
+        "AheadGainingTimeColor",
+        settings.ahead_gaining_time_color,
+    )?;
+    color(
+        writer,
+        "AheadLosingTimeColor",
+        settings.ahead_losing_time_color,
+    )?;
+    color(
+        writer,
+        "BehindGainingTimeColor",
+        settings.behind_gaining_time_color,
+    )?;
+    color(
+        writer,
+        "BehindLosingTimeColor",
+        settings.behind_losing_time_color,
+    )?;
+    color(writer, "BestSegmentColor", settings.best_segment_color)?;
+    bool_tag(writer, "UseRainbowColor", false)?;
+    color(writer, "NotRunningColor", settings.not_running_color)?;
+    color(writer, "PausedColor", settings.paused_color)?;
+    color(writer, "TextOutlineColor", Color::transparent())?;
+    color(
+        writer,
+        "ShadowsColor",
+        settings
+            .text_shadow
+            .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+    )?;
+    font(
+        writer,
+        "TimesFont",
+        settings.times_font.as_ref(),
+        &TIMES_FONT,
+        &mut buf,
+        &mut base64_buf,
+    )?;
+    font(
+        writer,
+        "TimerFont",
+        settings.timer_font.as_ref(),
+        &TIMER_FONT,
+        &mut buf,
+        &mut base64_buf,
+    )?;
+    font(
+        writer,
+        "TextFont",
+        settings.text_font.as_ref(),
+        &TEXT_FONT,
+        &mut buf,
+        &mut base64_buf,
+    )?;
+    bool_tag(writer, "AlwaysOnTop", true)?;
+    bool_tag(writer, "ShowBestSegments", true)?;
+    bool_tag(writer, "AntiAliasing", true)?;
+    bool_tag(writer, "DropShadows", settings.text_shadow.is_some())?;
+    writer.tag_with_text_content(
+        "BackgroundType",
+        NO_ATTRIBUTES,
+        Text::new_escaped(background_type),
+    )?;
+
+    match &settings.background {
+        LayoutBackground::Image(background_image) => {
+            let image_buf = &mut Cow::Borrowed(&LSS_IMAGE_HEADER[..]);
+            image(
+                writer,
+                "BackgroundImage",
+                &background_image.image,
+                &mut base64_buf,
+                image_buf,
+            )?;
+            // The parser interprets the opacity as the brightness, as that's
+            // what it's used for in practice.
+            display_tag(writer, "ImageOpacity", background_image.brightness)?;
+            display_tag(writer, "ImageBlur", background_image.blur)?;
+        }
+        LayoutBackground::Gradient(_) => {
+            writer.empty_tag("BackgroundImage", NO_ATTRIBUTES)?;
+            display_tag(writer, "ImageOpacity", 1)?;
+            display_tag(writer, "ImageBlur", 0)?;
+        }
+    }
+
+    display_tag(writer, "Opacity", 1)
+}
+
+fn component<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    component: &Component,
+    layout_settings: &GeneralSettings,
+) -> fmt::Result {
+    let path = match component {
+        Component::BlankSpace(_) => "LiveSplit.BlankSpace.dll",
+        Component::CurrentComparison(_) => "LiveSplit.CurrentComparison.dll",
+        Component::CurrentPace(_) => "LiveSplit.RunPrediction.dll",
+        Component::Delta(_) => "LiveSplit.Delta.dll",
+        Component::DetailedTimer(_) => "LiveSplit.DetailedTimer.dll",
+        Component::Graph(_) => "LiveSplit.Graph.dll",
+        // The original LiveSplit doesn't have an Overlay Widgets Component.
+        Component::OverlayWidgets(_) => return Ok(()),
+        Component::PbChance(_) => "PBChance.dll",
+        Component::Placeholder(c) => return placeholder(writer, c),
+        Component::PossibleTimeSave(_) => "LiveSplit.PossibleTimeSave.dll",
+        Component::PreviousSegment(_) => "LiveSplit.PreviousSegment.dll",
+        Component::ResetChance(_) => "LiveSplit.ResetChance.dll",
+        // The original LiveSplit doesn't have a Segment Time Component.
+        Component::SegmentTime(_) => return Ok(()),
+        Component::Separator(_) => "",
+        Component::Splits(_) => "LiveSplit.Splits.dll",
+        Component::SumOfBest(_) => "LiveSplit.SumOfBest.dll",
+        // The original LiveSplit doesn't have tabs, so only the components of
+        // the selected tab are saved in place of the Tabs Component.
+        Component::Tabs(c) => {
+            for tab_component in c.selected_tab().into_iter().flat_map(|t| &t.components) {
+                self::component(writer, tab_component, layout_settings)?;
+            }
+            return Ok(());
+        }
+        Component::Text(_) => "LiveSplit.Text.dll",
+        Component::Timer(_) => "LiveSplit.Timer.dll",
+        Component::Title(_) => "LiveSplit.Title.dll",
+        Component::TotalPlaytime(_) => "LiveSplit.TotalPlaytime.dll",
+    };
+
+    writer.tag_with_content("Component", NO_ATTRIBUTES, |writer| {
+        writer.tag_with_text_content("Path", NO_ATTRIBUTES, Text::new_escaped(path))?;
+        if let Component::Separator(_) = component {
+            return writer.empty_tag("Settings", NO_ATTRIBUTES);
+        }
+        writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| match component {
+            Component::BlankSpace(c) => blank_space::settings(writer, c),
+            Component::CurrentComparison(c) => current_comparison::settings(writer, c),
+            Component::CurrentPace(c) => current_pace::settings(writer, c),
+            Component::Delta(c) => delta::settings(writer, c),
+            Component::DetailedTimer(c) => detailed_timer::settings(writer, c),
+            Component::Graph(c) => graph::settings(writer, c, layout_settings),
+            Component::PbChance(c) => pb_chance::settings(writer, c),
+            Component::PossibleTimeSave(c) => possible_time_save::settings(writer, c),
+            Component::PreviousSegment(c) => previous_segment::settings(writer, c),
+            Component::ResetChance(c) => reset_chance::settings(writer, c),
+            Component::Splits(c) => splits::settings(writer, c),
+            Component::SumOfBest(c) => sum_of_best::settings(writer, c),
+            Component::Text(c) => text::settings(writer, c),
+            Component::Timer(c) => timer::settings(writer, c),
+            Component::Title(c) => title::settings(writer, c),
+            Component::TotalPlaytime(c) => total_playtime::settings(writer, c),
+            Component::OverlayWidgets(_)
+            | Component::Placeholder(_)
+            | Component::SegmentTime(_)
+            | Component::Separator(_)
+            | Component::Tabs(_) => Ok(()),
+        })
+    })
+}
+
+// Writes the component of the original LiveSplit that the placeholder stands in
+// for, exactly the way it was stored in the layout file it was parsed from.
+fn placeholder<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    component: &placeholder::Component,
+) -> fmt::Result {
+    let settings = component.settings();
+    writer.tag_with_content("Component", NO_ATTRIBUTES, |writer| {
+        writer.tag_with_text_content("Path", NO_ATTRIBUTES, settings.path.as_str())?;
+        writer.tag_with_text_content(
+            "Settings",
+            NO_ATTRIBUTES,
+            Text::new_escaped(&settings.xml_settings),
+        )
+    })
+}
+
+/// Saves a layout as a layout file of the original LiveSplit. Only the
+/// settings that the original LiveSplit has an equivalent for are saved, so
+/// parsing the layout file again may result in a slightly different layout.
+pub fn save_layout<W: fmt::Write>(layout: &Layout, writer: W) -> fmt::Result {
+    let writer = &mut Writer::new_with_default_header(writer)?;
+
+    writer.tag_with_content(
+        "Layout",
+        [("version", Text::new_escaped(LAYOUT_VERSION))],
+        |writer| {
+            let settings = layout.general_settings();
+            writer.tag_with_text_content(
+                "Mode",
+                NO_ATTRIBUTES,
+                Text::new_escaped(match settings.direction {
+                    LayoutDirection::Vertical => "Vertical",
+                    LayoutDirection::Horizontal => "Horizontal",
+                }),
+            )?;
+
+            // The position and the size of the window are not part of the
+            // layout in livesplit-core, so the original LiveSplit is left to
+            // choose them.
+            display_tag(writer, "X", 0)?;
+            display_tag(writer, "Y", 0)?;
+            display_tag(writer, "VerticalWidth", -1)?;
+            display_tag(writer, "VerticalHeight", -1)?;
+            display_tag(writer, "HorizontalWidth", -1)?;
+            display_tag(writer, "HorizontalHeight", -1)?;
+
+            writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| {
+                general_settings(writer, settings)
+            })?;
+
+            writer.tag_with_content("Components", NO_ATTRIBUTES, |writer| {
+                for c in layout.components() {
+                    component(writer, c, settings)?;
+                }
+                Ok(())
+            })
+        },
+    )
+}
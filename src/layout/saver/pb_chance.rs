@@ -0,0 +1,8 @@
+use super::version;
+use crate::{component::pb_chance::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, _: &Component) -> fmt::Result {
+    // None of the settings have an equivalent in the original component.
+    version(writer, "0.1")
+}
@@ -0,0 +1,32 @@
+use super::{
+    accuracy, background, bool_tag, comparison_override, gradient_parts, override_color, version,
+};
+use crate::{component::possible_time_save::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.5")?;
+    override_color(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    override_color(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.value_color,
+    )?;
+    accuracy(writer, "Accuracy", settings.accuracy)?;
+    background(writer, gradient_parts(settings.background))?;
+    comparison_override(
+        writer,
+        "Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool_tag(writer, "Display2Rows", settings.display_two_rows)?;
+    bool_tag(writer, "TotalTimeSave", settings.total_possible_time_save)
+}
@@ -0,0 +1,32 @@
+use super::{
+    accuracy, background, bool_tag, comparison_override, gradient_parts, override_color, version,
+};
+use crate::{component::previous_segment::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.6")?;
+    override_color(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    background(writer, gradient_parts(settings.background))?;
+    accuracy(writer, "DeltaAccuracy", settings.accuracy)?;
+    bool_tag(writer, "DropDecimals", settings.drop_decimals)?;
+    comparison_override(
+        writer,
+        "Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool_tag(writer, "Display2Rows", settings.display_two_rows)?;
+    bool_tag(
+        writer,
+        "ShowPossibleTimeSave",
+        settings.show_possible_time_save,
+    )?;
+    accuracy(writer, "TimeSaveAccuracy", settings.accuracy)
+}
@@ -0,0 +1,99 @@
+use super::{
+    accuracy, background, bool_tag, comparison_override, display_tag, gradient_parts,
+    gradient_with_tags, list_gradient_parts, timing_method_override, version,
+};
+use crate::{
+    component::splits::{ColumnKind, ColumnStartWith, ColumnUpdateWith, Component},
+    util::xml::{NO_ATTRIBUTES, Text, Writer},
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.6")?;
+    gradient_with_tags(
+        writer,
+        [
+            "CurrentSplitTopColor",
+            "CurrentSplitBottomColor",
+            "CurrentSplitGradient",
+        ],
+        gradient_parts(settings.current_split_gradient),
+    )?;
+    display_tag(writer, "VisualSplitCount", settings.visual_split_count)?;
+    display_tag(writer, "SplitPreviewCount", settings.split_preview_count)?;
+    bool_tag(writer, "DisplayIcons", true)?;
+    bool_tag(writer, "ShowThinSeparators", settings.show_thin_separators)?;
+    bool_tag(
+        writer,
+        "AlwaysShowLastSplit",
+        settings.always_show_last_split,
+    )?;
+    display_tag(writer, "SplitWidth", 20)?;
+    accuracy(writer, "SplitTimesAccuracy", settings.split_time_accuracy)?;
+    bool_tag(writer, "ShowBlankSplits", settings.fill_with_blank_space)?;
+    bool_tag(writer, "LockLastSplit", true)?;
+    display_tag(writer, "IconSize", 24)?;
+    bool_tag(writer, "IconShadows", true)?;
+    display_tag(writer, "SplitHeight", 3.6)?;
+    background(writer, list_gradient_parts(settings.background))?;
+    bool_tag(writer, "SeparatorLastSplit", settings.separator_last_split)?;
+    accuracy(writer, "DeltasAccuracy", settings.delta_time_accuracy)?;
+    bool_tag(writer, "DropDecimals", settings.delta_drop_decimals)?;
+    bool_tag(writer, "Display2Rows", settings.display_two_rows)?;
+    bool_tag(writer, "ShowColumnLabels", settings.show_column_labels)?;
+
+    writer.tag_with_content("Columns", NO_ATTRIBUTES, |writer| {
+        // The parser inserts every column at the front, so they need to be
+        // written in reverse.
+        for column in settings.columns.iter().rev() {
+            writer.tag_with_content("Settings", NO_ATTRIBUTES, |writer| {
+                version(writer, "1.5")?;
+                let (name, kind, comparison, timing_method) = match &column.kind {
+                    ColumnKind::Variable(variable) => {
+                        // The name of the column is also the name of the
+                        // variable in the original LiveSplit.
+                        (
+                            variable.variable_name.as_str(),
+                            "CustomVariable",
+                            None,
+                            None,
+                        )
+                    }
+                    ColumnKind::Time(time) => (
+                        column.name.as_str(),
+                        match (time.update_with, time.start_with) {
+                            (ColumnUpdateWith::Delta, _) => "Delta",
+                            (ColumnUpdateWith::SplitTime, _) => "SplitTime",
+                            (ColumnUpdateWith::DeltaWithFallback, _) => "DeltaorSplitTime",
+                            (ColumnUpdateWith::SegmentDelta, _) => "SegmentDelta",
+                            (ColumnUpdateWith::SegmentTime, _) => "SegmentTime",
+                            (ColumnUpdateWith::SegmentDeltaWithFallback, _) => {
+                                "SegmentDeltaorSegmentTime"
+                            }
+                            (ColumnUpdateWith::DontUpdate, ColumnStartWith::ComparisonTime) => {
+                                "SplitTime"
+                            }
+                            (
+                                ColumnUpdateWith::DontUpdate,
+                                ColumnStartWith::ComparisonSegmentTime,
+                            ) => "SegmentTime",
+                            (
+                                ColumnUpdateWith::DontUpdate,
+                                ColumnStartWith::Empty | ColumnStartWith::PossibleTimeSave,
+                            ) => "Delta",
+                        },
+                        time.comparison_override.as_deref(),
+                        time.timing_method,
+                    ),
+                };
+                writer.tag_with_text_content("Name", NO_ATTRIBUTES, name)?;
+                writer.tag_with_text_content("Type", NO_ATTRIBUTES, Text::new_escaped(kind))?;
+                comparison_override(writer, "Comparison", comparison)?;
+                timing_method_override(writer, "TimingMethod", timing_method)
+            })?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,41 @@
+use super::{background, bool_tag, gradient_parts, override_color, version};
+use crate::{
+    component::text::{Component, Text},
+    util::xml::{NO_ATTRIBUTES, Writer},
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    // When showing a custom variable, `Text2` is the name of the variable and
+    // `Text1` is only used to decide whether the name is shown as well.
+    let (left_center, right, custom_variable) = match &settings.text {
+        Text::Center(center) => (center.as_str(), "", false),
+        Text::Split(left, right) => (left.as_str(), right.as_str(), false),
+        Text::Variable(name, show_name) => (
+            if *show_name { name.as_str() } else { "" },
+            name.as_str(),
+            true,
+        ),
+    };
+
+    version(writer, "1.4")?;
+    override_color(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.left_center_color,
+    )?;
+    override_color(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.right_color,
+    )?;
+    background(writer, gradient_parts(settings.background))?;
+    writer.tag_with_text_content("Text1", NO_ATTRIBUTES, left_center)?;
+    writer.tag_with_text_content("Text2", NO_ATTRIBUTES, right)?;
+    bool_tag(writer, "Display2Rows", settings.display_two_rows)?;
+    bool_tag(writer, "CustomVariable", custom_variable)
+}
@@ -0,0 +1,35 @@
+use super::{
+    background, bool_tag, color, delta_gradient_parts, display_tag, timer_format,
+    timing_method_override, translate_size, version,
+};
+use crate::{component::timer::Component, settings::Color, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.5")?;
+    display_tag(writer, "TimerHeight", translate_size(settings.height))?;
+    display_tag(writer, "TimerWidth", 225)?;
+    timer_format(
+        writer,
+        "TimerFormat",
+        settings.digits_format,
+        settings.accuracy,
+    )?;
+    bool_tag(
+        writer,
+        "OverrideSplitColors",
+        settings.color_override.is_some(),
+    )?;
+    bool_tag(writer, "ShowGradient", settings.show_gradient)?;
+    color(
+        writer,
+        "TimerColor",
+        settings
+            .color_override
+            .unwrap_or(Color::rgba8(0xAA, 0xAA, 0xAA, 0xFF)),
+    )?;
+    background(writer, delta_gradient_parts(settings.background))?;
+    timing_method_override(writer, "TimingMethod", settings.timing_method)
+}
@@ -0,0 +1,43 @@
+use super::{background, bool_tag, color, display_tag, gradient_parts, version};
+use crate::{
+    component::title::Component,
+    settings::{Alignment, Color},
+    util::xml::Writer,
+};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.7.3")?;
+    bool_tag(writer, "ShowGameName", settings.show_game_name)?;
+    bool_tag(writer, "ShowCategoryName", settings.show_category_name)?;
+    bool_tag(writer, "ShowAttemptCount", settings.show_attempt_count)?;
+    bool_tag(
+        writer,
+        "ShowFinishedRunsCount",
+        settings.show_finished_runs_count,
+    )?;
+    bool_tag(writer, "OverrideTitleFont", false)?;
+    bool_tag(writer, "OverrideTitleColor", settings.text_color.is_some())?;
+    bool_tag(writer, "SingleLine", settings.display_as_single_line)?;
+    color(
+        writer,
+        "TitleColor",
+        settings.text_color.unwrap_or_else(Color::white),
+    )?;
+    background(writer, gradient_parts(settings.background))?;
+    bool_tag(writer, "DisplayGameIcon", settings.display_game_icon)?;
+    bool_tag(writer, "ShowRegion", settings.show_region)?;
+    bool_tag(writer, "ShowPlatform", settings.show_platform)?;
+    bool_tag(writer, "ShowVariables", settings.show_variables)?;
+    display_tag(
+        writer,
+        "TextAlignment",
+        match settings.text_alignment {
+            Alignment::Auto => 0,
+            Alignment::Left => 1,
+            Alignment::Center => 2,
+        },
+    )
+}
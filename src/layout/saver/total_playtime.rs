@@ -0,0 +1,24 @@
+use super::{background, bool_tag, gradient_parts, override_color, version};
+use crate::{component::total_playtime::Component, util::xml::Writer};
+use core::fmt;
+
+pub fn settings<W: fmt::Write>(writer: &mut Writer<W>, component: &Component) -> fmt::Result {
+    let settings = component.settings();
+
+    version(writer, "1.6")?;
+    override_color(
+        writer,
+        "TextColor",
+        "OverrideTextColor",
+        settings.label_color,
+    )?;
+    override_color(
+        writer,
+        "TimeColor",
+        "OverrideTimeColor",
+        settings.value_color,
+    )?;
+    background(writer, gradient_parts(settings.background))?;
+    bool_tag(writer, "Display2Rows", settings.display_two_rows)?;
+    bool_tag(writer, "ShowTotalHours", !settings.show_days)
+}
@@ -0,0 +1,113 @@
+use crate::platform::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+
+/// Scaling rules describe how a [`Layout`](super::Layout) adapts to the size it
+/// is shown at. Without any rules, a renderer simply stretches the whole layout
+/// to fill the space available, so the same layout has tiny text in a narrow
+/// sidebar and huge text in a fullscreen overlay. With scaling rules, the
+/// layout is designed for a target size and the text only grows or shrinks
+/// within the limits specified. Additionally the height of the rows can be
+/// adjusted at certain breakpoints, so the layout can be more compact when it
+/// is shown in a small window and more spacious when it is shown in a large
+/// one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalingRules {
+    /// The width in pixels the layout is designed for.
+    pub target_width: f32,
+    /// The height in pixels the layout is designed for.
+    pub target_height: f32,
+    /// The smallest factor the text is allowed to be scaled by compared to the
+    /// size it has at the target size.
+    pub min_text_scale: f32,
+    /// The largest factor the text is allowed to be scaled by compared to the
+    /// size it has at the target size.
+    pub max_text_scale: f32,
+    /// The breakpoints that adjust the height of the rows based on the width
+    /// the layout is shown at. The breakpoint with the largest minimum width
+    /// that is still reached is used.
+    pub breakpoints: Vec<ScalingBreakpoint>,
+}
+
+/// A breakpoint of the [`ScalingRules`] that adjusts the height of the rows of
+/// the layout once it is shown at a certain width or wider.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalingBreakpoint {
+    /// The width in pixels starting at which the breakpoint applies.
+    pub min_width: f32,
+    /// The factor the height of the rows is scaled by. The components are
+    /// centered within the rows, so values above 1 add space between them.
+    /// Values below 1 are treated as 1.
+    pub row_height_scale: f32,
+}
+
+/// The scale factors the [`ScalingRules`] of a layout resolve to for the size
+/// the layout is currently shown at. These are part of the
+/// [`LayoutState`](super::LayoutState) and are applied by the renderer.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutScaling {
+    /// The factor to scale all the text by.
+    pub text_scale: f32,
+    /// The factor to scale the height of the components by when they are laid
+    /// out vertically. The components are centered within the additional
+    /// space.
+    pub row_height_scale: f32,
+}
+
+impl Default for ScalingRules {
+    fn default() -> Self {
+        Self {
+            target_width: 300.0,
+            target_height: 500.0,
+            min_text_scale: 0.75,
+            max_text_scale: 1.5,
+            breakpoints: Vec::new(),
+        }
+    }
+}
+
+impl Default for LayoutScaling {
+    fn default() -> Self {
+        Self {
+            text_scale: 1.0,
+            row_height_scale: 1.0,
+        }
+    }
+}
+
+impl ScalingRules {
+    /// Resolves the scale factors to use for the layout being shown at the
+    /// size in pixels provided.
+    pub fn resolve(&self, [width, height]: [f32; 2]) -> LayoutScaling {
+        let row_height_scale = self
+            .breakpoints
+            .iter()
+            .filter(|breakpoint| width >= breakpoint.min_width)
+            .max_by(|a, b| a.min_width.total_cmp(&b.min_width))
+            .map_or(1.0, |breakpoint| breakpoint.row_height_scale.max(1.0));
+
+        if !(width > 0.0 && height > 0.0 && self.target_width > 0.0 && self.target_height > 0.0) {
+            return LayoutScaling {
+                text_scale: 1.0,
+                row_height_scale,
+            };
+        }
+
+        // The renderers fit the layout to the height available, which scales
+        // everything, including the text, by this factor.
+        let height_scale = height / self.target_height;
+
+        // If the layout is narrower than designed, the text needs to shrink
+        // even more to still fit horizontally.
+        let text_scale = (width / self.target_width)
+            .min(height_scale)
+            .max(self.min_text_scale)
+            .min(self.max_text_scale);
+
+        LayoutScaling {
+            text_scale: text_scale / height_scale,
+            row_height_scale,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::platform::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+
+/// Describes which settings of a [`Layout`](super::Layout) changed since the
+/// changes were last taken. Frontends and renderers can use this to only
+/// refresh what actually changed instead of comparing entire states.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingsChanges {
+    /// Specifies whether the general settings of the layout changed.
+    pub general_settings: bool,
+    /// Specifies whether components were added, removed or moved. In that case
+    /// the indices of the components may have changed as well, so all of them
+    /// need to be considered changed.
+    pub components_rearranged: bool,
+    /// The indices of the components whose settings or appearance changed, in
+    /// ascending order. This is empty if the components got rearranged.
+    pub components: Vec<usize>,
+}
+
+impl SettingsChanges {
+    /// Checks whether no settings changed at all.
+    pub const fn is_empty(&self) -> bool {
+        !self.general_settings && !self.components_rearranged && self.components.is_empty()
+    }
+
+    /// Checks whether the settings or the appearance of the component with
+    /// the index provided changed. This is always the case if the components
+    /// got rearranged.
+    pub fn component_changed(&self, index: usize) -> bool {
+        self.components_rearranged || self.components.binary_search(&index).is_ok()
+    }
+
+    /// Encodes the changes as JSON.
+    #[cfg(feature = "std")]
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+
+    const fn everything() -> Self {
+        Self {
+            general_settings: true,
+            components_rearranged: true,
+            components: Vec::new(),
+        }
+    }
+}
+
+// Collects the changes to the settings of a layout. A new layout starts out
+// with everything being considered changed, as nobody has seen it yet.
+#[derive(Clone)]
+pub(super) struct ChangeTracker {
+    generation: u64,
+    changes: SettingsChanges,
+}
+
+impl Default for ChangeTracker {
+    fn default() -> Self {
+        Self {
+            generation: 0,
+            changes: SettingsChanges::everything(),
+        }
+    }
+}
+
+impl ChangeTracker {
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub const fn general_settings(&mut self) {
+        self.generation += 1;
+        self.changes.general_settings = true;
+    }
+
+    pub fn component(&mut self, index: usize) {
+        self.generation += 1;
+        if !self.changes.components_rearranged
+            && let Err(pos) = self.changes.components.binary_search(&index)
+        {
+            self.changes.components.insert(pos, index);
+        }
+    }
+
+    pub fn components_rearranged(&mut self) {
+        self.generation += 1;
+        self.changes.components_rearranged = true;
+        self.changes.components.clear();
+    }
+
+    // The layout got replaced by an entirely different one, such as when a
+    // change got undone. The generation keeps counting up from the generation
+    // of the layout that got replaced.
+    pub fn replaced(&mut self, generation: u64) {
+        self.generation = generation.max(self.generation) + 1;
+        self.changes = SettingsChanges::everything();
+    }
+
+    pub fn take(&mut self) -> SettingsChanges {
+        core::mem::take(&mut self.changes)
+    }
+}
@@ -0,0 +1,659 @@
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor, value::Error,
+};
+use serde_derive::Serialize;
+
+use super::ComponentState;
+use crate::platform::prelude::*;
+
+/// Describes the fields that the state object of a specific kind of component
+/// consists of. This is generated from the Rust types of the state objects, so
+/// generic renderers and tooling can adapt automatically when components gain
+/// new fields.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentStateSchema {
+    /// The kind of component. This matches the name of the variant of the
+    /// [`ComponentState`] when it gets encoded as JSON.
+    pub kind: &'static str,
+    /// The fields of the state object.
+    pub fields: Vec<FieldSchema>,
+}
+
+/// Describes a single field of a state object.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldSchema {
+    /// The name of the field, as it appears when the state gets encoded as
+    /// JSON.
+    pub name: &'static str,
+    /// The type of the field.
+    pub ty: TypeSchema,
+    /// Specifies whether the field's value may change on every frame while the
+    /// timer is running. Fields that don't update frequently usually only
+    /// change when the timer's state or the settings change. This can be used
+    /// for rendering optimizations.
+    pub updates_frequently: bool,
+}
+
+/// Describes the type of a field of a state object.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TypeSchema {
+    /// A boolean.
+    Bool,
+    /// An integer of any size.
+    Integer,
+    /// A floating point number.
+    Float,
+    /// A string of text.
+    String,
+    /// A sequence of raw bytes.
+    Bytes,
+    /// A value that carries no information.
+    Unit,
+    /// A value that may not be present.
+    Option(Box<TypeSchema>),
+    /// A list of values of the same type.
+    List(Box<TypeSchema>),
+    /// A fixed size list of values of potentially different types. Colors for
+    /// example are encoded as a tuple of their four components.
+    Tuple(Vec<TypeSchema>),
+    /// A mapping from keys to values.
+    Map(Box<TypeSchema>, Box<TypeSchema>),
+    /// A structure with named fields.
+    Struct {
+        /// The name of the structure's Rust type.
+        name: &'static str,
+        /// The fields of the structure.
+        fields: Vec<FieldSchema>,
+    },
+    /// A value that is one of several variants.
+    Enum {
+        /// The name of the enum's Rust type.
+        name: &'static str,
+        /// The names of all the variants.
+        variants: &'static [&'static str],
+    },
+    /// The type could not be determined. This is the case for self referential
+    /// types beyond a certain depth.
+    Unknown,
+}
+
+impl ComponentState {
+    /// Enumerates the schemas of the state objects of all the kinds of
+    /// components.
+    pub fn schemas() -> Vec<ComponentStateSchema> {
+        let mut ty = TypeSchema::Unknown;
+        // The probe is designed to be accepted by all the types used in the
+        // state objects. Even if it isn't, everything up until the rejection is
+        // still recorded, so there's nothing useful to do with the errors.
+        let _ = Self::deserialize(Probe {
+            out: &mut ty,
+            depth: 0,
+        });
+        let TypeSchema::Enum { variants, .. } = ty else {
+            return Vec::new();
+        };
+
+        variants
+            .iter()
+            .map(|&kind| {
+                let mut payload = TypeSchema::Unknown;
+                let _ = Self::deserialize(VariantProbe {
+                    variant: kind,
+                    out: &mut payload,
+                });
+
+                let mut fields = match payload {
+                    TypeSchema::Struct { fields, .. } => fields,
+                    _ => Vec::new(),
+                };
+                let frequently_updated = frequently_updated_fields(kind);
+                for field in &mut fields {
+                    field.updates_frequently = frequently_updated.contains(&field.name);
+                }
+
+                ComponentStateSchema { kind, fields }
+            })
+            .collect()
+    }
+}
+
+/// The fields of the state objects that may change on every frame while the
+/// timer is running. The tests ensure that all of these refer to actual fields.
+fn frequently_updated_fields(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "DetailedTimer" => &["timer", "segment_timer"],
+        "Graph" => &[
+            "points",
+            "horizontal_grid_lines",
+            "vertical_grid_lines",
+            "middle",
+        ],
+        "KeyValue" => &["value"],
+        "OverlayWidgets" => &["widgets"],
+        "Splits" => &["splits"],
+        "Tabs" => &["components"],
+        "Text" => &["text"],
+        "Timer" => &["time", "fraction"],
+        _ => &[],
+    }
+}
+
+#[cfg(feature = "std")]
+impl ComponentStateSchema {
+    /// Encodes the schema as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+/// Self referential types would otherwise cause infinite recursion.
+const MAX_DEPTH: usize = 16;
+
+/// Strings get probed with this value. Aside from arbitrary text, it is also a
+/// valid image id and a valid time.
+const STRING_PROBE: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A deserializer that doesn't deserialize any actual data, but records the
+/// type that is requested of it instead.
+struct Probe<'a> {
+    out: &'a mut TypeSchema,
+    depth: usize,
+}
+
+impl Probe<'_> {
+    const fn is_too_deep(&self) -> bool {
+        self.depth >= MAX_DEPTH
+    }
+}
+
+macro_rules! probe_primitive {
+    ($($method:ident => $visit:ident($($value:expr)?) as $ty:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                *self.out = TypeSchema::$ty;
+                visitor.$visit($($value)?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = Error;
+
+    probe_primitive! {
+        deserialize_bool => visit_bool(false) as Bool,
+        deserialize_i8 => visit_i8(0) as Integer,
+        deserialize_i16 => visit_i16(0) as Integer,
+        deserialize_i32 => visit_i32(0) as Integer,
+        deserialize_i64 => visit_i64(0) as Integer,
+        deserialize_i128 => visit_i128(0) as Integer,
+        deserialize_u8 => visit_u8(0) as Integer,
+        deserialize_u16 => visit_u16(0) as Integer,
+        deserialize_u32 => visit_u32(0) as Integer,
+        deserialize_u64 => visit_u64(0) as Integer,
+        deserialize_u128 => visit_u128(0) as Integer,
+        deserialize_f32 => visit_f32(0.0) as Float,
+        deserialize_f64 => visit_f64(0.0) as Float,
+        deserialize_char => visit_char('0') as String,
+        deserialize_str => visit_str(STRING_PROBE) as String,
+        deserialize_string => visit_str(STRING_PROBE) as String,
+        deserialize_identifier => visit_str(STRING_PROBE) as String,
+        deserialize_bytes => visit_bytes(&[]) as Bytes,
+        deserialize_byte_buf => visit_bytes(&[]) as Bytes,
+        deserialize_unit => visit_unit() as Unit,
+        deserialize_any => visit_unit() as Unknown,
+        deserialize_ignored_any => visit_unit() as Unknown,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut inner = TypeSchema::Unknown;
+        let result = if self.is_too_deep() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(Probe {
+                out: &mut inner,
+                depth: self.depth + 1,
+            })
+        };
+        *self.out = TypeSchema::Option(Box::new(inner));
+        result
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.out = TypeSchema::Unit;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut access = SeqProbe {
+            remaining: if self.is_too_deep() { 0 } else { 1 },
+            types: Vec::new(),
+            depth: self.depth,
+        };
+        let result = visitor.visit_seq(&mut access);
+        *self.out = TypeSchema::List(Box::new(access.types.pop().unwrap_or(TypeSchema::Unknown)));
+        result
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        let mut access = SeqProbe {
+            remaining: len,
+            types: Vec::with_capacity(len),
+            depth: self.depth,
+        };
+        let result = visitor.visit_seq(&mut access);
+        *self.out = TypeSchema::Tuple(access.types);
+        result
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut access = MapProbe {
+            remaining: !self.is_too_deep(),
+            key: TypeSchema::Unknown,
+            value: TypeSchema::Unknown,
+            depth: self.depth,
+        };
+        let result = visitor.visit_map(&mut access);
+        *self.out = TypeSchema::Map(Box::new(access.key), Box::new(access.value));
+        result
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut access = StructProbe {
+            names: fields,
+            fields: Vec::with_capacity(fields.len()),
+            depth: self.depth,
+        };
+        let result = visitor.visit_map(&mut access);
+        *self.out = TypeSchema::Struct {
+            name,
+            fields: access.fields,
+        };
+        result
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.out = TypeSchema::Enum { name, variants };
+        let Some(&variant) = variants.first() else {
+            return Err(de::Error::custom("enum without variants"));
+        };
+        visitor.visit_enum(EnumProbe {
+            variant,
+            depth: self.depth,
+            payload: None,
+        })
+    }
+}
+
+/// A deserializer for an enum that visits the variant provided and records the
+/// type of its payload.
+struct VariantProbe<'a> {
+    variant: &'static str,
+    out: &'a mut TypeSchema,
+}
+
+impl<'de> Deserializer<'de> for VariantProbe<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::custom("expected an enum"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(EnumProbe {
+            variant: self.variant,
+            depth: 0,
+            payload: Some(self.out),
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqProbe {
+    remaining: usize,
+    types: Vec<TypeSchema>,
+    depth: usize,
+}
+
+impl<'de> SeqAccess<'de> for &mut SeqProbe {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        let mut ty = TypeSchema::Unknown;
+        let result = seed.deserialize(Probe {
+            out: &mut ty,
+            depth: self.depth + 1,
+        });
+        self.types.push(ty);
+        result.map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct MapProbe {
+    remaining: bool,
+    key: TypeSchema,
+    value: TypeSchema,
+    depth: usize,
+}
+
+impl<'de> MapAccess<'de> for &mut MapProbe {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if !self.remaining {
+            return Ok(None);
+        }
+        self.remaining = false;
+        seed.deserialize(Probe {
+            out: &mut self.key,
+            depth: self.depth + 1,
+        })
+        .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(Probe {
+            out: &mut self.value,
+            depth: self.depth + 1,
+        })
+    }
+}
+
+struct StructProbe {
+    names: &'static [&'static str],
+    fields: Vec<FieldSchema>,
+    depth: usize,
+}
+
+impl<'de> MapAccess<'de> for &mut StructProbe {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some(&name) = self.names.get(self.fields.len()) else {
+            return Ok(None);
+        };
+        seed.deserialize(name.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let name = self.names[self.fields.len()];
+        let mut ty = TypeSchema::Unknown;
+        let result = seed.deserialize(Probe {
+            out: &mut ty,
+            depth: self.depth + 1,
+        });
+        self.fields.push(FieldSchema {
+            name,
+            ty,
+            updates_frequently: false,
+        });
+        result
+    }
+}
+
+struct EnumProbe<'a> {
+    variant: &'static str,
+    depth: usize,
+    payload: Option<&'a mut TypeSchema>,
+}
+
+impl<'de> EnumAccess<'de> for EnumProbe<'_> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+// The payloads of the variants are probed, so a value can be produced. They are
+// only recorded when a specific variant is requested, as otherwise only the
+// first variant would ever be visited.
+impl<'de> VariantAccess<'de> for EnumProbe<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        let mut unrecorded = TypeSchema::Unknown;
+        seed.deserialize(Probe {
+            out: self.payload.unwrap_or(&mut unrecorded),
+            depth: self.depth + 1,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        Probe {
+            out: &mut TypeSchema::Unknown,
+            depth: self.depth,
+        }
+        .deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        Probe {
+            out: &mut TypeSchema::Unknown,
+            depth: self.depth,
+        }
+        .deserialize_struct("", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::{
+            blank_space, current_comparison, detailed_timer, graph, overlay_widgets, separator,
+            splits, tabs, text, timer, title,
+        },
+        layout::Layout,
+        settings::ImageCache,
+        util::tests_helper::create_timer,
+    };
+
+    fn field<'a>(schema: &'a ComponentStateSchema, name: &str) -> &'a FieldSchema {
+        schema.fields.iter().find(|f| f.name == name).unwrap()
+    }
+
+    #[test]
+    fn schemas_match_the_encoded_states() {
+        let mut layout = Layout::new();
+        layout.push(blank_space::Component::new());
+        layout.push(Box::new(detailed_timer::Component::new()));
+        layout.push(graph::Component::new());
+        layout.push(current_comparison::Component::new());
+        layout.push(overlay_widgets::Component::new());
+        layout.push(separator::Component::new());
+        layout.push(splits::Component::new());
+        layout.push(tabs::Component::new());
+        layout.push(text::Component::new());
+        layout.push(timer::Component::new());
+        layout.push(title::Component::new());
+
+        let timer = create_timer(&["A", "B"]);
+        let state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+        let schemas = ComponentState::schemas();
+
+        let kinds: Vec<_> = schemas.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                "BlankSpace",
+                "DetailedTimer",
+                "Graph",
+                "KeyValue",
+                "OverlayWidgets",
+                "Separator",
+                "Splits",
+                "Tabs",
+                "Text",
+                "Timer",
+                "Title",
+            ],
+        );
+
+        for component in &state.components {
+            let serde_json::Value::Object(encoded) = serde_json::to_value(component).unwrap()
+            else {
+                panic!("component state is not encoded as an object");
+            };
+            let (kind, encoded) = encoded.into_iter().next().unwrap();
+            let schema = schemas.iter().find(|s| s.kind == kind).unwrap();
+
+            let mut encoded_fields: Vec<_> = match encoded {
+                serde_json::Value::Object(fields) => fields.into_iter().map(|(k, _)| k).collect(),
+                _ => Vec::new(),
+            };
+            let mut fields: Vec<_> = schema.fields.iter().map(|f| f.name.to_owned()).collect();
+            encoded_fields.sort_unstable();
+            fields.sort_unstable();
+            assert_eq!(fields, encoded_fields, "{kind} doesn't match its schema");
+
+            assert!(
+                schema
+                    .fields
+                    .iter()
+                    .all(|f| !matches!(f.ty, TypeSchema::Unknown)),
+                "{kind} has fields of unknown type",
+            );
+        }
+    }
+
+    #[test]
+    fn frequently_updated_fields_exist() {
+        for schema in ComponentState::schemas() {
+            for &name in frequently_updated_fields(schema.kind) {
+                assert!(
+                    field(&schema, name).updates_frequently,
+                    "{} has no field {name}",
+                    schema.kind,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn timer() {
+        let schemas = ComponentState::schemas();
+        let timer = schemas.iter().find(|s| s.kind == "Timer").unwrap();
+
+        let time = field(timer, "time");
+        assert_eq!(time.ty, TypeSchema::String);
+        assert!(time.updates_frequently);
+
+        let height = field(timer, "height");
+        assert_eq!(height.ty, TypeSchema::Integer);
+        assert!(!height.updates_frequently);
+
+        assert_eq!(
+            field(timer, "top_color").ty,
+            TypeSchema::Tuple(vec![TypeSchema::Float; 4]),
+        );
+        assert!(matches!(
+            field(timer, "semantic_color").ty,
+            TypeSchema::Enum {
+                name: "SemanticColor",
+                ..
+            },
+        ));
+    }
+
+    #[test]
+    fn splits_recurse_into_lists() {
+        let schemas = ComponentState::schemas();
+        let splits = schemas.iter().find(|s| s.kind == "Splits").unwrap();
+
+        let TypeSchema::List(split) = &field(splits, "splits").ty else {
+            panic!("splits is not a list");
+        };
+        let TypeSchema::Struct { name, fields } = &**split else {
+            panic!("split is not a struct");
+        };
+        assert_eq!(*name, "SplitState");
+        assert!(
+            fields
+                .iter()
+                .any(|f| f.name == "icon" && f.ty == TypeSchema::String)
+        );
+        assert!(
+            fields
+                .iter()
+                .any(|f| f.name == "columns" && matches!(f.ty, TypeSchema::List(_)))
+        );
+
+        assert!(matches!(
+            &field(splits, "column_labels").ty,
+            TypeSchema::Option(labels) if **labels == TypeSchema::List(Box::new(TypeSchema::String)),
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+use super::GeneralSettings;
+use crate::{
+    platform::prelude::*,
+    settings::{Color, Gradient, LayoutBackground},
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// A `ThemeColor` refers to one of the colors of the layout's theme by its
+/// meaning rather than by its actual value. Components that use a theme color
+/// instead of a hardcoded color automatically follow the layout whenever a
+/// different [`Theme`] gets applied.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ThemeColor {
+    /// The background color of the layout. If the layout's background is a
+    /// gradient, this is its first color. If it is an image, this is fully
+    /// transparent.
+    Background,
+    /// The color to use for text that doesn't specify its own color.
+    TextPrimary,
+    /// The color to use for drawn shadows. This is fully transparent if the
+    /// layout doesn't draw any shadows.
+    TextShadow,
+    /// The color of normal separators.
+    Separators,
+    /// The color of thin separators.
+    ThinSeparators,
+    /// The color to use for when the runner achieved a best segment.
+    BestSegment,
+    /// The color to use for when the runner is ahead of the comparison and is
+    /// gaining even more time.
+    AheadGaining,
+    /// The color to use for when the runner is ahead of the comparison, but is
+    /// losing time.
+    AheadLosing,
+    /// The color to use for when the runner is behind the comparison, but is
+    /// gaining back time.
+    BehindGaining,
+    /// The color to use for when the runner is behind the comparison and is
+    /// losing even more time.
+    BehindLosing,
+    /// The color to use for when there is no active attempt.
+    NotRunning,
+    /// The color to use for when the runner achieved a new Personal Best.
+    PersonalBest,
+    /// The color to use for when the timer is paused.
+    Paused,
+}
+
+impl ThemeColor {
+    /// All the colors that are part of a theme.
+    pub const ALL: [ThemeColor; 13] = [
+        ThemeColor::Background,
+        ThemeColor::TextPrimary,
+        ThemeColor::TextShadow,
+        ThemeColor::Separators,
+        ThemeColor::ThinSeparators,
+        ThemeColor::BestSegment,
+        ThemeColor::AheadGaining,
+        ThemeColor::AheadLosing,
+        ThemeColor::BehindGaining,
+        ThemeColor::BehindLosing,
+        ThemeColor::NotRunning,
+        ThemeColor::PersonalBest,
+        ThemeColor::Paused,
+    ];
+
+    /// Looks up the actual color in the general settings of the layout.
+    pub const fn resolve(self, settings: &GeneralSettings) -> Color {
+        match self {
+            ThemeColor::Background => match &settings.background {
+                LayoutBackground::Gradient(
+                    Gradient::Plain(color)
+                    | Gradient::Vertical(color, _)
+                    | Gradient::Horizontal(color, _)
+                    | Gradient::Angled(color, ..)
+                    | Gradient::Radial(color, ..)
+                    | Gradient::Conic(color, ..)
+                    | Gradient::HueCycling(color, ..)
+                    | Gradient::Rotating(color, ..),
+                ) => *color,
+                _ => Color::transparent(),
+            },
+            ThemeColor::TextPrimary => settings.text_color,
+            ThemeColor::TextShadow => match settings.text_shadow {
+                Some(color) => color,
+                None => Color::transparent(),
+            },
+            ThemeColor::Separators => settings.separators_color,
+            ThemeColor::ThinSeparators => settings.thin_separators_color,
+            ThemeColor::BestSegment => settings.best_segment_color,
+            ThemeColor::AheadGaining => settings.ahead_gaining_time_color,
+            ThemeColor::AheadLosing => settings.ahead_losing_time_color,
+            ThemeColor::BehindGaining => settings.behind_gaining_time_color,
+            ThemeColor::BehindLosing => settings.behind_losing_time_color,
+            ThemeColor::NotRunning => settings.not_running_color,
+            ThemeColor::PersonalBest => settings.personal_best_color,
+            ThemeColor::Paused => settings.paused_color,
+        }
+    }
+}
+
+/// A `ThemedColor` is a color setting of a component that is either a fixed
+/// color or refers to one of the colors of the layout's theme or its palette.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThemedColor {
+    /// A fixed color that doesn't change with the theme.
+    Custom(Color),
+    /// A color that is taken from the layout's theme.
+    Theme(ThemeColor),
+    /// A color that is taken from the layout's palette by the name of the
+    /// [`PaletteColor`]. If the palette doesn't contain a color with that
+    /// name, the color is fully transparent.
+    Palette {
+        /// The name of the color in the palette.
+        #[serde(rename = "palette")]
+        name: String,
+    },
+}
+
+/// A `PaletteColor` is a named color of the layout's palette. Components can
+/// refer to it by its name through a [`ThemedColor`], so changing the color of
+/// the palette changes the color everywhere it is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaletteColor {
+    /// The name of the color, such as `Accent`.
+    pub name: String,
+    /// The actual color.
+    pub color: Color,
+}
+
+impl From<Color> for ThemedColor {
+    fn from(color: Color) -> Self {
+        ThemedColor::Custom(color)
+    }
+}
+
+impl From<ThemeColor> for ThemedColor {
+    fn from(color: ThemeColor) -> Self {
+        ThemedColor::Theme(color)
+    }
+}
+
+impl ThemedColor {
+    /// Resolves the actual color to use based on the general settings of the
+    /// layout.
+    pub fn resolve(&self, settings: &GeneralSettings) -> Color {
+        match self {
+            ThemedColor::Custom(color) => *color,
+            ThemedColor::Theme(color) => color.resolve(settings),
+            ThemedColor::Palette { name } => settings
+                .palette_color(name)
+                .unwrap_or_else(Color::transparent),
+        }
+    }
+}
+
+/// A `Theme` is a named palette of colors that can be applied to a layout all
+/// at once. Applying a theme replaces the layout's background and all the
+/// colors of its [`GeneralSettings`], which restyles every component that
+/// uses the layout's colors.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// The name of the theme.
+    pub name: String,
+    /// The background color of the layout.
+    pub background: Color,
+    /// The color to use for text that doesn't specify its own color.
+    pub text_primary: Color,
+    /// The color to use for drawn shadows.
+    pub text_shadow: Option<Color>,
+    /// The color of normal separators.
+    pub separators: Color,
+    /// The color of thin separators.
+    pub thin_separators: Color,
+    /// The color to use for when the runner achieved a best segment.
+    pub best_segment: Color,
+    /// The color to use for when the runner is ahead of the comparison and is
+    /// gaining even more time.
+    pub ahead_gaining: Color,
+    /// The color to use for when the runner is ahead of the comparison, but is
+    /// losing time.
+    pub ahead_losing: Color,
+    /// The color to use for when the runner is behind the comparison, but is
+    /// gaining back time.
+    pub behind_gaining: Color,
+    /// The color to use for when the runner is behind the comparison and is
+    /// losing even more time.
+    pub behind_losing: Color,
+    /// The color to use for when there is no active attempt.
+    pub not_running: Color,
+    /// The color to use for when the runner achieved a new Personal Best.
+    pub personal_best: Color,
+    /// The color to use for when the timer is paused.
+    pub paused: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// The dark theme that layouts use by default.
+    pub fn dark() -> Self {
+        Self {
+            name: "Dark".into(),
+            background: Color::hsla(0.0, 0.0, 0.06, 1.0),
+            text_primary: Color::hsla(0.0, 0.0, 1.0, 1.0),
+            text_shadow: Some(Color::hsla(0.0, 0.0, 0.0, 0.5)),
+            separators: Color::hsla(0.0, 0.0, 1.0, 0.35),
+            thin_separators: Color::hsla(0.0, 0.0, 1.0, 0.09),
+            best_segment: Color::hsla(50.0, 1.0, 0.5, 1.0),
+            ahead_gaining: Color::hsla(136.0, 1.0, 0.4, 1.0),
+            ahead_losing: Color::hsla(136.0, 0.55, 0.6, 1.0),
+            behind_gaining: Color::hsla(0.0, 0.55, 0.6, 1.0),
+            behind_losing: Color::hsla(0.0, 1.0, 0.4, 1.0),
+            not_running: Color::hsla(0.0, 0.0, 0.67, 1.0),
+            personal_best: Color::hsla(203.0, 1.0, 0.54, 1.0),
+            paused: Color::hsla(0.0, 0.0, 0.48, 1.0),
+        }
+    }
+
+    /// A light theme with dark text on a bright background.
+    pub fn light() -> Self {
+        Self {
+            name: "Light".into(),
+            background: Color::hsla(0.0, 0.0, 0.94, 1.0),
+            text_primary: Color::hsla(0.0, 0.0, 0.0, 1.0),
+            text_shadow: None,
+            separators: Color::hsla(0.0, 0.0, 0.0, 0.35),
+            thin_separators: Color::hsla(0.0, 0.0, 0.0, 0.09),
+            best_segment: Color::hsla(45.0, 1.0, 0.42, 1.0),
+            ahead_gaining: Color::hsla(136.0, 1.0, 0.3, 1.0),
+            ahead_losing: Color::hsla(136.0, 0.55, 0.42, 1.0),
+            behind_gaining: Color::hsla(0.0, 0.55, 0.48, 1.0),
+            behind_losing: Color::hsla(0.0, 1.0, 0.4, 1.0),
+            not_running: Color::hsla(0.0, 0.0, 0.4, 1.0),
+            personal_best: Color::hsla(203.0, 1.0, 0.4, 1.0),
+            paused: Color::hsla(0.0, 0.0, 0.5, 1.0),
+        }
+    }
+
+    /// Captures the colors of the general settings of a layout as a theme with
+    /// the name provided.
+    pub const fn from_general_settings(name: String, settings: &GeneralSettings) -> Self {
+        Self {
+            name,
+            background: ThemeColor::Background.resolve(settings),
+            text_primary: settings.text_color,
+            text_shadow: settings.text_shadow,
+            separators: settings.separators_color,
+            thin_separators: settings.thin_separators_color,
+            best_segment: settings.best_segment_color,
+            ahead_gaining: settings.ahead_gaining_time_color,
+            ahead_losing: settings.ahead_losing_time_color,
+            behind_gaining: settings.behind_gaining_time_color,
+            behind_losing: settings.behind_losing_time_color,
+            not_running: settings.not_running_color,
+            personal_best: settings.personal_best_color,
+            paused: settings.paused_color,
+        }
+    }
+
+    /// Accesses the color of the theme that is referred to by the theme color
+    /// provided.
+    pub const fn color(&self, color: ThemeColor) -> Color {
+        match color {
+            ThemeColor::Background => self.background,
+            ThemeColor::TextPrimary => self.text_primary,
+            ThemeColor::TextShadow => match self.text_shadow {
+                Some(color) => color,
+                None => Color::transparent(),
+            },
+            ThemeColor::Separators => self.separators,
+            ThemeColor::ThinSeparators => self.thin_separators,
+            ThemeColor::BestSegment => self.best_segment,
+            ThemeColor::AheadGaining => self.ahead_gaining,
+            ThemeColor::AheadLosing => self.ahead_losing,
+            ThemeColor::BehindGaining => self.behind_gaining,
+            ThemeColor::BehindLosing => self.behind_losing,
+            ThemeColor::NotRunning => self.not_running,
+            ThemeColor::PersonalBest => self.personal_best,
+            ThemeColor::Paused => self.paused,
+        }
+    }
+}
@@ -71,6 +71,8 @@ mod hotkey_config;
 #[cfg(feature = "std")]
 mod hotkey_system;
 pub mod layout;
+#[cfg(feature = "std")]
+pub mod livesplit_settings;
 pub mod networking;
 #[cfg(feature = "rendering")]
 pub mod rendering;
@@ -94,4 +96,11 @@ pub use livesplit_hotkey as hotkey;
 pub use crate::platform::{Clock, Duration, register_clock};
 
 #[cfg(feature = "std")]
-pub use crate::{hotkey_config::HotkeyConfig, hotkey_system::HotkeySystem, timing::SharedTimer};
+pub use crate::{
+    hotkey_config::{
+        HotkeyConfig, HotkeyConflict, HotkeyProfile, HotkeyScope, HotkeyScopes, MidiTriggers,
+        ProfileBindings,
+    },
+    hotkey_system::{BoundAction, CapturedHotkey, HotkeySystem},
+    timing::SharedTimer,
+};
@@ -0,0 +1,527 @@
+//! Provides a parser for the settings file (`settings.cfg`) of the original
+//! LiveSplit. This allows runners that switch over from the original LiveSplit
+//! to keep their hotkeys, their comparison preferences and their recently used
+//! splits and layouts. The settings file is only parsed on a best effort basis,
+//! so settings that livesplit-core has no equivalent for are skipped.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use livesplit_core::livesplit_settings;
+//! use std::fs;
+//!
+//! let source = fs::read_to_string("path/to/LiveSplit/settings.cfg").unwrap();
+//! let settings = livesplit_settings::parse(&source).expect("Not a valid settings file");
+//! let hotkey_config = settings.hotkeys;
+//! ```
+
+use crate::{
+    HotkeyConfig, TimeSpan, TimingMethod,
+    hotkey::{Hotkey, KeyCode, Modifiers},
+    platform::{Duration, prelude::*},
+    util::xml::{
+        Attributes, Reader,
+        helper::{
+            Error as XmlError, end_tag, parse_attributes, parse_base, parse_children, text,
+            text_as_escaped_string_err,
+        },
+    },
+};
+use core::num::ParseFloatError;
+
+/// The Error type for parsing the settings file of the original LiveSplit.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// The underlying XML format couldn't be parsed.
+    Xml {
+        /// The underlying error.
+        source: XmlError,
+    },
+    /// Failed to parse a floating point number.
+    ParseFloat {
+        /// The underlying error.
+        source: ParseFloatError,
+    },
+    /// Failed to parse a boolean.
+    ParseBool,
+    /// Failed to parse a timing method.
+    ParseTimingMethod,
+}
+
+impl From<XmlError> for Error {
+    fn from(source: XmlError) -> Self {
+        Self::Xml { source }
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(source: ParseFloatError) -> Self {
+        Self::ParseFloat { source }
+    }
+}
+
+/// The Result type for parsing the settings file of the original LiveSplit.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// The settings parsed from the settings file of the original LiveSplit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// The hotkeys of the hotkey profile that was used last.
+    pub hotkeys: HotkeyConfig,
+    /// All the hotkey profiles. Older versions of the original LiveSplit only
+    /// have a single set of hotkeys, which is turned into a profile called
+    /// `Default`.
+    pub hotkey_profiles: Vec<HotkeyProfile>,
+    /// Whether a confirmation is requested before resetting an attempt that
+    /// would update the splits.
+    pub warn_on_reset: bool,
+    /// The splits files that were opened most recently, with the most recent
+    /// one coming last.
+    pub recent_splits: Vec<RecentSplits>,
+    /// The paths of the layout files that were opened most recently, with the
+    /// most recent one coming last.
+    pub recent_layouts: Vec<String>,
+    /// The comparison that was used last, if it was stored.
+    pub last_comparison: Option<String>,
+    /// The timing method that was used last.
+    pub last_timing_method: TimingMethod,
+    /// Whether the Sum of Best is calculated without taking the segment
+    /// history into account.
+    pub simple_sum_of_best: bool,
+    /// The names of the comparison generators and whether they are enabled.
+    pub comparison_generators: Vec<(String, bool)>,
+}
+
+/// A named set of hotkeys and the settings that apply to them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotkeyProfile {
+    /// The name of the profile.
+    pub name: String,
+    /// The hotkeys of the profile.
+    pub hotkeys: HotkeyConfig,
+    /// Whether the hotkeys work even if the timer is not focused.
+    pub global_hotkeys_enabled: bool,
+    /// Whether the hotkeys are disabled while other applications are focused
+    /// that listen for the same keys.
+    pub deactivate_hotkeys_for_other_programs: bool,
+    /// Whether hotkeys that are pressed twice in quick succession are ignored.
+    pub double_tap_prevention: bool,
+    /// How long the actions are delayed after the hotkey got pressed.
+    pub hotkey_delay: TimeSpan,
+}
+
+impl Default for HotkeyProfile {
+    fn default() -> Self {
+        Self {
+            name: String::from("Default"),
+            hotkeys: HotkeyConfig::default(),
+            global_hotkeys_enabled: false,
+            deactivate_hotkeys_for_other_programs: false,
+            double_tap_prevention: true,
+            hotkey_delay: TimeSpan::zero(),
+        }
+    }
+}
+
+/// A splits file that was opened recently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentSplits {
+    /// The path of the splits file.
+    pub path: String,
+    /// The name of the game the splits are for.
+    pub game_name: String,
+    /// The name of the category the splits are for.
+    pub category_name: String,
+    /// The timing method that was used with the splits.
+    pub last_timing_method: Option<TimingMethod>,
+    /// The name of the hotkey profile that was used with the splits.
+    pub last_hotkey_profile: Option<String>,
+}
+
+fn parse_bool<F>(reader: &mut Reader, f: F) -> Result<()>
+where
+    F: FnOnce(bool),
+{
+    text_as_escaped_string_err(reader, |t| match t {
+        "True" => {
+            f(true);
+            Ok(())
+        }
+        "False" => {
+            f(false);
+            Ok(())
+        }
+        _ => Err(Error::ParseBool),
+    })
+}
+
+fn timing_method(text: &str) -> Result<TimingMethod> {
+    match text {
+        "RealTime" => Ok(TimingMethod::RealTime),
+        "GameTime" => Ok(TimingMethod::GameTime),
+        _ => Err(Error::ParseTimingMethod),
+    }
+}
+
+/// Parses a key as it's stored by the original LiveSplit. These are the names
+/// of the variants of the `System.Windows.Forms.Keys` enum, with the modifiers
+/// being separated by commas. Keys that have no equivalent, such as gamepad
+/// buttons, result in no hotkey.
+fn hotkey(text: &str) -> Option<Hotkey> {
+    let mut key_code = None;
+    let mut modifiers = Modifiers::empty();
+
+    for token in text.split(',').map(str::trim) {
+        match token {
+            "Shift" => modifiers |= Modifiers::SHIFT,
+            "Control" => modifiers |= Modifiers::CONTROL,
+            "Alt" => modifiers |= Modifiers::ALT,
+            "None" | "" => {}
+            _ => key_code = Some(key_code_from_name(token)?),
+        }
+    }
+
+    Some(Hotkey {
+        key_code: key_code?,
+        modifiers,
+    })
+}
+
+fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    use KeyCode::*;
+
+    if let [b'D', digit @ b'0'..=b'9'] = name.as_bytes() {
+        return Some(
+            [
+                Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+            ][(digit - b'0') as usize],
+        );
+    }
+    if let [letter @ b'A'..=b'Z'] = name.as_bytes() {
+        return Some(
+            [
+                KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN,
+                KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+            ][(letter - b'A') as usize],
+        );
+    }
+    if let Some(digit) = name.strip_prefix("NumPad") {
+        return Some(match digit {
+            "0" => Numpad0,
+            "1" => Numpad1,
+            "2" => Numpad2,
+            "3" => Numpad3,
+            "4" => Numpad4,
+            "5" => Numpad5,
+            "6" => Numpad6,
+            "7" => Numpad7,
+            "8" => Numpad8,
+            "9" => Numpad9,
+            _ => return None,
+        });
+    }
+
+    // The enum has multiple names for some of the keys. We accept all of them,
+    // as it's not specified which one ends up being stored.
+    Some(match name {
+        "F1" => F1,
+        "F2" => F2,
+        "F3" => F3,
+        "F4" => F4,
+        "F5" => F5,
+        "F6" => F6,
+        "F7" => F7,
+        "F8" => F8,
+        "F9" => F9,
+        "F10" => F10,
+        "F11" => F11,
+        "F12" => F12,
+        "F13" => F13,
+        "F14" => F14,
+        "F15" => F15,
+        "F16" => F16,
+        "F17" => F17,
+        "F18" => F18,
+        "F19" => F19,
+        "F20" => F20,
+        "F21" => F21,
+        "F22" => F22,
+        "F23" => F23,
+        "F24" => F24,
+        "Back" => Backspace,
+        "Tab" => Tab,
+        "Return" | "Enter" => Enter,
+        "Pause" => Pause,
+        "Capital" | "CapsLock" => CapsLock,
+        "Escape" => Escape,
+        "Space" => Space,
+        "Prior" | "PageUp" => PageUp,
+        "Next" | "PageDown" => PageDown,
+        "End" => End,
+        "Home" => Home,
+        "Left" => ArrowLeft,
+        "Up" => ArrowUp,
+        "Right" => ArrowRight,
+        "Down" => ArrowDown,
+        "Snapshot" | "PrintScreen" => PrintScreen,
+        "Insert" => Insert,
+        "Delete" => Delete,
+        "Help" => Help,
+        "LWin" => MetaLeft,
+        "RWin" => MetaRight,
+        "Apps" => ContextMenu,
+        "Sleep" => Sleep,
+        "Multiply" => NumpadMultiply,
+        "Add" => NumpadAdd,
+        "Separator" => NumpadComma,
+        "Subtract" => NumpadSubtract,
+        "Decimal" => NumpadDecimal,
+        "Divide" => NumpadDivide,
+        "NumLock" => NumLock,
+        "Scroll" => ScrollLock,
+        "LShiftKey" | "ShiftKey" => ShiftLeft,
+        "RShiftKey" => ShiftRight,
+        "LControlKey" | "ControlKey" => ControlLeft,
+        "RControlKey" => ControlRight,
+        "LMenu" | "Menu" => AltLeft,
+        "RMenu" => AltRight,
+        "BrowserBack" => BrowserBack,
+        "BrowserForward" => BrowserForward,
+        "BrowserRefresh" => BrowserRefresh,
+        "BrowserStop" => BrowserStop,
+        "BrowserSearch" => BrowserSearch,
+        "BrowserFavorites" => BrowserFavorites,
+        "BrowserHome" => BrowserHome,
+        "VolumeMute" => AudioVolumeMute,
+        "VolumeDown" => AudioVolumeDown,
+        "VolumeUp" => AudioVolumeUp,
+        "MediaNextTrack" => MediaTrackNext,
+        "MediaPreviousTrack" => MediaTrackPrevious,
+        "MediaStop" => MediaStop,
+        "MediaPlayPause" => MediaPlayPause,
+        "LaunchMail" => LaunchMail,
+        "SelectMedia" => MediaSelect,
+        "LaunchApplication1" => LaunchApp1,
+        "LaunchApplication2" => LaunchApp2,
+        "Oem1" | "OemSemicolon" => Semicolon,
+        "Oemplus" => Equal,
+        "Oemcomma" => Comma,
+        "OemMinus" => Minus,
+        "OemPeriod" => Period,
+        "Oem2" | "OemQuestion" => Slash,
+        "Oem3" | "Oemtilde" => Backquote,
+        "Oem4" | "OemOpenBrackets" => BracketLeft,
+        "Oem5" | "OemPipe" => Backslash,
+        "Oem6" | "OemCloseBrackets" => BracketRight,
+        "Oem7" | "OemQuotes" => Quote,
+        "Oem102" | "OemBackslash" => IntlBackslash,
+        _ => return None,
+    })
+}
+
+/// Parses a setting that is part of a hotkey profile. Returns `false` if the
+/// tag doesn't belong to a hotkey profile.
+fn parse_hotkey_setting(
+    reader: &mut Reader,
+    tag_name: &str,
+    profile: &mut HotkeyProfile,
+) -> Result<bool> {
+    let hotkeys = &mut profile.hotkeys;
+    let slot = match tag_name {
+        "SplitKey" => &mut hotkeys.split,
+        "ResetKey" => &mut hotkeys.reset,
+        "SkipKey" => &mut hotkeys.skip,
+        "UndoKey" => &mut hotkeys.undo,
+        "PauseKey" => &mut hotkeys.pause,
+        "SwitchComparisonPrevious" => &mut hotkeys.previous_comparison,
+        "SwitchComparisonNext" => &mut hotkeys.next_comparison,
+        "GlobalHotkeysEnabled" => {
+            parse_bool(reader, |b| profile.global_hotkeys_enabled = b)?;
+            return Ok(true);
+        }
+        "DeactivateHotkeysForOtherPrograms" => {
+            parse_bool(reader, |b| {
+                profile.deactivate_hotkeys_for_other_programs = b
+            })?;
+            return Ok(true);
+        }
+        "DoubleTapPrevention" => {
+            parse_bool(reader, |b| profile.double_tap_prevention = b)?;
+            return Ok(true);
+        }
+        "HotkeyDelay" => {
+            text_as_escaped_string_err::<_, _, Error>(reader, |t| {
+                // A delay that can't be represented is skipped, just like the
+                // settings that livesplit-core has no equivalent for.
+                if let Some(delay) = Duration::checked_seconds_f64(t.parse()?) {
+                    profile.hotkey_delay = delay.into();
+                }
+                Ok(())
+            })?;
+            return Ok(true);
+        }
+        // Unsupported:
+        // ToggleGlobalHotkeys
+        // AllowGamepadsAsHotkeys
+        _ => return Ok(false),
+    };
+    text_as_escaped_string_err::<_, _, Error>(reader, |t| {
+        *slot = hotkey(t).map(Into::into);
+        Ok(())
+    })?;
+    Ok(true)
+}
+
+fn recent_splits(reader: &mut Reader, attributes: Attributes) -> Result<RecentSplits> {
+    let mut recent = RecentSplits {
+        path: String::new(),
+        game_name: String::new(),
+        category_name: String::new(),
+        last_timing_method: None,
+        last_hotkey_profile: None,
+    };
+
+    parse_attributes(attributes, |k, v| {
+        match k {
+            "gameName" => recent.game_name = v.unescape_cow().into_owned(),
+            "categoryName" => recent.category_name = v.unescape_cow().into_owned(),
+            "lastTimingMethod" => {
+                recent.last_timing_method = Some(timing_method(v.escaped())?);
+            }
+            "lastHotkeyProfile" => {
+                recent.last_hotkey_profile = Some(v.unescape_cow().into_owned());
+            }
+            _ => {}
+        }
+        Ok::<_, Error>(true)
+    })?;
+
+    text::<_, Error>(reader, |t| recent.path = t.into_owned())?;
+
+    Ok(recent)
+}
+
+/// Attempts to parse the settings file of the original LiveSplit.
+pub fn parse(source: &str) -> Result<Settings> {
+    let reader = &mut Reader::new(source);
+
+    let mut settings = Settings {
+        hotkeys: HotkeyConfig::default(),
+        hotkey_profiles: Vec::new(),
+        warn_on_reset: true,
+        recent_splits: Vec::new(),
+        recent_layouts: Vec::new(),
+        last_comparison: None,
+        last_timing_method: TimingMethod::RealTime,
+        simple_sum_of_best: false,
+        comparison_generators: Vec::new(),
+    };
+
+    // Versions before 1.8 store the hotkeys directly in the settings.
+    let mut legacy_profile = HotkeyProfile::default();
+    let mut has_legacy_profile = false;
+
+    parse_base(reader, "Settings", |reader, _| {
+        parse_children(reader, |reader, tag, _| {
+            if parse_hotkey_setting(reader, tag.name(), &mut legacy_profile)? {
+                has_legacy_profile = true;
+                return Ok(());
+            }
+            match tag.name() {
+                "HotkeyProfiles" => parse_children(reader, |reader, _, attributes| {
+                    let mut profile = HotkeyProfile::default();
+                    parse_attributes(attributes, |k, v| {
+                        if k == "name" {
+                            profile.name = v.unescape_cow().into_owned();
+                            Ok::<_, Error>(false)
+                        } else {
+                            Ok(true)
+                        }
+                    })?;
+                    parse_children(reader, |reader, tag, _| {
+                        if parse_hotkey_setting(reader, tag.name(), &mut profile)? {
+                            Ok(())
+                        } else {
+                            end_tag::<Error>(reader)
+                        }
+                    })?;
+                    settings.hotkey_profiles.push(profile);
+                    Ok(())
+                }),
+                "WarnOnReset" => parse_bool(reader, |b| settings.warn_on_reset = b),
+                "RecentSplits" => parse_children(reader, |reader, tag, attributes| {
+                    match tag.name() {
+                        // Version >= 1.7
+                        "SplitsFile" => {
+                            let recent = recent_splits(reader, attributes)?;
+                            settings.recent_splits.push(recent);
+                            Ok(())
+                        }
+                        // Version < 1.7
+                        "SplitsPath" => text(reader, |t| {
+                            settings.recent_splits.push(RecentSplits {
+                                path: t.into_owned(),
+                                game_name: String::new(),
+                                category_name: String::new(),
+                                last_timing_method: None,
+                                last_hotkey_profile: None,
+                            })
+                        }),
+                        _ => end_tag(reader),
+                    }
+                }),
+                "RecentLayouts" => parse_children(reader, |reader, _, _| {
+                    text(reader, |t| settings.recent_layouts.push(t.into_owned()))
+                }),
+                "LastComparison" => text(reader, |t| {
+                    settings.last_comparison = Some(t.into_owned()).filter(|t| !t.is_empty());
+                }),
+                "LastTimingMethod" => text_as_escaped_string_err(reader, |t| {
+                    settings.last_timing_method = timing_method(t)?;
+                    Ok(())
+                }),
+                "SimpleSumOfBest" => parse_bool(reader, |b| settings.simple_sum_of_best = b),
+                "ComparisonGeneratorStates" => parse_children(reader, |reader, _, attributes| {
+                    let mut name = String::new();
+                    parse_attributes(attributes, |k, v| {
+                        if k == "name" {
+                            name = v.unescape_cow().into_owned();
+                            Ok::<_, Error>(false)
+                        } else {
+                            Ok(true)
+                        }
+                    })?;
+                    parse_bool(reader, |b| settings.comparison_generators.push((name, b)))
+                }),
+                _ => end_tag(reader),
+            }
+        })
+    })?;
+
+    if has_legacy_profile {
+        settings.hotkey_profiles.insert(0, legacy_profile);
+    }
+
+    // The hotkey profile that was used with the most recent splits is the one
+    // that is active when the original LiveSplit starts up.
+    let active_profile = settings
+        .recent_splits
+        .last()
+        .and_then(|s| s.last_hotkey_profile.as_deref())
+        .and_then(|name| settings.hotkey_profiles.iter().find(|p| p.name == name))
+        .or_else(|| settings.hotkey_profiles.first());
+
+    if let Some(profile) = active_profile {
+        settings.hotkeys = profile.hotkeys.clone();
+    }
+
+    if let Some(last_timing_method) = settings
+        .recent_splits
+        .last()
+        .and_then(|s| s.last_timing_method)
+    {
+        settings.last_timing_method = last_timing_method;
+    }
+
+    Ok(settings)
+}
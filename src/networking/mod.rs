@@ -5,3 +5,25 @@
 
 #[cfg(feature = "std")]
 pub mod server_protocol;
+#[cfg(feature = "networking")]
+pub mod template_index;
+#[cfg(feature = "networking")]
+pub mod webhooks;
+
+#[cfg(feature = "networking")]
+use crate::platform::prelude::*;
+
+/// An HTTP client provided by the frontend. It is used by the networking
+/// functionality that needs to perform HTTP requests, such as the
+/// [`webhooks`] and the [`template_index`], so the frontend only needs to
+/// provide a single implementation.
+#[cfg(feature = "networking")]
+pub trait HttpClient {
+    /// Fetches the body of the URL provided. If the request fails, `None` is
+    /// returned.
+    fn get(&self, url: &str) -> impl Future<Output = Option<Vec<u8>>>;
+
+    /// Posts the JSON payload to the URL specified. The client is expected to
+    /// not block, so it should send the request in the background.
+    fn post_json(&self, url: &str, body: String);
+}
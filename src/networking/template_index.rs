@@ -0,0 +1,142 @@
+//! Provides access to a networking backed index of community maintained split
+//! [`Template`]s. The index is a JSON document that lists the game and
+//! category of every template along with the URL it can be fetched from:
+//!
+//! ```json
+//! {
+//!     "templates": [
+//!         { "game": "Celeste", "category": "Any%", "url": "https://..." }
+//!     ]
+//! }
+//! ```
+//!
+//! The HTTP requests are performed by an [`HttpClient`] provided by the
+//! frontend.
+
+use super::HttpClient;
+use crate::{platform::prelude::*, run::template::Template, util::caseless};
+use serde_derive::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+/// The error type for fetching the index or a template.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum Error {
+    /// The HTTP request failed.
+    Request,
+    /// The response couldn't be decoded.
+    Json {
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+}
+
+/// The result type for fetching the index or a template.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// An entry of the index that describes where to find a template.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The name of the game.
+    pub game: String,
+    /// The name of the category.
+    pub category: String,
+    /// The URL the template can be fetched from.
+    pub url: String,
+}
+
+/// An index of community maintained split templates.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Index {
+    /// All the templates listed in the index.
+    pub templates: Vec<Entry>,
+}
+
+impl Index {
+    /// Decodes an index from JSON.
+    pub fn from_json(json: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(json)
+    }
+
+    /// Fetches the index from the URL provided.
+    pub async fn fetch(client: &impl HttpClient, url: &str) -> Result<Self> {
+        let body = client.get(url).await.ok_or(Error::Request)?;
+        Self::from_json(&body).context(Json)
+    }
+
+    /// Returns all the entries for the game provided. The names are compared
+    /// case insensitively.
+    pub fn categories<'a>(&'a self, game: &'a str) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.templates
+            .iter()
+            .filter(move |e| caseless::eq(&e.game, game))
+    }
+
+    /// Looks up the entry for the game and category provided. The names are
+    /// compared case insensitively.
+    pub fn find(&self, game: &str, category: &str) -> Option<&Entry> {
+        self.templates
+            .iter()
+            .find(|e| caseless::eq(&e.game, game) && caseless::eq(&e.category, category))
+    }
+}
+
+impl Entry {
+    /// Fetches the template this entry refers to.
+    pub async fn fetch(&self, client: &impl HttpClient) -> Result<Template> {
+        let body = client.get(&self.url).await.ok_or(Error::Request)?;
+        Template::from_json(&body).context(Json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    struct Client;
+
+    impl HttpClient for Client {
+        async fn get(&self, url: &str) -> Option<Vec<u8>> {
+            Some(match url {
+                "index" => br#"{"templates":[
+                    { "game": "Celeste", "category": "Any%", "url": "celeste" },
+                    { "game": "Celeste", "category": "100%", "url": "missing" }
+                ]}"#
+                .to_vec(),
+                "celeste" => {
+                    br#"{"game":"Celeste","category":"Any%","segments":[{"name":"City"}]}"#.to_vec()
+                }
+                _ => return None,
+            })
+        }
+
+        fn post_json(&self, _: &str, _: String) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fetch() {
+        let index = block_on(Index::fetch(&Client, "index")).unwrap();
+        assert_eq!(index.categories("celeste").count(), 2);
+
+        let template = block_on(index.find("Celeste", "any%").unwrap().fetch(&Client)).unwrap();
+        assert_eq!(template.instantiate().segment(0).name(), "City");
+
+        let missing = block_on(index.find("Celeste", "100%").unwrap().fetch(&Client));
+        assert!(matches!(missing, Err(Error::Request)));
+    }
+}
@@ -0,0 +1,355 @@
+//! Provides a dispatcher for webhooks that notify other services about notable
+//! moments of an attempt, such as a new Personal Best or a gold split. This
+//! allows Discord bots, home automation and similar services to react to runs
+//! without requiring a custom frontend.
+//!
+//! Each [`Webhook`] consists of a URL, the [`Trigger`]s it reacts to and a
+//! template for the JSON payload that is posted to the URL. The template may
+//! contain the following placeholders, which are replaced by information about
+//! the attempt. The values are escaped as JSON strings, so the placeholders
+//! are supposed to be used within string literals of the template.
+//!
+//! | Placeholder      | Value                                                 |
+//! |------------------|-------------------------------------------------------|
+//! | `{trigger}`      | The name of the trigger, such as `PersonalBest`.       |
+//! | `{game}`         | The name of the game.                                  |
+//! | `{category}`     | The name of the category.                              |
+//! | `{attempts}`     | The amount of attempts.                                |
+//! | `{segment}`      | The name of the segment the trigger is about.          |
+//! | `{split_time}`   | The split time of that segment.                        |
+//! | `{segment_time}` | The segment time of that segment.                      |
+//! | `{delta}`        | The difference to the Personal Best at that segment.   |
+//!
+//! The dispatcher doesn't perform any HTTP requests on its own. Instead the
+//! requests are handed to an [`HttpClient`] provided by the frontend, so the
+//! frontend is in control of how and when they are sent.
+
+use super::HttpClient;
+use crate::{
+    TimeSpan, Timer, TimerPhase,
+    analysis::state_helper::{check_best_segment, last_delta, previous_segment_time},
+    comparison::personal_best,
+    event::Event,
+    platform::prelude::*,
+    timing::formatter::{Accuracy, Delta, Regular, TimeFormatter},
+    util::json,
+};
+use core::fmt::Write;
+use serde_derive::{Deserialize, Serialize};
+
+/// The payload template that is used if a [`Webhook`] doesn't specify one.
+pub const DEFAULT_TEMPLATE: &str = r#"{"trigger":"{trigger}","game":"{game}","category":"{category}","attempts":"{attempts}","segment":"{segment}","split_time":"{split_time}","segment_time":"{segment_time}","delta":"{delta}"}"#;
+
+/// A moment of an attempt that a [`Webhook`] can react to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Trigger {
+    /// The attempt was finished with a new Personal Best.
+    PersonalBest,
+    /// A segment was finished with a new best segment time.
+    Gold,
+    /// The final split of the attempt happened.
+    FinalSplit,
+    /// The attempt was reset while it was ahead of the Personal Best.
+    ResetAtPace,
+}
+
+impl Trigger {
+    /// Returns the name of the trigger, as it is provided to the payload
+    /// template.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Trigger::PersonalBest => "PersonalBest",
+            Trigger::Gold => "Gold",
+            Trigger::FinalSplit => "FinalSplit",
+            Trigger::ResetAtPace => "ResetAtPace",
+        }
+    }
+}
+
+/// A webhook that posts a JSON payload to a URL whenever one of its triggers
+/// occurs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    /// The URL the payload is posted to.
+    pub url: String,
+    /// The triggers that cause the payload to be posted.
+    pub triggers: Vec<Trigger>,
+    /// The template of the JSON payload. If there is none, the
+    /// [`DEFAULT_TEMPLATE`] is used.
+    pub template: Option<String>,
+}
+
+/// The webhook dispatcher observes the events of a [`Timer`] and posts the
+/// payloads of all the [`Webhook`]s whose triggers occur.
+#[derive(Clone, Debug, Default)]
+pub struct Dispatcher {
+    webhooks: Vec<Webhook>,
+    /// The index of the last segment that was split and the delta to the
+    /// Personal Best at that split, if the attempt is ahead of it.
+    ahead_of_pb: Option<(usize, TimeSpan)>,
+}
+
+impl Dispatcher {
+    /// Creates a new dispatcher for the webhooks provided.
+    pub const fn new(webhooks: Vec<Webhook>) -> Self {
+        Self {
+            webhooks,
+            ahead_of_pb: None,
+        }
+    }
+
+    /// Accesses the webhooks of the dispatcher.
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Grants mutable access to the webhooks of the dispatcher.
+    pub const fn webhooks_mut(&mut self) -> &mut Vec<Webhook> {
+        &mut self.webhooks
+    }
+
+    /// Handles an event that the timer reported. This needs to be called for
+    /// every event right after it happened, as the dispatcher inspects the
+    /// current state of the timer to determine which triggers occurred.
+    pub fn handle_event(&mut self, event: Event, timer: &Timer, client: &impl HttpClient) {
+        match event {
+            Event::Splitted | Event::SplitSkipped | Event::SplitUndone => {
+                let index = timer.current_split_index().and_then(|i| i.checked_sub(1));
+                self.update_pace(timer, index);
+                if event == Event::Splitted
+                    && let Some(index) = index
+                    && check_best_segment(timer, index, timer.current_timing_method())
+                {
+                    self.dispatch(Trigger::Gold, timer, index, client);
+                }
+            }
+            Event::Finished => {
+                let index = timer.run().len() - 1;
+                let method = timer.current_timing_method();
+                self.ahead_of_pb = None;
+
+                if check_best_segment(timer, index, method) {
+                    self.dispatch(Trigger::Gold, timer, index, client);
+                }
+                let segment = timer.run().segment(index);
+                let pb = segment.personal_best_split_time()[method];
+                if segment.split_time()[method].is_some_and(|t| pb.is_none_or(|pb| t < pb)) {
+                    self.dispatch(Trigger::PersonalBest, timer, index, client);
+                }
+                self.dispatch(Trigger::FinalSplit, timer, index, client);
+            }
+            Event::Reset => {
+                if let Some((index, _)) = self.ahead_of_pb {
+                    self.dispatch(Trigger::ResetAtPace, timer, index, client);
+                }
+                self.ahead_of_pb = None;
+            }
+            Event::Started => self.ahead_of_pb = None,
+            _ => {}
+        }
+    }
+
+    fn update_pace(&mut self, timer: &Timer, index: Option<usize>) {
+        self.ahead_of_pb = catch! {
+            let index = index?;
+            let delta = last_delta(
+                timer.run(),
+                index,
+                personal_best::NAME,
+                timer.current_timing_method(),
+            )?;
+            (delta < TimeSpan::zero()).then_some((index, delta))?
+        };
+    }
+
+    fn dispatch(
+        &self,
+        trigger: Trigger,
+        timer: &Timer,
+        segment_index: usize,
+        client: &impl HttpClient,
+    ) {
+        for webhook in &self.webhooks {
+            if webhook.triggers.contains(&trigger) {
+                let template = webhook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+                let body = self.render(template, trigger, timer, segment_index);
+                client.post_json(&webhook.url, body);
+            }
+        }
+    }
+
+    fn render(
+        &self,
+        template: &str,
+        trigger: Trigger,
+        timer: &Timer,
+        segment_index: usize,
+    ) -> String {
+        let run = timer.run();
+        let method = timer.current_timing_method();
+        let segment = run.segment(segment_index);
+        let is_reset = timer.current_phase() == TimerPhase::NotRunning;
+        let time_formatter = Regular::with_accuracy(Accuracy::Hundredths);
+
+        let mut body = Vec::with_capacity(template.len());
+        let mut value = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            let (literal, placeholder) = rest.split_at(start);
+            body.extend_from_slice(literal.as_bytes());
+
+            let Some(end) = placeholder.find('}') else {
+                rest = placeholder;
+                break;
+            };
+
+            value.clear();
+            let _ = match &placeholder[1..end] {
+                "trigger" => value.write_str(trigger.name()),
+                "game" => value.write_str(run.game_name()),
+                "category" => value.write_str(run.category_name()),
+                "attempts" => write!(value, "{}", run.attempt_count()),
+                "segment" => value.write_str(segment.name()),
+                "split_time" if !is_reset => {
+                    write!(
+                        value,
+                        "{}",
+                        time_formatter.format(segment.split_time()[method])
+                    )
+                }
+                "segment_time" if !is_reset => {
+                    let time = previous_segment_time(timer, segment_index, method);
+                    write!(value, "{}", time_formatter.format(time))
+                }
+                "delta" => {
+                    let delta = if is_reset {
+                        self.ahead_of_pb.map(|(_, delta)| delta)
+                    } else {
+                        last_delta(run, segment_index, personal_best::NAME, method)
+                    };
+                    write!(value, "{}", Delta::with_decimal_dropping().format(delta))
+                }
+                "split_time" | "segment_time" => Ok(()),
+                _ => {
+                    // Not a placeholder, so the brace is kept as is.
+                    body.push(b'{');
+                    rest = &placeholder[1..];
+                    continue;
+                }
+            };
+
+            let value_start = body.len();
+            if json::to_vec(&*value, &mut body).is_ok() {
+                // Strip the quotes, as the placeholder is within a string.
+                body.pop();
+                body.remove(value_start);
+            }
+            rest = &placeholder[end + 1..];
+        }
+        body.extend_from_slice(rest.as_bytes());
+
+        String::from_utf8(body).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::tests_helper::*;
+    use core::cell::RefCell;
+
+    #[derive(Default)]
+    struct Client(RefCell<Vec<(String, String)>>);
+
+    impl HttpClient for Client {
+        async fn get(&self, _: &str) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn post_json(&self, url: &str, body: String) {
+            self.0.borrow_mut().push((url.to_owned(), body));
+        }
+    }
+
+    fn webhook(url: &str, triggers: &[Trigger], template: &str) -> Webhook {
+        Webhook {
+            url: url.to_owned(),
+            triggers: triggers.to_vec(),
+            template: Some(template.to_owned()),
+        }
+    }
+
+    fn split(timer: &mut Timer, dispatcher: &mut Dispatcher, client: &Client, time: f64) {
+        timer.set_game_time(TimeSpan::from_seconds(time)).unwrap();
+        let event = timer.split().unwrap();
+        dispatcher.handle_event(event, timer, client);
+    }
+
+    #[test]
+    fn personal_best_and_golds() {
+        let mut timer = create_timer(&["A \"quoted\"", "B"]);
+        run_with_splits(&mut timer, &[10.0, 20.0]);
+
+        let client = Client::default();
+        let mut dispatcher = Dispatcher::new(vec![
+            webhook(
+                "pb",
+                &[Trigger::PersonalBest],
+                r#"{"text":"{segment} {split_time}"}"#,
+            ),
+            webhook(
+                "gold",
+                &[Trigger::Gold],
+                r#"{"text":"{trigger} {segment}"}"#,
+            ),
+        ]);
+
+        start_run(&mut timer);
+        split(&mut timer, &mut dispatcher, &client, 9.0);
+        split(&mut timer, &mut dispatcher, &client, 19.5);
+
+        let posted = client.0.into_inner();
+        assert_eq!(
+            posted,
+            [
+                ("gold".into(), r#"{"text":"Gold A \"quoted\""}"#.into()),
+                ("pb".into(), r#"{"text":"B 0:19.50"}"#.into()),
+            ],
+        );
+    }
+
+    #[test]
+    fn reset_at_pace() {
+        let mut timer = create_timer(&["A", "B", "C"]);
+        run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+
+        let client = Client::default();
+        let mut dispatcher = Dispatcher::new(vec![webhook(
+            "reset",
+            &[Trigger::ResetAtPace],
+            r#"{"text":"{trigger} after {segment} at {delta}","x":{}}"#,
+        )]);
+
+        start_run(&mut timer);
+        split(&mut timer, &mut dispatcher, &client, 11.0);
+        timer.reset(true).unwrap();
+        dispatcher.handle_event(Event::Reset, &timer, &client);
+        assert!(client.0.borrow().is_empty());
+
+        start_run(&mut timer);
+        dispatcher.handle_event(Event::Started, &timer, &client);
+        split(&mut timer, &mut dispatcher, &client, 9.0);
+        timer.reset(true).unwrap();
+        dispatcher.handle_event(Event::Reset, &timer, &client);
+
+        let posted = client.0.into_inner();
+        assert_eq!(
+            posted,
+            [(
+                "reset".into(),
+                r#"{"text":"ResetAtPace after A at −1.0","x":{}}"#.into()
+            )],
+        );
+    }
+}
@@ -6,8 +6,32 @@ pub mod f32 {
             pub fn abs(x: f32) -> f32 {
                 x.abs()
             }
+
+            #[cfg(feature = "rendering")]
+            #[inline(always)]
+            pub fn sin_cos(x: f32) -> (f32, f32) {
+                x.sin_cos()
+            }
+
+            #[cfg(feature = "software-rendering")]
+            #[inline(always)]
+            pub fn atan2(y: f32, x: f32) -> f32 {
+                y.atan2(x)
+            }
+
+            #[cfg(feature = "rendering")]
+            #[inline(always)]
+            pub fn round(x: f32) -> f32 {
+                x.round()
+            }
         } else {
             pub use libm::fabsf as abs;
+            #[cfg(feature = "rendering")]
+            pub use libm::sincosf as sin_cos;
+            #[cfg(feature = "software-rendering")]
+            pub use libm::atan2f as atan2;
+            #[cfg(feature = "rendering")]
+            pub use libm::roundf as round;
         }
     }
 
@@ -22,15 +46,25 @@ pub mod f32 {
     }
 }
 
-mod f64 {
+pub mod f64 {
     cfg_if::cfg_if! {
         if #[cfg(all(feature = "std"))] {
             #[inline(always)]
             pub fn powf(x: f64, y: f64) -> f64 {
                 x.powf(y)
             }
+
+            #[inline(always)]
+            pub fn sqrt(x: f64) -> f64 {
+                x.sqrt()
+            }
+
+            #[inline(always)]
+            pub fn round(x: f64) -> f64 {
+                x.round()
+            }
         } else {
-            pub use libm::pow as powf;
+            pub use libm::{pow as powf, round, sqrt};
         }
     }
 }
@@ -10,8 +10,10 @@ pub mod blank_space;
 pub mod detailed_timer;
 pub mod graph;
 pub mod key_value;
+pub mod overlay_widgets;
 pub mod separator;
 pub mod splits;
+pub mod tabs;
 pub mod text;
 pub mod timer;
 pub mod title;
@@ -20,7 +22,9 @@ pub enum Cache<L> {
     Empty,
     DetailedTimer(detailed_timer::Cache<L>),
     KeyValue(key_value::Cache<L>),
+    OverlayWidgets(overlay_widgets::Cache<L>),
     Splits(splits::Cache<L>),
+    Tabs(tabs::Cache<L>),
     Text(text::Cache<L>),
     Timer(timer::Cache<L>),
     Title(title::Cache<L>),
@@ -47,7 +51,11 @@ impl<L> Cache<L> {
         match component {
             ComponentState::DetailedTimer(_) => Self::DetailedTimer(detailed_timer::Cache::new()),
             ComponentState::KeyValue(_) => Self::KeyValue(key_value::Cache::new()),
+            ComponentState::OverlayWidgets(_) => {
+                Self::OverlayWidgets(overlay_widgets::Cache::new())
+            }
             ComponentState::Splits(_) => Self::Splits(splits::Cache::new()),
+            ComponentState::Tabs(_) => Self::Tabs(tabs::Cache::new()),
             ComponentState::Text(_) => Self::Text(text::Cache::new()),
             ComponentState::Timer(_) => Self::Timer(timer::Cache::new()),
             ComponentState::Title(_) => Self::Title(title::Cache::new()),
@@ -62,7 +70,9 @@ impl<L> Cache<L> {
     accessors! {
         DetailedTimer detailed_timer,
         KeyValue key_value,
+        OverlayWidgets overlay_widgets,
         Splits splits,
+        Tabs tabs,
         Text text,
         Timer timer,
         Title title
@@ -74,7 +84,7 @@ pub fn layout_width(layout: &LayoutState) -> f32 {
 }
 
 pub fn layout_height(layout: &LayoutState) -> f32 {
-    layout.components.iter().map(height).sum()
+    layout.components.iter().map(height).sum::<f32>() * layout.scaling.row_height_scale
 }
 
 pub fn width(component: &ComponentState) -> f32 {
@@ -83,6 +93,7 @@ pub fn width(component: &ComponentState) -> f32 {
         ComponentState::DetailedTimer(_) => 7.0,
         ComponentState::Graph(_) => 7.0,
         ComponentState::KeyValue(_) => 6.0,
+        ComponentState::OverlayWidgets(state) => overlay_widgets::width(state),
         ComponentState::Separator(_) => SEPARATOR_THICKNESS,
         ComponentState::Splits(state) => {
             let column_count = 2.0; // FIXME: Not always 2.
@@ -90,6 +101,7 @@ pub fn width(component: &ComponentState) -> f32 {
             let split_width = 2.0 + column_count * column_width;
             state.splits.len() as f32 * split_width
         }
+        ComponentState::Tabs(state) => tabs::width(state),
         ComponentState::Text(_) => 6.0,
         ComponentState::Timer(_) => 8.25,
         ComponentState::Title(_) => 8.0,
@@ -110,6 +122,7 @@ pub fn height(component: &ComponentState) -> f32 {
                 DEFAULT_COMPONENT_HEIGHT
             }
         }
+        ComponentState::OverlayWidgets(state) => overlay_widgets::height(state),
         ComponentState::Separator(_) => SEPARATOR_THICKNESS,
         ComponentState::Splits(state) => {
             state.splits.len() as f32
@@ -124,6 +137,7 @@ pub fn height(component: &ComponentState) -> f32 {
                     0.0
                 }
         }
+        ComponentState::Tabs(state) => tabs::height(state),
         ComponentState::Text(state) => {
             if state.display_two_rows {
                 TWO_ROW_HEIGHT
@@ -158,6 +172,9 @@ pub(super) fn render<A: ResourceAllocator>(
         ComponentState::KeyValue(component) => {
             key_value::render(cache.key_value(), context, dim, component, state)
         }
+        ComponentState::OverlayWidgets(component) => {
+            overlay_widgets::render(cache.overlay_widgets(), context, dim, component, state)
+        }
         ComponentState::Separator(component) => {
             cache.make_empty();
             separator::render(context, dim, component, state)
@@ -165,6 +182,9 @@ pub(super) fn render<A: ResourceAllocator>(
         ComponentState::Splits(component) => {
             splits::render(cache.splits(), context, dim, component, state)
         }
+        ComponentState::Tabs(component) => {
+            tabs::render(cache.tabs(), context, dim, component, state)
+        }
         ComponentState::Text(component) => {
             text::render(cache.text(), context, dim, component, state)
         }
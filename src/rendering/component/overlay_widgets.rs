@@ -0,0 +1,115 @@
+use crate::{
+    component::overlay_widgets::State,
+    layout::{LayoutDirection, LayoutState},
+    platform::prelude::*,
+    rendering::{
+        RenderContext,
+        consts::{DEFAULT_COMPONENT_HEIGHT, TWO_ROW_HEIGHT},
+        font::{AbbreviatedLabel, CachedLabel},
+        resource::ResourceAllocator,
+    },
+    settings::Gradient,
+};
+
+pub struct Cache<L> {
+    widgets: Vec<WidgetCache<L>>,
+}
+
+struct WidgetCache<L> {
+    label: AbbreviatedLabel<L>,
+    value: CachedLabel<L>,
+}
+
+impl<L> Cache<L> {
+    pub const fn new() -> Self {
+        Self {
+            widgets: Vec::new(),
+        }
+    }
+}
+
+const fn widget_height(component: &State) -> f32 {
+    if component.display_two_rows {
+        TWO_ROW_HEIGHT
+    } else {
+        DEFAULT_COMPONENT_HEIGHT
+    }
+}
+
+pub(in crate::rendering) fn width(component: &State) -> f32 {
+    component.widgets.len() as f32 * 6.0
+}
+
+pub(in crate::rendering) fn height(component: &State) -> f32 {
+    component.widgets.len() as f32 * widget_height(component)
+}
+
+pub(in crate::rendering) fn render<A: ResourceAllocator>(
+    cache: &mut Cache<A::Label>,
+    context: &mut RenderContext<A>,
+    [width, height]: [f32; 2],
+    component: &State,
+    layout_state: &LayoutState,
+) {
+    context.render_background([width, height], &component.background);
+
+    cache.widgets.truncate(component.widgets.len());
+    cache
+        .widgets
+        .resize_with(component.widgets.len(), || WidgetCache {
+            label: AbbreviatedLabel::new(),
+            value: CachedLabel::new(),
+        });
+
+    let label_color = component.label_color.unwrap_or(layout_state.text_color);
+    let value_color = component.value_color.unwrap_or(layout_state.text_color);
+    let progress_gradient = Gradient::Plain(component.progress_color);
+
+    // In horizontal mode the widgets are placed next to each other, each of
+    // them filling the whole height of the component.
+    let (widget_dim, offset, display_two_rows) = match layout_state.direction {
+        LayoutDirection::Vertical => {
+            let widget_height = widget_height(component);
+            (
+                [width, widget_height],
+                [0.0, widget_height],
+                component.display_two_rows,
+            )
+        }
+        LayoutDirection::Horizontal => {
+            let widget_width = width / component.widgets.len().max(1) as f32;
+            ([widget_width, height], [widget_width, 0.0], true)
+        }
+    };
+
+    let transform = context.transform;
+
+    for (widget, cache) in component.widgets.iter().zip(&mut cache.widgets) {
+        if let Some(progress) = widget.progress {
+            let [widget_width, widget_height] = widget_dim;
+            context.render_rectangle(
+                [0.0, 0.0],
+                [progress * widget_width, widget_height],
+                &progress_gradient,
+            );
+        }
+
+        context.render_key_value_component(
+            &widget.label,
+            &[],
+            &mut cache.label,
+            &widget.value,
+            &mut cache.value,
+            true,
+            widget_dim,
+            label_color,
+            value_color,
+            display_two_rows,
+        );
+
+        let [x, y] = offset;
+        context.translate(x, y);
+    }
+
+    context.transform = transform;
+}
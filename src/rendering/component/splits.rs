@@ -13,7 +13,7 @@ use crate::{
         scene::Layer,
         solid,
     },
-    settings::{Gradient, ListGradient},
+    settings::{Color, Gradient, ListGradient},
 };
 
 pub struct Cache<L> {
@@ -164,7 +164,10 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
 
     let (split_width, (delta_x, delta_y), separator_pos, split_background_bottom_right, icon_y) =
         if layout_state.direction == LayoutDirection::Horizontal {
-            let split_width = width / component.splits.len() as f32;
+            // Segments that fade in or out only take up part of their space,
+            // so the others can smoothly make room for them.
+            let visible_splits: f32 = component.splits.iter().map(|split| split.opacity).sum();
+            let split_width = width / visible_splits.max(1.0);
             (
                 split_width,
                 (split_width, 0.0),
@@ -233,26 +236,40 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
         .resize_with(component.splits.len(), SplitCache::new);
 
     for (i, (split, split_cache)) in component.splits.iter().zip(&mut cache.splits).enumerate() {
+        // Segments may be shown away from their position in the list and
+        // faded out while they are transitioning, as produced by the layout's
+        // animator.
+        context.translate(delta_x * split.offset, delta_y * split.offset);
+        let opacity = split.opacity;
+        let fade = |color: Color| Color {
+            alpha: color.alpha * opacity,
+            ..color
+        };
+
         if component.show_thin_separators && i + 1 != component.splits.len() {
             context.render_rectangle(
                 separator_pos,
                 [split_width, split_height],
-                &Gradient::Plain(layout_state.thin_separators_color),
+                &Gradient::Plain(fade(layout_state.thin_separators_color)),
             );
         }
 
         if split.is_current_split {
             context.render_background(
                 [split_width, split_height],
-                &component.current_split_gradient,
+                &component.current_split_gradient.with_opacity(opacity),
             );
         } else if let Some((even, odd)) = &split_background {
             let color = if split.index % 2 == 0 { even } else { odd };
-            context.render_background(split_background_bottom_right, color);
+            context.render_background(split_background_bottom_right, &color.with_opacity(opacity));
         }
 
         {
-            if let Some(icon) = context.create_image(&split.icon) {
+            // Images can't be faded, so the icon is only shown for as long as
+            // the segment is mostly visible.
+            if opacity >= 0.5
+                && let Some(icon) = context.create_image(&split.icon)
+            {
                 context.render_image([PADDING, icon_y], [icon_size, icon_size], icon);
             }
 
@@ -276,7 +293,7 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
                         Layer::from_updates_frequently(column.updates_frequently),
                         [right_x, split_height + TEXT_ALIGN_BOTTOM],
                         DEFAULT_TEXT_SIZE,
-                        solid(&column.visual_color),
+                        solid(&fade(column.visual_color)),
                     );
                 }
                 right_x -= max_width + PADDING;
@@ -291,14 +308,19 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
                 &mut split_cache.name,
                 [icon_right, TEXT_ALIGN_TOP],
                 DEFAULT_TEXT_SIZE,
-                text_color,
+                solid(&fade(layout_state.text_color)),
                 left_x - PADDING,
             );
         }
-        context.translate(delta_x, delta_y);
+        context.translate(
+            delta_x * (1.0 - split.offset),
+            delta_y * (1.0 - split.offset),
+        );
     }
 
     if component.show_final_separator {
+        let offset = component.splits.last().map_or(0.0, |split| split.offset);
+        context.translate(delta_x * offset, delta_y * offset);
         let (pos, end) = if layout_state.direction == LayoutDirection::Horizontal {
             (
                 [-split_width - THIN_SEPARATOR_THICKNESS, 0.0],
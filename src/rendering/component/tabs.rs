@@ -0,0 +1,103 @@
+use crate::{
+    component::tabs::State,
+    layout::{LayoutDirection, LayoutState},
+    platform::prelude::*,
+    rendering::{
+        RenderContext,
+        consts::{DEFAULT_COMPONENT_HEIGHT, DEFAULT_TEXT_SIZE, PADDING, TEXT_ALIGN_TOP},
+        font::CachedLabel,
+        resource::ResourceAllocator,
+        solid,
+    },
+};
+
+pub struct Cache<L> {
+    tab_name: CachedLabel<L>,
+    components: Vec<super::Cache<L>>,
+}
+
+impl<L> Cache<L> {
+    pub const fn new() -> Self {
+        Self {
+            tab_name: CachedLabel::new(),
+            components: Vec::new(),
+        }
+    }
+}
+
+const fn header_height(component: &State) -> f32 {
+    if component.show_tab_names {
+        DEFAULT_COMPONENT_HEIGHT
+    } else {
+        0.0
+    }
+}
+
+pub(in crate::rendering) fn width(component: &State) -> f32 {
+    component.components.iter().map(super::width).sum()
+}
+
+pub(in crate::rendering) fn height(component: &State) -> f32 {
+    header_height(component) + component.components.iter().map(super::height).sum::<f32>()
+}
+
+pub(in crate::rendering) fn render<A: ResourceAllocator>(
+    cache: &mut Cache<A::Label>,
+    context: &mut RenderContext<A>,
+    [width, height]: [f32; 2],
+    component: &State,
+    layout_state: &LayoutState,
+) {
+    context.render_background([width, height], &component.background);
+
+    cache.components.truncate(component.components.len());
+    for state in &component.components[cache.components.len()..] {
+        cache.components.push(super::Cache::new(state));
+    }
+
+    let transform = context.transform;
+
+    match layout_state.direction {
+        LayoutDirection::Vertical => {
+            if component.show_tab_names {
+                if let Some(name) = component.tab_names.get(component.selected_tab as usize) {
+                    context.render_text_centered(
+                        name,
+                        &mut cache.tab_name,
+                        PADDING,
+                        width - PADDING,
+                        [0.5 * width, TEXT_ALIGN_TOP],
+                        DEFAULT_TEXT_SIZE,
+                        solid(&layout_state.text_color),
+                    );
+                }
+                context.translate(0.0, header_height(component));
+            }
+
+            for (state, cache) in component.components.iter().zip(&mut cache.components) {
+                let height = super::height(state);
+                super::render(cache, context, state, layout_state, [width, height]);
+                context.translate(0.0, height);
+            }
+        }
+        LayoutDirection::Horizontal => {
+            // There's no room for the name of the tab in horizontal mode, so
+            // the components fill the whole component. They get scaled the
+            // same way the layout scales its own components.
+            let total_width = self::width(component);
+            let width_scaling = if total_width > 0.0 {
+                width / total_width
+            } else {
+                0.0
+            };
+
+            for (state, cache) in component.components.iter().zip(&mut cache.components) {
+                let width = super::width(state) * width_scaling;
+                super::render(cache, context, state, layout_state, [width, height]);
+                context.translate(width, 0.0);
+            }
+        }
+    }
+
+    context.transform = transform;
+}
@@ -20,6 +20,7 @@ pub struct Cache<L> {
     line1: AbbreviatedLabel<L>,
     line2: AbbreviatedLabel<L>,
     attempts: CachedLabel<L>,
+    practice: CachedLabel<L>,
     attempts_buffer: String,
 }
 
@@ -29,6 +30,7 @@ impl<L> Cache<L> {
             line1: AbbreviatedLabel::new(),
             line2: AbbreviatedLabel::new(),
             attempts: CachedLabel::new(),
+            practice: CachedLabel::new(),
             attempts_buffer: String::new(),
         }
     }
@@ -62,6 +64,7 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
     };
 
     let attempts = match (component.finished_runs, component.attempts) {
+        _ if component.is_practicing => "",
         (Some(a), Some(b)) => {
             cache.attempts_buffer.clear();
             let _ = write!(cache.attempts_buffer, "{a}/{b}");
@@ -75,14 +78,25 @@ pub(in crate::rendering) fn render<A: ResourceAllocator>(
         _ => "",
     };
 
-    let line2_end_x = context.render_numbers(
-        attempts,
-        &mut cache.attempts,
-        Layer::Bottom,
-        [width - PADDING, height + TEXT_ALIGN_BOTTOM],
-        DEFAULT_TEXT_SIZE,
-        text_color,
-    ) - PADDING;
+    let line2_end_x = if component.is_practicing {
+        context.render_text_right_align(
+            "Practice",
+            &mut cache.practice,
+            Layer::Bottom,
+            [width - PADDING, height + TEXT_ALIGN_BOTTOM],
+            DEFAULT_TEXT_SIZE,
+            text_color,
+        )
+    } else {
+        context.render_numbers(
+            attempts,
+            &mut cache.attempts,
+            Layer::Bottom,
+            [width - PADDING, height + TEXT_ALIGN_BOTTOM],
+            DEFAULT_TEXT_SIZE,
+            text_color,
+        )
+    } - PADDING;
 
     let (line1_y, line1_end_x) = if !component.line2.is_empty() {
         context.render_abbreviated_text_align(
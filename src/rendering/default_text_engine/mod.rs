@@ -2,20 +2,20 @@
 //! manage text labels. That way the underlying renderer doesn't by itself need
 //! to be able to render text, as all the text gets turned into paths.
 
-use core::str;
+use core::{fmt::Write, str};
 
 use crate::{
     platform::{Arc, RwLock, prelude::*},
-    settings::{FontStretch, FontStyle},
+    settings::{CacheLimits, CacheUsage, EvictionPolicy, FontStretch, FontStyle},
 };
 
 use cosmic_text::{
-    Attrs, AttrsList, Family, FeatureTag, FontFeatures, FontSystem, ShapeLine, Shaping, Stretch,
-    Style, Weight,
-    fontdb::{Database, ID, Query, Source},
-    rustybuzz::ttf_parser::{GlyphId, OutlineBuilder},
+    Attrs, AttrsList, Family, FeatureTag, FontFeatures, FontSystem, ShapeLine, ShapeWord, Shaping,
+    Stretch, Style, Weight,
+    fontdb::{Database, ID, Language, Query, Source},
+    rustybuzz::ttf_parser::{GlyphId, OutlineBuilder, RasterImageFormat},
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 use crate::settings;
 
@@ -25,16 +25,31 @@ use super::{FontKind, PathBuilder, Rgba, SharedOwnership, TEXT_FONT, TIMER_FONT}
 
 mod color_font;
 
-struct CachedGlyph<P> {
+struct CachedGlyph<P, I> {
     scale: f32,
     paths: Vec<(Option<Rgba>, P)>,
+    image: Option<GlyphImage<I>>,
+    last_used: u64,
+}
+
+struct GlyphCache<P, I> {
+    glyphs: HashMap<(ID, u16), CachedGlyph<P, I>>,
+    // Every lookup advances the tick, so the glyphs can be ordered by when
+    // they were used last.
+    tick: u64,
+    last_collection: u64,
+    limits: CacheLimits,
 }
 
 /// The text engine allows you to create fonts and manage text labels. That way
 /// the underlying renderer doesn't by itself need to be able to render text.
-pub struct TextEngine<P> {
+/// Glyphs that are stored as bitmaps, like most color emoji, are provided as
+/// images of the type `I`.
+pub struct TextEngine<P, I = ()> {
     font_system: FontSystem,
-    glyph_cache: HashMap<(ID, u16), CachedGlyph<P>>,
+    glyph_cache: GlyphCache<P, I>,
+    embedded_fonts: HashSet<[u8; 32]>,
+    decode_image: fn(&[u8]) -> Option<I>,
 }
 
 impl<P: SharedOwnership> Default for TextEngine<P> {
@@ -44,8 +59,18 @@ impl<P: SharedOwnership> Default for TextEngine<P> {
 }
 
 impl<P: SharedOwnership> TextEngine<P> {
-    /// Creates a new path based text engine.
+    /// Creates a new path based text engine. Glyphs that are stored as bitmaps
+    /// are not rendered.
     pub fn new() -> Self {
+        Self::with_image_decoder(|_| None)
+    }
+}
+
+impl<P: SharedOwnership, I: SharedOwnership> TextEngine<P, I> {
+    /// Creates a new path based text engine that renders glyphs stored as
+    /// bitmaps, like color emoji, as images. The function provided decodes the
+    /// PNG data of such a glyph into an image.
+    pub fn with_image_decoder(decode_image: fn(&[u8]) -> Option<I>) -> Self {
         let mut db = Database::new();
 
         #[cfg(feature = "font-loading")]
@@ -58,10 +83,99 @@ impl<P: SharedOwnership> TextEngine<P> {
             // FIXME: Whenever we introduce localization, we need to make sure
             // to use the correct locale here.
             font_system: FontSystem::new_with_locale_and_db(String::from("en-US"), db),
-            glyph_cache: HashMap::new(),
+            glyph_cache: GlyphCache {
+                glyphs: HashMap::new(),
+                tick: 0,
+                last_collection: 0,
+                limits: CacheLimits::UNLIMITED,
+            },
+            embedded_fonts: HashSet::new(),
+            decode_image,
         }
     }
 
+    /// Returns the limits the garbage collection keeps the glyph cache within.
+    /// By default the glyph cache is unlimited.
+    pub const fn glyph_cache_limits(&self) -> &CacheLimits {
+        &self.glyph_cache.limits
+    }
+
+    /// Sets the limits the garbage collection keeps the glyph cache within.
+    /// The amount of memory the glyphs use is unknown, so only the limit on
+    /// the amount of glyphs applies.
+    pub const fn set_glyph_cache_limits(&mut self, limits: CacheLimits) {
+        self.glyph_cache.limits = limits;
+    }
+
+    /// Returns how many glyphs are in the glyph cache.
+    pub fn glyph_cache_usage(&self) -> CacheUsage {
+        CacheUsage {
+            entries: self.glyph_cache.glyphs.len(),
+            bytes: 0,
+        }
+    }
+
+    /// Runs the garbage collection of the glyph cache. This removes the glyphs
+    /// that the limits don't allow to keep, starting with the least recently
+    /// used ones. Glyphs count as used when a label gets created or updated
+    /// with them. Labels keep their glyphs, so this never affects any existing
+    /// labels. Returns the amount of glyphs that got collected.
+    pub fn collect_glyphs(&mut self) -> usize {
+        let cache = &mut self.glyph_cache;
+        let used = if cache.limits.eviction_policy == EvictionPolicy::OverLimits {
+            0
+        } else {
+            cache
+                .glyphs
+                .values()
+                .filter(|glyph| glyph.last_used > cache.last_collection)
+                .count()
+        };
+        cache.last_collection = cache.tick;
+
+        let collect_count = cache
+            .glyphs
+            .len()
+            .saturating_sub(cache.limits.retained_entries(used));
+        if collect_count == 0 {
+            return 0;
+        }
+
+        let mut ticks: Vec<u64> = cache.glyphs.values().map(|glyph| glyph.last_used).collect();
+        let (_, &mut newest_collected, _) = ticks.select_nth_unstable(collect_count - 1);
+        cache
+            .glyphs
+            .retain(|_, glyph| glyph.last_used > newest_collected);
+
+        collect_count
+    }
+
+    /// Loads the font file embedded in a font, unless it was loaded before,
+    /// and returns the family name its faces can be found with. The faces get
+    /// a family name that is unique to the font file, so they are preferred
+    /// over any installed font with the same family name.
+    fn load_embedded_font(&mut self, data: &settings::FontData) -> String {
+        let id = data.id();
+        let mut family = String::from("LiveSplit Embedded ");
+        for byte in &id[..8] {
+            let _ = write!(family, "{byte:02x}");
+        }
+
+        if self.embedded_fonts.insert(*id) {
+            let mut embedded = Database::new();
+            embedded.load_font_source(Source::Binary(Arc::new(data.shared_data().clone())));
+
+            let db = self.font_system.db_mut();
+            for face in embedded.faces() {
+                let mut face = face.clone();
+                face.families = vec![(family.clone(), Language::English_UnitedStates)];
+                db.push_face_info(face);
+            }
+        }
+
+        family
+    }
+
     /// Creates a new font. You can call this directly from a
     /// [`ResourceAllocator`](super::ResourceAllocator).
     pub fn create_font(&mut self, font: Option<&settings::Font>, kind: FontKind) -> Font {
@@ -71,6 +185,7 @@ impl<P: SharedOwnership> TextEngine<P> {
         };
 
         let (mut stretch, mut style, mut weight, mut family, single, multiple, families);
+        let embedded_family;
 
         if let Some(font) = font {
             stretch = match font.stretch {
@@ -90,8 +205,18 @@ impl<P: SharedOwnership> TextEngine<P> {
                 FontStyle::Oblique => Style::Oblique,
             };
             weight = Weight(font.weight.to_u16());
-            family = font.family.as_str();
-            multiple = [Family::Name(&font.family), Family::Name(fallback_family)];
+            family = match &font.data {
+                Some(data) => {
+                    embedded_family = self.load_embedded_font(data);
+                    embedded_family.as_str()
+                }
+                None => font.family.as_str(),
+            };
+            multiple = [
+                Family::Name(family),
+                Family::Name(&font.family),
+                Family::Name(fallback_family),
+            ];
             families = &multiple[..];
         } else {
             stretch = Stretch::Normal;
@@ -203,11 +328,12 @@ impl<P: SharedOwnership> TextEngine<P> {
         text: &str,
         font: &Font,
         max_width: Option<f32>,
-    ) -> Label<P> {
+    ) -> Label<P, I> {
         let label = Arc::new(RwLock::new(LockedLabel {
             width: 0.0,
             width_without_max_width: 0.0,
             glyphs: Vec::new(),
+            images: Vec::new(),
         }));
 
         self.update_label(path_builder, &label, text, font, max_width);
@@ -220,7 +346,7 @@ impl<P: SharedOwnership> TextEngine<P> {
     pub fn update_label<PB: PathBuilder<Path = P>>(
         &mut self,
         mut path_builder: impl FnMut() -> PB,
-        label: &Label<P>,
+        label: &Label<P, I>,
         text: &str,
         font: &Font,
         max_width: Option<f32>,
@@ -228,6 +354,7 @@ impl<P: SharedOwnership> TextEngine<P> {
         let mut label = label.write().unwrap();
 
         label.glyphs.clear();
+        label.images.clear();
 
         // FIXME: Look into shape plans in 0.11
         let shape_line = ShapeLine::new(
@@ -240,89 +367,75 @@ impl<P: SharedOwnership> TextEngine<P> {
         let [mut x, mut y] = [0.0; 2];
 
         if let Some(monotonic) = &font.monotonic {
-            for span in &shape_line.spans {
-                for word in &span.words {
-                    if !word.blank {
-                        let mut glyphs = word.glyphs.iter();
-                        while let Some(glyph) = if shape_line.rtl {
-                            glyphs.next_back()
-                        } else {
-                            glyphs.next()
-                        } {
-                            let cached_glyph = cache_glyph(
-                                &mut self.glyph_cache,
-                                &mut self.font_system,
-                                glyph.font_id,
-                                glyph.glyph_id,
-                                &mut path_builder,
-                            );
-
-                            let (x_advance, x_offset) = if monotonic
-                                .digit_glyphs
-                                .contains(&(glyph.font_id, glyph.glyph_id))
-                            {
-                                (
-                                    monotonic.digit_width,
-                                    0.5 * (monotonic.digit_width - glyph.x_advance)
-                                        + glyph.x_offset,
-                                )
-                            } else {
-                                (glyph.x_advance, glyph.x_offset)
-                            };
-
-                            label
-                                .glyphs
-                                .extend(cached_glyph.paths.iter().map(|(color, path)| Glyph {
-                                    color: *color,
-                                    x: x + x_offset,
-                                    y: y - glyph.y_offset,
-                                    path: path.share(),
-                                    scale: cached_glyph.scale,
-                                }));
-
-                            x += x_advance;
-                            y -= glyph.y_advance;
-                        }
+            for word in visual_words(&shape_line) {
+                if !word.blank {
+                    let mut glyphs = word.glyphs.iter();
+                    while let Some(glyph) = if shape_line.rtl {
+                        glyphs.next_back()
                     } else {
-                        x += word.width(1.0);
+                        glyphs.next()
+                    } {
+                        let cached_glyph = cache_glyph(
+                            &mut self.glyph_cache,
+                            &mut self.font_system,
+                            glyph.font_id,
+                            glyph.glyph_id,
+                            &mut path_builder,
+                            self.decode_image,
+                        );
+
+                        let (x_advance, x_offset) = if monotonic
+                            .digit_glyphs
+                            .contains(&(glyph.font_id, glyph.glyph_id))
+                        {
+                            (
+                                monotonic.digit_width,
+                                0.5 * (monotonic.digit_width - glyph.x_advance) + glyph.x_offset,
+                            )
+                        } else {
+                            (glyph.x_advance, glyph.x_offset)
+                        };
+
+                        push_glyph(&mut label, cached_glyph, x + x_offset, y - glyph.y_offset);
+
+                        x += x_advance;
+                        y -= glyph.y_advance;
                     }
+                } else {
+                    x += word.width(1.0);
                 }
             }
         } else {
-            for span in &shape_line.spans {
-                for word in &span.words {
-                    if !word.blank {
-                        let [mut glyph_x, mut glyph_y] = [x, y];
-                        let mut glyphs = word.glyphs.iter();
-                        while let Some(glyph) = if shape_line.rtl {
-                            glyphs.next_back()
-                        } else {
-                            glyphs.next()
-                        } {
-                            let cached_glyph = cache_glyph(
-                                &mut self.glyph_cache,
-                                &mut self.font_system,
-                                glyph.font_id,
-                                glyph.glyph_id,
-                                &mut path_builder,
-                            );
-
-                            label
-                                .glyphs
-                                .extend(cached_glyph.paths.iter().map(|(color, path)| Glyph {
-                                    color: *color,
-                                    x: glyph_x + glyph.x_offset,
-                                    y: glyph_y - glyph.y_offset,
-                                    path: path.share(),
-                                    scale: cached_glyph.scale,
-                                }));
-
-                            glyph_x += glyph.x_advance;
-                            glyph_y -= glyph.y_advance;
-                        }
+            for word in visual_words(&shape_line) {
+                if !word.blank {
+                    let [mut glyph_x, mut glyph_y] = [x, y];
+                    let mut glyphs = word.glyphs.iter();
+                    while let Some(glyph) = if shape_line.rtl {
+                        glyphs.next_back()
+                    } else {
+                        glyphs.next()
+                    } {
+                        let cached_glyph = cache_glyph(
+                            &mut self.glyph_cache,
+                            &mut self.font_system,
+                            glyph.font_id,
+                            glyph.glyph_id,
+                            &mut path_builder,
+                            self.decode_image,
+                        );
+
+                        push_glyph(
+                            &mut label,
+                            cached_glyph,
+                            glyph_x + glyph.x_offset,
+                            glyph_y - glyph.y_offset,
+                        );
+
+                        glyph_x += glyph.x_advance;
+                        glyph_y -= glyph.y_advance;
                     }
-                    x += word.width(1.0);
                 }
+                x += word.width(1.0);
             }
         }
 
@@ -331,69 +444,223 @@ impl<P: SharedOwnership> TextEngine<P> {
         if let Some(max_width) = max_width
             && x > max_width
         {
-            let x_to_look_for = max_width - font.ellipsis_width;
-
-            let last_index = label
-                .glyphs
-                .iter()
-                .enumerate()
-                .rfind(|(_, g)| {
-                    x = g.x;
-                    y = g.y;
-                    g.x <= x_to_look_for
-                })
-                .map(|(i, _)| i)
-                .unwrap_or_default();
-            label.glyphs.drain(last_index..);
-
-            // FIXME: Test RTL text.
-
             let cached_glyph = cache_glyph(
                 &mut self.glyph_cache,
                 &mut self.font_system,
                 font.ellipsis_font_id,
                 font.ellipsis_glyph_id,
                 &mut path_builder,
+                self.decode_image,
             );
 
-            label
-                .glyphs
-                .extend(cached_glyph.paths.iter().map(|(color, path)| Glyph {
-                    color: *color,
-                    x,
-                    y,
-                    path: path.share(),
-                    scale: cached_glyph.scale,
-                }));
-            x += font.ellipsis_width;
+            // Glyphs stored as images are cut off just like the outlined ones.
+            let positions = |label: &LockedLabel<P, I>| {
+                let glyphs = label.glyphs.iter().map(|g| g.x);
+                glyphs
+                    .chain(label.images.iter().map(|i| i.x))
+                    .collect::<Vec<_>>()
+            };
+
+            if shape_line.rtl {
+                // The end of right-to-left text is on the left side, so that's
+                // where the text gets cut off and the ellipsis gets placed.
+                let x_to_look_for = x - (max_width - font.ellipsis_width);
+
+                let cut_x = positions(&label)
+                    .into_iter()
+                    .filter(|&glyph_x| glyph_x >= x_to_look_for)
+                    .reduce(f32::min)
+                    .unwrap_or(x);
+                label.glyphs.retain(|g| g.x >= cut_x);
+                label.images.retain(|i| i.x >= cut_x);
+
+                let shift = cut_x - font.ellipsis_width;
+                for glyph in &mut label.glyphs {
+                    glyph.x -= shift;
+                }
+                for image in &mut label.images {
+                    image.x -= shift;
+                }
+                x -= shift;
+
+                label.glyphs.splice(
+                    0..0,
+                    cached_glyph.paths.iter().map(|(color, path)| Glyph {
+                        color: *color,
+                        x: 0.0,
+                        y,
+                        path: path.share(),
+                        scale: cached_glyph.scale,
+                    }),
+                );
+            } else {
+                let x_to_look_for = max_width - font.ellipsis_width;
+
+                let positions = positions(&label);
+                let cut_x = positions
+                    .iter()
+                    .copied()
+                    .filter(|&glyph_x| glyph_x <= x_to_look_for)
+                    .reduce(f32::max)
+                    .or_else(|| positions.iter().copied().reduce(f32::min))
+                    .unwrap_or(x);
+                label.glyphs.retain(|g| g.x < cut_x);
+                label.images.retain(|i| i.x < cut_x);
+
+                push_glyph(&mut label, cached_glyph, cut_x, y);
+                x = cut_x + font.ellipsis_width;
+            }
         }
 
         label.width = x;
     }
 }
 
-fn cache_glyph<'gc, P, PB: PathBuilder<Path = P>>(
-    glyph_cache: &'gc mut HashMap<(ID, u16), CachedGlyph<P>>,
+/// Iterates over the words of the line in the order they need to be laid out
+/// from left to right.
+fn visual_words(shape_line: &ShapeLine) -> impl Iterator<Item = &ShapeWord> {
+    // The words of a span are stored in the direction of the line, so in a
+    // right-to-left line they need to be visited in reverse.
+    visual_span_order(shape_line)
+        .into_iter()
+        .flat_map(move |span_index| {
+            let words = &shape_line.spans[span_index].words;
+            (0..words.len()).map(move |i| {
+                &words[if shape_line.rtl {
+                    words.len() - 1 - i
+                } else {
+                    i
+                }]
+            })
+        })
+}
+
+/// Determines the visual order of the spans by reversing every sequence of
+/// spans at or above each embedding level, starting from the highest level
+/// down to the lowest odd level.
+///
+/// <https://www.unicode.org/reports/tr9/#L2>
+fn visual_span_order(shape_line: &ShapeLine) -> Vec<usize> {
+    let level = |span_index: usize| shape_line.spans[span_index].level.number();
+    let mut order: Vec<usize> = (0..shape_line.spans.len()).collect();
+
+    let (Some(min_level), Some(max_level)) = (
+        order.iter().map(|&i| level(i)).min(),
+        order.iter().map(|&i| level(i)).max(),
+    ) else {
+        return order;
+    };
+
+    for current_level in ((min_level | 1)..=max_level).rev() {
+        let mut start = 0;
+        while start < order.len() {
+            if level(order[start]) < current_level {
+                start += 1;
+                continue;
+            }
+            let mut end = start + 1;
+            while end < order.len() && level(order[end]) >= current_level {
+                end += 1;
+            }
+            order[start..end].reverse();
+            start = end;
+        }
+    }
+
+    order
+}
+
+fn push_glyph<P: SharedOwnership, I: SharedOwnership>(
+    label: &mut LockedLabel<P, I>,
+    cached_glyph: &CachedGlyph<P, I>,
+    x: f32,
+    y: f32,
+) {
+    label
+        .glyphs
+        .extend(cached_glyph.paths.iter().map(|(color, path)| Glyph {
+            color: *color,
+            x,
+            y,
+            path: path.share(),
+            scale: cached_glyph.scale,
+        }));
+
+    if let Some(image) = &cached_glyph.image {
+        label.images.push(GlyphImage {
+            x: x + image.x,
+            y: y + image.y,
+            width: image.width,
+            height: image.height,
+            image: image.image.share(),
+        });
+    }
+}
+
+fn cache_glyph<'gc, P, I, PB: PathBuilder<Path = P>>(
+    glyph_cache: &'gc mut GlyphCache<P, I>,
     font_system: &mut FontSystem,
     font_id: ID,
     glyph_id: u16,
     path_builder: &mut impl FnMut() -> PB,
-) -> &'gc mut CachedGlyph<P> {
-    glyph_cache.entry((font_id, glyph_id)).or_insert_with(|| {
-        let font = font_system.get_font(font_id).unwrap();
-        let font = font.rustybuzz();
-        let mut paths = Vec::new();
-        let color_tables = ColorTables::new(font);
-        let glyph = GlyphId(glyph_id);
-        color_font::iter_colored_glyphs(&color_tables, 0, glyph, |glyph, color| {
-            let mut builder = GlyphBuilder(path_builder());
-            font.outline_glyph(glyph, &mut builder);
-            let path = builder.0.finish();
-            paths.push((color.map(|c| c.to_array()), path));
+    decode_image: fn(&[u8]) -> Option<I>,
+) -> &'gc mut CachedGlyph<P, I> {
+    glyph_cache.tick += 1;
+    let tick = glyph_cache.tick;
+    let glyph = glyph_cache
+        .glyphs
+        .entry((font_id, glyph_id))
+        .or_insert_with(|| {
+            let font = font_system.get_font(font_id).unwrap();
+            let font = font.rustybuzz();
+            let glyph = GlyphId(glyph_id);
+
+            // Color emoji are often stored as bitmaps in the sbix or CBDT tables.
+            // We use the largest size available, as the glyphs get scaled down.
+            let image = font
+                .glyph_raster_image(glyph, u16::MAX)
+                .filter(|raster| {
+                    raster.format == RasterImageFormat::PNG && raster.pixels_per_em != 0
+                })
+                .and_then(|raster| {
+                    let pixels_per_em = raster.pixels_per_em as f32;
+                    let [x, y, width, height] = [
+                        raster.x as f32,
+                        raster.y as f32,
+                        raster.width as f32,
+                        raster.height as f32,
+                    ]
+                    .map(|v| v / pixels_per_em);
+                    Some(GlyphImage {
+                        x,
+                        // The offset is to the bottom of the image and points up.
+                        y: -(y + height),
+                        width,
+                        height,
+                        image: decode_image(raster.data)?,
+                    })
+                });
+
+            let mut paths = Vec::new();
+            if image.is_none() {
+                let color_tables = ColorTables::new(font);
+                color_font::iter_colored_glyphs(&color_tables, 0, glyph, |glyph, color| {
+                    let mut builder = GlyphBuilder(path_builder());
+                    font.outline_glyph(glyph, &mut builder);
+                    let path = builder.0.finish();
+                    paths.push((color.map(|c| c.to_array()), path));
+                });
+            }
+            let scale = f32::recip(font.units_per_em() as _);
+            CachedGlyph {
+                scale,
+                paths,
+                image,
+                last_used: tick,
+            }
         });
-        let scale = f32::recip(font.units_per_em() as _);
-        CachedGlyph { scale, paths }
-    })
+    glyph.last_used = tick;
+    glyph
 }
 
 struct MonotonicInfo {
@@ -431,24 +698,31 @@ impl<PB: PathBuilder> OutlineBuilder for GlyphBuilder<PB> {
 }
 
 /// The label to use in the [`ResourceAllocator`](super::ResourceAllocator).
-pub type Label<P> = Arc<RwLock<LockedLabel<P>>>;
+pub type Label<P, I = ()> = Arc<RwLock<LockedLabel<P, I>>>;
 
 /// You need to lock the [`Label`] to use it. This is the locked type that
 /// provides all the methods you need to use the label.
-pub struct LockedLabel<P> {
+pub struct LockedLabel<P, I = ()> {
     width: f32,
     width_without_max_width: f32,
     glyphs: Vec<Glyph<P>>,
+    images: Vec<GlyphImage<I>>,
 }
 
-impl<P> LockedLabel<P> {
+impl<P, I> LockedLabel<P, I> {
     /// The glyphs to render.
     pub fn glyphs(&self) -> &[Glyph<P>] {
         &self.glyphs
     }
+
+    /// The glyphs that are stored as images, such as color emoji. They need to
+    /// be rendered in addition to the [`glyphs`](Self::glyphs).
+    pub fn images(&self) -> &[GlyphImage<I>] {
+        &self.images
+    }
 }
 
-impl<P> super::Label for Label<P> {
+impl<P, I> super::Label for Label<P, I> {
     fn width(&self, scale: f32) -> f32 {
         self.read().unwrap().width * scale
     }
@@ -472,3 +746,17 @@ pub struct Glyph<P> {
     /// The scale of the glyph.
     pub scale: f32,
 }
+
+/// A glyph that is stored as an image, such as a color emoji.
+pub struct GlyphImage<I> {
+    /// The x-coordinate of the left edge of the image.
+    pub x: f32,
+    /// The y-coordinate of the top edge of the image.
+    pub y: f32,
+    /// The width of the image.
+    pub width: f32,
+    /// The height of the image.
+    pub height: f32,
+    /// The image to render.
+    pub image: I,
+}
@@ -3,7 +3,7 @@ use core::{
     mem,
 };
 
-use crate::settings::BackgroundImage;
+use crate::settings::{BackgroundImage, BlendMode};
 
 use super::{
     Background, FillShader, Rgba, Transform,
@@ -83,6 +83,13 @@ pub enum Entity<P, I, L> {
     /// A text label with a [`FillShader`] that describes the text color and an
     /// optional text shadow.
     Label(LabelHandle<L>, FillShader, Option<Rgba>, Transform),
+    /// Starts a group of entities. All the entities up to the next
+    /// [`Entity::EndGroup`] are supposed to be drawn on their own and then
+    /// composited as a whole onto the entities below, with the opacity and the
+    /// [`BlendMode`] provided. Groups are never nested.
+    BeginGroup(f32, BlendMode),
+    /// Ends the group started by the last [`Entity::BeginGroup`].
+    EndGroup,
 }
 
 pub fn calculate_hash<P, I, L>(
@@ -132,6 +139,17 @@ fn hash_shader(shader: &FillShader, state: &mut impl Hasher) {
             hash_floats(l, state);
             hash_floats(r, state);
         }
+        FillShader::AngledGradient(s, e, d) => {
+            hash_floats(s, state);
+            hash_floats(e, state);
+            d.map(f32::to_bits).hash(state);
+        }
+        FillShader::RadialGradient(s, e, c, v) | FillShader::ConicGradient(s, e, c, v) => {
+            hash_floats(s, state);
+            hash_floats(e, state);
+            c.map(f32::to_bits).hash(state);
+            v.to_bits().hash(state);
+        }
     }
 }
 
@@ -163,6 +181,11 @@ impl<P, I, L> Hash for Entity<P, I, L> {
                 }
                 hash_transform(transform, state);
             }
+            Entity::BeginGroup(opacity, blend_mode) => {
+                hash_float(*opacity, state);
+                blend_mode.hash(state);
+            }
+            Entity::EndGroup => {}
         }
     }
 }
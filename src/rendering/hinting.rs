@@ -0,0 +1,78 @@
+use super::Transform;
+use crate::platform::math::f32::round;
+
+/// Describes how text and lines are aligned to the pixel grid of the display.
+/// Aligning them results in crisper output, especially on displays with a
+/// fractional device pixel ratio, at the cost of slightly shifting the
+/// positions of the elements. The backend coordinate space is assumed to be
+/// the pixel grid.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Hinting {
+    /// Nothing is aligned to the pixel grid. Everything is placed exactly
+    /// where the layout puts it, which keeps the proportions the same at any
+    /// size.
+    #[default]
+    None,
+    /// The edges of rectangles, such as separators and backgrounds, and the
+    /// baselines of the text are aligned to the pixel grid. Lines are at least
+    /// one logical pixel thick. The text is still placed horizontally with
+    /// subpixel precision.
+    Vertical,
+    /// In addition to what is aligned with [`Hinting::Vertical`], the text is
+    /// also aligned to the pixel grid horizontally.
+    Full,
+}
+
+/// The pixel grid the scene gets aligned to.
+#[derive(Copy, Clone)]
+pub struct PixelGrid {
+    pub hinting: Hinting,
+    pub device_pixel_ratio: f32,
+}
+
+impl PixelGrid {
+    /// Aligns the origin of a text label to the pixel grid.
+    pub fn snap_text(&self, mut transform: Transform) -> Transform {
+        match self.hinting {
+            Hinting::None => {}
+            Hinting::Vertical => transform.y = round(transform.y),
+            Hinting::Full => {
+                transform.x = round(transform.x);
+                transform.y = round(transform.y);
+            }
+        }
+        transform
+    }
+
+    /// Aligns the edges of the unit square the transform maps to a rectangle
+    /// to the pixel grid.
+    pub fn snap_rectangle(&self, mut transform: Transform) -> Transform {
+        if self.hinting != Hinting::None {
+            let min_size = round(self.device_pixel_ratio).max(1.0);
+            (transform.x, transform.scale_x) = snap_span(transform.x, transform.scale_x, min_size);
+            (transform.y, transform.scale_y) = snap_span(transform.y, transform.scale_y, min_size);
+        }
+        transform
+    }
+}
+
+fn snap_span(start: f32, size: f32, min_size: f32) -> (f32, f32) {
+    let (low, high) = if size < 0.0 {
+        (start + size, start)
+    } else {
+        (start, start + size)
+    };
+
+    let (mut snapped_low, mut snapped_high) = (round(low), round(high));
+    // Thin lines would otherwise vanish or get thinner than a logical pixel.
+    if high > low && snapped_high - snapped_low < min_size {
+        snapped_low = round(0.5 * (low + high - min_size));
+        snapped_high = snapped_low + min_size;
+    }
+
+    if size < 0.0 {
+        (snapped_high, snapped_low - snapped_high)
+    } else {
+        (snapped_low, snapped_high - snapped_low)
+    }
+}
@@ -9,7 +9,10 @@
 //! fonts and labels. An optional software renderer is available behind the
 //! `software-rendering` feature that uses tiny-skia to efficiently render the
 //! paths on the CPU. It is surprisingly fast and can be considered the default
-//! renderer.
+//! renderer. A renderer that uses the GPU through wgpu is available behind the
+//! `wgpu-rendering` feature. Layouts can also be shown as text in terminals
+//! with the renderer behind the `terminal-rendering` feature, which works on
+//! the [`LayoutState`] directly instead of a [`Scene`].
 
 // # Coordinate spaces used in this module
 //
@@ -74,6 +77,7 @@ mod component;
 mod consts;
 mod entity;
 mod font;
+mod hinting;
 mod icon;
 mod resource;
 mod scene;
@@ -85,8 +89,12 @@ pub mod default_text_engine;
 pub mod software;
 #[cfg(feature = "svg-rendering")]
 pub mod svg;
+#[cfg(feature = "terminal-rendering")]
+pub mod terminal;
 #[cfg(all(target_family = "wasm", feature = "web-rendering"))]
 pub mod web;
+#[cfg(feature = "wgpu-rendering")]
+pub mod wgpu;
 
 use self::{
     consts::{
@@ -94,13 +102,19 @@ use self::{
         TWO_ROW_HEIGHT,
     },
     font::{AbbreviatedLabel, CachedLabel, FontCache},
+    hinting::PixelGrid,
     icon::{CachedImage, ImageHandle},
     resource::Handles,
 };
 use crate::{
     layout::{LayoutDirection, LayoutState},
-    platform::prelude::*,
-    settings::{self, BackgroundImage, Color, Gradient, ImageCache, ImageId, LayoutBackground},
+    platform::{
+        math::f32::{abs, sin_cos},
+        prelude::*,
+    },
+    settings::{
+        self, BackgroundImage, Color, Gradient, ImageCache, ImageFit, ImageId, LayoutBackground,
+    },
 };
 use alloc::borrow::Cow;
 use bytemuck_derive::{Pod, Zeroable};
@@ -109,6 +123,7 @@ use core::iter;
 pub use self::{
     entity::Entity,
     font::{TEXT_FONT, TIMER_FONT},
+    hinting::Hinting,
     resource::{
         FontKind, Handle, Image, Label, LabelHandle, PathBuilder, ResourceAllocator,
         SharedOwnership,
@@ -146,6 +161,43 @@ pub enum FillShader {
     VerticalGradient(Rgba, Rgba),
     /// Use a horizontal gradient (left, right) to fill the path.
     HorizontalGradient(Rgba, Rgba),
+    /// Use a gradient (start, end) along the direction provided as a unit
+    /// vector to fill the path. The gradient spans the path's bounding box,
+    /// with the start color at the corner furthest in the opposite direction
+    /// and the end color at the corner furthest in the direction. Use
+    /// [`angled_gradient_points`] to calculate these points.
+    AngledGradient(Rgba, Rgba, [f32; 2]),
+    /// Use a radial gradient (center, edge) to fill the path. The center `[x,
+    /// y]` and the radius are relative to the path's bounding box, so the
+    /// gradient is an ellipse that is stretched along with the bounding box.
+    /// Renderers that can only draw circular gradients may use the larger
+    /// radius of the ellipse instead.
+    RadialGradient(Rgba, Rgba, [f32; 2], f32),
+    /// Use a conic gradient (start, end) to fill the path. It sweeps clockwise
+    /// around the center `[x, y]`, which is relative to the path's bounding
+    /// box, starting at the angle provided in degrees, where 0° points straight
+    /// up. Renderers that can't draw conic gradients may approximate them with
+    /// a linear gradient along the direction the sweep starts at.
+    ConicGradient(Rgba, Rgba, [f32; 2], f32),
+}
+
+/// Calculates the start and end points of an angled gradient with the
+/// direction provided, spanning the bounding box (`[left, right]`, `[top,
+/// bottom]`). The direction is interpreted relative to the bounding box, so a
+/// direction of 45° always goes from one corner to the opposite corner.
+pub fn angled_gradient_points(
+    [dx, dy]: [f32; 2],
+    [left, right]: [f32; 2],
+    [top, bottom]: [f32; 2],
+) -> [[f32; 2]; 2] {
+    let half_length = 0.5 * (abs(dx) + abs(dy));
+    let (width, height) = (right - left, bottom - top);
+    let (center_x, center_y) = (left + 0.5 * width, top + 0.5 * height);
+    let (offset_x, offset_y) = (dx * half_length * width, dy * half_length * height);
+    [
+        [center_x - offset_x, center_y - offset_y],
+        [center_x + offset_x, center_y + offset_y],
+    ]
 }
 
 /// The background of the bottom layer of the scene.
@@ -178,6 +230,7 @@ pub struct SceneManager<P, I, F, L> {
     cached_size: Option<CachedSize>,
     fonts: FontCache<F>,
     images: ImageCache<CachedImage<I>>,
+    grid: PixelGrid,
 }
 
 impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager<P, I, F, L> {
@@ -196,9 +249,26 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
             cached_size: None,
             fonts,
             images: ImageCache::new(),
+            grid: PixelGrid {
+                hinting: Hinting::None,
+                device_pixel_ratio: 1.0,
+            },
         }
     }
 
+    /// Sets how text and lines are aligned to the pixel grid. By default
+    /// nothing is aligned to it.
+    pub const fn set_hinting(&mut self, hinting: Hinting) {
+        self.grid.hinting = hinting;
+    }
+
+    /// Sets the amount of pixels of the backend coordinate space per logical
+    /// pixel of the display. With hinting, this is the minimum thickness of
+    /// lines, rounded to whole pixels. By default this is 1.
+    pub const fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f32) {
+        self.grid.device_pixel_ratio = device_pixel_ratio;
+    }
+
     /// Accesses the [`Scene`] in order to render the [`Entities`](Entity).
     pub const fn scene(&self) -> &Scene<P, I, L> {
         &self.scene
@@ -285,6 +355,7 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
             images: &mut self.images,
             image_cache,
             state,
+            grid: self.grid,
         };
 
         let background = context.decode_layout_background(&state.background, resolution);
@@ -305,14 +376,28 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
         // mode, all the components have the same width.
         let width = aspect_ratio * total_height;
 
-        for (component, cache) in state.components.iter().zip(&mut self.components) {
+        let row_height_scale = state.scaling.row_height_scale;
+
+        for (index, (component, cache)) in state
+            .components
+            .iter()
+            .zip(&mut self.components)
+            .enumerate()
+        {
             let height = component::height(component);
             let dim = [width, height];
+            // If the rows are scaled up, the component is centered within the
+            // additional space.
+            let margin = 0.5 * (row_height_scale - 1.0) * height;
+            context.translate(0.0, margin);
+            let group = context.begin_group(index);
+            context.render_component_background_image(index, dim);
             component::render(cache, &mut context, component, state, dim);
+            context.end_group(group);
             // We translate the coordinate space to the Component Coordinate
             // Space of the next component by shifting by the height of the
             // current component in the Component Coordinate Space.
-            context.translate(0.0, height);
+            context.translate(0.0, height + margin);
         }
 
         self.next_id = context.handles.into_next_id();
@@ -359,6 +444,7 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
             images: &mut self.images,
             image_cache,
             state,
+            grid: self.grid,
         };
 
         let background = context.decode_layout_background(&state.background, resolution);
@@ -382,11 +468,19 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
         // distribute to each of the components. This factor is this adjustment.
         let width_scaling = TWO_ROW_HEIGHT * aspect_ratio / total_width;
 
-        for (component, cache) in state.components.iter().zip(&mut self.components) {
+        for (index, (component, cache)) in state
+            .components
+            .iter()
+            .zip(&mut self.components)
+            .enumerate()
+        {
             let width = component::width(component) * width_scaling;
             let height = TWO_ROW_HEIGHT;
             let dim = [width, height];
+            let group = context.begin_group(index);
+            context.render_component_background_image(index, dim);
             component::render(cache, &mut context, component, state, dim);
+            context.end_group(group);
             // We translate the coordinate space to the Component Coordinate
             // Space of the next component by shifting by the width of the
             // current component in the Component Coordinate Space.
@@ -399,6 +493,9 @@ impl<P: SharedOwnership, I: SharedOwnership, F, L: SharedOwnership> SceneManager
     }
 }
 
+// The maximum number of copies of a tiled component background image.
+const MAX_BACKGROUND_IMAGE_TILES: f32 = 64.0;
+
 struct RenderContext<'b, A: ResourceAllocator> {
     transform: Transform,
     handles: Handles<A>,
@@ -407,6 +504,14 @@ struct RenderContext<'b, A: ResourceAllocator> {
     images: &'b mut ImageCache<CachedImage<A::Image>>,
     image_cache: &'b ImageCache,
     state: &'b LayoutState,
+    grid: PixelGrid,
+}
+
+struct Group {
+    opacity: f32,
+    blend_mode: settings::BlendMode,
+    bottom_start: usize,
+    top_start: usize,
 }
 
 impl<A: ResourceAllocator> RenderContext<'_, A> {
@@ -414,22 +519,54 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         self.scene.rectangle()
     }
 
+    /// Remembers where the entities of the component with the index provided
+    /// start, if the component needs to be composited as a group.
+    fn begin_group(&self, index: usize) -> Option<Group> {
+        let appearance = self.state.component_appearances.get(index)?;
+        if appearance.is_composited_normally() {
+            return None;
+        }
+        Some(Group {
+            opacity: appearance.opacity.clamp(0.0, 1.0),
+            blend_mode: appearance.blend_mode,
+            bottom_start: self.scene.bottom_layer().len(),
+            top_start: self.scene.top_layer().len(),
+        })
+    }
+
+    /// Wraps the entities placed since the group began on each of the layers
+    /// into a group.
+    fn end_group(&mut self, group: Option<Group>) {
+        let Some(group) = group else { return };
+        for (layer, start) in [
+            (Layer::Bottom, group.bottom_start),
+            (Layer::Top, group.top_start),
+        ] {
+            let entities = self.scene.layer_mut(layer);
+            if entities.len() > start {
+                entities.insert(start, Entity::BeginGroup(group.opacity, group.blend_mode));
+                entities.push(Entity::EndGroup);
+            }
+        }
+    }
+
     fn render_background(&mut self, [w, h]: Pos, gradient: &Gradient) {
-        if let Some(shader) = decode_gradient(gradient) {
+        if let Some(shader) = decode_gradient(gradient, self.state.animation_time) {
             let rectangle = self.rectangle();
             self.scene.bottom_layer_mut().push(Entity::FillPath(
                 rectangle,
                 shader,
-                self.transform.pre_scale(w, h),
+                self.grid.snap_rectangle(self.transform.pre_scale(w, h)),
             ));
         }
     }
 
     fn backend_render_rectangle(&mut self, [x1, y1]: Pos, [x2, y2]: Pos, shader: FillShader) {
-        let transform = self
-            .transform
-            .pre_translate(x1, y1)
-            .pre_scale(x2 - x1, y2 - y1);
+        let transform = self.grid.snap_rectangle(
+            self.transform
+                .pre_translate(x1, y1)
+                .pre_scale(x2 - x1, y2 - y1),
+        );
 
         let rectangle = self.rectangle();
 
@@ -445,10 +582,11 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         shader: FillShader,
         layer: Layer,
     ) {
-        let transform = self
-            .transform
-            .pre_translate(x1, y1)
-            .pre_scale(x2 - x1, y2 - y1);
+        let transform = self.grid.snap_rectangle(
+            self.transform
+                .pre_translate(x1, y1)
+                .pre_scale(x2 - x1, y2 - y1),
+        );
 
         let rectangle = self.rectangle();
 
@@ -508,7 +646,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
     }
 
     fn render_rectangle(&mut self, top_left: Pos, bottom_right: Pos, gradient: &Gradient) {
-        if let Some(colors) = decode_gradient(gradient) {
+        if let Some(colors) = decode_gradient(gradient, self.state.animation_time) {
             self.backend_render_rectangle(top_left, bottom_right, colors);
         }
     }
@@ -520,7 +658,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         gradient: &Gradient,
         layer: Layer,
     ) {
-        if let Some(colors) = decode_gradient(gradient) {
+        if let Some(colors) = decode_gradient(gradient, self.state.animation_time) {
             self.backend_render_layer_rectangle(top_left, bottom_right, colors, layer);
         }
     }
@@ -546,6 +684,15 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             height = new_height;
         }
 
+        self.render_stretched_image([x, y], [width, height], &image);
+    }
+
+    fn render_stretched_image(
+        &mut self,
+        [x, y]: Pos,
+        [width, height]: Pos,
+        image: &ImageHandle<A::Image>,
+    ) {
         let transform = self.transform.pre_translate(x, y).pre_scale(width, height);
 
         self.scene
@@ -553,6 +700,42 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             .push(Entity::Image(image.handle.share(), transform));
     }
 
+    /// Renders the background image of the component with the index provided
+    /// below everything else the component draws.
+    fn render_component_background_image(&mut self, index: usize, dim @ [width, height]: Pos) {
+        let Some(background) = self
+            .state
+            .component_appearances
+            .get(index)
+            .and_then(|appearance| appearance.background_image)
+        else {
+            return;
+        };
+        let Some(image) = self.create_image(&background.image) else {
+            return;
+        };
+
+        match background.fit {
+            ImageFit::Fit => self.render_image([0.0, 0.0], dim, image),
+            ImageFit::Stretch => self.render_stretched_image([0.0, 0.0], dim, &image),
+            ImageFit::Tile => {
+                // Each copy is as tall as the component. They are stretched a
+                // little, so that there are no partial copies at the end. Very
+                // narrow images are limited to a reasonable amount of copies.
+                let count = (width / (height * image.handle.aspect_ratio()) + 0.5)
+                    .clamp(1.0, MAX_BACKGROUND_IMAGE_TILES) as usize;
+                let tile_width = width / count as f32;
+                for i in 0..count {
+                    self.render_stretched_image(
+                        [i as f32 * tile_width, 0.0],
+                        [tile_width, height],
+                        &image,
+                    );
+                }
+            }
+        }
+    }
+
     fn render_key_value_component(
         &mut self,
         key: &str,
@@ -599,6 +782,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         shader: FillShader,
         max_x: f32,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(
             abbreviations,
             &mut self.handles,
@@ -610,7 +794,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::left_aligned(&self.transform, pos, scale),
+            self.grid
+                .snap_text(font::left_aligned(&self.transform, pos, scale)),
         ));
 
         x + label.width(scale)
@@ -625,6 +810,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         shader: FillShader,
         max_x: f32,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(
             text,
             &mut self.handles,
@@ -636,7 +822,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::left_aligned(&self.transform, pos, scale),
+            self.grid
+                .snap_text(font::left_aligned(&self.transform, pos, scale)),
         ));
 
         x + label.width(scale)
@@ -652,6 +839,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         scale: f32,
         shader: FillShader,
     ) {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(
             text,
             &mut self.handles,
@@ -663,14 +851,14 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::centered(
+            self.grid.snap_text(font::centered(
                 &self.transform,
                 pos,
                 scale,
                 label.width(scale),
                 min_x,
                 max_x,
-            ),
+            )),
         ));
     }
 
@@ -684,6 +872,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         scale: f32,
         shader: FillShader,
     ) {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(
             abbreviations,
             &mut self.handles,
@@ -695,14 +884,14 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::centered(
+            self.grid.snap_text(font::centered(
                 &self.transform,
                 pos,
                 scale,
                 label.width(scale),
                 min_x,
                 max_x,
-            ),
+            )),
         ));
     }
 
@@ -715,6 +904,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         scale: f32,
         shader: FillShader,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(text, &mut self.handles, &mut self.fonts.text.font, None);
         let width = label.width(scale);
 
@@ -722,7 +912,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::right_aligned(&self.transform, pos, scale, width),
+            self.grid
+                .snap_text(font::right_aligned(&self.transform, pos, scale, width)),
         ));
 
         x - width
@@ -763,6 +954,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         scale: f32,
         shader: FillShader,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(text, &mut self.handles, &mut self.fonts.times.font, None);
         let width = label.width(scale);
 
@@ -770,7 +962,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::right_aligned(&self.transform, pos, scale, width),
+            self.grid
+                .snap_text(font::right_aligned(&self.transform, pos, scale, width)),
         ));
 
         x - width
@@ -785,6 +978,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         scale: f32,
         shader: FillShader,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(text, &mut self.handles, &mut self.fonts.timer.font, None);
         let width = label.width(scale);
 
@@ -792,7 +986,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
             label.share(),
             shader,
             self.state.text_shadow.as_ref().map(Color::to_array),
-            font::right_aligned(&self.transform, pos, scale, width),
+            self.grid
+                .snap_text(font::right_aligned(&self.transform, pos, scale, width)),
         ));
 
         x - width
@@ -804,6 +999,7 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         label: &mut CachedLabel<A::Label>,
         scale: f32,
     ) -> f32 {
+        let scale = scale * self.state.scaling.text_scale;
         let label = label.update(text, &mut self.handles, &mut self.fonts.times.font, None);
         label.width(scale)
     }
@@ -814,7 +1010,9 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
         [mut width, mut height]: [f32; 2],
     ) -> Option<Background<A::Image>> {
         Some(match background {
-            LayoutBackground::Gradient(gradient) => Background::Shader(decode_gradient(gradient)?),
+            LayoutBackground::Gradient(gradient) => {
+                Background::Shader(decode_gradient(gradient, self.state.animation_time)?)
+            }
             LayoutBackground::Image(background_image) => {
                 let image = self.create_image(&background_image.image)?;
 
@@ -848,8 +1046,8 @@ impl<A: ResourceAllocator> RenderContext<'_, A> {
     }
 }
 
-const fn decode_gradient(gradient: &Gradient) -> Option<FillShader> {
-    Some(match gradient {
+fn decode_gradient(gradient: &Gradient, time: f64) -> Option<FillShader> {
+    Some(match gradient.at(time) {
         Gradient::Transparent => return None,
         Gradient::Horizontal(left, right) => {
             FillShader::HorizontalGradient(left.to_array(), right.to_array())
@@ -857,6 +1055,18 @@ const fn decode_gradient(gradient: &Gradient) -> Option<FillShader> {
         Gradient::Vertical(top, bottom) => {
             FillShader::VerticalGradient(top.to_array(), bottom.to_array())
         }
+        Gradient::Angled(start, end, angle) => {
+            let (sin, cos) = sin_cos(angle.to_radians());
+            FillShader::AngledGradient(start.to_array(), end.to_array(), [sin, cos])
+        }
+        Gradient::Radial(center, edge, position, radius) => {
+            FillShader::RadialGradient(center.to_array(), edge.to_array(), position, radius)
+        }
+        Gradient::Conic(start, end, position, angle) => {
+            FillShader::ConicGradient(start.to_array(), end.to_array(), position, angle)
+        }
+        // Animated gradients are already resolved at this point.
+        Gradient::HueCycling(..) | Gradient::Rotating(..) => return None,
         Gradient::Plain(plain) => FillShader::SolidColor(plain.to_array()),
     })
 }
@@ -896,7 +1106,7 @@ impl Transform {
     }
 
     #[cfg(feature = "software-rendering")]
-    fn transform_y(&self, y: f32) -> f32 {
-        self.y + self.scale_y * y
+    fn transform_point(&self, [x, y]: Pos) -> Pos {
+        [self.x + self.scale_x * x, self.y + self.scale_y * y]
     }
 }
@@ -2,21 +2,32 @@
 //! surprisingly fast and can be considered the default rendering backend.
 
 use super::{
-    FillShader, FontKind, Scene, SceneManager, SharedOwnership, Transform,
+    FillShader, FontKind, Hinting, Scene, SceneManager, SharedOwnership, Transform,
+    angled_gradient_points,
     consts::SHADOW_OFFSET,
     default_text_engine::{Font, Label, TextEngine},
     entity::Entity,
     resource::{self, ResourceAllocator},
 };
 use crate::{
-    layout::LayoutState, platform::prelude::*, rendering::Background, settings,
-    settings::ImageCache,
+    layout::LayoutState,
+    platform::{
+        math::f32::{abs, atan2},
+        prelude::*,
+    },
+    rendering::Background,
+    settings,
+    settings::{CacheLimits, CacheUsage, ImageCache},
 };
 use alloc::rc::Rc;
-use core::{mem, ops::Deref};
+use core::{
+    array, iter, mem,
+    ops::{Deref, Range},
+};
 use tiny_skia::{
-    BlendMode, Color, FillRule, FilterQuality, GradientStop, LinearGradient, Paint, Path,
-    PathBuilder, Pattern, Pixmap, PixmapMut, Point, Rect, Shader, SpreadMode, Stroke,
+    BlendMode, Color, FillRule, FilterQuality, GradientStop, LinearGradient, Mask, Paint, Path,
+    PathBuilder, Pattern, Pixmap, PixmapMut, PixmapPaint, Point, PremultipliedColorU8,
+    RadialGradient, Rect, Shader, SpreadMode, Stroke,
 };
 use tiny_skia_path::NormalizedF32;
 
@@ -27,6 +38,8 @@ use image::{ImageBuffer, imageops::FilterType};
 #[cfg(feature = "image")]
 use tiny_skia_path::IntSize;
 
+#[cfg(feature = "image")]
+use crate::{layout::Layout, timing::Snapshot};
 #[cfg(feature = "image")]
 pub use image::{self, RgbaImage};
 
@@ -35,11 +48,148 @@ struct SkiaBuilder(PathBuilder);
 type SkiaPath = Option<UnsafeRc<Path>>;
 type SkiaImage = UnsafeRc<Image>;
 type SkiaFont = Font;
-type SkiaLabel = Label<SkiaPath>;
+type SkiaLabel = Label<SkiaPath, SkiaImage>;
 
-struct Image {
+pub(super) struct Image {
     pixmap: Pixmap,
     aspect_ratio: f32,
+    #[cfg(feature = "svg-images")]
+    svg: Option<SvgImage>,
+}
+
+/// An SVG image gets rasterized at the exact size it is drawn at. The most
+/// recent rasterization is kept around, so it only needs to be redone when the
+/// size changes.
+#[cfg(feature = "svg-images")]
+struct SvgImage {
+    tree: resvg::usvg::Tree,
+    rasterized: std::sync::Mutex<Option<Pixmap>>,
+}
+
+/// The largest width or height an SVG image is rasterized at.
+#[cfg(feature = "svg-images")]
+const MAX_SVG_SIZE: u32 = 4096;
+
+impl Image {
+    /// Decodes the image from the data provided. The pixels are stored with
+    /// premultiplied alpha.
+    pub(super) fn decode(_data: &[u8]) -> Option<Self> {
+        #[cfg(feature = "svg-images")]
+        if settings::is_svg(_data) {
+            return create_svg_image(_data);
+        }
+
+        #[cfg(feature = "image")]
+        {
+            let mut buf = image::load_from_memory(_data).ok()?.to_rgba8();
+
+            // Premultiplication
+            for [r, g, b, a] in bytemuck::cast_slice_mut::<u8, [u8; 4]>(&mut buf) {
+                // If it's opaque we can skip the entire pixel. However this
+                // hurts vectorization, so we want to avoid it if the compiler
+                // can vectorize the loop. WASM, PowerPC, and MIPS are
+                // unaffected at the moment.
+                #[cfg(not(any(target_feature = "avx2", target_feature = "neon")))]
+                if *a == 0xFF {
+                    continue;
+                }
+                let a = *a as u16;
+                *r = ((*r as u16 * a) / 255) as u8;
+                *g = ((*g as u16 * a) / 255) as u8;
+                *b = ((*b as u16 * a) / 255) as u8;
+            }
+
+            let (width, height) = (buf.width(), buf.height());
+
+            let pixmap = Pixmap::from_vec(buf.into_raw(), IntSize::from_wh(width, height)?)?;
+
+            Some(Image {
+                pixmap,
+                aspect_ratio: width as f32 / height as f32,
+                #[cfg(feature = "svg-images")]
+                svg: None,
+            })
+        }
+        #[cfg(not(feature = "image"))]
+        {
+            None
+        }
+    }
+
+    /// The pixels of the image at its intrinsic size.
+    #[cfg(feature = "wgpu-rendering")]
+    pub(super) const fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    /// The width of the image divided by its height.
+    #[cfg(feature = "wgpu-rendering")]
+    pub(super) const fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    /// Calls the closure with the pixels to use for drawing the image with the
+    /// transform provided, which maps the unit square to the image's area.
+    pub(super) fn with_pixmap<R>(&self, _transform: &Transform, f: impl FnOnce(&Pixmap) -> R) -> R {
+        #[cfg(feature = "svg-images")]
+        if let Some(svg) = &self.svg {
+            let width = ((_transform.scale_x.abs() + 0.5) as u32).clamp(1, MAX_SVG_SIZE);
+            let height = ((_transform.scale_y.abs() + 0.5) as u32).clamp(1, MAX_SVG_SIZE);
+
+            let mut rasterized = svg.rasterized.lock().unwrap();
+            if rasterized
+                .as_ref()
+                .is_none_or(|pixmap| pixmap.width() != width || pixmap.height() != height)
+            {
+                *rasterized = rasterize_svg(&svg.tree, width, height);
+            }
+            if let Some(pixmap) = &*rasterized {
+                return f(pixmap);
+            }
+        }
+        f(&self.pixmap)
+    }
+}
+
+#[cfg(feature = "svg-images")]
+fn create_svg_image(data: &[u8]) -> Option<Image> {
+    let tree = resvg::usvg::Tree::from_data(data, &Default::default()).ok()?;
+    let size = tree.size();
+    let (width, height) = (size.width(), size.height());
+
+    // The image is also drawn without a known size, such as when it gets
+    // blurred as the background of the layout, so we rasterize it at its
+    // intrinsic size, limited to the size of large images.
+    let scale = (settings::Image::LARGE as f32 / width.max(height)).min(1.0);
+    let pixmap = rasterize_svg(
+        &tree,
+        ((width * scale + 0.5) as u32).max(1),
+        ((height * scale + 0.5) as u32).max(1),
+    )?;
+
+    Some(Image {
+        pixmap,
+        aspect_ratio: width / height,
+        svg: Some(SvgImage {
+            tree,
+            rasterized: Default::default(),
+        }),
+    })
+}
+
+#[cfg(feature = "svg-images")]
+fn rasterize_svg(tree: &resvg::usvg::Tree, width: u32, height: u32) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(width, height)?;
+    let size = tree.size();
+    resvg::render(
+        tree,
+        tiny_skia::Transform::from_scale(
+            width as f32 / size.width(),
+            height as f32 / size.height(),
+        ),
+        &mut pixmap.as_mut(),
+    );
+    Some(pixmap)
 }
 
 impl resource::Image for SkiaImage {
@@ -92,7 +242,7 @@ fn convert_transform(transform: &Transform) -> tiny_skia::Transform {
 }
 
 struct SkiaAllocator {
-    text_engine: TextEngine<SkiaPath>,
+    text_engine: TextEngine<SkiaPath, SkiaImage>,
 }
 
 impl ResourceAllocator for SkiaAllocator {
@@ -106,40 +256,8 @@ impl ResourceAllocator for SkiaAllocator {
         path_builder()
     }
 
-    fn create_image(&mut self, _data: &[u8]) -> Option<Self::Image> {
-        #[cfg(feature = "image")]
-        {
-            let mut buf = image::load_from_memory(_data).ok()?.to_rgba8();
-
-            // Premultiplication
-            for [r, g, b, a] in bytemuck::cast_slice_mut::<u8, [u8; 4]>(&mut buf) {
-                // If it's opaque we can skip the entire pixel. However this
-                // hurts vectorization, so we want to avoid it if the compiler
-                // can vectorize the loop. WASM, PowerPC, and MIPS are
-                // unaffected at the moment.
-                #[cfg(not(any(target_feature = "avx2", target_feature = "neon")))]
-                if *a == 0xFF {
-                    continue;
-                }
-                let a = *a as u16;
-                *r = ((*r as u16 * a) / 255) as u8;
-                *g = ((*g as u16 * a) / 255) as u8;
-                *b = ((*b as u16 * a) / 255) as u8;
-            }
-
-            let (width, height) = (buf.width(), buf.height());
-
-            let pixmap = Pixmap::from_vec(buf.into_raw(), IntSize::from_wh(width, height)?)?;
-
-            Some(UnsafeRc::new(Image {
-                pixmap,
-                aspect_ratio: width as f32 / height as f32,
-            }))
-        }
-        #[cfg(not(feature = "image"))]
-        {
-            None
-        }
+    fn create_image(&mut self, data: &[u8]) -> Option<Self::Image> {
+        Image::decode(data).map(UnsafeRc::new)
     }
 
     fn create_font(&mut self, font: Option<&settings::Font>, kind: FontKind) -> Self::Font {
@@ -172,6 +290,40 @@ fn path_builder() -> SkiaBuilder {
     SkiaBuilder(PathBuilder::new())
 }
 
+/// A rectangular region of pixels in the image.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// The x coordinate of the left edge of the region.
+    pub x: u32,
+    /// The y coordinate of the top edge of the region.
+    pub y: u32,
+    /// The width of the region.
+    pub width: u32,
+    /// The height of the region.
+    pub height: u32,
+}
+
+/// Describes how the software renderer anti-aliases text.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TextAntialiasing {
+    /// The text is anti-aliased based on how much of each pixel it covers.
+    /// This works for any image, including ones with transparent backgrounds.
+    #[default]
+    Grayscale,
+    /// The text is anti-aliased based on how much of each subpixel it covers,
+    /// for LCD screens where the red, green and blue subpixels are arranged
+    /// horizontally in that order. This is only suitable for images that end
+    /// up fully opaque, as the colored edges otherwise become visible. Text
+    /// that is filled with a gradient is anti-aliased based on whole pixels.
+    SubpixelRgb,
+    /// The text is anti-aliased based on how much of each subpixel it covers,
+    /// for LCD screens where the subpixels are arranged horizontally in blue,
+    /// green and red order. This is only suitable for images that end up fully
+    /// opaque, as the colored edges otherwise become visible. Text that is
+    /// filled with a gradient is anti-aliased based on whole pixels.
+    SubpixelBgr,
+}
+
 /// The software renderer allows rendering layouts entirely on the CPU. This is
 /// surprisingly fast and can be considered the default renderer. There are two
 /// versions of the software renderer. This version of the software renderer
@@ -183,8 +335,11 @@ pub struct BorrowedRenderer {
     #[cfg(feature = "image")]
     blurred_background_image: Option<(BackgroundImage<usize>, Pixmap)>,
     background: Pixmap,
-    min_y: f32,
-    max_y: f32,
+    top_layer_bounds: Vec<Bounds>,
+    previous_top_layer_bounds: Vec<Bounds>,
+    dirty_regions: Vec<Region>,
+    text_antialiasing: TextAntialiasing,
+    background_outdated: bool,
 }
 
 struct UnsafeRc<T>(Rc<T>);
@@ -239,7 +394,9 @@ impl BorrowedRenderer {
     /// Creates a new software renderer.
     pub fn new() -> Self {
         let mut allocator = SkiaAllocator {
-            text_engine: TextEngine::new(),
+            text_engine: TextEngine::with_image_decoder(|data| {
+                Image::decode(data).map(UnsafeRc::new)
+            }),
         };
         let scene_manager = SceneManager::new(&mut allocator);
         Self {
@@ -248,8 +405,11 @@ impl BorrowedRenderer {
             #[cfg(feature = "image")]
             blurred_background_image: None,
             background: Pixmap::new(1, 1).unwrap(),
-            min_y: f32::INFINITY,
-            max_y: f32::NEG_INFINITY,
+            top_layer_bounds: Vec::new(),
+            previous_top_layer_bounds: Vec::new(),
+            dirty_regions: Vec::new(),
+            text_antialiasing: TextAntialiasing::Grayscale,
+            background_outdated: false,
         }
     }
 
@@ -274,6 +434,106 @@ impl BorrowedRenderer {
         stride: u32,
         force_redraw: bool,
     ) -> Option<[f32; 2]> {
+        self.render_rgba(
+            state,
+            image_cache,
+            image,
+            [width, height],
+            stride,
+            force_redraw,
+        )
+        .0
+    }
+
+    /// Renders the layout state provided into the image buffer provided, just
+    /// like [`render`](Self::render) does. However the image has to be an array
+    /// of `BGRA8` encoded pixels (blue, green, red, alpha with each channel
+    /// being an u8) instead. This is the format that most video capture and
+    /// streaming software expects for overlays. Just like the `RGBA8` image,
+    /// the colors are premultiplied by their alpha. Only the pixels that got
+    /// redrawn are converted, so this is barely slower than rendering `RGBA8`
+    /// pixels.
+    pub fn render_bgra(
+        &mut self,
+        state: &LayoutState,
+        image_cache: &ImageCache,
+        image: &mut [u8],
+        [width, height]: [u32; 2],
+        stride: u32,
+        force_redraw: bool,
+    ) -> Option<[f32; 2]> {
+        let (new_resolution, redrawn) = self.render_rgba(
+            state,
+            image_cache,
+            image,
+            [width, height],
+            stride,
+            force_redraw,
+        );
+        for pixel in image[redrawn].chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        new_resolution
+    }
+
+    /// Returns the regions of the image that changed during the last call to
+    /// [`render`](Self::render). All the other pixels are the same as before.
+    /// This allows only updating the parts of the screen or a texture that
+    /// actually changed. If the whole image got redrawn, this is a single
+    /// region covering the whole image. The regions don't overlap.
+    pub fn dirty_regions(&self) -> &[Region] {
+        &self.dirty_regions
+    }
+
+    /// Sets how text gets anti-aliased. By default the coverage of each pixel
+    /// is used, which works for any image. If the image is shown as part of an
+    /// opaque window on an LCD screen, subpixel anti-aliasing results in
+    /// noticeably sharper text. The next frame is fully redrawn if the setting
+    /// changes.
+    pub fn set_text_antialiasing(&mut self, text_antialiasing: TextAntialiasing) {
+        if self.text_antialiasing != text_antialiasing {
+            self.text_antialiasing = text_antialiasing;
+            self.background_outdated = true;
+        }
+    }
+
+    /// Sets the limits the cache of the glyphs used by the text is kept
+    /// within. The cache is collected after every frame. By default it is
+    /// unlimited.
+    pub const fn set_glyph_cache_limits(&mut self, limits: CacheLimits) {
+        self.allocator.text_engine.set_glyph_cache_limits(limits);
+    }
+
+    /// Returns how many glyphs are in the glyph cache.
+    pub fn glyph_cache_usage(&self) -> CacheUsage {
+        self.allocator.text_engine.glyph_cache_usage()
+    }
+
+    /// Sets how text and lines are aligned to the pixel grid of the image. By
+    /// default nothing is aligned to it.
+    pub const fn set_hinting(&mut self, hinting: Hinting) {
+        self.scene_manager.set_hinting(hinting);
+    }
+
+    /// Sets the amount of pixels of the image per logical pixel of the
+    /// display it is shown on. With hinting, lines are at least one logical
+    /// pixel thick. By default this is 1.
+    pub const fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f32) {
+        self.scene_manager
+            .set_device_pixel_ratio(device_pixel_ratio);
+    }
+
+    /// Renders the layout state into the `RGBA8` image and returns the range
+    /// of bytes that got redrawn. All the other bytes are left untouched.
+    fn render_rgba(
+        &mut self,
+        state: &LayoutState,
+        image_cache: &ImageCache,
+        image: &mut [u8],
+        [width, height]: [u32; 2],
+        stride: u32,
+        force_redraw: bool,
+    ) -> (Option<[f32; 2]>, Range<usize>) {
         let mut frame_buffer = PixmapMut::from_bytes(image, stride, height).unwrap();
 
         if stride != self.background.width() || height != self.background.height() {
@@ -286,12 +546,14 @@ impl BorrowedRenderer {
             state,
             image_cache,
         );
+        self.allocator.text_engine.collect_glyphs();
 
         let scene = self.scene_manager.scene();
         let rectangle = scene.rectangle();
         let rectangle = rectangle.as_deref().unwrap();
 
-        let bottom_layer_changed = scene.bottom_layer_changed();
+        let bottom_layer_changed =
+            scene.bottom_layer_changed() || mem::take(&mut self.background_outdated);
 
         let mut background = self.background.as_mut();
 
@@ -305,19 +567,46 @@ impl BorrowedRenderer {
                 height,
                 rectangle,
             );
-            render_layer(&mut background, scene.bottom_layer(), rectangle);
+            render_layer(
+                &mut background,
+                scene.bottom_layer(),
+                rectangle,
+                self.text_antialiasing,
+            );
         }
 
         let top_layer = scene.top_layer();
 
-        let [min_y, max_y] = calculate_bounds(top_layer);
-        let min_y = mem::replace(&mut self.min_y, min_y).min(min_y);
-        let max_y = mem::replace(&mut self.max_y, max_y).max(max_y);
-
-        if force_redraw || bottom_layer_changed {
+        mem::swap(
+            &mut self.top_layer_bounds,
+            &mut self.previous_top_layer_bounds,
+        );
+        calculate_bounds(top_layer, &mut self.top_layer_bounds);
+        let bounds = self
+            .top_layer_bounds
+            .iter()
+            .chain(&self.previous_top_layer_bounds);
+        let min_y = bounds
+            .clone()
+            .map(|[_, top, ..]| *top)
+            .fold(f32::INFINITY, f32::min);
+        let max_y = bounds
+            .map(|[.., bottom]| *bottom)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        self.dirty_regions.clear();
+
+        let redrawn = if force_redraw || bottom_layer_changed {
             frame_buffer
                 .data_mut()
                 .copy_from_slice(background.data_mut());
+            self.dirty_regions.push(Region {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            });
+            0..frame_buffer.data_mut().len()
         } else if min_y <= max_y {
             let stride = 4 * stride as usize;
             let min_y = stride * (min_y - 1.0) as usize;
@@ -325,11 +614,26 @@ impl BorrowedRenderer {
 
             frame_buffer.data_mut()[min_y..max_y]
                 .copy_from_slice(&background.data_mut()[min_y..max_y]);
-        }
+            collect_dirty_regions(
+                &mut self.dirty_regions,
+                self.top_layer_bounds
+                    .iter()
+                    .chain(&self.previous_top_layer_bounds),
+                [width, height],
+            );
+            min_y..max_y
+        } else {
+            0..0
+        };
 
-        render_layer(&mut frame_buffer, top_layer, rectangle);
+        render_layer(
+            &mut frame_buffer,
+            top_layer,
+            rectangle,
+            self.text_antialiasing,
+        );
 
-        new_resolution
+        (new_resolution, redrawn)
     }
 }
 
@@ -381,6 +685,48 @@ impl Renderer {
         )
     }
 
+    /// Returns the regions of the image that changed during the last call to
+    /// [`render`](Self::render). All the other pixels are the same as before.
+    /// This allows only updating the parts of the screen or a texture that
+    /// actually changed. If the whole image got redrawn, this is a single
+    /// region covering the whole image. The regions don't overlap.
+    pub fn dirty_regions(&self) -> &[Region] {
+        self.renderer.dirty_regions()
+    }
+
+    /// Sets how text gets anti-aliased. By default the coverage of each pixel
+    /// is used, which works for any image. If the image is shown as part of an
+    /// opaque window on an LCD screen, subpixel anti-aliasing results in
+    /// noticeably sharper text.
+    pub fn set_text_antialiasing(&mut self, text_antialiasing: TextAntialiasing) {
+        self.renderer.set_text_antialiasing(text_antialiasing);
+    }
+
+    /// Sets the limits the cache of the glyphs used by the text is kept
+    /// within. The cache is collected after every frame. By default it is
+    /// unlimited.
+    pub const fn set_glyph_cache_limits(&mut self, limits: CacheLimits) {
+        self.renderer.set_glyph_cache_limits(limits);
+    }
+
+    /// Returns how many glyphs are in the glyph cache.
+    pub fn glyph_cache_usage(&self) -> CacheUsage {
+        self.renderer.glyph_cache_usage()
+    }
+
+    /// Sets how text and lines are aligned to the pixel grid of the image. By
+    /// default nothing is aligned to it.
+    pub const fn set_hinting(&mut self, hinting: Hinting) {
+        self.renderer.set_hinting(hinting);
+    }
+
+    /// Sets the amount of pixels of the image per logical pixel of the
+    /// display it is shown on. With hinting, lines are at least one logical
+    /// pixel thick. By default this is 1.
+    pub const fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f32) {
+        self.renderer.set_device_pixel_ratio(device_pixel_ratio);
+    }
+
     /// Accesses the image as a byte slice of RGBA8 encoded pixels (red, green,
     /// blue, alpha with each channel being an u8).
     pub fn image_data(&self) -> &[u8] {
@@ -416,144 +762,188 @@ impl Renderer {
     }
 }
 
+/// Renders the layout with the state of the timer provided into an image with
+/// the resolution provided and encodes it as a PNG. This is a convenient way to
+/// create an image of a run to share in a single call. Unlike the image of the
+/// [`Renderer`], the image doesn't use premultiplied alpha, so transparent
+/// backgrounds are preserved correctly. Use a [`Renderer`] to repeatedly render
+/// a layout instead.
+///
+/// # Panics
+///
+/// This panics if the width or the height is zero.
+#[cfg(feature = "image")]
+pub fn render_png(layout: &mut Layout, timer: &Snapshot, [width, height]: [u32; 2]) -> Vec<u8> {
+    use image::{ExtendedColorType, ImageEncoder, codecs::png::PngEncoder};
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, timer);
+
+    let mut renderer = Renderer::new();
+    renderer.render(&state, &image_cache, [width, height]);
+
+    let pixels: Vec<u8> = renderer
+        .frame_buffer
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let pixel = pixel.demultiply();
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+        .collect();
+
+    let mut png = Vec::new();
+    // Encoding into memory can only fail for invalid dimensions, which are
+    // already ruled out by the renderer.
+    let _ = PngEncoder::new(&mut png).write_image(&pixels, width, height, ExtendedColorType::Rgba8);
+    png
+}
+
 fn render_layer(
     canvas: &mut PixmapMut,
     layer: &[Entity<SkiaPath, SkiaImage, SkiaLabel>],
     rectangle: &Path,
+    text_antialiasing: TextAntialiasing,
 ) {
-    for entity in layer {
-        match entity {
-            Entity::FillPath(path, shader, transform) => {
-                if let Some(path) = path.as_deref() {
-                    let paint = convert_shader(
-                        shader,
-                        path,
-                        |path| {
-                            let bounds = path.bounds();
-                            [bounds.top(), bounds.bottom()]
-                        },
-                        |path| {
-                            let bounds = path.bounds();
-                            [bounds.left(), bounds.right()]
-                        },
-                    );
-
-                    canvas.fill_path(
-                        path,
-                        &paint,
-                        FillRule::Winding,
-                        convert_transform(transform),
-                        None,
-                    );
-                }
-            }
-            Entity::StrokePath(path, stroke_width, color, transform) => {
-                if let Some(path) = path.as_deref() {
-                    canvas.stroke_path(
-                        path,
-                        &Paint {
-                            shader: Shader::SolidColor(convert_color(color)),
-                            anti_alias: true,
-                            ..Default::default()
-                        },
-                        &Stroke {
-                            width: *stroke_width,
-                            ..Default::default()
-                        },
-                        convert_transform(transform),
-                        None,
-                    );
+    let mut entities = layer.iter();
+    while let Some(entity) = entities.next() {
+        if let Entity::BeginGroup(opacity, blend_mode) = entity {
+            // The entities of the group get rendered into their own pixmap
+            // first, so that they can be composited as a whole.
+            let Some(mut group) = Pixmap::new(canvas.width(), canvas.height()) else {
+                continue;
+            };
+            let mut group_canvas = group.as_mut();
+            for entity in entities.by_ref() {
+                if let Entity::EndGroup = entity {
+                    break;
                 }
+                render_entity(&mut group_canvas, entity, rectangle, text_antialiasing);
             }
-            Entity::Image(image, transform) => {
+            canvas.draw_pixmap(
+                0,
+                0,
+                group.as_ref(),
+                &PixmapPaint {
+                    opacity: *opacity,
+                    blend_mode: convert_blend_mode(*blend_mode),
+                    quality: FilterQuality::Nearest,
+                },
+                tiny_skia::Transform::identity(),
+                None,
+            );
+        } else {
+            render_entity(canvas, entity, rectangle, text_antialiasing);
+        }
+    }
+}
+
+fn render_entity(
+    canvas: &mut PixmapMut,
+    entity: &Entity<SkiaPath, SkiaImage, SkiaLabel>,
+    rectangle: &Path,
+    text_antialiasing: TextAntialiasing,
+) {
+    match entity {
+        Entity::FillPath(path, shader, transform) => {
+            if let Some(path) = path.as_deref() {
+                let mut conic_pixmap = None;
+                let paint = convert_shader(
+                    shader,
+                    path,
+                    |path| {
+                        let bounds = path.bounds();
+                        [bounds.top(), bounds.bottom()]
+                    },
+                    |path| {
+                        let bounds = path.bounds();
+                        [bounds.left(), bounds.right()]
+                    },
+                    transform,
+                    &mut conic_pixmap,
+                );
+
                 canvas.fill_path(
-                    rectangle,
+                    path,
+                    &paint,
+                    FillRule::Winding,
+                    convert_transform(transform),
+                    None,
+                );
+            }
+        }
+        Entity::StrokePath(path, stroke_width, color, transform) => {
+            if let Some(path) = path.as_deref() {
+                canvas.stroke_path(
+                    path,
                     &Paint {
-                        shader: Pattern::new(
-                            image.pixmap.as_ref(),
-                            SpreadMode::Pad,
-                            FilterQuality::Bilinear,
-                            1.0,
-                            tiny_skia::Transform::from_scale(
-                                1.0 / image.pixmap.width() as f32,
-                                1.0 / image.pixmap.height() as f32,
-                            ),
-                        ),
+                        shader: Shader::SolidColor(convert_color(color)),
                         anti_alias: true,
                         ..Default::default()
                     },
-                    FillRule::Winding,
+                    &Stroke {
+                        width: *stroke_width,
+                        ..Default::default()
+                    },
                     convert_transform(transform),
                     None,
                 );
             }
-            Entity::Label(label, shader, text_shadow, transform) => {
-                let label = &*label.read().unwrap();
-
-                let paint = convert_shader(
-                    shader,
-                    label,
-                    |label| {
-                        let (mut top, mut bottom) = (f32::INFINITY, f32::NEG_INFINITY);
-                        for glyph in label.glyphs() {
-                            if let Some(path) = &glyph.path {
-                                let bounds = path.bounds();
-                                top = top.min(bounds.top());
-                                bottom = bottom.max(bounds.bottom());
-                            }
-                        }
-                        if bottom < top {
-                            [0.0, 0.0]
-                        } else {
-                            [top, bottom]
-                        }
-                    },
-                    |label| {
-                        let (mut left, mut right) = (f32::INFINITY, f32::NEG_INFINITY);
-                        for glyph in label.glyphs() {
-                            if let Some(path) = &glyph.path {
-                                let bounds = path.bounds();
-                                left = left.min(bounds.left());
-                                right = right.max(bounds.right());
-                            }
-                        }
-                        if right < left {
-                            [0.0, 0.0]
-                        } else {
-                            [left, right]
+        }
+        Entity::Image(image, transform) => render_image(canvas, image, transform, rectangle),
+        Entity::Label(label, shader, text_shadow, transform) => {
+            let label = &*label.read().unwrap();
+
+            let mut conic_pixmap = None;
+            let paint = convert_shader(
+                shader,
+                label,
+                |label| {
+                    let (mut top, mut bottom) = (f32::INFINITY, f32::NEG_INFINITY);
+                    for glyph in label.glyphs() {
+                        if let Some(path) = &glyph.path {
+                            let bounds = path.bounds();
+                            top = top.min(bounds.top());
+                            bottom = bottom.max(bounds.bottom());
                         }
-                    },
-                );
-
-                if let Some(text_shadow) = text_shadow {
-                    let mut color = convert_color(text_shadow);
-                    let alpha = match shader {
-                        FillShader::SolidColor([.., a]) => *a,
-                        FillShader::VerticalGradient([.., a1], [.., a2])
-                        | FillShader::HorizontalGradient([.., a1], [.., a2]) => 0.5 * (a1 + a2),
-                    };
-                    color.apply_opacity(alpha);
-                    let transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
-
+                    }
+                    if bottom < top {
+                        [0.0, 0.0]
+                    } else {
+                        [top, bottom]
+                    }
+                },
+                |label| {
+                    let (mut left, mut right) = (f32::INFINITY, f32::NEG_INFINITY);
                     for glyph in label.glyphs() {
                         if let Some(path) = &glyph.path {
-                            let transform = transform
-                                .pre_translate(glyph.x, glyph.y)
-                                .pre_scale(glyph.scale, glyph.scale);
-
-                            canvas.fill_path(
-                                path,
-                                &Paint {
-                                    shader: Shader::SolidColor(color),
-                                    ..paint
-                                },
-                                FillRule::Winding,
-                                convert_transform(&transform),
-                                None,
-                            );
+                            let bounds = path.bounds();
+                            left = left.min(bounds.left());
+                            right = right.max(bounds.right());
                         }
                     }
-                }
+                    if right < left {
+                        [0.0, 0.0]
+                    } else {
+                        [left, right]
+                    }
+                },
+                transform,
+                &mut conic_pixmap,
+            );
+
+            if let Some(text_shadow) = text_shadow {
+                let mut color = convert_color(text_shadow);
+                let alpha = match shader {
+                    FillShader::SolidColor([.., a]) => *a,
+                    FillShader::VerticalGradient([.., a1], [.., a2])
+                    | FillShader::HorizontalGradient([.., a1], [.., a2])
+                    | FillShader::AngledGradient([.., a1], [.., a2], _)
+                    | FillShader::RadialGradient([.., a1], [.., a2], ..)
+                    | FillShader::ConicGradient([.., a1], [.., a2], ..) => 0.5 * (a1 + a2),
+                };
+                color.apply_opacity(alpha);
+                let transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
 
                 for glyph in label.glyphs() {
                     if let Some(path) = &glyph.path {
@@ -561,35 +951,203 @@ fn render_layer(
                             .pre_translate(glyph.x, glyph.y)
                             .pre_scale(glyph.scale, glyph.scale);
 
-                        let paint = if let Some(color) = &glyph.color {
+                        fill_glyph(
+                            canvas,
+                            path,
                             &Paint {
-                                shader: Shader::SolidColor(convert_color(color)),
+                                shader: Shader::SolidColor(color),
                                 ..paint
-                            }
-                        } else {
-                            &paint
-                        };
-
-                        canvas.fill_path(
-                            path,
-                            paint,
-                            FillRule::Winding,
-                            convert_transform(&transform),
-                            None,
+                            },
+                            &transform,
+                            text_antialiasing,
                         );
                     }
                 }
             }
+
+            for glyph in label.glyphs() {
+                if let Some(path) = &glyph.path {
+                    let transform = transform
+                        .pre_translate(glyph.x, glyph.y)
+                        .pre_scale(glyph.scale, glyph.scale);
+
+                    let paint = if let Some(color) = &glyph.color {
+                        &Paint {
+                            shader: Shader::SolidColor(convert_color(color)),
+                            ..paint
+                        }
+                    } else {
+                        &paint
+                    };
+
+                    fill_glyph(canvas, path, paint, &transform, text_antialiasing);
+                }
+            }
+
+            for image in label.images() {
+                let transform = transform
+                    .pre_translate(image.x, image.y)
+                    .pre_scale(image.width, image.height);
+                render_image(canvas, &image.image, &transform, rectangle);
+            }
+        }
+        Entity::BeginGroup(..) | Entity::EndGroup => {}
+    }
+}
+
+fn fill_glyph(
+    canvas: &mut PixmapMut,
+    path: &Path,
+    paint: &Paint,
+    transform: &Transform,
+    text_antialiasing: TextAntialiasing,
+) {
+    let transform = convert_transform(transform);
+    match (text_antialiasing, &paint.shader) {
+        (TextAntialiasing::SubpixelRgb, Shader::SolidColor(color)) => {
+            fill_subpixels(canvas, path, *color, transform, false)
+        }
+        (TextAntialiasing::SubpixelBgr, Shader::SolidColor(color)) => {
+            fill_subpixels(canvas, path, *color, transform, true)
+        }
+        _ => canvas.fill_path(path, paint, FillRule::Winding, transform, None),
+    }
+}
+
+/// The weights of the filter that distributes the coverage of each subpixel to
+/// its neighbors, so the colored edges are less visible. These are the weights
+/// FreeType uses by default.
+const SUBPIXEL_FILTER: [f32; 5] = [
+    8.0 / 256.0,
+    77.0 / 256.0,
+    86.0 / 256.0,
+    77.0 / 256.0,
+    8.0 / 256.0,
+];
+
+/// Fills the path by rasterizing it at three times the horizontal resolution,
+/// so each of the subpixels gets its own coverage.
+fn fill_subpixels(
+    canvas: &mut PixmapMut,
+    path: &Path,
+    color: Color,
+    transform: tiny_skia::Transform,
+    bgr: bool,
+) {
+    let Some(bounds) = path.bounds().transform(transform) else {
+        return;
+    };
+    let left = (bounds.left().floor() as i32 - 1).max(0);
+    let top = (bounds.top().floor() as i32).max(0);
+    let right = (bounds.right().ceil() as i32 + 1).min(canvas.width() as i32);
+    let bottom = (bounds.bottom().ceil() as i32).min(canvas.height() as i32);
+    if left >= right || top >= bottom {
+        return;
+    }
+    let [width, height] = [(right - left) as u32, (bottom - top) as u32];
+
+    // The mask has an additional pixel on both sides, so the filter can
+    // always access the neighboring subpixels.
+    let Some(mut mask) = Mask::new(3 * (width + 2), height) else {
+        return;
+    };
+    mask.fill_path(
+        path,
+        FillRule::Winding,
+        true,
+        transform
+            .post_translate(1.0 - left as f32, -top as f32)
+            .post_scale(3.0, 1.0),
+    );
+
+    let mask_width = mask.width() as usize;
+    let coverage = mask.data();
+    let canvas_width = canvas.width() as usize;
+    let pixels = canvas.pixels_mut();
+    let [red, green, blue, alpha] = [color.red(), color.green(), color.blue(), color.alpha()];
+
+    for y in 0..height as usize {
+        let row = &coverage[y * mask_width..][..mask_width];
+        for x in 0..width as usize {
+            let [r, g, b] = array::from_fn(|channel| {
+                let center = 3 * (x + 1) + channel;
+                SUBPIXEL_FILTER
+                    .iter()
+                    .zip(&row[center - 2..=center + 2])
+                    .map(|(weight, &coverage)| weight * coverage as f32)
+                    .sum::<f32>()
+                    / 255.0
+                    * alpha
+            });
+            let [r, b] = if bgr { [b, r] } else { [r, b] };
+            let a = r.max(g).max(b);
+            if a == 0.0 {
+                continue;
+            }
+
+            let pixel = &mut pixels[(top as usize + y) * canvas_width + left as usize + x];
+            let blend = |src: f32, coverage: f32, dst: u8| {
+                (255.0 * src * coverage + dst as f32 * (1.0 - coverage) + 0.5) as u8
+            };
+            // Rounding must not result in a color channel exceeding the alpha.
+            let a = blend(1.0, a, pixel.alpha());
+            if let Some(blended) = PremultipliedColorU8::from_rgba(
+                blend(red, r, pixel.red()).min(a),
+                blend(green, g, pixel.green()).min(a),
+                blend(blue, b, pixel.blue()).min(a),
+                a,
+            ) {
+                *pixel = blended;
+            }
         }
     }
 }
 
-fn convert_shader<T>(
+fn render_image(canvas: &mut PixmapMut, image: &Image, transform: &Transform, rectangle: &Path) {
+    image.with_pixmap(transform, |pixmap| {
+        canvas.fill_path(
+            rectangle,
+            &Paint {
+                shader: Pattern::new(
+                    pixmap.as_ref(),
+                    SpreadMode::Pad,
+                    FilterQuality::Bilinear,
+                    1.0,
+                    tiny_skia::Transform::from_scale(
+                        1.0 / pixmap.width() as f32,
+                        1.0 / pixmap.height() as f32,
+                    ),
+                ),
+                anti_alias: true,
+                ..Default::default()
+            },
+            FillRule::Winding,
+            convert_transform(transform),
+            None,
+        );
+    });
+}
+
+const fn convert_blend_mode(blend_mode: settings::BlendMode) -> BlendMode {
+    match blend_mode {
+        settings::BlendMode::Normal => BlendMode::SourceOver,
+        settings::BlendMode::Multiply => BlendMode::Multiply,
+        settings::BlendMode::Screen => BlendMode::Screen,
+        settings::BlendMode::Overlay => BlendMode::Overlay,
+        settings::BlendMode::Darken => BlendMode::Darken,
+        settings::BlendMode::Lighten => BlendMode::Lighten,
+        settings::BlendMode::Add => BlendMode::Plus,
+    }
+}
+
+fn convert_shader<'a, T>(
     shader: &FillShader,
     has_bounds: &T,
     calculate_top_bottom: impl FnOnce(&T) -> [f32; 2],
     calculate_left_right: impl FnOnce(&T) -> [f32; 2],
-) -> Paint<'static> {
+    transform: &Transform,
+    conic_pixmap: &'a mut Option<Pixmap>,
+) -> Paint<'a> {
     let shader = match shader {
         FillShader::SolidColor(col) => Shader::SolidColor(convert_color(col)),
         FillShader::VerticalGradient(top, bottom) => {
@@ -620,6 +1178,42 @@ fn convert_shader<T>(
             )
             .unwrap()
         }
+        FillShader::AngledGradient(start, end, direction) => {
+            let [[x1, y1], [x2, y2]] = angled_gradient_points(
+                *direction,
+                calculate_left_right(has_bounds),
+                calculate_top_bottom(has_bounds),
+            );
+            LinearGradient::new(
+                Point::from_xy(x1, y1),
+                Point::from_xy(x2, y2),
+                vec![
+                    GradientStop::new(0.0, convert_color(start)),
+                    GradientStop::new(1.0, convert_color(end)),
+                ],
+                SpreadMode::Pad,
+                tiny_skia::Transform::identity(),
+            )
+            .unwrap()
+        }
+        FillShader::RadialGradient(center, edge, position, radius) => radial_gradient(
+            center,
+            edge,
+            *position,
+            *radius,
+            calculate_left_right(has_bounds),
+            calculate_top_bottom(has_bounds),
+        ),
+        FillShader::ConicGradient(start, end, position, angle) => conic_gradient(
+            start,
+            end,
+            *position,
+            *angle,
+            calculate_left_right(has_bounds),
+            calculate_top_bottom(has_bounds),
+            [transform.scale_x, transform.scale_y],
+            conic_pixmap,
+        ),
     };
 
     Paint {
@@ -629,6 +1223,89 @@ fn convert_shader<T>(
     }
 }
 
+fn radial_gradient(
+    center: &[f32; 4],
+    edge: &[f32; 4],
+    [x, y]: [f32; 2],
+    radius: f32,
+    [left, right]: [f32; 2],
+    [top, bottom]: [f32; 2],
+) -> Shader<'static> {
+    let center_point = Point::from_xy(x, y);
+    RadialGradient::new(
+        center_point,
+        center_point,
+        radius,
+        vec![
+            GradientStop::new(0.0, convert_color(center)),
+            GradientStop::new(1.0, convert_color(edge)),
+        ],
+        SpreadMode::Pad,
+        // The gradient is described relative to the bounding box, which is
+        // also what stretches it into an ellipse.
+        tiny_skia::Transform::from_row(right - left, 0.0, 0.0, bottom - top, left, top),
+    )
+    .unwrap_or_else(|| Shader::SolidColor(convert_color(edge)))
+}
+
+/// The largest width or height the pattern of a conic gradient is rendered at.
+const MAX_CONIC_SIZE: u32 = 2048;
+
+/// tiny-skia doesn't support conic gradients, so the gradient is rendered into
+/// a pattern instead. The pattern has the size that the bounding box has once
+/// scaled by the scale provided, so the angles of the gradient aren't distorted
+/// if the bounding box isn't square.
+#[expect(clippy::too_many_arguments)]
+fn conic_gradient<'a>(
+    start: &[f32; 4],
+    end: &[f32; 4],
+    [x, y]: [f32; 2],
+    angle: f32,
+    [left, right]: [f32; 2],
+    [top, bottom]: [f32; 2],
+    [scale_x, scale_y]: [f32; 2],
+    pixmap_slot: &'a mut Option<Pixmap>,
+) -> Shader<'a> {
+    let (width, height) = (right - left, bottom - top);
+    let pixel_width = ((abs(width * scale_x) + 0.5) as u32).clamp(1, MAX_CONIC_SIZE);
+    let pixel_height = ((abs(height * scale_y) + 0.5) as u32).clamp(1, MAX_CONIC_SIZE);
+
+    let Some(pixmap) = Pixmap::new(pixel_width, pixel_height) else {
+        return Shader::SolidColor(convert_color(start));
+    };
+    let pixmap = pixmap_slot.insert(pixmap);
+
+    let (center_x, center_y) = (x * pixel_width as f32, y * pixel_height as f32);
+    for (i, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let dx = (i % pixel_width as usize) as f32 + 0.5 - center_x;
+        let dy = (i / pixel_width as usize) as f32 + 0.5 - center_y;
+
+        // The angle is measured clockwise, starting at the top.
+        let mut t = ((atan2(dx, -dy).to_degrees() - angle) / 360.0) % 1.0;
+        if t < 0.0 {
+            t += 1.0;
+        }
+
+        let color: [f32; 4] = array::from_fn(|i| start[i] + t * (end[i] - start[i]));
+        *pixel = convert_color(&color).premultiply().to_color_u8();
+    }
+
+    Pattern::new(
+        pixmap.as_ref(),
+        SpreadMode::Pad,
+        FilterQuality::Bilinear,
+        1.0,
+        tiny_skia::Transform::from_row(
+            width / pixel_width as f32,
+            0.0,
+            0.0,
+            height / pixel_height as f32,
+            left,
+            top,
+        ),
+    )
+}
+
 fn fill_background(
     scene: &Scene<SkiaPath, SkiaImage, SkiaLabel>,
     #[cfg(feature = "image")] blurred_background_image: &mut Option<(
@@ -695,42 +1372,117 @@ fn fill_background(
                         None,
                     );
                 }
+                FillShader::AngledGradient(start, end, direction) => {
+                    let [[x1, y1], [x2, y2]] =
+                        angled_gradient_points(*direction, [0.0, width as _], [0.0, height as _]);
+                    background_layer.fill_rect(
+                        Rect::from_xywh(0.0, 0.0, width as _, height as _).unwrap(),
+                        &Paint {
+                            shader: LinearGradient::new(
+                                Point::from_xy(x1, y1),
+                                Point::from_xy(x2, y2),
+                                vec![
+                                    GradientStop::new(0.0, convert_color(start)),
+                                    GradientStop::new(1.0, convert_color(end)),
+                                ],
+                                SpreadMode::Pad,
+                                tiny_skia::Transform::identity(),
+                            )
+                            .unwrap(),
+                            blend_mode: BlendMode::Source,
+                            ..Default::default()
+                        },
+                        tiny_skia::Transform::identity(),
+                        None,
+                    );
+                }
+                FillShader::RadialGradient(center, edge, position, radius) => {
+                    background_layer.fill_rect(
+                        Rect::from_xywh(0.0, 0.0, width as _, height as _).unwrap(),
+                        &Paint {
+                            shader: radial_gradient(
+                                center,
+                                edge,
+                                *position,
+                                *radius,
+                                [0.0, width as _],
+                                [0.0, height as _],
+                            ),
+                            blend_mode: BlendMode::Source,
+                            ..Default::default()
+                        },
+                        tiny_skia::Transform::identity(),
+                        None,
+                    );
+                }
+                FillShader::ConicGradient(start, end, position, angle) => {
+                    let mut conic_pixmap = None;
+                    background_layer.fill_rect(
+                        Rect::from_xywh(0.0, 0.0, width as _, height as _).unwrap(),
+                        &Paint {
+                            shader: conic_gradient(
+                                start,
+                                end,
+                                *position,
+                                *angle,
+                                [0.0, width as _],
+                                [0.0, height as _],
+                                [1.0, 1.0],
+                                &mut conic_pixmap,
+                            ),
+                            blend_mode: BlendMode::Source,
+                            ..Default::default()
+                        },
+                        tiny_skia::Transform::identity(),
+                        None,
+                    );
+                }
             },
             Background::Image(image, transform) => {
+                let scene_transform = transform;
+                let transform = convert_transform(transform);
+                let fill_image = |background_layer: &mut PixmapMut, pixmap: &Pixmap| {
+                    background_layer.fill_path(
+                        rectangle,
+                        &Paint {
+                            shader: Pattern::new(
+                                pixmap.as_ref(),
+                                SpreadMode::Pad,
+                                FilterQuality::Bilinear,
+                                image.opacity,
+                                tiny_skia::Transform::from_scale(
+                                    1.0 / pixmap.width() as f32,
+                                    1.0 / pixmap.height() as f32,
+                                ),
+                            ),
+                            anti_alias: true,
+                            blend_mode: BlendMode::Source,
+                            ..Default::default()
+                        },
+                        FillRule::Winding,
+                        transform,
+                        None,
+                    )
+                };
+
                 #[cfg(feature = "image")]
-                let pixmap = if image.blur != 0.0 {
-                    blurred_background_image
-                        .as_ref()
-                        .map(|(_, pixmap)| pixmap)
-                        .unwrap()
+                if image.blur != 0.0 {
+                    fill_image(
+                        background_layer,
+                        blurred_background_image
+                            .as_ref()
+                            .map(|(_, pixmap)| pixmap)
+                            .unwrap(),
+                    );
                 } else {
-                    &image.image.pixmap
-                };
+                    image.image.with_pixmap(scene_transform, |pixmap| {
+                        fill_image(background_layer, pixmap)
+                    });
+                }
                 #[cfg(not(feature = "image"))]
-                let pixmap = &image.image.pixmap;
-
-                let transform = convert_transform(transform);
-                background_layer.fill_path(
-                    rectangle,
-                    &Paint {
-                        shader: Pattern::new(
-                            pixmap.as_ref(),
-                            SpreadMode::Pad,
-                            FilterQuality::Bilinear,
-                            image.opacity,
-                            tiny_skia::Transform::from_scale(
-                                1.0 / pixmap.width() as f32,
-                                1.0 / pixmap.height() as f32,
-                            ),
-                        ),
-                        anti_alias: true,
-                        blend_mode: BlendMode::Source,
-                        ..Default::default()
-                    },
-                    FillRule::Winding,
-                    transform,
-                    None,
-                );
+                image.image.with_pixmap(scene_transform, |pixmap| {
+                    fill_image(background_layer, pixmap)
+                });
 
                 if image.brightness != 1.0 {
                     let brightness = NormalizedF32::new_clamped(image.brightness).get();
@@ -766,57 +1518,7 @@ fn update_blurred_background_image(
                 .as_ref()
                 .is_some_and(|(key, _)| &current_key == key)
             {
-                let original_image = ImageBuffer::<image::Rgba<u8>, _>::from_raw(
-                    image.image.pixmap.width(),
-                    image.image.pixmap.height(),
-                    image.image.pixmap.data(),
-                )
-                .unwrap();
-
-                // Formula to calculate the sigma as specified
-                let dim = original_image.width().max(original_image.height()) as f32;
-                let sigma = BLUR_FACTOR * image.blur * dim;
-
-                // For large blurs the calculation is actually very expensive,
-                // but we can get around that because large blurs don't require
-                // high resolutions in the first place. So we simply scale down
-                // the image based on the sigma to a smaller size and then blur
-                // the image. For the scaled down image we always use a sigma of
-                // 2.0, so scaling the image by 2.0 / sigma should resulting in
-                // the same amount of blur. Of course we never want to scale the
-                // image up, so in case the scale factor would end up in >= 1x,
-                // we simply don't do any scaling and keep the original sigma.
-                const SIGMA_WHEN_SCALED: f32 = 2.0;
-                let scale = SIGMA_WHEN_SCALED / sigma;
-
-                let scaled;
-                let (image, sigma) = if scale < 1.0 {
-                    // The image needs to at least be 1x1, because tiny-skia
-                    // doesn't allow images to be smaller than that. A triangle
-                    // filter is probably fine, the blur will hide most scaling
-                    // artifacts anyway.
-                    scaled = image::imageops::resize(
-                        &original_image,
-                        ((scale * original_image.width() as f32) as u32).max(1),
-                        ((scale * original_image.height() as f32) as u32).max(1),
-                        FilterType::Triangle,
-                    );
-                    (
-                        ImageBuffer::<image::Rgba<u8>, _>::from_raw(
-                            scaled.width(),
-                            scaled.height(),
-                            &*scaled,
-                        )
-                        .unwrap(),
-                        SIGMA_WHEN_SCALED,
-                    )
-                } else {
-                    (original_image, sigma)
-                };
-
-                let image_buffer = image::imageops::blur(&image, sigma);
-                let size = IntSize::from_wh(image_buffer.width(), image_buffer.height()).unwrap();
-                let pixmap = Pixmap::from_vec(image_buffer.into_raw(), size).unwrap();
+                let pixmap = blur(&image.image.pixmap, image.blur);
                 *blurred_background_image = Some((current_key, pixmap));
             }
         }
@@ -826,76 +1528,172 @@ fn update_blurred_background_image(
     }
 }
 
-fn calculate_bounds(layer: &[Entity<SkiaPath, SkiaImage, SkiaLabel>]) -> [f32; 2] {
-    let (mut min_y, mut max_y) = (f32::INFINITY, f32::NEG_INFINITY);
+/// Blurs the image the same way the original LiveSplit blurs background images.
+/// The amount of blur is relative to the size of the image.
+#[cfg(feature = "image")]
+pub(super) fn blur(pixmap: &Pixmap, blur: f32) -> Pixmap {
+    let original_image =
+        ImageBuffer::<image::Rgba<u8>, _>::from_raw(pixmap.width(), pixmap.height(), pixmap.data())
+            .unwrap();
+
+    // Formula to calculate the sigma as specified
+    let dim = original_image.width().max(original_image.height()) as f32;
+    let sigma = BLUR_FACTOR * blur * dim;
+
+    // For large blurs the calculation is actually very expensive,
+    // but we can get around that because large blurs don't require
+    // high resolutions in the first place. So we simply scale down
+    // the image based on the sigma to a smaller size and then blur
+    // the image. For the scaled down image we always use a sigma of
+    // 2.0, so scaling the image by 2.0 / sigma should resulting in
+    // the same amount of blur. Of course we never want to scale the
+    // image up, so in case the scale factor would end up in >= 1x,
+    // we simply don't do any scaling and keep the original sigma.
+    const SIGMA_WHEN_SCALED: f32 = 2.0;
+    let scale = SIGMA_WHEN_SCALED / sigma;
+
+    let scaled;
+    let (image, sigma) = if scale < 1.0 {
+        // The image needs to at least be 1x1, because tiny-skia
+        // doesn't allow images to be smaller than that. A triangle
+        // filter is probably fine, the blur will hide most scaling
+        // artifacts anyway.
+        scaled = image::imageops::resize(
+            &original_image,
+            ((scale * original_image.width() as f32) as u32).max(1),
+            ((scale * original_image.height() as f32) as u32).max(1),
+            FilterType::Triangle,
+        );
+        (
+            ImageBuffer::<image::Rgba<u8>, _>::from_raw(scaled.width(), scaled.height(), &*scaled)
+                .unwrap(),
+            SIGMA_WHEN_SCALED,
+        )
+    } else {
+        (original_image, sigma)
+    };
+
+    let image_buffer = image::imageops::blur(&image, sigma);
+    let size = IntSize::from_wh(image_buffer.width(), image_buffer.height()).unwrap();
+    Pixmap::from_vec(image_buffer.into_raw(), size).unwrap()
+}
+
+/// The left, top, right and bottom edges of an entity in pixels.
+type Bounds = [f32; 4];
+
+/// Calculates the bounds of each of the entities of the layer.
+fn calculate_bounds(layer: &[Entity<SkiaPath, SkiaImage, SkiaLabel>], bounds: &mut Vec<Bounds>) {
+    bounds.clear();
     for entity in layer.iter() {
+        let mut entity_bounds = [
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        let mut include =
+            |transform: &Transform, [left, top, right, bottom]: Bounds, margin: f32| {
+                for point in [[left, top], [right, bottom]] {
+                    let [x, y] = transform.transform_point(point);
+                    entity_bounds[0] = entity_bounds[0].min(x - margin);
+                    entity_bounds[1] = entity_bounds[1].min(y - margin);
+                    entity_bounds[2] = entity_bounds[2].max(x + margin);
+                    entity_bounds[3] = entity_bounds[3].max(y + margin);
+                }
+            };
+        let path_bounds = |path: &Path| {
+            let bounds = path.bounds();
+            [bounds.left(), bounds.top(), bounds.right(), bounds.bottom()]
+        };
+
         match entity {
             Entity::FillPath(path, _, transform) => {
                 if let Some(path) = &**path {
-                    let bounds = path.bounds();
-                    for y in [bounds.top(), bounds.bottom()] {
-                        let transformed_y = transform.transform_y(y);
-                        min_y = min_y.min(transformed_y);
-                        max_y = max_y.max(transformed_y);
-                    }
+                    include(transform, path_bounds(path), 0.0);
                 }
             }
             Entity::StrokePath(path, radius, _, transform) => {
                 if let Some(path) = &**path {
-                    let radius = transform.scale_y * radius;
-                    let bounds = path.bounds();
-                    for y in [bounds.top(), bounds.bottom()] {
-                        let transformed_y = transform.transform_y(y);
-                        min_y = min_y.min(transformed_y - radius);
-                        max_y = max_y.max(transformed_y + radius);
-                    }
-                }
-            }
-            Entity::Image(_, transform) => {
-                for y in [0.0, 1.0] {
-                    let transformed_y = transform.transform_y(y);
-                    min_y = min_y.min(transformed_y);
-                    max_y = max_y.max(transformed_y);
+                    let radius = abs(transform.scale_x).max(abs(transform.scale_y)) * radius;
+                    include(transform, path_bounds(path), radius);
                 }
             }
+            Entity::Image(_, transform) => include(transform, [0.0, 0.0, 1.0, 1.0], 0.0),
             Entity::Label(label, _, text_shadow, transform) => {
                 let label = &*label.read().unwrap();
 
-                if text_shadow.is_some() {
-                    let transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
+                let shadow_transform = text_shadow
+                    .is_some()
+                    .then(|| transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET));
 
+                for transform in iter::once(transform).chain(&shadow_transform) {
                     for glyph in label.glyphs() {
                         if let Some(path) = &glyph.path {
                             let transform = transform
                                 .pre_translate(glyph.x, glyph.y)
                                 .pre_scale(glyph.scale, glyph.scale);
-
-                            let bounds = path.bounds();
-                            for y in [bounds.top(), bounds.bottom()] {
-                                let transformed_y = transform.transform_y(y);
-                                min_y = min_y.min(transformed_y);
-                                max_y = max_y.max(transformed_y);
-                            }
+                            include(&transform, path_bounds(path), 0.0);
                         }
                     }
                 }
 
-                for glyph in label.glyphs() {
-                    if let Some(path) = &glyph.path {
-                        let transform = transform
-                            .pre_translate(glyph.x, glyph.y)
-                            .pre_scale(glyph.scale, glyph.scale);
-
-                        let bounds = path.bounds();
-                        for y in [bounds.top(), bounds.bottom()] {
-                            let transformed_y = transform.transform_y(y);
-                            min_y = min_y.min(transformed_y);
-                            max_y = max_y.max(transformed_y);
-                        }
-                    }
+                for image in label.images() {
+                    let transform = transform
+                        .pre_translate(image.x, image.y)
+                        .pre_scale(image.width, image.height);
+                    include(&transform, [0.0, 0.0, 1.0, 1.0], 0.0);
                 }
             }
+            Entity::BeginGroup(..) | Entity::EndGroup => {}
+        }
+
+        if entity_bounds[0] <= entity_bounds[2] {
+            bounds.push(entity_bounds);
+        }
+    }
+}
+
+/// Turns the bounds of the entities into the regions of pixels that they
+/// cover. Regions that overlap are merged.
+fn collect_dirty_regions<'a>(
+    regions: &mut Vec<Region>,
+    bounds: impl Iterator<Item = &'a Bounds>,
+    [width, height]: [u32; 2],
+) {
+    let mut edges: Vec<[u32; 4]> = Vec::new();
+    // Anti-aliasing may touch the pixels right next to the bounds, so they
+    // are included as well.
+    for &[left, top, right, bottom] in bounds {
+        let left = ((left - 1.0) as u32).min(width);
+        let top = ((top - 1.0) as u32).min(height);
+        let right = ((right + 2.0) as u32).min(width);
+        let bottom = ((bottom + 2.0) as u32).min(height);
+        if left < right && top < bottom {
+            edges.push([left, top, right, bottom]);
+        }
+    }
+
+    let mut i = 0;
+    while i < edges.len() {
+        let [left, top, right, bottom] = edges[i];
+        let overlapping = edges[i + 1..]
+            .iter()
+            .position(|&[l, t, r, b]| left < r && l < right && top < b && t < bottom);
+        if let Some(j) = overlapping {
+            let [l, t, r, b] = edges.swap_remove(i + 1 + j);
+            edges[i] = [left.min(l), top.min(t), right.max(r), bottom.max(b)];
+            // The merged region may now overlap regions that were checked
+            // before, so all of them need to be checked again.
+            i = 0;
+        } else {
+            i += 1;
         }
     }
-    [min_y, max_y]
+
+    regions.extend(edges.into_iter().map(|[left, top, right, bottom]| Region {
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    }));
 }
@@ -11,14 +11,14 @@ use hashbrown::{HashSet, HashTable};
 
 use crate::{
     layout::LayoutState,
-    platform::prelude::*,
-    settings::{BLUR_FACTOR, Font, ImageCache},
+    platform::{math::f32::sin_cos, prelude::*},
+    settings::{BLUR_FACTOR, BlendMode, CacheLimits, CacheUsage, Font, ImageCache},
     util::xml::{AttributeWriter, DisplayAlreadyEscaped, Text, Value, Writer},
 };
 
 use super::{
     Background, Entity, FillShader, FontKind, ResourceAllocator, SceneManager, SharedOwnership,
-    Transform,
+    Transform, angled_gradient_points,
     consts::SHADOW_OFFSET,
     default_text_engine::{self, TextEngine},
     resource,
@@ -58,6 +58,18 @@ impl Renderer {
         }
     }
 
+    /// Sets the limits the cache of the glyphs used by the text is kept
+    /// within. The cache is collected after every render. By default it is
+    /// unlimited.
+    pub const fn set_glyph_cache_limits(&mut self, limits: CacheLimits) {
+        self.allocator.text_engine.set_glyph_cache_limits(limits);
+    }
+
+    /// Returns how many glyphs are in the glyph cache.
+    pub fn glyph_cache_usage(&self) -> CacheUsage {
+        self.allocator.text_engine.glyph_cache_usage()
+    }
+
     /// Renders the layout state with the chosen dimensions to the writer
     /// provided. It may detect that the layout got resized. In that case it
     /// returns the new ideal size. This is just a hint and can be ignored
@@ -75,6 +87,7 @@ impl Renderer {
             layout_state,
             image_cache,
         );
+        self.allocator.text_engine.collect_glyphs();
 
         let writer = &mut Writer::new_with_default_header(writer)?;
 
@@ -186,6 +199,7 @@ impl Renderer {
                         visit_path(current_id, defs, writer, &glyph.path)?;
                     }
                 }
+                Entity::BeginGroup(..) | Entity::EndGroup => {}
             }
         }
 
@@ -306,7 +320,10 @@ impl Renderer {
                             let alpha = match shader {
                                 FillShader::SolidColor([.., a]) => *a,
                                 FillShader::VerticalGradient([.., a1], [.., a2])
-                                | FillShader::HorizontalGradient([.., a1], [.., a2]) => {
+                                | FillShader::HorizontalGradient([.., a1], [.., a2])
+                                | FillShader::AngledGradient([.., a1], [.., a2], _)
+                                | FillShader::RadialGradient([.., a1], [.., a2], ..)
+                                | FillShader::ConicGradient([.., a1], [.., a2], ..) => {
                                     0.5 * (a1 + a2)
                                 }
                             };
@@ -354,6 +371,21 @@ impl Renderer {
                         )?;
                     }
                 }
+                Entity::BeginGroup(opacity, blend_mode) => {
+                    writer.just_start_tag("g", |writer| {
+                        if *opacity < 1.0 {
+                            writer.attribute("opacity", DisplayAlreadyEscaped(*opacity))?;
+                        }
+                        if let Some(blend_mode) = convert_blend_mode(*blend_mode) {
+                            writer.attribute(
+                                "style",
+                                DisplayAlreadyEscaped(format_args!("mix-blend-mode:{blend_mode}")),
+                            )?;
+                        }
+                        Ok::<_, fmt::Error>(())
+                    })?;
+                }
+                Entity::EndGroup => writer.just_end_tag("g")?,
             }
         }
 
@@ -426,35 +458,50 @@ fn visit_shader<W: Write>(
     writer: &mut Writer<W>,
     shader: &FillShader,
 ) -> fmt::Result {
-    let (vertical, start, end) = match shader {
-        FillShader::SolidColor(_) => return Ok(()),
-        FillShader::VerticalGradient(top, bottom) => (true, top, bottom),
-        FillShader::HorizontalGradient(left, right) => (false, left, right),
+    let Some((kind, start, end)) = gradient_kind(shader) else {
+        return Ok(());
     };
 
-    let gradient = defs.add_gradient(vertical, start, end);
+    let gradient = defs.add_gradient(kind, start, end);
 
     if defs.ptr_lookup.insert(Rc::as_ptr(&gradient) as usize) {
         gradient.id.set(*current_id);
         *current_id += 1;
 
-        writer.tag_with_content(
-            "linearGradient",
-            [
-                (
-                    "id",
-                    DisplayAlreadyEscaped(format_args!("{}", gradient.id.get())),
-                ),
-                (
-                    "x2",
-                    DisplayAlreadyEscaped(format_args!("{}", if vertical { "0" } else { "1" })),
-                ),
-                (
-                    "y2",
-                    DisplayAlreadyEscaped(format_args!("{}", if vertical { "1" } else { "0" })),
-                ),
-            ],
-            |writer| {
+        let tag = match kind {
+            GradientKind::Linear(_) => "linearGradient",
+            GradientKind::Radial(..) => "radialGradient",
+        };
+
+        writer.tag(tag, |mut writer| {
+            writer.attribute(
+                "id",
+                DisplayAlreadyEscaped(format_args!("{}", gradient.id.get())),
+            )?;
+            match kind {
+                GradientKind::Linear(VERTICAL) => {
+                    writer.attribute("x2", Text::new_escaped("0"))?;
+                    writer.attribute("y2", Text::new_escaped("1"))?;
+                }
+                GradientKind::Linear(HORIZONTAL) => {
+                    writer.attribute("x2", Text::new_escaped("1"))?;
+                    writer.attribute("y2", Text::new_escaped("0"))?;
+                }
+                GradientKind::Linear(direction) => {
+                    let [[x1, y1], [x2, y2]] =
+                        angled_gradient_points(direction, [0.0, 1.0], [0.0, 1.0]);
+                    writer.attribute("x1", DisplayAlreadyEscaped(x1))?;
+                    writer.attribute("y1", DisplayAlreadyEscaped(y1))?;
+                    writer.attribute("x2", DisplayAlreadyEscaped(x2))?;
+                    writer.attribute("y2", DisplayAlreadyEscaped(y2))?;
+                }
+                GradientKind::Radial([x, y], radius) => {
+                    writer.attribute("cx", DisplayAlreadyEscaped(x))?;
+                    writer.attribute("cy", DisplayAlreadyEscaped(y))?;
+                    writer.attribute("r", DisplayAlreadyEscaped(radius))?;
+                }
+            }
+            writer.content(|writer| {
                 writer.tag("stop", |mut writer| {
                     let (start_rgb, start_a) = convert_color_or_transparent(start);
                     writer.attribute("stop-color", start_rgb)?;
@@ -472,8 +519,8 @@ fn visit_shader<W: Write>(
                     }
                     Ok(())
                 })
-            },
-        )?;
+            })
+        })?;
     }
 
     Ok(())
@@ -484,16 +531,65 @@ struct SvgAllocator {
     defs: Rc<RefCell<Defs>>,
 }
 
+/// The direction of a vertical gradient.
+const VERTICAL: [f32; 2] = [0.0, 1.0];
+/// The direction of a horizontal gradient.
+const HORIZONTAL: [f32; 2] = [1.0, 0.0];
+
+/// The geometry of a gradient. The coordinates are relative to the bounding
+/// box of the element that is filled.
+#[derive(Copy, Clone)]
+enum GradientKind {
+    /// A linear gradient along the direction provided.
+    Linear([f32; 2]),
+    /// A radial gradient around the center provided, with the radius provided.
+    Radial([f32; 2], f32),
+}
+
+impl GradientKind {
+    const fn to_bits(self) -> (bool, [u32; 3]) {
+        match self {
+            GradientKind::Linear([x, y]) => (false, [x.to_bits(), y.to_bits(), 0]),
+            GradientKind::Radial([x, y], radius) => {
+                (true, [x.to_bits(), y.to_bits(), radius.to_bits()])
+            }
+        }
+    }
+}
+
+/// Determines the gradient to use for the shader. SVG doesn't support conic
+/// gradients, so they are approximated with a linear gradient along the
+/// direction the sweep starts at.
+fn gradient_kind(shader: &FillShader) -> Option<(GradientKind, &[f32; 4], &[f32; 4])> {
+    Some(match shader {
+        FillShader::SolidColor(_) => return None,
+        FillShader::VerticalGradient(top, bottom) => (GradientKind::Linear(VERTICAL), top, bottom),
+        FillShader::HorizontalGradient(left, right) => {
+            (GradientKind::Linear(HORIZONTAL), left, right)
+        }
+        FillShader::AngledGradient(start, end, direction) => {
+            (GradientKind::Linear(*direction), start, end)
+        }
+        FillShader::RadialGradient(center, edge, position, radius) => {
+            (GradientKind::Radial(*position, *radius), center, edge)
+        }
+        FillShader::ConicGradient(start, end, _, angle) => {
+            let (sin, cos) = sin_cos(angle.to_radians());
+            (GradientKind::Linear([sin, -cos]), start, end)
+        }
+    })
+}
+
 struct Gradient {
     id: Cell<usize>,
-    vertical: bool,
+    kind: GradientKind,
     start: [f32; 4],
     end: [f32; 4],
 }
 
 impl core::hash::Hash for Gradient {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-        self.vertical.hash(state);
+        self.kind.to_bits().hash(state);
         self.start.map(f32::to_bits).hash(state);
         self.end.map(f32::to_bits).hash(state);
     }
@@ -501,7 +597,7 @@ impl core::hash::Hash for Gradient {
 
 impl PartialEq for Gradient {
     fn eq(&self, other: &Self) -> bool {
-        self.vertical == other.vertical
+        self.kind.to_bits() == other.kind.to_bits()
             && self.start.map(f32::to_bits) == other.start.map(f32::to_bits)
             && self.end.map(f32::to_bits) == other.end.map(f32::to_bits)
     }
@@ -515,12 +611,17 @@ struct Defs {
 }
 
 impl Defs {
-    fn add_gradient(&mut self, vertical: bool, start: &[f32; 4], end: &[f32; 4]) -> Rc<Gradient> {
+    fn add_gradient(
+        &mut self,
+        kind: GradientKind,
+        start: &[f32; 4],
+        end: &[f32; 4],
+    ) -> Rc<Gradient> {
         let hasher = foldhash::fast::FixedState::default();
         let hasher = |val: &Gradient| hasher.hash_one(val);
         let gradient = Gradient {
             id: Cell::new(0),
-            vertical,
+            kind,
             start: *start,
             end: *end,
         };
@@ -718,6 +819,25 @@ impl super::PathBuilder for PathBuilder {
     }
 }
 
+/// Determines the media type to use for the data URL of the image and its
+/// dimensions. Raster images don't need a media type, as browsers detect their
+/// format, but SVG images do. They are embedded as they are, so they stay crisp
+/// at any size.
+#[cfg(feature = "image")]
+fn media_type_and_dimensions(data: &[u8]) -> Option<(&'static str, f32, f32)> {
+    #[cfg(feature = "svg-images")]
+    if crate::settings::is_svg(data) {
+        let size = resvg::usvg::Tree::from_data(data, &Default::default())
+            .ok()?
+            .size();
+        return Some(("image/svg+xml", size.width(), size.height()));
+    }
+
+    let format = image::guess_format(data).ok()?;
+    let (width, height) = crate::util::image::get_dimensions(format, data)?;
+    Some(("", width as f32, height as f32))
+}
+
 impl ResourceAllocator for SvgAllocator {
     type PathBuilder = PathBuilder;
     type Path = SvgPath;
@@ -734,14 +854,13 @@ impl ResourceAllocator for SvgAllocator {
     fn create_image(&mut self, _data: &[u8]) -> Option<Self::Image> {
         #[cfg(feature = "image")]
         {
-            let format = image::guess_format(_data).ok()?;
-
-            let (width, height) = crate::util::image::get_dimensions(format, _data)?;
-            let (width, height) = (width as f32, height as f32);
+            let (media_type, width, height) = media_type_and_dimensions(_data)?;
             let (rwidth, rheight) = (width.recip(), height.recip());
 
             let mut buf = String::new();
-            buf.push_str("data:;base64,");
+            buf.push_str("data:");
+            buf.push_str(media_type);
+            buf.push_str(";base64,");
 
             // SAFETY: We encode Base64 to the end of the string, which is
             // always valid UTF-8. Once we've written it, we simply increase
@@ -955,12 +1074,9 @@ fn convert_shader(shader: &FillShader, defs: &Rc<RefCell<Defs>>) -> Option<(Fill
             let (rgb, a) = convert_color(c)?;
             (Fill::Rgb(rgb), a)
         }
-        FillShader::VerticalGradient(top, bottom) => {
-            let gradient = defs.borrow_mut().add_gradient(true, top, bottom);
-            (Fill::Url(gradient.id.get()), None)
-        }
-        FillShader::HorizontalGradient(left, right) => {
-            let gradient = defs.borrow_mut().add_gradient(false, left, right);
+        _ => {
+            let (kind, start, end) = gradient_kind(shader)?;
+            let gradient = defs.borrow_mut().add_gradient(kind, start, end);
             (Fill::Url(gradient.id.get()), None)
         }
     })
@@ -1000,6 +1116,18 @@ impl Value for Rgb {
     }
 }
 
+const fn convert_blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+    Some(match blend_mode {
+        BlendMode::Normal => return None,
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Add => "plus-lighter",
+    })
+}
+
 fn convert_color(&[r, g, b, a]: &[f32; 4]) -> Option<(Rgb, Option<f32>)> {
     if a == 0.0 {
         return None;
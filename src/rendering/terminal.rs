@@ -0,0 +1,685 @@
+//! Provides a renderer that draws layouts as text into a grid of character
+//! cells, so they can be shown in terminals. Every component is reduced to its
+//! text and colors. There are no fonts, images or effects. The grid can either
+//! be accessed directly, which is useful for terminal user interface
+//! libraries, or it can be written out with ANSI escape codes.
+
+use core::{fmt, iter};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    component::{
+        detailed_timer, graph, key_value, overlay_widgets, splits, tabs, text, timer, title,
+    },
+    layout::{ComponentState, LayoutDirection, LayoutState},
+    platform::prelude::*,
+    settings::{Color, Gradient, LayoutBackground, ListGradient},
+};
+
+/// The number of pixels in the default pixel space that make up a row.
+const PIXELS_PER_ROW: u32 = 24;
+
+/// The cell following a wide character, such as most of the East Asian
+/// characters, is covered by it. Its symbol is this character.
+pub const CONTINUATION: char = '\0';
+
+/// A single cell of the grid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cell {
+    /// The character shown in the cell. This is [`CONTINUATION`] if the cell
+    /// is covered by the wide character in the cell before it.
+    pub symbol: char,
+    /// The color of the character.
+    pub foreground: Color,
+    /// The color of the cell. A fully transparent color means that the
+    /// terminal's own background is meant to be visible.
+    pub background: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            foreground: Color::transparent(),
+            background: Color::transparent(),
+        }
+    }
+}
+
+/// The terminal renderer draws layouts into a grid of character cells.
+#[derive(Default)]
+pub struct Renderer {
+    cells: Vec<Cell>,
+    size: [usize; 2],
+}
+
+impl Renderer {
+    /// Creates a new terminal renderer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the layout state into a grid with the amount of columns and
+    /// rows provided. Components that don't fit into the grid are cut off. The
+    /// amount of rows the layout needs to be shown in its entirety is
+    /// returned. This is just a hint and can be ignored entirely.
+    pub fn render(&mut self, layout_state: &LayoutState, [columns, rows]: [usize; 2]) -> usize {
+        self.size = [columns, rows];
+        self.cells.clear();
+        self.cells.resize(columns * rows, Cell::default());
+
+        let mut canvas = Canvas {
+            cells: &mut self.cells,
+            columns,
+            rows,
+            layout_state,
+        };
+
+        if let LayoutBackground::Gradient(gradient) = &layout_state.background {
+            canvas.fill([0, 0], [columns, rows], gradient);
+        }
+
+        let components = &layout_state.components;
+        match layout_state.direction {
+            LayoutDirection::Vertical => {
+                let mut y = 0;
+                for component in components {
+                    y += canvas.component([0, y], columns, component);
+                }
+                y
+            }
+            LayoutDirection::Horizontal => {
+                // Terminals are rarely wide enough to show the components with
+                // their preferred widths, so they share the width equally.
+                let mut needed_rows = 0;
+                for (index, component) in components.iter().enumerate() {
+                    let x = index * columns / components.len();
+                    let width = (index + 1) * columns / components.len() - x;
+                    needed_rows = needed_rows.max(canvas.component([x, 0], width, component));
+                }
+                needed_rows
+            }
+        }
+    }
+
+    /// Accesses the cells of the grid, row by row.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns the amount of columns and rows of the grid.
+    pub const fn size(&self) -> [usize; 2] {
+        self.size
+    }
+
+    /// Writes the grid with ANSI escape codes for the colors to the writer
+    /// provided. The colors are specified as 24-bit colors. Each row ends with
+    /// a carriage return and a line feed, so the output can be written as is,
+    /// even if the terminal is in raw mode. Positioning the cursor is up to the
+    /// caller.
+    pub fn write_ansi<W: fmt::Write>(&self, mut writer: W) -> fmt::Result {
+        let [columns, _] = self.size;
+        if columns == 0 {
+            return Ok(());
+        }
+        for row in self.cells.chunks(columns) {
+            let (mut foreground, mut background) = (None, None);
+            for cell in row {
+                if cell.symbol == CONTINUATION {
+                    continue;
+                }
+                let cell_background = visible(cell.background);
+                if background != Some(cell_background) {
+                    match cell_background {
+                        Some([r, g, b]) => write!(writer, "\x1b[48;2;{r};{g};{b}m")?,
+                        None => writer.write_str("\x1b[49m")?,
+                    }
+                    background = Some(cell_background);
+                }
+                let cell_foreground = visible(blend(cell.foreground, cell.background));
+                if cell.symbol != ' ' && foreground != Some(cell_foreground) {
+                    match cell_foreground {
+                        Some([r, g, b]) => write!(writer, "\x1b[38;2;{r};{g};{b}m")?,
+                        None => writer.write_str("\x1b[39m")?,
+                    }
+                    foreground = Some(cell_foreground);
+                }
+                writer.write_char(cell.symbol)?;
+            }
+            writer.write_str("\x1b[0m\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+struct Canvas<'a> {
+    cells: &'a mut [Cell],
+    columns: usize,
+    rows: usize,
+    layout_state: &'a LayoutState,
+}
+
+impl Canvas<'_> {
+    /// Draws the component with its top left corner at the position provided.
+    /// The amount of rows the component needs is returned.
+    fn component(
+        &mut self,
+        position: [usize; 2],
+        width: usize,
+        component: &ComponentState,
+    ) -> usize {
+        match component {
+            ComponentState::BlankSpace(state) => {
+                let rows = to_rows(state.size);
+                self.fill(position, [width, rows], &state.background);
+                rows
+            }
+            ComponentState::DetailedTimer(state) => self.detailed_timer(position, width, state),
+            ComponentState::Graph(state) => self.graph(position, width, state),
+            ComponentState::KeyValue(state) => self.key_value(position, width, state),
+            ComponentState::OverlayWidgets(state) => self.overlay_widgets(position, width, state),
+            ComponentState::Separator(_) => {
+                let color = self.layout_state.separators_color;
+                let [x, y] = position;
+                for x in x..x + width {
+                    self.put([x, y], '─', color);
+                }
+                1
+            }
+            ComponentState::Splits(state) => self.splits(position, width, state),
+            ComponentState::Tabs(state) => self.tabs(position, width, state),
+            ComponentState::Text(state) => self.text_component(position, width, state),
+            ComponentState::Timer(state) => self.timer(position, width, state),
+            ComponentState::Title(state) => self.title(position, width, state),
+        }
+    }
+
+    fn detailed_timer(
+        &mut self,
+        [x, mut y]: [usize; 2],
+        width: usize,
+        state: &detailed_timer::State,
+    ) -> usize {
+        let text_color = self.layout_state.text_color;
+        let start_y = y;
+        let rows = 2 + state.segment_name.is_some() as usize;
+        self.fill([x, y], [width, rows], &state.background);
+
+        if let Some(name) = &state.segment_name {
+            let color = state.segment_name_color.unwrap_or(text_color);
+            self.text_left([x + 1, y], width.saturating_sub(2), name, color);
+            y += 1;
+        }
+
+        for (timer, comparison) in [
+            (&state.timer, &state.comparison1),
+            (&state.segment_timer, &state.comparison2),
+        ] {
+            let left = self.timer_text([x, y], width, timer);
+            if let Some(comparison) = comparison {
+                let times_color = state.comparison_times_color.unwrap_or(text_color);
+                let names_color = state.comparison_names_color.unwrap_or(text_color);
+                let time_x = x
+                    + 1
+                    + self.text_left(
+                        [x + 1, y],
+                        left.saturating_sub(x + 2),
+                        &comparison.name,
+                        names_color,
+                    );
+                self.text_left(
+                    [time_x + 1, y],
+                    left.saturating_sub(time_x + 2),
+                    &comparison.time,
+                    times_color,
+                );
+            }
+            y += 1;
+        }
+
+        y - start_y
+    }
+
+    fn graph(&mut self, [x, y]: [usize; 2], width: usize, state: &graph::State) -> usize {
+        let rows = to_rows(state.height).max(1);
+        for row in 0..rows {
+            let center = (row as f32 + 0.5) / rows as f32;
+            let color = if center < state.middle {
+                state.top_background_color
+            } else {
+                state.bottom_background_color
+            };
+            self.fill([x, y + row], [width, 1], &Gradient::Plain(color));
+        }
+
+        let to_cell = |px: f32, py: f32| {
+            let column = ((px * width as f32) as usize).min(width.saturating_sub(1));
+            let row = ((py * rows as f32) as usize).min(rows - 1);
+            [x + column, y + row]
+        };
+
+        for &grid_y in &state.horizontal_grid_lines {
+            let [_, row] = to_cell(0.0, grid_y);
+            for column in x..x + width {
+                self.put([column, row], '┄', state.grid_lines_color);
+            }
+        }
+
+        for point in state.points.iter().skip(1) {
+            let color = if point.is_best_segment {
+                state.best_segment_color
+            } else {
+                state.graph_lines_color
+            };
+            self.put(to_cell(point.x, point.y), '●', color);
+        }
+
+        rows
+    }
+
+    fn key_value(&mut self, [x, y]: [usize; 2], width: usize, state: &key_value::State) -> usize {
+        let text_color = self.layout_state.text_color;
+        let rows = 1 + state.display_two_rows as usize;
+        self.fill([x, y], [width, rows], &state.background);
+
+        let value_color = state.value_color.unwrap_or(text_color);
+        let value_x = self.text_right(
+            [x + width.saturating_sub(1), y + rows - 1],
+            width,
+            &state.value,
+            value_color,
+        );
+        let key_end = if state.display_two_rows {
+            x + width.saturating_sub(1)
+        } else {
+            value_x.saturating_sub(1)
+        };
+        let key = abbreviate(
+            iter::once(&*state.key).chain(state.key_abbreviations.iter().map(|a| &**a)),
+            key_end.saturating_sub(x + 1),
+        );
+        self.text_left(
+            [x + 1, y],
+            key_end.saturating_sub(x + 1),
+            key,
+            state.key_color.unwrap_or(text_color),
+        );
+
+        rows
+    }
+
+    fn overlay_widgets(
+        &mut self,
+        [x, mut y]: [usize; 2],
+        width: usize,
+        state: &overlay_widgets::State,
+    ) -> usize {
+        let text_color = self.layout_state.text_color;
+        let label_color = state.label_color.unwrap_or(text_color);
+        let value_color = state.value_color.unwrap_or(text_color);
+        let progress_gradient = Gradient::Plain(state.progress_color);
+        let rows = 1 + state.display_two_rows as usize;
+        let start_y = y;
+
+        for widget in &state.widgets {
+            self.fill([x, y], [width, rows], &state.background);
+            if let Some(progress) = widget.progress {
+                let filled = (progress * width as f32).round() as usize;
+                self.fill([x, y], [filled.min(width), rows], &progress_gradient);
+            }
+
+            let value_x = self.text_right(
+                [x + width.saturating_sub(1), y + rows - 1],
+                width,
+                &widget.value,
+                value_color,
+            );
+            let label_end = if state.display_two_rows {
+                x + width.saturating_sub(1)
+            } else {
+                value_x.saturating_sub(1)
+            };
+            self.text_left(
+                [x + 1, y],
+                label_end.saturating_sub(x + 1),
+                &widget.label,
+                label_color,
+            );
+
+            y += rows;
+        }
+
+        y - start_y
+    }
+
+    fn splits(&mut self, [x, mut y]: [usize; 2], width: usize, state: &splits::State) -> usize {
+        let text_color = self.layout_state.text_color;
+        let start_y = y;
+        let right = x + width.saturating_sub(1);
+        let split_rows = 1 + state.display_two_rows as usize;
+
+        // All the values of a column are aligned to the right edge of the
+        // column, so the columns need to be as wide as their widest value.
+        let mut column_widths = Vec::new();
+        let labels = state.column_labels.as_deref().unwrap_or_default();
+        for values in iter::once(labels.iter().map(|l| &**l).collect::<Vec<_>>()).chain(
+            state
+                .splits
+                .iter()
+                .map(|s| s.columns.iter().map(|c| &*c.value).collect()),
+        ) {
+            if values.len() > column_widths.len() {
+                column_widths.resize(values.len(), 0);
+            }
+            for (value, column_width) in values.iter().zip(&mut column_widths) {
+                *column_width = (*column_width).max(text_width(value));
+            }
+        }
+
+        if let Some(labels) = &state.column_labels {
+            let mut column_right = right;
+            for (label, column_width) in labels.iter().zip(&column_widths) {
+                self.text_right([column_right, y], *column_width, label, text_color);
+                column_right = column_right.saturating_sub(column_width + 1);
+            }
+            y += 1;
+        }
+
+        let split_backgrounds = match &state.background {
+            ListGradient::Same(gradient) => [*gradient, *gradient],
+            ListGradient::Alternating(even, odd) => [Gradient::Plain(*even), Gradient::Plain(*odd)],
+        };
+
+        for split in state.splits.iter() {
+            let background = if split.is_current_split {
+                &state.current_split_gradient
+            } else {
+                &split_backgrounds[split.index % 2]
+            };
+            self.fill([x, y], [width, split_rows], background);
+
+            let mut column_right = right;
+            let mut name_end = right;
+            for (column, column_width) in split.columns.iter().zip(&column_widths) {
+                if !column.value.is_empty() {
+                    name_end = self.text_right(
+                        [column_right, y + split_rows - 1],
+                        *column_width,
+                        &column.value,
+                        column.visual_color,
+                    );
+                }
+                column_right = column_right.saturating_sub(column_width + 1);
+            }
+            if state.display_two_rows {
+                name_end = right;
+            }
+            self.text_left(
+                [x + 1, y],
+                name_end.saturating_sub(x + 2),
+                &split.name,
+                text_color,
+            );
+
+            y += split_rows;
+        }
+
+        y - start_y
+    }
+
+    fn tabs(&mut self, [x, mut y]: [usize; 2], width: usize, state: &tabs::State) -> usize {
+        let start_y = y;
+        if state.show_tab_names {
+            self.fill([x, y], [width, 1], &state.background);
+            if let Some(name) = state.tab_names.get(state.selected_tab as usize) {
+                self.text_centered([x, y], width, name, self.layout_state.text_color);
+            }
+            y += 1;
+        }
+        for component in &state.components {
+            y += self.component([x, y], width, component);
+        }
+        y - start_y
+    }
+
+    fn text_component(&mut self, [x, y]: [usize; 2], width: usize, state: &text::State) -> usize {
+        let text_color = self.layout_state.text_color;
+        let rows = 1 + state.display_two_rows as usize;
+        self.fill([x, y], [width, rows], &state.background);
+
+        let left_center_color = state.left_center_color.unwrap_or(text_color);
+        match &state.text {
+            text::TextState::Center(text) => {
+                self.text_centered([x, y], width, text, left_center_color);
+            }
+            text::TextState::Split(left, right) => {
+                let right_color = state.right_color.unwrap_or(text_color);
+                let right_x = self.text_right(
+                    [x + width.saturating_sub(1), y + rows - 1],
+                    width,
+                    right,
+                    right_color,
+                );
+                let left_end = if state.display_two_rows {
+                    x + width.saturating_sub(1)
+                } else {
+                    right_x.saturating_sub(1)
+                };
+                self.text_left(
+                    [x + 1, y],
+                    left_end.saturating_sub(x + 1),
+                    left,
+                    left_center_color,
+                );
+            }
+        }
+
+        rows
+    }
+
+    fn timer(&mut self, [x, y]: [usize; 2], width: usize, state: &timer::State) -> usize {
+        self.fill([x, y], [width, 1], &state.background);
+        self.timer_text([x, y], width, state);
+        1
+    }
+
+    /// Draws the time of the timer aligned to the right. The column where the
+    /// time begins is returned.
+    fn timer_text(&mut self, [x, y]: [usize; 2], width: usize, state: &timer::State) -> usize {
+        let right = x + width.saturating_sub(1);
+        let fraction_x = self.text_right([right, y], width, &state.fraction, state.bottom_color);
+        self.text_right(
+            [fraction_x.saturating_sub(1), y],
+            fraction_x.saturating_sub(x),
+            &state.time,
+            state.top_color,
+        )
+    }
+
+    fn title(&mut self, [x, y]: [usize; 2], width: usize, state: &title::State) -> usize {
+        let text_color = state.text_color.unwrap_or(self.layout_state.text_color);
+        let rows = if state.line2.is_empty() { 1 } else { 2 };
+        self.fill([x, y], [width, rows], &state.background);
+
+        let attempts = match (state.finished_runs, state.attempts) {
+            _ if state.is_practicing => String::from("Practice"),
+            (Some(a), Some(b)) => format!("{a}/{b}"),
+            (Some(a), _) | (_, Some(a)) => format!("{a}"),
+            _ => String::new(),
+        };
+        let right = x + width.saturating_sub(1);
+        let attempts_x = self.text_right([right, y + rows - 1], width, &attempts, text_color);
+
+        for (row, line) in [&state.line1, &state.line2]
+            .into_iter()
+            .enumerate()
+            .take(rows)
+        {
+            let end = if row + 1 == rows && !attempts.is_empty() {
+                attempts_x.saturating_sub(1)
+            } else {
+                right
+            };
+            let available = end.saturating_sub(x + 1);
+            let text = abbreviate(line.iter().map(|l| &**l), available);
+            if state.is_centered {
+                self.text_centered([x + 1, y + row], available, text, text_color);
+            } else {
+                self.text_left([x + 1, y + row], available, text, text_color);
+            }
+        }
+
+        rows
+    }
+
+    fn fill(&mut self, [x, y]: [usize; 2], [width, height]: [usize; 2], gradient: &Gradient) {
+        if matches!(gradient, Gradient::Transparent) {
+            return;
+        }
+        for row in y..(y + height).min(self.rows) {
+            for column in x..(x + width).min(self.columns) {
+                let position = [
+                    (column - x) as f32 / width.saturating_sub(1).max(1) as f32,
+                    (row - y) as f32 / height.saturating_sub(1).max(1) as f32,
+                ];
+                let cell = &mut self.cells[row * self.columns + column];
+                cell.background = blend(gradient_color(gradient, position), cell.background);
+            }
+        }
+    }
+
+    fn put(&mut self, [x, y]: [usize; 2], symbol: char, color: Color) {
+        if x < self.columns && y < self.rows {
+            let cell = &mut self.cells[y * self.columns + x];
+            cell.symbol = symbol;
+            cell.foreground = color;
+        }
+    }
+
+    /// Draws the text starting at the position provided. If it's wider than
+    /// the width provided, it gets cut off with an ellipsis. The width of the
+    /// text that was drawn is returned.
+    fn text_left(&mut self, [x, y]: [usize; 2], width: usize, text: &str, color: Color) -> usize {
+        let needs_ellipsis = text_width(text) > width;
+        let available = if needs_ellipsis {
+            width.saturating_sub(1)
+        } else {
+            width
+        };
+        let mut column = x;
+        for c in text.chars() {
+            let Some(char_width) = c.width().filter(|&w| w != 0) else {
+                continue;
+            };
+            if column + char_width > x + available {
+                break;
+            }
+            self.put([column, y], c, color);
+            for continuation in 1..char_width {
+                self.put([column + continuation, y], CONTINUATION, color);
+            }
+            column += char_width;
+        }
+        if needs_ellipsis && width > 0 {
+            self.put([column, y], '…', color);
+            column += 1;
+        }
+        column - x
+    }
+
+    /// Draws the text such that it ends at the column provided. The column
+    /// where the text begins is returned.
+    fn text_right(
+        &mut self,
+        [right, y]: [usize; 2],
+        width: usize,
+        text: &str,
+        color: Color,
+    ) -> usize {
+        let text_width = text_width(text).min(width);
+        let x = (right + 1).saturating_sub(text_width);
+        self.text_left([x, y], text_width, text, color);
+        x
+    }
+
+    fn text_centered(&mut self, [x, y]: [usize; 2], width: usize, text: &str, color: Color) {
+        let text_width = text_width(text).min(width);
+        self.text_left([x + (width - text_width) / 2, y], text_width, text, color);
+    }
+}
+
+/// Chooses the longest of the abbreviations that fits into the width provided.
+/// If none of them fit, the shortest one is chosen.
+fn abbreviate<'a>(abbreviations: impl IntoIterator<Item = &'a str>, width: usize) -> &'a str {
+    let mut shortest = "";
+    let mut shortest_width = usize::MAX;
+    let mut longest_fitting = None;
+    for abbreviation in abbreviations {
+        let abbreviation_width = text_width(abbreviation);
+        if abbreviation_width <= width
+            && longest_fitting.is_none_or(|(_, w)| abbreviation_width > w)
+        {
+            longest_fitting = Some((abbreviation, abbreviation_width));
+        }
+        if abbreviation_width < shortest_width {
+            shortest = abbreviation;
+            shortest_width = abbreviation_width;
+        }
+    }
+    longest_fitting.map_or(shortest, |(abbreviation, _)| abbreviation)
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().filter_map(|c| c.width()).sum()
+}
+
+const fn to_rows(pixels: u32) -> usize {
+    ((pixels + PIXELS_PER_ROW / 2) / PIXELS_PER_ROW) as usize
+}
+
+/// Determines the color of the gradient at the position provided, which is
+/// relative to the area that is filled. Only vertical and horizontal gradients
+/// vary between the cells, all the others are shown as the average of their
+/// colors.
+fn gradient_color(gradient: &Gradient, [x, y]: [f32; 2]) -> Color {
+    match *gradient {
+        Gradient::Transparent => Color::transparent(),
+        Gradient::Plain(color) => color,
+        Gradient::Vertical(top, bottom) => mix(top, bottom, y),
+        Gradient::Horizontal(left, right) => mix(left, right, x),
+        Gradient::Angled(a, b, _)
+        | Gradient::Radial(a, b, ..)
+        | Gradient::Conic(a, b, ..)
+        | Gradient::HueCycling(a, b, _)
+        | Gradient::Rotating(a, b, _) => mix(a, b, 0.5),
+    }
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let [a, b] = [a, b].map(|c| c.to_array());
+    let [red, green, blue, alpha] = [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t);
+    Color::rgba(red, green, blue, alpha)
+}
+
+/// Draws the color on top of the color below it.
+fn blend(top: Color, bottom: Color) -> Color {
+    let alpha = top.alpha + bottom.alpha * (1.0 - top.alpha);
+    if alpha <= 0.0 {
+        return Color::transparent();
+    }
+    let channel = |t: f32, b: f32| (t * top.alpha + b * bottom.alpha * (1.0 - top.alpha)) / alpha;
+    Color::rgba(
+        channel(top.red, bottom.red),
+        channel(top.green, bottom.green),
+        channel(top.blue, bottom.blue),
+        alpha,
+    )
+}
+
+/// Converts the color to the color to show in the terminal. Fully transparent
+/// colors are not shown at all, so the terminal's default color is used.
+fn visible(color: Color) -> Option<[u8; 3]> {
+    let [r, g, b, a] = color.to_rgba8();
+    (a != 0).then_some([r, g, b])
+}
@@ -19,6 +19,10 @@ extern "C" {
     pub fn set_font(this: &CanvasRenderingContext2d, value: &JsString);
     #[wasm_bindgen(structural, method, setter, js_class = "CanvasRenderingContext2D", js_name = fontKerning)]
     pub fn set_font_kerning(this: &CanvasRenderingContext2d, value: &JsString);
+    #[wasm_bindgen(structural, method, setter, js_class = "CanvasRenderingContext2D", js_name = globalAlpha)]
+    pub fn set_global_alpha(this: &CanvasRenderingContext2d, value: f64);
+    #[wasm_bindgen(structural, method, setter, js_class = "CanvasRenderingContext2D", js_name = globalCompositeOperation)]
+    pub fn set_global_composite_operation(this: &CanvasRenderingContext2d, value: &JsString);
     #[wasm_bindgen(structural, method, setter, js_class = "CanvasRenderingContext2D", js_name = shadowColor)]
     pub fn set_shadow_color(this: &CanvasRenderingContext2d, value: &JsString);
     #[wasm_bindgen(structural, method, setter, js_class = "CanvasRenderingContext2D", js_name = shadowBlur)]
@@ -48,6 +52,23 @@ extern "C" {
         x1: f64,
         y1: f64,
     ) -> web_sys::CanvasGradient;
+    #[wasm_bindgen(catch, method, structural, js_class = "CanvasRenderingContext2D", js_name = createRadialGradient)]
+    pub fn create_radial_gradient(
+        this: &CanvasRenderingContext2d,
+        x0: f64,
+        y0: f64,
+        r0: f64,
+        x1: f64,
+        y1: f64,
+        r1: f64,
+    ) -> Result<web_sys::CanvasGradient, JsValue>;
+    #[wasm_bindgen(method, structural, js_class = "CanvasRenderingContext2D", js_name = createConicGradient)]
+    pub fn create_conic_gradient(
+        this: &CanvasRenderingContext2d,
+        start_angle: f64,
+        x: f64,
+        y: f64,
+    ) -> web_sys::CanvasGradient;
     #[wasm_bindgen(method, structural, js_class = "CanvasRenderingContext2D", js_name = clearRect)]
     pub fn clear_rect(this: &CanvasRenderingContext2d, x: f64, y: f64, w: f64, h: f64);
     #[wasm_bindgen(method, structural, js_class = "CanvasRenderingContext2D", js_name = fillRect)]
@@ -64,6 +85,10 @@ extern "C" {
         this: &CanvasRenderingContext2d,
         text: &JsString,
     ) -> Result<web_sys::TextMetrics, JsValue>;
+    #[wasm_bindgen(method, structural, js_class = "CanvasRenderingContext2D", js_name = save)]
+    pub fn save(this: &CanvasRenderingContext2d);
+    #[wasm_bindgen(method, structural, js_class = "CanvasRenderingContext2D", js_name = restore)]
+    pub fn restore(this: &CanvasRenderingContext2d);
     #[wasm_bindgen(catch, method, structural, js_class = "CanvasRenderingContext2D", js_name = resetTransform)]
     pub fn reset_transform(this: &CanvasRenderingContext2d) -> Result<(), JsValue>;
     #[wasm_bindgen(catch, method, structural, js_class = "CanvasRenderingContext2D", js_name = setTransform)]
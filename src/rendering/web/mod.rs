@@ -19,15 +19,17 @@ use web_sys::{Blob, HtmlCanvasElement, HtmlElement, ImageBitmap, Path2d, Window}
 use crate::{
     layout::LayoutState,
     settings::{
-        BLUR_FACTOR, BackgroundImage, Font, FontStretch, FontStyle, FontWeight, ImageCache,
+        BLUR_FACTOR, BackgroundImage, BlendMode, Font, FontStretch, FontStyle, FontWeight,
+        ImageCache,
     },
 };
 
 use self::bindings::CanvasRenderingContext2d;
 
 use super::{
-    Background, Entity, FillShader, FontKind, Handle, Label, PathBuilder, ResourceAllocator,
-    SceneManager, SharedOwnership, Transform, consts::SHADOW_OFFSET,
+    Background, Entity, FillShader, FontKind, Handle, Hinting, Label, PathBuilder,
+    ResourceAllocator, SceneManager, SharedOwnership, Transform, angled_gradient_points,
+    consts::SHADOW_OFFSET,
 };
 
 mod bindings;
@@ -534,6 +536,26 @@ impl JsValueCache {
             FillShader::HorizontalGradient(l, r) => {
                 HashShader::HorizontalGradient(cast(*l), cast(*r), cast(handle.bounds_x()))
             }
+            FillShader::AngledGradient(s, e, d) => HashShader::AngledGradient(
+                cast(*s),
+                cast(*e),
+                cast(*d),
+                cast([handle.bounds_x(), handle.bounds_y()]),
+            ),
+            FillShader::RadialGradient(c, e, p, r) => HashShader::RadialGradient(
+                cast(*c),
+                cast(*e),
+                cast(*p),
+                r.to_bits(),
+                cast([handle.bounds_x(), handle.bounds_y()]),
+            ),
+            FillShader::ConicGradient(s, e, p, a) => HashShader::ConicGradient(
+                cast(*s),
+                cast(*e),
+                cast(*p),
+                a.to_bits(),
+                cast([handle.bounds_x(), handle.bounds_y()]),
+            ),
         };
 
         self.shaders
@@ -554,6 +576,42 @@ impl JsValueCache {
                     let _ = gradient.add_color_stop(1.0, Self::raw_color(&mut self.str_buf, r));
                     gradient.unchecked_into()
                 }
+                FillShader::AngledGradient(s, e, d) => {
+                    let [[x1, y1], [x2, y2]] =
+                        angled_gradient_points(*d, handle.bounds_x(), handle.bounds_y());
+                    let gradient = ctx.create_linear_gradient(x1 as _, y1 as _, x2 as _, y2 as _);
+                    let _ = gradient.add_color_stop(0.0, Self::raw_color(&mut self.str_buf, s));
+                    let _ = gradient.add_color_stop(1.0, Self::raw_color(&mut self.str_buf, e));
+                    gradient.unchecked_into()
+                }
+                FillShader::RadialGradient(c, e, [x, y], r) => {
+                    // Canvas gradients can only be circles, so the larger
+                    // radius of the ellipse is used.
+                    let [min_x, max_x] = handle.bounds_x();
+                    let [min_y, max_y] = handle.bounds_y();
+                    let (width, height) = (max_x - min_x, max_y - min_y);
+                    let (x, y) = ((min_x + x * width) as f64, (min_y + y * height) as f64);
+                    let radius = (r * width.max(height)) as f64;
+                    let Ok(gradient) = ctx.create_radial_gradient(x, y, 0.0, x, y, radius) else {
+                        return Self::raw_color(&mut self.str_buf, e).into();
+                    };
+                    let _ = gradient.add_color_stop(0.0, Self::raw_color(&mut self.str_buf, c));
+                    let _ = gradient.add_color_stop(1.0, Self::raw_color(&mut self.str_buf, e));
+                    gradient.unchecked_into()
+                }
+                FillShader::ConicGradient(s, e, [x, y], a) => {
+                    let [min_x, max_x] = handle.bounds_x();
+                    let [min_y, max_y] = handle.bounds_y();
+                    let x = min_x + x * (max_x - min_x);
+                    let y = min_y + y * (max_y - min_y);
+                    // The canvas measures the angle starting at the right
+                    // instead of the top.
+                    let angle = (a - 90.0).to_radians();
+                    let gradient = ctx.create_conic_gradient(angle as _, x as _, y as _);
+                    let _ = gradient.add_color_stop(0.0, Self::raw_color(&mut self.str_buf, s));
+                    let _ = gradient.add_color_stop(1.0, Self::raw_color(&mut self.str_buf, e));
+                    gradient.unchecked_into()
+                }
             })
     }
 
@@ -696,6 +754,13 @@ impl Renderer {
         &self.div
     }
 
+    /// Sets how text and lines are aligned to the pixel grid of the canvas. By
+    /// default nothing is aligned to it. The device pixel ratio of the window
+    /// is taken into account automatically.
+    pub const fn set_hinting(&mut self, hinting: Hinting) {
+        self.manager.set_hinting(hinting);
+    }
+
     /// Renders the layout state into the canvas. The image cache is used to
     /// retrieve images that are used in the layout state.
     pub fn render(&mut self, state: &LayoutState, image_cache: &ImageCache) -> Option<[f32; 2]> {
@@ -731,6 +796,8 @@ impl Renderer {
             self.canvas_top.set_height(height as _);
         }
 
+        self.manager.set_device_pixel_ratio(ratio as f32);
+
         let new_dims = self.manager.update_scene(
             &mut self.allocator,
             [width as _, height as _],
@@ -816,6 +883,9 @@ impl Renderer {
 enum HashShader {
     VerticalGradient([u32; 4], [u32; 4], [u32; 2]),
     HorizontalGradient([u32; 4], [u32; 4], [u32; 2]),
+    AngledGradient([u32; 4], [u32; 4], [u32; 2], [u32; 4]),
+    RadialGradient([u32; 4], [u32; 4], [u32; 2], u32, [u32; 4]),
+    ConicGradient([u32; 4], [u32; 4], [u32; 2], u32, [u32; 4]),
 }
 
 #[derive(PartialEq, Eq, Hash)]
@@ -929,6 +999,17 @@ fn render_layer(
                     ctx.set_shadow_color(&cache.transparent);
                 }
             }
+            Entity::BeginGroup(opacity, blend_mode) => {
+                // The canvas can't composite a group as a whole without an
+                // additional canvas, so the opacity and the blend mode get
+                // applied to each entity of the group individually instead.
+                ctx.save();
+                ctx.set_global_alpha(*opacity as f64);
+                ctx.set_global_composite_operation(&JsString::from(blend_mode_as_css_str(
+                    *blend_mode,
+                )));
+            }
+            Entity::EndGroup => ctx.restore(),
         }
     }
 }
@@ -951,6 +1032,18 @@ fn set_transform(ctx: &CanvasRenderingContext2d, transform: &Transform) {
     let _ = ctx.set_transform(sx, ky, kx, sy, tx, ty);
 }
 
+const fn blend_mode_as_css_str(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::Normal => "source-over",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Add => "lighter",
+    }
+}
+
 const fn weight_as_css_str(weight: FontWeight) -> &'static str {
     match weight {
         FontWeight::Thin => "100",
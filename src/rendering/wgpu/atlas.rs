@@ -0,0 +1,302 @@
+use super::{Gpu, GpuPath, PathData};
+use crate::{
+    platform::prelude::*,
+    settings::{CacheLimits, CacheUsage},
+};
+use hashbrown::HashMap;
+use std::sync::Arc;
+use tiny_skia::{FillRule, Paint, Pixmap};
+
+/// The width and height of the texture that stores the glyphs.
+pub const ATLAS_SIZE: u32 = 1024;
+/// Glyphs that are larger than this are drawn as triangles instead.
+const MAX_GLYPH_SIZE: u32 = 256;
+/// The number of positions within a pixel that glyphs are rasterized at.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+#[derive(PartialEq, Eq, Hash)]
+struct GlyphKey {
+    path: usize,
+    scale: [u32; 2],
+    subpixel: [u8; 2],
+}
+
+#[derive(Copy, Clone)]
+struct AtlasGlyph {
+    position: [u32; 2],
+    size: [u32; 2],
+    offset: [f32; 2],
+}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    x: u32,
+}
+
+/// A glyph that is placed in the atlas.
+pub struct PlacedGlyph {
+    /// The top left corner of the glyph in pixels.
+    pub position: [f32; 2],
+    /// The width and height of the glyph in pixels.
+    pub size: [f32; 2],
+    /// The texture coordinates of the top left and the bottom right corners.
+    pub uv: [[f32; 2]; 2],
+}
+
+/// The result of looking up a glyph in the atlas.
+pub enum Lookup {
+    /// The glyph is placed in the atlas.
+    Placed(PlacedGlyph),
+    /// The glyph doesn't cover any pixels.
+    Empty,
+    /// The glyph can't be placed in the atlas. It needs to be drawn as
+    /// triangles instead.
+    Unavailable,
+}
+
+/// The glyph atlas caches the coverage of glyphs rasterized at the exact size
+/// and subpixel position they are drawn at. This way text is drawn as simple
+/// textured rectangles that look the same as if they were drawn by the
+/// software renderer.
+pub struct GlyphAtlas {
+    texture: wgpu::Texture,
+    pub bind_group: wgpu::BindGroup,
+    // The paths are kept alive, so that their address can't be reused by
+    // another path while they are in the atlas.
+    glyphs: HashMap<GlyphKey, (Arc<PathData>, Option<AtlasGlyph>)>,
+    shelves: Vec<Shelf>,
+    next_shelf_y: u32,
+    is_full: bool,
+    pub limits: CacheLimits,
+}
+
+impl GlyphAtlas {
+    pub fn new(gpu: &Gpu) -> Self {
+        let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Glyph Atlas"),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let bind_group = gpu.texture_bind_group(&texture);
+        Self {
+            texture,
+            bind_group,
+            glyphs: HashMap::new(),
+            shelves: Vec::new(),
+            next_shelf_y: 0,
+            is_full: false,
+            limits: CacheLimits::UNLIMITED,
+        }
+    }
+
+    /// Returns how many glyphs are in the atlas and how many bytes of the
+    /// texture the shelves they are placed on take up.
+    pub fn usage(&self) -> CacheUsage {
+        CacheUsage {
+            entries: self.glyphs.len(),
+            bytes: (self.next_shelf_y * ATLAS_SIZE) as usize,
+        }
+    }
+
+    /// Removes all the glyphs if the atlas ran out of space or exceeds its
+    /// limits. Individual glyphs can't be removed from the shelves, so the
+    /// atlas is always cleared as a whole, regardless of the eviction policy.
+    /// This must only be called when none of the glyphs are used by any of the
+    /// draw calls that are yet to be submitted.
+    pub fn clear_if_full(&mut self) {
+        let usage = self.usage();
+        if self.is_full
+            || self
+                .limits
+                .max_entries
+                .is_some_and(|max| usage.entries > max)
+            || self.limits.exceeds_bytes(usage.bytes)
+        {
+            self.glyphs.clear();
+            self.shelves.clear();
+            self.next_shelf_y = 0;
+            self.is_full = false;
+        }
+    }
+
+    /// Looks up the glyph with the scale provided, placed at the position
+    /// provided in pixels. If the glyph isn't in the atlas yet, it gets
+    /// rasterized and uploaded.
+    pub fn glyph(
+        &mut self,
+        queue: &wgpu::Queue,
+        path: &GpuPath,
+        [scale_x, scale_y]: [f32; 2],
+        [x, y]: [f32; 2],
+    ) -> Lookup {
+        let Some(path) = path else {
+            return Lookup::Empty;
+        };
+        if !(scale_x > 0.0 && scale_y > 0.0) {
+            return Lookup::Unavailable;
+        }
+
+        let (x, y) = ((x * SUBPIXEL_STEPS).round(), (y * SUBPIXEL_STEPS).round());
+        let (pixel_x, pixel_y) = ((x / SUBPIXEL_STEPS).floor(), (y / SUBPIXEL_STEPS).floor());
+        let (subpixel_x, subpixel_y) = (x - pixel_x * SUBPIXEL_STEPS, y - pixel_y * SUBPIXEL_STEPS);
+        let key = GlyphKey {
+            path: Arc::as_ptr(path) as usize,
+            scale: [scale_x.to_bits(), scale_y.to_bits()],
+            subpixel: [subpixel_x as u8, subpixel_y as u8],
+        };
+
+        let glyph = match self.glyphs.get(&key) {
+            Some((_, glyph)) => *glyph,
+            None => {
+                if self.is_full {
+                    return Lookup::Unavailable;
+                }
+                let fraction = [subpixel_x, subpixel_y].map(|v| v / SUBPIXEL_STEPS);
+                let Some(glyph) = self.rasterize(queue, &path.path, [scale_x, scale_y], fraction)
+                else {
+                    return Lookup::Unavailable;
+                };
+                self.glyphs.insert(key, (path.clone(), glyph));
+                glyph
+            }
+        };
+
+        let Some(glyph) = glyph else {
+            return Lookup::Empty;
+        };
+
+        let to_uv = |[x, y]: [u32; 2]| [x, y].map(|v| v as f32 / ATLAS_SIZE as f32);
+        Lookup::Placed(PlacedGlyph {
+            position: [pixel_x + glyph.offset[0], pixel_y + glyph.offset[1]],
+            size: glyph.size.map(|v| v as f32),
+            uv: [
+                to_uv(glyph.position),
+                to_uv([
+                    glyph.position[0] + glyph.size[0],
+                    glyph.position[1] + glyph.size[1],
+                ]),
+            ],
+        })
+    }
+
+    /// Rasterizes the glyph and uploads it into the atlas. There's no glyph
+    /// placed in the atlas if it is empty. If it can't be placed, [`None`] is
+    /// returned.
+    fn rasterize(
+        &mut self,
+        queue: &wgpu::Queue,
+        path: &tiny_skia::Path,
+        [scale_x, scale_y]: [f32; 2],
+        [fraction_x, fraction_y]: [f32; 2],
+    ) -> Option<Option<AtlasGlyph>> {
+        let bounds = path.bounds();
+        // There's a pixel of space around the glyph, so its anti-aliased edges
+        // are never cut off.
+        let left = (fraction_x + bounds.left() * scale_x).floor() - 1.0;
+        let top = (fraction_y + bounds.top() * scale_y).floor() - 1.0;
+        let right = (fraction_x + bounds.right() * scale_x).ceil() + 1.0;
+        let bottom = (fraction_y + bounds.bottom() * scale_y).ceil() + 1.0;
+        let (width, height) = ((right - left) as u32, (bottom - top) as u32);
+
+        if width > MAX_GLYPH_SIZE || height > MAX_GLYPH_SIZE {
+            return None;
+        }
+
+        let mut pixmap = Pixmap::new(width, height)?;
+        pixmap.fill_path(
+            path,
+            &Paint {
+                anti_alias: true,
+                ..Default::default()
+            },
+            FillRule::Winding,
+            tiny_skia::Transform::from_row(
+                scale_x,
+                0.0,
+                0.0,
+                scale_y,
+                fraction_x - left,
+                fraction_y - top,
+            ),
+            None,
+        );
+
+        let coverage: Vec<u8> = pixmap.pixels().iter().map(|p| p.alpha()).collect();
+        if coverage.iter().all(|&a| a == 0) {
+            return Some(None);
+        }
+
+        let Some(position) = self.allocate(width, height) else {
+            self.is_full = true;
+            return None;
+        };
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: position[0],
+                    y: position[1],
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &coverage,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Some(Some(AtlasGlyph {
+            position,
+            size: [width, height],
+            offset: [left, top],
+        }))
+    }
+
+    /// Finds space for a glyph of the size provided. The glyphs are placed on
+    /// shelves of glyphs of similar heights.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+        for shelf in &mut self.shelves {
+            if height <= shelf.height
+                && 4 * height >= 3 * shelf.height
+                && shelf.x + width <= ATLAS_SIZE
+            {
+                let x = shelf.x;
+                shelf.x += width;
+                return Some([x, shelf.y]);
+            }
+        }
+
+        if self.next_shelf_y + height > ATLAS_SIZE {
+            return None;
+        }
+
+        let y = self.next_shelf_y;
+        self.next_shelf_y += height;
+        self.shelves.push(Shelf {
+            y,
+            height,
+            x: width,
+        });
+        Some([0, y])
+    }
+}
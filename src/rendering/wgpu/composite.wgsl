@@ -0,0 +1,102 @@
+// Composites a group onto the pixels below it and presents the final image.
+// Both work on whole textures, so they are drawn as a single triangle covering
+// the entire viewport. All the colors use premultiplied alpha.
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(5) params: vec4<f32>,
+    @location(6) kind: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat, either) opacity: f32,
+    @location(1) @interpolate(flat, either) mode: u32,
+}
+
+const MODE_REPLACE: u32 = 0u;
+const MODE_NORMAL: u32 = 1u;
+const MODE_MULTIPLY: u32 = 2u;
+const MODE_SCREEN: u32 = 3u;
+const MODE_OVERLAY: u32 = 4u;
+const MODE_DARKEN: u32 = 5u;
+const MODE_LIGHTEN: u32 = 6u;
+const MODE_ADD: u32 = 7u;
+
+// Whether the target of the presentation converts the colors to sRGB by
+// itself, in which case they need to be converted to linear colors first.
+override SRGB: bool = false;
+
+@group(0) @binding(0) var source_texture: texture_2d<f32>;
+@group(0) @binding(1) var backdrop_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(in.position, 0.0, 1.0);
+    out.opacity = in.params.x;
+    out.mode = in.kind;
+    return out;
+}
+
+fn union_alpha(s: vec4<f32>, d: vec4<f32>) -> f32 {
+    return s.a + d.a - s.a * d.a;
+}
+
+fn overlay(s: vec4<f32>, d: vec4<f32>) -> vec3<f32> {
+    let low = 2.0 * s.rgb * d.rgb;
+    let high = s.a * d.a - 2.0 * (d.a - d.rgb) * (s.a - s.rgb);
+    let mixed = select(high, low, 2.0 * d.rgb <= vec3<f32>(d.a));
+    return mixed + s.rgb * (1.0 - d.a) + d.rgb * (1.0 - s.a);
+}
+
+@fragment
+fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    let position = vec2<i32>(in.position.xy);
+    let s = textureLoad(source_texture, position, 0) * in.opacity;
+    if in.mode == MODE_REPLACE {
+        return s;
+    }
+    let d = textureLoad(backdrop_texture, position, 0);
+    switch in.mode {
+        case MODE_MULTIPLY: {
+            let rgb = s.rgb * (1.0 - d.a) + d.rgb * (1.0 - s.a) + s.rgb * d.rgb;
+            return vec4<f32>(rgb, union_alpha(s, d));
+        }
+        case MODE_SCREEN: {
+            return s + d - s * d;
+        }
+        case MODE_OVERLAY: {
+            return vec4<f32>(overlay(s, d), union_alpha(s, d));
+        }
+        case MODE_DARKEN: {
+            let rgb = s.rgb + d.rgb - max(s.rgb * d.a, d.rgb * s.a);
+            return vec4<f32>(rgb, union_alpha(s, d));
+        }
+        case MODE_LIGHTEN: {
+            let rgb = s.rgb + d.rgb - min(s.rgb * d.a, d.rgb * s.a);
+            return vec4<f32>(rgb, union_alpha(s, d));
+        }
+        case MODE_ADD: {
+            return min(s + d, vec4<f32>(1.0));
+        }
+        default: {
+            return s + d * (1.0 - s.a);
+        }
+    }
+}
+
+fn to_linear(srgb: vec3<f32>) -> vec3<f32> {
+    let low = srgb / 12.92;
+    let high = pow((srgb + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, srgb <= vec3<f32>(0.04045));
+}
+
+@fragment
+fn fs_present(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureLoad(source_texture, vec2<i32>(in.position.xy), 0);
+    if SRGB && color.a > 0.0 {
+        return vec4<f32>(to_linear(color.rgb / color.a) * color.a, color.a);
+    }
+    return color;
+}
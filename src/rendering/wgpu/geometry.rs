@@ -0,0 +1,315 @@
+use crate::{
+    platform::prelude::*,
+    rendering::{FillShader, Rgba, Transform, angled_gradient_points},
+};
+use bytemuck_derive::{Pod, Zeroable};
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator,
+    StrokeVertex, VertexBuffers, math::point, path::PathEvent,
+};
+use tiny_skia::{Path, PathSegment};
+
+/// The maximum distance in pixels that the tessellated triangles may deviate
+/// from the actual curves of a path.
+const TOLERANCE: f32 = 0.1;
+
+pub const PAINT_SOLID: u32 = 0;
+pub const PAINT_LINEAR: u32 = 1;
+pub const PAINT_RADIAL: u32 = 2;
+pub const PAINT_CONIC: u32 = 3;
+
+/// The color is multiplied by the coverage stored in the glyph atlas.
+pub const SOURCE_COVERAGE: u32 = 1 << 4;
+/// The image is sampled and multiplied by the first color of the vertex.
+pub const SOURCE_IMAGE: u32 = 2 << 4;
+
+/// A single vertex as it is consumed by the shaders.
+#[derive(Copy, Clone, Pod, Zeroable)]
+#[repr(C)]
+pub struct Vertex {
+    /// The position in normalized device coordinates.
+    pub position: [f32; 2],
+    /// The texture coordinates for sampling the glyph atlas or an image.
+    pub uv: [f32; 2],
+    /// The coordinates to evaluate the gradient at.
+    pub paint: [f32; 2],
+    pub color0: Rgba,
+    pub color1: Rgba,
+    pub params: [f32; 4],
+    /// The kind of paint combined with the kind of source.
+    pub kind: u32,
+}
+
+impl Vertex {
+    pub const ATTRIBUTES: [wgpu::VertexAttribute; 7] = wgpu::vertex_attr_array![
+        0 => Float32x2,
+        1 => Float32x2,
+        2 => Float32x2,
+        3 => Float32x4,
+        4 => Float32x4,
+        5 => Float32x4,
+        6 => Uint32,
+    ];
+}
+
+/// Describes how to color the pixels of an entity. The paint coordinates of a
+/// vertex are an affine mapping of its position in the local coordinate space
+/// of the entity.
+#[derive(Copy, Clone)]
+pub struct Paint {
+    pub kind: u32,
+    pub color0: Rgba,
+    pub color1: Rgba,
+    pub params: [f32; 4],
+    mapping: [[f32; 3]; 2],
+}
+
+impl Paint {
+    pub const fn solid(color: Rgba) -> Self {
+        Self {
+            kind: PAINT_SOLID,
+            color0: color,
+            color1: color,
+            params: [0.0; 4],
+            mapping: [[0.0; 3]; 2],
+        }
+    }
+
+    /// Creates the paint for the shader provided. The bounds of the entity
+    /// are only calculated if the shader needs them. They are the left and
+    /// right edges followed by the top and bottom edges.
+    pub fn new(
+        shader: &FillShader,
+        bounds: impl FnOnce() -> [[f32; 2]; 2],
+        transform: &Transform,
+    ) -> Self {
+        match shader {
+            FillShader::SolidColor(color) => Self::solid(*color),
+            FillShader::VerticalGradient(top, bottom) => {
+                let [_, [bound_top, bound_bottom]] = bounds();
+                Self::linear(top, bottom, [0.0, bound_top], [0.0, bound_bottom])
+            }
+            FillShader::HorizontalGradient(left, right) => {
+                let [[bound_left, bound_right], _] = bounds();
+                Self::linear(left, right, [bound_left, 0.0], [bound_right, 0.0])
+            }
+            FillShader::AngledGradient(start, end, direction) => {
+                let [left_right, top_bottom] = bounds();
+                let [from, to] = angled_gradient_points(*direction, left_right, top_bottom);
+                Self::linear(start, end, from, to)
+            }
+            FillShader::RadialGradient(center, edge, [x, y], radius) => {
+                let [left_right, top_bottom] = bounds();
+                match Self::relative_to_bounds(left_right, top_bottom) {
+                    Some(mapping) if *radius > 0.0 => Self {
+                        kind: PAINT_RADIAL,
+                        color0: *center,
+                        color1: *edge,
+                        params: [*x, *y, *radius, 0.0],
+                        mapping,
+                    },
+                    _ => Self::solid(*edge),
+                }
+            }
+            FillShader::ConicGradient(start, end, [x, y], angle) => {
+                let [[left, right], [top, bottom]] = bounds();
+                match Self::relative_to_bounds([left, right], [top, bottom]) {
+                    Some(mapping) => {
+                        // The angles are measured in pixels, so the bounding
+                        // box needs to keep its aspect ratio on the screen.
+                        let aspect_ratio = ((right - left) * transform.scale_x
+                            / ((bottom - top) * transform.scale_y))
+                            .abs();
+                        Self {
+                            kind: PAINT_CONIC,
+                            color0: *start,
+                            color1: *end,
+                            params: [*x, *y, *angle, aspect_ratio],
+                            mapping,
+                        }
+                    }
+                    None => Self::solid(*start),
+                }
+            }
+        }
+    }
+
+    fn linear(start: &Rgba, end: &Rgba, [x1, y1]: [f32; 2], [x2, y2]: [f32; 2]) -> Self {
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length_squared = dx * dx + dy * dy;
+        if length_squared <= f32::EPSILON {
+            return Self::solid(*end);
+        }
+        let (dx, dy) = (dx / length_squared, dy / length_squared);
+        Self {
+            kind: PAINT_LINEAR,
+            color0: *start,
+            color1: *end,
+            params: [0.0; 4],
+            mapping: [[dx, dy, -(x1 * dx + y1 * dy)], [0.0; 3]],
+        }
+    }
+
+    fn relative_to_bounds(
+        [left, right]: [f32; 2],
+        [top, bottom]: [f32; 2],
+    ) -> Option<[[f32; 3]; 2]> {
+        let (width, height) = (right - left, bottom - top);
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+        Some([
+            [width.recip(), 0.0, -left / width],
+            [0.0, height.recip(), -top / height],
+        ])
+    }
+
+    /// Creates a vertex at the position provided in normalized device
+    /// coordinates. The local position is the position in the local coordinate
+    /// space of the entity.
+    pub fn vertex(
+        &self,
+        position: [f32; 2],
+        uv: [f32; 2],
+        [x, y]: [f32; 2],
+        source: u32,
+    ) -> Vertex {
+        let [[a, b, c], [d, e, f]] = self.mapping;
+        Vertex {
+            position,
+            uv,
+            paint: [a * x + b * y + c, d * x + e * y + f],
+            color0: self.color0,
+            color1: self.color1,
+            params: self.params,
+            kind: self.kind | source,
+        }
+    }
+}
+
+/// The triangles of a path in its local coordinate space.
+pub struct Mesh {
+    pub vertices: Vec<[f32; 2]>,
+    pub indices: Vec<u32>,
+}
+
+/// Calculates the tolerance to tessellate a path with, such that it looks
+/// smooth once it is drawn with the transform provided.
+pub fn tolerance(transform: &Transform) -> f32 {
+    TOLERANCE / transform.scale_x.abs().max(transform.scale_y.abs())
+}
+
+/// Checks if a mesh tessellated with the tolerance provided can be used where
+/// the tolerance that is needed is the one provided. Meshes that are a bit
+/// more detailed than necessary are fine.
+pub fn is_suitable(cached: f32, needed: f32) -> bool {
+    cached <= needed && 4.0 * cached >= needed
+}
+
+/// Fills the path with the non-zero fill rule.
+pub fn fill(path: &Path, tolerance: f32) -> Mesh {
+    let mut buffers = VertexBuffers::new();
+    // Paths that can't be tessellated aren't visible anyway, so whatever was
+    // produced until then is fine.
+    let _ = FillTessellator::new().tessellate(
+        events(path),
+        &FillOptions::non_zero().with_tolerance(tolerance),
+        &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+            vertex.position().to_array()
+        }),
+    );
+    Mesh {
+        vertices: buffers.vertices,
+        indices: buffers.indices,
+    }
+}
+
+/// Strokes the path with the width provided.
+pub fn stroke(path: &Path, width: f32, tolerance: f32) -> Mesh {
+    let mut buffers = VertexBuffers::new();
+    let _ = StrokeTessellator::new().tessellate(
+        events(path),
+        &StrokeOptions::default()
+            .with_line_width(width)
+            .with_tolerance(tolerance),
+        &mut BuffersBuilder::new(&mut buffers, |vertex: StrokeVertex| {
+            vertex.position().to_array()
+        }),
+    );
+    Mesh {
+        vertices: buffers.vertices,
+        indices: buffers.indices,
+    }
+}
+
+fn events(path: &Path) -> Vec<PathEvent> {
+    let mut events = Vec::new();
+    let (mut first, mut last) = (point(0.0, 0.0), point(0.0, 0.0));
+    let mut is_open = false;
+    for segment in path.segments() {
+        // Drawing after closing a subpath continues from where it started.
+        if !is_open && !matches!(segment, PathSegment::MoveTo(_) | PathSegment::Close) {
+            first = last;
+            is_open = true;
+            events.push(PathEvent::Begin { at: first });
+        }
+        match segment {
+            PathSegment::MoveTo(p) => {
+                if is_open {
+                    events.push(PathEvent::End {
+                        last,
+                        first,
+                        close: false,
+                    });
+                }
+                first = point(p.x, p.y);
+                last = first;
+                is_open = true;
+                events.push(PathEvent::Begin { at: first });
+            }
+            PathSegment::LineTo(p) => {
+                let to = point(p.x, p.y);
+                events.push(PathEvent::Line { from: last, to });
+                last = to;
+            }
+            PathSegment::QuadTo(p1, p) => {
+                let to = point(p.x, p.y);
+                events.push(PathEvent::Quadratic {
+                    from: last,
+                    ctrl: point(p1.x, p1.y),
+                    to,
+                });
+                last = to;
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                let to = point(p.x, p.y);
+                events.push(PathEvent::Cubic {
+                    from: last,
+                    ctrl1: point(p1.x, p1.y),
+                    ctrl2: point(p2.x, p2.y),
+                    to,
+                });
+                last = to;
+            }
+            PathSegment::Close => {
+                if is_open {
+                    events.push(PathEvent::End {
+                        last,
+                        first,
+                        close: true,
+                    });
+                    last = first;
+                    is_open = false;
+                }
+            }
+        }
+    }
+    if is_open {
+        events.push(PathEvent::End {
+            last,
+            first,
+            close: false,
+        });
+    }
+    events
+}
@@ -0,0 +1,1238 @@
+//! Provides a renderer that uses the GPU through [`wgpu`](::wgpu). It renders
+//! the same scene as the software renderer, but the paths are turned into
+//! triangles, text is drawn from a glyph atlas and the gradients, images and
+//! blend modes are all evaluated on the GPU. This keeps the CPU mostly idle,
+//! even when rendering at high refresh rates.
+
+mod atlas;
+mod geometry;
+
+use self::{
+    atlas::{GlyphAtlas, Lookup},
+    geometry::{Mesh, Paint, SOURCE_COVERAGE, SOURCE_IMAGE, Vertex},
+};
+use super::{
+    Background, FillShader, FontKind, Hinting, Rgba, SceneManager, Transform,
+    consts::SHADOW_OFFSET,
+    default_text_engine::{Font, Label, LockedLabel, TextEngine},
+    entity::Entity,
+    resource::{self, ResourceAllocator},
+    software,
+};
+use crate::{
+    layout::LayoutState,
+    platform::prelude::*,
+    settings::{self, BackgroundImage, BlendMode, CacheLimits, CacheUsage, ImageCache},
+};
+use core::{mem, ops::Range};
+use hashbrown::HashMap;
+use std::sync::{Arc, Mutex};
+use tiny_skia::Pixmap;
+
+pub use ::wgpu;
+
+/// The format of the textures that the layers are rendered into.
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+/// The number of samples per pixel used for anti-aliasing the triangles.
+const SAMPLE_COUNT: u32 = 4;
+
+const MODE_REPLACE: u32 = 0;
+
+struct PathData {
+    path: tiny_skia::Path,
+    fill: Mutex<Option<(f32, Arc<Mesh>)>>,
+    stroke: Mutex<Option<(f32, f32, Arc<Mesh>)>>,
+}
+
+type GpuPath = Option<Arc<PathData>>;
+type GpuImage = Arc<ImageData>;
+type GpuFont = Font;
+type GpuLabel = Label<GpuPath>;
+
+impl PathData {
+    /// Accesses the triangles that fill the path when it is drawn with the
+    /// transform provided. They are only tessellated again if the path is
+    /// drawn at a very different size.
+    fn fill(&self, transform: &Transform) -> Arc<Mesh> {
+        let tolerance = geometry::tolerance(transform);
+        let mut cached = self.fill.lock().unwrap();
+        match &*cached {
+            Some((cached_tolerance, mesh))
+                if geometry::is_suitable(*cached_tolerance, tolerance) =>
+            {
+                mesh.clone()
+            }
+            _ => {
+                let mesh = Arc::new(geometry::fill(&self.path, tolerance));
+                *cached = Some((tolerance, mesh.clone()));
+                mesh
+            }
+        }
+    }
+
+    /// Accesses the triangles that stroke the path with the width provided
+    /// when it is drawn with the transform provided.
+    fn stroke(&self, width: f32, transform: &Transform) -> Arc<Mesh> {
+        let tolerance = geometry::tolerance(transform);
+        let mut cached = self.stroke.lock().unwrap();
+        match &*cached {
+            Some((cached_width, cached_tolerance, mesh))
+                if cached_width.to_bits() == width.to_bits()
+                    && geometry::is_suitable(*cached_tolerance, tolerance) =>
+            {
+                mesh.clone()
+            }
+            _ => {
+                let mesh = Arc::new(geometry::stroke(&self.path, width, tolerance));
+                *cached = Some((width, tolerance, mesh.clone()));
+                mesh
+            }
+        }
+    }
+}
+
+struct ImageData {
+    image: software::Image,
+    texture: Mutex<Option<([u32; 2], wgpu::BindGroup)>>,
+}
+
+impl ImageData {
+    /// Accesses the texture to use for drawing the image with the transform
+    /// provided. It is only uploaded again if it needs to be drawn with
+    /// different pixels, which happens when SVG images change their size.
+    fn bind_group(&self, gpu: &Gpu, transform: &Transform) -> wgpu::BindGroup {
+        self.image.with_pixmap(transform, |pixmap| {
+            let size = [pixmap.width(), pixmap.height()];
+            let mut texture = self.texture.lock().unwrap();
+            if let Some((cached_size, bind_group)) = &*texture
+                && *cached_size == size
+            {
+                return bind_group.clone();
+            }
+            let bind_group = gpu.upload(pixmap);
+            *texture = Some((size, bind_group.clone()));
+            bind_group
+        })
+    }
+}
+
+impl resource::Image for GpuImage {
+    fn aspect_ratio(&self) -> f32 {
+        self.image.aspect_ratio()
+    }
+}
+
+struct GpuBuilder(tiny_skia::PathBuilder);
+
+impl resource::PathBuilder for GpuBuilder {
+    type Path = GpuPath;
+
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y)
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y)
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y)
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, y1, x2, y2, x, y)
+    }
+
+    fn close(&mut self) {
+        self.0.close()
+    }
+
+    fn finish(self) -> Self::Path {
+        self.0.finish().map(|path| {
+            Arc::new(PathData {
+                path,
+                fill: Mutex::new(None),
+                stroke: Mutex::new(None),
+            })
+        })
+    }
+}
+
+fn path_builder() -> GpuBuilder {
+    GpuBuilder(tiny_skia::PathBuilder::new())
+}
+
+struct GpuAllocator {
+    text_engine: TextEngine<GpuPath>,
+}
+
+impl ResourceAllocator for GpuAllocator {
+    type PathBuilder = GpuBuilder;
+    type Path = GpuPath;
+    type Image = GpuImage;
+    type Font = GpuFont;
+    type Label = GpuLabel;
+
+    fn path_builder(&mut self) -> Self::PathBuilder {
+        path_builder()
+    }
+
+    fn create_image(&mut self, data: &[u8]) -> Option<Self::Image> {
+        Some(Arc::new(ImageData {
+            image: software::Image::decode(data)?,
+            texture: Mutex::new(None),
+        }))
+    }
+
+    fn create_font(&mut self, font: Option<&settings::Font>, kind: FontKind) -> Self::Font {
+        self.text_engine.create_font(font, kind)
+    }
+
+    fn create_label(
+        &mut self,
+        text: &str,
+        font: &mut Self::Font,
+        max_width: Option<f32>,
+    ) -> Self::Label {
+        self.text_engine
+            .create_label(path_builder, text, font, max_width)
+    }
+
+    fn update_label(
+        &mut self,
+        label: &mut Self::Label,
+        text: &str,
+        font: &mut Self::Font,
+        max_width: Option<f32>,
+    ) {
+        self.text_engine
+            .update_label(path_builder, label, text, font, max_width)
+    }
+}
+
+/// The device along with everything that is needed for drawing into the
+/// textures of the renderer.
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    sampler: wgpu::Sampler,
+    texture_layout: wgpu::BindGroupLayout,
+    composite_layout: wgpu::BindGroupLayout,
+    composite_module: wgpu::ShaderModule,
+    composite_pipeline_layout: wgpu::PipelineLayout,
+    fill_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+}
+
+impl Gpu {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let texture_entry = |binding, filterable| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Texture Layout"),
+            entries: &[
+                texture_entry(0, true),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let composite_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Composite Layout"),
+            entries: &[texture_entry(0, false), texture_entry(1, false)],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let fill_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Fill Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+        let composite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Composite Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("composite.wgsl").into()),
+        });
+
+        let fill_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fill Pipeline Layout"),
+            bind_group_layouts: &[Some(&texture_layout)],
+            immediate_size: 0,
+        });
+        let composite_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Composite Pipeline Layout"),
+                bind_group_layouts: &[Some(&composite_layout)],
+                immediate_size: 0,
+            });
+
+        let fill_pipeline = create_pipeline(
+            device,
+            &fill_pipeline_layout,
+            &fill_module,
+            "fs_main",
+            FORMAT,
+            SAMPLE_COUNT,
+            Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            &[],
+        );
+        let composite_pipeline = create_pipeline(
+            device,
+            &composite_pipeline_layout,
+            &composite_module,
+            "fs_composite",
+            FORMAT,
+            SAMPLE_COUNT,
+            None,
+            &[],
+        );
+
+        Self {
+            device: device.clone(),
+            queue: queue.clone(),
+            sampler,
+            texture_layout,
+            composite_layout,
+            composite_module,
+            composite_pipeline_layout,
+            fill_pipeline,
+            composite_pipeline,
+        }
+    }
+
+    /// Creates the pipeline that copies the final image into a texture of the
+    /// format provided.
+    fn create_present_pipeline(&self, format: wgpu::TextureFormat) -> wgpu::RenderPipeline {
+        create_pipeline(
+            &self.device,
+            &self.composite_pipeline_layout,
+            &self.composite_module,
+            "fs_present",
+            format,
+            1,
+            None,
+            &[("SRGB", if format.is_srgb() { 1.0 } else { 0.0 })],
+        )
+    }
+
+    fn texture_bind_group(&self, texture: &wgpu::Texture) -> wgpu::BindGroup {
+        let view = texture.create_view(&Default::default());
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.texture_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        })
+    }
+
+    fn composite_bind_group(
+        &self,
+        source: &wgpu::TextureView,
+        backdrop: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.composite_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(backdrop),
+                },
+            ],
+        })
+    }
+
+    /// Uploads the pixels into a new texture.
+    fn upload(&self, pixmap: &Pixmap) -> wgpu::BindGroup {
+        let size = wgpu::Extent3d {
+            width: pixmap.width(),
+            height: pixmap.height(),
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Image"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        self.queue.write_texture(
+            texture.as_image_copy(),
+            pixmap.data(),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * size.width),
+                rows_per_image: Some(size.height),
+            },
+            size,
+        );
+        self.texture_bind_group(&texture)
+    }
+}
+
+#[expect(clippy::too_many_arguments)]
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    module: &wgpu::ShaderModule,
+    fragment_entry_point: &str,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    blend: Option<wgpu::BlendState>,
+    constants: &[(&str, f64)],
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(fragment_entry_point),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[Some(wgpu::VertexBufferLayout {
+                array_stride: size_of::<Vertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &Vertex::ATTRIBUTES,
+            })],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: Some(fragment_entry_point),
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants,
+                ..Default::default()
+            },
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+struct RenderTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl RenderTarget {
+    fn new(
+        device: &wgpu::Device,
+        [width, height]: [u32; 2],
+        sample_count: u32,
+        usage: wgpu::TextureUsages,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+        Self { texture, view }
+    }
+}
+
+/// All the textures that are rendered into. The layers are drawn into
+/// multisampled textures that are then resolved into the bottom layer, the
+/// final image, called the canvas, or a group that is yet to be composited.
+struct Targets {
+    size: [u32; 2],
+    multisampled: RenderTarget,
+    multisampled_group: RenderTarget,
+    bottom: RenderTarget,
+    canvas: RenderTarget,
+    group: RenderTarget,
+    backdrop: RenderTarget,
+    bottom_bind_group: wgpu::BindGroup,
+    group_bind_group: wgpu::BindGroup,
+    canvas_bind_group: wgpu::BindGroup,
+}
+
+impl Targets {
+    fn new(gpu: &Gpu, size: [u32; 2]) -> Self {
+        use wgpu::TextureUsages as Usages;
+
+        let device = &gpu.device;
+        let multisampled = RenderTarget::new(device, size, SAMPLE_COUNT, Usages::RENDER_ATTACHMENT);
+        let multisampled_group =
+            RenderTarget::new(device, size, SAMPLE_COUNT, Usages::RENDER_ATTACHMENT);
+        let layer_usage = Usages::RENDER_ATTACHMENT | Usages::TEXTURE_BINDING | Usages::COPY_SRC;
+        let bottom = RenderTarget::new(device, size, 1, layer_usage);
+        let canvas = RenderTarget::new(device, size, 1, layer_usage);
+        let group = RenderTarget::new(device, size, 1, layer_usage);
+        let backdrop =
+            RenderTarget::new(device, size, 1, Usages::TEXTURE_BINDING | Usages::COPY_DST);
+
+        Self {
+            size,
+            bottom_bind_group: gpu.composite_bind_group(&bottom.view, &backdrop.view),
+            group_bind_group: gpu.composite_bind_group(&group.view, &backdrop.view),
+            canvas_bind_group: gpu.composite_bind_group(&canvas.view, &canvas.view),
+            multisampled,
+            multisampled_group,
+            bottom,
+            canvas,
+            group,
+            backdrop,
+        }
+    }
+
+    /// The multisampled texture to draw into and the texture it gets resolved
+    /// into.
+    const fn views(&self, target: Target) -> (&wgpu::TextureView, &wgpu::TextureView) {
+        match target {
+            Target::Bottom => (&self.multisampled.view, &self.bottom.view),
+            Target::Canvas => (&self.multisampled.view, &self.canvas.view),
+            Target::Group => (&self.multisampled_group.view, &self.group.view),
+        }
+    }
+
+    const fn texture(&self, target: Target) -> &wgpu::Texture {
+        match target {
+            Target::Bottom => &self.bottom.texture,
+            Target::Canvas => &self.canvas.texture,
+            Target::Group => &self.group.texture,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Target {
+    Bottom,
+    Canvas,
+    Group,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Pipeline {
+    Fill,
+    Composite,
+}
+
+/// Consecutive triangles that are drawn with the same pipeline and the same
+/// textures.
+struct Batch {
+    pipeline: Pipeline,
+    bind_group: wgpu::BindGroup,
+    indices: Range<u32>,
+}
+
+enum Operation {
+    /// Draws the batches into the target, optionally clearing it first.
+    Draw {
+        target: Target,
+        clear: bool,
+        batches: Range<usize>,
+    },
+    /// Copies the target, so it can be used as the backdrop for compositing a
+    /// group onto it.
+    CopyToBackdrop(Target),
+}
+
+/// Collects all the triangles of a frame, so they can be uploaded at once
+/// before any of them get drawn.
+#[derive(Default)]
+struct DrawList {
+    size: [f32; 2],
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    batches: Vec<Batch>,
+    operations: Vec<Operation>,
+    flushed_batches: usize,
+}
+
+impl DrawList {
+    fn clear(&mut self, [width, height]: [u32; 2]) {
+        self.size = [width as f32, height as f32];
+        self.vertices.clear();
+        self.indices.clear();
+        self.batches.clear();
+        self.operations.clear();
+        self.flushed_batches = 0;
+    }
+
+    /// Converts pixels to normalized device coordinates.
+    fn to_device(&self) -> impl Fn(f32, f32) -> [f32; 2] + use<> {
+        let [width, height] = self.size;
+        move |x, y| [2.0 * x / width - 1.0, 1.0 - 2.0 * y / height]
+    }
+
+    fn push(
+        &mut self,
+        pipeline: Pipeline,
+        bind_group: &wgpu::BindGroup,
+        vertices: impl IntoIterator<Item = Vertex>,
+        indices: impl IntoIterator<Item = u32>,
+    ) {
+        let base = self.vertices.len() as u32;
+        self.vertices.extend(vertices);
+        let start = self.indices.len() as u32;
+        self.indices
+            .extend(indices.into_iter().map(|index| base + index));
+        let end = self.indices.len() as u32;
+
+        if let Some(batch) = self.batches[self.flushed_batches..].last_mut()
+            && batch.pipeline == pipeline
+            && batch.bind_group == *bind_group
+            && batch.indices.end == start
+        {
+            batch.indices.end = end;
+        } else {
+            self.batches.push(Batch {
+                pipeline,
+                bind_group: bind_group.clone(),
+                indices: start..end,
+            });
+        }
+    }
+
+    /// Adds a triangle that covers the entire viewport.
+    fn push_fullscreen(&mut self, bind_group: &wgpu::BindGroup, opacity: f32, mode: u32) {
+        let vertices = [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]].map(|position| Vertex {
+            position,
+            params: [opacity, 0.0, 0.0, 0.0],
+            kind: mode,
+            ..bytemuck::Zeroable::zeroed()
+        });
+        self.push(Pipeline::Composite, bind_group, vertices, 0..3);
+    }
+
+    /// Draws all the batches added since the last flush into the target.
+    fn flush(&mut self, target: Target, clear: bool) {
+        let batches = self.flushed_batches..self.batches.len();
+        if clear || !batches.is_empty() {
+            self.operations.push(Operation::Draw {
+                target,
+                clear,
+                batches,
+            });
+        }
+        self.flushed_batches = self.batches.len();
+    }
+}
+
+struct Context<'a> {
+    gpu: &'a Gpu,
+    atlas: &'a mut GlyphAtlas,
+    list: &'a mut DrawList,
+    group_bind_group: &'a wgpu::BindGroup,
+}
+
+impl Context<'_> {
+    fn draw_layer(&mut self, layer: &[Entity<GpuPath, GpuImage, GpuLabel>], target: Target) {
+        let mut entities = layer.iter();
+        while let Some(entity) = entities.next() {
+            if let Entity::BeginGroup(opacity, blend_mode) = entity {
+                // The entities of the group get drawn into their own texture
+                // first, so that they can be composited as a whole.
+                self.list.flush(target, false);
+                for entity in entities.by_ref() {
+                    if let Entity::EndGroup = entity {
+                        break;
+                    }
+                    self.draw_entity(entity);
+                }
+                self.list.flush(Target::Group, true);
+                self.list.operations.push(Operation::CopyToBackdrop(target));
+                self.list.push_fullscreen(
+                    self.group_bind_group,
+                    *opacity,
+                    convert_blend_mode(*blend_mode),
+                );
+            } else {
+                self.draw_entity(entity);
+            }
+        }
+        self.list.flush(target, false);
+    }
+
+    fn draw_entity(&mut self, entity: &Entity<GpuPath, GpuImage, GpuLabel>) {
+        match entity {
+            Entity::FillPath(path, shader, transform) => {
+                if let Some(path) = &**path {
+                    let paint = Paint::new(shader, || bounds(&path.path), transform);
+                    self.draw_mesh(&path.fill(transform), &paint, transform);
+                }
+            }
+            Entity::StrokePath(path, stroke_width, color, transform) => {
+                if let Some(path) = &**path {
+                    let mesh = path.stroke(*stroke_width, transform);
+                    self.draw_mesh(&mesh, &Paint::solid(*color), transform);
+                }
+            }
+            Entity::Image(image, transform) => {
+                let bind_group = image.bind_group(self.gpu, transform);
+                self.draw_image(&bind_group, [1.0; 4], transform);
+            }
+            Entity::Label(label, shader, text_shadow, transform) => {
+                let label = &*label.read().unwrap();
+                self.draw_label(label, shader, text_shadow.as_ref(), transform);
+            }
+            Entity::BeginGroup(..) | Entity::EndGroup => {}
+        }
+    }
+
+    fn draw_mesh(&mut self, mesh: &Mesh, paint: &Paint, transform: &Transform) {
+        let to_device = self.list.to_device();
+        let vertices = mesh.vertices.iter().map(|&[x, y]| {
+            paint.vertex(
+                to_device(
+                    transform.x + transform.scale_x * x,
+                    transform.y + transform.scale_y * y,
+                ),
+                [0.0; 2],
+                [x, y],
+                0,
+            )
+        });
+        let bind_group = &self.atlas.bind_group;
+        self.list.push(
+            Pipeline::Fill,
+            bind_group,
+            vertices,
+            mesh.indices.iter().copied(),
+        );
+    }
+
+    /// Draws the image onto the unit square, which the transform maps to the
+    /// image's area. The pixels of the image get multiplied by the color.
+    fn draw_image(&mut self, bind_group: &wgpu::BindGroup, color: Rgba, transform: &Transform) {
+        let to_device = self.list.to_device();
+        let paint = Paint::solid(color);
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|[x, y]| {
+            paint.vertex(
+                to_device(
+                    transform.x + transform.scale_x * x,
+                    transform.y + transform.scale_y * y,
+                ),
+                [x, y],
+                [x, y],
+                SOURCE_IMAGE,
+            )
+        });
+        self.list
+            .push(Pipeline::Fill, bind_group, vertices, QUAD_INDICES);
+    }
+
+    fn draw_label(
+        &mut self,
+        label: &LockedLabel<GpuPath>,
+        shader: &FillShader,
+        text_shadow: Option<&Rgba>,
+        transform: &Transform,
+    ) {
+        let paint = Paint::new(shader, || label_bounds(label), transform);
+
+        if let Some(&[r, g, b, a]) = text_shadow {
+            let alpha = match shader {
+                FillShader::SolidColor([.., a]) => *a,
+                FillShader::VerticalGradient([.., a1], [.., a2])
+                | FillShader::HorizontalGradient([.., a1], [.., a2])
+                | FillShader::AngledGradient([.., a1], [.., a2], _)
+                | FillShader::RadialGradient([.., a1], [.., a2], ..)
+                | FillShader::ConicGradient([.., a1], [.., a2], ..) => 0.5 * (a1 + a2),
+            };
+            let shadow = Paint::solid([r, g, b, a * alpha]);
+            let transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
+
+            for glyph in label.glyphs() {
+                let transform = transform
+                    .pre_translate(glyph.x, glyph.y)
+                    .pre_scale(glyph.scale, glyph.scale);
+                self.draw_glyph(&glyph.path, &shadow, &transform);
+            }
+        }
+
+        for glyph in label.glyphs() {
+            let transform = transform
+                .pre_translate(glyph.x, glyph.y)
+                .pre_scale(glyph.scale, glyph.scale);
+            let paint = match &glyph.color {
+                Some(color) => Paint::solid(*color),
+                None => paint,
+            };
+            self.draw_glyph(&glyph.path, &paint, &transform);
+        }
+    }
+
+    /// Draws the glyph from the glyph atlas. If it doesn't fit into the atlas,
+    /// it gets drawn as triangles instead.
+    fn draw_glyph(&mut self, path: &GpuPath, paint: &Paint, transform: &Transform) {
+        match self.atlas.glyph(
+            &self.gpu.queue,
+            path,
+            [transform.scale_x, transform.scale_y],
+            [transform.x, transform.y],
+        ) {
+            Lookup::Placed(glyph) => {
+                let to_device = self.list.to_device();
+                let [[u1, v1], [u2, v2]] = glyph.uv;
+                let [left, top] = glyph.position;
+                let [right, bottom] = [left + glyph.size[0], top + glyph.size[1]];
+                let vertices = [
+                    ([left, top], [u1, v1]),
+                    ([right, top], [u2, v1]),
+                    ([right, bottom], [u2, v2]),
+                    ([left, bottom], [u1, v2]),
+                ]
+                .map(|([x, y], uv)| {
+                    // The gradient is evaluated in the glyph's coordinate
+                    // space, just like for the triangles of a glyph.
+                    let local = [
+                        (x - transform.x) / transform.scale_x,
+                        (y - transform.y) / transform.scale_y,
+                    ];
+                    paint.vertex(to_device(x, y), uv, local, SOURCE_COVERAGE)
+                });
+                let bind_group = &self.atlas.bind_group;
+                self.list
+                    .push(Pipeline::Fill, bind_group, vertices, QUAD_INDICES);
+            }
+            Lookup::Empty => {}
+            Lookup::Unavailable => {
+                if let Some(path) = path {
+                    self.draw_mesh(&path.fill(transform), paint, transform);
+                }
+            }
+        }
+    }
+
+    fn draw_background(
+        &mut self,
+        background: &Option<Background<GpuImage>>,
+        blurred_background_image: &Option<(BackgroundImage<usize>, wgpu::BindGroup)>,
+    ) {
+        let [width, height] = self.list.size;
+        match background {
+            Some(Background::Shader(shader)) => {
+                // The background is evaluated in pixels, just like the
+                // software renderer does, so angled gradients keep their
+                // direction on the screen.
+                let paint = Paint::new(
+                    shader,
+                    || [[0.0, width], [0.0, height]],
+                    &Transform::scale(1.0, 1.0),
+                );
+                let to_device = self.list.to_device();
+                let vertices = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|[x, y]| {
+                    let position = [x * width, y * height];
+                    paint.vertex(to_device(position[0], position[1]), [0.0; 2], position, 0)
+                });
+                let bind_group = &self.atlas.bind_group;
+                self.list
+                    .push(Pipeline::Fill, bind_group, vertices, QUAD_INDICES);
+            }
+            Some(Background::Image(image, transform)) => {
+                let bind_group = match blurred_background_image {
+                    Some((_, bind_group)) if image.blur != 0.0 => bind_group.clone(),
+                    _ => image.image.bind_group(self.gpu, transform),
+                };
+                let brightness = image.brightness.clamp(0.0, 1.0);
+                let color = brightness * image.opacity;
+                self.draw_image(&bind_group, [color, color, color, image.opacity], transform);
+            }
+            None => {}
+        }
+    }
+}
+
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+fn bounds(path: &tiny_skia::Path) -> [[f32; 2]; 2] {
+    let bounds = path.bounds();
+    [
+        [bounds.left(), bounds.right()],
+        [bounds.top(), bounds.bottom()],
+    ]
+}
+
+fn label_bounds(label: &LockedLabel<GpuPath>) -> [[f32; 2]; 2] {
+    let (mut left, mut right) = (f32::INFINITY, f32::NEG_INFINITY);
+    let (mut top, mut bottom) = (f32::INFINITY, f32::NEG_INFINITY);
+    for glyph in label.glyphs() {
+        if let Some(path) = &glyph.path {
+            let bounds = path.path.bounds();
+            left = left.min(bounds.left());
+            right = right.max(bounds.right());
+            top = top.min(bounds.top());
+            bottom = bottom.max(bounds.bottom());
+        }
+    }
+    [
+        if right < left {
+            [0.0, 0.0]
+        } else {
+            [left, right]
+        },
+        if bottom < top {
+            [0.0, 0.0]
+        } else {
+            [top, bottom]
+        },
+    ]
+}
+
+const fn convert_blend_mode(blend_mode: BlendMode) -> u32 {
+    match blend_mode {
+        BlendMode::Normal => 1,
+        BlendMode::Multiply => 2,
+        BlendMode::Screen => 3,
+        BlendMode::Overlay => 4,
+        BlendMode::Darken => 5,
+        BlendMode::Lighten => 6,
+        BlendMode::Add => 7,
+    }
+}
+
+/// The wgpu renderer renders layouts on the GPU. It renders into any texture
+/// that can be used as a render attachment, such as the current texture of a
+/// surface. Just like the software renderer, it only redraws the parts of the
+/// scene that change frequently for most frames.
+pub struct Renderer {
+    gpu: Gpu,
+    allocator: GpuAllocator,
+    scene_manager: SceneManager<GpuPath, GpuImage, GpuFont, GpuLabel>,
+    atlas: GlyphAtlas,
+    targets: Option<Targets>,
+    present_pipelines: HashMap<wgpu::TextureFormat, wgpu::RenderPipeline>,
+    list: DrawList,
+    vertex_buffer: Option<wgpu::Buffer>,
+    index_buffer: Option<wgpu::Buffer>,
+    blurred_background_image: Option<(BackgroundImage<usize>, wgpu::BindGroup)>,
+    top_layer_was_empty: bool,
+}
+
+impl Renderer {
+    /// Creates a new renderer that renders with the device provided.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let gpu = Gpu::new(device, queue);
+        let mut allocator = GpuAllocator {
+            text_engine: TextEngine::new(),
+        };
+        let scene_manager = SceneManager::new(&mut allocator);
+        Self {
+            atlas: GlyphAtlas::new(&gpu),
+            gpu,
+            allocator,
+            scene_manager,
+            targets: None,
+            present_pipelines: HashMap::new(),
+            list: DrawList::default(),
+            vertex_buffer: None,
+            index_buffer: None,
+            blurred_background_image: None,
+            top_layer_was_empty: true,
+        }
+    }
+
+    /// Sets the limits the cache of the glyph outlines used by the text is
+    /// kept within. The cache is collected after every frame. By default it is
+    /// unlimited.
+    pub const fn set_glyph_cache_limits(&mut self, limits: CacheLimits) {
+        self.allocator.text_engine.set_glyph_cache_limits(limits);
+    }
+
+    /// Returns how many glyph outlines are in the glyph cache.
+    pub fn glyph_cache_usage(&self) -> CacheUsage {
+        self.allocator.text_engine.glyph_cache_usage()
+    }
+
+    /// Sets the limits the glyph atlas, which stores the rasterized glyphs on
+    /// the GPU, is kept within. The atlas can only be cleared as a whole, so
+    /// it gets cleared once it exceeds the limits. Its texture has a fixed
+    /// size, so by default it is only cleared once it runs out of space.
+    pub const fn set_glyph_atlas_limits(&mut self, limits: CacheLimits) {
+        self.atlas.limits = limits;
+    }
+
+    /// Returns how many rasterized glyphs are in the glyph atlas and how many
+    /// bytes of its texture they take up.
+    pub fn glyph_atlas_usage(&self) -> CacheUsage {
+        self.atlas.usage()
+    }
+
+    /// Sets how text and lines are aligned to the pixel grid of the texture.
+    /// By default nothing is aligned to it.
+    pub const fn set_hinting(&mut self, hinting: Hinting) {
+        self.scene_manager.set_hinting(hinting);
+    }
+
+    /// Sets the amount of pixels of the texture per logical pixel of the
+    /// display it is shown on. With hinting, lines are at least one logical
+    /// pixel thick. By default this is 1.
+    pub const fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f32) {
+        self.scene_manager
+            .set_device_pixel_ratio(device_pixel_ratio);
+    }
+
+    /// Renders the layout state provided into the texture provided. The layout
+    /// is rendered with the resolution of the texture, which needs to be
+    /// usable as a render attachment. It may detect that the layout got
+    /// resized. In that case it returns the new ideal size. This is just a
+    /// hint and can be ignored entirely. The rendering commands are submitted
+    /// to the queue before this returns.
+    pub fn render(
+        &mut self,
+        state: &LayoutState,
+        image_cache: &ImageCache,
+        target: &wgpu::Texture,
+    ) -> Option<[f32; 2]> {
+        let size = [target.width(), target.height()];
+
+        let new_resolution = self.scene_manager.update_scene(
+            &mut self.allocator,
+            size.map(|v| v as f32),
+            state,
+            image_cache,
+        );
+
+        self.allocator.text_engine.collect_glyphs();
+
+        // All the glyphs of the previous frame are submitted already, so the
+        // atlas can make space for new glyphs now.
+        self.atlas.clear_if_full();
+
+        let resized = self.targets.as_ref().is_none_or(|t| t.size != size);
+        if resized {
+            self.targets = Some(Targets::new(&self.gpu, size));
+        }
+        let targets = self.targets.as_ref().unwrap();
+
+        let scene = self.scene_manager.scene();
+        let redraw_bottom_layer = resized || scene.bottom_layer_changed();
+        let top_layer = scene.top_layer();
+        let redraw_canvas = redraw_bottom_layer
+            || !top_layer.is_empty()
+            || !mem::replace(&mut self.top_layer_was_empty, top_layer.is_empty());
+
+        if redraw_bottom_layer {
+            update_blurred_background_image(
+                &self.gpu,
+                scene.background(),
+                &mut self.blurred_background_image,
+            );
+        }
+
+        self.list.clear(size);
+        let mut context = Context {
+            gpu: &self.gpu,
+            atlas: &mut self.atlas,
+            list: &mut self.list,
+            group_bind_group: &targets.group_bind_group,
+        };
+
+        if redraw_bottom_layer {
+            context.draw_background(scene.background(), &self.blurred_background_image);
+            context.list.flush(Target::Bottom, true);
+            context.draw_layer(scene.bottom_layer(), Target::Bottom);
+        }
+
+        if redraw_canvas {
+            context
+                .list
+                .push_fullscreen(&targets.bottom_bind_group, 1.0, MODE_REPLACE);
+            context.list.flush(Target::Canvas, true);
+            context.draw_layer(top_layer, Target::Canvas);
+        }
+
+        // The final image gets copied into the target, converting it to the
+        // target's format.
+        let present_start = self.list.indices.len() as u32;
+        self.list
+            .push_fullscreen(&targets.canvas_bind_group, 1.0, MODE_REPLACE);
+        let present_indices = present_start..self.list.indices.len() as u32;
+
+        let gpu = &self.gpu;
+        let vertex_buffer = upload(
+            gpu,
+            &mut self.vertex_buffer,
+            bytemuck::cast_slice(&self.list.vertices),
+            wgpu::BufferUsages::VERTEX,
+        );
+        let index_buffer = upload(
+            gpu,
+            &mut self.index_buffer,
+            bytemuck::cast_slice(&self.list.indices),
+            wgpu::BufferUsages::INDEX,
+        );
+
+        let mut encoder = gpu
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        for operation in &self.list.operations {
+            match operation {
+                Operation::Draw {
+                    target,
+                    clear,
+                    batches,
+                } => {
+                    let (view, resolve_target) = targets.views(*target);
+                    let mut pass = begin_render_pass(
+                        &mut encoder,
+                        view,
+                        Some(resolve_target),
+                        if *clear {
+                            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+                        } else {
+                            wgpu::LoadOp::Load
+                        },
+                    );
+                    pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                    pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    for batch in &self.list.batches[batches.clone()] {
+                        pass.set_pipeline(match batch.pipeline {
+                            Pipeline::Fill => &gpu.fill_pipeline,
+                            Pipeline::Composite => &gpu.composite_pipeline,
+                        });
+                        pass.set_bind_group(0, &batch.bind_group, &[]);
+                        pass.draw_indexed(batch.indices.clone(), 0, 0..1);
+                    }
+                }
+                Operation::CopyToBackdrop(target) => {
+                    encoder.copy_texture_to_texture(
+                        targets.texture(*target).as_image_copy(),
+                        targets.backdrop.texture.as_image_copy(),
+                        targets.backdrop.texture.size(),
+                    );
+                }
+            }
+        }
+
+        let present_pipeline = self
+            .present_pipelines
+            .entry(target.format())
+            .or_insert_with(|| gpu.create_present_pipeline(target.format()));
+        let view = target.create_view(&Default::default());
+        let mut pass = begin_render_pass(
+            &mut encoder,
+            &view,
+            None,
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+        );
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.set_pipeline(present_pipeline);
+        pass.set_bind_group(0, &targets.canvas_bind_group, &[]);
+        pass.draw_indexed(present_indices, 0, 0..1);
+        drop(pass);
+
+        gpu.queue.submit([encoder.finish()]);
+
+        new_resolution
+    }
+}
+
+fn begin_render_pass<'encoder>(
+    encoder: &'encoder mut wgpu::CommandEncoder,
+    view: &wgpu::TextureView,
+    resolve_target: Option<&wgpu::TextureView>,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPass<'encoder> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            depth_slice: None,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    })
+}
+
+/// Writes the data into the buffer, growing the buffer if it's too small.
+fn upload<'a>(
+    gpu: &Gpu,
+    buffer: &'a mut Option<wgpu::Buffer>,
+    data: &[u8],
+    usage: wgpu::BufferUsages,
+) -> &'a wgpu::Buffer {
+    let size = data.len() as wgpu::BufferAddress;
+    if buffer.as_ref().is_none_or(|buffer| buffer.size() < size) {
+        *buffer = Some(gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: size.next_power_of_two().max(1024),
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+    let buffer = buffer.as_ref().unwrap();
+    gpu.queue.write_buffer(buffer, 0, data);
+    buffer
+}
+
+fn update_blurred_background_image(
+    gpu: &Gpu,
+    background: &Option<Background<GpuImage>>,
+    blurred_background_image: &mut Option<(BackgroundImage<usize>, wgpu::BindGroup)>,
+) {
+    match background {
+        Some(Background::Image(image, _)) if image.blur != 0.0 => {
+            let current_key = image.map(image.image.id);
+            if !blurred_background_image
+                .as_ref()
+                .is_some_and(|(key, _)| &current_key == key)
+            {
+                let pixmap = software::blur(image.image.image.pixmap(), image.blur);
+                *blurred_background_image = Some((current_key, gpu.upload(&pixmap)));
+            }
+        }
+        _ => *blurred_background_image = None,
+    }
+}
@@ -0,0 +1,86 @@
+// Draws the triangles of paths, glyphs and images. The positions are already
+// in normalized device coordinates. The paint coordinates are the positions in
+// the local coordinate space of the entity, mapped such that the gradients can
+// be evaluated directly.
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) paint: vec2<f32>,
+    @location(3) color0: vec4<f32>,
+    @location(4) color1: vec4<f32>,
+    @location(5) params: vec4<f32>,
+    @location(6) kind: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) paint: vec2<f32>,
+    @location(2) @interpolate(flat, either) color0: vec4<f32>,
+    @location(3) @interpolate(flat, either) color1: vec4<f32>,
+    @location(4) @interpolate(flat, either) params: vec4<f32>,
+    @location(5) @interpolate(flat, either) kind: u32,
+}
+
+const PAINT_SOLID: u32 = 0u;
+const PAINT_LINEAR: u32 = 1u;
+const PAINT_RADIAL: u32 = 2u;
+const PAINT_CONIC: u32 = 3u;
+
+const SOURCE_COVERAGE: u32 = 1u;
+const SOURCE_IMAGE: u32 = 2u;
+
+@group(0) @binding(0) var source_texture: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(in.position, 0.0, 1.0);
+    out.uv = in.uv;
+    out.paint = in.paint;
+    out.color0 = in.color0;
+    out.color1 = in.color1;
+    out.params = in.params;
+    out.kind = in.kind;
+    return out;
+}
+
+fn paint_color(in: VertexOutput) -> vec4<f32> {
+    var t = 0.0;
+    switch in.kind & 15u {
+        case PAINT_LINEAR: {
+            t = in.paint.x;
+        }
+        case PAINT_RADIAL: {
+            t = length(in.paint - in.params.xy) / in.params.z;
+        }
+        case PAINT_CONIC: {
+            // The angle is measured clockwise, starting at the top.
+            let d = (in.paint - in.params.xy) * vec2<f32>(in.params.w, 1.0);
+            t = fract((degrees(atan2(d.x, -d.y)) - in.params.z) / 360.0);
+        }
+        default: {}
+    }
+    // The colors are interpolated without premultiplied alpha, just like the
+    // software renderer does it.
+    let color = mix(in.color0, in.color1, clamp(t, 0.0, 1.0));
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureSample(source_texture, source_sampler, in.uv);
+    switch in.kind >> 4u {
+        case SOURCE_COVERAGE: {
+            return paint_color(in) * texel.r;
+        }
+        case SOURCE_IMAGE: {
+            return texel * in.color0;
+        }
+        default: {
+            return paint_color(in);
+        }
+    }
+}
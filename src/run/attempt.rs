@@ -1,4 +1,4 @@
-use crate::{AtomicDateTime, Time, TimeSpan};
+use crate::{AtomicDateTime, Time, TimeSpan, platform::prelude::*, timing::event_log::EventLog};
 
 /// An `Attempt` describes information about an attempt to run a specific category
 /// by a specific runner in the past. Every time a new attempt is started and
@@ -10,6 +10,10 @@ pub struct Attempt {
     started: Option<AtomicDateTime>,
     ended: Option<AtomicDateTime>,
     pause_time: Option<TimeSpan>,
+    variables: Vec<(String, String)>,
+    split_variables: Vec<(usize, Vec<(String, String)>)>,
+    tags: Vec<String>,
+    event_log: EventLog,
 }
 
 impl Attempt {
@@ -35,6 +39,10 @@ impl Attempt {
             started,
             ended,
             pause_time,
+            variables: Vec::new(),
+            split_variables: Vec::new(),
+            tags: Vec::new(),
+            event_log: EventLog::from_entries(Vec::new()),
         }
     }
 
@@ -65,6 +73,12 @@ impl Attempt {
         self.time
     }
 
+    /// Sets the split time of the last segment.
+    #[inline]
+    pub(crate) const fn set_time(&mut self, time: Time) {
+        self.time = time;
+    }
+
     /// Accesses the amount of time the attempt has been paused for. If it is
     /// not known, this returns `None`. This means that it may not necessarily
     /// be possible to differentiate whether a Run has not been paused or it
@@ -87,4 +101,91 @@ impl Attempt {
     pub const fn ended(&self) -> Option<AtomicDateTime> {
         self.ended
     }
+
+    /// Accesses the values the custom variables had at the end of the
+    /// attempt.
+    #[inline]
+    pub fn variables(&self) -> &[(String, String)] {
+        &self.variables
+    }
+
+    /// Accesses the value the custom variable with the name specified had at
+    /// the end of the attempt.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Sets the values the custom variables had at the end of the attempt.
+    pub fn set_variables(&mut self, variables: Vec<(String, String)>) {
+        self.variables = variables;
+    }
+
+    /// Accesses the values the custom variables had at each split of the
+    /// attempt, along with the index of the segment that was split. Only
+    /// segments that were split and had their variables recorded are listed.
+    #[inline]
+    pub fn split_variables(&self) -> &[(usize, Vec<(String, String)>)] {
+        &self.split_variables
+    }
+
+    /// Sets the values the custom variables had when the segment with the
+    /// index specified was split.
+    pub fn set_split_variables(&mut self, segment_index: usize, variables: Vec<(String, String)>) {
+        match self
+            .split_variables
+            .binary_search_by_key(&segment_index, |&(i, _)| i)
+        {
+            Ok(index) => self.split_variables[index].1 = variables,
+            Err(index) => self
+                .split_variables
+                .insert(index, (segment_index, variables)),
+        }
+    }
+
+    /// Accesses the tags attached to the attempt. Tags can be used to mark
+    /// attempts, like route tests or marathon runs, so they can be told apart
+    /// or excluded from the comparisons.
+    #[inline]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns whether the tag specified is attached to the attempt.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Attaches the tag specified to the attempt. Returns `false` if it was
+    /// already attached.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        if self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_owned());
+        true
+    }
+
+    /// Removes the tag specified from the attempt. Returns `false` if it wasn't
+    /// attached.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let len = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != len
+    }
+
+    /// Accesses the [`EventLog`] of the attempt, which allows replaying the
+    /// attempt into a [`Timer`](crate::Timer). It is empty if it wasn't
+    /// stored.
+    #[inline]
+    pub const fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    /// Sets the [`EventLog`] of the attempt.
+    pub fn set_event_log(&mut self, event_log: EventLog) {
+        self.event_log = event_log;
+    }
 }
@@ -0,0 +1,218 @@
+use super::Run;
+use crate::{Timer, TimerPhase, platform::prelude::*, settings::Image, util::PopulateString};
+
+/// Describes an error that occurred while switching the active [`Run`] of a
+/// [`RunBundle`].
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum SwitchError {
+    /// There is no run at the index provided.
+    IndexOutOfBounds,
+    /// The timer has an attempt in progress, so the run can't be switched.
+    AttemptInProgress,
+    /// The run doesn't have any segments, so it can't be used for timing.
+    EmptyRun,
+}
+
+/// A `RunBundle` holds multiple related [`Run`]s in a single file, such as the
+/// different categories of the same game. The game name and game icon are
+/// shared between all the runs. Segments without an icon share the icon of a
+/// segment with the same name in another run of the bundle. One of the runs is
+/// the active run, which is the one in use by the [`Timer`].
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::run::RunBundle;
+/// use livesplit_core::{Run, Segment, Timer};
+///
+/// let mut any = Run::new();
+/// any.set_game_name("Super Mario Odyssey");
+/// any.set_category_name("Any%");
+/// any.push_segment(Segment::new("Cap Kingdom"));
+///
+/// let mut all_moons = Run::new();
+/// all_moons.set_category_name("All Moons");
+/// all_moons.push_segment(Segment::new("Cap Kingdom"));
+///
+/// let mut bundle = RunBundle::new();
+/// bundle.push(any);
+/// bundle.push(all_moons);
+/// assert_eq!(bundle.runs()[1].game_name(), "Super Mario Odyssey");
+///
+/// let mut timer = Timer::new(bundle.active_run().unwrap().clone()).unwrap();
+/// bundle.switch(1, &mut timer).unwrap();
+/// assert_eq!(timer.run().category_name(), "All Moons");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunBundle {
+    game_name: String,
+    game_icon: Image,
+    runs: Vec<Run>,
+    active: usize,
+}
+
+impl RunBundle {
+    /// Creates a new empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accesses the name of the game that all the runs are for.
+    #[inline]
+    pub fn game_name(&self) -> &str {
+        &self.game_name
+    }
+
+    /// Sets the name of the game for all the runs of the bundle.
+    pub fn set_game_name<S>(&mut self, name: S)
+    where
+        S: PopulateString,
+    {
+        name.populate(&mut self.game_name);
+        for run in &mut self.runs {
+            run.set_game_name(&*self.game_name);
+        }
+    }
+
+    /// Accesses the game icon shared by all the runs.
+    #[inline]
+    pub const fn game_icon(&self) -> &Image {
+        &self.game_icon
+    }
+
+    /// Sets the game icon for all the runs of the bundle.
+    pub fn set_game_icon(&mut self, image: Image) {
+        for run in &mut self.runs {
+            run.set_game_icon(image.clone());
+        }
+        self.game_icon = image;
+    }
+
+    /// Adds a run to the bundle. If the bundle doesn't have a game name or game
+    /// icon yet, the ones of the run are used for the bundle. Otherwise the
+    /// run receives the bundle's game name and game icon. Segments of the run
+    /// without an icon receive the icon of a segment with the same name in
+    /// another run of the bundle.
+    pub fn push(&mut self, mut run: Run) {
+        if self.game_name.is_empty() {
+            run.game_name().populate(&mut self.game_name);
+        } else {
+            run.set_game_name(&*self.game_name);
+        }
+
+        if self.game_icon.is_empty() {
+            self.game_icon = run.game_icon().clone();
+        } else {
+            run.set_game_icon(self.game_icon.clone());
+        }
+
+        for segment in run.segments_mut() {
+            if !segment.icon().is_empty() {
+                continue;
+            }
+            let shared_icon = self
+                .runs
+                .iter()
+                .flat_map(|run| run.segments())
+                .find(|other| other.name() == segment.name() && !other.icon().is_empty());
+            if let Some(other) = shared_icon {
+                segment.set_icon(other.icon().clone());
+            }
+        }
+
+        self.runs.push(run);
+    }
+
+    /// Removes the run at the index provided from the bundle and returns it.
+    /// The active run can't be removed, so `None` is returned if the index is
+    /// the one of the active run or if there is no run at the index.
+    pub fn remove(&mut self, index: usize) -> Option<Run> {
+        if index == self.active || index >= self.runs.len() {
+            return None;
+        }
+        if index < self.active {
+            self.active -= 1;
+        }
+        Some(self.runs.remove(index))
+    }
+
+    /// Accesses all the runs of the bundle.
+    #[inline]
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    /// Returns the amount of runs in the bundle.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns `true` if there are no runs in the bundle.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Returns the index of the active run.
+    #[inline]
+    pub const fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Accesses the active run. This is `None` if the bundle is empty. While
+    /// the active run is in use by a [`Timer`], the version stored in the
+    /// bundle may be outdated. Use [`store_active_run`](Self::store_active_run)
+    /// to update it.
+    pub fn active_run(&self) -> Option<&Run> {
+        self.runs.get(self.active)
+    }
+
+    /// Stores the [`Run`] in use by the [`Timer`] provided as the active run of
+    /// the bundle. If there is an attempt in progress, it is stored in the
+    /// run's history, without ending the attempt in the timer.
+    pub fn store_active_run(&mut self, timer: &Timer) {
+        if let Some(active) = self.runs.get_mut(self.active) {
+            *active = if timer.current_phase() == TimerPhase::NotRunning {
+                timer.run().clone()
+            } else {
+                timer.clone().into_run(true)
+            };
+        }
+    }
+
+    /// Switches the active run to the run at the index provided. The
+    /// [`Timer`] is expected to use the current active run. Its run is stored
+    /// back into the bundle and replaced by the new active run. The run can't
+    /// be switched while an attempt is in progress.
+    pub fn switch(&mut self, index: usize, timer: &mut Timer) -> Result<(), SwitchError> {
+        if timer.current_phase() != TimerPhase::NotRunning {
+            return Err(SwitchError::AttemptInProgress);
+        }
+        let run = self.runs.get(index).ok_or(SwitchError::IndexOutOfBounds)?;
+        if index == self.active {
+            return Ok(());
+        }
+        let previous = timer
+            .replace_run(run.clone(), false)
+            .map_err(|_| SwitchError::EmptyRun)?;
+        if let Some(active) = self.runs.get_mut(self.active) {
+            *active = previous;
+        }
+        self.active = index;
+        Ok(())
+    }
+
+    /// Switches the active run of the [`SharedTimer`](crate::SharedTimer)
+    /// provided to the run at the index provided. See
+    /// [`switch`](Self::switch) for more information.
+    #[cfg(feature = "std")]
+    pub fn switch_shared(
+        &mut self,
+        index: usize,
+        timer: &crate::SharedTimer,
+    ) -> Result<(), SwitchError> {
+        self.switch(index, &mut timer.write().unwrap())
+    }
+}
@@ -6,13 +6,19 @@
 
 use super::{AddComparisonError, CopyComparisonError, LinkedLayout};
 use crate::{
-    Run, Segment, Time, TimeSpan, TimingMethod, comparison,
-    platform::prelude::*,
+    Attempt, DateTime, Run, Segment, Time, TimeSpan, TimingMethod,
+    analysis::{
+        comparison_health::{self, ComparisonHealth, Suggestion},
+        sanity_check::{self, Issue},
+    },
+    comparison::{self, AverageSegments, LastAttempts},
+    platform::{Duration, prelude::*},
     settings::Image,
     timing::ParseError as ParseTimeSpanError,
     util::{PopulateString, caseless},
 };
-use core::{mem::swap, num::ParseIntError};
+use core::{mem::swap, num::ParseIntError, ops::Range};
+use hashbrown::HashMap;
 use snafu::{OptionExt, ResultExt};
 
 pub mod cleaning;
@@ -32,6 +38,7 @@ pub use self::{
 /// Describes an Error that occurred while parsing a time.
 #[derive(Debug, snafu::Snafu)]
 #[snafu(context(suffix(false)))]
+#[non_exhaustive]
 pub enum ParseError {
     /// Couldn't parse the time.
     ParseTime {
@@ -42,6 +49,35 @@ pub enum ParseError {
     NegativeTimeNotAllowed,
     /// Empty times are not allowed here.
     EmptyTimeNotAllowed,
+    /// The attempt doesn't have a segment history element for the segment.
+    AttemptNotFound,
+}
+
+/// Describes an Error that occurred while adding a manually authored
+/// comparison.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum ManualComparisonError {
+    /// The comparison couldn't be added.
+    AddComparison {
+        /// The underlying error.
+        source: AddComparisonError,
+    },
+    /// The amount of split times doesn't match the amount of segments.
+    SegmentCountMismatch,
+    /// The split time of a segment couldn't be parsed.
+    ParseSplitTime {
+        /// The index of the segment.
+        index: usize,
+        /// The underlying error.
+        source: ParseError,
+    },
+    /// The split time of a segment is earlier than a split time of a previous
+    /// segment.
+    NonMonotonic {
+        /// The index of the segment.
+        index: usize,
+    },
 }
 
 /// Describes an Error that occurred while opening the Run Editor.
@@ -53,6 +89,28 @@ pub enum OpenError {
     EmptyRun,
 }
 
+/// Describes an Error that occurred while shifting or scaling the times of the
+/// Run.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum TimeOperationError {
+    /// The operation would result in negative times.
+    NegativeTime,
+    /// The factor to scale the times by needs to be finite and positive.
+    InvalidFactor,
+    /// The operation would result in times that are too large to be
+    /// represented.
+    TimeTooLarge,
+}
+
+/// Describes an Error that occurred while importing segments from another Run.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum ImportSegmentsError {
+    /// The range of segments to import is empty or out of bounds.
+    InvalidRange,
+}
+
 /// Error type for a failed Rename.
 #[derive(PartialEq, Eq, Debug, snafu::Snafu)]
 #[snafu(context(suffix(false)))]
@@ -302,6 +360,16 @@ impl Editor {
         self.raise_run_edited();
     }
 
+    /// Sets the frame rate in frames per second the times of the [`Run`] are
+    /// counted in. Specify `None` to stop counting the times in frames. Frame
+    /// rates outside of [`MIN_FRAME_RATE`](crate::run::MIN_FRAME_RATE) and
+    /// [`MAX_FRAME_RATE`](crate::run::MAX_FRAME_RATE) are ignored and remove
+    /// the frame rate instead.
+    pub fn set_frame_rate(&mut self, frame_rate: Option<f64>) {
+        self.run.set_frame_rate(frame_rate);
+        self.raise_run_edited();
+    }
+
     /// Accesses all the custom comparisons that exist on the Run.
     pub fn custom_comparisons(&self) -> &[String] {
         self.run.custom_comparisons()
@@ -524,6 +592,77 @@ impl Editor {
         self.fix();
     }
 
+    /// Imports a range of segments from another Run below the active segment.
+    /// The names, icons, variables, best segment times and segment histories
+    /// of the segments are copied. The attempts of the other Run don't exist
+    /// in this Run, so the segment history elements of the imported segments
+    /// are remapped to new indices that don't belong to any attempt, similar to
+    /// imported best segments. An attempt of the other Run keeps the same new
+    /// index across all the imported segments, so skipped segments remain
+    /// combined with the following segment. The imported segments are then
+    /// selected, with the first one being the active segment.
+    pub fn import_segments(
+        &mut self,
+        run: &Run,
+        range: Range<usize>,
+    ) -> Result<(), ImportSegmentsError> {
+        if range.is_empty() || range.end > run.len() {
+            return Err(ImportSegmentsError::InvalidRange);
+        }
+
+        let next_segment = self.active_segment_index() + 1;
+        if next_segment < self.run.len() {
+            self.run.import_best_segment(next_segment);
+        }
+
+        let max_index = self.run.max_attempt_history_index().unwrap_or(0);
+        let min_index = self.run.min_segment_history_index().unwrap();
+
+        // The attempts are remapped in the order they first show up in, so
+        // they keep the same new index across all the imported segments.
+        let mut remapped_indices = HashMap::<i32, i32>::new();
+        let mut next_free_index = min_index - 1;
+        for source in &run.segments()[range.clone()] {
+            for &(source_index, _) in source.segment_history() {
+                remapped_indices.entry(source_index).or_insert_with(|| {
+                    let index = next_free_index;
+                    next_free_index -= 1;
+                    index
+                });
+            }
+        }
+
+        for (offset, source) in run.segments()[range.clone()].iter().enumerate() {
+            let mut segment = Segment::new(source.name());
+            segment.set_icon(source.icon().clone());
+            segment.variables_mut().clone_from(source.variables());
+            segment.set_best_segment_time(source.best_segment_time());
+
+            for x in min_index..=max_index {
+                segment.segment_history_mut().insert(x, Default::default());
+            }
+
+            for &(source_index, time) in source.segment_history() {
+                segment
+                    .segment_history_mut()
+                    .insert(remapped_indices[&source_index], time);
+            }
+
+            self.run
+                .segments_mut()
+                .insert(next_segment + offset, segment);
+        }
+
+        self.selected_segments.clear();
+        self.selected_segments
+            .extend((next_segment..next_segment + range.len()).rev());
+
+        self.times_modified();
+        self.fix();
+
+        Ok(())
+    }
+
     fn fix_after_deletion(&mut self, index: usize) {
         self.fix_with_timing_method(index, TimingMethod::RealTime);
         self.fix_with_timing_method(index, TimingMethod::GameTime);
@@ -740,6 +879,75 @@ impl Editor {
         self.fix();
     }
 
+    /// Moves the segment at the index provided to a new position, such as when
+    /// dragging it to a different place in the list. The segment is moved by
+    /// repeatedly switching it with its neighbor, so the segment histories,
+    /// best segments and comparisons are adjusted the same way as when moving
+    /// the segment up or down step by step. Segment history elements of
+    /// attempts where only one of two switched segments was skipped can't be
+    /// preserved and are removed. The moved segment then becomes the only
+    /// selected segment.
+    ///
+    /// # Panics
+    ///
+    /// This panics if either of the indices provided is out of bounds.
+    pub fn move_segment(&mut self, from: usize, to: usize) {
+        let len = self.run.len();
+        if from >= len || to >= len {
+            panic!("Index out of bounds for moving a segment.");
+        }
+
+        if from < to {
+            for i in from..to {
+                self.switch_segments(i);
+            }
+        } else {
+            for i in (to..from).rev() {
+                self.switch_segments(i);
+            }
+        }
+
+        self.selected_segments.clear();
+        self.selected_segments.push(to);
+
+        self.times_modified();
+        self.fix();
+    }
+
+    /// Checks if the active segment can be merged with the segment below it.
+    /// The last segment can't be merged.
+    pub fn can_merge_segment_with_next(&self) -> bool {
+        self.active_segment_index() + 1 < self.run.len()
+    }
+
+    /// Merges the active segment with the segment below it. The segment
+    /// histories of both segments are combined and the best segments are
+    /// adjusted accordingly. The merged segment keeps the name of the segment
+    /// below, as its split time is the one that remains. If it doesn't have an
+    /// icon, the icon of the active segment is used. The merged segment then
+    /// becomes the only selected segment.
+    pub fn merge_segment_with_next(&mut self) {
+        if !self.can_merge_segment_with_next() {
+            return;
+        }
+
+        let index = self.active_segment_index();
+        self.fix_after_deletion(index);
+        let removed = self.run.segments_mut().remove(index);
+
+        let merged = self.run.segment_mut(index);
+        if merged.icon().is_empty() {
+            merged.set_icon(removed.icon().clone());
+        }
+
+        self.selected_segments.clear();
+        self.selected_segments.push(index);
+
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+    }
+
     /// Adds a new custom comparison. It can't be added if it starts with
     /// `[Race]` or it already exists.
     pub fn add_comparison<S: PopulateString>(
@@ -751,6 +959,66 @@ impl Editor {
         Ok(())
     }
 
+    /// Adds a new custom comparison with split times that are manually
+    /// authored rather than derived from the history. A split time needs to be
+    /// provided for each segment, where `None` means that the segment has no
+    /// split time. The split times need to be monotonic, so no split time may
+    /// be earlier than the split time of a previous segment. The split times
+    /// are set for the selected timing method. The comparison can't be added
+    /// if it starts with `[Race]` or it already exists.
+    pub fn add_manual_comparison<S: PopulateString>(
+        &mut self,
+        comparison: S,
+        split_times: &[Option<TimeSpan>],
+    ) -> Result<(), ManualComparisonError> {
+        if split_times.len() != self.run.len() {
+            return Err(ManualComparisonError::SegmentCountMismatch);
+        }
+        if let Some(index) = split_times
+            .iter()
+            .position(|t| t.is_some_and(|t| t < TimeSpan::zero()))
+        {
+            return Err(ManualComparisonError::ParseSplitTime {
+                index,
+                source: ParseError::NegativeTimeNotAllowed,
+            });
+        }
+        if let Some(index) = first_non_monotonic_split(split_times.iter().copied()) {
+            return Err(ManualComparisonError::NonMonotonic { index });
+        }
+
+        let name = comparison.into_string();
+        self.run
+            .add_custom_comparison(name.as_str())
+            .context(AddComparison)?;
+
+        let method = self.selected_method;
+        for (segment, &time) in self.run.segments_mut().iter_mut().zip(split_times) {
+            segment.comparison_mut(&name)[method] = time;
+        }
+
+        self.times_modified();
+        self.fix();
+        Ok(())
+    }
+
+    /// Parses the split times of a manually authored comparison and adds it
+    /// as a new custom comparison. An empty string means that the segment has
+    /// no split time. See [`add_manual_comparison`](Self::add_manual_comparison)
+    /// for more information.
+    pub fn parse_and_add_manual_comparison<'a, S: PopulateString>(
+        &mut self,
+        comparison: S,
+        split_times: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ManualComparisonError> {
+        let split_times = split_times
+            .into_iter()
+            .enumerate()
+            .map(|(index, time)| parse_positive(time).context(ParseSplitTime { index }))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.add_manual_comparison(comparison, &split_times)
+    }
+
     /// Imports the Personal Best from the provided run as a comparison. The
     /// comparison can't be added if its name starts with `[Race]` or it already
     /// exists.
@@ -802,6 +1070,7 @@ impl Editor {
         for segment in self.run.segments_mut() {
             segment.comparisons_mut().remove(comparison);
         }
+        self.run.set_bound_timing_method(comparison, None);
 
         self.fix();
     }
@@ -835,6 +1104,10 @@ impl Editor {
             }
         }
 
+        let method = self.run.bound_timing_method(old);
+        self.run.set_bound_timing_method(old, None);
+        self.run.set_bound_timing_method(new, method);
+
         self.fix();
 
         Ok(())
@@ -938,6 +1211,17 @@ impl Editor {
         Ok(())
     }
 
+    /// Rebuilds the Personal Best split times and the Best Segment times purely
+    /// from the Attempt History and the Segment Histories, discarding the
+    /// values that are currently stored. This is useful for repairing splits
+    /// files where these values got corrupted by other tools.
+    pub fn rebuild_from_history(&mut self) {
+        self.run.rebuild_from_history();
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+    }
+
     /// Clears out the Attempt History and the Segment Histories of all the
     /// segments.
     pub fn clear_history(&mut self) {
@@ -945,6 +1229,57 @@ impl Editor {
         self.fix();
     }
 
+    /// Removes all the attempts that were started before the date provided,
+    /// along with their segment times. Attempts where it's not known when they
+    /// were started are kept. Best segments that were achieved in the removed
+    /// attempts are kept. The amount of attempts that got removed is returned.
+    pub fn remove_attempts_before(&mut self, date: DateTime) -> usize {
+        self.prune_attempts(|attempt| {
+            attempt
+                .started()
+                .or(attempt.ended())
+                .is_none_or(|started| started.time >= date)
+        })
+    }
+
+    /// Removes all but the most recent attempts, such that at most the amount
+    /// of attempts provided remain, along with their segment times. Best
+    /// segments that were achieved in the removed attempts are kept. The
+    /// amount of attempts that got removed is returned.
+    pub fn keep_last_attempts(&mut self, count: usize) -> usize {
+        let mut indices = self
+            .run
+            .attempt_history()
+            .iter()
+            .map(Attempt::index)
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        let first_kept = match indices.len().checked_sub(count) {
+            None | Some(0) => return 0,
+            Some(i) => indices.get(i).copied(),
+        };
+        self.prune_attempts(|attempt| first_kept.is_some_and(|first| attempt.index() >= first))
+    }
+
+    /// Removes all the attempts that didn't finish the run, along with their
+    /// segment times. Best segments that were achieved in the removed attempts
+    /// are kept. The amount of attempts that got removed is returned.
+    pub fn remove_unfinished_attempts(&mut self) -> usize {
+        self.prune_attempts(|attempt| {
+            let time = attempt.time();
+            time.real_time.is_some() || time.game_time.is_some()
+        })
+    }
+
+    fn prune_attempts(&mut self, f: impl FnMut(&Attempt) -> bool) -> usize {
+        let removed = self.run.retain_attempts(f);
+        if removed != 0 {
+            self.fix();
+            self.run.regenerate_comparisons();
+        }
+        removed
+    }
+
     /// Clears out the Attempt History, the Segment Histories, all the times,
     /// sets the Attempt Count to 0 and clears the speedrun.com run id
     /// association. All Custom Comparisons other than `Personal Best` are
@@ -954,6 +1289,113 @@ impl Editor {
         self.fix();
     }
 
+    /// Shifts all the split times of the selected timing method by the offset
+    /// provided. This affects the split times of all the comparisons, the
+    /// final times of the attempts and the times of the first segment, such as
+    /// its best segment time and segment history, as the time of the first
+    /// segment is where the shift happens. If any of these times would become
+    /// negative, nothing is changed and an error is returned.
+    pub fn shift_times(&mut self, offset: TimeSpan) -> Result<(), TimeOperationError> {
+        let method = self.selected_method;
+        let mut run = self.run.clone();
+
+        let shift = |time: &mut Option<TimeSpan>| {
+            if let Some(time) = time {
+                *time += offset;
+                if *time < TimeSpan::zero() {
+                    return Err(TimeOperationError::NegativeTime);
+                }
+            }
+            Ok(())
+        };
+
+        let mut shifted_attempts = Vec::new();
+        for (segment_index, segment) in run.segments_mut().iter_mut().enumerate() {
+            for (_, time) in segment.comparisons_mut().iter_mut() {
+                shift(&mut time[method])?;
+            }
+
+            if segment_index == 0 {
+                shift(&mut segment.best_segment_time_mut()[method])?;
+            }
+
+            for (attempt_index, time) in segment.segment_history_mut().iter_mut() {
+                // Imported best segments with a non-positive index only
+                // describe their own segment, so they are only shifted for the
+                // first segment. For all the actual attempts, the first
+                // segment that isn't skipped contains the shift.
+                if (*attempt_index <= 0 && segment_index > 0) || time[method].is_none() {
+                    continue;
+                }
+                if let Err(pos) = shifted_attempts.binary_search(attempt_index) {
+                    shifted_attempts.insert(pos, *attempt_index);
+                    shift(&mut time[method])?;
+                }
+            }
+        }
+
+        for attempt in run.attempt_history_mut() {
+            let mut time = attempt.time();
+            shift(&mut time[method])?;
+            attempt.set_time(time);
+        }
+
+        self.run = run;
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+
+        Ok(())
+    }
+
+    /// Scales all the times of the selected timing method by the factor
+    /// provided. This is useful for correcting times that were recorded with
+    /// an emulator running at the wrong speed. This affects the split times of
+    /// all the comparisons, the best segment times, the segment histories and
+    /// the final times of the attempts. The factor needs to be finite and
+    /// positive. If any of the times would become too large to be represented,
+    /// nothing is changed and an error is returned.
+    pub fn scale_times(&mut self, factor: f64) -> Result<(), TimeOperationError> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(TimeOperationError::InvalidFactor);
+        }
+
+        let method = self.selected_method;
+        let mut run = self.run.clone();
+
+        let scale = |time: &mut Option<TimeSpan>| {
+            if let Some(time) = time {
+                *time = Duration::checked_seconds_f64(time.total_seconds() * factor)
+                    .ok_or(TimeOperationError::TimeTooLarge)?
+                    .into();
+            }
+            Ok(())
+        };
+
+        for segment in run.segments_mut() {
+            for (_, time) in segment.comparisons_mut().iter_mut() {
+                scale(&mut time[method])?;
+            }
+            scale(&mut segment.best_segment_time_mut()[method])?;
+            for (_, time) in segment.segment_history_mut().iter_mut() {
+                scale(&mut time[method])?;
+            }
+        }
+
+        for attempt in run.attempt_history_mut() {
+            let mut time = attempt.time();
+            scale(&mut time[method])?;
+            attempt.set_time(time);
+        }
+
+        self.run = run;
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+
+        Ok(())
+    }
+
     /// Creates a Sum of Best Cleaner which allows you to interactively remove
     /// potential issues in the segment history that lead to an inaccurate Sum
     /// of Best. If you skip a split, whenever you will do the next split, the
@@ -963,6 +1405,216 @@ impl Editor {
     pub fn clean_sum_of_best(&mut self) -> SumOfBestCleaner<'_> {
         SumOfBestCleaner::new(&mut self.run)
     }
+
+    /// Binds the comparison to the [`TimingMethod`] provided, so it is always
+    /// evaluated against that timing method, regardless of the timing method
+    /// the layout shows. Specify `None` to remove the binding.
+    pub fn set_bound_timing_method(&mut self, comparison: &str, method: Option<TimingMethod>) {
+        self.run.set_bound_timing_method(comparison, method);
+        self.raise_run_edited();
+    }
+
+    /// Attaches the tag specified to the attempt with the index provided.
+    /// Returns `false` if there is no such attempt or the tag was already
+    /// attached. If the tag excludes attempts from the comparisons, the
+    /// comparisons are regenerated immediately.
+    pub fn add_attempt_tag(&mut self, attempt_index: i32, tag: &str) -> bool {
+        self.modify_attempt_tags(attempt_index, tag, |attempt| attempt.add_tag(tag))
+    }
+
+    /// Removes the tag specified from the attempt with the index provided.
+    /// Returns `false` if there is no such attempt or the tag wasn't attached.
+    /// If the tag excludes attempts from the comparisons, the comparisons are
+    /// regenerated immediately.
+    pub fn remove_attempt_tag(&mut self, attempt_index: i32, tag: &str) -> bool {
+        self.modify_attempt_tags(attempt_index, tag, |attempt| attempt.remove_tag(tag))
+    }
+
+    fn modify_attempt_tags(
+        &mut self,
+        attempt_index: i32,
+        tag: &str,
+        modify: impl FnOnce(&mut Attempt) -> bool,
+    ) -> bool {
+        let Some(attempt) = self
+            .run
+            .attempt_history_mut()
+            .iter_mut()
+            .find(|attempt| attempt.index() == attempt_index)
+        else {
+            return false;
+        };
+        if !modify(attempt) {
+            return false;
+        }
+        if self.run.excluded_attempt_tags().iter().any(|t| t == tag) {
+            self.run.regenerate_comparisons();
+        }
+        self.raise_run_edited();
+        true
+    }
+
+    /// Sets whether attempts that have the tag specified attached are
+    /// excluded from the comparisons. The comparisons are regenerated
+    /// immediately.
+    pub fn set_attempt_tag_excluded(&mut self, tag: &str, excluded: bool) {
+        self.run.set_attempt_tag_excluded(tag, excluded);
+        self.run.regenerate_comparisons();
+        self.raise_run_edited();
+    }
+
+    /// Sets how quickly older segment times stop mattering for the
+    /// [`AverageSegments`] comparison. The comparison is regenerated
+    /// immediately.
+    pub fn set_average_segments_comparison(&mut self, settings: AverageSegments) {
+        self.run.set_average_segments_comparison(settings);
+        self.run.regenerate_comparisons();
+        self.raise_run_edited();
+    }
+
+    /// Sets how many of the most recent segment times the
+    /// [`LastAttempts`] comparison considers
+    /// and how they are combined. The comparison is regenerated immediately.
+    pub fn set_last_attempts_comparison(&mut self, settings: LastAttempts) {
+        self.run.set_last_attempts_comparison(settings);
+        self.run.regenerate_comparisons();
+        self.raise_run_edited();
+    }
+
+    /// Sets whether the [`LastAttempts`] comparison is generated for the Run.
+    /// The comparisons are regenerated immediately.
+    pub fn set_last_attempts_comparison_enabled(&mut self, enabled: bool) {
+        self.run.set_last_attempts_comparison_enabled(enabled);
+        self.run.regenerate_comparisons();
+        self.raise_run_edited();
+    }
+
+    /// Evaluates the health of all the comparisons of the Run for the
+    /// currently selected timing method. The report suggests which
+    /// comparisons should be removed or regenerated. The suggestions can be
+    /// applied with [`apply_comparison_suggestion`](Self::apply_comparison_suggestion).
+    pub fn comparison_health_report(&self) -> comparison_health::Report {
+        comparison_health::calculate(&self.run, self.selected_method)
+    }
+
+    /// Applies the action that is suggested for the comparison by a
+    /// comparison health report. Nothing happens if there is no suggestion
+    /// for the comparison.
+    pub fn apply_comparison_suggestion(&mut self, comparison: &ComparisonHealth) {
+        match comparison.suggestion {
+            Some(Suggestion::Remove) => self.remove_comparison(&comparison.name),
+            Some(Suggestion::Regenerate) => {
+                self.run.regenerate_comparisons();
+                self.raise_run_edited();
+            }
+            None => {}
+        }
+    }
+
+    /// Applies all the actions suggested by a comparison health report.
+    pub fn apply_comparison_suggestions(&mut self, report: &comparison_health::Report) {
+        for comparison in report.suggestions() {
+            self.apply_comparison_suggestion(comparison);
+        }
+    }
+
+    /// Checks the Run for inconsistencies, such as negative segment times,
+    /// segment history elements that don't belong to any attempt, decreasing
+    /// split times and best segments that are slower than other known segment
+    /// times. Opening the Run Editor already repairs some of these
+    /// automatically. The issues found can be repaired with
+    /// [`repair_sanity_issue`](Self::repair_sanity_issue).
+    pub fn sanity_check(&self) -> sanity_check::Report {
+        sanity_check::check(&self.run)
+    }
+
+    /// Repairs an issue found by a sanity check.
+    pub fn repair_sanity_issue(&mut self, issue: &Issue) {
+        self.repair_issue(issue);
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+    }
+
+    /// Repairs all the issues found by a sanity check.
+    pub fn repair_sanity_issues(&mut self, report: &sanity_check::Report) {
+        for issue in &report.issues {
+            self.repair_issue(issue);
+        }
+        self.times_modified();
+        self.fix();
+        self.run.regenerate_comparisons();
+    }
+
+    fn repair_issue(&mut self, issue: &Issue) {
+        match *issue {
+            Issue::NegativeSegmentTime {
+                segment_index,
+                attempt_index,
+                method,
+            } => {
+                if let Some(time) = self
+                    .run
+                    .segments_mut()
+                    .get_mut(segment_index)
+                    .and_then(|s| s.segment_history_mut().get_mut(attempt_index))
+                {
+                    time[method] = None;
+                }
+            }
+            Issue::NegativeBestSegmentTime {
+                segment_index,
+                method,
+            } => {
+                if let Some(segment) = self.run.segments_mut().get_mut(segment_index) {
+                    segment.best_segment_time_mut()[method] = None;
+                }
+            }
+            Issue::UnattachedSegmentHistory { attempt_index } => {
+                for segment in self.run.segments_mut() {
+                    segment.segment_history_mut().remove(attempt_index);
+                }
+            }
+            Issue::NonMonotonicSplitTime {
+                ref comparison,
+                segment_index,
+                method,
+            } => {
+                if let Some(segment) = self.run.segments_mut().get_mut(segment_index) {
+                    segment.comparison_mut(comparison)[method] = None;
+                }
+            }
+            Issue::BestSegmentNotBest {
+                segment_index,
+                method,
+                fastest,
+            } => {
+                if let Some(segment) = self.run.segments_mut().get_mut(segment_index) {
+                    let best_segment = &mut segment.best_segment_time_mut()[method];
+                    if best_segment.is_none_or(|t| t > fastest) {
+                        *best_segment = Some(fastest);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the index of the first split time that is earlier than a split
+/// time of a previous segment.
+fn first_non_monotonic_split(
+    split_times: impl IntoIterator<Item = Option<TimeSpan>>,
+) -> Option<usize> {
+    let mut previous = TimeSpan::zero();
+    for (index, time) in split_times.into_iter().enumerate() {
+        if let Some(time) = time {
+            if time < previous {
+                return Some(index);
+            }
+            previous = time;
+        }
+    }
+    None
 }
 
 fn parse_positive(time: &str) -> Result<Option<TimeSpan>, ParseError> {
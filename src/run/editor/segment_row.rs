@@ -50,6 +50,19 @@ impl<T: Borrow<Editor>> SegmentRow<T> {
         let method = editor.selected_method;
         editor.run.segment(self.index).comparison(comparison)[method]
     }
+
+    /// Accesses the segment time the attempt with the index provided has in
+    /// the segment's history for the active timing method. This is `None` if
+    /// the attempt either skipped the segment or didn't reach it.
+    pub fn segment_history_time(&self, attempt_index: i32) -> Option<TimeSpan> {
+        let editor: &Editor = self.editor.borrow();
+        let method = editor.selected_method;
+        editor
+            .run
+            .segment(self.index)
+            .segment_history()
+            .get(attempt_index)?[method]
+    }
 }
 
 impl<'a> SegmentRow<&'a Editor> {
@@ -138,6 +151,64 @@ impl<'a> SegmentRow<&'a mut Editor> {
         Ok(())
     }
 
+    /// Sets the segment time the attempt with the index provided has in the
+    /// segment's history for the active timing method. The final time of the
+    /// attempt is adjusted by the difference, if the attempt finished. The
+    /// best segment time is recalculated from the segment's history afterwards.
+    /// Setting the time fails if the attempt didn't reach the segment.
+    pub fn set_segment_history_time(
+        &mut self,
+        attempt_index: i32,
+        time: Option<TimeSpan>,
+    ) -> Result<(), ParseError> {
+        let method = self.editor.selected_method;
+        let run = &mut self.editor.run;
+        let segment = run.segment_mut(self.index);
+
+        let element = segment
+            .segment_history_mut()
+            .get_mut(attempt_index)
+            .ok_or(ParseError::AttemptNotFound)?;
+        let previous = element[method];
+        element[method] = time;
+
+        segment.best_segment_time_mut()[method] = segment
+            .segment_history()
+            .iter()
+            .filter_map(|(_, time)| time[method])
+            .min();
+
+        if let Some(attempt) = run
+            .attempt_history_mut()
+            .iter_mut()
+            .find(|a| a.index() == attempt_index)
+        {
+            let mut attempt_time = attempt.time();
+            if let Some(final_time) = &mut attempt_time[method]
+                && let (Some(previous), Some(time)) = (previous, time)
+            {
+                *final_time += time - previous;
+                attempt.set_time(attempt_time);
+            }
+        }
+
+        self.editor.times_modified();
+        self.editor.fix();
+        self.editor.run.regenerate_comparisons();
+        Ok(())
+    }
+
+    /// Parses a segment time from a string and sets it as the segment time the
+    /// attempt with the index provided has in the segment's history for the
+    /// active timing method.
+    pub fn parse_and_set_segment_history_time(
+        &mut self,
+        attempt_index: i32,
+        time: &str,
+    ) -> Result<(), ParseError> {
+        self.set_segment_history_time(attempt_index, parse_positive(time)?)
+    }
+
     /// Sets the provided comparison's time of the segment for the active timing method.
     pub fn set_comparison_time(&mut self, comparison: &str, time: Option<TimeSpan>) {
         let method = self.editor.selected_method;
@@ -56,6 +56,9 @@ pub struct Buttons {
     /// any one of the selected segments is the last segment, then they can't be
     /// moved.
     pub can_move_down: bool,
+    /// Describes whether the active segment can be merged with the segment
+    /// below it. The last segment can't be merged.
+    pub can_merge_with_next: bool,
 }
 
 /// Describes the current state of a segment.
@@ -139,6 +142,7 @@ impl Editor {
             can_remove: self.can_remove_segments(),
             can_move_up: self.can_move_segments_up(),
             can_move_down: self.can_move_segments_down(),
+            can_merge_with_next: self.can_merge_segment_with_next(),
         };
         let mut segments = Vec::with_capacity(self.run.len());
 
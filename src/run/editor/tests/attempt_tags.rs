@@ -0,0 +1,87 @@
+use super::Editor;
+use crate::{
+    TimingMethod,
+    analysis::{improvement_trend, segment_correlation},
+    comparison::{average_segments, latest_run, median_segments},
+    util::tests_helper::{create_timer, run_with_splits, span},
+};
+
+#[test]
+fn excluded_attempts_are_ignored_by_the_comparisons() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[1.0, 2.0]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    let latest = |editor: &Editor| {
+        editor.run().segment(1).comparison(latest_run::NAME)[TimingMethod::GameTime]
+    };
+    assert_eq!(latest(&editor), Some(span(20.0)));
+
+    assert!(editor.add_attempt_tag(2, "Route Test"));
+    assert!(!editor.add_attempt_tag(2, "Route Test"));
+    assert!(!editor.add_attempt_tag(3, "Route Test"));
+    assert_eq!(latest(&editor), Some(span(20.0)));
+
+    editor.set_attempt_tag_excluded("Route Test", true);
+    assert!(editor.run().is_attempt_excluded(2));
+    assert!(!editor.run().is_attempt_excluded(1));
+    assert_eq!(latest(&editor), Some(span(2.0)));
+    assert_eq!(
+        editor
+            .run()
+            .segment(1)
+            .segment_history()
+            .get(2)
+            .unwrap()
+            .game_time,
+        Some(span(10.0))
+    );
+
+    assert!(editor.remove_attempt_tag(2, "Route Test"));
+    assert_eq!(latest(&editor), Some(span(20.0)));
+}
+
+#[test]
+fn excluded_attempts_are_ignored_by_the_statistics() {
+    let mut reference = create_timer(&["A", "B"]);
+    run_with_splits(&mut reference, &[1.0, 3.0]);
+    run_with_splits(&mut reference, &[2.0, 5.0]);
+    let mut timer = reference.clone();
+    run_with_splits(&mut timer, &[3.0, 4.0]);
+    run_with_splits(&mut timer, &[10.0, 11.0]);
+
+    let reference = reference.into_run(true);
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    let trend = |editor: &Editor| {
+        improvement_trend::calculate(editor.run(), TimingMethod::GameTime, &Default::default())
+            .points
+            .len()
+    };
+    let correlation = |editor: &Editor| {
+        segment_correlation::calculate(editor.run(), TimingMethod::GameTime).get(0, 1)
+    };
+    assert_eq!(trend(&editor), 4);
+    assert!(correlation(&editor).is_some());
+
+    editor.add_attempt_tag(3, "Route Test");
+    editor.add_attempt_tag(4, "Route Test");
+    editor.set_attempt_tag_excluded("Route Test", true);
+
+    for name in [average_segments::NAME, median_segments::NAME] {
+        for (segment, expected) in editor.run().segments().iter().zip(reference.segments()) {
+            assert_eq!(segment.comparison(name), expected.comparison(name));
+        }
+    }
+    assert_eq!(
+        editor
+            .run()
+            .segments_without_excluded_attempts()
+            .iter()
+            .map(|s| s.segment_history().iter().count())
+            .collect::<Vec<_>>(),
+        [2, 2],
+    );
+    assert_eq!(trend(&editor), 2);
+    assert_eq!(correlation(&editor), None);
+}
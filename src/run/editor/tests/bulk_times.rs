@@ -0,0 +1,178 @@
+use super::super::{Editor, TimeOperationError};
+use crate::{
+    Run, TimingMethod,
+    comparison::best_segments,
+    util::tests_helper::{create_timer, run_with_splits, run_with_splits_opt, span},
+};
+
+fn run() -> Run {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 25.0, 40.0]);
+    run_with_splits_opt(&mut timer, &[None, Some(20.0), Some(45.0)]);
+    timer.into_run(true)
+}
+
+fn editor() -> Editor {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+#[test]
+fn shifting_moves_all_split_times() {
+    let mut editor = editor();
+    editor.shift_times(span(2.0)).unwrap();
+    let run = editor.close();
+
+    let splits = run
+        .segments()
+        .iter()
+        .map(|s| s.personal_best_split_time().game_time)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        splits,
+        [Some(span(12.0)), Some(span(27.0)), Some(span(42.0))]
+    );
+
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(12.0))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(15.0))
+    );
+
+    // The second attempt skipped the first segment, so its shift happens in
+    // the second segment.
+    assert_eq!(
+        run.segment(0).segment_history().get(2),
+        Some(Default::default())
+    );
+    assert_eq!(
+        run.segment(1).segment_history().get(2).unwrap().game_time,
+        Some(span(22.0))
+    );
+    assert_eq!(
+        run.segment(2).segment_history().get(2).unwrap().game_time,
+        Some(span(25.0))
+    );
+
+    assert_eq!(run.attempt_history()[0].time().game_time, Some(span(42.0)));
+    assert_eq!(run.attempt_history()[1].time().game_time, Some(span(47.0)));
+    // The skipped first segment of the second attempt combined with the
+    // second segment is faster than the shifted best segments.
+    assert_eq!(
+        run.segment(2).comparison(best_segments::NAME).game_time,
+        Some(span(37.0)),
+    );
+}
+
+#[test]
+fn shifting_into_negative_times_changes_nothing() {
+    let mut editor = editor();
+    assert!(matches!(
+        editor.shift_times(span(-11.0)),
+        Err(TimeOperationError::NegativeTime)
+    ));
+    let run = editor.close();
+    assert_eq!(
+        run.segment(0).personal_best_split_time().game_time,
+        Some(span(10.0))
+    );
+    assert_eq!(run.attempt_history()[0].time().game_time, Some(span(40.0)));
+}
+
+#[test]
+fn scaling_scales_all_times() {
+    let original = run();
+    let mut editor = Editor::new(original.clone()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor.scale_times(0.5).unwrap();
+    let scaled = editor.close();
+
+    assert_eq!(
+        scaled.segment(2).personal_best_split_time().game_time,
+        Some(span(20.0))
+    );
+    assert_eq!(
+        scaled.segment(1).best_segment_time().game_time,
+        Some(span(7.5))
+    );
+    assert_eq!(
+        scaled
+            .segment(2)
+            .segment_history()
+            .get(2)
+            .unwrap()
+            .game_time,
+        Some(span(12.5))
+    );
+    assert_eq!(
+        scaled.attempt_history()[1].time().game_time,
+        Some(span(22.5))
+    );
+    assert_eq!(
+        scaled.segment(2).personal_best_split_time().real_time,
+        original.segment(2).personal_best_split_time().real_time
+    );
+}
+
+#[test]
+fn scaling_requires_a_positive_factor() {
+    let mut editor = editor();
+    assert!(editor.scale_times(0.0).is_err());
+    assert!(editor.scale_times(f64::NAN).is_err());
+    assert!(editor.scale_times(-1.0).is_err());
+}
+
+#[test]
+fn scaling_rejects_times_that_are_too_large() {
+    let original = run();
+    let mut editor = Editor::new(original.clone()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    assert!(matches!(
+        editor.scale_times(1e300),
+        Err(TimeOperationError::TimeTooLarge),
+    ));
+    assert_eq!(editor.close(), original);
+}
+
+#[test]
+fn merging_combines_segment_histories() {
+    let mut editor = editor();
+    editor.merge_segment_with_next();
+    let run = editor.close();
+
+    assert_eq!(run.len(), 2);
+    assert_eq!(run.segment(0).name(), "B");
+    assert_eq!(
+        run.segment(0).personal_best_split_time().game_time,
+        Some(span(25.0))
+    );
+    assert_eq!(
+        run.segment(0).segment_history().get(1).unwrap().game_time,
+        Some(span(25.0))
+    );
+    assert_eq!(
+        run.segment(0).segment_history().get(2).unwrap().game_time,
+        Some(span(20.0))
+    );
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(20.0))
+    );
+    assert_eq!(
+        run.segment(1).comparison(best_segments::NAME).game_time,
+        Some(span(35.0)),
+    );
+}
+
+#[test]
+fn last_segment_cant_be_merged() {
+    let mut editor = editor();
+    editor.select_only(2);
+    assert!(!editor.can_merge_segment_with_next());
+    editor.merge_segment_with_next();
+    assert_eq!(editor.close().len(), 3);
+}
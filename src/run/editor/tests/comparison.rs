@@ -1,7 +1,11 @@
 use crate::{
+    Run, Segment, TimeSpan,
+    analysis::comparison_health::Suggestion,
     comparison::{best_segments, personal_best},
-    run::{AddComparisonError, CopyComparisonError, Editor, RenameError},
-    Run, Segment,
+    run::{
+        AddComparisonError, CopyComparisonError, Editor, RenameError,
+        editor::ManualComparisonError, parser, saver,
+    },
 };
 
 #[test]
@@ -156,3 +160,90 @@ fn reordering_works() {
         ["D", "A", "C", "B"]
     );
 }
+
+#[test]
+fn applying_comparison_health_suggestions_works() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("s"));
+    let mut editor = Editor::new(run).unwrap();
+    editor.add_comparison("Empty").unwrap();
+
+    let report = editor.comparison_health_report();
+    assert!(
+        report
+            .suggestions()
+            .any(|c| c.name == "Empty" && c.suggestion == Some(Suggestion::Remove))
+    );
+
+    editor.apply_comparison_suggestions(&report);
+    assert!(editor.run().comparisons().all(|c| c != "Empty"));
+    assert_eq!(editor.comparison_health_report().suggestions().count(), 0);
+}
+
+#[test]
+fn adding_a_manual_comparison_works() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.push_segment(Segment::new("B"));
+    run.push_segment(Segment::new("C"));
+    let mut editor = Editor::new(run).unwrap();
+
+    editor
+        .parse_and_add_manual_comparison("Target", ["1:00", "", "3:00"])
+        .unwrap();
+
+    editor.select_only(1);
+    assert_eq!(editor.active_segment().comparison_time("Target"), None);
+    editor.select_only(2);
+    assert_eq!(
+        editor.active_segment().comparison_time("Target"),
+        Some(TimeSpan::from_seconds(180.0)),
+    );
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(editor.run(), &mut buf).unwrap();
+    let run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(
+        run.segment(0).comparison("Target").real_time,
+        Some(TimeSpan::from_seconds(60.0)),
+    );
+}
+
+#[test]
+fn manual_comparisons_need_to_be_monotonic() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.push_segment(Segment::new("B"));
+    let mut editor = Editor::new(run).unwrap();
+
+    assert!(matches!(
+        editor.parse_and_add_manual_comparison("Target", ["2:00", "1:00"]),
+        Err(ManualComparisonError::NonMonotonic { index: 1 }),
+    ));
+    assert!(matches!(
+        editor.parse_and_add_manual_comparison("Target", ["2:00"]),
+        Err(ManualComparisonError::SegmentCountMismatch),
+    ));
+    assert!(!editor.run().comparisons().any(|c| c == "Target"));
+
+    editor
+        .parse_and_add_manual_comparison("Target", ["1:00", "2:00"])
+        .unwrap();
+
+    // Editing the comparison afterwards may temporarily pass through split
+    // times that aren't monotonic.
+    editor.select_only(0);
+    editor
+        .active_segment()
+        .parse_and_set_comparison_time("Target", "3:00")
+        .unwrap();
+    editor.select_only(1);
+    editor
+        .active_segment()
+        .parse_and_set_comparison_time("Target", "4:00")
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().comparison_time("Target"),
+        Some(TimeSpan::from_seconds(240.0)),
+    );
+}
@@ -0,0 +1,99 @@
+use super::super::{Editor, ImportSegmentsError};
+use crate::{
+    Run, TimingMethod,
+    settings::Image,
+    util::tests_helper::{create_timer, run_with_splits, run_with_splits_opt, span},
+};
+
+fn source() -> Run {
+    let mut timer = create_timer(&["X", "Y", "Z"]);
+    run_with_splits(&mut timer, &[5.0, 12.0, 20.0]);
+    run_with_splits_opt(&mut timer, &[None, Some(10.0), Some(21.0)]);
+    let mut run = timer.into_run(true);
+    run.segment_mut(1)
+        .set_icon(Image::new([1, 2, 3].as_slice().into(), Image::ICON));
+    run
+}
+
+fn editor() -> Editor {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+#[test]
+fn imports_segments_below_the_active_segment() {
+    let source = source();
+    let mut editor = editor();
+    editor.import_segments(&source, 1..3).unwrap();
+    let run = editor.close();
+
+    let names = run.segments().iter().map(|s| s.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "Y", "Z", "B"]);
+    assert_eq!(run.segment(1).icon(), source.segment(1).icon());
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(7.0))
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(8.0))
+    );
+}
+
+#[test]
+fn remaps_attempt_indices() {
+    let source = source();
+    let mut editor = editor();
+    editor.import_segments(&source, 0..2).unwrap();
+    let run = editor.close();
+
+    let imported = run.segment(1).segment_history();
+    assert!(imported.get(1).unwrap().game_time.is_none());
+    assert!(
+        imported
+            .iter()
+            .filter(|&&(index, _)| index > 0)
+            .all(|(_, time)| time.game_time.is_none())
+    );
+
+    // The second attempt of the source skipped its first segment, which needs
+    // to stay combined with the following segment.
+    let skipped_index = run
+        .segment(2)
+        .segment_history()
+        .iter()
+        .find(|(_, time)| time.game_time == Some(span(10.0)))
+        .unwrap()
+        .0;
+    assert!(skipped_index <= 0);
+    assert_eq!(imported.get(skipped_index).unwrap().game_time, None);
+    assert_eq!(run.segment(0).segment_history().get(skipped_index), None);
+}
+
+#[test]
+fn selects_the_imported_segments() {
+    let mut editor = editor();
+    editor.import_segments(&source(), 0..2).unwrap();
+    assert_eq!(editor.active_segment().name(), "X");
+    editor.remove_segments();
+    let run = editor.close();
+    assert_eq!(run.len(), 2);
+}
+
+#[test]
+fn rejects_invalid_ranges() {
+    let source = source();
+    let mut editor = editor();
+    assert!(matches!(
+        editor.import_segments(&source, 1..1),
+        Err(ImportSegmentsError::InvalidRange)
+    ));
+    assert!(matches!(
+        editor.import_segments(&source, 2..4),
+        Err(ImportSegmentsError::InvalidRange)
+    ));
+    assert_eq!(editor.close().len(), 2);
+}
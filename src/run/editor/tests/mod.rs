@@ -4,10 +4,17 @@ use crate::{
     Run, Segment,
 };
 
+mod attempt_tags;
+mod bulk_times;
 mod comparison;
 mod custom_variables;
 mod dissociate_run;
+mod import_segments;
 mod mark_as_modified;
+mod move_segment;
+mod prune_attempts;
+mod sanity_check;
+mod segment_history;
 
 #[test]
 fn new_best_segment() {
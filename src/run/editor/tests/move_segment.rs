@@ -0,0 +1,106 @@
+use super::super::Editor;
+use crate::{
+    Run, TimeSpan, TimingMethod,
+    util::tests_helper::{create_timer, run_with_splits, run_with_splits_opt, span},
+};
+
+fn run() -> Run {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 25.0, 40.0]);
+    run_with_splits_opt(&mut timer, &[None, Some(22.0), Some(45.0)]);
+    timer.into_run(true)
+}
+
+fn editor() -> Editor {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+fn names(run: &Run) -> Vec<&str> {
+    run.segments().iter().map(|s| s.name()).collect()
+}
+
+#[test]
+fn moves_segment_up_and_remaps_times() {
+    let mut editor = editor();
+    editor.move_segment(2, 0);
+    assert_eq!(editor.active_segment().name(), "C");
+    let run = editor.close();
+
+    assert_eq!(names(&run), ["C", "A", "B"]);
+    let splits = run
+        .segments()
+        .iter()
+        .map(|s| s.personal_best_split_time().game_time)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        splits,
+        [Some(span(15.0)), Some(span(25.0)), Some(span(40.0))]
+    );
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(15.0))
+    );
+    assert_eq!(
+        run.segment(0).segment_history().get(1).unwrap().game_time,
+        Some(span(15.0))
+    );
+}
+
+#[test]
+fn moves_segment_down() {
+    let mut editor = editor();
+    editor.move_segment(0, 2);
+    let run = editor.close();
+
+    assert_eq!(names(&run), ["B", "C", "A"]);
+    assert_eq!(
+        run.segment(2).segment_history().get(1).unwrap().game_time,
+        Some(span(10.0))
+    );
+    assert_eq!(
+        run.segment(2).personal_best_split_time().game_time,
+        Some(span(40.0))
+    );
+}
+
+#[test]
+fn removes_history_that_cant_be_preserved() {
+    let mut editor = editor();
+    editor.move_segment(0, 1);
+    let run = editor.close();
+
+    // The second attempt skipped the first segment, so its combined time
+    // can't be split up anymore.
+    assert_eq!(names(&run), ["B", "A", "C"]);
+    assert!(run.segment(0).segment_history().get(2).is_none());
+    assert!(run.segment(1).segment_history().get(2).is_none());
+    assert_eq!(
+        run.segment(2).segment_history().get(2).unwrap().game_time,
+        Some(span(23.0))
+    );
+}
+
+#[test]
+fn moving_back_restores_the_run() {
+    let mut editor = editor();
+    editor.move_segment(2, 0);
+    editor.move_segment(0, 2);
+    let run = editor.close();
+
+    assert_eq!(run_times(&run), run_times(&self::run()));
+}
+
+fn run_times(run: &Run) -> Vec<(String, Option<TimeSpan>, Option<TimeSpan>)> {
+    run.segments()
+        .iter()
+        .map(|s| {
+            (
+                s.name().to_owned(),
+                s.personal_best_split_time().game_time,
+                s.best_segment_time().game_time,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+use super::super::Editor;
+use crate::{
+    AtomicDateTime, DateTime, Run, Time, TimingMethod,
+    comparison::best_segments,
+    util::tests_helper::{create_run, create_timer, run_with_splits_opt, span},
+};
+
+fn run() -> Run {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits_opt(&mut timer, &[Some(10.0), Some(25.0)]);
+    run_with_splits_opt(&mut timer, &[Some(8.0)]);
+    run_with_splits_opt(&mut timer, &[Some(9.0), Some(20.0)]);
+    run_with_splits_opt(&mut timer, &[Some(11.0)]);
+    timer.into_run(true)
+}
+
+fn editor() -> Editor {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+fn attempt_indices(run: &Run) -> Vec<i32> {
+    run.attempt_history().iter().map(|a| a.index()).collect()
+}
+
+#[test]
+fn removes_unfinished_attempts() {
+    let mut editor = editor();
+    assert_eq!(editor.remove_unfinished_attempts(), 2);
+    let run = editor.close();
+
+    assert_eq!(attempt_indices(&run), [1, 3]);
+    for segment in run.segments() {
+        assert!(segment.segment_history().get(2).is_none());
+        assert!(segment.segment_history().get(4).is_none());
+    }
+
+    // The best segment of the removed second attempt is kept.
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(8.0))
+    );
+    assert!(
+        run.segment(0)
+            .segment_history()
+            .iter()
+            .any(|&(index, time)| index <= 0 && time.game_time == Some(span(8.0)))
+    );
+    assert_eq!(
+        run.segment(1).comparison(best_segments::NAME).game_time,
+        Some(span(19.0))
+    );
+}
+
+#[test]
+fn keeps_the_last_attempts() {
+    let mut editor = editor();
+    assert_eq!(editor.keep_last_attempts(10), 0);
+    assert_eq!(editor.keep_last_attempts(2), 2);
+    let run = editor.close();
+
+    assert_eq!(attempt_indices(&run), [3, 4]);
+    assert!(run.segment(0).segment_history().get(1).is_none());
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(8.0))
+    );
+}
+
+#[test]
+fn keeping_no_attempts_removes_all_of_them() {
+    let mut editor = editor();
+    assert_eq!(editor.keep_last_attempts(0), 4);
+    let run = editor.close();
+
+    assert!(run.attempt_history().is_empty());
+    assert!(
+        run.segments()
+            .iter()
+            .all(|s| s.segment_history().iter_actual_runs().next().is_none())
+    );
+}
+
+#[test]
+fn removes_attempts_before_a_date() {
+    let date = |secs| AtomicDateTime::new(DateTime::from_unix_timestamp(secs).unwrap(), true);
+
+    let mut run = create_run(&["A"]);
+    let time = Time::new().with_real_time(Some(span(5.0)));
+    run.add_attempt_with_index(time, 1, Some(date(1000)), Some(date(1010)), None);
+    run.add_attempt_with_index(time, 2, Some(date(2000)), Some(date(2010)), None);
+    run.add_attempt_with_index(time, 3, None, None, None);
+    for index in 1..=3 {
+        run.segment_mut(0).segment_history_mut().insert(index, time);
+    }
+    run.segment_mut(0).set_personal_best_split_time(time);
+
+    let mut editor = Editor::new(run).unwrap();
+    assert_eq!(editor.remove_attempts_before(date(1500).time), 1);
+    let run = editor.close();
+
+    assert_eq!(attempt_indices(&run), [2, 3]);
+    assert!(run.segment(0).segment_history().get(1).is_none());
+}
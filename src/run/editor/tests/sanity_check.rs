@@ -0,0 +1,32 @@
+use super::super::Editor;
+use crate::{
+    Time,
+    analysis::sanity_check::Issue,
+    util::tests_helper::{create_timer, run_with_splits, span},
+};
+
+#[test]
+fn repairs_unattached_segment_history() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[12.0, 21.0]);
+    let mut run = timer.into_run(true);
+    run.segment_mut(1)
+        .segment_history_mut()
+        .insert(3, Time::new().with_game_time(Some(span(5.0))));
+    run.add_attempt_with_index(Time::new(), 4, None, None, None);
+
+    let mut editor = Editor::new(run).unwrap();
+    let report = editor.sanity_check();
+    assert_eq!(
+        report.issues,
+        [Issue::UnattachedSegmentHistory { attempt_index: 3 }]
+    );
+
+    editor.repair_sanity_issues(&report);
+    assert!(editor.sanity_check().is_empty());
+
+    let run = editor.close();
+    assert!(run.segment(1).segment_history().get(3).is_none());
+    assert!(run.segment(1).segment_history().get(2).is_some());
+}
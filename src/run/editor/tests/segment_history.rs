@@ -0,0 +1,97 @@
+use super::super::{Editor, ParseError};
+use crate::{
+    Run, TimingMethod,
+    comparison::average_segments,
+    util::tests_helper::{create_timer, run_with_splits, span},
+};
+
+fn run() -> Run {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[12.0, 24.0]);
+    run_with_splits(&mut timer, &[11.0]);
+    timer.into_run(true)
+}
+
+fn editor() -> Editor {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+    editor
+}
+
+#[test]
+fn accesses_segment_history_times() {
+    let mut editor = editor();
+    editor.select_only(1);
+    let row = editor.active_segment();
+    assert_eq!(row.segment_history_time(1), Some(span(10.0)));
+    assert_eq!(row.segment_history_time(2), Some(span(12.0)));
+    assert_eq!(row.segment_history_time(3), None);
+}
+
+#[test]
+fn fixes_a_single_segment_time() {
+    let mut editor = editor();
+    editor.select_only(1);
+    let average = editor
+        .active_segment()
+        .comparison_time(average_segments::NAME);
+    editor
+        .active_segment()
+        .parse_and_set_segment_history_time(2, "11")
+        .unwrap();
+    let run = editor.close();
+
+    assert_eq!(
+        run.segment(1).segment_history().get(2).unwrap().game_time,
+        Some(span(11.0))
+    );
+    assert_eq!(run.attempt_history()[1].time().game_time, Some(span(23.0)));
+    assert!(run.segment(1).comparison(average_segments::NAME).game_time < average);
+}
+
+#[test]
+fn faster_segment_time_becomes_best_segment() {
+    let mut editor = editor();
+    editor
+        .active_segment()
+        .set_segment_history_time(3, Some(span(9.0)))
+        .unwrap();
+    assert_eq!(editor.active_segment().best_segment_time(), Some(span(9.0)));
+    let run = editor.close();
+    assert_eq!(run.attempt_history()[2].time().game_time, None);
+}
+
+#[test]
+fn attempts_that_didnt_reach_the_segment_cant_be_edited() {
+    let mut editor = editor();
+    editor.select_only(1);
+    assert!(matches!(
+        editor
+            .active_segment()
+            .parse_and_set_segment_history_time(3, "5"),
+        Err(ParseError::AttemptNotFound)
+    ));
+}
+
+#[test]
+fn slower_segment_time_recalculates_the_best_segment() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[12.0, 24.0]);
+    run_with_splits(&mut timer, &[10.0]);
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    editor.select_timing_method(TimingMethod::GameTime);
+
+    assert_eq!(
+        editor.active_segment().best_segment_time(),
+        Some(span(10.0))
+    );
+    editor
+        .active_segment()
+        .set_segment_history_time(2, Some(span(13.0)))
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().best_segment_time(),
+        Some(span(12.0))
+    );
+}
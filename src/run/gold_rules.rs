@@ -0,0 +1,60 @@
+use crate::{Time, TimeSpan, TimingMethod};
+
+/// The `GoldRules` of a [`Run`](crate::Run) determine whether a segment time
+/// of a finished or reset attempt qualifies as a new best segment (gold). By
+/// default every segment time that is faster than the current best segment
+/// qualifies. The rules allow rejecting segment times that are likely to be
+/// inaccurate, so the best segments stay trustworthy. Rejected segment times
+/// are removed from the segment history. The rules don't apply to attempts
+/// that are a new Personal Best, as the best segments always need to be
+/// consistent with the Personal Best.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GoldRules {
+    /// Game time segment times only qualify if the game time was paused for
+    /// at most this amount of time during the segment. The time the game time
+    /// was paused for is the difference between the real time and the game
+    /// time of the segment.
+    pub max_game_time_pause: Option<TimeSpan>,
+    /// Segment times of segments that were split again after undoing their
+    /// split only qualify if they are at least this long. This rejects golds
+    /// caused by accidentally splitting twice in a row and undoing the wrong
+    /// split.
+    pub min_segment_time_after_undo: Option<TimeSpan>,
+}
+
+impl GoldRules {
+    /// Returns `true` if none of the rules are active, which means that every
+    /// faster segment time qualifies as a new best segment.
+    pub const fn is_empty(&self) -> bool {
+        self.max_game_time_pause.is_none() && self.min_segment_time_after_undo.is_none()
+    }
+
+    /// Checks whether the segment time qualifies as a new best segment for the
+    /// timing method specified. The segment time needs to contain both the
+    /// real time and the game time, if available, so the time the game time
+    /// was paused for can be determined. Whether the segment was split again
+    /// after undoing its split needs to be provided as well.
+    pub fn qualifies(&self, segment_time: Time, method: TimingMethod, was_undone: bool) -> bool {
+        let Some(time) = segment_time[method] else {
+            return false;
+        };
+
+        if was_undone
+            && self
+                .min_segment_time_after_undo
+                .is_some_and(|min| time < min)
+        {
+            return false;
+        }
+
+        if method == TimingMethod::GameTime
+            && let Some(max) = self.max_game_time_pause
+            && let Some(real_time) = segment_time.real_time
+            && real_time - time > max
+        {
+            return false;
+        }
+
+        true
+    }
+}
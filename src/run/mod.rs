@@ -18,21 +18,26 @@ mod attempt;
 
 #[cfg(feature = "auto-splitting")]
 mod auto_splitter_settings;
+mod bundle;
 mod comparisons;
 pub mod editor;
+mod gold_rules;
 mod linked_layout;
 pub mod parser;
 mod run_metadata;
 pub mod saver;
 mod segment;
 mod segment_history;
+pub mod template;
 
 #[cfg(test)]
 mod tests;
 
 pub use attempt::Attempt;
+pub use bundle::{RunBundle, SwitchError};
 pub use comparisons::Comparisons;
 pub use editor::{Editor, RenameError};
+pub use gold_rules::GoldRules;
 pub use linked_layout::LinkedLayout;
 pub use run_metadata::{CustomVariable, RunMetadata};
 pub use segment::Segment;
@@ -42,7 +47,14 @@ pub use segment_history::SegmentHistory;
 use crate::run::auto_splitter_settings::AutoSplitterSettings;
 use crate::{
     AtomicDateTime, Time, TimeSpan, TimingMethod,
-    comparison::{ComparisonGenerator, RACE_COMPARISON_PREFIX, default_generators, personal_best},
+    comparison::{
+        ComparisonGenerator, RACE_COMPARISON_PREFIX,
+        average_segments::{self, AverageSegments},
+        default_generators,
+        last_attempts::{self, LastAttempts},
+        personal_best,
+        registry::Registry,
+    },
     platform::prelude::*,
     settings::Image,
     util::{PopulateString, caseless::matches_ascii_key},
@@ -51,6 +63,11 @@ use alloc::borrow::Cow;
 use core::{cmp::max, fmt};
 use hashbrown::HashSet;
 
+/// The smallest frame rate in frames per second a [`Run`] can be counted in.
+pub const MIN_FRAME_RATE: f64 = 1.0;
+/// The largest frame rate in frames per second a [`Run`] can be counted in.
+pub const MAX_FRAME_RATE: f64 = 1000.0;
+
 /// A Run stores the split times for a specific game and category of a runner.
 ///
 /// # Examples
@@ -83,6 +100,12 @@ pub struct Run {
     #[cfg(feature = "auto-splitting")]
     parsed_auto_splitter_settings: Option<AutoSplitterSettings>,
     linked_layout: Option<LinkedLayout>,
+    gold_rules: GoldRules,
+    average_segments: AverageSegments,
+    last_attempts: LastAttempts,
+    timing_method_bindings: Vec<(String, TimingMethod)>,
+    excluded_attempt_tags: Vec<String>,
+    frame_rate: Option<f64>,
 }
 
 #[derive(Clone, Debug)]
@@ -138,6 +161,12 @@ impl Run {
             #[cfg(feature = "auto-splitting")]
             parsed_auto_splitter_settings: None,
             linked_layout: None,
+            gold_rules: GoldRules::default(),
+            average_segments: AverageSegments::default(),
+            last_attempts: LastAttempts::default(),
+            timing_method_bindings: Vec::new(),
+            excluded_attempt_tags: Vec::new(),
+            frame_rate: None,
         }
     }
 
@@ -275,6 +304,13 @@ impl Run {
         &self.attempt_history
     }
 
+    /// Grants mutable access to the Attempt History. This allows modifying
+    /// the information stored for each attempt.
+    #[inline]
+    pub fn attempt_history_mut(&mut self) -> &mut [Attempt] {
+        &mut self.attempt_history
+    }
+
     /// Accesses the custom comparisons that are stored in this Run. This
     /// includes `Personal Best` but excludes all the other Comparison
     /// Generators.
@@ -318,6 +354,16 @@ impl Run {
         &mut self.comparison_generators.0
     }
 
+    /// Replaces all the [`Unknown`](crate::comparison::registry::Unknown)
+    /// Comparison Generators with the Comparison Generators registered for
+    /// their names in the registry provided. Comparison Generators of the
+    /// application that were stored in the splits file are restored this way
+    /// after parsing it. The comparisons are regenerated afterwards.
+    pub fn resolve_comparison_generators(&mut self, registry: &Registry) {
+        registry.resolve(&mut self.comparison_generators.0);
+        self.regenerate_comparisons();
+    }
+
     /// Accesses the Auto Splitter Settings that are encoded as XML.
     #[inline]
     pub fn auto_splitter_settings(&self) -> &str {
@@ -413,6 +459,209 @@ impl Run {
         self.linked_layout = linked_layout;
     }
 
+    /// Accesses the [`GoldRules`] of this `Run`. They determine whether a
+    /// segment time qualifies as a new best segment.
+    #[inline]
+    pub const fn gold_rules(&self) -> &GoldRules {
+        &self.gold_rules
+    }
+
+    /// Sets the [`GoldRules`] of this `Run`. They determine whether a segment
+    /// time qualifies as a new best segment.
+    #[inline]
+    pub const fn set_gold_rules(&mut self, gold_rules: GoldRules) {
+        self.gold_rules = gold_rules;
+    }
+
+    /// Returns the frame rate in frames per second the times of this `Run` are
+    /// counted in. If a frame rate is set, the times are stored on frame
+    /// boundaries and can be shown as frame counts.
+    #[inline]
+    pub const fn frame_rate(&self) -> Option<f64> {
+        self.frame_rate
+    }
+
+    /// Sets the frame rate in frames per second the times of this `Run` are
+    /// counted in. Frame rates outside of [`MIN_FRAME_RATE`] and
+    /// [`MAX_FRAME_RATE`] are ignored and remove the frame rate instead.
+    pub fn set_frame_rate(&mut self, frame_rate: Option<f64>) {
+        self.frame_rate = frame_rate.filter(|f| (MIN_FRAME_RATE..=MAX_FRAME_RATE).contains(f));
+    }
+
+    /// Returns the [`TimingMethod`] the comparison is bound to. A comparison
+    /// that is bound to a timing method is always evaluated against that
+    /// timing method, regardless of the timing method that is currently
+    /// shown. If the comparison isn't bound to a timing method, `None` is
+    /// returned.
+    pub fn bound_timing_method(&self, comparison: &str) -> Option<TimingMethod> {
+        self.timing_method_bindings
+            .iter()
+            .find(|(c, _)| c == comparison)
+            .map(|&(_, method)| method)
+    }
+
+    /// Binds the comparison to the [`TimingMethod`] provided, so it is always
+    /// evaluated against that timing method. Specify `None` to remove the
+    /// binding.
+    pub fn set_bound_timing_method(&mut self, comparison: &str, method: Option<TimingMethod>) {
+        self.timing_method_bindings.retain(|(c, _)| c != comparison);
+        if let Some(method) = method {
+            self.timing_method_bindings
+                .push((comparison.to_owned(), method));
+        }
+    }
+
+    /// Accesses all the comparisons that are bound to a [`TimingMethod`] along
+    /// with the timing method they are bound to.
+    pub fn timing_method_bindings(&self) -> &[(String, TimingMethod)] {
+        &self.timing_method_bindings
+    }
+
+    /// Accesses the attempt tags that exclude attempts from the comparisons.
+    /// Attempts that have any of these tags attached are ignored when the
+    /// comparisons are generated.
+    pub fn excluded_attempt_tags(&self) -> &[String] {
+        &self.excluded_attempt_tags
+    }
+
+    /// Sets whether attempts that have the tag specified attached are
+    /// excluded from the comparisons. The comparisons are not regenerated
+    /// automatically, so you may want to call
+    /// [`regenerate_comparisons`](Self::regenerate_comparisons) afterwards.
+    pub fn set_attempt_tag_excluded(&mut self, tag: &str, excluded: bool) {
+        self.excluded_attempt_tags.retain(|t| t != tag);
+        if excluded {
+            self.excluded_attempt_tags.push(tag.to_owned());
+        }
+    }
+
+    /// Returns whether the attempt with the index specified is excluded,
+    /// because it has an excluded tag attached. Analysis functions can use
+    /// this to skip the attempt's times in the segment history.
+    pub fn is_attempt_excluded(&self, index: i32) -> bool {
+        !self.excluded_attempt_tags.is_empty()
+            && self
+                .attempt_history
+                .iter()
+                .any(|attempt| attempt.index() == index && self.has_excluded_tag(attempt))
+    }
+
+    pub(crate) fn has_excluded_tag(&self, attempt: &Attempt) -> bool {
+        attempt
+            .tags()
+            .iter()
+            .any(|tag| self.excluded_attempt_tags.contains(tag))
+    }
+
+    fn excluded_attempts(&self) -> HashSet<i32> {
+        self.attempt_history
+            .iter()
+            .filter(|attempt| self.has_excluded_tag(attempt))
+            .map(Attempt::index)
+            .collect()
+    }
+
+    /// Returns the segments of this `Run` with the times of all the excluded
+    /// attempts removed from their segment history. Statistics that are based
+    /// on the segment history should be calculated on these segments. If no
+    /// attempts are excluded, the segments are borrowed.
+    pub fn segments_without_excluded_attempts(&self) -> Cow<'_, [Segment]> {
+        if self.excluded_attempt_tags.is_empty() {
+            return Cow::Borrowed(&self.segments);
+        }
+        let excluded = self.excluded_attempts();
+        if excluded.is_empty() {
+            return Cow::Borrowed(&self.segments);
+        }
+        let mut segments = self.segments.clone();
+        for segment in &mut segments {
+            segment
+                .segment_history_mut()
+                .retain(|(index, _)| !excluded.contains(index));
+        }
+        Cow::Owned(segments)
+    }
+
+    /// Returns the [`TimingMethod`] the comparison needs to be evaluated
+    /// against. This is the timing method the comparison is bound to, if it
+    /// is bound to one, and the timing method provided otherwise.
+    #[inline]
+    pub fn resolve_timing_method(&self, comparison: &str, method: TimingMethod) -> TimingMethod {
+        self.bound_timing_method(comparison).unwrap_or(method)
+    }
+
+    /// Accesses the settings of the [`AverageSegments`] comparison of this
+    /// `Run`. They determine how quickly older segment times stop mattering.
+    #[inline]
+    pub const fn average_segments_comparison(&self) -> &AverageSegments {
+        &self.average_segments
+    }
+
+    /// Sets the settings of the [`AverageSegments`] comparison of this `Run`.
+    /// If the comparison is generated for this `Run`, its generator is
+    /// replaced by one with the new settings. The comparison is not
+    /// regenerated automatically, so you may want to call
+    /// [`regenerate_comparisons`](Self::regenerate_comparisons) afterwards.
+    pub fn set_average_segments_comparison(&mut self, settings: AverageSegments) {
+        self.average_segments = settings;
+        self.replace_comparison_generator(average_segments::NAME, settings);
+    }
+
+    /// Accesses the settings of the [`LastAttempts`] comparison of this `Run`.
+    /// They determine how many of the most recent segment times are considered
+    /// and how they are combined.
+    #[inline]
+    pub const fn last_attempts_comparison(&self) -> &LastAttempts {
+        &self.last_attempts
+    }
+
+    /// Sets the settings of the [`LastAttempts`] comparison of this `Run`. If
+    /// the comparison is generated for this `Run`, its generator is replaced
+    /// by one with the new settings. The comparison is not regenerated
+    /// automatically, so you may want to call
+    /// [`regenerate_comparisons`](Self::regenerate_comparisons) afterwards.
+    pub fn set_last_attempts_comparison(&mut self, settings: LastAttempts) {
+        self.last_attempts = settings;
+        self.replace_comparison_generator(last_attempts::NAME, settings);
+    }
+
+    /// Returns whether the [`LastAttempts`] comparison is generated for this
+    /// `Run`. It is not part of the default comparison generators and needs
+    /// to be enabled explicitly.
+    pub fn is_last_attempts_comparison_enabled(&self) -> bool {
+        self.comparison_generators()
+            .iter()
+            .any(|g| g.name() == last_attempts::NAME)
+    }
+
+    /// Sets whether the [`LastAttempts`] comparison is generated for this
+    /// `Run`. When it gets enabled, its generator uses the settings stored in
+    /// the `Run`. The comparison is not regenerated automatically, so you may
+    /// want to call [`regenerate_comparisons`](Self::regenerate_comparisons)
+    /// afterwards.
+    pub fn set_last_attempts_comparison_enabled(&mut self, enabled: bool) {
+        if enabled {
+            if !self.is_last_attempts_comparison_enabled() {
+                let settings = self.last_attempts;
+                self.comparison_generators_mut().push(Box::new(settings));
+            }
+        } else {
+            self.comparison_generators_mut()
+                .retain(|g| g.name() != last_attempts::NAME);
+        }
+    }
+
+    fn replace_comparison_generator<G>(&mut self, name: &str, generator: G)
+    where
+        G: ComparisonGenerator + Clone + 'static,
+    {
+        for existing in self.comparison_generators_mut() {
+            if existing.name() == name {
+                *existing = Box::new(generator.clone());
+            }
+        }
+    }
+
     /// Returns the amount of segments stored in this Run.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -507,8 +756,30 @@ impl Run {
     /// Recalculates all the comparison times the Comparison Generators provide.
     #[inline]
     pub fn regenerate_comparisons(&mut self) {
+        if self.excluded_attempt_tags.is_empty() {
+            for generator in &mut self.comparison_generators.0 {
+                generator.generate(&mut self.segments, &self.attempt_history);
+            }
+            return;
+        }
+
+        // The generators only see the attempts that are not excluded, so they
+        // work on copies of the segments that don't contain the excluded
+        // attempts' times. The generated comparisons are then copied back.
+        let mut segments = self.segments_without_excluded_attempts().into_owned();
+        let attempts: Vec<Attempt> = self
+            .attempt_history
+            .iter()
+            .filter(|attempt| !self.has_excluded_tag(attempt))
+            .cloned()
+            .collect();
+
         for generator in &mut self.comparison_generators.0 {
-            generator.generate(&mut self.segments, &self.attempt_history);
+            generator.generate(&mut segments, &attempts);
+            let name = generator.name();
+            for (segment, generated) in self.segments.iter_mut().zip(&segments) {
+                *segment.comparison_mut(name) = generated.comparison(name);
+            }
         }
     }
 
@@ -593,6 +864,51 @@ impl Run {
         self.reattach_unattached_segment_history_elements();
     }
 
+    /// Rebuilds the Personal Best split times and the Best Segment times purely
+    /// from the Attempt History and the Segment Histories, discarding the
+    /// values that are currently stored. This is useful for repairing splits
+    /// files where these values got corrupted. The Personal Best of each
+    /// timing method is the fastest finished attempt, with its split times
+    /// being derived from its segment times. The Best Segment times are the
+    /// fastest segment times stored in the Segment Histories. Make sure to
+    /// call [`regenerate_comparisons`](Self::regenerate_comparisons)
+    /// afterwards.
+    pub fn rebuild_from_history(&mut self) {
+        for method in TimingMethod::all() {
+            for segment in &mut self.segments {
+                segment.best_segment_time_mut()[method] = segment
+                    .segment_history()
+                    .iter()
+                    .filter_map(|(_, time)| time[method])
+                    .filter(|&time| time >= TimeSpan::zero())
+                    .min();
+            }
+
+            let personal_best = self
+                .attempt_history
+                .iter()
+                .filter_map(|attempt| Some((attempt.index(), attempt.time()[method]?)))
+                .min_by_key(|&(_, time)| time)
+                .map(|(index, _)| index);
+
+            let mut split_time = Some(TimeSpan::zero());
+            for segment in &mut self.segments {
+                let segment_time = personal_best
+                    .and_then(|index| segment.segment_history().get(index))
+                    .and_then(|time| time[method]);
+                let time = catch! { split_time? + segment_time? };
+                if segment_time.is_some() {
+                    split_time = time;
+                } else if personal_best
+                    .is_none_or(|index| segment.segment_history().get(index).is_none())
+                {
+                    split_time = None;
+                }
+                segment.personal_best_split_time_mut()[method] = time;
+            }
+        }
+    }
+
     /// Clears out the Attempt History and the Segment Histories of all the segments.
     pub fn clear_history(&mut self) {
         self.attempt_history.clear();
@@ -601,6 +917,54 @@ impl Run {
         }
     }
 
+    /// Removes all the attempts from the Attempt History for which the
+    /// predicate returns `false`, along with their elements in the Segment
+    /// Histories. Best Segment times that were only stored in the removed
+    /// attempts are kept by importing them into the Segment Histories. The
+    /// amount of attempts that got removed is returned.
+    pub fn retain_attempts<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&Attempt) -> bool,
+    {
+        let mut removed = Vec::new();
+        self.attempt_history.retain(|attempt| {
+            let keep = f(attempt);
+            if !keep {
+                removed.push(attempt.index());
+            }
+            keep
+        });
+
+        if removed.is_empty() {
+            return 0;
+        }
+
+        removed.sort_unstable();
+        for segment in &mut self.segments {
+            segment
+                .segment_history_mut()
+                .retain(|&(index, _)| removed.binary_search(&index).is_err());
+        }
+
+        for index in 0..self.len() {
+            let segment = &self.segments[index];
+            let best_segment_time = segment.best_segment_time();
+            let is_best_segment_lost = TimingMethod::all().into_iter().any(|method| {
+                best_segment_time[method].is_some_and(|best| {
+                    !segment
+                        .segment_history()
+                        .iter()
+                        .any(|(_, time)| time[method] == Some(best))
+                })
+            });
+            if is_best_segment_lost {
+                self.import_best_segment(index);
+            }
+        }
+
+        removed.len()
+    }
+
     /// Clears out the Attempt History, the Segment Histories, all the times,
     /// sets the Attempt Count to 0 and clears the speedrun.com run id
     /// association. All Custom Comparisons other than `Personal Best` are
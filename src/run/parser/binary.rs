@@ -0,0 +1,349 @@
+//! Provides the parser for livesplit-core's compact binary splits files. The
+//! format is described in the [`Binary Saver`](crate::run::saver::binary).
+
+use super::livesplit;
+use crate::{
+    AtomicDateTime, Run, Segment, Time, TimeSpan, TimingMethod,
+    comparison::{
+        AverageSegments,
+        last_attempts::{LastAttempts, Statistic},
+        personal_best,
+        registry::Registry,
+    },
+    platform::{DateTime, Duration, prelude::*},
+    run::{
+        AddComparisonError, GoldRules, LinkedLayout,
+        saver::binary::{MAGIC, VERSION},
+    },
+    settings::Image,
+    util::byte_parsing::{strip_pod, strip_slice, strip_u8},
+};
+use core::result::Result as StdResult;
+use snafu::{OptionExt, ResultExt};
+
+/// The Error type for splits files that couldn't be parsed by the Binary
+/// Parser.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum Error {
+    /// The file doesn't start with the magic bytes of a binary splits file.
+    InvalidHeader,
+    /// The file was saved with a version of the format that isn't supported.
+    #[snafu(display("Version {version} of the binary splits file format is not supported."))]
+    UnsupportedVersion {
+        /// The version of the format the file was saved with.
+        version: u16,
+    },
+    /// The file ended unexpectedly.
+    UnexpectedEndOfFile,
+    /// An integer is out of range.
+    InvalidInteger,
+    /// A string is not encoded as valid UTF-8.
+    InvalidUtf8,
+    /// A time is out of range.
+    InvalidTime,
+    /// A date is out of range.
+    InvalidDate,
+    /// A value that is stored as one of several variants has an unknown
+    /// variant.
+    InvalidVariant,
+    /// Parsed comparison has an invalid name.
+    InvalidComparisonName {
+        /// The underlying error.
+        source: AddComparisonError,
+    },
+    /// Failed to parse the auto splitter settings.
+    AutoSplitterSettings {
+        /// The underlying error.
+        source: livesplit::Error,
+    },
+}
+
+/// The Result type for the Binary Parser.
+pub type Result<T> = StdResult<T, Error>;
+
+struct Decoder<'a> {
+    cursor: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn u8(&mut self) -> Result<u8> {
+        strip_u8(&mut self.cursor).context(UnexpectedEndOfFile)
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            let bits = u64::from(byte & 0x7F);
+            if shift == 63 && bits > 1 {
+                return Err(Error::InvalidInteger);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+            if shift > 63 {
+                return Err(Error::InvalidInteger);
+            }
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.u64()?.try_into().ok().context(InvalidInteger)
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        self.u64()?.try_into().ok().context(InvalidInteger)
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        let value = self.u64()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        self.i64()?.try_into().ok().context(InvalidInteger)
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        let bytes: &[u8; 8] = strip_pod(&mut self.cursor).context(UnexpectedEndOfFile)?;
+        Ok(f64::from_le_bytes(*bytes))
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidVariant),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.usize()?;
+        strip_slice(&mut self.cursor, len).context(UnexpectedEndOfFile)
+    }
+
+    fn str(&mut self) -> Result<&'a str> {
+        simdutf8::basic::from_utf8(self.bytes()?)
+            .ok()
+            .context(InvalidUtf8)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.str().map(Into::into)
+    }
+
+    fn image(&mut self) -> Result<Image> {
+        let data = self.bytes()?;
+        Ok(if data.is_empty() {
+            Image::default()
+        } else {
+            Image::new(data.into(), Image::ICON)
+        })
+    }
+
+    fn time_span(&mut self) -> Result<TimeSpan> {
+        let seconds = self.i64()?;
+        let nanoseconds = self.i64()?;
+        if nanoseconds.unsigned_abs() >= 1_000_000_000 {
+            return Err(Error::InvalidTime);
+        }
+        Ok(Duration::new(seconds, nanoseconds as i32).into())
+    }
+
+    fn optional_time_span(&mut self) -> Result<Option<TimeSpan>> {
+        Ok(if self.bool()? {
+            Some(self.time_span()?)
+        } else {
+            None
+        })
+    }
+
+    fn time(&mut self) -> Result<Time> {
+        let flags = self.u8()?;
+        if flags > 0b11 {
+            return Err(Error::InvalidVariant);
+        }
+        let mut time = Time::new();
+        if flags & 0b01 != 0 {
+            time.real_time = Some(self.time_span()?);
+        }
+        if flags & 0b10 != 0 {
+            time.game_time = Some(self.time_span()?);
+        }
+        Ok(time)
+    }
+
+    fn optional_date_time(&mut self) -> Result<Option<AtomicDateTime>> {
+        if !self.bool()? {
+            return Ok(None);
+        }
+        let seconds = self.i64()?;
+        let nanoseconds = self.u32()?;
+        let time = DateTime::from_unix_timestamp(seconds)
+            .ok()
+            .and_then(|t| t.replace_nanosecond(nanoseconds).ok())
+            .context(InvalidDate)?;
+        Ok(Some(AtomicDateTime::new(time, self.bool()?)))
+    }
+
+    fn list<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.usize()?;
+        // Every element takes up at least a byte, so this doesn't allocate
+        // huge amounts of memory for invalid lengths.
+        let mut list = Vec::with_capacity(len.min(self.cursor.len()));
+        for _ in 0..len {
+            list.push(f(self)?);
+        }
+        Ok(list)
+    }
+
+    fn variables(&mut self) -> Result<Vec<(String, String)>> {
+        self.list(|d| Ok((d.string()?, d.string()?)))
+    }
+
+    fn timing_method(&mut self) -> Result<TimingMethod> {
+        match self.u8()? {
+            0 => Ok(TimingMethod::RealTime),
+            1 => Ok(TimingMethod::GameTime),
+            _ => Err(Error::InvalidVariant),
+        }
+    }
+}
+
+/// Attempts to parse a binary splits file.
+pub fn parse(source: &[u8]) -> Result<Run> {
+    let cursor = source.strip_prefix(MAGIC).context(InvalidHeader)?;
+    let d = &mut Decoder { cursor };
+
+    let version = u16::from_le_bytes(*strip_pod(&mut d.cursor).context(UnexpectedEndOfFile)?);
+    if version == 0 || version > VERSION {
+        return Err(Error::UnsupportedVersion { version });
+    }
+
+    let mut run = Run::new();
+
+    run.set_game_icon(d.image()?);
+    run.set_game_name(d.str()?);
+    run.set_category_name(d.str()?);
+    run.set_offset(d.time_span()?);
+    run.set_attempt_count(d.u32()?);
+
+    let metadata = run.metadata_mut();
+    metadata.set_run_id(d.str()?);
+    metadata.set_platform_name(d.str()?);
+    metadata.set_emulator_usage(d.bool()?);
+    metadata.set_region_name(d.str()?);
+    for _ in 0..d.usize()? {
+        metadata.set_speedrun_com_variable(d.str()?, d.str()?);
+    }
+    for _ in 0..d.usize()? {
+        let name = d.str()?;
+        metadata
+            .custom_variable_mut(name)
+            .permanent()
+            .set_value(d.str()?);
+    }
+
+    run.set_linked_layout(match d.u8()? {
+        0 => None,
+        1 => Some(LinkedLayout::Default),
+        2 => Some(LinkedLayout::Path(d.string()?)),
+        _ => return Err(Error::InvalidVariant),
+    });
+
+    for _ in 0..d.usize()? {
+        let index = d.i32()?;
+        let time = d.time()?;
+        let pause_time = d.optional_time_span()?;
+        let started = d.optional_date_time()?;
+        let ended = d.optional_date_time()?;
+        let variables = d.variables()?;
+        let tags = d.list(|d| d.str())?;
+        let split_variables = d.list(|d| Ok((d.usize()?, d.variables()?)))?;
+
+        run.add_attempt_with_index(time, index, started, ended, pause_time);
+        if !variables.is_empty() || !split_variables.is_empty() || !tags.is_empty() {
+            let attempt = run.attempt_history_mut().last_mut().unwrap();
+            attempt.set_variables(variables);
+            for (segment_index, values) in split_variables {
+                attempt.set_split_variables(segment_index, values);
+            }
+            for tag in tags {
+                attempt.add_tag(tag);
+            }
+        }
+    }
+
+    let comparisons = d.list(|d| d.str())?;
+    for &comparison in &comparisons {
+        if comparison != personal_best::NAME {
+            run.add_custom_comparison(comparison)
+                .context(InvalidComparisonName)?;
+        }
+    }
+
+    for _ in 0..d.usize()? {
+        let mut segment = Segment::new(d.str()?);
+        segment.set_icon(d.image()?);
+        for comparison in &comparisons {
+            *segment.comparison_mut(comparison) = d.time()?;
+        }
+        segment.set_best_segment_time(d.time()?);
+        let history = segment.segment_history_mut();
+        for _ in 0..d.usize()? {
+            let index = d.i32()?;
+            history.insert(index, d.time()?);
+        }
+        run.push_segment(segment);
+    }
+
+    run.set_gold_rules(GoldRules {
+        max_game_time_pause: d.optional_time_span()?,
+        min_segment_time_after_undo: d.optional_time_span()?,
+    });
+
+    let average_segments = AverageSegments::new(d.f64()?);
+    let last_attempts = LastAttempts {
+        attempts: d.u32()?,
+        statistic: match d.u8()? {
+            0 => Statistic::Average,
+            1 => Statistic::Median,
+            _ => return Err(Error::InvalidVariant),
+        },
+    };
+
+    let registry = Registry::default();
+    let generators = d.list(|d| {
+        let name = d.str()?;
+        let settings = Some(d.str()?).filter(|s| !s.is_empty());
+        Ok(registry.create(name, settings))
+    })?;
+    *run.comparison_generators_mut() = generators;
+
+    // The settings of the built-in generators are stored separately, so they
+    // need to be applied to the newly created generators.
+    run.set_average_segments_comparison(average_segments);
+    run.set_last_attempts_comparison(last_attempts);
+
+    for _ in 0..d.usize()? {
+        let comparison = d.str()?;
+        run.set_bound_timing_method(comparison, Some(d.timing_method()?));
+    }
+
+    for _ in 0..d.usize()? {
+        run.set_attempt_tag_excluded(d.str()?, true);
+    }
+
+    if d.bool()? {
+        run.set_frame_rate(Some(d.f64()?));
+    }
+
+    livesplit::parse_auto_splitter_settings_element(d.str()?, &mut run)
+        .context(AutoSplitterSettings)?;
+
+    Ok(run)
+}
@@ -28,12 +28,15 @@
 //! ```
 
 use super::{
-    TimerKind, face_split, flitter, livesplit, llanfair, llanfair_gered, opensplit,
+    TimerKind, binary, face_split, flitter, livesplit, llanfair, llanfair_gered, opensplit,
     portal2_live_timer, shit_split, source_live_timer, speedrun_igt, splitterino, splitterz,
-    splitty, time_split_tracker, urn, wsplit,
+    splitty, therun_gg, time_split_tracker, urn, wsplit,
 };
-use crate::{Run, platform::path::Path};
-use core::{result::Result as StdResult, str};
+use crate::{
+    Run,
+    platform::{path::Path, prelude::*},
+};
+use core::{fmt, result::Result as StdResult, str};
 
 /// The Error type for splits files that couldn't be parsed by the Composite
 /// Parser.
@@ -47,6 +50,30 @@ pub enum Error {
 /// The Result type for the Composite Parser.
 pub type Result<T> = StdResult<T, Error>;
 
+/// Describes why a parser rejected a splits file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// The parser that rejected the splits file.
+    pub kind: TimerKind<'static>,
+    /// The message of the error the parser encountered.
+    pub message: String,
+    /// The byte offset in the splits file at which the parser encountered the
+    /// error, if the parser is able to tell.
+    pub offset: Option<usize>,
+}
+
+/// A report about why none of the parsers were able to parse a splits file.
+/// The parsers are listed in the order they were tried in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The reasons each of the parsers rejected the splits file for.
+    pub rejections: Vec<Rejection>,
+    /// The splits file is not valid UTF-8, so all the text based parsers
+    /// were skipped. This is the byte offset of the first byte that is not
+    /// valid UTF-8.
+    pub invalid_utf8_offset: Option<usize>,
+}
+
 /// A run parsed by the Composite Parser. This contains the Run itself and
 /// information about which parser parsed it.
 pub struct ParsedRun<'a> {
@@ -96,77 +123,220 @@ pub fn parse<'source>(
     source: &'source [u8],
     load_files_path: Option<&Path>,
 ) -> Result<ParsedRun<'source>> {
-    if let Ok(source) = simdutf8::basic::from_utf8(source) {
-        if let Ok(run) = livesplit::parse(source) {
-            return Ok(parsed(run, TimerKind::LiveSplit));
-        }
+    parse_impl(source, load_files_path, &mut None).ok_or(Error::NoParserParsedIt)
+}
 
-        if let Ok(run) = wsplit::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::WSplit));
-        }
+/// Attempts to parse a splits file just like [`parse`], but if no parser is
+/// able to parse it, a [`Report`] is returned that describes why each of the
+/// parsers rejected the splits file. This can be used to show the user
+/// actionable messages for files that aren't recognized.
+pub fn parse_with_report<'source>(
+    source: &'source [u8],
+    load_files_path: Option<&Path>,
+) -> StdResult<ParsedRun<'source>, Report> {
+    let mut report = Report::default();
+    match parse_impl(source, load_files_path, &mut Some(&mut report)) {
+        Some(run) => Ok(run),
+        None => Err(report),
+    }
+}
 
-        if let Ok(run) = splitterz::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::SplitterZ));
-        }
+/// Records why a parser rejected the splits file, if a report is requested.
+fn reject<E: fmt::Display>(
+    report: &mut Option<&mut Report>,
+    kind: TimerKind<'static>,
+    error: &E,
+    offset: impl FnOnce(&E) -> Option<usize>,
+) {
+    if let Some(report) = report {
+        report.rejections.push(Rejection {
+            kind,
+            message: error.to_string(),
+            offset: offset(error),
+        });
+    }
+}
 
-        if let Ok(run) = shit_split::parse(source) {
-            return Ok(parsed(run, TimerKind::ShitSplit));
-        }
+const fn no_offset<E>(_: &E) -> Option<usize> {
+    None
+}
 
-        if let Ok(run) = splitty::parse(source) {
-            return Ok(parsed(run, TimerKind::Splitty));
-        }
+/// Converts the line and column of a JSON error into a byte offset.
+fn json_offset(source: &str, error: &serde_json::Error) -> Option<usize> {
+    let line = error.line().checked_sub(1)?;
+    let line_start = if line == 0 {
+        0
+    } else {
+        source
+            .match_indices('\n')
+            .nth(line - 1)
+            .map(|(index, _)| index + 1)?
+    };
+    Some((line_start + error.column().saturating_sub(1)).min(source.len()))
+}
 
-        if let Ok(run) = time_split_tracker::parse(source, load_files_path) {
-            return Ok(parsed(run, TimerKind::TimeSplitTracker));
+macro_rules! json_offset {
+    ($source:expr, $parser:ident) => {
+        |error: &$parser::Error| match error {
+            $parser::Error::Json { source } => json_offset($source, source),
+            #[allow(unreachable_patterns)]
+            _ => None,
         }
+    };
+}
 
-        if let Ok(run) = portal2_live_timer::parse(source) {
-            return Ok(parsed(run, TimerKind::Portal2LiveTimer));
-        }
+fn parse_impl<'source>(
+    source: &'source [u8],
+    load_files_path: Option<&Path>,
+    report: &mut Option<&mut Report>,
+) -> Option<ParsedRun<'source>> {
+    // Binary splits files are recognized by their magic bytes, so they can be
+    // detected before trying any of the text based parsers.
+    match binary::parse(source) {
+        Ok(run) => return Some(parsed(run, TimerKind::Binary)),
+        Err(binary::Error::InvalidHeader) => {}
+        Err(error) => reject(report, TimerKind::Binary, &error, no_offset),
+    }
 
-        if let Ok(run) = face_split::parse(source, load_files_path.is_some()) {
-            return Ok(parsed(run, TimerKind::FaceSplit));
-        }
+    match simdutf8::basic::from_utf8(source) {
+        Ok(source) => {
+            match livesplit::parse_with_offset(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::LiveSplit)),
+                Err((error, offset)) => {
+                    reject(report, TimerKind::LiveSplit, &error, |_| Some(offset))
+                }
+            }
 
-        // Should be parsed after LiveSplit's parser, as it also parses all
-        // LiveSplit files with the current implementation.
-        if let Ok(run) = llanfair_gered::parse(source) {
-            return Ok(parsed(run, TimerKind::LlanfairGered));
-        }
+            match wsplit::parse(source, load_files_path.is_some()) {
+                Ok(run) => return Some(parsed(run, TimerKind::WSplit)),
+                Err(error) => reject(report, TimerKind::WSplit, &error, no_offset),
+            }
 
-        // Splitterino, SourceLiveTimer, Flitter, and SpeedRunIGT need to be
-        // before Urn because of a false positive due to the nature of parsing
-        // JSON files.
-        if let Ok(run) = splitterino::parse(source) {
-            return Ok(parsed(run, TimerKind::Splitterino));
-        }
+            match splitterz::parse(source, load_files_path.is_some()) {
+                Ok(run) => return Some(parsed(run, TimerKind::SplitterZ)),
+                Err(error) => reject(report, TimerKind::SplitterZ, &error, no_offset),
+            }
 
-        if let Ok(run) = flitter::parse(source) {
-            return Ok(parsed(run, TimerKind::Flitter));
-        }
+            match shit_split::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::ShitSplit)),
+                Err(error) => reject(report, TimerKind::ShitSplit, &error, no_offset),
+            }
 
-        if let Ok(run) = source_live_timer::parse(source) {
-            return Ok(parsed(run, TimerKind::SourceLiveTimer));
-        }
+            match splitty::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::Splitty)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::Splitty,
+                    &error,
+                    json_offset!(source, splitty),
+                ),
+            }
 
-        if let Ok(run) = speedrun_igt::parse(source) {
-            return Ok(parsed(run, TimerKind::SpeedRunIGT));
-        }
+            match time_split_tracker::parse(source, load_files_path) {
+                Ok(run) => return Some(parsed(run, TimerKind::TimeSplitTracker)),
+                Err(error) => reject(report, TimerKind::TimeSplitTracker, &error, no_offset),
+            }
 
-        if let Ok(run) = opensplit::parse(source) {
-            return Ok(parsed(run, TimerKind::OpenSplit));
-        }
+            match portal2_live_timer::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::Portal2LiveTimer)),
+                Err(error) => reject(report, TimerKind::Portal2LiveTimer, &error, no_offset),
+            }
+
+            match face_split::parse(source, load_files_path.is_some()) {
+                Ok(run) => return Some(parsed(run, TimerKind::FaceSplit)),
+                Err(error) => reject(report, TimerKind::FaceSplit, &error, no_offset),
+            }
 
-        // Urn accepts entirely empty JSON files.
-        if let Ok(run) = urn::parse(source) {
-            return Ok(parsed(run, TimerKind::Urn));
+            // Should be parsed after LiveSplit's parser, as it also parses all
+            // LiveSplit files with the current implementation.
+            match llanfair_gered::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::LlanfairGered)),
+                Err(error) => reject(report, TimerKind::LlanfairGered, &error, no_offset),
+            }
+
+            // Splitterino, SourceLiveTimer, Flitter, and SpeedRunIGT need to be
+            // before Urn because of a false positive due to the nature of parsing
+            // JSON files.
+            match splitterino::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::Splitterino)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::Splitterino,
+                    &error,
+                    json_offset!(source, splitterino),
+                ),
+            }
+
+            match flitter::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::Flitter)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::Flitter,
+                    &error,
+                    json_offset!(source, flitter),
+                ),
+            }
+
+            match source_live_timer::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::SourceLiveTimer)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::SourceLiveTimer,
+                    &error,
+                    json_offset!(source, source_live_timer),
+                ),
+            }
+
+            match speedrun_igt::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::SpeedRunIGT)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::SpeedRunIGT,
+                    &error,
+                    json_offset!(source, speedrun_igt),
+                ),
+            }
+
+            match opensplit::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::OpenSplit)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::OpenSplit,
+                    &error,
+                    json_offset!(source, opensplit),
+                ),
+            }
+
+            match therun_gg::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::TheRunGg)),
+                Err(error) => reject(
+                    report,
+                    TimerKind::TheRunGg,
+                    &error,
+                    json_offset!(source, therun_gg),
+                ),
+            }
+
+            // Urn accepts entirely empty JSON files.
+            match urn::parse(source) {
+                Ok(run) => return Some(parsed(run, TimerKind::Urn)),
+                Err(error) => reject(report, TimerKind::Urn, &error, json_offset!(source, urn)),
+            }
+        }
+        Err(_) => {
+            if let Some(report) = report {
+                report.invalid_utf8_offset = str::from_utf8(source)
+                    .err()
+                    .map(|error| error.valid_up_to());
+            }
         }
     }
 
-    if let Ok(run) = llanfair::parse(source) {
-        return Ok(parsed(run, TimerKind::Llanfair));
+    match llanfair::parse(source) {
+        Ok(run) => Some(parsed(run, TimerKind::Llanfair)),
+        Err(error) => {
+            reject(report, TimerKind::Llanfair, &error, no_offset);
+            None
+        }
     }
-
-    Err(Error::NoParserParsedIt)
 }
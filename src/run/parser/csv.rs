@@ -0,0 +1,475 @@
+//! Provides a configurable parser for splits stored as comma or tab separated
+//! values, such as the ones exported from spreadsheets. Which column holds
+//! which information is described by the [`Settings`].
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::run::parser::csv::{self, Column, Settings};
+//! use livesplit_core::TimeSpan;
+//!
+//! let source = "Segment,Time\nFirst,1:05.25\nSecond,2:30.00\n";
+//!
+//! let settings = Settings {
+//!     split_time: Some(Column::Header("Time".into())),
+//!     ..Settings::default()
+//! };
+//! let run = csv::parse(source, &settings).unwrap();
+//!
+//! assert_eq!(run.segment(1).name(), "Second");
+//! assert_eq!(
+//!     run.segment(1).personal_best_split_time().real_time,
+//!     Some(TimeSpan::from_seconds(150.0)),
+//! );
+//! ```
+
+use crate::{
+    Run, Segment, TimeSpan, TimingMethod,
+    platform::{Duration, prelude::*},
+};
+use alloc::borrow::Cow;
+use core::result::Result as StdResult;
+
+/// The Error type for splits files that couldn't be parsed by the CSV Parser.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum Error {
+    /// The splits file doesn't contain any segments.
+    Empty,
+    /// The settings refer to a column header that couldn't be found.
+    #[snafu(display("The column `{name}` couldn't be found."))]
+    HeaderNotFound {
+        /// The name of the column header.
+        name: Box<str>,
+    },
+    /// A row doesn't have as many columns as needed.
+    #[snafu(display("Line {line} is missing a column."))]
+    MissingColumn {
+        /// The line of the row, starting at 1.
+        line: usize,
+    },
+    /// A time couldn't be parsed.
+    #[snafu(display("Line {line} contains a time that couldn't be parsed."))]
+    InvalidTime {
+        /// The line of the row, starting at 1.
+        line: usize,
+    },
+}
+
+/// The Result type for the CSV Parser.
+pub type Result<T> = StdResult<T, Error>;
+
+/// Refers to a column of the splits file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// The column at the index provided, starting at 0.
+    Index(usize),
+    /// The column with the header provided. The header is compared case
+    /// insensitively. This requires the splits file to have a header row.
+    Header(String),
+}
+
+/// The format of the times stored in the splits file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TimeFormat {
+    /// The format is detected for each time individually. Times with colons
+    /// are parsed as [`Clock`](Self::Clock) times, times with units as
+    /// [`Units`](Self::Units) and all other times as
+    /// [`Seconds`](Self::Seconds).
+    #[default]
+    Auto,
+    /// Times like `1:23:45.678`, where hours and minutes are optional.
+    Clock,
+    /// Times in seconds, like `83.5`. Both a dot and a comma are accepted as
+    /// the decimal separator.
+    Seconds,
+    /// Times in milliseconds, like `83500`.
+    Milliseconds,
+    /// Times with units, like `1h 23m 45.678s` or `500ms`.
+    Units,
+}
+
+/// The settings describing how the splits file is to be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The character separating the columns. If this is `None`, the
+    /// delimiter is detected from the first line, preferring tabs, then
+    /// semicolons and then commas.
+    pub delimiter: Option<char>,
+    /// Whether the first row is a header row that doesn't describe a segment.
+    pub has_header: bool,
+    /// The column holding the names of the segments.
+    pub name: Column,
+    /// The column holding the cumulative split times of the Personal Best.
+    /// If this is specified, the segment time column is not used for the
+    /// Personal Best.
+    pub split_time: Option<Column>,
+    /// The column holding the segment times of the Personal Best. The split
+    /// times are the sum of the segment times. An empty segment time is
+    /// treated as a skipped split, so the next segment time is expected to
+    /// cover both segments.
+    pub segment_time: Option<Column>,
+    /// The column holding the Best Segment times.
+    pub best_segment_time: Option<Column>,
+    /// The format of the times.
+    pub time_format: TimeFormat,
+    /// The timing method the times are stored as.
+    pub timing_method: TimingMethod,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            has_header: true,
+            name: Column::Index(0),
+            split_time: Some(Column::Index(1)),
+            segment_time: None,
+            best_segment_time: None,
+            time_format: TimeFormat::Auto,
+            timing_method: TimingMethod::RealTime,
+        }
+    }
+}
+
+fn detect_delimiter(source: &str) -> char {
+    let first_line = source.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    if first_line.contains('\t') {
+        '\t'
+    } else if first_line.contains(';') {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// Splits a line into its fields, handling fields that are quoted with double
+/// quotes. Quotes within quoted fields are escaped by doubling them.
+fn fields(line: &str, delimiter: char) -> Vec<Cow<'_, str>> {
+    let mut fields = Vec::new();
+    let mut rem = line;
+
+    loop {
+        let trimmed =
+            rem.trim_start_matches(|c: char| c == ' ' || (c == '\t' && delimiter != '\t'));
+        if let Some(quoted) = trimmed.strip_prefix('"') {
+            let mut field = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                if c == '"' {
+                    if quoted[i + 1..].starts_with('"') {
+                        field.push('"');
+                        chars.next();
+                    } else {
+                        end = i + 1;
+                        break;
+                    }
+                } else {
+                    field.push(c);
+                }
+            }
+            fields.push(Cow::Owned(field));
+            rem = &quoted[end..];
+            match rem.split_once(delimiter) {
+                Some((_, after)) => rem = after,
+                None => break,
+            }
+        } else {
+            match rem.split_once(delimiter) {
+                Some((field, after)) => {
+                    fields.push(Cow::Borrowed(field.trim()));
+                    rem = after;
+                }
+                None => {
+                    fields.push(Cow::Borrowed(rem.trim()));
+                    break;
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+fn parse_seconds(text: &str) -> Option<f64> {
+    if text.contains(',') {
+        text.replace(',', ".").parse().ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+/// Turns the seconds into a time, unless they are not a finite number or too
+/// large to be represented.
+fn from_seconds(seconds: f64) -> Option<TimeSpan> {
+    Duration::checked_seconds_f64(seconds).map(Into::into)
+}
+
+fn parse_units(text: &str) -> Option<TimeSpan> {
+    let mut seconds = 0.0;
+    let mut rem = text.trim();
+    let mut any = false;
+
+    while !rem.is_empty() {
+        let number_len = rem
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+            .unwrap_or(rem.len());
+        if number_len == 0 {
+            return None;
+        }
+        let value = parse_seconds(&rem[..number_len])?;
+        rem = rem[number_len..].trim_start();
+
+        let unit_len = rem
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rem.len());
+        let factor = match &rem[..unit_len] {
+            "h" => 3600.0,
+            "m" | "min" => 60.0,
+            "s" | "" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        seconds += value * factor;
+        rem = rem[unit_len..].trim_start();
+        any = true;
+    }
+
+    if !any {
+        return None;
+    }
+    from_seconds(seconds)
+}
+
+fn parse_time(text: &str, format: TimeFormat) -> Option<TimeSpan> {
+    match format {
+        TimeFormat::Auto => {
+            if text.contains(':') {
+                parse_time(text, TimeFormat::Clock)
+            } else if text.contains(|c: char| c.is_ascii_alphabetic()) {
+                parse_time(text, TimeFormat::Units)
+            } else {
+                parse_time(text, TimeFormat::Seconds)
+            }
+        }
+        TimeFormat::Clock => text.parse().ok(),
+        TimeFormat::Seconds => from_seconds(parse_seconds(text)?),
+        TimeFormat::Milliseconds => from_seconds(0.001 * parse_seconds(text)?),
+        TimeFormat::Units => parse_units(text),
+    }
+}
+
+fn resolve(column: &Column, header: Option<&[Cow<'_, str>]>) -> Result<usize> {
+    match column {
+        Column::Index(index) => Ok(*index),
+        Column::Header(name) => header
+            .and_then(|header| {
+                header
+                    .iter()
+                    .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+            })
+            .ok_or_else(|| Error::HeaderNotFound {
+                name: name.as_str().into(),
+            }),
+    }
+}
+
+/// Attempts to parse a splits file stored as comma or tab separated values
+/// with the settings provided.
+pub fn parse(source: &str, settings: &Settings) -> Result<Run> {
+    let delimiter = settings
+        .delimiter
+        .unwrap_or_else(|| detect_delimiter(source));
+    let method = settings.timing_method;
+
+    let mut rows = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, fields(line, delimiter)));
+
+    let header = if settings.has_header {
+        rows.next().map(|(_, header)| header)
+    } else {
+        None
+    };
+    let header = header.as_deref();
+
+    let name_column = resolve(&settings.name, header)?;
+    let split_time_column = settings
+        .split_time
+        .as_ref()
+        .map(|c| resolve(c, header))
+        .transpose()?;
+    let segment_time_column = settings
+        .segment_time
+        .as_ref()
+        .map(|c| resolve(c, header))
+        .transpose()?;
+    let best_segment_time_column = settings
+        .best_segment_time
+        .as_ref()
+        .map(|c| resolve(c, header))
+        .transpose()?;
+
+    let time = |row: &[Cow<'_, str>], column: Option<usize>, line: usize| {
+        let Some(column) = column else {
+            return Ok(None);
+        };
+        let text = row.get(column).map(|t| t.trim()).unwrap_or("");
+        if text.is_empty() {
+            return Ok(None);
+        }
+        parse_time(text, settings.time_format)
+            .map(Some)
+            .ok_or(Error::InvalidTime { line })
+    };
+
+    let mut run = Run::new();
+    let mut total = TimeSpan::zero();
+
+    for (line, row) in rows {
+        let name = row.get(name_column).ok_or(Error::MissingColumn { line })?;
+        let mut segment = Segment::new(name.trim());
+
+        if split_time_column.is_some() {
+            segment.personal_best_split_time_mut()[method] = time(&row, split_time_column, line)?;
+        } else if let Some(segment_time) = time(&row, segment_time_column, line)? {
+            total += segment_time;
+            segment.personal_best_split_time_mut()[method] = Some(total);
+        }
+
+        segment.best_segment_time_mut()[method] = time(&row, best_segment_time_column, line)?;
+
+        run.push_segment(segment);
+    }
+
+    if run.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cumulative_times() {
+        let run = parse(
+            "Name,Split,Gold\nA,1:00.5,58.25\nB,,\nC,3:10,1m 2.5s\n",
+            &Settings {
+                best_segment_time: Some(Column::Header("gold".into())),
+                ..Settings::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(run.len(), 3);
+        let splits = run
+            .segments()
+            .iter()
+            .map(|s| s.personal_best_split_time().real_time)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            splits,
+            [
+                Some(TimeSpan::from_seconds(60.5)),
+                None,
+                Some(TimeSpan::from_seconds(190.0)),
+            ]
+        );
+        assert_eq!(
+            run.segment(2).best_segment_time().real_time,
+            Some(TimeSpan::from_seconds(62.5))
+        );
+    }
+
+    #[test]
+    fn segment_times_with_tabs_and_quotes() {
+        let run = parse(
+            "\"Level, Part 1\"\t2500\n\"Say \"\"Hi\"\"\"\t\nEnd\t1500\n",
+            &Settings {
+                has_header: false,
+                split_time: None,
+                segment_time: Some(Column::Index(1)),
+                time_format: TimeFormat::Milliseconds,
+                timing_method: TimingMethod::GameTime,
+                ..Settings::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(run.segment(0).name(), "Level, Part 1");
+        assert_eq!(run.segment(1).name(), "Say \"Hi\"");
+        assert_eq!(run.segment(1).personal_best_split_time().game_time, None);
+        assert_eq!(
+            run.segment(2).personal_best_split_time().game_time,
+            Some(TimeSpan::from_seconds(4.0))
+        );
+        assert_eq!(run.segment(2).personal_best_split_time().real_time, None);
+    }
+
+    #[test]
+    fn seconds_with_comma_decimal_separator() {
+        let run = parse(
+            "Segment;Time\nA;12,5\n",
+            &Settings {
+                time_format: TimeFormat::Seconds,
+                ..Settings::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            run.segment(0).personal_best_split_time().real_time,
+            Some(TimeSpan::from_seconds(12.5))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            parse("Name,Time\n", &Settings::default()),
+            Err(Error::Empty)
+        ));
+        assert!(matches!(
+            parse(
+                "Name,Time\nA,1\n",
+                &Settings {
+                    split_time: Some(Column::Header("Split".into())),
+                    ..Settings::default()
+                }
+            ),
+            Err(Error::HeaderNotFound { .. })
+        ));
+        assert!(matches!(
+            parse("Name,Time\nA,1\nB,soon\n", &Settings::default()),
+            Err(Error::InvalidTime { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn times_that_cant_be_represented() {
+        for (time, time_format) in [
+            ("99999999999999999999999", TimeFormat::Auto),
+            ("inf", TimeFormat::Seconds),
+            ("NaN", TimeFormat::Seconds),
+            ("1e30", TimeFormat::Milliseconds),
+            ("99999999999999999999999h", TimeFormat::Units),
+        ] {
+            assert!(matches!(
+                parse(
+                    &format!("Name,Time\nA,{time}\n"),
+                    &Settings {
+                        time_format,
+                        ..Settings::default()
+                    }
+                ),
+                Err(Error::InvalidTime { line: 2 })
+            ));
+        }
+    }
+}
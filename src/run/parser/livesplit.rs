@@ -1,14 +1,20 @@
 //! Provides the parser for LiveSplit splits files.
 
 use crate::{
-    AtomicDateTime, DateTime, Run, RunMetadata, Segment, Time, TimeSpan,
+    AtomicDateTime, DateTime, Run, RunMetadata, Segment, Time, TimeSpan, TimingMethod,
+    comparison::{
+        AverageSegments,
+        last_attempts::{LastAttempts, Statistic},
+        registry::Registry,
+    },
     platform::prelude::*,
-    run::{AddComparisonError, LinkedLayout},
+    run::{AddComparisonError, GoldRules, LinkedLayout, RunBundle},
     settings::Image,
+    timing::event_log::{Command, Entry, EventLog},
     util::{
         ascii_char::AsciiChar,
         xml::{
-            Reader,
+            Attributes, Reader,
             helper::{
                 Error as XmlError, attribute, attribute_escaped_err, end_tag, image,
                 optional_attribute_escaped_err, parse_attributes, parse_base, parse_children,
@@ -18,13 +24,13 @@ use crate::{
     },
 };
 use alloc::borrow::Cow;
+use core::cell::Cell;
 use core::fmt::{Display, Formatter};
 use core::{mem::MaybeUninit, str};
 use time::{Date, Duration, PrimitiveDateTime};
 #[cfg(feature = "auto-splitting")]
 use {
-    crate::run::auto_splitter_settings::AutoSplitterSettings, crate::util::xml::Attributes,
-    livesplit_auto_splitting::settings,
+    crate::run::auto_splitter_settings::AutoSplitterSettings, livesplit_auto_splitting::settings,
 };
 
 /// The Error type for splits files that couldn't be parsed by the LiveSplit
@@ -62,6 +68,8 @@ pub enum Error {
     },
     /// Failed to parse a boolean.
     ParseBool,
+    /// Failed to parse an entry of the event log of an attempt.
+    ParseEventLog,
 }
 
 impl From<XmlError> for Error {
@@ -97,6 +105,57 @@ impl From<AddComparisonError> for Error {
 /// The Result type for the LiveSplit Parser.
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Describes data that got dropped while recovering a splits file with
+/// [`parse_lenient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// An attempt in the Attempt History is malformed and got dropped.
+    DroppedAttempt {
+        /// The index of the attempt, if it could be parsed.
+        index: Option<i32>,
+    },
+    /// An element of a segment's history is malformed and got dropped.
+    DroppedSegmentHistoryElement {
+        /// The index of the segment.
+        segment_index: usize,
+        /// The index of the attempt the element belongs to, if it could be
+        /// parsed.
+        index: Option<i32>,
+    },
+    /// The file ended unexpectedly or couldn't be read any further.
+    /// Everything before that point got recovered.
+    Truncated,
+}
+
+/// A [`Run`] recovered by [`parse_lenient`], along with the warnings about
+/// what got dropped.
+#[derive(Debug, Clone)]
+pub struct Recovered {
+    /// The Run that got recovered.
+    pub run: Run,
+    /// The warnings about everything that got dropped.
+    pub warnings: Vec<Warning>,
+}
+
+/// Decides whether malformed values abort the parsing, or whether they only
+/// mark the element they belong to as malformed, so it can be dropped.
+#[derive(Copy, Clone)]
+struct Tolerance<'a>(Option<&'a Cell<bool>>);
+
+impl Tolerance<'_> {
+    const STRICT: Tolerance<'static> = Tolerance(None);
+
+    fn check(self, result: Result<()>) -> Result<()> {
+        match (result, self.0) {
+            (Err(_), Some(malformed)) => {
+                malformed.set(true);
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+}
+
 // FIXME: Generalized Type Ascription (GTA 6)
 #[inline]
 const fn type_hint<T>(v: Result<T>) -> Result<T> {
@@ -168,17 +227,17 @@ where
     })
 }
 
-fn time_span_opt<F>(reader: &mut Reader, f: F) -> Result<()>
+fn time_span_opt_tolerant<F>(reader: &mut Reader, tolerance: Tolerance<'_>, f: F) -> Result<()>
 where
     F: FnOnce(Option<TimeSpan>),
 {
     text_as_escaped_string_err(reader, |text| {
-        f(if text.is_empty() {
-            None
+        tolerance.check(if text.is_empty() {
+            f(None);
+            Ok(())
         } else {
-            Some(parse_time_span(text)?)
-        });
-        Ok(())
+            parse_time_span(text).map(|t| f(Some(t)))
+        })
     })
 }
 
@@ -212,6 +271,13 @@ fn parse_time_span(text: &str) -> Result<TimeSpan> {
 }
 
 fn time<F>(reader: &mut Reader, f: F) -> Result<()>
+where
+    F: FnOnce(Time),
+{
+    time_tolerant(reader, Tolerance::STRICT, f)
+}
+
+fn time_tolerant<F>(reader: &mut Reader, tolerance: Tolerance<'_>, f: F) -> Result<()>
 where
     F: FnOnce(Time),
 {
@@ -219,9 +285,9 @@ where
 
     parse_children(reader, |reader, tag, _| {
         if tag.name() == "RealTime" {
-            time_span_opt(reader, |t| time.real_time = t)
+            time_span_opt_tolerant(reader, tolerance, |t| time.real_time = t)
         } else if tag.name() == "GameTime" {
-            time_span_opt(reader, |t| time.game_time = t)
+            time_span_opt_tolerant(reader, tolerance, |t| time.game_time = t)
         } else {
             end_tag(reader)
         }
@@ -236,7 +302,14 @@ fn time_old<F>(reader: &mut Reader, f: F) -> Result<()>
 where
     F: FnOnce(Time),
 {
-    time_span_opt(reader, |t| f(Time::new().with_real_time(t)))
+    time_old_tolerant(reader, Tolerance::STRICT, f)
+}
+
+fn time_old_tolerant<F>(reader: &mut Reader, tolerance: Tolerance<'_>, f: F) -> Result<()>
+where
+    F: FnOnce(Time),
+{
+    time_span_opt_tolerant(reader, tolerance, |t| f(Time::new().with_real_time(t)))
 }
 
 fn parse_bool(value: &str) -> Result<bool> {
@@ -291,7 +364,9 @@ fn parse_segment(
     reader: &mut Reader,
     image_buf: &mut Vec<MaybeUninit<u8>>,
     run: &mut Run,
+    mut warnings: Option<&mut Vec<Warning>>,
 ) -> Result<Segment> {
+    let segment_index = run.len();
     let mut segment = Segment::new("");
 
     parse_children(reader, |reader, tag, _| match tag.name() {
@@ -339,16 +414,51 @@ fn parse_segment(
             }
         }
         "SegmentHistory" => parse_children(reader, |reader, _, attributes| {
-            let mut index = 0;
-            type_hint(attribute_escaped_err(attributes, "id", |t| {
-                index = t.parse()?;
-                Ok(())
+            let malformed = Cell::new(false);
+            let tolerance = match warnings {
+                Some(_) => Tolerance(Some(&malformed)),
+                None => Tolerance::STRICT,
+            };
+
+            let mut index = None;
+            type_hint(parse_attributes(attributes, |k, v| {
+                if k == "id" {
+                    tolerance.check(
+                        v.escaped()
+                            .parse()
+                            .map(|v| index = Some(v))
+                            .map_err(Into::into),
+                    )?;
+                }
+                Ok(true)
             }))?;
+            if index.is_none() {
+                tolerance.check(Err(Error::Xml {
+                    source: XmlError::AttributeNotFound,
+                }))?;
+            }
+
+            let mut time = Time::new();
             if version >= Version(1, 4, 1, 0) {
-                time(reader, |t| segment.segment_history_mut().insert(index, t))
+                time_tolerant(reader, tolerance, |t| time = t)?;
             } else {
-                time_old(reader, |t| segment.segment_history_mut().insert(index, t))
+                time_old_tolerant(reader, tolerance, |t| time = t)?;
             }
+
+            match index {
+                Some(index) if !malformed.get() => {
+                    segment.segment_history_mut().insert(index, time);
+                }
+                _ => {
+                    if let Some(warnings) = warnings.as_deref_mut() {
+                        warnings.push(Warning::DroppedSegmentHistoryElement {
+                            segment_index,
+                            index,
+                        });
+                    }
+                }
+            }
+            Ok(())
         }),
         _ => end_tag(reader),
     })?;
@@ -384,9 +494,20 @@ fn parse_run_history(version: Version, reader: &mut Reader, run: &mut Run) -> Re
     }
 }
 
-fn parse_attempt_history(version: Version, reader: &mut Reader, run: &mut Run) -> Result<()> {
+fn parse_attempt_history(
+    version: Version,
+    reader: &mut Reader,
+    run: &mut Run,
+    mut warnings: Option<&mut Vec<Warning>>,
+) -> Result<()> {
     if version >= Version(1, 5, 0, 0) {
         parse_children(reader, |reader, _, attributes| {
+            let malformed = Cell::new(false);
+            let tolerance = match warnings {
+                Some(_) => Tolerance(Some(&malformed)),
+                None => Tolerance::STRICT,
+            };
+
             let mut time = Time::new();
             let mut pause_time = None;
             let mut index = None;
@@ -394,28 +515,75 @@ fn parse_attempt_history(version: Version, reader: &mut Reader, run: &mut Run) -
             let (mut ended, mut ended_synced) = (None, false);
 
             type_hint(parse_attributes(attributes, |k, v| {
-                match k {
-                    "id" => index = Some(v.escaped().parse()?),
-                    "started" => started = Some(parse_date_time(v.escaped())?),
-                    "isStartedSynced" => started_synced = parse_bool(v.escaped())?,
-                    "ended" => ended = Some(parse_date_time(v.escaped())?),
-                    "isEndedSynced" => ended_synced = parse_bool(v.escaped())?,
-                    _ => {}
-                }
+                tolerance.check(match k {
+                    "id" => v
+                        .escaped()
+                        .parse()
+                        .map(|v| index = Some(v))
+                        .map_err(Into::into),
+                    "started" => parse_date_time(v.escaped()).map(|v| started = Some(v)),
+                    "isStartedSynced" => parse_bool(v.escaped()).map(|v| started_synced = v),
+                    "ended" => parse_date_time(v.escaped()).map(|v| ended = Some(v)),
+                    "isEndedSynced" => parse_bool(v.escaped()).map(|v| ended_synced = v),
+                    _ => Ok(()),
+                })?;
                 Ok(true)
             }))?;
 
-            let index = index.ok_or(Error::Xml {
-                source: XmlError::AttributeNotFound,
-            })?;
+            if index.is_none() {
+                tolerance.check(Err(Error::Xml {
+                    source: XmlError::AttributeNotFound,
+                }))?;
+            }
 
-            parse_children(reader, |reader, tag, _| match tag.name() {
-                "RealTime" => time_span_opt(reader, |t| time.real_time = t),
-                "GameTime" => time_span_opt(reader, |t| time.game_time = t),
-                "PauseTime" => time_span_opt(reader, |t| pause_time = t),
+            let mut variables = Vec::new();
+            let mut split_variables = Vec::new();
+            let mut tags = Vec::new();
+            let mut event_log = EventLog::default();
+
+            parse_children(reader, |reader, tag, attributes| match tag.name() {
+                "RealTime" => time_span_opt_tolerant(reader, tolerance, |t| time.real_time = t),
+                "GameTime" => time_span_opt_tolerant(reader, tolerance, |t| time.game_time = t),
+                "PauseTime" => time_span_opt_tolerant(reader, tolerance, |t| pause_time = t),
+                "Variables" => parse_variables(reader, &mut variables),
+                "Tags" => parse_tags(reader, |t| tags.push(t)),
+                "EventLog" => parse_event_log(reader, attributes, &mut event_log),
+                "SplitVariables" => parse_children(reader, |reader, _, attributes| {
+                    let mut segment_index = None;
+                    type_hint(parse_attributes(attributes, |k, v| {
+                        if k == "segment" {
+                            tolerance.check(
+                                v.escaped()
+                                    .parse()
+                                    .map(|v| segment_index = Some(v))
+                                    .map_err(Into::into),
+                            )?;
+                        }
+                        Ok(true)
+                    }))?;
+                    let mut values = Vec::new();
+                    parse_variables(reader, &mut values)?;
+                    match segment_index {
+                        Some(segment_index) => split_variables.push((segment_index, values)),
+                        None => tolerance.check(Err(Error::Xml {
+                            source: XmlError::AttributeNotFound,
+                        }))?,
+                    }
+                    Ok(())
+                }),
                 _ => end_tag(reader),
             })?;
 
+            let index = match index {
+                Some(index) if !malformed.get() => index,
+                _ => {
+                    if let Some(warnings) = warnings.as_deref_mut() {
+                        warnings.push(Warning::DroppedAttempt { index });
+                    }
+                    return Ok(());
+                }
+            };
+
             let started = started.map(|t| AtomicDateTime::new(t, started_synced));
             let ended = if version <= Version(1, 7, 0, 0)
                 && catch! { ended? < started?.time }.unwrap_or(false)
@@ -426,6 +594,21 @@ fn parse_attempt_history(version: Version, reader: &mut Reader, run: &mut Run) -
             };
 
             run.add_attempt_with_index(time, index, started, ended, pause_time);
+            if !variables.is_empty()
+                || !split_variables.is_empty()
+                || !tags.is_empty()
+                || !event_log.is_empty()
+            {
+                let attempt = run.attempt_history_mut().last_mut().unwrap();
+                attempt.set_event_log(event_log);
+                attempt.set_variables(variables);
+                for (segment_index, values) in split_variables {
+                    attempt.set_split_variables(segment_index, values);
+                }
+                for tag in tags {
+                    attempt.add_tag(&tag);
+                }
+            }
 
             Ok(())
         })
@@ -434,6 +617,210 @@ fn parse_attempt_history(version: Version, reader: &mut Reader, run: &mut Run) -
     }
 }
 
+fn parse_event_log(
+    reader: &mut Reader,
+    attributes: Attributes<'_>,
+    event_log: &mut EventLog,
+) -> Result<()> {
+    let mut truncated = false;
+    type_hint(parse_attributes(attributes, |k, v| {
+        if k == "truncated" {
+            truncated = parse_bool(v.escaped())?;
+        }
+        Ok(true)
+    }))?;
+
+    let mut entries = Vec::new();
+    parse_children(reader, |reader, _, attributes| {
+        let (mut elapsed, mut real_time, mut game_time) = (None, None, None);
+        let (mut command, mut segment_index, mut comparison) = (None, None, None);
+        let (mut feed_segment_history, mut update_splits) = (false, false);
+        let (mut timing_method, mut time, mut name, mut value) = (None, None, None, None);
+
+        type_hint(parse_attributes(attributes, |k, v| {
+            match k {
+                "elapsed" => elapsed = Some(parse_time_span(v.escaped())?),
+                "realTime" => real_time = Some(parse_time_span(v.escaped())?),
+                "gameTime" => game_time = Some(parse_time_span(v.escaped())?),
+                "command" => command = Some(v.unescape_str()),
+                "segment" => segment_index = Some(v.escaped().parse()?),
+                "comparison" => comparison = Some(v.unescape_str()),
+                "feedSegmentHistory" => feed_segment_history = parse_bool(v.escaped())?,
+                "updateSplits" => update_splits = parse_bool(v.escaped())?,
+                "timingMethod" => {
+                    timing_method = Some(match v.escaped() {
+                        "RealTime" => TimingMethod::RealTime,
+                        "GameTime" => TimingMethod::GameTime,
+                        _ => return Err(Error::ParseEventLog),
+                    })
+                }
+                "amount" | "time" => time = Some(parse_time_span(v.escaped())?),
+                "name" => name = Some(v.unescape_str()),
+                "value" => value = Some(v.unescape_str()),
+                _ => {}
+            }
+            Ok(true)
+        }))?;
+
+        let command = match command.as_deref().ok_or(Error::ParseEventLog)? {
+            "Start" => Command::Start,
+            "StartAtSegment" => Command::StartAtSegment {
+                segment_index: segment_index.ok_or(Error::ParseEventLog)?,
+                comparison: comparison.ok_or(Error::ParseEventLog)?,
+                feed_segment_history,
+            },
+            "Split" => Command::Split,
+            "SkipSplit" => Command::SkipSplit,
+            "UndoSplit" => Command::UndoSplit,
+            "Reset" => Command::Reset {
+                update_splits,
+                timing_method: timing_method.ok_or(Error::ParseEventLog)?,
+            },
+            "ResetAndSetAttemptAsPb" => Command::ResetAndSetAttemptAsPb,
+            "Pause" => Command::Pause,
+            "Resume" => Command::Resume,
+            "UndoAllPauses" => Command::UndoAllPauses,
+            "AdjustOffset" => Command::AdjustOffset(time.ok_or(Error::ParseEventLog)?),
+            "AdjustTime" => Command::AdjustTime {
+                amount: time.ok_or(Error::ParseEventLog)?,
+                timing_method: timing_method.ok_or(Error::ParseEventLog)?,
+            },
+            "Undo" => Command::Undo,
+            "Redo" => Command::Redo,
+            "InitializeGameTime" => Command::InitializeGameTime,
+            "DeinitializeGameTime" => Command::DeinitializeGameTime,
+            "SetGameTime" => Command::SetGameTime(time.ok_or(Error::ParseEventLog)?),
+            "PauseGameTime" => Command::PauseGameTime,
+            "ResumeGameTime" => Command::ResumeGameTime,
+            "SetLoadingTimes" => Command::SetLoadingTimes(time.ok_or(Error::ParseEventLog)?),
+            "SetCustomVariable" => Command::SetCustomVariable {
+                name: name.ok_or(Error::ParseEventLog)?,
+                value: value.ok_or(Error::ParseEventLog)?,
+            },
+            _ => return Err(Error::ParseEventLog),
+        };
+
+        entries.push(Entry {
+            elapsed: elapsed.ok_or(Error::ParseEventLog)?,
+            real_time: real_time.ok_or(Error::ParseEventLog)?,
+            game_time,
+            command,
+        });
+
+        end_tag(reader)
+    })?;
+
+    *event_log = EventLog::from_entries(entries);
+    if truncated {
+        event_log.mark_as_truncated();
+    }
+
+    Ok(())
+}
+
+fn parse_variables(reader: &mut Reader, variables: &mut Vec<(String, String)>) -> Result<()> {
+    parse_children(reader, |reader, _, attributes| {
+        let mut name = String::new();
+        let mut value = String::new();
+        type_hint(attribute(attributes, "name", |t| name = t.into_owned()))?;
+        type_hint(text(reader, |t| value = t.into_owned()))?;
+        variables.push((name, value));
+        Ok(())
+    })
+}
+
+fn parse_tags(reader: &mut Reader, mut f: impl FnMut(String)) -> Result<()> {
+    parse_children(reader, |reader, _, _| text(reader, |t| f(t.into_owned())))
+}
+
+fn parse_gold_rules(reader: &mut Reader, run: &mut Run) -> Result<()> {
+    let mut gold_rules = GoldRules::default();
+
+    parse_children(reader, |reader, tag, _| match tag.name() {
+        "MaxGameTimePause" => time_span(reader, |t| gold_rules.max_game_time_pause = Some(t)),
+        "MinSegmentTimeAfterUndo" => {
+            time_span(reader, |t| gold_rules.min_segment_time_after_undo = Some(t))
+        }
+        _ => end_tag(reader),
+    })?;
+
+    run.set_gold_rules(gold_rules);
+
+    Ok(())
+}
+
+fn parse_comparison_generators(reader: &mut Reader, run: &mut Run) -> Result<()> {
+    let registry = Registry::default();
+    let mut generators = Vec::new();
+
+    parse_children::<_, Error>(reader, |reader, _, attributes| {
+        let mut name = Cow::Borrowed("");
+        type_hint(attribute(attributes, "name", |t| name = t))?;
+        text(reader, |settings| {
+            let settings = Some(&*settings).filter(|s| !s.is_empty());
+            generators.push(registry.create(&name, settings));
+        })
+    })?;
+
+    *run.comparison_generators_mut() = generators;
+
+    // The settings of the built-in generators are stored separately, so they
+    // need to be applied to the newly created generators.
+    let average_segments = *run.average_segments_comparison();
+    run.set_average_segments_comparison(average_segments);
+    let last_attempts = *run.last_attempts_comparison();
+    run.set_last_attempts_comparison(last_attempts);
+
+    Ok(())
+}
+
+fn parse_timing_method_bindings(reader: &mut Reader, run: &mut Run) -> Result<()> {
+    parse_children::<_, Error>(reader, |reader, _, attributes| {
+        let mut comparison = Cow::Borrowed("");
+        type_hint(attribute(attributes, "comparison", |t| comparison = t))?;
+        text(reader, |method| {
+            let method = match &*method {
+                "RealTime" => TimingMethod::RealTime,
+                "GameTime" => TimingMethod::GameTime,
+                _ => return,
+            };
+            run.set_bound_timing_method(&comparison, Some(method));
+        })
+    })
+}
+
+fn parse_average_segments(reader: &mut Reader, run: &mut Run) -> Result<()> {
+    let mut settings = AverageSegments::default();
+
+    parse_children::<_, Error>(reader, |reader, tag, _| match tag.name() {
+        "Weight" => text_parsed(reader, |v| settings = AverageSegments::new(v)),
+        _ => end_tag(reader),
+    })?;
+
+    run.set_average_segments_comparison(settings);
+
+    Ok(())
+}
+
+fn parse_last_attempts(reader: &mut Reader, run: &mut Run) -> Result<()> {
+    let mut settings = LastAttempts::default();
+
+    parse_children::<_, Error>(reader, |reader, tag, _| match tag.name() {
+        "Attempts" => text_parsed(reader, |v| settings.attempts = v),
+        "Statistic" => text(reader, |v| {
+            settings.statistic = match &*v {
+                "Median" => Statistic::Median,
+                _ => Statistic::Average,
+            }
+        }),
+        _ => end_tag(reader),
+    })?;
+
+    run.set_last_attempts_comparison(settings);
+
+    Ok(())
+}
+
 fn parse_auto_splitter_settings(
     _version: Version,
     reader: &mut Reader<'_>,
@@ -479,6 +866,15 @@ fn parse_auto_splitter_settings(
     Ok(())
 }
 
+/// Parses an `AutoSplitterSettings` element on its own, so other parsers can
+/// embed it.
+pub(super) fn parse_auto_splitter_settings_element(source: &str, run: &mut Run) -> Result<()> {
+    let mut reader = Reader::new(source);
+    parse_base(&mut reader, "AutoSplitterSettings", |reader, _| {
+        parse_auto_splitter_settings(Version::default(), reader, run)
+    })
+}
+
 #[cfg(feature = "auto-splitting")]
 fn parse_settings_map(reader: &mut Reader<'_>) -> settings::Map {
     let mut settings_map = settings::Map::new();
@@ -572,15 +968,118 @@ fn parse_settings_entry(
 
 /// Attempts to parse a LiveSplit splits file.
 pub fn parse(source: &str) -> Result<Run> {
+    parse_with_offset(source).map_err(|(error, _)| error)
+}
+
+/// Attempts to parse a LiveSplit splits file. If parsing fails, the byte
+/// offset at which the parser stopped is returned along with the error.
+pub(super) fn parse_with_offset(source: &str) -> core::result::Result<Run, (Error, usize)> {
+    let mut reader = Reader::new(source);
+
+    let mut image_buf = Vec::new();
+
+    let mut run = None;
+
+    type_hint(parse_base(&mut reader, "Run", |reader, attributes| {
+        let mut parsed = Run::new();
+        parse_run(reader, attributes, &mut image_buf, &mut parsed, None)
+            .map(|()| run = Some(parsed))
+    }))
+    .map_err(|error| (error, reader.offset_in(source)))?;
+
+    run.ok_or_else(|| {
+        (
+            Error::Xml {
+                source: XmlError::ElementNotFound,
+            },
+            reader.offset_in(source),
+        )
+    })
+}
+
+/// Attempts to recover as much as possible from a LiveSplit splits file that
+/// is corrupted, such as a file that got truncated or edited by hand.
+/// Malformed attempts and segment history elements are dropped instead of
+/// failing the whole file. If the file can't be read any further at some
+/// point, everything up to that point is kept. Elements that are missing
+/// keep their default values. Parsing only fails if the file isn't a LiveSplit
+/// splits file at all. The warnings describe everything that got dropped.
+pub fn parse_lenient(source: &str) -> Result<Recovered> {
     let mut reader = Reader::new(source);
 
     let mut image_buf = Vec::new();
 
     let mut run = Run::new();
+    let mut warnings = Vec::new();
+    let mut found = false;
+
+    let result = parse_base(&mut reader, "Run", |reader, attributes| {
+        found = true;
+        parse_run(
+            reader,
+            attributes,
+            &mut image_buf,
+            &mut run,
+            Some(&mut warnings),
+        )
+    });
+
+    if let Err(error) = result {
+        if !found {
+            return Err(error);
+        }
+        warnings.push(Warning::Truncated);
+    }
+
+    Ok(Recovered { run, warnings })
+}
+
+/// Attempts to parse a LiveSplit run bundle file, which contains multiple
+/// related runs.
+pub fn parse_bundle(source: &str) -> Result<RunBundle> {
+    let mut reader = Reader::new(source);
+
+    let mut image_buf = Vec::new();
 
+    let mut bundle = RunBundle::new();
+    let mut runs = Vec::new();
+
+    parse_base(&mut reader, "RunBundle", |reader, _| {
+        parse_children(reader, |reader, tag, _| match tag.name() {
+            "GameIcon" => image(reader, &mut image_buf, |i| {
+                bundle.set_game_icon(Image::new(i.into(), Image::ICON))
+            }),
+            "GameName" => text(reader, |t| bundle.set_game_name(t)),
+            "Runs" => parse_children(reader, |reader, tag, attributes| {
+                if tag.name() == "Run" {
+                    let mut run = Run::new();
+                    parse_run(reader, attributes, &mut image_buf, &mut run, None)
+                        .map(|()| runs.push(run))
+                } else {
+                    end_tag(reader)
+                }
+            }),
+            _ => end_tag(reader),
+        })
+    })?;
+
+    for run in runs {
+        bundle.push(run);
+    }
+
+    Ok(bundle)
+}
+
+fn parse_run(
+    reader: &mut Reader,
+    attributes: Attributes,
+    image_buf: &mut Vec<MaybeUninit<u8>>,
+    run: &mut Run,
+    mut warnings: Option<&mut Vec<Warning>>,
+) -> Result<()> {
     let mut required_flags = 0u8;
 
-    parse_base(&mut reader, "Run", |reader, attributes| {
+    {
         let mut version = Version(1, 0, 0, 0);
         type_hint(optional_attribute_escaped_err(attributes, "version", |t| {
             version = parse_version(t)?;
@@ -590,7 +1089,7 @@ pub fn parse(source: &str) -> Result<Run> {
         parse_children(reader, |reader, tag, _| match tag.name() {
             "GameIcon" => {
                 required_flags |= 1;
-                image(reader, &mut image_buf, |i| {
+                image(reader, image_buf, |i| {
                     run.set_game_icon(Image::new(i.into(), Image::ICON))
                 })
             }
@@ -610,14 +1109,22 @@ pub fn parse(source: &str) -> Result<Run> {
                 required_flags |= 1 << 4;
                 text_parsed(reader, |t| run.set_attempt_count(t))
             }
-            "AttemptHistory" => parse_attempt_history(version, reader, &mut run),
-            "RunHistory" => parse_run_history(version, reader, &mut run),
+            "AttemptHistory" => {
+                parse_attempt_history(version, reader, run, warnings.as_deref_mut())
+            }
+            "RunHistory" => parse_run_history(version, reader, run),
             "Metadata" => parse_metadata(version, reader, run.metadata_mut()),
             "Segments" => {
                 required_flags |= 1 << 5;
                 parse_children(reader, |reader, tag, _| {
                     if tag.name() == "Segment" {
-                        let segment = parse_segment(version, reader, &mut image_buf, &mut run)?;
+                        let segment = parse_segment(
+                            version,
+                            reader,
+                            image_buf,
+                            run,
+                            warnings.as_deref_mut(),
+                        )?;
                         run.push_segment(segment);
                         Ok(())
                     } else {
@@ -625,7 +1132,14 @@ pub fn parse(source: &str) -> Result<Run> {
                     }
                 })
             }
-            "AutoSplitterSettings" => parse_auto_splitter_settings(version, reader, &mut run),
+            "AutoSplitterSettings" => parse_auto_splitter_settings(version, reader, run),
+            "GoldRules" => parse_gold_rules(reader, run),
+            "ComparisonGenerators" => parse_comparison_generators(reader, run),
+            "FrameRate" => text_parsed(reader, |v| run.set_frame_rate(Some(v))),
+            "TimingMethodBindings" => parse_timing_method_bindings(reader, run),
+            "ExcludedAttemptTags" => parse_tags(reader, |t| run.set_attempt_tag_excluded(&t, true)),
+            "AverageSegmentsComparison" => parse_average_segments(reader, run),
+            "LastAttemptsComparison" => parse_last_attempts(reader, run),
             "LayoutPath" => text(reader, |t| {
                 run.set_linked_layout(if t == "?default" {
                     Some(LinkedLayout::Default)
@@ -636,16 +1150,16 @@ pub fn parse(source: &str) -> Result<Run> {
                 });
             }),
             _ => end_tag(reader),
-        })
-    })?;
+        })?;
+    }
 
-    if required_flags != (1 << 6) - 1 {
+    if warnings.is_none() && required_flags != (1 << 6) - 1 {
         return Err(Error::Xml {
             source: XmlError::ElementNotFound,
         });
     }
 
-    Ok(run)
+    Ok(())
 }
 
 #[cfg(test)]
@@ -28,7 +28,9 @@
 //! let run = parsed.run;
 //! ```
 
+pub mod binary;
 pub mod composite;
+pub mod csv;
 pub mod face_split;
 pub mod flitter;
 pub mod livesplit;
@@ -42,6 +44,7 @@ pub mod speedrun_igt;
 pub mod splitterino;
 pub mod splitterz;
 pub mod splitty;
+pub mod therun_gg;
 pub mod time_split_tracker;
 pub mod urn;
 pub mod wsplit;
@@ -50,4 +53,4 @@ mod timer_kind;
 
 pub use self::timer_kind::TimerKind;
 
-pub use composite::{parse, parse_and_fix};
+pub use composite::{parse, parse_and_fix, parse_with_report};
@@ -1,6 +1,6 @@
 //! Provides the parser for SpeedrunIGT splits files.
 
-use alloc::borrow::Cow;
+use alloc::{borrow::Cow, collections::BTreeMap};
 use serde_derive::Deserialize;
 use time::Duration;
 
@@ -38,14 +38,34 @@ struct Splits<'a> {
     category: Cow<'a, str>,
     #[serde(borrow)]
     run_type: Cow<'a, str>,
+    #[serde(default = "default_true")]
+    is_completed: bool,
     is_coop: bool,
     is_hardcore: bool,
     #[serde(borrow)]
     world_name: Cow<'a, str>,
     date: i64,
+    #[serde(default)]
+    retimed_igt: Option<i64>,
     final_igt: i64,
     final_rta: i64,
     timelines: Vec<Timeline<'a>>,
+    #[serde(default, borrow)]
+    advancements: BTreeMap<Cow<'a, str>, Advancement>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct Advancement {
+    complete: bool,
+    is_advancement: bool,
+    #[serde(default)]
+    igt: i64,
+    #[serde(default)]
+    rta: i64,
 }
 
 #[derive(Deserialize)]
@@ -76,6 +96,23 @@ fn map_seed_type<'a>(
     }
 }
 
+fn title_case(name: &str) -> String {
+    let mut new_name = String::with_capacity(name.len());
+    let mut last_is_space = true;
+    for c in name.chars() {
+        if c == '_' {
+            new_name.push(' ');
+            last_is_space = true;
+        } else if last_is_space {
+            new_name.extend(c.to_uppercase());
+            last_is_space = false;
+        } else {
+            new_name.push(c);
+        }
+    }
+    new_name
+}
+
 fn time(rta: i64, igt: i64) -> Time {
     Time {
         real_time: Some(Duration::milliseconds(rta).into()),
@@ -433,55 +470,69 @@ pub fn parse(source: &str) -> Result<Run> {
     world_name.set_value(splits.world_name);
     world_name.is_permanent = true;
 
-    for timeline in splits.timelines {
-        let name: Cow<'_, str> = match &*timeline.name {
-            "crafted_ender_eye" => "Crafted Ender Eye".into(),
-            "enter_bastion" => "Found Bastion".into(),
-            "enter_end" => "Enter The End".into(),
-            "enter_fortress" => "Found Fortress".into(),
-            "enter_nether" => "Enter Nether".into(),
-            "enter_stronghold" => "Enter Stronghold".into(),
-            "found_villager" => "Found Villager".into(),
-            "got_trident" => "Got Trident".into(),
-            "kill_elder_guardian" => "Defeat Elder Guardian".into(),
-            "kill_ender_dragon" => "Defeat Ender Dragon".into(),
-            "kill_warden" => "Defeat Warden".into(),
-            "kill_wither" => "Defeat Wither".into(),
-            "nether_travel" => "Nether Travel".into(),
-            "pick_gold_block" => "Pick Gold Block".into(),
-            "pickup_book" => "Pickup Book".into(),
-            "sleep_on_tower" => "Sleep on Tower".into(),
-            "trade_with_villager" => "Trade with Villager".into(),
-            name => {
-                if let Some(rem) = name.strip_prefix("portal_no_") {
-                    format!("Portal No. {rem}").into()
-                } else if let Some(rem) = name.strip_prefix("got_shell_") {
-                    format!("Got Nautilus Shell {rem}").into()
-                } else {
-                    let mut new_name = String::with_capacity(name.len());
-                    let mut last_is_space = true;
-                    for c in name.chars() {
-                        if c == '_' {
-                            new_name.push(' ');
-                            last_is_space = true;
-                        } else if last_is_space {
-                            new_name.extend(c.to_uppercase());
-                            last_is_space = false;
-                        } else {
-                            new_name.push(c);
-                        }
+    // The timelines don't track the advancements, so for All Advancements
+    // every advancement that got completed becomes its own split instead of
+    // the timelines.
+    if splits.category == "ALL_ADVANCEMENTS" {
+        let mut advancements = splits
+            .advancements
+            .iter()
+            .filter(|(_, advancement)| advancement.complete && advancement.is_advancement)
+            .collect::<Vec<_>>();
+        advancements.sort_by_key(|(_, advancement)| (advancement.igt, advancement.rta));
+
+        for (id, advancement) in advancements {
+            let name = id.rsplit(['/', ':']).next().unwrap_or(id);
+            let mut segment = Segment::new(title_case(name));
+            segment.set_personal_best_split_time(time(advancement.rta, advancement.igt));
+            run.push_segment(segment);
+        }
+    } else {
+        for timeline in splits.timelines {
+            let name: Cow<'_, str> = match &*timeline.name {
+                "crafted_ender_eye" => "Crafted Ender Eye".into(),
+                "enter_bastion" => "Found Bastion".into(),
+                "enter_end" => "Enter The End".into(),
+                "enter_fortress" => "Found Fortress".into(),
+                "enter_nether" => "Enter Nether".into(),
+                "enter_stronghold" => "Enter Stronghold".into(),
+                "found_villager" => "Found Villager".into(),
+                "got_trident" => "Got Trident".into(),
+                "kill_elder_guardian" => "Defeat Elder Guardian".into(),
+                "kill_ender_dragon" => "Defeat Ender Dragon".into(),
+                "kill_warden" => "Defeat Warden".into(),
+                "kill_wither" => "Defeat Wither".into(),
+                "nether_travel" => "Nether Travel".into(),
+                "pick_gold_block" => "Pick Gold Block".into(),
+                "pickup_book" => "Pickup Book".into(),
+                "sleep_on_tower" => "Sleep on Tower".into(),
+                "trade_with_villager" => "Trade with Villager".into(),
+                name => {
+                    if let Some(rem) = name.strip_prefix("portal_no_") {
+                        format!("Portal No. {rem}").into()
+                    } else if let Some(rem) = name.strip_prefix("got_shell_") {
+                        format!("Got Nautilus Shell {rem}").into()
+                    } else {
+                        title_case(name).into()
                     }
-                    new_name.into()
                 }
-            }
-        };
-        let mut segment = Segment::new(name);
-        segment.set_personal_best_split_time(time(timeline.rta, timeline.igt));
-        run.push_segment(segment);
+            };
+            let mut segment = Segment::new(name);
+            segment.set_personal_best_split_time(time(timeline.rta, timeline.igt));
+            run.push_segment(segment);
+        }
     }
 
+    // Runs that didn't get completed still have a final time, which is just
+    // the time at which the record got saved, so it's not a split.
     let mut segment = Segment::new("Finish");
-    segment.set_personal_best_split_time(time(splits.final_rta, splits.final_igt));
+    if splits.is_completed {
+        let igt = splits
+            .retimed_igt
+            .filter(|&igt| igt > 0)
+            .unwrap_or(splits.final_igt);
+        segment.set_personal_best_split_time(time(splits.final_rta, igt));
+    }
     run.push_segment(segment);
 
     process_segments(&mut run, splits.date);
@@ -511,6 +562,10 @@ fn process_segments(run: &mut Run, ended: i64) {
 
     for segment in run.segments_mut() {
         let split_time = segment.personal_best_split_time();
+        if split_time.real_time.is_none() {
+            // Only the final split is missing, if the run wasn't completed.
+            break;
+        }
         // This assumes that there aren't any skipped segments, which should
         // always be the case with SpeedRunIGT.
         let segment_time = split_time - previous_split;
@@ -519,6 +574,12 @@ fn process_segments(run: &mut Run, ended: i64) {
         previous_split = split_time;
     }
 
+    let final_time = run
+        .segments()
+        .last()
+        .map(|segment| segment.personal_best_split_time())
+        .unwrap_or_default();
+
     let ended = DateTime::from_unix_timestamp(ended / 1000)
         .ok()
         .map(|date| AtomicDateTime::new(date + Duration::milliseconds(ended % 1000), false));
@@ -530,5 +591,5 @@ fn process_segments(run: &mut Run, ended: i64) {
         ))
     });
 
-    run.add_attempt_with_index(previous_split, 1, started, ended, None);
+    run.add_attempt_with_index(final_time, 1, started, ended, None);
 }
@@ -112,14 +112,14 @@ struct DetailedTime {
     pause_time: u64,
 }
 
-fn parse_segment_time(SegmentTime { igt, rta }: SegmentTime) -> [TimeSpan; 2] {
+fn parse_segment_time(SegmentTime { igt, rta }: &SegmentTime) -> [TimeSpan; 2] {
     [
-        TimeSpan::from_milliseconds((rta.raw_time - rta.pause_time) as _),
-        TimeSpan::from_milliseconds((igt.raw_time - igt.pause_time) as _),
+        TimeSpan::from_milliseconds(rta.raw_time.saturating_sub(rta.pause_time) as _),
+        TimeSpan::from_milliseconds(igt.raw_time.saturating_sub(igt.pause_time) as _),
     ]
 }
 
-fn parse_best_segment_time(segment_time: SegmentTime) -> Time {
+fn parse_best_segment_time(segment_time: &SegmentTime) -> Time {
     let [rta, igt] = parse_segment_time(segment_time);
     let mut time = Time::new();
     if rta != TimeSpan::zero() {
@@ -134,7 +134,7 @@ fn parse_best_segment_time(segment_time: SegmentTime) -> Time {
 fn parse_split_time(
     total_rta: &mut TimeSpan,
     total_igt: &mut TimeSpan,
-    segment_time: SegmentTime,
+    segment_time: &SegmentTime,
 ) -> Time {
     let [rta, igt] = parse_segment_time(segment_time);
 
@@ -171,7 +171,6 @@ pub fn parse(source: &str) -> Result<Run> {
     run.metadata_mut().set_region_name(splits.game.region);
 
     // FIXME: Region may need to be translated to speedrun.com's region.
-    // FIXME: Parse pause times.
     // FIXME: Parse default timing method, if we ever store that.
 
     if let Some(start_delay) = splits.start_delay {
@@ -179,27 +178,65 @@ pub fn parse(source: &str) -> Result<Run> {
     }
 
     let (mut total_rta, mut total_igt) = (TimeSpan::zero(), TimeSpan::zero());
+    let mut pause_time = 0;
 
     run.segments_mut()
         .extend(splits.segments.into_iter().map(|split| {
             let mut segment = Segment::new(split.name);
 
             if !split.skipped
-                && let Some(personal_best) = split.personal_best
+                && let Some(personal_best) = &split.personal_best
             {
                 segment.set_personal_best_split_time(parse_split_time(
                     &mut total_rta,
                     &mut total_igt,
                     personal_best,
                 ));
+                pause_time += personal_best.rta.pause_time;
             }
 
-            if let Some(overall_best) = split.overall_best {
+            if let Some(overall_best) = &split.overall_best {
                 segment.set_best_segment_time(parse_best_segment_time(overall_best));
             }
 
             segment
         }));
 
+    // Splitterino doesn't store an attempt history, but the Personal Best
+    // itself is an attempt, which is the only place the pause times can be
+    // stored.
+    let final_time = run
+        .segments()
+        .last()
+        .map(|segment| segment.personal_best_split_time())
+        .unwrap_or_default();
+
+    if final_time.real_time.is_some() || final_time.game_time.is_some() {
+        let mut previous = Time::zero();
+        for segment in run.segments_mut() {
+            let split_time = segment.personal_best_split_time();
+            let segment_time = Time {
+                real_time: catch! { split_time.real_time? - previous.real_time? },
+                game_time: catch! { split_time.game_time? - previous.game_time? },
+            };
+            segment.segment_history_mut().insert(1, segment_time);
+            if let Some(real_time) = split_time.real_time {
+                previous.real_time = Some(real_time);
+            }
+            if let Some(game_time) = split_time.game_time {
+                previous.game_time = Some(game_time);
+            }
+        }
+
+        run.add_attempt_with_index(
+            final_time,
+            1,
+            None,
+            None,
+            Some(TimeSpan::from_milliseconds(pause_time as _)).filter(|&p| p != TimeSpan::zero()),
+        );
+        run.set_attempt_count(1);
+    }
+
     Ok(run)
 }
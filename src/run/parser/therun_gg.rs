@@ -0,0 +1,179 @@
+//! Provides the parser for splits exported from therun.gg.
+//!
+//! The export is a JSON document that contains the game and category
+//! metadata, the segments with their Personal Best split times and Best
+//! Segment times, and the attempt history. All times are stored in
+//! milliseconds. The split times of each attempt are cumulative, with `null`
+//! marking a skipped split. An attempt that got reset only stores the split
+//! times of the splits it reached.
+
+use crate::{
+    AtomicDateTime, Run, Segment, Time, TimeSpan, TimingMethod,
+    platform::{DateTime, prelude::*},
+};
+use alloc::{borrow::Cow, collections::BTreeMap};
+use core::result::Result as StdResult;
+use serde_derive::Deserialize;
+use serde_json::Error as JsonError;
+
+/// The Error type for splits files that couldn't be parsed by the therun.gg
+/// Parser.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(context(suffix(false)))]
+pub enum Error {
+    /// Failed to parse JSON.
+    Json {
+        /// The underlying error.
+        #[cfg_attr(not(feature = "std"), snafu(source(false)))]
+        source: JsonError,
+    },
+    /// An attempt contains more split times than there are segments.
+    TooManySplitTimes,
+}
+
+/// The Result type for the therun.gg Parser.
+pub type Result<T> = StdResult<T, Error>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Splits<'a> {
+    #[serde(borrow)]
+    game: Cow<'a, str>,
+    #[serde(borrow)]
+    run: Cow<'a, str>,
+    #[serde(default, borrow)]
+    platform: Option<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    region: Option<Cow<'a, str>>,
+    #[serde(default)]
+    emulator: bool,
+    #[serde(default, borrow)]
+    variables: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+    #[serde(default)]
+    timing_method: TimingMethodPayload,
+    #[serde(default)]
+    attempt_count: Option<u32>,
+    #[serde(borrow)]
+    splits: Vec<SplitPayload<'a>>,
+    #[serde(default)]
+    history: Vec<AttemptPayload>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+enum TimingMethodPayload {
+    #[default]
+    RealTime,
+    GameTime,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitPayload<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(default)]
+    pb_split_time: Option<u64>,
+    #[serde(default)]
+    best_segment_time: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AttemptPayload {
+    #[serde(default)]
+    started_at: Option<i64>,
+    #[serde(default)]
+    ended_at: Option<i64>,
+    #[serde(default)]
+    pause_time: Option<u64>,
+    split_times: Vec<Option<u64>>,
+}
+
+fn time(method: TimingMethod, milliseconds: Option<u64>) -> Time {
+    let mut time = Time::new();
+    time[method] = milliseconds.map(|ms| TimeSpan::from_milliseconds(ms as f64));
+    time
+}
+
+fn date_time(milliseconds: Option<i64>) -> Option<AtomicDateTime> {
+    let date_time = DateTime::from_unix_timestamp_nanos(milliseconds? as i128 * 1_000_000).ok()?;
+    Some(AtomicDateTime::new(date_time, false))
+}
+
+/// Attempts to parse splits exported from therun.gg.
+pub fn parse(source: &str) -> Result<Run> {
+    let splits: Splits<'_> =
+        serde_json::from_str(source).map_err(|source| Error::Json { source })?;
+
+    let method = match splits.timing_method {
+        TimingMethodPayload::RealTime => TimingMethod::RealTime,
+        TimingMethodPayload::GameTime => TimingMethod::GameTime,
+    };
+
+    let mut run = Run::new();
+
+    run.set_game_name(splits.game);
+    run.set_category_name(splits.run);
+
+    let metadata = run.metadata_mut();
+    if let Some(platform) = splits.platform {
+        metadata.set_platform_name(platform);
+    }
+    if let Some(region) = splits.region {
+        metadata.set_region_name(region);
+    }
+    metadata.set_emulator_usage(splits.emulator);
+    for (name, value) in splits.variables {
+        metadata.set_speedrun_com_variable(name, value);
+    }
+
+    for split in splits.splits {
+        let mut segment = Segment::new(split.name);
+        segment.set_personal_best_split_time(time(method, split.pb_split_time));
+        segment.set_best_segment_time(time(method, split.best_segment_time));
+        run.push_segment(segment);
+    }
+
+    let mut attempt_count = 0;
+
+    for (attempt_index, attempt) in (1..).zip(splits.history) {
+        if attempt.split_times.len() > run.len() {
+            return Err(Error::TooManySplitTimes);
+        }
+
+        let mut previous_split_time = 0;
+        for (segment, &split_time) in run.segments_mut().iter_mut().zip(&attempt.split_times) {
+            let segment_time = split_time.map(|split_time| {
+                let segment_time = split_time.saturating_sub(previous_split_time);
+                previous_split_time = split_time;
+                segment_time
+            });
+            segment
+                .segment_history_mut()
+                .insert(attempt_index, time(method, segment_time));
+        }
+
+        let final_time = if attempt.split_times.len() == run.len() {
+            attempt.split_times.last().copied().flatten()
+        } else {
+            None
+        };
+
+        run.add_attempt_with_index(
+            time(method, final_time),
+            attempt_index,
+            date_time(attempt.started_at),
+            date_time(attempt.ended_at),
+            attempt
+                .pause_time
+                .map(|ms| TimeSpan::from_milliseconds(ms as f64)),
+        );
+
+        attempt_count += 1;
+    }
+
+    run.set_attempt_count(splits.attempt_count.unwrap_or(attempt_count));
+
+    Ok(run)
+}
@@ -4,9 +4,12 @@ use core::fmt;
 
 /// Describes the different Timers available that store splits files.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
 pub enum TimerKind<'a> {
     /// LiveSplit
     LiveSplit,
+    /// livesplit-core's compact binary splits file format
+    Binary,
     /// WSplit
     WSplit,
     /// SplitterZ
@@ -37,6 +40,8 @@ pub enum TimerKind<'a> {
     Splitterino,
     /// SpeedRunIGT
     SpeedRunIGT,
+    /// therun.gg
+    TheRunGg,
     /// A Generic Timer. The name of the timer is associated with the variant.
     /// "Generic Timer" is used if there is no known name.
     Generic(Cow<'a, str>),
@@ -47,6 +52,7 @@ impl TimerKind<'_> {
     pub fn into_owned(self) -> TimerKind<'static> {
         match self {
             TimerKind::LiveSplit => TimerKind::LiveSplit,
+            TimerKind::Binary => TimerKind::Binary,
             TimerKind::WSplit => TimerKind::WSplit,
             TimerKind::SplitterZ => TimerKind::SplitterZ,
             TimerKind::ShitSplit => TimerKind::ShitSplit,
@@ -62,6 +68,7 @@ impl TimerKind<'_> {
             TimerKind::SourceLiveTimer => TimerKind::SourceLiveTimer,
             TimerKind::Splitterino => TimerKind::Splitterino,
             TimerKind::SpeedRunIGT => TimerKind::SpeedRunIGT,
+            TimerKind::TheRunGg => TimerKind::TheRunGg,
             TimerKind::Generic(v) => TimerKind::Generic(v.into_owned().into()),
         }
     }
@@ -71,6 +78,7 @@ impl fmt::Display for TimerKind<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match self {
             TimerKind::LiveSplit => "LiveSplit",
+            TimerKind::Binary => "LiveSplit (Binary)",
             TimerKind::WSplit => "WSplit",
             TimerKind::SplitterZ => "SplitterZ",
             TimerKind::ShitSplit => "ShitSplit",
@@ -86,6 +94,7 @@ impl fmt::Display for TimerKind<'_> {
             TimerKind::SourceLiveTimer => "SourceLiveTimer",
             TimerKind::Splitterino => "Splitterino",
             TimerKind::SpeedRunIGT => "SpeedRunIGT",
+            TimerKind::TheRunGg => "therun.gg",
             TimerKind::Generic(name) => name,
         })
     }
@@ -0,0 +1,281 @@
+//! The Binary Saver saves Runs in livesplit-core's compact binary splits file
+//! format. Unlike the XML based LiveSplit splits files, the binary format
+//! doesn't need to format or escape any text and stores all the times as
+//! integers, which makes both saving and loading runs with huge attempt
+//! histories a lot faster. The format is versioned, so files saved by this
+//! version of livesplit-core can still be loaded by future versions.
+//!
+//! The file starts with the magic bytes `LSRB`, followed by the version of
+//! the format as a little endian `u16`. All the integers afterwards are
+//! stored as LEB128 variable length integers, with signed integers being zig
+//! zag encoded. Strings, images and lists are prefixed with their length.
+//! Times are stored as whole seconds and the nanoseconds past them.
+//!
+//! # Examples
+//!
+//! Using the Binary Saver to save a Run as a binary splits file.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::binary;
+//! use livesplit_core::{Run, Segment};
+//! use std::fs;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! // Save the Run as a binary splits file.
+//! let mut buf = Vec::new();
+//! binary::save_run(&run, &mut buf);
+//! fs::write("path/to/splits_file.lsb", buf).expect("Couldn't save the splits file");
+//! ```
+
+use crate::{
+    AtomicDateTime, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
+    comparison::{RACE_COMPARISON_PREFIX, last_attempts::Statistic},
+    platform::prelude::*,
+    run::LinkedLayout,
+    settings::Image,
+};
+
+/// The magic bytes every binary splits file starts with.
+pub(crate) const MAGIC: &[u8; 4] = b"LSRB";
+
+/// The version of the binary splits file format that gets saved.
+pub(crate) const VERSION: u16 = 1;
+
+struct Encoder<'b> {
+    buf: &'b mut Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn u64(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.u64(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.buf.push(value as u8);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.usize(bytes.len());
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn str(&mut self, text: &str) {
+        self.bytes(text.as_bytes());
+    }
+
+    fn image(&mut self, image: &Image) {
+        self.bytes(image.data());
+    }
+
+    fn time_span(&mut self, time: TimeSpan) {
+        let (seconds, nanoseconds) = time.to_seconds_and_subsec_nanoseconds();
+        self.i64(seconds);
+        self.i64(nanoseconds.into());
+    }
+
+    fn optional_time_span(&mut self, time: Option<TimeSpan>) {
+        self.bool(time.is_some());
+        if let Some(time) = time {
+            self.time_span(time);
+        }
+    }
+
+    fn time(&mut self, time: Time) {
+        self.buf
+            .push(time.real_time.is_some() as u8 | ((time.game_time.is_some() as u8) << 1));
+        if let Some(time) = time.real_time {
+            self.time_span(time);
+        }
+        if let Some(time) = time.game_time {
+            self.time_span(time);
+        }
+    }
+
+    fn optional_date_time(&mut self, date_time: Option<AtomicDateTime>) {
+        self.bool(date_time.is_some());
+        if let Some(date_time) = date_time {
+            self.i64(date_time.time.unix_timestamp());
+            self.u64(date_time.time.nanosecond().into());
+            self.bool(date_time.synced_with_atomic_clock);
+        }
+    }
+
+    fn strings<'a>(&mut self, strings: impl ExactSizeIterator<Item = &'a str>) {
+        self.usize(strings.len());
+        for string in strings {
+            self.str(string);
+        }
+    }
+
+    fn variables(&mut self, variables: &[(String, String)]) {
+        self.usize(variables.len());
+        for (name, value) in variables {
+            self.str(name);
+            self.str(value);
+        }
+    }
+
+    fn timing_method(&mut self, method: TimingMethod) {
+        self.buf.push(match method {
+            TimingMethod::RealTime => 0,
+            TimingMethod::GameTime => 1,
+        });
+    }
+}
+
+/// Saves the Run in use by the Timer provided as a binary splits file by
+/// appending it to the buffer provided.
+pub fn save_timer(timer: &Timer, buf: &mut Vec<u8>) {
+    let run = if timer.current_phase() == TimerPhase::NotRunning {
+        timer.run()
+    } else {
+        &timer.clone().into_run(true)
+    };
+    save_run(run, buf)
+}
+
+/// Saves a Run as a binary splits file by appending it to the buffer
+/// provided. Use the `save_timer` function if the Run is in use by a timer in
+/// order to properly save the current attempt as well.
+pub fn save_run(run: &Run, buf: &mut Vec<u8>) {
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&VERSION.to_le_bytes());
+
+    let e = &mut Encoder { buf };
+
+    e.image(run.game_icon());
+    e.str(run.game_name());
+    e.str(run.category_name());
+    e.time_span(run.offset());
+    e.u64(run.attempt_count().into());
+
+    let metadata = run.metadata();
+    e.str(metadata.run_id());
+    e.str(metadata.platform_name());
+    e.bool(metadata.uses_emulator());
+    e.str(metadata.region_name());
+    e.usize(metadata.speedrun_com_variables().count());
+    for (name, value) in metadata.speedrun_com_variables() {
+        e.str(name);
+        e.str(value);
+    }
+    let custom_variables = || {
+        metadata
+            .custom_variables()
+            .filter(|(_, var)| var.is_permanent)
+    };
+    e.usize(custom_variables().count());
+    for (name, var) in custom_variables() {
+        e.str(name);
+        e.str(&var.value);
+    }
+
+    match run.linked_layout() {
+        None => e.buf.push(0),
+        Some(LinkedLayout::Default) => e.buf.push(1),
+        Some(LinkedLayout::Path(path)) => {
+            e.buf.push(2);
+            e.str(path);
+        }
+    }
+
+    e.usize(run.attempt_history().len());
+    for attempt in run.attempt_history() {
+        e.i64(attempt.index().into());
+        e.time(attempt.time());
+        e.optional_time_span(attempt.pause_time());
+        e.optional_date_time(attempt.started());
+        e.optional_date_time(attempt.ended());
+        e.variables(attempt.variables());
+        e.strings(attempt.tags().iter().map(String::as_str));
+        e.usize(attempt.split_variables().len());
+        for (segment_index, values) in attempt.split_variables() {
+            e.usize(*segment_index);
+            e.variables(values);
+        }
+    }
+
+    let comparisons = || {
+        run.custom_comparisons()
+            .iter()
+            .filter(|c| !c.starts_with(RACE_COMPARISON_PREFIX))
+    };
+    e.usize(comparisons().count());
+    for comparison in comparisons() {
+        e.str(comparison);
+    }
+
+    e.usize(run.len());
+    for segment in run.segments() {
+        e.str(segment.name());
+        e.image(segment.icon());
+        for comparison in comparisons() {
+            e.time(segment.comparison(comparison));
+        }
+        e.time(segment.best_segment_time());
+        let history = segment.segment_history();
+        e.usize(history.iter().len());
+        for &(index, time) in history {
+            e.i64(index.into());
+            e.time(time);
+        }
+    }
+
+    let gold_rules = run.gold_rules();
+    e.optional_time_span(gold_rules.max_game_time_pause);
+    e.optional_time_span(gold_rules.min_segment_time_after_undo);
+
+    let average_segments = run.average_segments_comparison();
+    e.buf
+        .extend_from_slice(&average_segments.weight().to_le_bytes());
+    let last_attempts = run.last_attempts_comparison();
+    e.u64(last_attempts.attempts.into());
+    e.buf.push(match last_attempts.statistic {
+        Statistic::Average => 0,
+        Statistic::Median => 1,
+    });
+
+    let generators = run.comparison_generators();
+    e.usize(generators.len());
+    for generator in generators {
+        e.str(generator.name());
+        e.str(generator.settings().as_deref().unwrap_or_default());
+    }
+
+    let bindings = run.timing_method_bindings();
+    e.usize(bindings.len());
+    for (comparison, method) in bindings {
+        e.str(comparison);
+        e.timing_method(*method);
+    }
+
+    e.strings(run.excluded_attempt_tags().iter().map(String::as_str));
+
+    e.bool(run.frame_rate().is_some());
+    if let Some(frame_rate) = run.frame_rate() {
+        e.buf.extend_from_slice(&frame_rate.to_le_bytes());
+    }
+
+    // The auto splitter settings are small and already encoded as XML, so
+    // they are stored the same way they are in LiveSplit splits files.
+    let mut auto_splitter_settings = String::new();
+    let _ = super::livesplit::write_auto_splitter_settings(&mut auto_splitter_settings, run);
+    e.str(&auto_splitter_settings);
+}
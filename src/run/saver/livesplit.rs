@@ -27,11 +27,18 @@
 #[cfg(feature = "auto-splitting")]
 use crate::run::AutoSplitterSettings;
 use crate::{
-    DateTime, Run, Time, Timer, TimerPhase,
+    DateTime, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod,
+    comparison::{
+        AverageSegments, RACE_COMPARISON_PREFIX, default_generators,
+        last_attempts::{LastAttempts, Statistic},
+    },
     platform::prelude::*,
-    run::LinkedLayout,
+    run::{Attempt, GoldRules, LinkedLayout, RunBundle, Segment},
     settings::Image,
-    timing::formatter::{Complete, TimeFormatter},
+    timing::{
+        event_log::{Command, EventLog},
+        formatter::{Complete, TimeFormatter},
+    },
     util::xml::{AttributeWriter, DisplayAlreadyEscaped, NO_ATTRIBUTES, Text, Writer},
 };
 use alloc::borrow::Cow;
@@ -40,7 +47,9 @@ use core::{fmt, mem::MaybeUninit};
 use livesplit_auto_splitting::settings;
 use time::UtcOffset;
 
-const LSS_IMAGE_HEADER: &[u8; 156] = include_bytes!("lss_image_header.bin");
+pub(crate) const LSS_IMAGE_HEADER: &[u8; 156] = include_bytes!("lss_image_header.bin");
+
+const RUN_VERSION: &str = "1.8.0";
 
 const fn bool(value: bool) -> Text<'static> {
     Text::new_escaped(if value { "True" } else { "False" })
@@ -66,7 +75,148 @@ where
     })
 }
 
-fn image<W: fmt::Write>(
+/// Writes the values of custom variables that were stored for an attempt. As
+/// most attempts don't have any, nothing is written if there are none.
+fn variables<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    variables: &[(String, String)],
+) -> fmt::Result {
+    if variables.is_empty() {
+        return Ok(());
+    }
+    writer.tag_with_content(tag, NO_ATTRIBUTES, |writer| {
+        variable_list(writer, variables)
+    })
+}
+
+/// Writes the tags of an attempt or the excluded attempt tags of a run.
+/// Nothing is written if there are none.
+fn tags<W: fmt::Write>(writer: &mut Writer<W>, tag: &str, tags: &[String]) -> fmt::Result {
+    if tags.is_empty() {
+        return Ok(());
+    }
+    writer.tag_with_content(tag, NO_ATTRIBUTES, |writer| {
+        for tag in tags {
+            writer.tag_with_text_content("Tag", NO_ATTRIBUTES, tag.as_str())?;
+        }
+        Ok(())
+    })
+}
+
+/// Writes the event log of an attempt. Nothing is written if it is empty.
+fn event_log<W: fmt::Write>(writer: &mut Writer<W>, event_log: &EventLog) -> fmt::Result {
+    if event_log.is_empty() {
+        return Ok(());
+    }
+    writer.tag("EventLog", |mut tag| {
+        if event_log.is_truncated() {
+            tag.attribute("truncated", bool(true))?;
+        }
+        tag.content(|writer| {
+            for entry in event_log.entries() {
+                writer.tag("Entry", |mut tag| {
+                    time_span_attribute(&mut tag, "elapsed", entry.elapsed)?;
+                    time_span_attribute(&mut tag, "realTime", entry.real_time)?;
+                    if let Some(game_time) = entry.game_time {
+                        time_span_attribute(&mut tag, "gameTime", game_time)?;
+                    }
+                    command(&mut tag, &entry.command)
+                })?;
+            }
+            Ok(())
+        })
+    })
+}
+
+fn command<W: fmt::Write>(tag: &mut AttributeWriter<W>, command: &Command) -> fmt::Result {
+    let name = match command {
+        Command::Start => "Start",
+        Command::StartAtSegment { .. } => "StartAtSegment",
+        Command::Split => "Split",
+        Command::SkipSplit => "SkipSplit",
+        Command::UndoSplit => "UndoSplit",
+        Command::Reset { .. } => "Reset",
+        Command::ResetAndSetAttemptAsPb => "ResetAndSetAttemptAsPb",
+        Command::Pause => "Pause",
+        Command::Resume => "Resume",
+        Command::UndoAllPauses => "UndoAllPauses",
+        Command::AdjustOffset(_) => "AdjustOffset",
+        Command::AdjustTime { .. } => "AdjustTime",
+        Command::Undo => "Undo",
+        Command::Redo => "Redo",
+        Command::InitializeGameTime => "InitializeGameTime",
+        Command::DeinitializeGameTime => "DeinitializeGameTime",
+        Command::SetGameTime(_) => "SetGameTime",
+        Command::PauseGameTime => "PauseGameTime",
+        Command::ResumeGameTime => "ResumeGameTime",
+        Command::SetLoadingTimes(_) => "SetLoadingTimes",
+        Command::SetCustomVariable { .. } => "SetCustomVariable",
+    };
+    tag.attribute("command", Text::new_escaped(name))?;
+
+    match command {
+        Command::StartAtSegment {
+            segment_index,
+            comparison,
+            feed_segment_history,
+        } => {
+            tag.attribute("segment", DisplayAlreadyEscaped(segment_index))?;
+            tag.attribute("comparison", comparison.as_str())?;
+            tag.attribute("feedSegmentHistory", bool(*feed_segment_history))
+        }
+        &Command::Reset {
+            update_splits,
+            timing_method,
+        } => {
+            tag.attribute("updateSplits", bool(update_splits))?;
+            tag.attribute("timingMethod", timing_method_name(timing_method))
+        }
+        &Command::AdjustOffset(amount) => time_span_attribute(tag, "amount", amount),
+        &Command::AdjustTime {
+            amount,
+            timing_method,
+        } => {
+            time_span_attribute(tag, "amount", amount)?;
+            tag.attribute("timingMethod", timing_method_name(timing_method))
+        }
+        &Command::SetGameTime(time) | &Command::SetLoadingTimes(time) => {
+            time_span_attribute(tag, "time", time)
+        }
+        Command::SetCustomVariable { name, value } => {
+            tag.attribute("name", name.as_str())?;
+            tag.attribute("value", value.as_str())
+        }
+        _ => Ok(()),
+    }
+}
+
+const fn timing_method_name(timing_method: TimingMethod) -> Text<'static> {
+    Text::new_escaped(match timing_method {
+        TimingMethod::RealTime => "RealTime",
+        TimingMethod::GameTime => "GameTime",
+    })
+}
+
+fn time_span_attribute<W: fmt::Write>(
+    tag: &mut AttributeWriter<W>,
+    key: &str,
+    time: TimeSpan,
+) -> fmt::Result {
+    tag.attribute(key, DisplayAlreadyEscaped(Complete.format(time)))
+}
+
+fn variable_list<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    variables: &[(String, String)],
+) -> fmt::Result {
+    for (name, value) in variables {
+        writer.tag_with_text_content("Variable", [("name", name.as_str())], value.as_str())?;
+    }
+    Ok(())
+}
+
+pub(crate) fn image<W: fmt::Write>(
     writer: &mut Writer<W>,
     tag: &str,
     image: &Image,
@@ -174,142 +324,573 @@ pub fn save_run<W: fmt::Write>(run: &Run, writer: W) -> fmt::Result {
     let base64_buf = &mut Vec::new();
     let image_buf = &mut Cow::Borrowed(&LSS_IMAGE_HEADER[..]);
 
-    writer.tag_with_content("Run", [("version", Text::new_escaped("1.8.0"))], |writer| {
-        image(writer, "GameIcon", run.game_icon(), base64_buf, image_buf)?;
-        writer.tag_with_text_content("GameName", NO_ATTRIBUTES, run.game_name())?;
-        writer.tag_with_text_content("CategoryName", NO_ATTRIBUTES, run.category_name())?;
+    write_run(writer, run, base64_buf, image_buf)
+}
 
-        writer.tag_with_content("Metadata", NO_ATTRIBUTES, |writer| {
-            let metadata = run.metadata();
-            writer.empty_tag("Run", [("id", metadata.run_id())])?;
-            writer.tag_with_text_content(
-                "Platform",
-                [("usesEmulator", bool(metadata.uses_emulator()))],
-                metadata.platform_name(),
-            )?;
-            writer.tag_with_text_content("Region", NO_ATTRIBUTES, metadata.region_name())?;
-            scoped_iter(
+/// The amount of attempts of the attempt history, or of the times of a
+/// segment's history, that are written per chunk by [`Chunks`].
+const ATTEMPTS_PER_CHUNK: usize = 256;
+
+/// Saves the Run in use by the Timer provided as a LiveSplit splits file
+/// (*.lss) chunk by chunk. See [`save_run_chunked`] for more information.
+pub fn save_timer_chunked(timer: &Timer) -> Chunks<'_> {
+    if timer.current_phase() == TimerPhase::NotRunning {
+        Chunks::new(Cow::Borrowed(timer.run()))
+    } else {
+        Chunks::new(Cow::Owned(timer.clone().into_run(true)))
+    }
+}
+
+/// Saves a Run as a LiveSplit splits file (*.lss) chunk by chunk. Instead of
+/// writing the whole splits file in a single call, the returned [`Chunks`]
+/// produce the splits file piece by piece, with each piece being small enough
+/// to be written without blocking for long, even if the Run has a huge
+/// attempt history. This allows frontends to write the chunks to an
+/// asynchronous sink and yield in between. The chunks concatenated are
+/// identical to what [`save_run`] produces. Use the [`save_timer_chunked`]
+/// function if the Run is in use by a timer in order to properly save the
+/// current attempt as well.
+pub const fn save_run_chunked(run: &Run) -> Chunks<'_> {
+    Chunks::new(Cow::Borrowed(run))
+}
+
+/// Produces a LiveSplit splits file chunk by chunk. Use [`save_run_chunked`]
+/// or [`save_timer_chunked`] to create them.
+pub struct Chunks<'run> {
+    run: Cow<'run, Run>,
+    stage: Stage,
+    buf: String,
+    base64_buf: Vec<MaybeUninit<u8>>,
+    image_buf: Cow<'static, [u8]>,
+}
+
+#[derive(Copy, Clone)]
+enum Stage {
+    Header,
+    Attempts(usize),
+    Segments(usize),
+    // The index of the segment and the index of the next time of its history.
+    SegmentHistory(usize, usize),
+    Footer,
+    Done,
+}
+
+impl<'run> Chunks<'run> {
+    const fn new(run: Cow<'run, Run>) -> Self {
+        Self {
+            run,
+            stage: Stage::Header,
+            buf: String::new(),
+            base64_buf: Vec::new(),
+            image_buf: Cow::Borrowed(LSS_IMAGE_HEADER.as_slice()),
+        }
+    }
+
+    /// Writes the next chunk of the splits file and returns it. Returns
+    /// `None` once the whole splits file has been written.
+    pub fn next_chunk(&mut self) -> Option<&str> {
+        self.buf.clear();
+        let run = &*self.run;
+        let base64_buf = &mut self.base64_buf;
+        let image_buf = &mut self.image_buf;
+
+        // Writing into a String never fails.
+        let _ = match self.stage {
+            Stage::Header => {
+                self.stage = Stage::Attempts(0);
+                Writer::new_with_default_header(&mut self.buf).and_then(|mut writer| {
+                    writer.just_start_tag("Run", |tag| {
+                        tag.attribute("version", Text::new_escaped(RUN_VERSION))
+                    })?;
+                    write_run_header(&mut writer, run, base64_buf, image_buf)?;
+                    if run.attempt_history().is_empty() {
+                        writer.empty_tag("AttemptHistory", NO_ATTRIBUTES)
+                    } else {
+                        writer.just_start_tag("AttemptHistory", |_| Ok::<_, fmt::Error>(()))
+                    }
+                })
+            }
+            Stage::Attempts(start) => {
+                let attempts = run.attempt_history();
+                let end = attempts.len().min(start + ATTEMPTS_PER_CHUNK);
+                self.stage = if end < attempts.len() {
+                    Stage::Attempts(end)
+                } else {
+                    Stage::Segments(0)
+                };
+                let writer = &mut Writer::new_skip_header(&mut self.buf);
+                attempts[start..end]
+                    .iter()
+                    .try_for_each(|attempt| write_attempt(writer, attempt))
+                    .and_then(|()| {
+                        if end < attempts.len() {
+                            return Ok(());
+                        }
+                        if !attempts.is_empty() {
+                            writer.just_end_tag("AttemptHistory")?;
+                        }
+                        if run.segments().is_empty() {
+                            writer.empty_tag("Segments", NO_ATTRIBUTES)
+                        } else {
+                            writer.just_start_tag("Segments", |_| Ok::<_, fmt::Error>(()))
+                        }
+                    })
+            }
+            Stage::Segments(index) => {
+                let writer = &mut Writer::new_skip_header(&mut self.buf);
+                match run.segments().get(index) {
+                    Some(segment) => {
+                        self.stage = Stage::SegmentHistory(index, 0);
+                        write_segment_start(writer, run, segment, base64_buf, image_buf)
+                    }
+                    None => {
+                        self.stage = Stage::Footer;
+                        if run.segments().is_empty() {
+                            Ok(())
+                        } else {
+                            writer.just_end_tag("Segments")
+                        }
+                    }
+                }
+            }
+            Stage::SegmentHistory(index, start) => {
+                let segment = &run.segments()[index];
+                let history = segment.segment_history().iter().as_slice();
+                let end = history.len().min(start + ATTEMPTS_PER_CHUNK);
+                self.stage = if end < history.len() {
+                    Stage::SegmentHistory(index, end)
+                } else {
+                    Stage::Segments(index + 1)
+                };
+                let writer = &mut Writer::new_skip_header(&mut self.buf);
+                history[start..end]
+                    .iter()
+                    .try_for_each(|entry| write_segment_history_entry(writer, entry))
+                    .and_then(|()| {
+                        if end < history.len() {
+                            Ok(())
+                        } else {
+                            write_segment_end(writer, segment)
+                        }
+                    })
+            }
+            Stage::Footer => {
+                self.stage = Stage::Done;
+                let writer = &mut Writer::new_skip_header(&mut self.buf);
+                write_run_footer(writer, run).and_then(|()| writer.just_end_tag("Run"))
+            }
+            Stage::Done => return None,
+        };
+
+        Some(&self.buf)
+    }
+}
+
+/// Saves a [`RunBundle`] as a LiveSplit run bundle file. The game icon and
+/// segment icons that are shared between the runs of the bundle are only
+/// stored once. Make sure to store the [`Run`] in use by the [`Timer`] back
+/// into the bundle via [`RunBundle::store_active_run`] before saving it.
+pub fn save_bundle<W: fmt::Write>(bundle: &RunBundle, writer: W) -> fmt::Result {
+    let writer = &mut Writer::new_with_default_header(writer)?;
+
+    let base64_buf = &mut Vec::new();
+    let image_buf = &mut Cow::Borrowed(&LSS_IMAGE_HEADER[..]);
+
+    writer.tag_with_content(
+        "RunBundle",
+        [("version", Text::new_escaped("1.0.0"))],
+        |writer| {
+            image(
                 writer,
-                "SpeedrunComVariables",
-                metadata.speedrun_com_variables(),
-                |writer, (name, value)| {
-                    writer.tag_with_text_content("Variable", [("name", name)], value.as_str())
-                },
+                "GameIcon",
+                bundle.game_icon(),
+                base64_buf,
+                image_buf,
             )?;
+            writer.tag_with_text_content("GameName", NO_ATTRIBUTES, bundle.game_name())?;
+
+            let mut stored_icons = Vec::new();
+
+            writer.tag_with_content("Runs", NO_ATTRIBUTES, |writer| {
+                for run in bundle.runs() {
+                    let mut run = run.clone();
+                    run.set_game_icon(Image::default());
+                    for segment in run.segments_mut() {
+                        let icon = segment.icon();
+                        if icon.is_empty() {
+                            continue;
+                        }
+                        let key = (segment.name().to_owned(), *icon.id());
+                        if stored_icons.contains(&key) {
+                            segment.set_icon(Image::default());
+                        } else {
+                            stored_icons.push(key);
+                        }
+                    }
+                    write_run(writer, &run, base64_buf, image_buf)?;
+                }
+                Ok(())
+            })
+        },
+    )
+}
+
+fn write_run<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    run: &Run,
+    base64_buf: &mut Vec<MaybeUninit<u8>>,
+    image_buf: &mut Cow<[u8]>,
+) -> fmt::Result {
+    writer.tag_with_content(
+        "Run",
+        [("version", Text::new_escaped(RUN_VERSION))],
+        |writer| {
+            write_run_header(writer, run, base64_buf, image_buf)?;
             scoped_iter(
                 writer,
-                "CustomVariables",
-                metadata
-                    .custom_variables()
-                    .filter(|(_, var)| var.is_permanent),
-                |writer, (name, var)| {
-                    writer.tag_with_text_content("Variable", [("name", name)], var.value.as_str())
-                },
-            )
-        })?;
+                "AttemptHistory",
+                run.attempt_history(),
+                write_attempt,
+            )?;
+            scoped_iter(writer, "Segments", run.segments(), |writer, segment| {
+                write_segment(writer, run, segment, base64_buf, image_buf)
+            })?;
+            write_run_footer(writer, run)
+        },
+    )
+}
 
+/// Writes everything that is stored before the attempt history.
+fn write_run_header<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    run: &Run,
+    base64_buf: &mut Vec<MaybeUninit<u8>>,
+    image_buf: &mut Cow<[u8]>,
+) -> fmt::Result {
+    image(writer, "GameIcon", run.game_icon(), base64_buf, image_buf)?;
+    writer.tag_with_text_content("GameName", NO_ATTRIBUTES, run.game_name())?;
+    writer.tag_with_text_content("CategoryName", NO_ATTRIBUTES, run.category_name())?;
+
+    writer.tag_with_content("Metadata", NO_ATTRIBUTES, |writer| {
+        let metadata = run.metadata();
+        writer.empty_tag("Run", [("id", metadata.run_id())])?;
         writer.tag_with_text_content(
-            "LayoutPath",
-            NO_ATTRIBUTES,
-            match run.linked_layout() {
-                Some(LinkedLayout::Default) => "?default",
-                Some(LinkedLayout::Path(path)) => path,
-                None => "",
+            "Platform",
+            [("usesEmulator", bool(metadata.uses_emulator()))],
+            metadata.platform_name(),
+        )?;
+        writer.tag_with_text_content("Region", NO_ATTRIBUTES, metadata.region_name())?;
+        scoped_iter(
+            writer,
+            "SpeedrunComVariables",
+            metadata.speedrun_com_variables(),
+            |writer, (name, value)| {
+                writer.tag_with_text_content("Variable", [("name", name)], value.as_str())
             },
         )?;
+        scoped_iter(
+            writer,
+            "CustomVariables",
+            metadata
+                .custom_variables()
+                .filter(|(_, var)| var.is_permanent),
+            |writer, (name, var)| {
+                writer.tag_with_text_content("Variable", [("name", name)], var.value.as_str())
+            },
+        )
+    })?;
 
+    writer.tag_with_text_content(
+        "LayoutPath",
+        NO_ATTRIBUTES,
+        match run.linked_layout() {
+            Some(LinkedLayout::Default) => "?default",
+            Some(LinkedLayout::Path(path)) => path,
+            None => "",
+        },
+    )?;
+
+    writer.tag_with_text_content(
+        "Offset",
+        NO_ATTRIBUTES,
+        DisplayAlreadyEscaped(Complete.format(run.offset())),
+    )?;
+    writer.tag_with_text_content(
+        "AttemptCount",
+        NO_ATTRIBUTES,
+        DisplayAlreadyEscaped(run.attempt_count()),
+    )
+}
+
+fn write_attempt<W: fmt::Write>(writer: &mut Writer<W>, attempt: &Attempt) -> fmt::Result {
+    writer.tag("Attempt", |mut tag| {
+        tag.attribute("id", DisplayAlreadyEscaped(attempt.index()))?;
+
+        if let Some(started) = attempt.started() {
+            date(&mut tag, "started", started.time)?;
+            tag.attribute("isStartedSynced", bool(started.synced_with_atomic_clock))?;
+        }
+        if let Some(ended) = attempt.ended() {
+            date(&mut tag, "ended", ended.time)?;
+            tag.attribute("isEndedSynced", bool(ended.synced_with_atomic_clock))?;
+        }
+
+        let is_empty = attempt.time().real_time.is_none()
+            && attempt.time().game_time.is_none()
+            && attempt.pause_time().is_none()
+            && attempt.variables().is_empty()
+            && attempt.split_variables().is_empty()
+            && attempt.tags().is_empty()
+            && attempt.event_log().is_empty();
+
+        if !is_empty {
+            tag.content(|writer| {
+                time_inner(writer, attempt.time())?;
+
+                if let Some(pause_time) = attempt.pause_time() {
+                    writer.tag_with_text_content(
+                        "PauseTime",
+                        NO_ATTRIBUTES,
+                        DisplayAlreadyEscaped(Complete.format(pause_time)),
+                    )?;
+                }
+
+                variables(writer, "Variables", attempt.variables())?;
+                tags(writer, "Tags", attempt.tags())?;
+                event_log(writer, attempt.event_log())?;
+                if !attempt.split_variables().is_empty() {
+                    scoped_iter(
+                        writer,
+                        "SplitVariables",
+                        attempt.split_variables(),
+                        |writer, (segment_index, values)| {
+                            writer.tag("Split", |mut tag| {
+                                tag.attribute("segment", DisplayAlreadyEscaped(segment_index))?;
+                                tag.content(|writer| variable_list(writer, values))
+                            })
+                        },
+                    )?;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    })
+}
+
+fn write_segment<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    run: &Run,
+    segment: &Segment,
+    base64_buf: &mut Vec<MaybeUninit<u8>>,
+    image_buf: &mut Cow<[u8]>,
+) -> fmt::Result {
+    write_segment_start(writer, run, segment, base64_buf, image_buf)?;
+    segment
+        .segment_history()
+        .iter()
+        .try_for_each(|entry| write_segment_history_entry(writer, entry))?;
+    write_segment_end(writer, segment)
+}
+
+/// Writes everything of a segment up to the times of its segment history.
+/// The segment history can be huge, so [`Chunks`] writes its times separately.
+fn write_segment_start<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    run: &Run,
+    segment: &Segment,
+    base64_buf: &mut Vec<MaybeUninit<u8>>,
+    image_buf: &mut Cow<[u8]>,
+) -> fmt::Result {
+    writer.just_start_tag("Segment", |_| Ok::<_, fmt::Error>(()))?;
+    writer.tag_with_text_content("Name", NO_ATTRIBUTES, segment.name())?;
+    image(writer, "Icon", segment.icon(), base64_buf, image_buf)?;
+
+    scoped_iter(
+        writer,
+        "SplitTimes",
+        run.custom_comparisons()
+            .iter()
+            .filter(|c| !c.starts_with(RACE_COMPARISON_PREFIX)),
+        |writer, comparison| {
+            writer.tag("SplitTime", |mut tag| {
+                tag.attribute("name", comparison.as_str())?;
+                time(tag, segment.comparison(comparison))
+            })
+        },
+    )?;
+
+    writer.tag("BestSegmentTime", |tag| {
+        time(tag, segment.best_segment_time())
+    })?;
+
+    if segment.segment_history().iter().len() == 0 {
+        writer.empty_tag("SegmentHistory", NO_ATTRIBUTES)
+    } else {
+        writer.just_start_tag("SegmentHistory", |_| Ok::<_, fmt::Error>(()))
+    }
+}
+
+fn write_segment_history_entry<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    &(index, history_time): &(i32, Time),
+) -> fmt::Result {
+    writer.tag("Time", |mut tag| {
+        tag.attribute("id", DisplayAlreadyEscaped(index))?;
+        time(tag, history_time)
+    })
+}
+
+/// Writes everything of a segment that follows the times of its segment
+/// history.
+fn write_segment_end<W: fmt::Write>(writer: &mut Writer<W>, segment: &Segment) -> fmt::Result {
+    if segment.segment_history().iter().len() != 0 {
+        writer.just_end_tag("SegmentHistory")?;
+    }
+    writer.just_end_tag("Segment")
+}
+
+/// Writes everything that is stored after the segments.
+fn write_run_footer<W: fmt::Write>(writer: &mut Writer<W>, run: &Run) -> fmt::Result {
+    write_gold_rules(writer, run.gold_rules())?;
+    write_comparison_generators(writer, run)?;
+    write_timing_method_bindings(writer, run)?;
+    tags(writer, "ExcludedAttemptTags", run.excluded_attempt_tags())?;
+    if let Some(frame_rate) = run.frame_rate() {
         writer.tag_with_text_content(
-            "Offset",
-            NO_ATTRIBUTES,
-            DisplayAlreadyEscaped(Complete.format(run.offset())),
-        )?;
-        writer.tag_with_text_content(
-            "AttemptCount",
+            "FrameRate",
             NO_ATTRIBUTES,
-            DisplayAlreadyEscaped(run.attempt_count()),
+            DisplayAlreadyEscaped(frame_rate),
         )?;
+    }
+    write_average_segments(writer, run.average_segments_comparison())?;
+    write_last_attempts(writer, run.last_attempts_comparison())?;
 
-        scoped_iter(
-            writer,
-            "AttemptHistory",
-            run.attempt_history(),
-            |writer, attempt| {
-                writer.tag("Attempt", |mut tag| {
-                    tag.attribute("id", DisplayAlreadyEscaped(attempt.index()))?;
-
-                    if let Some(started) = attempt.started() {
-                        date(&mut tag, "started", started.time)?;
-                        tag.attribute("isStartedSynced", bool(started.synced_with_atomic_clock))?;
-                    }
-                    if let Some(ended) = attempt.ended() {
-                        date(&mut tag, "ended", ended.time)?;
-                        tag.attribute("isEndedSynced", bool(ended.synced_with_atomic_clock))?;
-                    }
+    write_run_auto_splitter_settings(writer, run)
+}
 
-                    let is_empty = attempt.time().real_time.is_none()
-                        && attempt.time().game_time.is_none()
-                        && attempt.pause_time().is_none();
+fn write_gold_rules<W: fmt::Write>(writer: &mut Writer<W>, gold_rules: &GoldRules) -> fmt::Result {
+    if gold_rules.is_empty() {
+        return Ok(());
+    }
 
-                    if !is_empty {
-                        tag.content(|writer| {
-                            time_inner(writer, attempt.time())?;
+    writer.tag_with_content("GoldRules", NO_ATTRIBUTES, |writer| {
+        if let Some(max) = gold_rules.max_game_time_pause {
+            writer.tag_with_text_content(
+                "MaxGameTimePause",
+                NO_ATTRIBUTES,
+                DisplayAlreadyEscaped(Complete.format(max)),
+            )?;
+        }
+        if let Some(min) = gold_rules.min_segment_time_after_undo {
+            writer.tag_with_text_content(
+                "MinSegmentTimeAfterUndo",
+                NO_ATTRIBUTES,
+                DisplayAlreadyEscaped(Complete.format(min)),
+            )?;
+        }
+        Ok(())
+    })
+}
 
-                            if let Some(pause_time) = attempt.pause_time() {
-                                writer.tag_with_text_content(
-                                    "PauseTime",
-                                    NO_ATTRIBUTES,
-                                    DisplayAlreadyEscaped(Complete.format(pause_time)),
-                                )?;
-                            }
+fn write_comparison_generators<W: fmt::Write>(writer: &mut Writer<W>, run: &Run) -> fmt::Result {
+    let generators = run.comparison_generators();
+    if generators.iter().all(|g| g.settings().is_none())
+        && generators
+            .iter()
+            .map(|g| g.name())
+            .eq(default_generators().iter().map(|g| g.name()))
+    {
+        return Ok(());
+    }
 
-                            Ok(())
-                        })?;
-                    }
+    scoped_iter(
+        writer,
+        "ComparisonGenerators",
+        generators,
+        |writer, generator| {
+            writer.tag_with_text_content(
+                "ComparisonGenerator",
+                [("name", generator.name())],
+                generator.settings().as_deref().unwrap_or_default(),
+            )
+        },
+    )
+}
 
-                    Ok(())
-                })
-            },
-        )?;
+fn write_timing_method_bindings<W: fmt::Write>(writer: &mut Writer<W>, run: &Run) -> fmt::Result {
+    let bindings = run.timing_method_bindings();
+    if bindings.is_empty() {
+        return Ok(());
+    }
 
-        scoped_iter(writer, "Segments", run.segments(), |writer, segment| {
-            writer.tag_with_content("Segment", NO_ATTRIBUTES, |writer| {
-                writer.tag_with_text_content("Name", NO_ATTRIBUTES, segment.name())?;
-                image(writer, "Icon", segment.icon(), base64_buf, image_buf)?;
-
-                scoped_iter(
-                    writer,
-                    "SplitTimes",
-                    run.custom_comparisons(),
-                    |writer, comparison| {
-                        writer.tag("SplitTime", |mut tag| {
-                            tag.attribute("name", comparison.as_str())?;
-                            time(tag, segment.comparison(comparison))
-                        })
-                    },
-                )?;
-
-                writer.tag("BestSegmentTime", |tag| {
-                    time(tag, segment.best_segment_time())
-                })?;
+    scoped_iter(
+        writer,
+        "TimingMethodBindings",
+        bindings,
+        |writer, (comparison, method)| {
+            writer.tag_with_text_content(
+                "Binding",
+                [("comparison", comparison.as_str())],
+                match method {
+                    TimingMethod::RealTime => "RealTime",
+                    TimingMethod::GameTime => "GameTime",
+                },
+            )
+        },
+    )
+}
 
-                scoped_iter(
-                    writer,
-                    "SegmentHistory",
-                    segment.segment_history(),
-                    |writer, &(index, history_time)| {
-                        writer.tag("Time", |mut tag| {
-                            tag.attribute("id", DisplayAlreadyEscaped(index))?;
-                            time(tag, history_time)
-                        })
-                    },
-                )
-            })
-        })?;
+fn write_average_segments<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    settings: &AverageSegments,
+) -> fmt::Result {
+    if *settings == AverageSegments::default() {
+        return Ok(());
+    }
 
-        write_run_auto_splitter_settings(writer, run)
+    writer.tag_with_content("AverageSegmentsComparison", NO_ATTRIBUTES, |writer| {
+        writer.tag_with_text_content(
+            "Weight",
+            NO_ATTRIBUTES,
+            DisplayAlreadyEscaped(settings.weight()),
+        )
     })
 }
 
+fn write_last_attempts<W: fmt::Write>(
+    writer: &mut Writer<W>,
+    settings: &LastAttempts,
+) -> fmt::Result {
+    if *settings == LastAttempts::default() {
+        return Ok(());
+    }
+
+    writer.tag_with_content("LastAttemptsComparison", NO_ATTRIBUTES, |writer| {
+        writer.tag_with_text_content(
+            "Attempts",
+            NO_ATTRIBUTES,
+            DisplayAlreadyEscaped(settings.attempts),
+        )?;
+        writer.tag_with_text_content(
+            "Statistic",
+            NO_ATTRIBUTES,
+            match settings.statistic {
+                Statistic::Average => "Average",
+                Statistic::Median => "Median",
+            },
+        )
+    })
+}
+
+/// Writes the `AutoSplitterSettings` element of the Run on its own, so other
+/// savers can embed it.
+pub(super) fn write_auto_splitter_settings<W: fmt::Write>(writer: W, run: &Run) -> fmt::Result {
+    write_run_auto_splitter_settings(&mut Writer::new_skip_header(writer), run)
+}
+
 fn write_run_auto_splitter_settings<W: fmt::Write>(
     writer: &mut Writer<W>,
     run: &Run,
@@ -25,4 +25,8 @@
 //! livesplit::save_run(&run, IoWrite(writer)).expect("Couldn't save the splits file");
 //! ```
 
+pub mod binary;
 pub mod livesplit;
+pub mod splits_io;
+pub mod urn;
+pub mod wsplit;
@@ -0,0 +1,206 @@
+//! The splits i/o Saver saves Runs in the splits i/o exchange format (v1.0.1).
+//! This is a JSON based format that is meant for exchanging splits between
+//! different timers. It stores both timing methods, the attempt history and
+//! the segment histories.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::run::saver::splits_io;
+//! use livesplit_core::{Run, Segment};
+//!
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let mut splits_file = String::new();
+//! splits_io::save_run(&run, &mut splits_file).unwrap();
+//! assert!(splits_file.contains("\"_schemaVersion\": \"v1.0.1\""));
+//! ```
+
+use crate::{AtomicDateTime, Run, Time, TimeSpan, Timer, TimerPhase, platform::prelude::*};
+use core::fmt;
+use serde_derive::Serialize;
+use time::UtcOffset;
+
+/// The version of the exchange format's schema that is saved.
+pub const SCHEMA_VERSION: &str = "v1.0.1";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeRun<'a> {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: &'static str,
+    timer: TimerInfo,
+    attempts: Attempts,
+    game: Name<'a>,
+    category: Name<'a>,
+    segments: Vec<ExchangeSegment<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimerInfo {
+    shortname: &'static str,
+    longname: &'static str,
+    website: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Attempts {
+    total: u32,
+    histories: Vec<AttemptHistory>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttemptHistory {
+    attempt_number: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    realtime_duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gametime_duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ended_at: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Name<'a> {
+    longname: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeSegment<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ended_at: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    best_duration: Option<Duration>,
+    is_skipped: bool,
+    histories: Vec<SegmentHistory>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Duration {
+    #[serde(rename = "realtimeMS", skip_serializing_if = "Option::is_none")]
+    realtime_ms: Option<i64>,
+    #[serde(rename = "gametimeMS", skip_serializing_if = "Option::is_none")]
+    gametime_ms: Option<i64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentHistory {
+    attempt_number: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    realtime_duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gametime_duration_ms: Option<i64>,
+    is_skipped: bool,
+}
+
+fn milliseconds(time: Option<TimeSpan>) -> Option<i64> {
+    time.map(|t| t.total_milliseconds() as i64)
+}
+
+fn duration(time: Time) -> Option<Duration> {
+    if time.real_time.is_none() && time.game_time.is_none() {
+        return None;
+    }
+    Some(Duration {
+        realtime_ms: milliseconds(time.real_time),
+        gametime_ms: milliseconds(time.game_time),
+    })
+}
+
+fn date_time(date_time: Option<AtomicDateTime>) -> Option<String> {
+    let date_time = date_time?.time.to_offset(UtcOffset::UTC);
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        date_time.year(),
+        date_time.month() as u8,
+        date_time.day(),
+        date_time.hour(),
+        date_time.minute(),
+        date_time.second(),
+    ))
+}
+
+/// Saves the Run in use by the Timer provided in the splits i/o exchange
+/// format.
+pub fn save_timer<W: fmt::Write>(timer: &Timer, writer: W) -> fmt::Result {
+    let run = if timer.current_phase() == TimerPhase::NotRunning {
+        timer.run()
+    } else {
+        &timer.clone().into_run(true)
+    };
+    save_run(run, writer)
+}
+
+/// Saves a Run in the splits i/o exchange format. Attempts that are stored in
+/// the segment histories with indices below 1, which belong to attempts that
+/// aren't part of the attempt history, are not saved.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    let exchange_run = ExchangeRun {
+        schema_version: SCHEMA_VERSION,
+        timer: TimerInfo {
+            shortname: "livesplit-core",
+            longname: "livesplit-core",
+            website: "https://livesplit.org",
+            version: env!("CARGO_PKG_VERSION"),
+        },
+        attempts: Attempts {
+            total: run.attempt_count(),
+            histories: run
+                .attempt_history()
+                .iter()
+                .map(|attempt| AttemptHistory {
+                    attempt_number: attempt.index(),
+                    realtime_duration_ms: milliseconds(attempt.time().real_time),
+                    gametime_duration_ms: milliseconds(attempt.time().game_time),
+                    started_at: date_time(attempt.started()),
+                    ended_at: date_time(attempt.ended()),
+                })
+                .collect(),
+        },
+        game: Name {
+            longname: run.game_name(),
+        },
+        category: Name {
+            longname: run.category_name(),
+        },
+        segments: run
+            .segments()
+            .iter()
+            .map(|segment| {
+                let split_time = segment.personal_best_split_time();
+                ExchangeSegment {
+                    name: segment.name(),
+                    ended_at: duration(split_time),
+                    best_duration: duration(segment.best_segment_time()),
+                    is_skipped: split_time.real_time.is_none() && split_time.game_time.is_none(),
+                    histories: segment
+                        .segment_history()
+                        .iter_actual_runs()
+                        .map(|&(index, time)| SegmentHistory {
+                            attempt_number: index,
+                            realtime_duration_ms: milliseconds(time.real_time),
+                            gametime_duration_ms: milliseconds(time.game_time),
+                            is_skipped: time.real_time.is_none() && time.game_time.is_none(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&exchange_run).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
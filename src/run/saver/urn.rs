@@ -0,0 +1,78 @@
+//! The Urn Saver saves Runs as Urn splits files. Urn only supports a single
+//! timing method, so only the Real Time is saved.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::run::saver::urn;
+//! use livesplit_core::{Run, Segment};
+//!
+//! let mut run = Run::new();
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let mut splits_file = String::new();
+//! urn::save_run(&run, &mut splits_file).unwrap();
+//! ```
+
+use crate::{Run, TimeSpan, Timer, TimerPhase, comparison::best_split_times, platform::prelude::*};
+use core::fmt;
+use serde_derive::Serialize;
+
+#[derive(Serialize)]
+struct Splits<'a> {
+    title: &'a str,
+    attempt_count: u32,
+    start_delay: TimeSpan,
+    splits: Vec<Split<'a>>,
+}
+
+#[derive(Serialize)]
+struct Split<'a> {
+    title: &'a str,
+    time: TimeSpan,
+    best_time: TimeSpan,
+    best_segment: TimeSpan,
+}
+
+/// Saves the Run in use by the Timer provided as an Urn splits file.
+pub fn save_timer<W: fmt::Write>(timer: &Timer, writer: W) -> fmt::Result {
+    let run = if timer.current_phase() == TimerPhase::NotRunning {
+        timer.run()
+    } else {
+        &timer.clone().into_run(true)
+    };
+    save_run(run, writer)
+}
+
+/// Saves a Run as an Urn splits file. The category name is stored as the
+/// title. Urn stores missing times as zero. The best times are taken from the
+/// Best Split Times comparison, falling back to the Personal Best if the
+/// comparison isn't available.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    let splits = Splits {
+        title: run.category_name(),
+        attempt_count: run.attempt_count(),
+        start_delay: -run.offset(),
+        splits: run
+            .segments()
+            .iter()
+            .map(|segment| {
+                let time = segment.personal_best_split_time().real_time;
+                Split {
+                    title: segment.name(),
+                    time: time.unwrap_or_default(),
+                    best_time: segment
+                        .comparison(best_split_times::NAME)
+                        .real_time
+                        .or(time)
+                        .unwrap_or_default(),
+                    best_segment: segment.best_segment_time().real_time.unwrap_or_default(),
+                }
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&splits).map_err(|_| fmt::Error)?;
+    writer.write_str(&json)
+}
@@ -0,0 +1,74 @@
+//! The WSplit Saver saves Runs as WSplit splits files. WSplit only supports a
+//! single timing method, so only the Real Time is saved. Segment icons can't
+//! be saved, as WSplit only references them by their file paths.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::run::saver::wsplit;
+//! use livesplit_core::{Run, Segment};
+//!
+//! let mut run = Run::new();
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let mut splits_file = String::new();
+//! wsplit::save_run(&run, &mut splits_file).unwrap();
+//! assert!(splits_file.starts_with("Title=Any%"));
+//! ```
+
+use crate::{Run, TimeSpan, Timer, TimerPhase};
+use core::fmt;
+
+fn seconds(time: Option<TimeSpan>) -> f64 {
+    time.map_or(0.0, |t| t.total_seconds())
+}
+
+/// Saves the Run in use by the Timer provided as a WSplit splits file.
+pub fn save_timer<W: fmt::Write>(timer: &Timer, writer: W) -> fmt::Result {
+    let run = if timer.current_phase() == TimerPhase::NotRunning {
+        timer.run()
+    } else {
+        &timer.clone().into_run(true)
+    };
+    save_run(run, writer)
+}
+
+/// Saves a Run as a WSplit splits file. The category name is stored as the
+/// title. As WSplit separates the values of a segment with commas, commas in
+/// segment names are replaced by spaces. The times of the `Old Run`
+/// comparison are stored as the old times, if the comparison exists.
+pub fn save_run<W: fmt::Write>(run: &Run, mut writer: W) -> fmt::Result {
+    writeln!(writer, "Title={}", run.category_name())?;
+    writeln!(writer, "Attempts={}", run.attempt_count())?;
+    writeln!(writer, "Offset={}", -run.offset().total_milliseconds())?;
+    writeln!(writer, "Size=152,25")?;
+
+    for segment in run.segments() {
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            segment.name().replace(',', " "),
+            seconds(segment.comparison("Old Run").real_time),
+            seconds(segment.personal_best_split_time().real_time),
+            seconds(segment.best_segment_time().real_time),
+        )?;
+    }
+
+    writer.write_str("Icons=")?;
+    for index in 0..run.len() {
+        if index != 0 {
+            writer.write_char(',')?;
+        }
+        writer.write_str("\"\"")?;
+    }
+    writeln!(writer)?;
+
+    if let Some(goal) = run.metadata().custom_variable_value("Goal")
+        && !goal.is_empty()
+    {
+        writeln!(writer, "Goal={goal}")?;
+    }
+
+    Ok(())
+}
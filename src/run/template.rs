@@ -0,0 +1,164 @@
+//! Provides community maintained split templates for games and categories. A
+//! template contains the segment names and icons of a category and optionally
+//! a recommended layout, so new runners don't need to build their splits by
+//! hand. A fresh [`Run`] can be instantiated from a template.
+//!
+//! Templates are stored as JSON. They can be loaded from a local directory or,
+//! with the `networking` feature, fetched through an index of templates.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::run::template::Template;
+//!
+//! let template = Template::from_json(br#"{
+//!     "game": "Super Mario Odyssey",
+//!     "category": "Any%",
+//!     "segments": [{ "name": "Cap" }, { "name": "Cascade" }]
+//! }"#).unwrap();
+//!
+//! let run = template.instantiate();
+//! assert_eq!(run.category_name(), "Any%");
+//! assert_eq!(run.segment(1).name(), "Cascade");
+//! ```
+
+use super::Segment;
+use crate::{
+    Layout, Run, layout::LayoutSettings, platform::prelude::*, settings::Image, util::caseless,
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// A template for a single segment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SegmentTemplate {
+    /// The name of the segment.
+    pub name: String,
+    /// The icon of the segment, encoded as base64 in the JSON.
+    #[serde(default)]
+    pub icon: Image,
+}
+
+/// A split template for a game and category that a fresh [`Run`] can be
+/// instantiated from.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Template {
+    /// The name of the game.
+    pub game: String,
+    /// The name of the category.
+    pub category: String,
+    /// The icon of the game, encoded as base64 in the JSON.
+    #[serde(default)]
+    pub game_icon: Image,
+    /// The segments of the category.
+    pub segments: Vec<SegmentTemplate>,
+    /// The layout that is recommended for the category, if there is any.
+    #[serde(default)]
+    pub layout: Option<LayoutSettings>,
+}
+
+impl Template {
+    /// Decodes a template from JSON.
+    #[cfg(feature = "std")]
+    pub fn from_json(json: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(json)
+    }
+
+    /// Checks whether the template is for the game and category provided. The
+    /// names are compared case insensitively.
+    pub fn matches(&self, game: &str, category: &str) -> bool {
+        caseless::eq(&self.game, game) && caseless::eq(&self.category, category)
+    }
+
+    /// Instantiates a fresh [`Run`] without any attempts from the template.
+    pub fn instantiate(&self) -> Run {
+        let mut run = Run::new();
+        run.set_game_name(self.game.as_str());
+        run.set_category_name(self.category.as_str());
+        run.set_game_icon(self.game_icon.clone());
+        for segment_template in &self.segments {
+            let mut segment = Segment::new(segment_template.name.as_str());
+            segment.set_icon(segment_template.icon.clone());
+            run.push_segment(segment);
+        }
+        run
+    }
+
+    /// Creates the [`Layout`] that is recommended for the category, if the
+    /// template has one.
+    pub fn recommended_layout(&self) -> Option<Layout> {
+        Some(Layout::from_settings(self.layout.clone()?))
+    }
+}
+
+/// Loads all the templates stored as `.json` files in the directory provided.
+/// Files that are not valid templates are skipped, so a single broken
+/// template doesn't prevent the others from being loaded.
+#[cfg(feature = "std")]
+pub fn load_directory(path: impl AsRef<std::path::Path>) -> std::io::Result<Vec<Template>> {
+    let mut templates = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        if let Ok(template) = Template::from_json(&std::fs::read(path)?) {
+            templates.push(template);
+        }
+    }
+    Ok(templates)
+}
+
+/// Loads the template for the game and category provided from the directory
+/// provided. The names are compared case insensitively.
+#[cfg(feature = "std")]
+pub fn find_in_directory(
+    path: impl AsRef<std::path::Path>,
+    game: &str,
+    category: &str,
+) -> std::io::Result<Option<Template>> {
+    Ok(load_directory(path)?
+        .into_iter()
+        .find(|t| t.matches(game, category)))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"{
+        "game": "Celeste",
+        "category": "Any%",
+        "segments": [{ "name": "Prologue" }, { "name": "City" }],
+        "layout": {
+            "components": [{ "Title": {} }],
+            "general": {}
+        }
+    }"#;
+
+    #[test]
+    fn instantiate() {
+        let template = Template::from_json(TEMPLATE.as_bytes()).unwrap();
+        let run = template.instantiate();
+        assert_eq!(run.game_name(), "Celeste");
+        assert_eq!(run.len(), 2);
+        assert_eq!(run.segment(0).name(), "Prologue");
+        assert_eq!(run.attempt_count(), 0);
+        assert_eq!(template.recommended_layout().unwrap().components().len(), 1);
+    }
+
+    #[test]
+    fn directory() {
+        let dir = std::env::temp_dir().join(format!("livesplit-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("celeste.json"), TEMPLATE).unwrap();
+        std::fs::write(dir.join("broken.json"), "{").unwrap();
+        std::fs::write(dir.join("readme.txt"), "Hi").unwrap();
+
+        let templates = load_directory(&dir).unwrap();
+        let found = find_in_directory(&dir, "celeste", "any%").unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(templates.len(), 1);
+        assert_eq!(found.unwrap().segments.len(), 2);
+    }
+}
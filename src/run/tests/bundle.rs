@@ -0,0 +1,150 @@
+use crate::{
+    Run, Segment, Timer,
+    run::{
+        RunBundle, SwitchError,
+        parser::livesplit::parse_bundle,
+        saver::livesplit::{save_bundle, save_run},
+    },
+    settings::Image,
+};
+
+fn run(category: &str, segments: &[&str]) -> Run {
+    let mut run = Run::new();
+    run.set_category_name(category);
+    for &name in segments {
+        run.push_segment(Segment::new(name));
+    }
+    run
+}
+
+fn bundle() -> RunBundle {
+    let mut any = run("Any%", &["Cap", "Cascade"]);
+    any.set_game_name("Super Mario Odyssey");
+    any.set_game_icon(Image::new([1, 2, 3].as_slice().into(), Image::ICON));
+    any.segments_mut()[0].set_icon(Image::new([4, 5, 6].as_slice().into(), Image::ICON));
+
+    let mut bundle = RunBundle::new();
+    bundle.push(any);
+    bundle.push(run("All Moons", &["Cap", "Cascade", "Sand"]));
+    bundle
+}
+
+#[test]
+fn shares_game_and_segment_icons() {
+    let bundle = bundle();
+    let all_moons = &bundle.runs()[1];
+
+    assert_eq!(all_moons.game_name(), "Super Mario Odyssey");
+    assert_eq!(all_moons.game_icon(), bundle.game_icon());
+    assert_eq!(
+        all_moons.segment(0).icon(),
+        bundle.runs()[0].segment(0).icon()
+    );
+    assert!(all_moons.segment(1).icon().is_empty());
+}
+
+#[test]
+fn setting_the_game_name_applies_to_all_runs() {
+    let mut bundle = bundle();
+    bundle.set_game_name("Super Mario 64");
+
+    assert!(
+        bundle
+            .runs()
+            .iter()
+            .all(|r| r.game_name() == "Super Mario 64")
+    );
+}
+
+#[test]
+fn switching_stores_the_previous_run() {
+    let mut bundle = bundle();
+    let mut timer = Timer::new(bundle.active_run().unwrap().clone()).unwrap();
+
+    timer.start().unwrap();
+    assert!(matches!(
+        bundle.switch(1, &mut timer),
+        Err(SwitchError::AttemptInProgress)
+    ));
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    assert!(matches!(
+        bundle.switch(2, &mut timer),
+        Err(SwitchError::IndexOutOfBounds)
+    ));
+
+    bundle.switch(1, &mut timer).unwrap();
+    assert_eq!(bundle.active_index(), 1);
+    assert_eq!(timer.run().category_name(), "All Moons");
+    assert_eq!(bundle.runs()[0].attempt_count(), 1);
+
+    bundle.switch(0, &mut timer).unwrap();
+    assert_eq!(timer.run().attempt_count(), 1);
+}
+
+#[test]
+fn storing_the_active_run_includes_the_current_attempt() {
+    let mut bundle = bundle();
+    let mut timer = Timer::new(bundle.active_run().unwrap().clone()).unwrap();
+
+    timer.start().unwrap();
+    bundle.store_active_run(&timer);
+
+    assert_eq!(bundle.runs()[0].attempt_history().len(), 1);
+    assert!(timer.run().attempt_history().is_empty());
+}
+
+#[test]
+fn active_run_cant_be_removed() {
+    let mut bundle = bundle();
+    let mut timer = Timer::new(bundle.active_run().unwrap().clone()).unwrap();
+    bundle.switch(1, &mut timer).unwrap();
+
+    assert!(bundle.remove(1).is_none());
+    assert!(bundle.remove(2).is_none());
+    assert_eq!(bundle.remove(0).unwrap().category_name(), "Any%");
+    assert_eq!(bundle.active_index(), 0);
+    assert_eq!(bundle.len(), 1);
+}
+
+#[test]
+fn survives_saving() {
+    let mut bundle = bundle();
+    let mut timer = Timer::new(bundle.active_run().unwrap().clone()).unwrap();
+    timer.start().unwrap();
+    timer.split().unwrap();
+    timer.split().unwrap();
+    bundle.store_active_run(&timer);
+
+    let mut saved = String::new();
+    save_bundle(&bundle, &mut saved).unwrap();
+    let parsed = parse_bundle(&saved).unwrap();
+
+    assert_eq!(parsed.game_name(), "Super Mario Odyssey");
+    assert_eq!(parsed.game_icon(), bundle.game_icon());
+    assert_eq!(parsed.len(), 2);
+    for (parsed, original) in parsed.runs().iter().zip(bundle.runs()) {
+        let (mut a, mut b) = (String::new(), String::new());
+        save_run(parsed, &mut a).unwrap();
+        save_run(original, &mut b).unwrap();
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn stores_shared_icons_once() {
+    let bundle = bundle();
+
+    let mut saved = String::new();
+    save_bundle(&bundle, &mut saved).unwrap();
+
+    let mut single = String::new();
+    save_run(&bundle.runs()[0], &mut single).unwrap();
+    let icon = single
+        .split("<Icon>")
+        .nth(1)
+        .and_then(|s| s.split("</Icon>").next())
+        .unwrap();
+    assert_eq!(saved.matches(icon).count(), 1);
+}
@@ -1,6 +1,6 @@
 use crate::{
-    util::tests_helper::{create_timer, run_with_splits},
-    Timer,
+    Time, Timer,
+    util::tests_helper::{create_timer, run_with_splits, run_with_splits_opt, span},
 };
 
 #[test]
@@ -75,3 +75,36 @@ fn timer_fix_run_upon_replacement() {
     assert_eq!(segments[1].segment_history().try_get_min_index(), Some(0));
     assert_eq!(segments[1].segment_history().try_get_max_index(), Some(1));
 }
+
+#[test]
+fn rebuilds_personal_best_and_best_segments_from_history() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[3.0, 6.0, 9.0]);
+    run_with_splits_opt(&mut timer, &[Some(2.0), None, Some(8.0)]);
+    run_with_splits(&mut timer, &[1.0]);
+    let mut run = timer.into_run(true);
+
+    for segment in run.segments_mut() {
+        *segment.personal_best_split_time_mut() = Time::new().with_game_time(Some(span(100.0)));
+        *segment.best_segment_time_mut() = Time::new().with_game_time(Some(span(50.0)));
+    }
+
+    run.rebuild_from_history();
+
+    let personal_best = run
+        .segments()
+        .iter()
+        .map(|s| s.personal_best_split_time().game_time)
+        .collect::<Vec<_>>();
+    assert_eq!(personal_best, [Some(span(2.0)), None, Some(span(8.0))]);
+
+    let best_segments = run
+        .segments()
+        .iter()
+        .map(|s| s.best_segment_time().game_time)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        best_segments,
+        [Some(span(1.0)), Some(span(3.0)), Some(span(3.0))]
+    );
+}
@@ -1,3 +1,4 @@
+mod bundle;
 mod comparison;
 mod empty_run;
 mod extended_category_name;
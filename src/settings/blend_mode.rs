@@ -0,0 +1,25 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Describes how the colors of something that is drawn get combined with the
+/// colors that are already below it.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The colors are drawn on top of what's below, only mixing with it based
+    /// on their opacity.
+    #[default]
+    Normal,
+    /// The colors are multiplied with what's below, which always darkens it.
+    Multiply,
+    /// The inverted colors are multiplied with the inverted colors below,
+    /// which always lightens it.
+    Screen,
+    /// Multiplies dark colors below and screens light colors below, which
+    /// increases the contrast.
+    Overlay,
+    /// The darker of both colors is used.
+    Darken,
+    /// The lighter of both colors is used.
+    Lighten,
+    /// The colors are added to what's below.
+    Add,
+}
@@ -0,0 +1,71 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Describes which of the entries of a cache get evicted when it runs its
+/// garbage collection, in addition to the ones evicted to stay within the
+/// [`CacheLimits`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EvictionPolicy {
+    /// Entries that have not been used since the last garbage collection are
+    /// evicted, as long as there are more than twice as many entries as there
+    /// are entries that have been used, plus 5 extra entries. This keeps
+    /// entries around that are likely to be used again soon.
+    #[default]
+    Adaptive,
+    /// All the entries that have not been used since the last garbage
+    /// collection are evicted.
+    Unused,
+    /// Entries are only evicted to stay within the limits.
+    OverLimits,
+}
+
+/// Limits how large a cache may grow and how it evicts its entries. Whenever
+/// a limit is exceeded, the least recently used entries are evicted, even if
+/// they have been used since the last garbage collection.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheLimits {
+    /// The maximum amount of entries the cache may hold. `None` means there is
+    /// no limit.
+    pub max_entries: Option<usize>,
+    /// The maximum amount of memory in bytes the entries of the cache may
+    /// use. `None` means there is no limit.
+    pub max_bytes: Option<usize>,
+    /// The policy that decides which entries to evict, even if the cache is
+    /// within its limits.
+    pub eviction_policy: EvictionPolicy,
+}
+
+impl CacheLimits {
+    /// Limits that never cause any entries to be evicted.
+    pub const UNLIMITED: Self = Self {
+        max_entries: None,
+        max_bytes: None,
+        eviction_policy: EvictionPolicy::OverLimits,
+    };
+
+    /// Returns how many entries the cache may keep when running its garbage
+    /// collection, based on how many of them have been used since the last
+    /// garbage collection.
+    pub(crate) fn retained_entries(&self, used: usize) -> usize {
+        let retained = match self.eviction_policy {
+            EvictionPolicy::Adaptive => used.saturating_mul(2).saturating_add(5),
+            EvictionPolicy::Unused => used,
+            EvictionPolicy::OverLimits => usize::MAX,
+        };
+        retained.min(self.max_entries.unwrap_or(usize::MAX))
+    }
+
+    /// Returns whether the amount of memory provided exceeds the limit.
+    pub(crate) fn exceeds_bytes(&self, bytes: usize) -> bool {
+        self.max_bytes.is_some_and(|max_bytes| bytes > max_bytes)
+    }
+}
+
+/// Describes how much a cache currently holds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheUsage {
+    /// The amount of entries in the cache.
+    pub entries: usize,
+    /// The amount of memory in bytes the entries of the cache use. Caches that
+    /// can't determine how much memory their entries use report 0.
+    pub bytes: usize,
+}
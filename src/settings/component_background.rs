@@ -0,0 +1,72 @@
+use super::{Image, ImageCache, ImageId};
+use serde_derive::{Deserialize, Serialize};
+
+/// An image that is shown behind a single component, such as art behind the
+/// title. The component's own background is drawn on top of the image, so it
+/// needs to be transparent or translucent for the image to show through.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComponentBackgroundImage<I = Image> {
+    /// The image itself.
+    pub image: I,
+    /// Describes how the image is fit into the area of the component.
+    #[serde(default)]
+    pub fit: ImageFit,
+}
+
+/// Describes how an image is fit into an area that may have a different aspect
+/// ratio than the image.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ImageFit {
+    /// The image is scaled to be as large as possible while preserving its
+    /// aspect ratio and still being fully visible. It is centered within the
+    /// area.
+    #[default]
+    Fit,
+    /// The image is stretched to fill the whole area, ignoring its aspect
+    /// ratio.
+    Stretch,
+    /// The image is repeated side by side, with each copy being as tall as the
+    /// area. The copies are slightly stretched horizontally, so that a whole
+    /// number of them fills the area.
+    Tile,
+}
+
+impl<I> ComponentBackgroundImage<I> {
+    /// Changes the representation of the image, while retaining the other
+    /// properties.
+    pub const fn map<T>(&self, image: T) -> ComponentBackgroundImage<T> {
+        ComponentBackgroundImage {
+            image,
+            fit: self.fit,
+        }
+    }
+}
+
+impl ComponentBackgroundImage<Image> {
+    /// Caches the image in the image cache provided and returns a
+    /// [`ComponentBackgroundImage`] which contains the cached
+    /// [`ImageId`](super::ImageId). The image gets marked as visited regardless
+    /// of whether it was already in the cache or not.
+    pub fn cache(&self, image_cache: &mut ImageCache) -> ComponentBackgroundImage<ImageId> {
+        self.map(
+            *image_cache
+                .cache(self.image.id(), || self.image.clone())
+                .id(),
+        )
+    }
+}
+
+impl ComponentBackgroundImage<ImageId> {
+    /// Converts the [`ComponentBackgroundImage`] containing an
+    /// [`ImageId`](super::ImageId) into a [`ComponentBackgroundImage`]
+    /// containing the image by possibly looking up the image in the image
+    /// cache. This does not mark the image as visited.
+    pub fn from_cache(self, image_cache: &ImageCache) -> ComponentBackgroundImage {
+        self.map(
+            image_cache
+                .lookup(&self.image)
+                .unwrap_or(Image::EMPTY)
+                .clone(),
+        )
+    }
+}
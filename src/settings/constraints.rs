@@ -0,0 +1,228 @@
+use super::Value;
+use crate::platform::prelude::*;
+use alloc::borrow::Cow;
+use serde_derive::{Deserialize, Serialize};
+
+/// Constraints restrict the values a setting accepts. Frontends can use them to
+/// configure their widgets, such as the range of a number input or the file
+/// types shown by a file picker. Setting a value through the
+/// [`LayoutEditor`](crate::layout::editor::Editor) checks the constraints and
+/// rejects values that don't satisfy them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Constraints {
+    /// The smallest number that is allowed. This applies to integer values.
+    pub min: Option<i64>,
+    /// The largest number that is allowed. This applies to integer values.
+    pub max: Option<i64>,
+    /// The largest number of characters that is allowed. This applies to
+    /// string values.
+    pub max_length: Option<usize>,
+    /// A regular expression that string values need to match in their
+    /// entirety. This requires the `setting-patterns` feature.
+    #[cfg(feature = "setting-patterns")]
+    pub pattern: Option<Pattern>,
+    /// The file extensions that are allowed, without the leading dot. This
+    /// applies to string values that are paths to files. The extensions are
+    /// compared case insensitively. If the list is empty, any file is
+    /// allowed.
+    pub file_extensions: Vec<Cow<'static, str>>,
+}
+
+/// The Error type for values that don't satisfy the constraints of a setting.
+#[derive(Debug, snafu::Snafu)]
+pub enum ValidationError {
+    /// There is no setting with the index provided.
+    UnknownSetting,
+    /// The value doesn't have the type of the setting's value.
+    WrongType,
+    /// The number is smaller than the smallest number that is allowed.
+    #[snafu(display("The value needs to be at least {min}."))]
+    TooSmall {
+        /// The smallest number that is allowed.
+        min: i64,
+    },
+    /// The number is larger than the largest number that is allowed.
+    #[snafu(display("The value needs to be at most {max}."))]
+    TooLarge {
+        /// The largest number that is allowed.
+        max: i64,
+    },
+    /// The string has more characters than allowed.
+    #[snafu(display("The value can't be longer than {max_length} characters."))]
+    TooLong {
+        /// The largest number of characters that is allowed.
+        max_length: usize,
+    },
+    /// The string doesn't match the regular expression of the setting.
+    #[cfg(feature = "setting-patterns")]
+    #[snafu(display("The value doesn't match the pattern `{pattern}`."))]
+    PatternMismatch {
+        /// The regular expression the string needs to match.
+        pattern: Cow<'static, str>,
+    },
+    /// The file doesn't have any of the file extensions that are allowed.
+    WrongFileExtension,
+}
+
+impl Constraints {
+    /// Constraints that allow every value.
+    pub const NONE: Self = Self {
+        min: None,
+        max: None,
+        max_length: None,
+        #[cfg(feature = "setting-patterns")]
+        pattern: None,
+        file_extensions: Vec::new(),
+    };
+
+    /// Creates constraints that only allow numbers within the range provided.
+    pub const fn range(min: i64, max: i64) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+            max_length: None,
+            #[cfg(feature = "setting-patterns")]
+            pattern: None,
+            file_extensions: Vec::new(),
+        }
+    }
+
+    /// Creates constraints that only allow numbers that are at least the
+    /// number provided.
+    pub const fn at_least(min: i64) -> Self {
+        Self {
+            min: Some(min),
+            max: None,
+            max_length: None,
+            #[cfg(feature = "setting-patterns")]
+            pattern: None,
+            file_extensions: Vec::new(),
+        }
+    }
+
+    /// Checks whether the constraints allow every value.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Checks whether the value provided satisfies the constraints. Only the
+    /// constraints that apply to the type of the value are checked. The
+    /// constraints of a list apply to each of its items.
+    pub fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        match value {
+            Value::UInt(v) => self.validate_number(i64::try_from(*v).unwrap_or(i64::MAX)),
+            Value::Int(v) => self.validate_number(*v),
+            Value::String(v) | Value::OptionalString(Some(v)) => self.validate_string(v),
+            Value::List(list) => list.items.iter().try_for_each(|item| self.validate(item)),
+            _ => Ok(()),
+        }
+    }
+
+    const fn validate_number(&self, value: i64) -> Result<(), ValidationError> {
+        if let Some(min) = self.min
+            && value < min
+        {
+            return Err(ValidationError::TooSmall { min });
+        }
+        if let Some(max) = self.max
+            && value > max
+        {
+            return Err(ValidationError::TooLarge { max });
+        }
+        Ok(())
+    }
+
+    fn validate_string(&self, value: &str) -> Result<(), ValidationError> {
+        if let Some(max_length) = self.max_length
+            && value.chars().count() > max_length
+        {
+            return Err(ValidationError::TooLong { max_length });
+        }
+
+        #[cfg(feature = "setting-patterns")]
+        if let Some(pattern) = &self.pattern
+            && !pattern.is_match(value)
+        {
+            return Err(ValidationError::PatternMismatch {
+                pattern: pattern.source.clone(),
+            });
+        }
+
+        if !self.file_extensions.is_empty() {
+            let extension = value
+                .rsplit_once('.')
+                .map(|(_, extension)| extension)
+                .filter(|extension| !extension.contains(['/', '\\']));
+            if !extension.is_some_and(|extension| {
+                self.file_extensions
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(extension))
+            }) {
+                return Err(ValidationError::WrongFileExtension);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A regular expression that string values need to match in their entirety.
+/// The regular expression is only compiled once it is needed for the first
+/// time.
+#[cfg(feature = "setting-patterns")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "Cow<'static, str>", into = "Cow<'static, str>")]
+pub struct Pattern {
+    source: Cow<'static, str>,
+    regex: std::sync::OnceLock<Option<regex::Regex>>,
+}
+
+#[cfg(feature = "setting-patterns")]
+impl Pattern {
+    /// Creates a new pattern from the regular expression provided.
+    pub fn new(source: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            source: source.into(),
+            regex: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Accesses the regular expression.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn is_match(&self, value: &str) -> bool {
+        self.regex
+            .get_or_init(|| regex::Regex::new(&format!("^(?:{})$", self.source)).ok())
+            .as_ref()
+            // An invalid pattern is a mistake of the component, so it can't be
+            // held against the value.
+            .is_none_or(|regex| regex.is_match(value))
+    }
+}
+
+#[cfg(feature = "setting-patterns")]
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+#[cfg(feature = "setting-patterns")]
+impl Eq for Pattern {}
+
+#[cfg(feature = "setting-patterns")]
+impl From<Cow<'static, str>> for Pattern {
+    fn from(source: Cow<'static, str>) -> Self {
+        Self::new(source)
+    }
+}
+
+#[cfg(feature = "setting-patterns")]
+impl From<Pattern> for Cow<'static, str> {
+    fn from(pattern: Pattern) -> Self {
+        pattern.source
+    }
+}
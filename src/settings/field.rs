@@ -1,25 +1,65 @@
-use super::Value;
+use super::{Constraints, ValidationError, Value};
 use alloc::borrow::Cow;
 use serde_derive::{Deserialize, Serialize};
 
 /// A Field describes a single setting by its name and its current value.
 #[derive(Serialize, Deserialize)]
 pub struct Field {
+    /// An identifier of the kind of setting that stays the same even if the
+    /// name of the setting changes. This can be used to look up translations
+    /// of the name and the tooltip. Settings that are repeated, such as the
+    /// settings of each column of the Splits Component, share the same key, so
+    /// only the index of a field identifies a specific setting.
+    #[serde(default)]
+    pub key: Cow<'static, str>,
     /// The name of the setting.
     pub text: Cow<'static, str>,
     /// The tooltip to show for the setting.
     pub tooltip: Cow<'static, str>,
     /// The current value of the setting.
     pub value: Value,
+    /// The constraints the values of the setting need to satisfy.
+    #[serde(default, skip_serializing_if = "Constraints::is_empty")]
+    pub constraints: Constraints,
+    /// The value the setting has by default. This is [`None`] if the default
+    /// is not known, such as for settings that only exist because of the
+    /// values of other settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<Value>,
 }
 
 impl Field {
     /// Creates a new field.
-    pub const fn new(text: Cow<'static, str>, tooltip: Cow<'static, str>, value: Value) -> Self {
+    pub const fn new(
+        key: &'static str,
+        text: Cow<'static, str>,
+        tooltip: Cow<'static, str>,
+        value: Value,
+    ) -> Self {
         Self {
+            key: Cow::Borrowed(key),
             text,
             tooltip,
             value,
+            constraints: Constraints::NONE,
+            default_value: None,
         }
     }
+
+    /// Restricts the values of the setting to the ones that satisfy the
+    /// constraints provided.
+    pub fn with_constraints(mut self, constraints: Constraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Checks whether the value provided can be set as the new value of the
+    /// setting. The value needs to be convertible to the type of the current
+    /// value and needs to satisfy the constraints.
+    pub fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        if !value.is_convertible_to_type_of(&self.value) {
+            return Err(ValidationError::WrongType);
+        }
+        self.constraints.validate(value)
+    }
 }
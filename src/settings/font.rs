@@ -1,5 +1,11 @@
-use crate::platform::prelude::*;
+use crate::platform::{Arc, prelude::*};
+use core::{fmt, hash};
+use serde::{
+    Deserializer, Serializer,
+    de::{self, Visitor},
+};
 use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Describes a Font to visualize text with. Depending on the platform, a font
 /// that matches the settings most closely is chosen. The settings may be
@@ -29,6 +35,130 @@ pub struct Font {
     pub weight: Weight,
     /// The stretch of the font to prefer selecting.
     pub stretch: Stretch,
+    /// The contents of a font file to use instead of looking up the font among
+    /// the fonts installed on the system. This way the layout looks the same
+    /// on every system, even if the font isn't installed there. The family
+    /// name is still used as a fallback for renderers that can't make use of
+    /// the font file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<FontData>,
+}
+
+/// The contents of a font file that is embedded into a [`Font`]. Each font file
+/// is identified by a strong hash of its contents, so it can be compared and
+/// cached cheaply.
+#[derive(Clone)]
+pub struct FontData {
+    data: Arc<[u8]>,
+    id: [u8; 32],
+}
+
+impl FontData {
+    /// Creates the embedded font from the contents of a font file. The
+    /// formats that are supported depend on the renderer, but TrueType and
+    /// OpenType fonts and collections are expected to be supported.
+    pub fn new(data: Arc<[u8]>) -> Self {
+        let hash = Sha256::digest(&*data);
+        Self {
+            data,
+            id: hash.into(),
+        }
+    }
+
+    /// Accesses the contents of the font file.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Accesses the contents of the font file as a shared buffer.
+    #[inline]
+    pub const fn shared_data(&self) -> &Arc<[u8]> {
+        &self.data
+    }
+
+    /// Accesses the font file's ID. This is a unique identifier for the
+    /// contents of the font file. It is implemented via a SHA-256 hash.
+    #[inline]
+    pub const fn id(&self) -> &[u8; 32] {
+        &self.id
+    }
+}
+
+impl fmt::Debug for FontData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FontData")
+            .field("len", &self.data.len())
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl PartialEq for FontData {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for FontData {}
+
+impl hash::Hash for FontData {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl serde::Serialize for FontData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64_simd::STANDARD.encode_to_string(self.data()))
+        } else {
+            serializer.serialize_bytes(self.data())
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for FontData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FontDataVisitor)
+        } else {
+            deserializer.deserialize_bytes(FontDataVisitor)
+        }
+    }
+}
+
+struct FontDataVisitor;
+
+impl Visitor<'_> for FontDataVisitor {
+    type Value = FontData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a base64 encoded font file or its bytes")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<FontData, E>
+    where
+        E: de::Error,
+    {
+        let data = base64_simd::STANDARD
+            .decode_to_vec(value.as_bytes())
+            .map_err(de::Error::custom)?;
+        Ok(FontData::new(data.into()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<FontData, E>
+    where
+        E: de::Error,
+    {
+        Ok(FontData::new(v.into()))
+    }
 }
 
 /// The style specifies whether to use a normal or italic version of a font. The
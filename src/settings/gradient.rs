@@ -14,6 +14,100 @@ pub enum Gradient {
     Vertical(Color, Color),
     /// Use a horizontal gradient (Left, Right).
     Horizontal(Color, Color),
+    /// Use a gradient (Start, End) along an angle in degrees. An angle of 0°
+    /// is the same as a vertical gradient and an angle of 90° is the same as a
+    /// horizontal gradient.
+    Angled(Color, Color, f32),
+    /// Use a radial gradient (Center, Edge) around a center point. The center
+    /// `[x, y]` and the radius are relative to the size of the region, so a
+    /// center of `[0.5, 0.5]` and a radius of 0.5 describe an ellipse that
+    /// touches all four sides of the region.
+    Radial(Color, Color, [f32; 2], f32),
+    /// Use a conic gradient (Start, End) that sweeps clockwise around a center
+    /// point. The center `[x, y]` is relative to the size of the region. The
+    /// last value is the angle in degrees that the sweep starts at, where 0°
+    /// points straight up.
+    Conic(Color, Color, [f32; 2], f32),
+    /// Use a vertical gradient (Top, Bottom) whose hues continuously cycle
+    /// through the color wheel. The last value is the number of seconds it
+    /// takes to complete one full cycle.
+    HueCycling(Color, Color, f32),
+    /// Use a gradient (Start, End) whose angle continuously rotates. The last
+    /// value is the number of seconds it takes to complete one full rotation.
+    Rotating(Color, Color, f32),
+}
+
+impl Gradient {
+    /// Returns `true` if the gradient changes over time.
+    pub const fn is_animated(&self) -> bool {
+        matches!(self, Gradient::HueCycling(..) | Gradient::Rotating(..))
+    }
+
+    /// Resolves the gradient to what it looks like at the point in time
+    /// provided, in seconds. Animated gradients are turned into the static
+    /// gradient they show at that time, while static gradients are returned
+    /// as they are. The time is usually the
+    /// [`animation_time`](crate::layout::LayoutState::animation_time) of the
+    /// layout state.
+    pub fn at(self, seconds: f64) -> Gradient {
+        match self {
+            Gradient::HueCycling(top, bottom, period) => {
+                let shift = 360.0 * phase(seconds, period);
+                Gradient::Vertical(shift_hue(top, shift), shift_hue(bottom, shift))
+            }
+            Gradient::Rotating(start, end, period) => {
+                Gradient::Angled(start, end, 360.0 * phase(seconds, period))
+            }
+            _ => self,
+        }
+    }
+
+    /// Multiplies the alpha of each of the colors of the gradient with the
+    /// opacity provided.
+    pub fn with_opacity(self, opacity: f32) -> Gradient {
+        let fade = |color: Color| Color {
+            alpha: color.alpha * opacity,
+            ..color
+        };
+        match self {
+            Gradient::Transparent => Gradient::Transparent,
+            Gradient::Plain(color) => Gradient::Plain(fade(color)),
+            Gradient::Vertical(top, bottom) => Gradient::Vertical(fade(top), fade(bottom)),
+            Gradient::Horizontal(left, right) => Gradient::Horizontal(fade(left), fade(right)),
+            Gradient::Angled(start, end, angle) => Gradient::Angled(fade(start), fade(end), angle),
+            Gradient::Radial(center, edge, pos, radius) => {
+                Gradient::Radial(fade(center), fade(edge), pos, radius)
+            }
+            Gradient::Conic(start, end, pos, angle) => {
+                Gradient::Conic(fade(start), fade(end), pos, angle)
+            }
+            Gradient::HueCycling(top, bottom, period) => {
+                Gradient::HueCycling(fade(top), fade(bottom), period)
+            }
+            Gradient::Rotating(start, end, period) => {
+                Gradient::Rotating(fade(start), fade(end), period)
+            }
+        }
+    }
+}
+
+/// Calculates how far along its current cycle an animation with the period
+/// provided is, as a value between 0 and 1.
+fn phase(seconds: f64, period: f32) -> f32 {
+    if period.is_nan() || period <= 0.0 {
+        return 0.0;
+    }
+    let phase = (seconds / period as f64) % 1.0;
+    if phase < 0.0 {
+        phase as f32 + 1.0
+    } else {
+        phase as f32
+    }
+}
+
+fn shift_hue(color: Color, shift: f32) -> Color {
+    let [hue, saturation, value, alpha] = color.to_hsva();
+    Color::hsva((hue + shift) % 360.0, saturation, value, alpha)
 }
 
 /// Describes an extended form of a gradient, specifically made for use with
@@ -5,18 +5,29 @@ use hashbrown::{hash_table::Entry, HashTable};
 use slab::Slab;
 
 use super::{Image, ImageId};
+use crate::settings::{CacheLimits, CacheUsage, EvictionPolicy};
 
 /// A trait for types that have an image ID. This is used for the [`ImageCache`]
 /// to look up images by their ID.
 pub trait HasImageId {
     /// Returns the image ID of the object.
     fn image_id(&self) -> &ImageId;
+
+    /// Returns the amount of memory in bytes the object uses. This is used to
+    /// keep the [`ImageCache`] within its limits. By default this is 0.
+    fn byte_len(&self) -> usize {
+        0
+    }
 }
 
 impl HasImageId for Image {
     fn image_id(&self) -> &ImageId {
         &self.id
     }
+
+    fn byte_len(&self) -> usize {
+        self.data().len()
+    }
 }
 
 /// A cache for images that allows looking up images by their ID. The cache uses
@@ -25,17 +36,21 @@ impl HasImageId for Image {
 /// image it stores, so you may use it to store textures or image URLs as well.
 /// Functions updating the cache usually don't run the garbage collection
 /// themselves, so make sure to call [`collect`](Self::collect) every now and
-/// then to remove unvisited images.
+/// then to remove unvisited images. How many images are kept can be
+/// configured with [`CacheLimits`].
 pub struct ImageCache<T = Image> {
     table: HashTable<Key>,
     elements: Slab<Element<T>>,
     bitvec_visited: Vec<u64>,
     newest: Key,
     oldest: Key,
+    limits: CacheLimits,
+    bytes: usize,
 }
 
 struct Element<T> {
     value: T,
+    bytes: usize,
     newer: Key,
     older: Key,
 }
@@ -57,9 +72,10 @@ const KEY_NONE: Key = Key::MAX;
 // element in the doubly linked list. We also have a bit vector that stores
 // which elements have been visited with the key being used to determine the bit
 // position in the bit vector. To garbage collect we first determine how many
-// elements to collect based on the fill rate of the bit vector. We then drain
-// elements from the least recently used side of the doubly linked list and
-// reset the bit vector to 0.
+// elements to keep based on the fill rate of the bit vector and the limits. We
+// then drain elements from the least recently used side of the doubly linked
+// list, until both the amount of elements and their size are within the
+// limits, and reset the bit vector to 0.
 
 impl<T: HasImageId> Default for ImageCache<T> {
     fn default() -> Self {
@@ -76,6 +92,33 @@ impl<T: HasImageId> ImageCache<T> {
             bitvec_visited: Vec::new(),
             newest: KEY_NONE,
             oldest: KEY_NONE,
+            limits: CacheLimits {
+                max_entries: None,
+                max_bytes: None,
+                eviction_policy: EvictionPolicy::Adaptive,
+            },
+            bytes: 0,
+        }
+    }
+
+    /// Returns the limits the garbage collection keeps the cache within.
+    pub const fn limits(&self) -> &CacheLimits {
+        &self.limits
+    }
+
+    /// Sets the limits the garbage collection keeps the cache within. The
+    /// limits are only applied the next time [`collect`](Self::collect) is
+    /// called.
+    pub const fn set_limits(&mut self, limits: CacheLimits) {
+        self.limits = limits;
+    }
+
+    /// Returns how many images the cache currently holds and how much memory
+    /// they use.
+    pub fn usage(&self) -> CacheUsage {
+        CacheUsage {
+            entries: self.table.len(),
+            bytes: self.bytes,
         }
     }
 
@@ -122,8 +165,12 @@ impl<T: HasImageId> ImageCache<T> {
                 element_key
             }
             Entry::Vacant(v) => {
+                let value = build();
+                let bytes = value.byte_len();
+                self.bytes += bytes;
                 let element_key = self.elements.insert(Element {
-                    value: build(),
+                    value,
+                    bytes,
                     newer: KEY_NONE,
                     older: KEY_NONE,
                 });
@@ -154,34 +201,22 @@ impl<T: HasImageId> ImageCache<T> {
     }
 
     /// Runs the garbage collection of the cache. This removes images from the
-    /// cache that have not been visited since the last garbage collection. Not
-    /// every image that has not been visited is removed. There is a heuristic
-    /// that keeps a certain amount of images in the cache regardless of whether
-    /// they have been visited or not. Returns the amount of images that got
-    /// collected.
+    /// cache that have not been visited since the last garbage collection,
+    /// according to the [`EvictionPolicy`] of the limits. By default, not every
+    /// image that has not been visited is removed. There is a heuristic that
+    /// keeps a certain amount of images in the cache regardless of whether
+    /// they have been visited or not. Afterwards the least recently used images
+    /// are removed until the cache is within its limits. Returns the amount of
+    /// images that got collected.
     pub fn collect(&mut self) -> usize {
-        // The strategy is to allow twice the amount of images in the cache than
-        // the ones that are currently actively still being used + 5 extra so
-        // the strategy is still useful at a low amount of images.
-        let total_collect_count = self
-            .table
-            .len()
-            .saturating_sub(2 * self.visited_count() + 5);
-
-        if total_collect_count > 0 {
-            let mut current_oldest = self.oldest;
-            for _ in 0..total_collect_count {
-                let removed = self.elements.remove(current_oldest);
-                self.table
-                    .find_entry(removed.value.image_id().hash(), |&image_key| {
-                        image_key == current_oldest
-                    })
-                    .unwrap()
-                    .remove();
-                current_oldest = removed.newer;
-            }
-            self.oldest = current_oldest;
-            self.elements[self.oldest].older = KEY_NONE;
+        let retained = self.limits.retained_entries(self.visited_count());
+
+        let mut total_collect_count = 0;
+        while self.oldest != KEY_NONE
+            && (self.table.len() > retained || self.limits.exceeds_bytes(self.bytes))
+        {
+            self.remove_oldest();
+            total_collect_count += 1;
         }
 
         self.bitvec_visited.fill(0);
@@ -189,6 +224,25 @@ impl<T: HasImageId> ImageCache<T> {
         total_collect_count
     }
 
+    fn remove_oldest(&mut self) {
+        let oldest = self.oldest;
+        let removed = self.elements.remove(oldest);
+        self.table
+            .find_entry(removed.value.image_id().hash(), |&image_key| {
+                image_key == oldest
+            })
+            .unwrap()
+            .remove();
+        self.bytes -= removed.bytes;
+
+        self.oldest = removed.newer;
+        if self.oldest != KEY_NONE {
+            self.elements[self.oldest].older = KEY_NONE;
+        } else {
+            self.newest = KEY_NONE;
+        }
+    }
+
     fn visited_count(&self) -> usize {
         self.bitvec_visited
             .iter()
@@ -277,6 +331,62 @@ mod tests {
         assert_eq!(image_cache.table.len(), 5);
     }
 
+    #[test]
+    fn stays_within_limits() {
+        struct SizedImage(ImageId, usize);
+
+        impl HasImageId for SizedImage {
+            fn image_id(&self) -> &ImageId {
+                &self.0
+            }
+
+            fn byte_len(&self) -> usize {
+                self.1
+            }
+        }
+
+        let mut image_cache = ImageCache::new();
+        image_cache.set_limits(CacheLimits {
+            max_entries: Some(8),
+            max_bytes: Some(500),
+            eviction_policy: EvictionPolicy::OverLimits,
+        });
+
+        for i in 0..10 {
+            let id = ImageId([i; 32]);
+            image_cache.cache(&id, || SizedImage(id, 100));
+        }
+        assert_eq!(
+            image_cache.usage(),
+            CacheUsage {
+                entries: 10,
+                bytes: 1000,
+            },
+        );
+
+        // Even though all of the images were visited, the least recently used
+        // ones are removed to stay within the limits.
+        assert_eq!(image_cache.collect(), 5);
+        assert_consistency(&image_cache);
+        assert_eq!(
+            image_cache.usage(),
+            CacheUsage {
+                entries: 5,
+                bytes: 500,
+            },
+        );
+        assert!(image_cache.lookup(&ImageId([4; 32])).is_none());
+        assert!(image_cache.lookup(&ImageId([5; 32])).is_some());
+
+        image_cache.set_limits(CacheLimits {
+            max_entries: Some(0),
+            ..CacheLimits::UNLIMITED
+        });
+        assert_eq!(image_cache.collect(), 5);
+        assert_consistency(&image_cache);
+        assert_eq!(image_cache.usage(), CacheUsage::default());
+    }
+
     #[test]
     fn single() {
         let mut image_cache = ImageCache::new();
@@ -19,7 +19,9 @@ pub use image_id::ImageId;
 
 /// Images can be used to store segment and game icons. Each image object comes
 /// with a strong hash to quickly compare images. There's no specific image
-/// format you need to use for the images.
+/// format you need to use for the images. Besides the usual raster formats,
+/// images may also be SVG documents. Those are never shrunk and renderers that
+/// support them rasterize them at whatever size they are drawn at.
 #[derive(Clone)]
 pub struct Image {
     data: Option<Arc<[u8]>>,
@@ -145,11 +147,14 @@ impl Image {
         }
     }
 
-    /// Creates a new image with the image data provided.
+    /// Creates a new image with the image data provided. SVG images are kept
+    /// as they are, as they can be scaled to any size without losing quality.
     pub fn new(data: Arc<[u8]>, max_image_size: u32) -> Self {
         let _ = max_image_size;
         #[cfg(all(feature = "std", feature = "image-shrinking"))]
-        let data = {
+        let data = if is_svg(&data) {
+            data
+        } else {
             match shrinking::shrink(&data, max_image_size) {
                 alloc::borrow::Cow::Borrowed(_) => data,
                 alloc::borrow::Cow::Owned(data) => data.into(),
@@ -193,4 +198,28 @@ impl Image {
     pub fn is_empty(&self) -> bool {
         self.data().is_empty()
     }
+
+    /// Checks if the image is an SVG document rather than a raster image.
+    #[inline]
+    pub fn is_svg(&self) -> bool {
+        is_svg(self.data())
+    }
+}
+
+/// Checks if the data is an SVG document. This only looks at the beginning of
+/// the data, so it doesn't guarantee that the document is valid.
+pub(crate) fn is_svg(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let data = data.trim_ascii_start();
+    if data.starts_with(b"<svg") {
+        return true;
+    }
+    if !data.starts_with(b"<?xml") && !data.starts_with(b"<!") {
+        return false;
+    }
+    // The XML declaration, comments and the doctype may precede the root
+    // element, so we search for it within the beginning of the document.
+    data[..data.len().min(4096)]
+        .windows(4)
+        .any(|window| window == b"<svg")
 }
@@ -0,0 +1,18 @@
+use alloc::borrow::Cow;
+
+/// A Localizer translates the names and tooltips of settings and the headings
+/// of their groups into another language. The settings are identified by their
+/// keys, which stay the same even if the English names change. The keys only
+/// have a meaning within a single kind of
+/// [`SettingsDescription`](super::SettingsDescription), so a Localizer is meant
+/// to be used for the settings of a specific kind of component or for the
+/// general settings.
+pub trait Localizer {
+    /// Translates the name of the setting or the heading of the group with the
+    /// key provided. Returning [`None`] keeps the English name.
+    fn name(&self, key: &str) -> Option<Cow<'static, str>>;
+
+    /// Translates the tooltip of the setting with the key provided. Returning
+    /// [`None`] keeps the English tooltip.
+    fn tooltip(&self, key: &str) -> Option<Cow<'static, str>>;
+}
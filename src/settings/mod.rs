@@ -2,25 +2,44 @@
 //! [`Component`](crate::layout::Component) and various other settings.
 
 mod alignment;
+mod blend_mode;
+mod cache_limits;
 mod color;
+mod component_background;
+mod constraints;
 mod field;
 mod font;
 mod gradient;
 mod image;
 mod layout_background;
+mod localizer;
 mod semantic_color;
 mod settings_description;
 mod value;
 
 pub use self::{
     alignment::Alignment,
+    blend_mode::BlendMode,
+    cache_limits::{CacheLimits, CacheUsage, EvictionPolicy},
     color::Color,
+    component_background::{ComponentBackgroundImage, ImageFit},
+    constraints::{Constraints, ValidationError},
     field::Field,
-    font::{Font, Stretch as FontStretch, Style as FontStyle, Weight as FontWeight},
+    font::{Font, FontData, Stretch as FontStretch, Style as FontStyle, Weight as FontWeight},
     gradient::{Gradient, ListGradient},
     image::{HasImageId, Image, ImageCache, ImageId},
-    layout_background::{BackgroundImage, LayoutBackground, BLUR_FACTOR},
+    layout_background::{BLUR_FACTOR, BackgroundImage, LayoutBackground},
+    localizer::Localizer,
     semantic_color::SemanticColor,
-    settings_description::SettingsDescription,
-    value::{ColumnKind, Error as ValueError, Result as ValueResult, Value},
+    settings_description::{SettingsDescription, SettingsGroup},
+    value::{ColumnKind, Error as ValueError, Result as ValueResult, Value, ValueList},
 };
+
+#[cfg(feature = "setting-patterns")]
+pub use self::constraints::Pattern;
+
+#[cfg(all(
+    feature = "svg-images",
+    any(feature = "software-rendering", feature = "svg-rendering")
+))]
+pub(crate) use self::image::is_svg;
@@ -1,5 +1,7 @@
-use super::Field;
+use super::{Field, Localizer, ValidationError, Value};
 use crate::platform::prelude::*;
+use alloc::borrow::Cow;
+use core::ops::Range;
 use serde_derive::{Deserialize, Serialize};
 
 /// A generic description of the settings available and their current values.
@@ -8,11 +10,163 @@ pub struct SettingsDescription {
     /// All of the different settings that are available and their current
     /// values.
     pub fields: Vec<Field>,
+    /// The groups the fields are organized in. Each group covers a contiguous
+    /// range of fields and the groups are ordered by the fields they cover.
+    /// Fields that are not covered by any group are meant to be shown without
+    /// a heading. The groups don't affect the indices of the fields.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<SettingsGroup>,
+}
+
+/// A group of fields of a [`SettingsDescription`] that is meant to be shown
+/// under a common heading, such as all the settings that affect the colors of
+/// a component. Settings dialogs can show each group as a collapsible section.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingsGroup {
+    /// An identifier of the kind of group that stays the same even if the
+    /// heading changes. This can be used to look up a translation of the
+    /// heading. Groups that are repeated, such as the groups of the columns of
+    /// the Splits Component, share the same key. This is [`None`] if the
+    /// heading is not meant to be translated, such as when it is a name the
+    /// user chose.
+    #[serde(default)]
+    pub key: Option<Cow<'static, str>>,
+    /// The heading of the group.
+    pub heading: Cow<'static, str>,
+    /// The index of the first field that is part of the group.
+    pub start: usize,
+    /// The index right after the last field that is part of the group.
+    pub end: usize,
+    /// Specifies whether the group should start out collapsed. This is the
+    /// case for groups that are usually not of interest.
+    pub collapsed: bool,
 }
 
 impl SettingsDescription {
     /// Creates a new Settings Description with the settings provided.
     pub const fn with_fields(fields: Vec<Field>) -> Self {
-        Self { fields }
+        Self {
+            fields,
+            groups: Vec::new(),
+        }
+    }
+
+    /// Provides each field with its default value based on the Settings
+    /// Description of the same kind of settings in their default state. Fields
+    /// are matched by their index and only if their keys are the same, as the
+    /// fields that are available may depend on the values of other settings.
+    /// Lists don't get a default value, as resetting them would throw away all
+    /// of their items.
+    pub fn with_defaults(mut self, defaults: SettingsDescription) -> Self {
+        for (field, default) in self.fields.iter_mut().zip(defaults.fields) {
+            if field.key == default.key && !matches!(default.value, Value::List(_)) {
+                field.default_value = Some(default.value);
+            }
+        }
+        self
+    }
+
+    /// Translates the names and tooltips of the fields and the headings of the
+    /// groups with the localizer provided. Anything the localizer doesn't have
+    /// a translation for stays unchanged.
+    pub fn localize<L: Localizer + ?Sized>(&mut self, localizer: &L) {
+        for field in &mut self.fields {
+            if let Some(text) = localizer.name(&field.key) {
+                field.text = text;
+            }
+            if let Some(tooltip) = localizer.tooltip(&field.key) {
+                field.tooltip = tooltip;
+            }
+        }
+        for group in &mut self.groups {
+            if let Some(heading) = group.key.as_deref().and_then(|key| localizer.name(key)) {
+                group.heading = heading;
+            }
+        }
+    }
+
+    /// Organizes the fields within the range of indices provided into a group
+    /// with the key and heading provided. The range must not overlap with any
+    /// of the groups that were already added and it needs to come after them.
+    pub fn add_group(
+        &mut self,
+        key: &'static str,
+        heading: &'static str,
+        fields: Range<usize>,
+        collapsed: bool,
+    ) {
+        self.push_group(Some(key.into()), heading.into(), fields, collapsed);
+    }
+
+    /// Organizes the fields within the range of indices provided into a group
+    /// with a heading that is not meant to be translated, such as a name the
+    /// user chose. The range must not overlap with any of the groups that were
+    /// already added and it needs to come after them.
+    pub fn add_untranslated_group(
+        &mut self,
+        heading: String,
+        fields: Range<usize>,
+        collapsed: bool,
+    ) {
+        self.push_group(None, heading.into(), fields, collapsed);
+    }
+
+    fn push_group(
+        &mut self,
+        key: Option<Cow<'static, str>>,
+        heading: Cow<'static, str>,
+        fields: Range<usize>,
+        collapsed: bool,
+    ) {
+        debug_assert!(fields.end <= self.fields.len());
+        debug_assert!(
+            self.groups
+                .last()
+                .is_none_or(|last| last.end <= fields.start)
+        );
+        self.groups.push(SettingsGroup {
+            key,
+            heading,
+            start: fields.start,
+            end: fields.end,
+            collapsed,
+        });
+    }
+
+    /// Organizes all the fields that were added after the last group into a
+    /// group with the key and heading provided.
+    pub fn add_group_for_remaining_fields(
+        &mut self,
+        key: &'static str,
+        heading: &'static str,
+        collapsed: bool,
+    ) {
+        let start = self.groups.last().map_or(0, |last| last.end);
+        self.add_group(key, heading, start..self.fields.len(), collapsed);
+    }
+
+    /// Checks whether the value provided can be set for the field with the
+    /// index provided. The value needs to have a type that is compatible with
+    /// the field's value and it needs to satisfy the field's constraints.
+    pub fn validate(&self, index: usize, value: &Value) -> Result<(), ValidationError> {
+        self.fields
+            .get(index)
+            .ok_or(ValidationError::UnknownSetting)?
+            .validate(value)
+    }
+
+    /// Accesses the group the field with the index provided belongs to. If the
+    /// field is not part of any group, [`None`] is returned.
+    pub fn group_of(&self, field_index: usize) -> Option<&SettingsGroup> {
+        self.groups
+            .iter()
+            .find(|group| (group.start..group.end).contains(&field_index))
+    }
+}
+
+impl SettingsGroup {
+    /// The range of indices of the fields that are part of the group.
+    pub const fn fields(&self) -> Range<usize> {
+        self.start..self.end
     }
 }
@@ -4,10 +4,13 @@ use crate::{
         splits::{ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith},
         timer::DeltaGradient,
     },
-    hotkey::Hotkey,
-    layout::LayoutDirection,
+    hotkey::{Binding, Hotkey, MidiMessage},
+    layout::{LayoutDirection, ThemedColor},
     platform::prelude::*,
-    settings::{Alignment, Color, Font, Gradient, ImageId, LayoutBackground, ListGradient},
+    settings::{
+        Alignment, BlendMode, Color, ComponentBackgroundImage, Font, Gradient, ImageId,
+        LayoutBackground, ListGradient,
+    },
     timing::formatter::{Accuracy, DigitsFormat},
 };
 use core::result::Result as StdResult;
@@ -22,6 +25,30 @@ pub enum ColumnKind {
     Variable,
 }
 
+/// An ordered list of values of the same type. This allows components to
+/// expose collections, such as the names of their tabs, as a single setting.
+/// The list is always set as a whole, so a frontend may add, remove and reorder
+/// the items before setting the modified list.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueList {
+    /// The items of the list in order.
+    pub items: Vec<Value>,
+    /// The value a newly added item starts out with. This also describes the
+    /// type of the items, even if the list is empty.
+    pub new_item: Box<Value>,
+}
+
+impl ValueList {
+    /// Creates a new list with the items provided. Newly added items start out
+    /// with the value provided.
+    pub fn new<T: Into<Value>>(items: impl IntoIterator<Item = T>, new_item: Value) -> Self {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            new_item: Box::new(new_item),
+        }
+    }
+}
+
 /// Describes a setting's value. Such a value can be of a variety of different
 /// types.
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -64,8 +91,12 @@ pub enum Value {
     ColumnUpdateWith(ColumnUpdateWith),
     /// A value describing when to update a column of the Splits Component.
     ColumnUpdateTrigger(ColumnUpdateTrigger),
-    /// A value describing what hotkey to press to trigger a certain action.
-    Hotkey(Option<Hotkey>),
+    /// A value describing what hotkey or sequence of hotkeys to press to
+    /// trigger a certain action.
+    Hotkey(Option<Binding>),
+    /// A value describing what MIDI message to use to trigger a certain
+    /// action.
+    MidiMessage(Option<MidiMessage>),
     /// A value describing the direction of a layout.
     LayoutDirection(LayoutDirection),
     /// A value describing a font to use. [`None`] if a default font should be
@@ -76,6 +107,16 @@ pub enum Value {
     DeltaGradient(DeltaGradient),
     /// A value describing the background of a layout.
     LayoutBackground(LayoutBackground<ImageId>),
+    /// A color that is either a fixed color or refers to one of the colors of
+    /// the layout's theme or its palette.
+    ThemedColor(ThemedColor),
+    /// A value describing how the colors of a component are combined with the
+    /// colors below it.
+    BlendMode(BlendMode),
+    /// An optional image that is shown behind a component.
+    ComponentBackgroundImage(Option<ComponentBackgroundImage<ImageId>>),
+    /// An ordered list of values of the same type.
+    List(ValueList),
 }
 
 impl From<bool> for Value {
@@ -174,9 +215,21 @@ impl From<ColumnUpdateTrigger> for Value {
     }
 }
 
+impl From<Option<Binding>> for Value {
+    fn from(x: Option<Binding>) -> Self {
+        Value::Hotkey(x)
+    }
+}
+
 impl From<Option<Hotkey>> for Value {
     fn from(x: Option<Hotkey>) -> Self {
-        Value::Hotkey(x)
+        Value::Hotkey(x.map(Into::into))
+    }
+}
+
+impl From<Option<MidiMessage>> for Value {
+    fn from(x: Option<MidiMessage>) -> Self {
+        Value::MidiMessage(x)
     }
 }
 
@@ -210,6 +263,30 @@ impl From<LayoutBackground<ImageId>> for Value {
     }
 }
 
+impl From<ThemedColor> for Value {
+    fn from(x: ThemedColor) -> Self {
+        Value::ThemedColor(x)
+    }
+}
+
+impl From<BlendMode> for Value {
+    fn from(x: BlendMode) -> Self {
+        Value::BlendMode(x)
+    }
+}
+
+impl From<Option<ComponentBackgroundImage<ImageId>>> for Value {
+    fn from(x: Option<ComponentBackgroundImage<ImageId>>) -> Self {
+        Value::ComponentBackgroundImage(x)
+    }
+}
+
+impl From<ValueList> for Value {
+    fn from(x: ValueList) -> Self {
+        Value::List(x)
+    }
+}
+
 /// The Error type for values that couldn't be converted.
 #[derive(Debug, snafu::Snafu)]
 pub enum Error {
@@ -354,8 +431,8 @@ impl Value {
         }
     }
 
-    /// Tries to convert the value into a hotkey.
-    pub fn into_hotkey(self) -> Result<Option<Hotkey>> {
+    /// Tries to convert the value into a hotkey binding.
+    pub fn into_hotkey(self) -> Result<Option<Binding>> {
         match self {
             Value::Hotkey(v) => Ok(v),
             Value::String(v) | Value::OptionalString(Some(v)) => {
@@ -366,6 +443,18 @@ impl Value {
         }
     }
 
+    /// Tries to convert the value into a MIDI message.
+    pub fn into_midi_message(self) -> Result<Option<MidiMessage>> {
+        match self {
+            Value::MidiMessage(v) => Ok(v),
+            Value::String(v) | Value::OptionalString(Some(v)) => {
+                v.parse().map_err(|_| Error::WrongType).map(Some)
+            }
+            Value::OptionalString(None) => Ok(None),
+            _ => Err(Error::WrongType),
+        }
+    }
+
     /// Tries to convert the value into a layout direction.
     pub fn into_layout_direction(self) -> Result<LayoutDirection> {
         match self {
@@ -409,6 +498,84 @@ impl Value {
             _ => Err(Error::WrongType),
         }
     }
+
+    /// Tries to convert the value into a themed color.
+    pub fn into_themed_color(self) -> Result<ThemedColor> {
+        match self {
+            Value::Color(v) => Ok(ThemedColor::Custom(v)),
+            Value::ThemedColor(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Tries to convert the value into a blend mode.
+    pub fn into_blend_mode(self) -> Result<BlendMode> {
+        match self {
+            Value::BlendMode(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Tries to convert the value into an optional component background image.
+    pub fn into_component_background_image(
+        self,
+    ) -> Result<Option<ComponentBackgroundImage<ImageId>>> {
+        match self {
+            Value::ComponentBackgroundImage(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Tries to convert the value into a list of values.
+    pub fn into_list(self) -> Result<ValueList> {
+        match self {
+            Value::List(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Checks whether the value can be converted into the type of the other
+    /// value. This is the case for values of the same type, but also for
+    /// values that the conversions accept in place of the other value's type,
+    /// such as a color in place of a gradient. The items of a list need to be
+    /// convertible into the type of the other list's items.
+    pub fn is_convertible_to_type_of(&self, other: &Value) -> bool {
+        let value = self.clone();
+        match other {
+            Value::Bool(_) => value.into_bool().is_ok(),
+            Value::UInt(_) => value.into_uint().is_ok(),
+            Value::Int(_) => value.into_int().is_ok(),
+            Value::String(_) => value.into_string().is_ok(),
+            Value::OptionalString(_) => value.into_optional_string().is_ok(),
+            Value::Accuracy(_) => value.into_accuracy().is_ok(),
+            Value::DigitsFormat(_) => value.into_digits_format().is_ok(),
+            Value::OptionalTimingMethod(_) => value.into_optional_timing_method().is_ok(),
+            Value::Color(_) => value.into_color().is_ok(),
+            Value::OptionalColor(_) => value.into_optional_color().is_ok(),
+            Value::Gradient(_) => value.into_gradient().is_ok(),
+            Value::ListGradient(_) => value.into_list_gradient().is_ok(),
+            Value::Alignment(_) => value.into_alignment().is_ok(),
+            Value::ColumnKind(_) => value.into_column_kind().is_ok(),
+            Value::ColumnStartWith(_) => value.into_column_start_with().is_ok(),
+            Value::ColumnUpdateWith(_) => value.into_column_update_with().is_ok(),
+            Value::ColumnUpdateTrigger(_) => value.into_column_update_trigger().is_ok(),
+            Value::Hotkey(_) => value.into_hotkey().is_ok(),
+            Value::MidiMessage(_) => value.into_midi_message().is_ok(),
+            Value::LayoutDirection(_) => value.into_layout_direction().is_ok(),
+            Value::Font(_) => value.into_font().is_ok(),
+            Value::DeltaGradient(_) => value.into_delta_gradient().is_ok(),
+            Value::LayoutBackground(_) => value.into_layout_background().is_ok(),
+            Value::ThemedColor(_) => value.into_themed_color().is_ok(),
+            Value::BlendMode(_) => value.into_blend_mode().is_ok(),
+            Value::ComponentBackgroundImage(_) => value.into_component_background_image().is_ok(),
+            Value::List(list) => value.into_list().is_ok_and(|value| {
+                value
+                    .items
+                    .iter()
+                    .all(|item| item.is_convertible_to_type_of(&list.new_item))
+            }),
+        }
+    }
 }
 
 impl From<Value> for bool {
@@ -507,12 +674,18 @@ impl From<Value> for ColumnUpdateTrigger {
     }
 }
 
-impl From<Value> for Option<Hotkey> {
+impl From<Value> for Option<Binding> {
     fn from(value: Value) -> Self {
         value.into_hotkey().unwrap()
     }
 }
 
+impl From<Value> for Option<MidiMessage> {
+    fn from(value: Value) -> Self {
+        value.into_midi_message().unwrap()
+    }
+}
+
 impl From<Value> for LayoutDirection {
     fn from(value: Value) -> Self {
         value.into_layout_direction().unwrap()
@@ -542,3 +715,27 @@ impl From<Value> for LayoutBackground<ImageId> {
         value.into_layout_background().unwrap()
     }
 }
+
+impl From<Value> for ThemedColor {
+    fn from(value: Value) -> Self {
+        value.into_themed_color().unwrap()
+    }
+}
+
+impl From<Value> for BlendMode {
+    fn from(value: Value) -> Self {
+        value.into_blend_mode().unwrap()
+    }
+}
+
+impl From<Value> for Option<ComponentBackgroundImage<ImageId>> {
+    fn from(value: Value) -> Self {
+        value.into_component_background_image().unwrap()
+    }
+}
+
+impl From<Value> for ValueList {
+    fn from(value: Value) -> Self {
+        value.into_list().unwrap()
+    }
+}
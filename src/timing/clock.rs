@@ -0,0 +1,69 @@
+use crate::{TimeSpan, TimeStamp, platform::Arc};
+use core::fmt;
+
+/// A clock is the source of time a [`Timer`](crate::Timer) measures attempts
+/// with. By default the timer uses the [`MonotonicClock`], which is based on
+/// the platform's monotonic clock. A custom clock can be used to drive the
+/// timer deterministically, such as in tests, or to drive it from the time of
+/// an emulator core or a video file.
+pub trait Clock: Send + Sync {
+    /// Returns the current point in time as the amount of time that passed
+    /// since an arbitrary, but fixed point in time. The time may never go
+    /// backwards.
+    fn now(&self) -> TimeSpan;
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> TimeSpan {
+        (**self).now()
+    }
+}
+
+/// The default clock of the [`Timer`](crate::Timer), which is based on the
+/// platform's monotonic clock.
+#[derive(Copy, Clone, Debug)]
+pub struct MonotonicClock {
+    origin: TimeStamp,
+}
+
+impl MonotonicClock {
+    /// Creates a new monotonic clock that measures the time from the moment it
+    /// got created.
+    pub fn new() -> Self {
+        Self {
+            origin: TimeStamp::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> TimeSpan {
+        TimeStamp::now() - self.origin
+    }
+}
+
+/// A clock that is shared between the timer and its active attempt.
+#[derive(Clone)]
+pub(crate) struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self(Arc::new(clock))
+    }
+
+    pub fn now(&self) -> TimeSpan {
+        self.0.now()
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
@@ -0,0 +1,219 @@
+//! The event log module provides a log of all the commands that were applied
+//! to a [`Timer`](crate::Timer) during an attempt. Every command is recorded
+//! along with the time it was applied at, so the attempt can be reconstructed
+//! by replaying the log into a fresh [`Timer`](crate::Timer). This is useful
+//! for auditing attempts, debugging frontends and retiming tools. The log can
+//! also be stored along with the attempt in the attempt history, see
+//! [`Timer::set_stores_event_logs`](crate::Timer::set_stores_event_logs).
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::{Run, Segment, Timer};
+//!
+//! let mut run = Run::new();
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let mut timer = Timer::new(run.clone()).unwrap();
+//! timer.start().unwrap();
+//! timer.split().unwrap();
+//!
+//! let mut replayed = Timer::new(run).unwrap();
+//! replayed.replay(timer.event_log()).unwrap();
+//! assert_eq!(
+//!     replayed.run().segment(0).split_time(),
+//!     timer.run().segment(0).split_time(),
+//! );
+//! ```
+
+use crate::{TimeSpan, TimingMethod, platform::prelude::*};
+use serde_derive::{Deserialize, Serialize};
+
+/// A command that was applied to the [`Timer`](crate::Timer).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+    /// A new attempt was started.
+    Start,
+    /// A practice attempt was started at a segment in the middle of the run.
+    StartAtSegment {
+        /// The index of the segment the attempt started at.
+        segment_index: usize,
+        /// The comparison the split times of the previous segments were taken
+        /// from.
+        comparison: String,
+        /// Whether the segments practiced are stored in the segment history.
+        feed_segment_history: bool,
+    },
+    /// A split happened.
+    Split,
+    /// The current split was skipped.
+    SkipSplit,
+    /// The previous split was undone.
+    UndoSplit,
+    /// The attempt was reset.
+    Reset {
+        /// Whether the splits were updated with the attempt's information.
+        update_splits: bool,
+        /// The timing method that was active when the attempt was reset. It
+        /// determines whether the attempt is a new Personal Best.
+        timing_method: TimingMethod,
+    },
+    /// The attempt was reset and stored as the new Personal Best.
+    ResetAndSetAttemptAsPb,
+    /// The attempt was paused.
+    Pause,
+    /// The attempt was resumed.
+    Resume,
+    /// All the pauses of the attempt were undone.
+    UndoAllPauses,
+    /// The offset of the attempt was adjusted by the amount specified.
+    AdjustOffset(TimeSpan),
+    /// Time was added to or removed from the current time of the attempt.
+    AdjustTime {
+        /// The amount of time that was added.
+        amount: TimeSpan,
+        /// The timing method the time was adjusted for.
+        timing_method: TimingMethod,
+    },
+    /// The most recent action was undone.
+    Undo,
+    /// The most recently undone action was redone.
+    Redo,
+    /// The game time was initialized.
+    InitializeGameTime,
+    /// The game time was deinitialized.
+    DeinitializeGameTime,
+    /// The game time was set.
+    SetGameTime(TimeSpan),
+    /// The game time was paused.
+    PauseGameTime,
+    /// The game time was resumed.
+    ResumeGameTime,
+    /// The loading times were set.
+    SetLoadingTimes(TimeSpan),
+    /// A custom variable was set.
+    SetCustomVariable {
+        /// The name of the variable.
+        name: String,
+        /// The value the variable was set to.
+        value: String,
+    },
+}
+
+/// A single command in the [`EventLog`] along with the time it was applied at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The amount of time that passed since the attempt was started, as
+    /// measured by the system clock. Unlike the timer's time, this time keeps
+    /// moving forward while the timer is paused.
+    pub elapsed: TimeSpan,
+    /// The real time the timer was showing when the command was applied.
+    pub real_time: TimeSpan,
+    /// The game time the timer was showing when the command was applied, if
+    /// the game time was initialized.
+    pub game_time: Option<TimeSpan>,
+    /// The command that was applied.
+    pub command: Command,
+}
+
+/// A log of all the commands that were applied to a [`Timer`](crate::Timer)
+/// during an attempt. Updates to the game time, the loading times and a custom
+/// variable are merged with the previous update of the same kind, as long as
+/// only such updates happened in between, as only the most recent one
+/// influences the attempt. The log holds at most [`MAX_ENTRIES`] entries, any
+/// further commands are not recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLog {
+    entries: Vec<Entry>,
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    truncated: bool,
+}
+
+/// The maximum amount of entries an [`EventLog`] holds.
+pub const MAX_ENTRIES: usize = 1 << 16;
+
+impl EventLog {
+    /// Creates a new event log from the entries provided. This can be used to
+    /// replay entries that were stored externally.
+    pub const fn from_entries(entries: Vec<Entry>) -> Self {
+        Self {
+            entries,
+            truncated: false,
+        }
+    }
+
+    /// Accesses all the entries of the log in the order the commands were
+    /// applied.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns `true` if no commands were recorded.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the log reached [`MAX_ENTRIES`] and commands were
+    /// dropped. Replaying such a log only reconstructs the beginning of the
+    /// attempt.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub(crate) const fn mark_as_truncated(&mut self) {
+        self.truncated = true;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.truncated = false;
+    }
+
+    pub(crate) fn set_last_elapsed(&mut self, elapsed: TimeSpan) {
+        if let Some(last) = self.entries.last_mut() {
+            last.elapsed = elapsed;
+        }
+    }
+
+    pub(crate) fn push(&mut self, mut entry: Entry) {
+        let superseded = self
+            .entries
+            .iter()
+            .rev()
+            .take_while(|e| is_update(&e.command))
+            .position(|e| match (&e.command, &entry.command) {
+                (Command::SetGameTime(_), Command::SetGameTime(_))
+                | (Command::SetLoadingTimes(_), Command::SetLoadingTimes(_)) => true,
+                (
+                    Command::SetCustomVariable { name: a, .. },
+                    Command::SetCustomVariable { name: b, .. },
+                ) => a == b,
+                _ => false,
+            });
+        if let Some(position) = superseded {
+            // The game time before the merged commands were applied needs
+            // to be kept, as that's what the remaining command observes
+            // when it gets replayed.
+            let superseded = self.entries.remove(self.entries.len() - 1 - position);
+            entry.game_time = superseded.game_time;
+        }
+        self.push_entry(entry);
+    }
+
+    fn push_entry(&mut self, entry: Entry) {
+        if self.entries.len() < MAX_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Updates only override state that the following updates of the same kind
+/// override again, so they can be reordered with each other.
+const fn is_update(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::SetGameTime(_) | Command::SetLoadingTimes(_) | Command::SetCustomVariable { .. }
+    )
+}
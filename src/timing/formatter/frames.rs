@@ -0,0 +1,110 @@
+use super::{DASH, MINUS, PLUS, TimeFormatter};
+use crate::TimeSpan;
+use core::fmt::{Display, Formatter, Result};
+
+pub struct Inner {
+    time: Option<TimeSpan>,
+    frame_rate: f64,
+    is_delta: bool,
+}
+
+/// The Frames Time Formatter formats a [`TimeSpan`] as the amount of frames it
+/// represents at a certain frame rate. This is most suitable for games that
+/// are retimed by counting frames, such as console games running at 60 frames
+/// per second. The time is rounded to the nearest frame. The formatter can
+/// also be used for deltas, in which case positive amounts of frames are shown
+/// with a plus sign.
+///
+/// # Example Formatting
+///
+/// * Empty Time `—`
+/// * Times `4523`
+/// * Negative Times `−30`
+/// * Deltas `+30`
+pub struct Frames {
+    frame_rate: f64,
+    is_delta: bool,
+}
+
+impl Frames {
+    /// Creates a new Frames Time Formatter that counts the frames at the frame
+    /// rate provided in frames per second.
+    pub const fn new(frame_rate: f64) -> Self {
+        Frames {
+            frame_rate,
+            is_delta: false,
+        }
+    }
+
+    /// Creates a new Frames Time Formatter that counts the frames at the frame
+    /// rate provided in frames per second and shows a plus sign for positive
+    /// amounts of frames.
+    pub const fn delta(frame_rate: f64) -> Self {
+        Frames {
+            frame_rate,
+            is_delta: true,
+        }
+    }
+}
+
+impl TimeFormatter<'_> for Frames {
+    type Inner = Inner;
+
+    fn format<T>(&self, time: T) -> Self::Inner
+    where
+        T: Into<Option<TimeSpan>>,
+    {
+        Inner {
+            time: time.into(),
+            frame_rate: self.frame_rate,
+            is_delta: self.is_delta,
+        }
+    }
+}
+
+impl Display for Inner {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if let Some(time) = self.time {
+            let frames = time.to_frames(self.frame_rate);
+            if frames < 0 {
+                f.write_str(MINUS)?;
+            } else if self.is_delta && frames > 0 {
+                f.write_str(PLUS)?;
+            }
+            f.write_str(itoa::Buffer::new().format(frames.unsigned_abs()))
+        } else {
+            f.write_str(DASH)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let formatter = Frames::new(60.0);
+        assert_eq!(
+            formatter
+                .format(TimeSpan::from_frames(4523, 60.0))
+                .to_string(),
+            "4523"
+        );
+        assert_eq!(
+            formatter.format(TimeSpan::from_seconds(-0.5)).to_string(),
+            "−30"
+        );
+        assert_eq!(formatter.format(None).to_string(), "—");
+    }
+
+    #[test]
+    fn delta() {
+        let formatter = Frames::delta(60.0);
+        assert_eq!(
+            formatter.format(TimeSpan::from_seconds(0.5)).to_string(),
+            "+30"
+        );
+        assert_eq!(formatter.format(TimeSpan::zero()).to_string(), "0");
+    }
+}
@@ -25,6 +25,7 @@ mod complete;
 mod days;
 mod delta;
 mod digits_format;
+mod frames;
 pub mod none_wrapper;
 mod regular;
 mod segment_time;
@@ -32,7 +33,7 @@ pub mod timer;
 
 pub use self::{
     accuracy::Accuracy, complete::Complete, days::Days, delta::Delta, digits_format::DigitsFormat,
-    regular::Regular, segment_time::SegmentTime,
+    frames::Frames, regular::Regular, segment_time::SegmentTime,
 };
 
 use crate::TimeSpan;
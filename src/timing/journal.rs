@@ -0,0 +1,111 @@
+//! The journal module provides a way to recover an attempt that was in
+//! progress when the application crashed or got closed. A [`Journal`] captures
+//! everything necessary to reconstruct the attempt, so frontends are expected
+//! to periodically store it, for example after every split or every few
+//! seconds. After a crash, the attempt can then be resumed by recovering a
+//! [`Timer`](crate::Timer) from the journal. The attempt continues as if it
+//! never got interrupted, so the time that passed in the meantime is part of
+//! the attempt.
+//!
+//! # Examples
+//!
+//! ```
+//! use livesplit_core::{Run, Segment, Timer};
+//!
+//! let mut run = Run::new();
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//! run.push_segment(Segment::new("Cascade Kingdom"));
+//!
+//! let mut timer = Timer::new(run.clone()).unwrap();
+//! timer.start().unwrap();
+//! timer.split().unwrap();
+//! let journal = timer.journal().unwrap();
+//!
+//! let mut recovered = Timer::new(run).unwrap();
+//! recovered.recover(&journal).unwrap();
+//! assert_eq!(recovered.current_split_index(), Some(1));
+//! ```
+
+use super::event_log::EventLog;
+use crate::{AtomicDateTime, platform::DateTime};
+use serde_derive::{Deserialize, Serialize};
+
+/// A journal stores the state of an attempt that is in progress, so it can be
+/// recovered after a crash. It consists of the [`EventLog`] of the attempt
+/// along with the points in time the attempt was started at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(with = "unix_nanos")]
+    attempt_started: DateTime,
+    #[serde(with = "unix_nanos")]
+    clock_started: DateTime,
+    event_log: EventLog,
+}
+
+impl Journal {
+    pub(super) const fn new(
+        attempt_started: DateTime,
+        clock_started: DateTime,
+        event_log: EventLog,
+    ) -> Self {
+        Self {
+            attempt_started,
+            clock_started,
+            event_log,
+        }
+    }
+
+    /// Returns the date time the attempt was started at, as it is stored in
+    /// the attempt history.
+    pub const fn attempt_started(&self) -> AtomicDateTime {
+        AtomicDateTime::new(self.attempt_started, false)
+    }
+
+    /// Returns the date time at which the system clock of the attempt started
+    /// measuring. The commands in the event log are relative to this point in
+    /// time.
+    pub const fn clock_started(&self) -> DateTime {
+        self.clock_started
+    }
+
+    /// Accesses the event log of the attempt.
+    pub const fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    /// Decodes the journal from JSON.
+    #[cfg(feature = "std")]
+    pub fn from_json<R>(reader: R) -> serde_json::Result<Self>
+    where
+        R: std::io::Read,
+    {
+        serde_json::from_reader(reader)
+    }
+
+    /// Encodes the journal as JSON.
+    #[cfg(feature = "std")]
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+/// Stores date times as the amount of nanoseconds since the Unix epoch.
+mod unix_nanos {
+    use crate::platform::DateTime;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        date_time: &DateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i128(date_time.unix_timestamp_nanos())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime, D::Error> {
+        let nanos = i128::deserialize(deserializer)?;
+        DateTime::from_unix_timestamp_nanos(nanos).map_err(D::Error::custom)
+    }
+}
@@ -2,7 +2,11 @@
 //! measuring them.
 
 mod atomic_date_time;
+mod clock;
+pub mod event_log;
 pub mod formatter;
+pub mod journal;
+mod overlay_widget;
 mod time;
 mod time_span;
 mod time_stamp;
@@ -14,12 +18,20 @@ mod timing_method;
 pub use self::timer::SharedTimer;
 pub use self::{
     atomic_date_time::AtomicDateTime,
+    clock::{Clock, MonotonicClock},
+    overlay_widget::{OverlayWidget, OverlayWidgetValue},
     time::{GameTime, RealTime, Time},
     time_span::{ParseError, TimeSpan},
     time_stamp::TimeStamp,
-    timer::{CreationError as TimerCreationError, Snapshot, Timer},
+    timer::{
+        CreationError as TimerCreationError, DEFAULT_UNDO_LIMIT, RaceComparisonError, Snapshot,
+        Timer,
+    },
     timer_phase::TimerPhase,
     timing_method::TimingMethod,
 };
 
-pub(crate) use self::time_span::{parse_custom, CustomParser};
+pub(crate) use self::{
+    clock::SharedClock,
+    time_span::{CustomParser, parse_custom},
+};
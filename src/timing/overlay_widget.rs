@@ -0,0 +1,23 @@
+use crate::platform::prelude::*;
+
+/// An overlay widget displays information that is provided by an auto
+/// splitter, such as the health of a boss or the seed of the random number
+/// generator. The overlay widgets of the timer get visualized by the
+/// [`Overlay Widgets Component`](crate::component::overlay_widgets).
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct OverlayWidget {
+    /// The label that describes what the widget displays.
+    pub label: String,
+    /// The value that the widget displays.
+    pub value: OverlayWidgetValue,
+}
+
+/// The value that an [`OverlayWidget`] displays.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum OverlayWidgetValue {
+    /// A text that is displayed as is.
+    Text(String),
+    /// A progress bar that is filled by the given fraction. The fraction is
+    /// between 0 and 1.
+    Progress(f64),
+}
@@ -1,5 +1,6 @@
 use crate::{
-    platform::{Duration, prelude::*},
+    platform::{Duration, math, prelude::*},
+    timing::formatter::{Complete, TimeFormatter},
     util::ascii_char::AsciiChar,
 };
 use core::{
@@ -54,6 +55,25 @@ impl TimeSpan {
         1_000.0 * self.total_seconds()
     }
 
+    /// Creates a new `TimeSpan` from a given amount of frames at the frame rate
+    /// provided in frames per second.
+    pub fn from_frames(frames: i64, frame_rate: f64) -> Self {
+        Self::from_seconds(frames as f64 / frame_rate)
+    }
+
+    /// Returns the amount of frames this `TimeSpan` represents at the frame
+    /// rate provided in frames per second. The amount is rounded to the
+    /// nearest frame.
+    pub fn to_frames(&self, frame_rate: f64) -> i64 {
+        math::f64::round(self.total_seconds() * frame_rate) as i64
+    }
+
+    /// Rounds the `TimeSpan` to the nearest frame boundary at the frame rate
+    /// provided in frames per second.
+    pub fn round_to_frames(&self, frame_rate: f64) -> Self {
+        Self::from_frames(self.to_frames(frame_rate), frame_rate)
+    }
+
     /// Parses an optional `TimeSpan` from a given textual representation of the
     /// `TimeSpan`. If the given text consists entirely of whitespace or is
     /// empty, `None` is returned.
@@ -241,7 +261,19 @@ impl Neg for TimeSpan {
 }
 
 use core::fmt;
-use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{
+    Serialize, Serializer,
+    de::{self, Deserialize, Deserializer, Visitor},
+};
+
+impl Serialize for TimeSpan {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&Complete.format(*self))
+    }
+}
 
 impl<'de> Deserialize<'de> for TimeSpan {
     fn deserialize<D>(deserializer: D) -> Result<TimeSpan, D::Error>
@@ -347,4 +379,16 @@ mod tests {
             (10, 0)
         );
     }
+
+    #[test]
+    fn frames() {
+        let time = TimeSpan::from_frames(4523, 60.0);
+        assert_eq!(time.to_frames(60.0), 4523);
+        assert_eq!(TimeSpan::from_seconds(1.0).to_frames(29.97), 30);
+        assert_eq!(TimeSpan::from_seconds(-0.5).to_frames(60.0), -30);
+        assert_eq!(
+            TimeSpan::from_seconds(1.01).round_to_frames(60.0),
+            TimeSpan::from_frames(61, 60.0),
+        );
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    AtomicDateTime, Run, Time, TimeSpan, TimeStamp, TimingMethod,
+    AtomicDateTime, Run, Time, TimeSpan, TimingMethod,
     event::{Error, Event, Result},
+    platform::prelude::*,
+    run::GoldRules,
+    timing::SharedClock,
 };
 
 #[derive(Debug, Clone)]
@@ -8,14 +11,46 @@ pub struct ActiveAttempt {
     pub state: State,
     /// The date time when the attempt started.
     pub attempt_started: AtomicDateTime,
-    /// The time stamp when the attempt started.
-    pub start_time: TimeStamp,
+    /// The clock the attempt is measured with.
+    pub clock: SharedClock,
+    /// The time of the clock when the attempt started.
+    pub start_time: TimeSpan,
     /// The original offset gets kept around to undo the pauses.
     pub original_offset: TimeSpan,
     /// The adjusted offset gets modified as pauses get accumulated.
     pub adjusted_offset: TimeSpan,
     pub game_time_paused_at: Option<TimeSpan>,
     pub loading_times: Option<TimeSpan>,
+    /// The indices of the segments whose split got undone during the attempt.
+    pub undone_splits: Vec<usize>,
+    /// Gets added to the time that passed since the start time. This is used
+    /// to continue an attempt that got reconstructed from an event log.
+    pub clock_shift: TimeSpan,
+    /// If this is set, it is used as the time that passed since the start
+    /// time instead of asking the system clock. This is used while replaying
+    /// an event log, so the commands get applied at the exact time they were
+    /// recorded at.
+    pub frozen_elapsed: Option<TimeSpan>,
+    /// Whether the Run was modified before a practice attempt was started.
+    /// This is used to restore the state when the practice attempt is reset.
+    pub run_modified_before_practice: bool,
+    /// If the attempt started in the middle of the run to practice individual
+    /// segments, this describes where it started.
+    pub segment_practice: Option<SegmentPractice>,
+    /// The time that passed since the start time when the attempt was started
+    /// or the most recent split was accepted. Splits are rejected if they
+    /// happen within the split cooldown of it.
+    pub last_split_at: Option<TimeSpan>,
+}
+
+/// Describes an attempt that started in the middle of the run to practice
+/// individual segments.
+#[derive(Debug, Clone, Copy)]
+pub struct SegmentPractice {
+    /// The index of the segment the attempt started at.
+    pub start_index: usize,
+    /// Whether the segments practiced are stored in the segment history.
+    pub feed_segment_history: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +79,12 @@ impl From<TimerTime> for Time {
 }
 
 impl ActiveAttempt {
+    /// The time that passed since the start time.
+    pub fn elapsed(&self) -> TimeSpan {
+        self.frozen_elapsed
+            .unwrap_or_else(|| self.clock.now() - self.start_time + self.clock_shift)
+    }
+
     pub fn current_time(&self, run: &Run) -> TimerTime {
         let real_time = match self.state {
             State::Ended { .. } => {
@@ -57,8 +98,9 @@ impl ActiveAttempt {
                     game_time,
                 };
             }
-            State::NotEnded { time_paused_at, .. } => time_paused_at
-                .unwrap_or_else(|| TimeStamp::now() - self.start_time + self.adjusted_offset),
+            State::NotEnded { time_paused_at, .. } => {
+                time_paused_at.unwrap_or_else(|| self.elapsed() + self.adjusted_offset)
+            }
         };
 
         let game_time = self
@@ -77,7 +119,7 @@ impl ActiveAttempt {
             ..
         } = self.state
         {
-            return Some(TimeStamp::now() - self.start_time + self.original_offset - pause_time);
+            return Some(self.elapsed() + self.original_offset - pause_time);
         }
 
         if self.original_offset != self.adjusted_offset {
@@ -94,7 +136,10 @@ impl ActiveAttempt {
         }
     }
 
-    pub fn prepare_split(&mut self, run: &Run) -> Result<(usize, Time, Event)> {
+    pub fn prepare_split(&mut self, run: &Run, cooldown: TimeSpan) -> Result<(usize, Time, Event)> {
+        let elapsed = self.elapsed();
+        let last_split_at = self.last_split_at;
+
         let State::NotEnded {
             current_split_index,
             time_paused_at,
@@ -107,7 +152,11 @@ impl ActiveAttempt {
             return Err(Error::TimerPaused);
         }
 
-        let real_time = TimeStamp::now() - self.start_time + self.adjusted_offset;
+        if last_split_at.is_some_and(|last| elapsed - last < cooldown) {
+            return Err(Error::SplitCooldown);
+        }
+
+        let real_time = elapsed + self.adjusted_offset;
 
         if real_time < TimeSpan::zero() {
             return Err(Error::NegativeTime);
@@ -119,6 +168,7 @@ impl ActiveAttempt {
 
         let previous_split_index = *current_split_index;
         *current_split_index += 1;
+        self.last_split_at = Some(elapsed);
 
         let event = if *current_split_index == run.len() {
             self.state = State::Ended {
@@ -171,9 +221,64 @@ impl ActiveAttempt {
 
     pub fn update_times(&self, run: &mut Run, timing_method: TimingMethod) {
         self.update_attempt_history(run);
-        update_best_segments(run);
+        // The best segments need to be consistent with a new Personal Best, so
+        // the gold rules don't apply to it.
+        let gold_rules = if is_new_pb(run, timing_method) {
+            GoldRules::default()
+        } else {
+            *run.gold_rules()
+        };
+        let rejected_golds = update_best_segments(run, 0, &gold_rules, &self.undone_splits);
         update_pb_splits(run, timing_method);
         run.update_segment_history(self.current_split_index_overflowing(run));
+
+        // Segment times that didn't qualify as golds are untrustworthy, so
+        // they are removed from the segment history. Otherwise they would
+        // become the best segments when fixing up the run. Only the timing
+        // method that got rejected is removed, so the history entry stays.
+        let index = run.attempt_history().last().unwrap().index();
+        for (segment_index, method) in rejected_golds {
+            if let Some(time) = run
+                .segment_mut(segment_index)
+                .segment_history_mut()
+                .get_mut(index)
+            {
+                time[method] = None;
+            }
+        }
+    }
+
+    /// Stores the segment times of the segments that were practiced during the
+    /// attempt in the segment history and updates the best segments. As the
+    /// attempt is not part of the attempt history, the segment times are
+    /// stored with indices that don't belong to any attempt.
+    pub fn update_practiced_segments(&self, run: &mut Run, start_index: usize) {
+        let gold_rules = *run.gold_rules();
+        let rejected_golds =
+            update_best_segments(run, start_index, &gold_rules, &self.undone_splits);
+
+        let end_index = self.current_split_index_overflowing(run);
+        let index = run.min_segment_history_index().unwrap_or(1) - 1;
+        let mut previous_split_time = Time::zero();
+
+        for (segment_index, segment) in run.segments_mut()[..end_index].iter_mut().enumerate() {
+            let split_time = segment.split_time();
+            if segment_index >= start_index {
+                let mut segment_time = split_time - previous_split_time;
+                for &(_, method) in rejected_golds.iter().filter(|(i, _)| *i == segment_index) {
+                    segment_time[method] = None;
+                }
+                if segment_time.real_time.is_some() || segment_time.game_time.is_some() {
+                    segment.segment_history_mut().insert(index, segment_time);
+                }
+            }
+            if let Some(time) = split_time.real_time {
+                previous_split_time.real_time = Some(time);
+            }
+            if let Some(time) = split_time.game_time {
+                previous_split_time.game_time = Some(time);
+            }
+        }
     }
 
     pub fn update_attempt_history(&self, run: &mut Run) {
@@ -195,48 +300,82 @@ impl ActiveAttempt {
     }
 }
 
-fn update_best_segments(run: &mut Run) {
+/// Updates the best segments, starting at the segment index provided, and
+/// returns the indices of the segments whose segment times were faster than
+/// the best segment, but didn't qualify as a gold according to the gold rules,
+/// along with the timing method the gold got rejected for.
+fn update_best_segments(
+    run: &mut Run,
+    start_index: usize,
+    gold_rules: &GoldRules,
+    undone_splits: &[usize],
+) -> Vec<(usize, TimingMethod)> {
+    let mut rejected_golds = Vec::new();
     let mut previous_split_time_rta = Some(TimeSpan::zero());
     let mut previous_split_time_game_time = Some(TimeSpan::zero());
 
-    for split in run.segments_mut() {
+    for (index, split) in run.segments_mut().iter_mut().enumerate() {
+        let split_time = split.split_time();
+        let current_segment = Time {
+            real_time: split_time
+                .real_time
+                .and_then(|t| Some(t - previous_split_time_rta?)),
+            game_time: split_time
+                .game_time
+                .and_then(|t| Some(t - previous_split_time_game_time?)),
+        };
+        if index < start_index {
+            if let Some(split_time) = split_time.real_time {
+                previous_split_time_rta = Some(split_time);
+            }
+            if let Some(split_time) = split_time.game_time {
+                previous_split_time_game_time = Some(split_time);
+            }
+            continue;
+        }
+        let was_undone = undone_splits.contains(&index);
+
         let mut new_best_segment = split.best_segment_time();
-        if let Some(split_time) = split.split_time().real_time {
-            let current_segment = previous_split_time_rta.map(|previous| split_time - previous);
+        if let Some(split_time) = split_time.real_time {
             previous_split_time_rta = Some(split_time);
-            if split
-                .best_segment_time()
+            if current_segment
                 .real_time
-                .is_none_or(|b| current_segment.is_some_and(|c| c < b))
+                .is_some_and(|c| split.best_segment_time().real_time.is_none_or(|b| c < b))
             {
-                new_best_segment.real_time = current_segment;
+                if gold_rules.qualifies(current_segment, TimingMethod::RealTime, was_undone) {
+                    new_best_segment.real_time = current_segment.real_time;
+                } else {
+                    rejected_golds.push((index, TimingMethod::RealTime));
+                }
             }
         }
-        if let Some(split_time) = split.split_time().game_time {
-            let current_segment =
-                previous_split_time_game_time.map(|previous| split_time - previous);
+        if let Some(split_time) = split_time.game_time {
             previous_split_time_game_time = Some(split_time);
-            if split
-                .best_segment_time()
+            if current_segment
                 .game_time
-                .is_none_or(|b| current_segment.is_some_and(|c| c < b))
+                .is_some_and(|c| split.best_segment_time().game_time.is_none_or(|b| c < b))
             {
-                new_best_segment.game_time = current_segment;
+                if gold_rules.qualifies(current_segment, TimingMethod::GameTime, was_undone) {
+                    new_best_segment.game_time = current_segment.game_time;
+                } else {
+                    rejected_golds.push((index, TimingMethod::GameTime));
+                }
             }
         }
         split.set_best_segment_time(new_best_segment);
     }
+
+    rejected_golds
+}
+
+fn is_new_pb(run: &Run, method: TimingMethod) -> bool {
+    let last_segment = run.segments().last().unwrap();
+    let pb_split_time = last_segment.personal_best_split_time()[method];
+    last_segment.split_time()[method].is_some_and(|s| pb_split_time.is_none_or(|pb| s < pb))
 }
 
 fn update_pb_splits(run: &mut Run, method: TimingMethod) {
-    let (split_time, pb_split_time) = {
-        let last_segment = run.segments().last().unwrap();
-        (
-            last_segment.split_time()[method],
-            last_segment.personal_best_split_time()[method],
-        )
-    };
-    if split_time.is_some_and(|s| pb_split_time.is_none_or(|pb| s < pb)) {
+    if is_new_pb(run, method) {
         super::set_run_as_pb(run);
     }
 }
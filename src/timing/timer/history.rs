@@ -0,0 +1,140 @@
+use super::active_attempt::ActiveAttempt;
+use crate::{Run, Time, platform::prelude::*};
+use hashbrown::HashMap;
+
+/// The amount of actions that can be undone by default.
+pub const DEFAULT_LIMIT: usize = 32;
+
+/// The undo and redo stacks of a [`Timer`](super::Timer). Each entry stores
+/// the state of the timer before an action was applied, so that the action
+/// can be reverted by restoring that state.
+#[derive(Debug, Clone)]
+pub struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    limit: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    active_attempt: Option<ActiveAttempt>,
+    run: RunState,
+}
+
+/// Most actions only modify the split information of the segments, so only
+/// that is stored. Resetting an attempt however modifies the whole run, so it
+/// needs to be stored entirely.
+#[derive(Debug, Clone)]
+enum RunState {
+    Splits(Vec<(Time, HashMap<String, String>)>),
+    Run(Box<Run>),
+}
+
+/// Which parts of the run an action modifies.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Scope {
+    Splits,
+    Run,
+}
+
+impl Entry {
+    pub fn capture(run: &Run, active_attempt: &Option<ActiveAttempt>, scope: Scope) -> Self {
+        Self {
+            active_attempt: active_attempt.clone(),
+            run: match scope {
+                Scope::Splits => RunState::Splits(
+                    run.segments()
+                        .iter()
+                        .map(|s| (s.split_time(), s.variables().clone()))
+                        .collect(),
+                ),
+                Scope::Run => RunState::Run(Box::new(run.clone())),
+            },
+        }
+    }
+
+    pub const fn scope(&self) -> Scope {
+        match self.run {
+            RunState::Splits(_) => Scope::Splits,
+            RunState::Run(_) => Scope::Run,
+        }
+    }
+
+    pub fn restore(self, run: &mut Run, active_attempt: &mut Option<ActiveAttempt>) {
+        *active_attempt = self.active_attempt;
+        match self.run {
+            RunState::Splits(splits) => {
+                for (segment, (split_time, variables)) in run.segments_mut().iter_mut().zip(splits)
+                {
+                    segment.set_split_time(split_time);
+                    *segment.variables_mut() = variables;
+                }
+            }
+            RunState::Run(stored) => *run = *stored,
+        }
+        run.mark_as_modified();
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl History {
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    pub fn push(&mut self, entry: Entry) {
+        self.undo.push(entry);
+        self.redo.clear();
+        self.truncate();
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub const fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub const fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Entry> {
+        self.undo.pop()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Entry> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, entry: Entry) {
+        self.undo.push(entry);
+        self.truncate();
+    }
+
+    pub fn push_redo(&mut self, entry: Entry) {
+        self.redo.push(entry);
+    }
+
+    fn truncate(&mut self) {
+        if let Some(excess) = self.undo.len().checked_sub(self.limit) {
+            self.undo.drain(..excess);
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use crate::{
-    AtomicDateTime, Run, Segment, Time, TimeSpan, TimeStamp,
+    AtomicDateTime, Run, Segment, Time, TimeSpan,
     TimerPhase::{self, *},
     TimingMethod,
-    analysis::check_best_segment,
-    comparison::personal_best,
+    analysis::{check_best_segment, ghost},
+    comparison::{RACE_COMPARISON_PREFIX, personal_best},
     event::{Error, Event},
-    platform::prelude::*,
+    platform::{prelude::*, utc_now},
+    timing::{
+        Clock, MonotonicClock, OverlayWidget, SharedClock,
+        event_log::{self, Command, EventLog},
+        journal::Journal,
+    },
     util::PopulateString,
 };
 use core::{mem, ops::Deref};
@@ -14,7 +19,11 @@ use core::{mem, ops::Deref};
 mod tests;
 
 mod active_attempt;
-use active_attempt::{ActiveAttempt, State};
+mod history;
+use active_attempt::{ActiveAttempt, SegmentPractice, State};
+use history::{Entry, History, Scope};
+
+pub use history::DEFAULT_LIMIT as DEFAULT_UNDO_LIMIT;
 
 /// A `Timer` provides all the capabilities necessary for doing speedrun attempts.
 ///
@@ -54,6 +63,14 @@ pub struct Timer {
     current_comparison: String,
     current_timing_method: TimingMethod,
     active_attempt: Option<ActiveAttempt>,
+    history: History,
+    event_log: EventLog,
+    is_practicing: bool,
+    split_cooldown: TimeSpan,
+    records_split_variables: bool,
+    stores_event_logs: bool,
+    overlay_widgets: Vec<OverlayWidget>,
+    clock: SharedClock,
 }
 
 /// A snapshot represents a specific point in time that the timer was observed
@@ -70,6 +87,14 @@ impl Snapshot<'_> {
     pub const fn current_time(&self) -> Time {
         self.time
     }
+
+    /// Calculates the position of a simulated ghost runner following the
+    /// split times of a comparison or a past attempt, as of the time the
+    /// snapshot was taken. See the [`ghost`](crate::analysis::ghost) analysis for
+    /// more information.
+    pub fn ghost(&self, source: ghost::Source<'_>, method: TimingMethod) -> Option<ghost::Ghost> {
+        ghost::calculate(self, source, method)
+    }
 }
 
 impl Deref for Snapshot<'_> {
@@ -90,6 +115,17 @@ pub enum CreationError {
     EmptyRun,
 }
 
+/// The Error type for race comparisons.
+#[derive(PartialEq, Eq, Debug, snafu::Snafu)]
+pub enum RaceComparisonError {
+    /// There already is a race comparison for the opponent.
+    DuplicateOpponent,
+    /// There is no race comparison for the opponent.
+    NoSuchOpponent,
+    /// The index of the segment is out of bounds.
+    SegmentIndexOutOfBounds,
+}
+
 pub type Result<T = Event, E = Error> = core::result::Result<T, E>;
 
 impl Timer {
@@ -98,7 +134,16 @@ impl Timer {
     /// that the Timer can store the final time. If a Run object with no
     /// segments is provided, the Timer creation fails.
     #[inline]
-    pub fn new(mut run: Run) -> Result<Self, CreationError> {
+    pub fn new(run: Run) -> Result<Self, CreationError> {
+        Self::with_clock(run, MonotonicClock::new())
+    }
+
+    /// Creates a new Timer based on a Run object, just like
+    /// [`new`](Self::new), but measures the time with the [`Clock`] provided
+    /// instead of the platform's monotonic clock. This allows driving the
+    /// timer deterministically, such as in tests, or from the time of an
+    /// emulator core or a video file.
+    pub fn with_clock(mut run: Run, clock: impl Clock + 'static) -> Result<Self, CreationError> {
         if run.is_empty() {
             return Err(CreationError::EmptyRun);
         }
@@ -111,6 +156,14 @@ impl Timer {
             current_comparison: personal_best::NAME.into(),
             current_timing_method: TimingMethod::RealTime,
             active_attempt: None,
+            history: History::default(),
+            event_log: EventLog::default(),
+            is_practicing: false,
+            split_cooldown: TimeSpan::zero(),
+            records_split_variables: false,
+            stores_event_logs: false,
+            overlay_widgets: Vec::new(),
+            clock: SharedClock::new(clock),
         })
     }
 
@@ -143,6 +196,7 @@ impl Timer {
         }
 
         let _ = self.reset(update_splits);
+        self.history.clear();
         if !run.comparisons().any(|c| c == self.current_comparison) {
             self.current_comparison = personal_best::NAME.to_string();
         }
@@ -288,9 +342,14 @@ impl Timer {
     /// Starts the Timer if there is no attempt in progress. If that's not the
     /// case, nothing happens.
     pub fn start(&mut self) -> Result {
+        self.logged(Command::Start, Self::apply_start)
+    }
+
+    fn apply_start(&mut self) -> Result {
         if self.active_attempt.is_none() {
+            self.history.clear();
             let attempt_started = AtomicDateTime::now();
-            let start_time = TimeStamp::now();
+            let start_time = self.clock.now();
             let offset = self.run.offset();
 
             self.active_attempt = Some(ActiveAttempt {
@@ -299,13 +358,23 @@ impl Timer {
                     time_paused_at: None,
                 },
                 attempt_started,
+                clock: self.clock.clone(),
                 start_time,
                 original_offset: offset,
                 adjusted_offset: offset,
                 game_time_paused_at: None,
                 loading_times: None,
+                undone_splits: Vec::new(),
+                clock_shift: TimeSpan::zero(),
+                frozen_elapsed: None,
+                run_modified_before_practice: self.run.has_been_modified(),
+                segment_practice: None,
+                last_split_at: Some(TimeSpan::zero()),
             });
-            self.run.start_next_run();
+            if !self.is_practicing {
+                self.run.start_next_run();
+            }
+            self.event_log.clear();
 
             Ok(Event::Started)
         } else {
@@ -313,12 +382,109 @@ impl Timer {
         }
     }
 
+    /// Starts a practice attempt at the segment specified, so that individual
+    /// segments can be practiced. The split times of all the segments before
+    /// it are taken from the comparison provided, so the timer starts at the
+    /// comparison's time and the deltas are the same as they would be in a
+    /// full attempt. Just like attempts in practice mode, the attempt is not
+    /// counted and not stored in the attempt history. If the segment history
+    /// is supposed to be fed, the segments completed during the attempt are
+    /// stored in the segment history when it gets reset with the splits being
+    /// updated, which also updates the best segments.
+    pub fn start_at_segment(
+        &mut self,
+        segment_index: usize,
+        comparison: &str,
+        feed_segment_history: bool,
+    ) -> Result {
+        let command = Command::StartAtSegment {
+            segment_index,
+            comparison: comparison.into(),
+            feed_segment_history,
+        };
+        self.logged(command, |timer| {
+            timer.apply_start_at_segment(segment_index, comparison, feed_segment_history)
+        })
+    }
+
+    fn apply_start_at_segment(
+        &mut self,
+        segment_index: usize,
+        comparison: &str,
+        feed_segment_history: bool,
+    ) -> Result {
+        if self.active_attempt.is_some() {
+            return Err(Error::RunAlreadyInProgress);
+        }
+        if segment_index >= self.run.len() {
+            return Err(Error::SegmentIndexOutOfBounds);
+        }
+        if !self.run.comparisons().any(|c| c == comparison) {
+            return Err(Error::ComparisonDoesntExist);
+        }
+
+        let run_modified_before_practice = self.run.has_been_modified();
+        let mut start_time = Time::zero();
+        for segment in &mut self.run.segments_mut()[..segment_index] {
+            let split_time = segment.comparison(comparison);
+            segment.set_split_time(split_time);
+            if let Some(time) = split_time.real_time {
+                start_time.real_time = Some(time);
+            }
+            if let Some(time) = split_time.game_time {
+                start_time.game_time = Some(time);
+            }
+        }
+
+        self.history.clear();
+        let start_real_time = start_time.real_time.unwrap_or_default();
+        let offset = self.run.offset() + start_real_time;
+        let loading_times = if segment_index > 0 {
+            start_time.game_time.map(|t| start_real_time - t)
+        } else {
+            None
+        };
+
+        self.active_attempt = Some(ActiveAttempt {
+            state: State::NotEnded {
+                current_split_index: segment_index,
+                time_paused_at: None,
+            },
+            attempt_started: AtomicDateTime::now(),
+            clock: self.clock.clone(),
+            start_time: self.clock.now(),
+            original_offset: offset,
+            adjusted_offset: offset,
+            game_time_paused_at: None,
+            loading_times,
+            undone_splits: Vec::new(),
+            clock_shift: TimeSpan::zero(),
+            frozen_elapsed: None,
+            run_modified_before_practice,
+            segment_practice: Some(SegmentPractice {
+                start_index: segment_index,
+                feed_segment_history,
+            }),
+            last_split_at: Some(TimeSpan::zero()),
+        });
+        self.event_log.clear();
+
+        Ok(Event::Started)
+    }
+
     /// If an attempt is in progress, stores the current time as the time of the
     /// current split. The attempt ends if the last split time is stored.
     pub fn split(&mut self) -> Result {
+        self.logged(Command::Split, |timer| {
+            timer.record(Scope::Splits, Self::apply_split)
+        })
+    }
+
+    fn apply_split(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
-        let (split_index, current_time, event) = active_attempt.prepare_split(&self.run)?;
+        let (split_index, current_time, event) =
+            active_attempt.prepare_split(&self.run, self.split_cooldown)?;
 
         // FIXME: We shouldn't need to collect here.
         let variables = self
@@ -328,6 +494,18 @@ impl Timer {
             .map(|(k, v)| (k.to_owned(), v.value.clone()))
             .collect();
 
+        let current_time = match self.run.frame_rate() {
+            Some(frame_rate) => Time {
+                real_time: current_time
+                    .real_time
+                    .map(|t| t.round_to_frames(frame_rate)),
+                game_time: current_time
+                    .game_time
+                    .map(|t| t.round_to_frames(frame_rate)),
+            },
+            None => current_time,
+        };
+
         let segment = self.run.segment_mut(split_index);
         segment.set_split_time(current_time);
         *segment.variables_mut() = variables;
@@ -350,6 +528,12 @@ impl Timer {
     /// Skips the current split if an attempt is in progress and the
     /// current split is not the last split.
     pub fn skip_split(&mut self) -> Result {
+        self.logged(Command::SkipSplit, |timer| {
+            timer.record(Scope::Splits, Self::apply_skip_split)
+        })
+    }
+
+    fn apply_skip_split(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         let Some(current_split_index) = active_attempt.current_split_index_mut() else {
@@ -375,11 +559,22 @@ impl Timer {
     /// and there is a previous split. The Timer Phase also switches to
     /// [`Running`] if it previously was [`Ended`].
     pub fn undo_split(&mut self) -> Result {
+        self.logged(Command::UndoSplit, |timer| {
+            timer.record(Scope::Splits, Self::apply_undo_split)
+        })
+    }
+
+    fn apply_undo_split(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
+        let first_split_index = active_attempt
+            .segment_practice
+            .map_or(0, |practice| practice.start_index);
+
         if let Some(previous_split_index) = active_attempt
             .current_split_index_overflowing(&self.run)
             .checked_sub(1)
+            .filter(|&index| index >= first_split_index)
         {
             let time_paused_at = match &active_attempt.state {
                 State::NotEnded { time_paused_at, .. } => *time_paused_at,
@@ -390,6 +585,10 @@ impl Timer {
                 current_split_index: previous_split_index,
                 time_paused_at,
             };
+            active_attempt.undone_splits.push(previous_split_index);
+            // Undoing a split is deliberate, so splitting again right away
+            // shouldn't be rejected.
+            active_attempt.last_split_at = None;
 
             self.run
                 .segment_mut(previous_split_index)
@@ -441,8 +640,12 @@ impl Timer {
     /// Checks whether the current attempt has new best segment times in any of
     /// the segments (for both [`TimingMethods`](TimingMethod)) or a new
     /// Personal Best (for the current [`TimingMethod`]). This can be used to
-    /// ask the user whether to update the splits when resetting.
+    /// ask the user whether to update the splits when resetting. Practice
+    /// attempts never have new best times, as they are not stored.
     pub fn current_attempt_has_new_best_times(&self) -> bool {
+        if self.is_practice_attempt() {
+            return false;
+        }
         self.current_attempt_has_new_best_segments(TimingMethod::RealTime)
             || self.current_attempt_has_new_best_segments(TimingMethod::GameTime)
             || self.current_attempt_has_new_personal_best(self.current_timing_method)
@@ -453,10 +656,23 @@ impl Timer {
     /// in the Run's history. Otherwise the current attempt's information is
     /// discarded.
     pub fn reset(&mut self, update_splits: bool) -> Result {
+        let command = Command::Reset {
+            update_splits,
+            timing_method: self.current_timing_method,
+        };
+        let attempts = self.run.attempt_history().len();
+        let event = self.logged(command, |timer| timer.apply_reset(update_splits))?;
+        self.store_event_log(attempts);
+        Ok(event)
+    }
+
+    fn apply_reset(&mut self, update_splits: bool) -> Result {
         if self.active_attempt.is_some() {
-            self.reset_state(update_splits);
-            self.reset_splits();
-            Ok(Event::Reset)
+            self.record(Scope::Run, |timer| {
+                let times_updated = timer.reset_state(update_splits);
+                timer.reset_splits(times_updated);
+                Ok(Event::Reset)
+            })
         } else {
             Err(Error::NoRunInProgress)
         }
@@ -466,47 +682,233 @@ impl Timer {
     /// updated such that the current attempt's split times are being stored as
     /// the new Personal Best.
     pub fn reset_and_set_attempt_as_pb(&mut self) -> Result {
-        if self.active_attempt.is_some() {
-            self.reset_state(true);
-            set_run_as_pb(&mut self.run);
-            self.reset_splits();
-            Ok(Event::Reset)
+        let attempts = self.run.attempt_history().len();
+        let event = self.logged(
+            Command::ResetAndSetAttemptAsPb,
+            Self::apply_reset_and_set_attempt_as_pb,
+        )?;
+        self.store_event_log(attempts);
+        Ok(event)
+    }
+
+    /// Stores the event log in the attempt that was added to the attempt
+    /// history by a reset, if there was one added. The log is only stored once
+    /// the reset itself is recorded in it.
+    fn store_event_log(&mut self, previous_attempts: usize) {
+        if !self.stores_event_logs || self.run.attempt_history().len() <= previous_attempts {
+            return;
+        }
+        if let Some(attempt) = self.run.attempt_history_mut().last_mut() {
+            attempt.set_event_log(self.event_log.clone());
+        }
+    }
+
+    fn apply_reset_and_set_attempt_as_pb(&mut self) -> Result {
+        if self.is_practice_attempt() {
+            Err(Error::Unsupported)
+        } else if self.active_attempt.is_some() {
+            self.record(Scope::Run, |timer| {
+                timer.reset_state(true);
+                set_run_as_pb(&mut timer.run);
+                timer.reset_splits(true);
+                Ok(Event::Reset)
+            })
         } else {
             Err(Error::NoRunInProgress)
         }
     }
 
-    fn reset_state(&mut self, update_times: bool) {
+    /// Returns whether the current attempt is a practice attempt, either
+    /// because the timer is in practice mode or because the attempt started in
+    /// the middle of the run.
+    fn is_practice_attempt(&self) -> bool {
+        self.is_practicing
+            || self
+                .active_attempt
+                .as_ref()
+                .is_some_and(|a| a.segment_practice.is_some())
+    }
+
+    /// Ends the current attempt and returns whether the times stored in the Run
+    /// may have been updated with the attempt's information.
+    fn reset_state(&mut self, update_times: bool) -> bool {
+        let is_practice_attempt = self.is_practice_attempt();
         let Some(active_attempt) = self.active_attempt.take() else {
-            return;
+            return true;
         };
 
-        if update_times {
-            active_attempt.update_times(&mut self.run, self.current_timing_method);
+        if !is_practice_attempt {
+            if update_times {
+                active_attempt.update_times(&mut self.run, self.current_timing_method);
+                self.store_attempt_variables();
+            }
+            return true;
+        }
+
+        match active_attempt.segment_practice {
+            Some(SegmentPractice {
+                start_index,
+                feed_segment_history: true,
+            }) if update_times => {
+                active_attempt.update_practiced_segments(&mut self.run, start_index);
+                true
+            }
+            _ => {
+                if !active_attempt.run_modified_before_practice {
+                    self.run.mark_as_unmodified();
+                }
+                false
+            }
+        }
+    }
+
+    /// Stores the values of the custom variables in the attempt that was most
+    /// recently added to the attempt history.
+    fn store_attempt_variables(&mut self) {
+        let variables = self
+            .run
+            .metadata()
+            .custom_variables()
+            .map(|(name, var)| (name.to_owned(), var.value.clone()))
+            .collect();
+
+        let split_variables: Vec<_> = if self.records_split_variables {
+            self.run
+                .segments()
+                .iter()
+                .enumerate()
+                .filter(|(_, segment)| !segment.variables().is_empty())
+                .map(|(index, segment)| {
+                    let mut variables: Vec<_> = segment
+                        .variables()
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect();
+                    variables.sort_unstable();
+                    (index, variables)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let Some(attempt) = self.run.attempt_history_mut().last_mut() else {
+            return;
+        };
+        attempt.set_variables(variables);
+        for (index, variables) in split_variables {
+            attempt.set_split_variables(index, variables);
         }
     }
 
-    fn reset_splits(&mut self) {
+    fn reset_splits(&mut self, fix_splits: bool) {
         // Reset Splits
         for segment in self.run.segments_mut() {
             segment.clear_split_info();
         }
 
-        self.run.fix_splits();
-        self.run.regenerate_comparisons();
+        if fix_splits {
+            self.run.fix_splits();
+            self.run.regenerate_comparisons();
+        }
+    }
+
+    /// Returns whether the timer is in practice mode. Attempts done in practice
+    /// mode are not counted and neither stored in the attempt history nor in
+    /// the segment history, so they don't influence any statistics or
+    /// comparisons.
+    #[inline]
+    pub const fn is_practicing(&self) -> bool {
+        self.is_practicing
+    }
+
+    /// Turns practice mode on or off. Attempts done in practice mode are not
+    /// counted and neither stored in the attempt history nor in the segment
+    /// history, so they don't influence any statistics or comparisons. The
+    /// mode can't be changed while an attempt is in progress.
+    pub const fn set_practice_mode(
+        &mut self,
+        is_practicing: bool,
+    ) -> core::result::Result<(), Error> {
+        if self.active_attempt.is_some() {
+            return Err(Error::RunAlreadyInProgress);
+        }
+        self.is_practicing = is_practicing;
+        Ok(())
+    }
+
+    /// Returns the minimum amount of time that needs to pass after the attempt
+    /// was started or the previous split happened before another split is
+    /// accepted. By default there is no cooldown.
+    pub const fn split_cooldown(&self) -> TimeSpan {
+        self.split_cooldown
+    }
+
+    /// Sets the minimum amount of time that needs to pass after the attempt
+    /// was started or the previous split happened before another split is
+    /// accepted. This prevents accidental double splits, for example from
+    /// bouncy keyboards. Splits that happen too soon are rejected with
+    /// [`Error::SplitCooldown`].
+    pub const fn set_split_cooldown(&mut self, cooldown: TimeSpan) {
+        self.split_cooldown = cooldown;
+    }
+
+    /// Returns whether the values of the custom variables at each split are
+    /// stored in the attempt history when an attempt is reset. The values at
+    /// the end of the attempt are always stored. This is off by default.
+    pub const fn records_split_variables(&self) -> bool {
+        self.records_split_variables
+    }
+
+    /// Sets whether the values of the custom variables at each split are
+    /// stored in the attempt history when an attempt is reset. The values at
+    /// the end of the attempt are always stored.
+    pub const fn set_records_split_variables(&mut self, records_split_variables: bool) {
+        self.records_split_variables = records_split_variables;
+    }
+
+    /// Returns whether the [`EventLog`] of an attempt is stored in the attempt
+    /// history when the attempt is reset, so the attempt can still be replayed
+    /// after the timer is gone. This is off by default.
+    pub const fn stores_event_logs(&self) -> bool {
+        self.stores_event_logs
+    }
+
+    /// Sets whether the [`EventLog`] of an attempt is stored in the attempt
+    /// history when the attempt is reset.
+    pub const fn set_stores_event_logs(&mut self, stores_event_logs: bool) {
+        self.stores_event_logs = stores_event_logs;
+    }
+
+    /// Returns the overlay widgets that an auto splitter currently wants to
+    /// display. They are not stored in the splits file.
+    pub fn overlay_widgets(&self) -> &[OverlayWidget] {
+        &self.overlay_widgets
+    }
+
+    /// Sets the overlay widgets to display, replacing all the previous ones.
+    /// This is usually called by an auto splitter.
+    pub fn set_overlay_widgets(&mut self, widgets: Vec<OverlayWidget>) {
+        self.overlay_widgets = widgets;
     }
 
     /// Pauses an active attempt that is not paused.
     pub fn pause(&mut self) -> Result {
+        self.logged(Command::Pause, |timer| {
+            timer.record(Scope::Splits, Self::apply_pause)
+        })
+    }
+
+    fn apply_pause(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+        let elapsed = active_attempt.elapsed();
 
         let State::NotEnded { time_paused_at, .. } = &mut active_attempt.state else {
             return Err(Error::RunFinished);
         };
 
         if time_paused_at.is_none() {
-            *time_paused_at =
-                Some(TimeStamp::now() - active_attempt.start_time + active_attempt.adjusted_offset);
+            *time_paused_at = Some(elapsed + active_attempt.adjusted_offset);
             Ok(Event::Paused)
         } else {
             Err(Error::AlreadyPaused)
@@ -515,15 +917,21 @@ impl Timer {
 
     /// Resumes an attempt that is paused.
     pub fn resume(&mut self) -> Result {
+        self.logged(Command::Resume, |timer| {
+            timer.record(Scope::Splits, Self::apply_resume)
+        })
+    }
+
+    fn apply_resume(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+        let elapsed = active_attempt.elapsed();
 
         let State::NotEnded { time_paused_at, .. } = &mut active_attempt.state else {
             return Err(Error::RunFinished);
         };
 
         if let Some(pause_time) = *time_paused_at {
-            active_attempt.adjusted_offset =
-                pause_time - (TimeStamp::now() - active_attempt.start_time);
+            active_attempt.adjusted_offset = pause_time - elapsed;
             *time_paused_at = None;
             Ok(Event::Resumed)
         } else {
@@ -563,9 +971,15 @@ impl Timer {
     /// time is modified, while all other split times are left unmodified, which
     /// may not be what actually happened during the run.
     pub fn undo_all_pauses(&mut self) -> Result {
+        self.logged(Command::UndoAllPauses, |timer| {
+            timer.record(Scope::Splits, Self::apply_undo_all_pauses)
+        })
+    }
+
+    fn apply_undo_all_pauses(&mut self) -> Result {
         let event = match self.current_phase() {
             Paused => {
-                self.resume()?;
+                self.apply_resume()?;
                 Event::PausesUndoneAndResumed
             }
             Ended => {
@@ -596,6 +1010,327 @@ impl Timer {
         }
     }
 
+    /// Adjusts the offset of the current attempt retroactively, as if the timer
+    /// was started earlier by the amount specified. This is useful if the timer
+    /// was started too late or too early. A positive amount adds time to the
+    /// attempt, while a negative amount removes time from it. The start of the
+    /// attempt and all the split times recorded so far are shifted
+    /// accordingly, so they stay consistent with each other. The offset of the
+    /// Run itself is not modified.
+    pub fn adjust_offset(&mut self, amount: TimeSpan) -> Result {
+        self.logged(Command::AdjustOffset(amount), |timer| {
+            timer.record(Scope::Splits, |timer| timer.apply_adjust_offset(amount))
+        })
+    }
+
+    fn apply_adjust_offset(&mut self, amount: TimeSpan) -> Result {
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+        let split_count = active_attempt.current_split_index_overflowing(&self.run);
+        let segments = &mut self.run.segments_mut()[..split_count];
+
+        if segments.iter().any(|segment| {
+            segment
+                .split_time()
+                .real_time
+                .is_some_and(|time| time + amount < TimeSpan::zero())
+        }) {
+            return Err(Error::NegativeTime);
+        }
+
+        for segment in segments {
+            let split_time = segment.split_time_mut();
+            if let Some(time) = &mut split_time.real_time {
+                *time += amount;
+            }
+            if let Some(time) = &mut split_time.game_time {
+                *time += amount;
+            }
+        }
+
+        active_attempt.original_offset += amount;
+        active_attempt.adjusted_offset += amount;
+        if let State::NotEnded {
+            time_paused_at: Some(time),
+            ..
+        } = &mut active_attempt.state
+        {
+            *time += amount;
+        }
+        if let Some(time) = &mut active_attempt.game_time_paused_at {
+            *time += amount;
+        }
+        active_attempt.attempt_started.time -= amount.to_duration();
+
+        self.run.mark_as_modified();
+
+        Ok(Event::OffsetAdjusted)
+    }
+
+    /// Adds time to or removes time from the current time of the attempt for
+    /// the timing method specified. This is useful for manually correcting
+    /// the time, like when a pause was missed or loads need to be removed by
+    /// hand. A negative amount removes time. Unlike adjusting the offset, the
+    /// split times recorded so far are not modified, so only the time going
+    /// forward is affected. The other timing method is not affected either.
+    pub fn adjust_time(&mut self, amount: TimeSpan, timing_method: TimingMethod) -> Result {
+        let command = Command::AdjustTime {
+            amount,
+            timing_method,
+        };
+        self.logged(command, |timer| {
+            timer.record(Scope::Splits, |timer| {
+                timer.apply_adjust_time(amount, timing_method)
+            })
+        })
+    }
+
+    fn apply_adjust_time(&mut self, amount: TimeSpan, timing_method: TimingMethod) -> Result {
+        let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
+
+        let State::NotEnded { time_paused_at, .. } = &mut active_attempt.state else {
+            return Err(Error::RunFinished);
+        };
+
+        match timing_method {
+            TimingMethod::RealTime => {
+                active_attempt.original_offset += amount;
+                active_attempt.adjusted_offset += amount;
+                if let Some(time) = time_paused_at {
+                    *time += amount;
+                }
+                // The game time is derived from the real time, so the loading
+                // times need to compensate for the adjustment.
+                if let Some(loading_times) = &mut active_attempt.loading_times {
+                    *loading_times += amount;
+                }
+            }
+            TimingMethod::GameTime => {
+                if let Some(time) = &mut active_attempt.game_time_paused_at {
+                    *time += amount;
+                } else if let Some(loading_times) = &mut active_attempt.loading_times {
+                    *loading_times -= amount;
+                } else {
+                    return Err(Error::GameTimeNotInitialized);
+                }
+            }
+        }
+
+        Ok(Event::TimeAdjusted)
+    }
+
+    /// Reverts the most recent split, skip, undone split, pause, resume, undone
+    /// pauses or reset of the current attempt. Starting a new attempt clears
+    /// the actions that can be undone, so only the actions of the current
+    /// attempt, or the attempt that was reset most recently, can be undone.
+    pub fn undo(&mut self) -> Result {
+        self.logged(Command::Undo, Self::apply_undo)
+    }
+
+    fn apply_undo(&mut self) -> Result {
+        let entry = self.history.pop_undo().ok_or(Error::NothingToUndo)?;
+        let current = Entry::capture(&self.run, &self.active_attempt, entry.scope());
+        entry.restore(&mut self.run, &mut self.active_attempt);
+        self.history.push_redo(current);
+        Ok(Event::ActionUndone)
+    }
+
+    /// Applies the action that was most recently reverted with
+    /// [`undo`](Self::undo) again. Any new action clears the actions that can
+    /// be redone.
+    pub fn redo(&mut self) -> Result {
+        self.logged(Command::Redo, Self::apply_redo)
+    }
+
+    fn apply_redo(&mut self) -> Result {
+        let entry = self.history.pop_redo().ok_or(Error::NothingToRedo)?;
+        let current = Entry::capture(&self.run, &self.active_attempt, entry.scope());
+        entry.restore(&mut self.run, &mut self.active_attempt);
+        self.history.push_undo(current);
+        Ok(Event::ActionRedone)
+    }
+
+    /// Checks whether there is an action that can be undone.
+    pub const fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Checks whether there is an action that can be redone.
+    pub const fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Returns the maximum amount of actions that can be undone. By default
+    /// this is [`DEFAULT_UNDO_LIMIT`](crate::timing::DEFAULT_UNDO_LIMIT).
+    pub const fn undo_limit(&self) -> usize {
+        self.history.limit()
+    }
+
+    /// Sets the maximum amount of actions that can be undone. The oldest
+    /// actions are forgotten if there are more actions than that.
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+
+    /// Accesses the [`EventLog`] of the current attempt or, if there is no
+    /// attempt in progress, of the attempt that was reset most recently.
+    pub const fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    /// Replays the [`EventLog`] provided into this timer to reconstruct the
+    /// attempt it was recorded for. Each command is applied as if it happened
+    /// at the time it was recorded at, so the attempt is reconstructed
+    /// deterministically. There may not be an attempt in progress. If the
+    /// attempt was not reset, it continues running from the time of the last
+    /// command.
+    pub fn replay(&mut self, log: &EventLog) -> core::result::Result<(), Error> {
+        if self.active_attempt.is_some() {
+            return Err(Error::RunAlreadyInProgress);
+        }
+
+        for entry in log.entries() {
+            if let Some(active_attempt) = &mut self.active_attempt {
+                active_attempt.frozen_elapsed = Some(entry.elapsed);
+            }
+
+            match &entry.command {
+                Command::Start => self.start(),
+                Command::StartAtSegment {
+                    segment_index,
+                    comparison,
+                    feed_segment_history,
+                } => self.start_at_segment(*segment_index, comparison, *feed_segment_history),
+                Command::Split => self.split(),
+                Command::SkipSplit => self.skip_split(),
+                Command::UndoSplit => self.undo_split(),
+                &Command::Reset {
+                    update_splits,
+                    timing_method,
+                } => {
+                    let current_timing_method = self.current_timing_method;
+                    self.current_timing_method = timing_method;
+                    let result = self.reset(update_splits);
+                    self.current_timing_method = current_timing_method;
+                    result
+                }
+                Command::ResetAndSetAttemptAsPb => self.reset_and_set_attempt_as_pb(),
+                Command::Pause => self.pause(),
+                Command::Resume => self.resume(),
+                Command::UndoAllPauses => self.undo_all_pauses(),
+                &Command::AdjustOffset(amount) => self.adjust_offset(amount),
+                &Command::AdjustTime {
+                    amount,
+                    timing_method,
+                } => self.adjust_time(amount, timing_method),
+                Command::Undo => self.undo(),
+                Command::Redo => self.redo(),
+                Command::InitializeGameTime => self.initialize_game_time(),
+                Command::DeinitializeGameTime => {
+                    self.deinitialize_game_time_logged();
+                    Ok(Event::Unknown)
+                }
+                &Command::SetGameTime(time) => self.set_game_time(time),
+                Command::PauseGameTime => self.pause_game_time(),
+                Command::ResumeGameTime => self.resume_game_time(),
+                &Command::SetLoadingTimes(time) => self.set_loading_times(time),
+                Command::SetCustomVariable { name, value } => {
+                    self.set_custom_variable(name.as_str(), value.as_str());
+                    Ok(Event::CustomVariableSet)
+                }
+            }?;
+
+            self.event_log.set_last_elapsed(entry.elapsed);
+        }
+
+        if let Some(active_attempt) = &mut self.active_attempt
+            && let Some(elapsed) = active_attempt.frozen_elapsed.take()
+        {
+            active_attempt.clock_shift =
+                elapsed - (active_attempt.clock.now() - active_attempt.start_time);
+        }
+
+        Ok(())
+    }
+
+    /// Captures the current attempt in a [`Journal`], so it can be recovered
+    /// after a crash. Frontends should store it periodically while an attempt
+    /// is in progress. Returns [`None`] if there is no attempt in progress.
+    pub fn journal(&self) -> Option<Journal> {
+        let active_attempt = self.active_attempt.as_ref()?;
+        let clock_started = utc_now() - active_attempt.elapsed().to_duration();
+        Some(Journal::new(
+            active_attempt.attempt_started.time,
+            clock_started,
+            self.event_log.clone(),
+        ))
+    }
+
+    /// Recovers the attempt stored in the [`Journal`] provided, for example
+    /// after the application crashed. The attempt is reconstructed by
+    /// replaying its event log and then continues as if it never got
+    /// interrupted, so the time that passed since the journal was stored is
+    /// part of the attempt. There may not be an attempt in progress.
+    pub fn recover(&mut self, journal: &Journal) -> core::result::Result<(), Error> {
+        self.replay(journal.event_log())?;
+
+        if let Some(active_attempt) = &mut self.active_attempt {
+            active_attempt.attempt_started = journal.attempt_started();
+            let elapsed = TimeSpan::from(utc_now() - journal.clock_started());
+            let replayed_elapsed = active_attempt.elapsed();
+            if elapsed > replayed_elapsed {
+                active_attempt.clock_shift += elapsed - replayed_elapsed;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the action and records the command in the event log if it
+    /// succeeds. The clock of the attempt is frozen while the action is
+    /// applied, so the time that gets recorded is exactly the time the action
+    /// observes.
+    fn logged<T>(
+        &mut self,
+        command: Command,
+        action: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let frozen = self.active_attempt.as_mut().map(|active_attempt| {
+            let elapsed = active_attempt.elapsed();
+            (elapsed, active_attempt.frozen_elapsed.replace(elapsed))
+        });
+        let time = self.snapshot().current_time();
+
+        let result = action(self);
+
+        let previously_frozen = frozen.and_then(|(_, previous)| previous);
+        if let Some(active_attempt) = &mut self.active_attempt {
+            active_attempt.frozen_elapsed = previously_frozen;
+        }
+        let value = result?;
+
+        let elapsed = match (&command, frozen, &self.active_attempt) {
+            (Command::Start | Command::StartAtSegment { .. }, _, _) => TimeSpan::zero(),
+            (_, Some((elapsed, _)), _) => elapsed,
+            (_, None, Some(active_attempt)) => active_attempt.elapsed(),
+            (_, None, None) => return Ok(value),
+        };
+        self.event_log.push(event_log::Entry {
+            elapsed,
+            real_time: time.real_time.unwrap_or_default(),
+            game_time: time.game_time,
+            command,
+        });
+
+        Ok(value)
+    }
+
+    fn record(&mut self, scope: Scope, action: impl FnOnce(&mut Self) -> Result) -> Result {
+        let entry = Entry::capture(&self.run, &self.active_attempt, scope);
+        let event = action(self)?;
+        self.history.push(entry);
+        Ok(event)
+    }
+
     /// Switches the current comparison to the next comparison in the list.
     pub fn switch_to_next_comparison(&mut self) {
         let mut comparisons = self.run.comparisons();
@@ -641,7 +1376,7 @@ impl Timer {
         if let State::Ended { attempt_ended } = active_attempt.state {
             attempt_ended - active_attempt.attempt_started
         } else {
-            TimeStamp::now() - active_attempt.start_time
+            active_attempt.elapsed()
         }
     }
 
@@ -665,6 +1400,13 @@ impl Timer {
     /// gets uninitialized for each new attempt.
     #[inline]
     pub fn initialize_game_time(&mut self) -> Result {
+        self.logged(
+            Command::InitializeGameTime,
+            Self::apply_initialize_game_time,
+        )
+    }
+
+    fn apply_initialize_game_time(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.loading_times.is_none() {
@@ -675,7 +1417,10 @@ impl Timer {
         }
     }
 
-    /// Deinitializes Game Time for the current attempt.
+    /// Deinitializes Game Time for the current attempt. This is not recorded
+    /// in the [`EventLog`]. Use
+    /// [`deinitialize_game_time_logged`](Self::deinitialize_game_time_logged)
+    /// if it should be.
     #[inline]
     pub const fn deinitialize_game_time(&mut self) {
         if let Some(active_attempt) = &mut self.active_attempt {
@@ -683,6 +1428,15 @@ impl Timer {
         }
     }
 
+    /// Deinitializes Game Time for the current attempt and records it in the
+    /// [`EventLog`], so it is part of the attempt when the log gets replayed.
+    pub fn deinitialize_game_time_logged(&mut self) {
+        let _ = self.logged(Command::DeinitializeGameTime, |timer| {
+            timer.deinitialize_game_time();
+            Ok(())
+        });
+    }
+
     /// Returns whether the Game Timer is currently paused. If the Game Timer is
     /// not paused, it automatically increments similar to Real Time.
     #[inline]
@@ -696,6 +1450,10 @@ impl Timer {
     /// Pauses the Game Timer such that it doesn't automatically increment
     /// similar to Real Time.
     pub fn pause_game_time(&mut self) -> Result {
+        self.logged(Command::PauseGameTime, Self::apply_pause_game_time)
+    }
+
+    fn apply_pause_game_time(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_none() {
@@ -713,6 +1471,10 @@ impl Timer {
     /// Resumes the Game Timer such that it automatically increments similar to
     /// Real Time, starting from the Game Time it was paused at.
     pub fn resume_game_time(&mut self) -> Result {
+        self.logged(Command::ResumeGameTime, Self::apply_resume_game_time)
+    }
+
+    fn apply_resume_game_time(&mut self) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_some() {
@@ -734,6 +1496,12 @@ impl Timer {
     /// the Game Timer never shows any time that is not coming from the game.
     #[inline]
     pub fn set_game_time(&mut self, game_time: TimeSpan) -> Result {
+        self.logged(Command::SetGameTime(game_time), |timer| {
+            timer.apply_set_game_time(game_time)
+        })
+    }
+
+    fn apply_set_game_time(&mut self, game_time: TimeSpan) -> Result {
         let active_attempt = self.active_attempt.as_mut().ok_or(Error::NoRunInProgress)?;
 
         if active_attempt.game_time_paused_at.is_some() {
@@ -759,6 +1527,12 @@ impl Timer {
     /// is then automatically determined by Real Time - Loading Times.
     #[inline]
     pub fn set_loading_times(&mut self, time: TimeSpan) -> Result {
+        self.logged(Command::SetLoadingTimes(time), |timer| {
+            timer.apply_set_loading_times(time)
+        })
+    }
+
+    fn apply_set_loading_times(&mut self, time: TimeSpan) -> Result {
         if let Some(active_attempt) = &mut self.active_attempt {
             active_attempt.set_loading_times(time, &self.run);
             Ok(Event::LoadingTimesSet)
@@ -771,6 +1545,25 @@ impl Timer {
     /// variable does not exist, a temporary variable gets created that will not
     /// be stored in the splits file.
     pub fn set_custom_variable<N, V>(&mut self, name: N, value: V)
+    where
+        N: PopulateString,
+        V: PopulateString,
+    {
+        if self.active_attempt.is_some() {
+            let command = Command::SetCustomVariable {
+                name: name.as_str().into(),
+                value: value.as_str().into(),
+            };
+            let _ = self.logged(command, |timer| {
+                timer.apply_set_custom_variable(name, value);
+                Ok(())
+            });
+        } else {
+            self.apply_set_custom_variable(name, value);
+        }
+    }
+
+    fn apply_set_custom_variable<N, V>(&mut self, name: N, value: V)
     where
         N: PopulateString,
         V: PopulateString,
@@ -782,6 +1575,85 @@ impl Timer {
         }
     }
 
+    /// Adds a race comparison for the opponent specified. A race comparison
+    /// is a comparison whose split times are updated live from an external
+    /// source, such as the splits of an opponent in a race, so the components
+    /// can show the gap to the opponent. Its name is the name of the opponent
+    /// prefixed by [`RACE_COMPARISON_PREFIX`]. Race comparisons are not stored
+    /// in the splits file.
+    pub fn add_race_comparison(
+        &mut self,
+        opponent: &str,
+    ) -> core::result::Result<(), RaceComparisonError> {
+        let name = race_comparison_name(opponent);
+        if self.run.comparisons().any(|c| c == name) {
+            return Err(RaceComparisonError::DuplicateOpponent);
+        }
+        self.run.custom_comparisons_mut().push(name);
+        Ok(())
+    }
+
+    /// Updates the split time of the opponent's race comparison for the
+    /// segment specified. The time should contain the opponent's split time
+    /// for all the timing methods that are known. An empty time clears the
+    /// split time, for example when the opponent undoes their split.
+    pub fn set_race_split_time(
+        &mut self,
+        opponent: &str,
+        segment_index: usize,
+        time: Time,
+    ) -> core::result::Result<(), RaceComparisonError> {
+        let name = self
+            .race_comparison(opponent)
+            .ok_or(RaceComparisonError::NoSuchOpponent)?
+            .to_owned();
+        let segment = self
+            .run
+            .segments_mut()
+            .get_mut(segment_index)
+            .ok_or(RaceComparisonError::SegmentIndexOutOfBounds)?;
+        *segment.comparison_mut(&name) = time;
+        Ok(())
+    }
+
+    /// Removes the race comparison of the opponent specified. If it is the
+    /// current comparison, the current comparison is switched to the Personal
+    /// Best.
+    pub fn remove_race_comparison(
+        &mut self,
+        opponent: &str,
+    ) -> core::result::Result<(), RaceComparisonError> {
+        let name = self
+            .race_comparison(opponent)
+            .ok_or(RaceComparisonError::NoSuchOpponent)?
+            .to_owned();
+        self.run.custom_comparisons_mut().retain(|c| *c != name);
+        for segment in self.run.segments_mut() {
+            segment.comparisons_mut().remove(&name);
+        }
+        if self.current_comparison == name {
+            self.current_comparison = personal_best::NAME.to_string();
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the names of all the opponents that have a
+    /// race comparison.
+    pub fn race_opponents(&self) -> impl Iterator<Item = &str> + '_ {
+        self.run
+            .custom_comparisons()
+            .iter()
+            .filter_map(|c| c.strip_prefix(RACE_COMPARISON_PREFIX))
+    }
+
+    fn race_comparison(&self, opponent: &str) -> Option<&str> {
+        self.run
+            .custom_comparisons()
+            .iter()
+            .find(|c| c.strip_prefix(RACE_COMPARISON_PREFIX) == Some(opponent))
+            .map(String::as_str)
+    }
+
     /// Notifies the `Timer` that the currently loaded [`Layout`](crate::Layout)
     /// has changed. If the [`Run`] has a
     /// [`LinkedLayout`](crate::run::LinkedLayout), it will be updated
@@ -798,6 +1670,13 @@ impl Timer {
     }
 }
 
+fn race_comparison_name(opponent: &str) -> String {
+    let mut name = String::with_capacity(RACE_COMPARISON_PREFIX.len() + opponent.len());
+    name.push_str(RACE_COMPARISON_PREFIX);
+    name.push_str(opponent);
+    name
+}
+
 fn set_run_as_pb(run: &mut Run) {
     run.import_pb_into_segment_history();
     run.fix_splits();
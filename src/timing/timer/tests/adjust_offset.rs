@@ -0,0 +1,63 @@
+use super::timer;
+use crate::{
+    event::{Error, Event},
+    util::tests_helper::{span, start_run},
+};
+
+#[test]
+fn shifts_the_split_times_and_the_current_time() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(5.0)).unwrap();
+    timer.split().unwrap();
+    let real_time = timer.run().segment(0).split_time().real_time.unwrap();
+
+    assert_eq!(timer.adjust_offset(span(2.0)), Ok(Event::OffsetAdjusted));
+
+    let split_time = timer.run().segment(0).split_time();
+    assert_eq!(split_time.real_time, Some(real_time + span(2.0)));
+    assert_eq!(split_time.game_time, Some(span(7.0)));
+    assert_eq!(timer.snapshot().current_time().game_time, Some(span(7.0)));
+    assert!(timer.snapshot().current_time().real_time.unwrap() >= span(2.0));
+}
+
+#[test]
+fn can_remove_time() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(5.0)).unwrap();
+
+    timer.adjust_offset(span(-2.0)).unwrap();
+    assert_eq!(timer.snapshot().current_time().game_time, Some(span(3.0)));
+    assert_eq!(timer.split(), Err(Error::NegativeTime));
+}
+
+#[test]
+fn split_times_cant_become_negative() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.split().unwrap();
+
+    assert_eq!(timer.adjust_offset(span(-60.0)), Err(Error::NegativeTime));
+}
+
+#[test]
+fn can_be_undone() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(5.0)).unwrap();
+    timer.split().unwrap();
+
+    timer.adjust_offset(span(2.0)).unwrap();
+    timer.undo().unwrap();
+    assert_eq!(
+        timer.run().segment(0).split_time().game_time,
+        Some(span(5.0))
+    );
+}
+
+#[test]
+fn requires_an_attempt() {
+    let mut timer = timer();
+    assert_eq!(timer.adjust_offset(span(2.0)), Err(Error::NoRunInProgress));
+}
@@ -0,0 +1,77 @@
+use super::timer;
+use crate::{
+    TimingMethod,
+    event::{Error, Event},
+    util::tests_helper::{span, start_run},
+};
+
+#[test]
+fn adjusts_the_game_time_only() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(5.0)).unwrap();
+    timer.split().unwrap();
+
+    assert_eq!(
+        timer.adjust_time(span(-2.0), TimingMethod::GameTime),
+        Ok(Event::TimeAdjusted)
+    );
+
+    assert_eq!(timer.snapshot().current_time().game_time, Some(span(3.0)));
+    assert_eq!(
+        timer.run().segment(0).split_time().game_time,
+        Some(span(5.0))
+    );
+    assert!(timer.snapshot().current_time().real_time.unwrap() < span(1.0));
+}
+
+#[test]
+fn adjusts_the_real_time_only() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.resume_game_time().unwrap();
+    timer.pause().unwrap();
+    let time = timer.snapshot().current_time();
+
+    timer
+        .adjust_time(span(10.0), TimingMethod::RealTime)
+        .unwrap();
+
+    let adjusted = timer.snapshot().current_time();
+    assert_eq!(adjusted.real_time, time.real_time.map(|t| t + span(10.0)));
+    assert_eq!(adjusted.game_time, time.game_time);
+
+    timer.resume().unwrap();
+    timer.undo_all_pauses().unwrap();
+    assert!(timer.snapshot().current_time().real_time.unwrap() >= span(10.0));
+}
+
+#[test]
+fn is_recorded_in_the_event_log() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer
+        .adjust_time(span(1.0), TimingMethod::GameTime)
+        .unwrap();
+    timer.split().unwrap();
+    timer.reset(false).unwrap();
+
+    let mut replayed = super::timer();
+    replayed.set_current_timing_method(TimingMethod::GameTime);
+    replayed.replay(timer.event_log()).unwrap();
+    assert_eq!(replayed.event_log(), timer.event_log());
+}
+
+#[test]
+fn requires_initialized_game_time() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    assert_eq!(
+        timer.adjust_time(span(1.0), TimingMethod::GameTime),
+        Err(Error::GameTimeNotInitialized)
+    );
+    assert_eq!(
+        timer.adjust_time(span(1.0), TimingMethod::RealTime),
+        Ok(Event::TimeAdjusted)
+    );
+}
@@ -0,0 +1,62 @@
+use super::run;
+use crate::{TimeSpan, Timer, TimerPhase, platform::Arc, timing::Clock, util::tests_helper::span};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Default)]
+struct ManualClock {
+    milliseconds: AtomicU64,
+}
+
+impl ManualClock {
+    fn advance(&self, seconds: u64) {
+        self.milliseconds
+            .fetch_add(seconds * 1000, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> TimeSpan {
+        TimeSpan::from_milliseconds(self.milliseconds.load(Ordering::Relaxed) as f64)
+    }
+}
+
+#[test]
+fn uses_the_clock_provided() {
+    let clock = Arc::new(ManualClock::default());
+    let mut timer = Timer::with_clock(run(), clock.clone()).unwrap();
+
+    timer.start().unwrap();
+    assert_eq!(timer.snapshot().current_time().real_time, Some(span(0.0)));
+
+    clock.advance(5);
+    timer.split().unwrap();
+    clock.advance(3);
+    timer.pause().unwrap();
+    clock.advance(100);
+    timer.resume().unwrap();
+    clock.advance(2);
+    timer.split().unwrap();
+
+    assert_eq!(
+        timer.run().segment(0).split_time().real_time,
+        Some(span(5.0))
+    );
+    assert_eq!(
+        timer.run().segment(1).split_time().real_time,
+        Some(span(10.0))
+    );
+    assert_eq!(timer.get_pause_time(), Some(span(100.0)));
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+}
+
+#[test]
+fn time_stands_still_without_the_clock_advancing() {
+    let clock = Arc::new(ManualClock::default());
+    let mut timer = Timer::with_clock(run(), clock.clone()).unwrap();
+    timer.start().unwrap();
+    clock.advance(42);
+
+    let time = timer.snapshot().current_time();
+    assert_eq!(time, timer.snapshot().current_time());
+    assert_eq!(time.real_time, Some(span(42.0)));
+}
@@ -0,0 +1,244 @@
+use super::{run, timer};
+use crate::{
+    Timer, TimerPhase,
+    event::Error,
+    run::{parser, saver},
+    timing::event_log::{Command, EventLog, MAX_ENTRIES},
+    util::tests_helper::{span, start_run},
+};
+
+fn commands(timer: &Timer) -> Vec<Command> {
+    timer
+        .event_log()
+        .entries()
+        .iter()
+        .map(|e| e.command.clone())
+        .collect()
+}
+
+fn play_attempt(timer: &mut Timer) {
+    start_run(timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    timer.pause().unwrap();
+    timer.resume().unwrap();
+    timer.set_custom_variable("Deaths", "3");
+    timer.set_game_time(span(1.5)).unwrap();
+    timer.set_game_time(span(2.0)).unwrap();
+    timer.split().unwrap();
+    timer.undo().unwrap();
+    timer.set_game_time(span(2.5)).unwrap();
+    timer.split().unwrap();
+}
+
+#[test]
+fn commands_are_recorded() {
+    let mut timer = timer();
+    play_attempt(&mut timer);
+
+    assert_eq!(
+        commands(&timer),
+        [
+            Command::Start,
+            Command::InitializeGameTime,
+            Command::PauseGameTime,
+            Command::SetGameTime(span(1.0)),
+            Command::Split,
+            Command::Pause,
+            Command::Resume,
+            Command::SetCustomVariable {
+                name: "Deaths".into(),
+                value: "3".into(),
+            },
+            Command::SetGameTime(span(2.0)),
+            Command::Split,
+            Command::Undo,
+            Command::SetGameTime(span(2.5)),
+            Command::Split,
+        ],
+    );
+
+    let entries = timer.event_log().entries();
+    assert_eq!(entries[4].game_time, Some(span(1.0)));
+    assert!(entries.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+}
+
+#[test]
+fn interleaved_updates_are_merged() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.split().unwrap();
+    for i in 1..=10 {
+        timer.set_game_time(span(i as f64)).unwrap();
+        timer.set_custom_variable("Deaths", i.to_string());
+        timer.set_custom_variable("Level", "1");
+    }
+
+    assert_eq!(
+        commands(&timer)[5..],
+        [
+            Command::SetGameTime(span(10.0)),
+            Command::SetCustomVariable {
+                name: "Deaths".into(),
+                value: "10".into(),
+            },
+            Command::SetCustomVariable {
+                name: "Level".into(),
+                value: "1".into(),
+            },
+        ],
+    );
+}
+
+#[test]
+fn deinitializing_the_game_time_is_recorded() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.deinitialize_game_time_logged();
+    timer.split().unwrap();
+    timer.initialize_game_time().unwrap();
+    timer.deinitialize_game_time_logged();
+
+    assert_eq!(
+        commands(&timer)[4..],
+        [
+            Command::DeinitializeGameTime,
+            Command::Split,
+            Command::InitializeGameTime,
+            Command::DeinitializeGameTime,
+        ],
+    );
+
+    // The game time is recorded as it was right before it got deinitialized.
+    assert_eq!(timer.event_log().entries()[4].game_time, Some(span(0.0)));
+
+    let mut replayed = Timer::new(run()).unwrap();
+    replayed.replay(timer.event_log()).unwrap();
+    assert_eq!(replayed.event_log(), timer.event_log());
+    assert!(!replayed.is_game_time_initialized());
+}
+
+#[test]
+fn the_log_is_capped() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    for _ in 0..MAX_ENTRIES / 2 {
+        timer.pause().unwrap();
+        timer.resume().unwrap();
+    }
+
+    assert!(timer.event_log().is_truncated());
+    assert_eq!(timer.event_log().entries().len(), MAX_ENTRIES);
+
+    timer.reset(true).unwrap();
+    timer.start().unwrap();
+    assert!(!timer.event_log().is_truncated());
+}
+
+#[test]
+fn commands_without_an_attempt_are_not_recorded() {
+    let mut timer = timer();
+    timer.set_custom_variable("Deaths", "3");
+    assert!(timer.event_log().is_empty());
+}
+
+#[test]
+fn replaying_reconstructs_the_attempt() {
+    let mut timer = timer();
+    play_attempt(&mut timer);
+    timer.reset(true).unwrap();
+    timer.undo().unwrap();
+    timer.reset(true).unwrap();
+
+    let mut replayed = Timer::new(run()).unwrap();
+    replayed.replay(timer.event_log()).unwrap();
+
+    assert_eq!(replayed.current_phase(), TimerPhase::NotRunning);
+    assert_eq!(replayed.event_log().entries(), timer.event_log().entries());
+    assert_eq!(
+        replayed.run().attempt_history().len(),
+        timer.run().attempt_history().len(),
+    );
+    for (a, b) in replayed.run().segments().iter().zip(timer.run().segments()) {
+        assert_eq!(a.personal_best_split_time(), b.personal_best_split_time());
+        assert_eq!(a.best_segment_time(), b.best_segment_time());
+    }
+}
+
+#[test]
+fn replayed_attempts_continue_running() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+
+    let mut replayed = Timer::new(run()).unwrap();
+    replayed.replay(timer.event_log()).unwrap();
+
+    assert_eq!(replayed.current_split_index(), Some(1));
+    assert_eq!(
+        replayed.run().segment(0).split_time(),
+        timer.run().segment(0).split_time(),
+    );
+    replayed.set_game_time(span(2.0)).unwrap();
+    replayed.split().unwrap();
+    assert_eq!(replayed.current_split_index(), Some(2));
+}
+
+#[test]
+fn replaying_requires_no_attempt_in_progress() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    assert_eq!(
+        timer.replay(&EventLog::default()),
+        Err(Error::RunAlreadyInProgress),
+    );
+}
+
+#[test]
+fn logs_can_be_stored_as_json() {
+    let mut timer = timer();
+    play_attempt(&mut timer);
+
+    let json = serde_json::to_string(timer.event_log()).unwrap();
+    let log: EventLog = serde_json::from_str(&json).unwrap();
+    assert_eq!(log.entries(), timer.event_log().entries());
+}
+
+#[test]
+fn logs_are_only_stored_in_the_attempt_history_if_enabled() {
+    let mut timer = timer();
+    play_attempt(&mut timer);
+    timer.reset(true).unwrap();
+    assert!(timer.run().attempt_history()[0].event_log().is_empty());
+
+    timer.set_stores_event_logs(true);
+    play_attempt(&mut timer);
+    timer.reset(true).unwrap();
+    assert_eq!(
+        timer.run().attempt_history()[1].event_log(),
+        timer.event_log(),
+    );
+}
+
+#[test]
+fn stored_logs_survive_saving() {
+    let mut timer = timer();
+    timer.set_stores_event_logs(true);
+    play_attempt(&mut timer);
+    timer.deinitialize_game_time_logged();
+    timer.reset(true).unwrap();
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(timer.run(), &mut buf).unwrap();
+    let run = parser::livesplit::parse(&buf).unwrap();
+    let log = run.attempt_history()[0].event_log();
+    assert_eq!(log, timer.event_log());
+
+    let mut replayed = Timer::new(run.clone()).unwrap();
+    replayed.replay(log).unwrap();
+    assert_eq!(
+        replayed.run().attempt_history().last().unwrap().time(),
+        timer.run().attempt_history()[0].time(),
+    );
+}
@@ -0,0 +1,47 @@
+use super::run;
+use crate::{
+    TimeSpan, Timer, TimingMethod,
+    util::tests_helper::{span, start_run},
+};
+
+#[test]
+fn split_times_are_stored_on_frame_boundaries() {
+    let mut run = run();
+    run.set_frame_rate(Some(60.0));
+    let mut timer = Timer::new(run).unwrap();
+
+    start_run(&mut timer);
+    timer.set_game_time(span(1.01)).unwrap();
+    timer.split().unwrap();
+
+    let split_time = timer.run().segment(0).split_time()[TimingMethod::GameTime].unwrap();
+    assert_eq!(split_time, TimeSpan::from_frames(61, 60.0));
+    assert_eq!(split_time.to_frames(60.0), 61);
+}
+
+#[test]
+fn invalid_frame_rates_are_ignored() {
+    let mut run = run();
+    run.set_frame_rate(Some(0.0));
+    assert_eq!(run.frame_rate(), None);
+    run.set_frame_rate(Some(f64::NAN));
+    assert_eq!(run.frame_rate(), None);
+    run.set_frame_rate(Some(1e-300));
+    assert_eq!(run.frame_rate(), None);
+    run.set_frame_rate(Some(1e300));
+    assert_eq!(run.frame_rate(), None);
+}
+
+#[test]
+fn extreme_frame_rates_do_not_break_splitting() {
+    let mut run = run();
+    run.set_frame_rate(Some(1e-300));
+    let mut timer = Timer::new(run).unwrap();
+
+    start_run(&mut timer);
+    timer.set_game_time(span(1.01)).unwrap();
+    timer.split().unwrap();
+
+    let split_time = timer.run().segment(0).split_time()[TimingMethod::GameTime];
+    assert_eq!(split_time, Some(span(1.01)));
+}
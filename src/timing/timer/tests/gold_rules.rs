@@ -0,0 +1,148 @@
+use super::run;
+use crate::{
+    Time, TimeSpan, Timer, TimingMethod,
+    run::GoldRules,
+    util::tests_helper::{run_with_splits, span, start_run},
+};
+
+fn timer(gold_rules: GoldRules, offset: TimeSpan) -> Timer {
+    let mut run = run();
+    run.set_gold_rules(gold_rules);
+    run.set_offset(offset);
+    let mut timer = Timer::new(run).unwrap();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+    timer
+}
+
+fn split_undo_and_resplit(timer: &mut Timer) {
+    start_run(timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(1.5)).unwrap();
+    timer.split().unwrap();
+    timer.undo_split().unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(20.0)).unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+}
+
+#[test]
+fn short_segments_after_undo_are_not_golds() {
+    let mut timer = timer(
+        GoldRules {
+            min_segment_time_after_undo: Some(span(1.0)),
+            ..Default::default()
+        },
+        TimeSpan::zero(),
+    );
+    split_undo_and_resplit(&mut timer);
+
+    let run = timer.run();
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(1.0))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(5.0))
+    );
+    assert_eq!(
+        run.segment(1).segment_history().get(2).unwrap().game_time,
+        None
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(5.0))
+    );
+}
+
+#[test]
+fn rejected_real_time_golds_keep_the_game_time_in_the_history() {
+    let mut run = run();
+    run.set_gold_rules(GoldRules {
+        min_segment_time_after_undo: Some(span(1.0)),
+        ..Default::default()
+    });
+    for (index, segment) in run.segments_mut().iter_mut().enumerate() {
+        let time = span(5.0);
+        let split_time = span(5.0 * (index + 1) as f64);
+        segment.set_best_segment_time(
+            Time::new()
+                .with_real_time(Some(time))
+                .with_game_time(Some(time)),
+        );
+        segment.set_personal_best_split_time(
+            Time::new()
+                .with_real_time(Some(split_time))
+                .with_game_time(Some(split_time)),
+        );
+    }
+    let mut timer = Timer::new(run).unwrap();
+    // The attempt is not a new Personal Best for game time, so the gold rules
+    // apply.
+    timer.set_current_timing_method(TimingMethod::GameTime);
+
+    // The real time of the segment after the undo is almost 0, so it's
+    // rejected as a gold, while its game time of 2 seconds qualifies.
+    start_run(&mut timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(1.5)).unwrap();
+    timer.split().unwrap();
+    timer.undo_split().unwrap();
+    timer.set_game_time(span(3.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(20.0)).unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    let segment = timer.run().segment(1);
+    assert_eq!(segment.best_segment_time().real_time, Some(span(5.0)));
+    assert_eq!(segment.best_segment_time().game_time, Some(span(2.0)));
+
+    let time = segment.segment_history().get(1).unwrap();
+    assert_eq!(time.real_time, None);
+    assert_eq!(time.game_time, Some(span(2.0)));
+}
+
+#[test]
+fn segments_after_undo_are_golds_by_default() {
+    let mut timer = timer(GoldRules::default(), TimeSpan::zero());
+    split_undo_and_resplit(&mut timer);
+
+    assert_eq!(
+        timer.run().segment(1).best_segment_time().game_time,
+        Some(span(0.5)),
+    );
+}
+
+#[test]
+fn segments_with_long_game_time_pauses_are_not_golds() {
+    // The offset makes the real time of the first segment roughly 100 seconds
+    // longer than its game time.
+    let mut timer = timer(
+        GoldRules {
+            max_game_time_pause: Some(span(10.0)),
+            ..Default::default()
+        },
+        TimeSpan::from_seconds(100.0),
+    );
+
+    start_run(&mut timer);
+    for split in [1.0, 2.0, 30.0] {
+        timer.set_game_time(span(split)).unwrap();
+        timer.split().unwrap();
+    }
+    timer.reset(true).unwrap();
+
+    let run = timer.run();
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(5.0))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(1.0))
+    );
+}
@@ -0,0 +1,84 @@
+use super::timer;
+use crate::{
+    TimerPhase,
+    event::Error,
+    timing::journal::Journal,
+    util::tests_helper::{span, start_run},
+};
+
+#[test]
+fn only_exists_during_an_attempt() {
+    let mut timer = timer();
+    assert!(timer.journal().is_none());
+    timer.start().unwrap();
+    assert!(timer.journal().is_some());
+    timer.reset(false).unwrap();
+    assert!(timer.journal().is_none());
+}
+
+#[test]
+fn recovers_the_attempt() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(5.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(7.0)).unwrap();
+    let journal = timer.journal().unwrap();
+
+    let mut recovered = super::timer();
+    recovered.recover(&journal).unwrap();
+
+    assert_eq!(recovered.current_phase(), TimerPhase::Running);
+    assert_eq!(recovered.current_split_index(), Some(1));
+    assert_eq!(
+        recovered.run().segment(0).split_time(),
+        timer.run().segment(0).split_time()
+    );
+    assert_eq!(
+        recovered.snapshot().current_time().game_time,
+        Some(span(7.0))
+    );
+    assert_eq!(recovered.event_log(), timer.event_log());
+    assert_eq!(
+        recovered.journal().unwrap().attempt_started(),
+        journal.attempt_started()
+    );
+}
+
+#[test]
+fn the_time_keeps_running_while_the_application_is_down() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    let journal = timer.journal().unwrap();
+    let journal = Journal::new(
+        journal.attempt_started().time,
+        journal.clock_started() - span(60.0).to_duration(),
+        journal.event_log().clone(),
+    );
+
+    let mut recovered = super::timer();
+    recovered.recover(&journal).unwrap();
+
+    let real_time = recovered.snapshot().current_time().real_time.unwrap();
+    assert!(real_time >= span(60.0) && real_time < span(61.0));
+}
+
+#[test]
+fn can_be_stored_as_json() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.split().unwrap();
+    let journal = timer.journal().unwrap();
+
+    let mut json = Vec::new();
+    journal.write_json(&mut json).unwrap();
+    assert_eq!(Journal::from_json(&*json).unwrap(), journal);
+}
+
+#[test]
+fn requires_no_attempt_to_be_in_progress() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    let journal = timer.journal().unwrap();
+    assert_eq!(timer.recover(&journal), Err(Error::RunAlreadyInProgress));
+}
@@ -6,8 +6,20 @@ use crate::{
     Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
 };
 
+mod adjust_offset;
+mod adjust_time;
+mod clock;
+mod event_log;
 mod events;
+mod frame_rate;
+mod gold_rules;
+mod journal;
 mod mark_as_modified;
+mod practice;
+mod race;
+mod segment_practice;
+mod split_cooldown;
+mod undo;
 mod variables;
 
 fn run() -> Run {
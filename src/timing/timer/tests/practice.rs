@@ -0,0 +1,50 @@
+use super::timer;
+use crate::{
+    TimingMethod,
+    event::Error,
+    util::tests_helper::{run_with_splits, span, start_run},
+};
+
+#[test]
+fn practice_attempts_are_not_stored() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+    timer.mark_as_unmodified();
+
+    timer.set_practice_mode(true).unwrap();
+    start_run(&mut timer);
+    assert_eq!(timer.run().attempt_count(), 1);
+
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    assert!(!timer.current_attempt_has_new_best_times());
+    timer.reset(true).unwrap();
+
+    let run = timer.run();
+    assert_eq!(run.attempt_history().len(), 1);
+    assert_eq!(run.segment(0).segment_history().iter().count(), 1);
+    assert_eq!(
+        run.segment(0).best_segment_time()[TimingMethod::GameTime],
+        Some(span(5.0))
+    );
+    assert!(!run.has_been_modified());
+}
+
+#[test]
+fn practice_attempts_cant_be_set_as_pb() {
+    let mut timer = timer();
+    timer.set_practice_mode(true).unwrap();
+    start_run(&mut timer);
+    assert_eq!(timer.reset_and_set_attempt_as_pb(), Err(Error::Unsupported));
+}
+
+#[test]
+fn practice_mode_cant_change_during_an_attempt() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    assert_eq!(
+        timer.set_practice_mode(true),
+        Err(Error::RunAlreadyInProgress)
+    );
+    assert!(!timer.is_practicing());
+}
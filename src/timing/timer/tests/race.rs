@@ -0,0 +1,77 @@
+use super::timer;
+use crate::{
+    Time, TimeSpan, TimingMethod,
+    analysis::state_helper::last_delta,
+    comparison::personal_best,
+    run::saver,
+    timing::RaceComparisonError,
+    util::tests_helper::{make_progress_run_with_splits_opt, start_run},
+};
+
+fn time(seconds: f64) -> Time {
+    Time::new()
+        .with_real_time(Some(TimeSpan::from_seconds(seconds)))
+        .with_game_time(Some(TimeSpan::from_seconds(seconds)))
+}
+
+#[test]
+fn opponent_splits_are_compared_against() {
+    let mut timer = timer();
+    timer.add_race_comparison("Opponent").unwrap();
+    assert_eq!(
+        timer.add_race_comparison("Opponent"),
+        Err(RaceComparisonError::DuplicateOpponent),
+    );
+    assert_eq!(timer.race_opponents().collect::<Vec<_>>(), ["Opponent"]);
+
+    timer.set_current_comparison("[Race]Opponent").unwrap();
+
+    start_run(&mut timer);
+    timer
+        .set_race_split_time("Opponent", 0, time(10.0))
+        .unwrap();
+    make_progress_run_with_splits_opt(&mut timer, &[Some(12.0)]);
+
+    let delta = last_delta(timer.run(), 0, "[Race]Opponent", TimingMethod::GameTime);
+    assert_eq!(delta, Some(TimeSpan::from_seconds(2.0)));
+
+    assert_eq!(
+        timer.set_race_split_time("Opponent", 3, time(20.0)),
+        Err(RaceComparisonError::SegmentIndexOutOfBounds),
+    );
+    assert_eq!(
+        timer.set_race_split_time("Someone Else", 0, time(20.0)),
+        Err(RaceComparisonError::NoSuchOpponent),
+    );
+}
+
+#[test]
+fn race_comparisons_are_not_saved() {
+    let mut timer = timer();
+    timer.add_race_comparison("Opponent").unwrap();
+    timer
+        .set_race_split_time("Opponent", 0, time(10.0))
+        .unwrap();
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(timer.run(), &mut buf).unwrap();
+    assert!(!buf.contains("[Race]"));
+}
+
+#[test]
+fn removing_the_current_race_comparison() {
+    let mut timer = timer();
+    timer.add_race_comparison("Opponent").unwrap();
+    timer
+        .set_race_split_time("Opponent", 0, time(10.0))
+        .unwrap();
+    timer.set_current_comparison("[Race]Opponent").unwrap();
+
+    timer.remove_race_comparison("Opponent").unwrap();
+    assert_eq!(timer.current_comparison(), personal_best::NAME);
+    assert_eq!(timer.race_opponents().count(), 0);
+    assert_eq!(
+        timer.run().segment(0).comparison("[Race]Opponent"),
+        Time::new()
+    );
+}
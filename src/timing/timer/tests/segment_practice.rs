@@ -0,0 +1,114 @@
+use super::timer;
+use crate::{
+    TimerPhase, TimingMethod,
+    comparison::personal_best,
+    event::Error,
+    util::tests_helper::{run_with_splits, span},
+};
+
+#[test]
+fn starts_at_the_comparison_time() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+
+    timer
+        .start_at_segment(1, personal_best::NAME, false)
+        .unwrap();
+
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert_eq!(timer.current_split_index(), Some(1));
+    assert_eq!(timer.run().attempt_count(), 1);
+    assert_eq!(
+        timer.run().segment(0).split_time().game_time,
+        Some(span(5.0))
+    );
+    let game_time = timer.snapshot().current_time().game_time.unwrap();
+    assert!(game_time >= span(5.0) && game_time < span(6.0));
+}
+
+#[test]
+fn practiced_segments_can_feed_the_segment_history() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+
+    timer
+        .start_at_segment(1, personal_best::NAME, true)
+        .unwrap();
+    timer.pause_game_time().unwrap();
+    timer.set_game_time(span(7.0)).unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    let run = timer.run();
+    assert_eq!(run.attempt_count(), 1);
+    assert_eq!(run.attempt_history().len(), 1);
+    assert_eq!(run.segment(0).segment_history().iter().count(), 1);
+    assert!(
+        run.segment(1)
+            .segment_history()
+            .iter()
+            .any(|&(index, time)| index <= 0 && time.game_time == Some(span(2.0)))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time()[TimingMethod::GameTime],
+        Some(span(2.0))
+    );
+    assert_eq!(
+        run.segment(1).personal_best_split_time().game_time,
+        Some(span(10.0))
+    );
+}
+
+#[test]
+fn practiced_segments_are_discarded_by_default() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+    timer.mark_as_unmodified();
+
+    timer
+        .start_at_segment(1, personal_best::NAME, false)
+        .unwrap();
+    timer.pause_game_time().unwrap();
+    timer.set_game_time(span(7.0)).unwrap();
+    timer.split().unwrap();
+    assert!(!timer.current_attempt_has_new_best_times());
+    timer.reset(true).unwrap();
+
+    let run = timer.run();
+    assert_eq!(run.segment(1).segment_history().iter().count(), 1);
+    assert_eq!(
+        run.segment(1).best_segment_time()[TimingMethod::GameTime],
+        Some(span(5.0))
+    );
+    assert!(!run.has_been_modified());
+}
+
+#[test]
+fn synthetic_splits_cant_be_undone() {
+    let mut timer = timer();
+    run_with_splits(&mut timer, &[5.0, 10.0, 15.0]);
+
+    timer
+        .start_at_segment(2, personal_best::NAME, false)
+        .unwrap();
+    assert_eq!(timer.undo_split(), Err(Error::CantUndoFirstSplit));
+    assert_eq!(timer.reset_and_set_attempt_as_pb(), Err(Error::Unsupported));
+}
+
+#[test]
+fn invalid_starts_are_rejected() {
+    let mut timer = timer();
+    assert_eq!(
+        timer.start_at_segment(3, personal_best::NAME, false),
+        Err(Error::SegmentIndexOutOfBounds)
+    );
+    assert_eq!(
+        timer.start_at_segment(1, "Nope", false),
+        Err(Error::ComparisonDoesntExist)
+    );
+    timer.start().unwrap();
+    assert_eq!(
+        timer.start_at_segment(1, personal_best::NAME, false),
+        Err(Error::RunAlreadyInProgress)
+    );
+}
@@ -0,0 +1,61 @@
+use super::timer;
+use crate::{
+    TimerPhase,
+    event::{Error, Event},
+    timing::event_log::{Command, Entry, EventLog},
+    util::tests_helper::span,
+};
+
+fn log(splits: &[f64]) -> EventLog {
+    let entry = |elapsed, command| Entry {
+        elapsed: span(elapsed),
+        real_time: span(elapsed),
+        game_time: None,
+        command,
+    };
+    EventLog::from_entries(
+        core::iter::once(entry(0.0, Command::Start))
+            .chain(splits.iter().map(|&s| entry(s, Command::Split)))
+            .collect(),
+    )
+}
+
+#[test]
+fn splits_within_the_cooldown_are_rejected() {
+    let mut timer = timer();
+    timer.set_split_cooldown(span(0.3));
+
+    assert_eq!(timer.replay(&log(&[0.5, 0.7])), Err(Error::SplitCooldown));
+    assert_eq!(timer.current_split_index(), Some(1));
+    assert_eq!(timer.event_log().entries().len(), 2);
+}
+
+#[test]
+fn the_cooldown_applies_to_the_start_of_the_attempt() {
+    let mut timer = timer();
+    timer.set_split_cooldown(span(0.3));
+
+    assert_eq!(timer.replay(&log(&[0.1])), Err(Error::SplitCooldown));
+    assert_eq!(timer.current_split_index(), Some(0));
+}
+
+#[test]
+fn splits_after_the_cooldown_are_accepted() {
+    let mut timer = timer();
+    timer.set_split_cooldown(span(0.3));
+
+    timer.replay(&log(&[0.5, 0.8, 1.5])).unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+}
+
+#[test]
+fn undoing_a_split_resets_the_cooldown() {
+    let mut timer = timer();
+    timer.start().unwrap();
+    assert_eq!(timer.split(), Ok(Event::Splitted));
+
+    timer.set_split_cooldown(span(60.0));
+    assert_eq!(timer.split(), Err(Error::SplitCooldown));
+    timer.undo_split().unwrap();
+    assert_eq!(timer.split(), Ok(Event::Splitted));
+}
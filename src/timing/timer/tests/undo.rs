@@ -0,0 +1,102 @@
+use super::timer;
+use crate::{
+    TimerPhase, TimingMethod,
+    event::{Error, Event},
+    util::tests_helper::{span, start_run},
+};
+
+#[test]
+fn splits_can_be_undone_and_redone() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    timer.set_game_time(span(2.0)).unwrap();
+    timer.split().unwrap();
+
+    assert_eq!(timer.undo(), Ok(Event::ActionUndone));
+    assert_eq!(timer.current_split_index(), Some(1));
+    assert_eq!(
+        timer.run().segment(1).split_time()[TimingMethod::GameTime],
+        None
+    );
+
+    assert_eq!(timer.redo(), Ok(Event::ActionRedone));
+    assert_eq!(timer.current_split_index(), Some(2));
+    assert_eq!(
+        timer.run().segment(1).split_time()[TimingMethod::GameTime],
+        Some(span(2.0))
+    );
+    assert_eq!(timer.redo(), Err(Error::NothingToRedo));
+}
+
+#[test]
+fn skips_and_pauses_can_be_undone() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.skip_split().unwrap();
+    timer.pause().unwrap();
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert_eq!(timer.current_split_index(), Some(1));
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_split_index(), Some(0));
+    assert_eq!(timer.undo(), Err(Error::NothingToUndo));
+}
+
+#[test]
+fn resets_can_be_undone() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.set_game_time(span(1.0)).unwrap();
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+    assert_eq!(timer.run().attempt_history().len(), 1);
+
+    timer.undo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert_eq!(timer.current_split_index(), Some(1));
+    assert!(timer.run().attempt_history().is_empty());
+    assert_eq!(
+        timer.run().segment(0).split_time()[TimingMethod::GameTime],
+        Some(span(1.0))
+    );
+}
+
+#[test]
+fn new_actions_clear_the_redo_stack() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.split().unwrap();
+    timer.undo().unwrap();
+    assert!(timer.can_redo());
+    timer.skip_split().unwrap();
+    assert!(!timer.can_redo());
+}
+
+#[test]
+fn starting_a_new_attempt_clears_the_history() {
+    let mut timer = timer();
+    start_run(&mut timer);
+    timer.split().unwrap();
+    timer.reset(false).unwrap();
+    start_run(&mut timer);
+    assert!(!timer.can_undo());
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut timer = timer();
+    timer.set_undo_limit(2);
+    start_run(&mut timer);
+    timer.pause().unwrap();
+    timer.resume().unwrap();
+    timer.pause().unwrap();
+
+    timer.undo().unwrap();
+    timer.undo().unwrap();
+    assert_eq!(timer.current_phase(), TimerPhase::Paused);
+    assert_eq!(timer.undo(), Err(Error::NothingToUndo));
+}
@@ -113,3 +113,39 @@ fn can_set_variable() {
             .is_permanent,
     );
 }
+
+#[test]
+fn variables_are_stored_in_the_attempt_history() {
+    let mut timer = create_timer(&["A", "B"]);
+    timer.start().unwrap();
+    timer.set_custom_variable("Deaths", "3");
+    timer.split().unwrap();
+    timer.set_custom_variable("Deaths", "5");
+    timer.reset(true).unwrap();
+
+    let attempt = &timer.run().attempt_history()[0];
+    assert_eq!(attempt.variable("Deaths"), Some("5"));
+    assert!(attempt.split_variables().is_empty());
+}
+
+#[test]
+fn split_variables_can_be_stored_in_the_attempt_history() {
+    let mut timer = create_timer(&["A", "B"]);
+    timer.set_records_split_variables(true);
+    timer.start().unwrap();
+    timer.set_custom_variable("Deaths", "3");
+    timer.split().unwrap();
+    timer.set_custom_variable("Deaths", "5");
+    timer.split().unwrap();
+    timer.reset(true).unwrap();
+
+    let attempt = &timer.run().attempt_history()[0];
+    assert_eq!(attempt.variable("Deaths"), Some("5"));
+    assert_eq!(
+        attempt.split_variables(),
+        [
+            (0, vec![("Deaths".into(), "3".into())]),
+            (1, vec![("Deaths".into(), "5".into())]),
+        ]
+    );
+}
@@ -0,0 +1,837 @@
+//! A JSON serializer that writes directly into a byte buffer. Unlike going
+//! through a generic [`std::io::Write`], there are no fallible writes and no
+//! intermediate strings, so serializing the state objects every frame doesn't
+//! allocate as long as the buffer is reused. Integers are formatted with
+//! `itoa` and floating point numbers with `zmij`, both on the stack. The
+//! output is the same as what [`serde_json`] produces.
+
+use crate::platform::prelude::*;
+use core::fmt::{self, Display, Write};
+use serde::ser::{self, Serialize};
+
+/// The error that occurs when a value can't be represented as JSON.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// Only strings, characters, booleans and integers can be used as the keys
+    /// of a map.
+    KeyMustBeAString,
+    /// A custom error reported by the value that is being serialized.
+    #[snafu(display("{message}"))]
+    Custom {
+        /// The message of the error.
+        message: String,
+    },
+}
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Serializes the value as JSON and appends it to the buffer provided. The
+/// buffer is not cleared, so it can be cleared and reused for each frame.
+#[inline]
+pub fn to_vec<T: Serialize + ?Sized>(value: &T, buf: &mut Vec<u8>) -> Result<(), Error> {
+    value.serialize(&mut Serializer { out: buf })
+}
+
+/// A JSON serializer that writes into a byte buffer.
+pub struct Serializer<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl<'a> Serializer<'a> {
+    /// Creates a new serializer that appends to the buffer provided.
+    #[inline]
+    pub const fn new(out: &'a mut Vec<u8>) -> Self {
+        Self { out }
+    }
+
+    #[inline]
+    fn write_str(&mut self, value: &str) {
+        self.out.push(b'"');
+        write_escaped(self.out, value);
+        self.out.push(b'"');
+    }
+
+    #[inline]
+    fn write_integer<I: itoa::Integer>(&mut self, value: I) {
+        self.out
+            .extend_from_slice(itoa::Buffer::new().format(value).as_bytes());
+    }
+
+    #[inline]
+    fn write_float<F: zmij::Float>(&mut self, value: F, is_finite: bool) {
+        if is_finite {
+            self.out
+                .extend_from_slice(zmij::Buffer::new().format_finite(value).as_bytes());
+        } else {
+            self.out.extend_from_slice(b"null");
+        }
+    }
+
+    #[inline]
+    fn begin_variant(&mut self, variant: &str) {
+        self.out.push(b'{');
+        self.write_str(variant);
+        self.out.push(b':');
+    }
+}
+
+/// Writes the string into the buffer, escaping everything JSON requires to be
+/// escaped. Unescaped runs of the string are copied in one go.
+#[inline]
+fn write_escaped(out: &mut Vec<u8>, value: &str) {
+    let bytes = value.as_bytes();
+    let mut start = 0;
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        let escape = ESCAPE[byte as usize];
+        if escape == 0 {
+            continue;
+        }
+
+        out.extend_from_slice(&bytes[start..i]);
+        match escape {
+            b'u' => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                out.extend_from_slice(&[
+                    b'\\',
+                    b'u',
+                    b'0',
+                    b'0',
+                    HEX[(byte >> 4) as usize],
+                    HEX[(byte & 0xF) as usize],
+                ]);
+            }
+            escape => out.extend_from_slice(&[b'\\', escape]),
+        }
+        start = i + 1;
+    }
+
+    out.extend_from_slice(&bytes[start..]);
+}
+
+/// Lookup table that maps each byte to the character it needs to be escaped
+/// with, `u` if it needs to be escaped as a unicode escape sequence, or 0 if it
+/// doesn't need to be escaped at all.
+static ESCAPE: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 0x20 {
+        table[i] = b'u';
+        i += 1;
+    }
+    table[0x08] = b'b';
+    table[0x09] = b't';
+    table[0x0A] = b'n';
+    table[0x0C] = b'f';
+    table[0x0D] = b'r';
+    table[b'"' as usize] = b'"';
+    table[b'\\' as usize] = b'\\';
+    table
+};
+
+/// Adapter that escapes everything written through [`fmt::Write`], so values
+/// implementing [`Display`] can be written as a string without formatting
+/// them into an intermediate string first.
+struct EscapingWriter<'a>(&'a mut Vec<u8>);
+
+impl Write for EscapingWriter<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        write_escaped(self.0, s);
+        Ok(())
+    }
+}
+
+impl<'b, 'a> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'b, 'a>;
+    type SerializeTuple = Compound<'b, 'a>;
+    type SerializeTupleStruct = Compound<'b, 'a>;
+    type SerializeTupleVariant = Compound<'b, 'a>;
+    type SerializeMap = Compound<'b, 'a>;
+    type SerializeStruct = Compound<'b, 'a>;
+    type SerializeStructVariant = Compound<'b, 'a>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out
+            .extend_from_slice(if v { b"true" } else { b"false" });
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.write_integer(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.write_float(v, v.is_finite());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.write_float(v, v.is_finite());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.write_str(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.write_str(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.out.push(b'[');
+        for (i, &byte) in v.iter().enumerate() {
+            if i != 0 {
+                self.out.push(b',');
+            }
+            self.write_integer(byte);
+        }
+        self.out.push(b']');
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.extend_from_slice(b"null");
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.write_str(variant);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.begin_variant(variant);
+        value.serialize(&mut *self)?;
+        self.out.push(b'}');
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'b, 'a>, Error> {
+        self.out.push(b'[');
+        Ok(Compound::new(self, b']', false))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.begin_variant(variant);
+        self.out.push(b'[');
+        Ok(Compound::new(self, b']', true))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'b, 'a>, Error> {
+        self.out.push(b'{');
+        Ok(Compound::new(self, b'}', false))
+    }
+
+    #[inline]
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.begin_variant(variant);
+        self.out.push(b'{');
+        Ok(Compound::new(self, b'}', true))
+    }
+
+    #[inline]
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.out.push(b'"');
+        let _ = write!(EscapingWriter(self.out), "{value}");
+        self.out.push(b'"');
+        Ok(())
+    }
+}
+
+/// The serializer for sequences, maps, structs and the variants containing
+/// them.
+pub struct Compound<'b, 'a> {
+    ser: &'b mut Serializer<'a>,
+    is_first: bool,
+    end: u8,
+    is_variant: bool,
+}
+
+impl<'b, 'a> Compound<'b, 'a> {
+    #[inline]
+    const fn new(ser: &'b mut Serializer<'a>, end: u8, is_variant: bool) -> Self {
+        Self {
+            ser,
+            is_first: true,
+            end,
+            is_variant,
+        }
+    }
+
+    #[inline]
+    fn separator(&mut self) {
+        if !self.is_first {
+            self.ser.out.push(b',');
+        }
+        self.is_first = false;
+    }
+
+    #[inline]
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.separator();
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.separator();
+        self.ser.write_str(key);
+        self.ser.out.push(b':');
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn finish(self) -> Result<(), Error> {
+        self.ser.out.push(self.end);
+        if self.is_variant {
+            self.ser.out.push(b'}');
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.separator();
+        key.serialize(MapKeySerializer(&mut *self.ser))?;
+        self.ser.out.push(b':');
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// Serializes the keys of maps. JSON only allows strings as keys, so all the
+/// other supported types are written as strings.
+struct MapKeySerializer<'b, 'a>(&'b mut Serializer<'a>);
+
+impl MapKeySerializer<'_, '_> {
+    #[inline]
+    fn quoted_integer<I: itoa::Integer>(self, value: I) -> Result<(), Error> {
+        self.0.out.push(b'"');
+        self.0.write_integer(value);
+        self.0.out.push(b'"');
+        Ok(())
+    }
+}
+
+impl ser::Serializer for MapKeySerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.0.write_str(if v { "true" } else { "false" });
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.quoted_integer(v)
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.0.write_str(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.0.write_str(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.0.write_str(variant);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::KeyMustBeAString)
+    }
+
+    #[inline]
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.0.collect_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HotkeyConfig, Layout, layout, run, settings::ImageCache, util::tests_helper::*};
+    use serde_json::Value;
+
+    fn roundtrips<T: Serialize + ?Sized>(value: &T) {
+        let mut buf = Vec::new();
+        to_vec(value, &mut buf).unwrap();
+        let ours: Value = serde_json::from_slice(&buf).unwrap();
+        let theirs: Value = serde_json::from_slice(&serde_json::to_vec(value).unwrap()).unwrap();
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    fn primitives() {
+        let mut buf = Vec::new();
+        to_vec(
+            &(1u8, -2i64, 0.5f32, 3.0f64, f64::NAN, true, None::<u8>),
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(buf, b"[1,-2,0.5,3.0,null,true,null]");
+
+        buf.clear();
+        to_vec("a\"b\\c\nd\u{1}é", &mut buf).unwrap();
+        assert_eq!(buf, "\"a\\\"b\\\\c\\nd\\u0001é\"".as_bytes());
+    }
+
+    #[test]
+    fn layout_state() {
+        let mut timer = create_timer(&["A", "B", "C"]);
+        run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+        start_run(&mut timer);
+        timer.split().unwrap();
+
+        let mut layout = Layout::default_layout();
+        let state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+        roundtrips(&state);
+        roundtrips(&layout.settings());
+    }
+
+    #[test]
+    fn editor_states() {
+        let mut image_cache = ImageCache::new();
+        let timer = create_timer(&["A", "B", "C"]);
+
+        let run_editor = run::Editor::new(timer.run().clone()).unwrap();
+        roundtrips(&run_editor.state(&mut image_cache));
+
+        let mut layout_editor = layout::Editor::new(Layout::default_layout()).unwrap();
+        roundtrips(&layout_editor.state(&mut image_cache));
+        roundtrips(&layout_editor.layout_state(&mut image_cache, &timer.snapshot()));
+
+        roundtrips(&HotkeyConfig::default());
+    }
+}
@@ -8,6 +8,8 @@ pub(crate) mod caseless;
 mod clear_vec;
 #[cfg(feature = "std")]
 pub(crate) mod image;
+pub mod json;
+pub mod msgpack;
 pub(crate) mod not_nan;
 pub mod ordered_map;
 mod populate_string;
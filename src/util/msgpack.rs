@@ -0,0 +1,1064 @@
+//! A [MessagePack](https://msgpack.org) serializer and deserializer working
+//! directly on byte buffers. MessagePack is a compact binary encoding with the
+//! same data model as JSON, so there are readily available decoders for
+//! basically every language. This makes it well suited for passing the state
+//! objects to renderers running in a different process, such as OBS plugins or
+//! overlays, as neither formatting nor parsing numbers and escaping strings is
+//! necessary.
+//!
+//! The values are encoded the same way as they are encoded as JSON: structs are
+//! maps with the names of the fields as their keys, unit variants are strings
+//! and all the other variants are maps with a single entry, the name of the
+//! variant mapped to its value. Unlike JSON, the encoding is not considered
+//! human readable, so types such as [`ImageId`](crate::settings::ImageId) are
+//! encoded as their raw bytes rather than as strings. Integers always use the
+//! smallest representation that fits them and floating point numbers keep their
+//! precision, so `f32` values are encoded as 32-bit floats.
+
+use crate::platform::prelude::*;
+use core::{
+    fmt::{self, Display, Write},
+    str,
+};
+use serde::{
+    de::{self, Deserialize, IntoDeserializer, Visitor},
+    ser::{self, Serialize},
+};
+
+/// The error that occurs when a value can't be encoded as or decoded from
+/// MessagePack.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// The number is too large to be represented by MessagePack.
+    NumberOutOfRange,
+    /// The string, byte array, sequence or map has more elements than
+    /// MessagePack can represent.
+    LengthOutOfRange,
+    /// The data ended before the value was fully decoded.
+    UnexpectedEnd,
+    /// The data contains a marker byte that is either not valid MessagePack or
+    /// refers to an extension type, which is not supported.
+    #[snafu(display("The marker byte {marker:#04x} is not supported."))]
+    InvalidMarker {
+        /// The marker byte that is not supported.
+        marker: u8,
+    },
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// There is more data after the value was fully decoded.
+    TrailingBytes,
+    /// The sequences, maps and enums are nested too deeply.
+    RecursionLimitExceeded,
+    /// A custom error reported by the value that is being encoded or decoded.
+    #[snafu(display("{message}"))]
+    Custom {
+        /// The message of the error.
+        message: String,
+    },
+}
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl de::Error for Error {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Serializes the value as MessagePack and appends it to the buffer provided.
+/// The buffer is not cleared, so it can be cleared and reused for each frame.
+#[inline]
+pub fn to_vec<T: Serialize + ?Sized>(value: &T, buf: &mut Vec<u8>) -> Result<(), Error> {
+    value.serialize(&mut Serializer { out: buf })
+}
+
+/// Deserializes a value from the MessagePack encoded data provided. The data
+/// needs to consist of exactly one value.
+pub fn from_slice<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, Error> {
+    let mut deserializer = Deserializer::new(data);
+    let value = T::deserialize(&mut deserializer)?;
+    if !deserializer.input.is_empty() {
+        return Err(Error::TrailingBytes);
+    }
+    Ok(value)
+}
+
+/// The kinds of values that are prefixed with their length.
+#[derive(Copy, Clone)]
+enum Kind {
+    Str,
+    Bin,
+    Array,
+    Map,
+}
+
+/// Encodes the marker and the length of a value of the kind provided. Returns
+/// the buffer and how many of its bytes are used.
+#[inline]
+fn header(kind: Kind, len: usize) -> Result<([u8; 5], usize), Error> {
+    let (fix, fix_limit, [marker8, marker16, marker32]) = match kind {
+        Kind::Str => (0xa0, 32, [0xd9, 0xda, 0xdb]),
+        Kind::Bin => (0, 0, [0xc4, 0xc5, 0xc6]),
+        Kind::Array => (0x90, 16, [0, 0xdc, 0xdd]),
+        Kind::Map => (0x80, 16, [0, 0xde, 0xdf]),
+    };
+
+    let mut buf = [0; 5];
+    let used = if len < fix_limit {
+        buf[0] = fix | len as u8;
+        1
+    } else if marker8 != 0 && len <= u8::MAX as usize {
+        buf[0] = marker8;
+        buf[1] = len as u8;
+        2
+    } else if len <= u16::MAX as usize {
+        buf[0] = marker16;
+        buf[1..3].copy_from_slice(&(len as u16).to_be_bytes());
+        3
+    } else {
+        let len = u32::try_from(len).map_err(|_| Error::LengthOutOfRange)?;
+        buf[0] = marker32;
+        buf[1..5].copy_from_slice(&len.to_be_bytes());
+        5
+    };
+
+    Ok((buf, used))
+}
+
+/// A MessagePack serializer that writes into a byte buffer.
+pub struct Serializer<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl<'a> Serializer<'a> {
+    /// Creates a new serializer that appends to the buffer provided.
+    #[inline]
+    pub const fn new(out: &'a mut Vec<u8>) -> Self {
+        Self { out }
+    }
+
+    #[inline]
+    fn write_header(&mut self, kind: Kind, len: usize) -> Result<(), Error> {
+        let (buf, used) = header(kind, len)?;
+        self.out.extend_from_slice(&buf[..used]);
+        Ok(())
+    }
+
+    /// Inserts the header in front of the value that starts at the position
+    /// provided. This is used for values whose length is only known after
+    /// they are written.
+    #[inline]
+    fn insert_header(&mut self, start: usize, kind: Kind, len: usize) -> Result<(), Error> {
+        let (buf, used) = header(kind, len)?;
+        self.out.extend_from_slice(&buf[..used]);
+        self.out[start..].rotate_right(used);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_str(&mut self, value: &str) -> Result<(), Error> {
+        self.write_header(Kind::Str, value.len())?;
+        self.out.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+
+    #[inline]
+    fn write_uint(&mut self, value: u64) {
+        if value < 0x80 {
+            self.out.push(value as u8);
+        } else if let Ok(value) = u8::try_from(value) {
+            self.out.extend_from_slice(&[0xcc, value]);
+        } else if let Ok(value) = u16::try_from(value) {
+            self.out.push(0xcd);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        } else if let Ok(value) = u32::try_from(value) {
+            self.out.push(0xce);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        } else {
+            self.out.push(0xcf);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    #[inline]
+    fn write_int(&mut self, value: i64) {
+        if value >= 0 {
+            self.write_uint(value as u64);
+        } else if value >= -32 {
+            self.out.push(value as u8);
+        } else if let Ok(value) = i8::try_from(value) {
+            self.out.extend_from_slice(&[0xd0, value as u8]);
+        } else if let Ok(value) = i16::try_from(value) {
+            self.out.push(0xd1);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        } else if let Ok(value) = i32::try_from(value) {
+            self.out.push(0xd2);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        } else {
+            self.out.push(0xd3);
+            self.out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    #[inline]
+    fn begin_variant(&mut self, variant: &str) -> Result<(), Error> {
+        self.out.push(0x81);
+        self.write_str(variant)
+    }
+}
+
+impl<'b, 'a> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'b, 'a>;
+    type SerializeTuple = Compound<'b, 'a>;
+    type SerializeTupleStruct = Compound<'b, 'a>;
+    type SerializeTupleVariant = Compound<'b, 'a>;
+    type SerializeMap = Compound<'b, 'a>;
+    type SerializeStruct = Compound<'b, 'a>;
+    type SerializeStructVariant = Compound<'b, 'a>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push(if v { 0xc3 } else { 0xc2 });
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.write_int(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.write_int(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.write_int(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.write_int(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        if let Ok(v) = i64::try_from(v) {
+            self.write_int(v);
+        } else {
+            self.write_uint(u64::try_from(v).map_err(|_| Error::NumberOutOfRange)?);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.write_uint(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.write_uint(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.write_uint(v.into());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.write_uint(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.write_uint(u64::try_from(v).map_err(|_| Error::NumberOutOfRange)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.out.push(0xca);
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.out.push(0xcb);
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.write_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.write_str(v)
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.write_header(Kind::Bin, v.len())?;
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push(0xc0);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.write_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.begin_variant(variant)?;
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'b, 'a>, Error> {
+        Compound::new(self, Kind::Array, len)
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.begin_variant(variant)?;
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'b, 'a>, Error> {
+        Compound::new(self, Kind::Map, len)
+    }
+
+    #[inline]
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'b, 'a>, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'b, 'a>, Error> {
+        self.begin_variant(variant)?;
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        let start = self.out.len();
+        let _ = write!(ByteWriter(self.out), "{value}");
+        let len = self.out.len() - start;
+        self.insert_header(start, Kind::Str, len)
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Adapter that allows values implementing [`Display`] to be written directly
+/// into the buffer.
+struct ByteWriter<'a>(&'a mut Vec<u8>);
+
+impl Write for ByteWriter<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// The serializer for sequences, maps, structs and the variants containing
+/// them. If the number of elements is not known upfront, they are counted and
+/// the header is inserted once all of them are written.
+pub struct Compound<'b, 'a> {
+    ser: &'b mut Serializer<'a>,
+    kind: Kind,
+    unknown_len: Option<(usize, usize)>,
+}
+
+impl<'b, 'a> Compound<'b, 'a> {
+    #[inline]
+    fn new(ser: &'b mut Serializer<'a>, kind: Kind, len: Option<usize>) -> Result<Self, Error> {
+        let unknown_len = match len {
+            Some(len) => {
+                ser.write_header(kind, len)?;
+                None
+            }
+            None => Some((ser.out.len(), 0)),
+        };
+        Ok(Self {
+            ser,
+            kind,
+            unknown_len,
+        })
+    }
+
+    #[inline]
+    const fn count(&mut self) {
+        if let Some((_, count)) = &mut self.unknown_len {
+            *count += 1;
+        }
+    }
+
+    #[inline]
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.count();
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.count();
+        self.ser.write_str(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn finish(self) -> Result<(), Error> {
+        if let Some((start, count)) = self.unknown_len {
+            self.ser.insert_header(start, self.kind, count)?;
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.element(key)
+    }
+
+    #[inline]
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.ser)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// The maximum depth sequences, maps and enums can be nested in when decoding.
+const RECURSION_LIMIT: u8 = 128;
+
+/// A MessagePack deserializer that reads from a byte slice. Strings and byte
+/// arrays are borrowed from the slice whenever possible.
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    remaining_depth: u8,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a new deserializer that reads from the data provided.
+    #[inline]
+    pub const fn new(input: &'de [u8]) -> Self {
+        Self {
+            input,
+            remaining_depth: RECURSION_LIMIT,
+        }
+    }
+
+    /// Decodes a nested value, making sure that the values aren't nested too
+    /// deeply, so malicious data can't overflow the stack.
+    #[inline]
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        self.remaining_depth = self
+            .remaining_depth
+            .checked_sub(1)
+            .ok_or(Error::RecursionLimitExceeded)?;
+        let result = f(self);
+        self.remaining_depth += 1;
+        result
+    }
+
+    #[inline]
+    const fn take(&mut self, len: usize) -> Result<&'de [u8], Error> {
+        if len > self.input.len() {
+            return Err(Error::UnexpectedEnd);
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    #[inline]
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    #[inline]
+    fn peek(&self) -> Result<u8, Error> {
+        self.input.first().copied().ok_or(Error::UnexpectedEnd)
+    }
+
+    #[inline]
+    fn byte(&mut self) -> Result<u8, Error> {
+        let [byte] = self.take_array()?;
+        Ok(byte)
+    }
+
+    #[inline]
+    fn len8(&mut self) -> Result<usize, Error> {
+        Ok(self.byte()? as usize)
+    }
+
+    #[inline]
+    fn len16(&mut self) -> Result<usize, Error> {
+        Ok(u16::from_be_bytes(self.take_array()?) as usize)
+    }
+
+    #[inline]
+    fn len32(&mut self) -> Result<usize, Error> {
+        usize::try_from(u32::from_be_bytes(self.take_array()?)).map_err(|_| Error::LengthOutOfRange)
+    }
+
+    #[inline]
+    fn str(&mut self, len: usize) -> Result<&'de str, Error> {
+        str::from_utf8(self.take(len)?).map_err(|_| Error::InvalidUtf8)
+    }
+
+    /// Reads the length of a string if the next value is one.
+    #[inline]
+    fn str_len(&mut self) -> Result<Option<usize>, Error> {
+        Ok(Some(match self.peek()? {
+            marker @ 0xa0..=0xbf => {
+                self.byte()?;
+                (marker & 0x1f) as usize
+            }
+            0xd9 => {
+                self.byte()?;
+                self.len8()?
+            }
+            0xda => {
+                self.byte()?;
+                self.len16()?
+            }
+            0xdb => {
+                self.byte()?;
+                self.len32()?
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Reads the length of a map if the next value is one.
+    #[inline]
+    fn map_len(&mut self) -> Result<Option<usize>, Error> {
+        Ok(Some(match self.peek()? {
+            marker @ 0x80..=0x8f => {
+                self.byte()?;
+                (marker & 0x0f) as usize
+            }
+            0xde => {
+                self.byte()?;
+                self.len16()?
+            }
+            0xdf => {
+                self.byte()?;
+                self.len32()?
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    #[inline]
+    fn visit_seq<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.nested(|de| {
+            let mut access = Access { de, remaining: len };
+            let value = visitor.visit_seq(&mut access)?;
+            if access.remaining != 0 {
+                return Err(de::Error::invalid_length(len, &"fewer elements"));
+            }
+            Ok(value)
+        })
+    }
+
+    #[inline]
+    fn visit_map<V: Visitor<'de>>(&mut self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.nested(|de| {
+            let mut access = Access { de, remaining: len };
+            let value = visitor.visit_map(&mut access)?;
+            if access.remaining != 0 {
+                return Err(de::Error::invalid_length(len, &"fewer entries"));
+            }
+            Ok(value)
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if let Some(len) = self.str_len()? {
+            return visitor.visit_borrowed_str(self.str(len)?);
+        }
+        if let Some(len) = self.map_len()? {
+            return self.visit_map(len, visitor);
+        }
+
+        match self.byte()? {
+            marker @ 0x00..=0x7f => visitor.visit_u8(marker),
+            marker @ 0x90..=0x9f => self.visit_seq((marker & 0x0f) as usize, visitor),
+            0xc0 => visitor.visit_unit(),
+            0xc2 => visitor.visit_bool(false),
+            0xc3 => visitor.visit_bool(true),
+            0xc4 => {
+                let len = self.len8()?;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            0xc5 => {
+                let len = self.len16()?;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            0xc6 => {
+                let len = self.len32()?;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            0xca => visitor.visit_f32(f32::from_be_bytes(self.take_array()?)),
+            0xcb => visitor.visit_f64(f64::from_be_bytes(self.take_array()?)),
+            0xcc => visitor.visit_u8(self.byte()?),
+            0xcd => visitor.visit_u16(u16::from_be_bytes(self.take_array()?)),
+            0xce => visitor.visit_u32(u32::from_be_bytes(self.take_array()?)),
+            0xcf => visitor.visit_u64(u64::from_be_bytes(self.take_array()?)),
+            0xd0 => visitor.visit_i8(i8::from_be_bytes(self.take_array()?)),
+            0xd1 => visitor.visit_i16(i16::from_be_bytes(self.take_array()?)),
+            0xd2 => visitor.visit_i32(i32::from_be_bytes(self.take_array()?)),
+            0xd3 => visitor.visit_i64(i64::from_be_bytes(self.take_array()?)),
+            0xdc => {
+                let len = self.len16()?;
+                self.visit_seq(len, visitor)
+            }
+            0xdd => {
+                let len = self.len32()?;
+                self.visit_seq(len, visitor)
+            }
+            marker @ 0xe0..=0xff => visitor.visit_i8(marker as i8),
+            marker => Err(Error::InvalidMarker { marker }),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.peek()? == 0xc0 {
+            self.byte()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if let Some(len) = self.str_len()? {
+            return visitor.visit_enum(self.str(len)?.into_deserializer());
+        }
+        match self.map_len()? {
+            Some(1) => self.nested(|de| visitor.visit_enum(VariantAccess { de })),
+            Some(len) => Err(de::Error::invalid_length(len, &"a single variant")),
+            None => Err(de::Error::custom("expected a string or a map for an enum")),
+        }
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Provides access to the elements of sequences and the entries of maps.
+struct Access<'b, 'de> {
+    de: &'b mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    #[inline]
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Provides access to an enum variant that is encoded as a map with a single
+/// entry.
+struct VariantAccess<'b, 'de> {
+    de: &'b mut Deserializer<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.de)
+    }
+
+    #[inline]
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    #[inline]
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Layout,
+        layout::{BinaryError, LayoutState},
+        settings::ImageCache,
+        util::tests_helper::*,
+    };
+
+    #[test]
+    fn primitives() {
+        let mut buf = Vec::new();
+        to_vec(
+            &(1u8, -2i64, 300u16, 0.5f32, true, None::<u8>, "ab"),
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            buf,
+            [
+                0x97, 0x01, 0xfe, 0xcd, 0x01, 0x2c, 0xca, 0x3f, 0x00, 0x00, 0x00, 0xc3, 0xc0, 0xa2,
+                b'a', b'b',
+            ]
+        );
+
+        let decoded: (u8, i64, u16, f32, bool, Option<u8>, String) = from_slice(&buf).unwrap();
+        assert_eq!(decoded, (1, -2, 300, 0.5, true, None, String::from("ab")));
+    }
+
+    #[test]
+    fn unknown_lengths() {
+        let mut buf = Vec::new();
+        to_vec(&format_args!("{}", "a".repeat(40)), &mut buf).unwrap();
+        assert_eq!(&buf[..2], [0xd9, 40]);
+        let decoded: String = from_slice(&buf).unwrap();
+        assert_eq!(decoded, "a".repeat(40));
+    }
+
+    #[test]
+    fn layout_state() {
+        let mut timer = create_timer(&["A", "B", "C"]);
+        run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+        start_run(&mut timer);
+        timer.split().unwrap();
+
+        let mut layout = Layout::default_layout();
+        let state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+
+        let mut buf = Vec::new();
+        state.write_binary(&mut buf).unwrap();
+        let decoded = LayoutState::from_binary(&buf).unwrap();
+
+        let mut reencoded = Vec::new();
+        decoded.write_binary(&mut reencoded).unwrap();
+        assert_eq!(buf, reencoded);
+
+        buf[1] = 0;
+        assert!(matches!(
+            LayoutState::from_binary(&buf),
+            Err(BinaryError::UnsupportedVersion { version: 0 }),
+        ));
+    }
+
+    #[test]
+    fn deeply_nested_values() {
+        let mut buf = vec![0x92, 0x02];
+        buf.extend([0x91; 100_000]);
+        assert!(matches!(
+            LayoutState::from_binary(&buf),
+            Err(BinaryError::Decode {
+                source: Error::RecursionLimitExceeded,
+            }),
+        ));
+        assert!(matches!(
+            from_slice::<de::IgnoredAny>(&buf),
+            Err(Error::RecursionLimitExceeded),
+        ));
+
+        let mut buf = vec![0x91; 100];
+        buf.push(0x01);
+        from_slice::<de::IgnoredAny>(&buf).unwrap();
+    }
+}
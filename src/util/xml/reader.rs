@@ -34,6 +34,12 @@ impl<'a> Reader<'a> {
         }
     }
 
+    /// Returns the byte offset of the reader's current position within the
+    /// source it was created with.
+    pub const fn offset_in(&self, source: &str) -> usize {
+        source.len() - self.source.len()
+    }
+
     pub fn read_event(&mut self) -> Option<Event<'a>> {
         match self.state {
             TagState::Closed => Some(self.read_until_open()),
@@ -0,0 +1,39 @@
+use livesplit_core::settings::{Color, Gradient};
+
+fn red() -> Color {
+    Color::rgba(1.0, 0.0, 0.0, 1.0)
+}
+
+fn blue() -> Color {
+    Color::rgba(0.0, 0.0, 1.0, 1.0)
+}
+
+#[test]
+fn static_gradients_stay_the_same() {
+    let gradient = Gradient::Vertical(red(), blue());
+    assert!(!gradient.is_animated());
+    assert_eq!(gradient.at(12.5), gradient);
+}
+
+#[test]
+fn hue_cycling_shifts_the_hues() {
+    let gradient = Gradient::HueCycling(red(), blue(), 2.0);
+    assert!(gradient.is_animated());
+    assert_eq!(gradient.at(0.0), Gradient::Vertical(red(), blue()));
+    let Gradient::Vertical(top, bottom) = gradient.at(1.0) else {
+        panic!("hue cycling should produce a vertical gradient");
+    };
+    assert_eq!(top.to_rgba8(), [0, 255, 255, 255]);
+    assert_eq!(bottom.to_rgba8(), [255, 255, 0, 255]);
+}
+
+#[test]
+fn rotating_gradients_turn_into_angled_gradients() {
+    let gradient = Gradient::Rotating(red(), blue(), 4.0);
+    assert_eq!(gradient.at(1.0), Gradient::Angled(red(), blue(), 90.0));
+    assert_eq!(gradient.at(5.0), Gradient::Angled(red(), blue(), 90.0));
+    assert_eq!(
+        Gradient::Rotating(red(), blue(), 0.0).at(1.0),
+        Gradient::Angled(red(), blue(), 0.0),
+    );
+}
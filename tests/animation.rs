@@ -0,0 +1,133 @@
+#[path = "../src/util/tests_helper.rs"]
+mod tests_helper;
+
+use livesplit_core::{
+    Layout, Run, Segment, TimeSpan, Timer, TimingMethod,
+    component::{splits, timer},
+    layout::{Animator, ComponentState, LayoutState},
+    settings::ImageCache,
+};
+
+fn layout() -> Layout {
+    let mut splits = splits::Component::new();
+    let settings = splits.settings_mut();
+    settings.visual_split_count = 3;
+    settings.split_preview_count = 1;
+    settings.always_show_last_split = false;
+    settings.fill_with_blank_space = false;
+
+    let mut layout = Layout::new();
+    layout.push(splits);
+    layout.push(timer::Component::new());
+    layout
+}
+
+fn state_at(layout: &mut Layout, timer: &Timer, time: f64) -> LayoutState {
+    let mut state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+    state.animation_time = time;
+    state
+}
+
+fn splits(state: &LayoutState) -> Vec<(usize, f32, f32)> {
+    let ComponentState::Splits(splits) = &state.components[0] else {
+        panic!("the first component should be the splits");
+    };
+    splits
+        .splits
+        .iter()
+        .map(|split| (split.index, split.opacity, split.offset))
+        .collect()
+}
+
+#[test]
+fn the_first_state_is_not_modified() {
+    let timer = tests_helper::create_timer(&["A", "B", "C", "D", "E"]);
+    let mut layout = layout();
+    let mut animator = Animator::new(1.0);
+
+    let mut state = state_at(&mut layout, &timer, 0.0);
+    animator.animate(&mut state);
+    assert_eq!(
+        splits(&state),
+        [(0, 1.0, 0.0), (1, 1.0, 0.0), (2, 1.0, 0.0)],
+    );
+}
+
+#[test]
+fn scrolling_fades_and_moves_the_splits() {
+    let mut timer = tests_helper::create_timer(&["A", "B", "C", "D", "E"]);
+    let mut layout = layout();
+    let mut animator = Animator::new(1.0);
+
+    let mut state = state_at(&mut layout, &timer, 0.0);
+    animator.animate(&mut state);
+
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(&mut timer, &[Some(1.0), Some(2.0)]);
+
+    // Right when the list scrolls, everything is still where it was, with the
+    // segment that appears not being visible yet.
+    let mut state = state_at(&mut layout, &timer, 2.0);
+    animator.animate(&mut state);
+    assert_eq!(
+        splits(&state),
+        [(0, 1.0, 0.0), (1, 1.0, 0.0), (2, 1.0, 0.0), (3, 0.0, 0.0)],
+    );
+
+    let mut state = state_at(&mut layout, &timer, 2.5);
+    animator.animate(&mut state);
+    let splits_midway = splits(&state);
+    assert_eq!(splits_midway.len(), 4);
+    let (index, opacity, offset) = splits_midway[0];
+    assert_eq!(index, 0);
+    assert!(opacity > 0.0 && opacity < 1.0);
+    assert!(offset < 0.0 && offset > -1.0);
+    let (index, opacity, offset) = splits_midway[3];
+    assert_eq!(index, 3);
+    assert!((opacity + splits_midway[0].1 - 1.0).abs() < 1e-6);
+    assert!((offset - splits_midway[0].2).abs() < 1e-6);
+
+    // Once the transition is over, the segment that scrolled out of view is
+    // gone.
+    let mut state = state_at(&mut layout, &timer, 3.0);
+    animator.animate(&mut state);
+    assert_eq!(
+        splits(&state),
+        [(1, 1.0, 0.0), (2, 1.0, 0.0), (3, 1.0, 0.0)],
+    );
+}
+
+#[test]
+fn colors_blend_into_their_new_values() {
+    let mut timer = tests_helper::create_timer(&["A", "B"]);
+    let mut layout = layout();
+    let mut animator = Animator::new(1.0);
+
+    let timer_color = |state: &LayoutState| {
+        let ComponentState::Timer(timer) = &state.components[1] else {
+            panic!("the second component should be the timer");
+        };
+        timer.top_color
+    };
+
+    let mut state = state_at(&mut layout, &timer, 0.0);
+    animator.animate(&mut state);
+    let before = timer_color(&state);
+
+    tests_helper::start_run(&mut timer);
+    let mut state = state_at(&mut layout, &timer, 1.0);
+    let after = timer_color(&state);
+    assert_ne!(before, after);
+    animator.animate(&mut state);
+    assert_eq!(timer_color(&state), before);
+
+    let mut state = state_at(&mut layout, &timer, 1.5);
+    animator.animate(&mut state);
+    let midway = timer_color(&state);
+    assert_ne!(midway, before);
+    assert_ne!(midway, after);
+
+    let mut state = state_at(&mut layout, &timer, 2.0);
+    animator.animate(&mut state);
+    assert_eq!(timer_color(&state), after);
+}
@@ -0,0 +1,129 @@
+use livesplit_core::{
+    Layout, LayoutEditor, Run, Segment, Timer,
+    component::{separator, text},
+    layout::{ComponentAppearance, LayoutSettings},
+    settings::{BlendMode, ComponentBackgroundImage, Image, ImageCache, ImageFit, Value},
+};
+
+fn layout() -> Layout {
+    let mut layout = Layout::new();
+    layout.push(text::Component::new());
+    layout.push(separator::Component::new());
+    layout
+}
+
+#[test]
+fn appearances_default_to_normal() {
+    let layout = layout();
+    assert!(layout.component_appearance(0).is_default());
+    assert!(layout.component_appearance(1).is_default());
+    assert!(layout.settings().component_appearances.is_empty());
+}
+
+#[test]
+fn editor_keeps_appearances_with_their_components() {
+    let image_cache = ImageCache::new();
+    let mut editor = LayoutEditor::new(layout()).unwrap();
+    editor
+        .set_component_appearance_value(0, Value::UInt(40), &image_cache)
+        .unwrap();
+    editor
+        .set_component_appearance_value(1, BlendMode::Screen.into(), &image_cache)
+        .unwrap();
+
+    editor.duplicate_component();
+    editor.move_component_down();
+
+    let layout = editor.close();
+    let appearance = ComponentAppearance {
+        opacity: 0.4,
+        blend_mode: BlendMode::Screen,
+        background_image: None,
+    };
+    assert_eq!(layout.component_appearance(0), appearance);
+    assert!(layout.component_appearance(1).is_default());
+    assert_eq!(layout.component_appearance(2), appearance);
+}
+
+#[test]
+fn layout_keeps_appearances_with_their_components() {
+    let mut settings = layout().settings();
+    // Settings may only contain the appearances of the first components.
+    settings.component_appearances = vec![ComponentAppearance {
+        opacity: 0.5,
+        ..Default::default()
+    }];
+    let mut layout = Layout::from_settings(settings);
+
+    layout.insert(0, separator::Component::new());
+    layout.swap(0, 2);
+    assert_eq!(layout.component_appearance(1).opacity, 0.5);
+    assert!(layout.component_appearance(2).is_default());
+
+    layout.remove(0);
+    assert_eq!(layout.component_appearance(0).opacity, 0.5);
+    assert!(layout.component_appearance(1).is_default());
+    assert_eq!(layout.settings().component_appearances.len(), 2);
+}
+
+#[test]
+fn appearances_round_trip_through_settings() {
+    let mut layout = layout();
+    layout.component_appearance_mut(1).blend_mode = BlendMode::Multiply;
+
+    let json = serde_json::to_string(&layout.settings()).unwrap();
+    let settings: LayoutSettings = serde_json::from_str(&json).unwrap();
+    let layout = Layout::from_settings(settings);
+
+    assert!(layout.component_appearance(0).is_default());
+    assert_eq!(
+        layout.component_appearance(1).blend_mode,
+        BlendMode::Multiply
+    );
+}
+
+#[test]
+fn background_images_are_cached_for_the_state() {
+    let image = Image::new([1, 2, 3].as_slice().into(), Image::LARGE);
+    let mut image_cache = ImageCache::new();
+    image_cache.cache(image.id(), || image.clone());
+
+    let mut editor = LayoutEditor::new(layout()).unwrap();
+    let background = ComponentBackgroundImage {
+        image: *image.id(),
+        fit: ImageFit::Tile,
+    };
+    editor
+        .set_component_appearance_value(2, Some(background).into(), &image_cache)
+        .unwrap();
+    let mut layout = editor.close();
+
+    assert_eq!(
+        layout.component_appearance(0).background_image,
+        Some(ComponentBackgroundImage {
+            image: image.clone(),
+            fit: ImageFit::Tile,
+        }),
+    );
+
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    let timer = Timer::new(run).unwrap();
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    assert_eq!(
+        state.component_appearances[0].background_image,
+        Some(background)
+    );
+    assert_eq!(state.component_appearances[1].background_image, None);
+    assert!(image_cache.lookup(image.id()).is_some());
+
+    let json = serde_json::to_string(&layout.settings()).unwrap();
+    let settings: LayoutSettings = serde_json::from_str(&json).unwrap();
+    let layout = Layout::from_settings(settings);
+    assert_eq!(
+        layout.component_appearance(0).background_image.unwrap().fit,
+        ImageFit::Tile
+    );
+}
@@ -1,6 +1,10 @@
 use livesplit_core::{
-    run::{parser, saver},
-    Run, Segment,
+    Run, Segment, TimeSpan, TimingMethod,
+    comparison::{
+        AverageSegments,
+        last_attempts::{LastAttempts, Statistic},
+    },
+    run::{GoldRules, parser, saver},
 };
 
 #[test]
@@ -29,3 +33,125 @@ fn escaping_works_for_auto_splitter_settings() {
     run = parser::livesplit::parse(&buf).unwrap();
     assert_eq!(run.auto_splitter_settings(), "<Hi>A &lt; B</Hi>");
 }
+
+#[test]
+fn gold_rules_survive_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.set_gold_rules(GoldRules {
+        max_game_time_pause: Some(TimeSpan::from_seconds(5.0)),
+        min_segment_time_after_undo: None,
+    });
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(
+        run.gold_rules().max_game_time_pause,
+        Some(TimeSpan::from_seconds(5.0)),
+    );
+    assert_eq!(run.gold_rules().min_segment_time_after_undo, None);
+}
+
+#[test]
+fn last_attempts_comparison_survives_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.set_last_attempts_comparison(LastAttempts::new(5, Statistic::Median));
+    run.set_last_attempts_comparison_enabled(true);
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(
+        *run.last_attempts_comparison(),
+        LastAttempts::new(5, Statistic::Median),
+    );
+    assert!(run.is_last_attempts_comparison_enabled());
+}
+
+#[test]
+fn average_segments_weight_survives_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.set_average_segments_comparison(AverageSegments::new(0.5));
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(run.average_segments_comparison().weight(), 0.5);
+}
+
+#[test]
+fn timing_method_bindings_survive_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.set_bound_timing_method("Game Time PB", Some(TimingMethod::GameTime));
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(
+        run.bound_timing_method("Game Time PB"),
+        Some(TimingMethod::GameTime),
+    );
+}
+
+#[test]
+fn frame_rate_survives_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.set_frame_rate(Some(59.94));
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(run.frame_rate(), Some(59.94));
+}
+
+#[test]
+fn attempt_variables_survive_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.push_segment(Segment::new("B"));
+    run.add_attempt(Default::default(), None, None, None);
+    run.add_attempt(Default::default(), None, None, None);
+    let attempt = &mut run.attempt_history_mut()[0];
+    attempt.set_variables(vec![("Deaths <3".into(), "12".into())]);
+    attempt.set_split_variables(1, vec![("Deaths <3".into(), "7".into())]);
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    let attempts = run.attempt_history();
+    assert_eq!(attempts[0].variable("Deaths <3"), Some("12"));
+    assert_eq!(
+        attempts[0].split_variables(),
+        [(1, vec![("Deaths <3".to_owned(), "7".to_owned())])]
+    );
+    assert!(attempts[1].variables().is_empty());
+    assert!(attempts[1].split_variables().is_empty());
+}
+
+#[test]
+fn attempt_tags_survive_saving() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    run.add_attempt(Default::default(), None, None, None);
+    run.attempt_history_mut()[0].add_tag("Marathon & Co");
+    run.set_attempt_tag_excluded("Marathon & Co", true);
+
+    let mut buf = String::new();
+    saver::livesplit::save_run(&run, &mut buf).unwrap();
+
+    run = parser::livesplit::parse(&buf).unwrap();
+    assert_eq!(run.attempt_history()[0].tags(), ["Marathon & Co"]);
+    assert_eq!(run.excluded_attempt_tags(), ["Marathon & Co"]);
+    assert!(run.is_attempt_excluded(1));
+}
@@ -0,0 +1,320 @@
+use livesplit_core::{
+    HotkeyConfig, HotkeyConflict, HotkeyProfile, HotkeyScope, ProfileBindings,
+    hotkey::{Binding, KeyCode, MidiMessage, Modifiers, Trigger},
+    settings::Value,
+};
+
+fn ctrl_k_then(key_code: KeyCode) -> Binding {
+    Binding::sequence(&[
+        KeyCode::KeyK.with_modifiers(Modifiers::CONTROL),
+        key_code.into(),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn sequences_are_serialized_as_strings() {
+    let config = HotkeyConfig {
+        reset: Some(ctrl_k_then(KeyCode::KeyR)),
+        skip: Some(
+            KeyCode::KeyS
+                .with_modifiers(Modifiers::CONTROL | Modifiers::SHIFT)
+                .into(),
+        ),
+        ..Default::default()
+    };
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""split":"Numpad1""#));
+    assert!(json.contains(r#""reset":"Ctrl + KeyK, KeyR""#));
+    assert!(json.contains(r#""skip":"Ctrl + Shift + KeyS""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+}
+
+#[test]
+fn sequences_conflict_with_their_beginning() {
+    let mut config = HotkeyConfig::default();
+    config
+        .set_value(1, Value::Hotkey(Some(ctrl_k_then(KeyCode::KeyR))))
+        .unwrap();
+    config
+        .set_value(3, Value::Hotkey(Some(ctrl_k_then(KeyCode::KeyS))))
+        .unwrap();
+
+    assert!(
+        config
+            .set_value(
+                2,
+                Value::Hotkey(Some(
+                    KeyCode::KeyK.with_modifiers(Modifiers::CONTROL).into()
+                ))
+            )
+            .is_err()
+    );
+    assert!(
+        config
+            .set_value(2, Value::String("Ctrl + KeyK, KeyR, KeyU".into()))
+            .is_err()
+    );
+    config
+        .set_value(2, Value::String("KeyR, Ctrl + KeyK".into()))
+        .unwrap();
+    assert_eq!(
+        config.undo.unwrap().steps().collect::<Vec<_>>(),
+        [
+            KeyCode::KeyR.into(),
+            KeyCode::KeyK.with_modifiers(Modifiers::CONTROL)
+        ],
+    );
+}
+
+#[test]
+fn triggers_are_part_of_the_binding() {
+    let config = HotkeyConfig {
+        reset: Some(Binding::from(KeyCode::Numpad3).with_trigger(Trigger::Hold { millis: 1000 })),
+        pause: Some(Binding::from(KeyCode::Numpad5).with_trigger(Trigger::DEFAULT_DOUBLE_TAP)),
+        split: Some(Binding::from(KeyCode::Numpad1).with_trigger(Trigger::Release)),
+        ..Default::default()
+    };
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""reset":"Numpad3 (Hold 1000 ms)""#));
+    assert!(json.contains(r#""pause":"Numpad5 (Double Tap 300 ms)""#));
+    assert!(json.contains(r#""split":"Numpad1 (Release)""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+}
+
+#[test]
+fn held_hotkeys_conflict_with_pressed_ones() {
+    let mut config = HotkeyConfig::default();
+    config
+        .set_value(1, Value::String("Numpad3 (Hold 500 ms)".into()))
+        .unwrap();
+    assert!(
+        config
+            .set_value(0, Value::String("Numpad3".into()))
+            .is_err()
+    );
+
+    // Double tapping a hotkey starts with a single press of it.
+    assert!(
+        config
+            .set_value(0, Value::String("Numpad5 (Double Tap 300 ms)".into()))
+            .is_err()
+    );
+    config
+        .set_value(0, Value::String("Numpad1 (Double Tap 300 ms)".into()))
+        .unwrap();
+}
+
+#[test]
+fn gamepad_buttons_are_serialized_like_keys() {
+    let config = HotkeyConfig {
+        split: Some(KeyCode::Gamepad0.into()),
+        reset: Some(
+            Binding::sequence(&[KeyCode::Gamepad8.into(), KeyCode::Gamepad9.into()]).unwrap(),
+        ),
+        ..Default::default()
+    };
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""split":"Gamepad0""#));
+    assert!(json.contains(r#""reset":"Gamepad8, Gamepad9""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+}
+
+#[test]
+fn midi_triggers_are_stored_with_the_hotkeys() {
+    let mut config = HotkeyConfig::default();
+    config
+        .set_value(13, Value::OptionalString(Some("Pedal".into())))
+        .unwrap();
+    config
+        .set_value(14, Value::String("CC 64 Channel 1".into()))
+        .unwrap();
+    assert!(
+        config
+            .set_value(15, Value::String("CC 64 Channel 1".into()))
+            .is_err()
+    );
+    config
+        .set_value(
+            15,
+            Value::MidiMessage(Some(MidiMessage::Note {
+                channel: 9,
+                note: 36,
+            })),
+        )
+        .unwrap();
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""midi_device":"Pedal""#));
+    assert!(json.contains(r#""split":"CC 64 Channel 1""#));
+    assert!(json.contains(r#""reset":"Note 36 Channel 10""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+}
+
+#[test]
+fn profiles_are_stored_with_the_hotkeys() {
+    let mut config = HotkeyConfig::default();
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    assert!(!String::from_utf8(json).unwrap().contains("profiles"));
+
+    config.profiles.push(HotkeyProfile {
+        name: "Keyboard".into(),
+        hotkeys: ProfileBindings {
+            split: Some(KeyCode::KeyA.into()),
+            ..Default::default()
+        },
+    });
+
+    // The hotkey for cycling the profiles is used in all of them.
+    assert!(
+        config
+            .set_value(27, Value::Hotkey(Some(KeyCode::KeyA.into())))
+            .is_err()
+    );
+    config
+        .set_value(27, Value::Hotkey(Some(ctrl_k_then(KeyCode::KeyP))))
+        .unwrap();
+    assert!(
+        config
+            .set_value(
+                0,
+                Value::Hotkey(Some(
+                    KeyCode::KeyK.with_modifiers(Modifiers::CONTROL).into()
+                ))
+            )
+            .is_err()
+    );
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""cycle_profile":"Ctrl + KeyK, KeyP""#));
+    assert!(json.contains(r#""name":"Keyboard""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+}
+
+#[test]
+fn profiles_only_store_what_they_apply() {
+    // Profiles used to be stored as entire configurations. The settings that
+    // are shared by all the profiles are ignored when loading them.
+    let json = r#"{"profiles": [{
+        "name": "Keyboard",
+        "hotkeys": {
+            "split": "KeyA",
+            "midi_device": "Pedal",
+            "cycle_profile": "KeyB",
+            "profiles": [],
+            "scopes": { "split": "Focused" }
+        }
+    }]}"#;
+    let config = HotkeyConfig::from_json(json.as_bytes()).unwrap();
+    let hotkeys = config.profiles[0].hotkeys;
+    assert_eq!(hotkeys.split, Some(KeyCode::KeyA.into()));
+    assert_eq!(hotkeys.reset, None);
+    assert_eq!(hotkeys.scopes.split, HotkeyScope::Focused);
+    assert_eq!(config.midi_device, None);
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(!json.contains("Pedal"));
+    assert_eq!(json.matches("cycle_profile").count(), 1);
+}
+
+#[test]
+fn conflicts_can_be_found_before_setting_a_binding() {
+    let mut config = HotkeyConfig {
+        cycle_profile: Some(ctrl_k_then(KeyCode::KeyP)),
+        ..Default::default()
+    };
+    config.profiles.push(HotkeyProfile {
+        name: "Keyboard".into(),
+        hotkeys: ProfileBindings {
+            reset: Some(KeyCode::KeyA.into()),
+            ..Default::default()
+        },
+    });
+
+    // The binding of the setting itself is not a conflict.
+    assert_eq!(config.find_conflict(0, config.split.unwrap()), None);
+    assert_eq!(
+        config.find_conflict(2, config.split.unwrap()),
+        Some(HotkeyConflict {
+            profile: 0,
+            index: 0
+        }),
+    );
+    assert_eq!(
+        config.find_conflict(0, KeyCode::KeyK.with_modifiers(Modifiers::CONTROL).into()),
+        Some(HotkeyConflict {
+            profile: 0,
+            index: 27
+        }),
+    );
+
+    // The profiles only conflict with the hotkey for cycling through them.
+    assert_eq!(config.find_conflict(0, KeyCode::KeyA.into()), None);
+    assert_eq!(
+        config.find_conflict(27, KeyCode::KeyA.into()),
+        Some(HotkeyConflict {
+            profile: 1,
+            index: 1
+        }),
+    );
+}
+
+#[test]
+fn scopes_are_stored_with_the_hotkeys() {
+    let mut config = HotkeyConfig::default();
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    assert!(
+        String::from_utf8(json)
+            .unwrap()
+            .contains(r#""split":"Global""#)
+    );
+
+    config.set_value(28, Value::Bool(true)).unwrap();
+    assert_eq!(config.scopes.split, HotkeyScope::Focused);
+    assert_eq!(config.scopes.reset, HotkeyScope::Global);
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""split":"Focused""#));
+
+    assert_eq!(HotkeyConfig::from_json(json.as_bytes()).unwrap(), config);
+    // Configurations without scopes use global hotkeys.
+    assert_eq!(
+        HotkeyConfig::from_json(r#"{"split":"Numpad1"}"#.as_bytes())
+            .unwrap()
+            .scopes
+            .split,
+        HotkeyScope::Global,
+    );
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    let mut config = HotkeyConfig::default();
+    assert!(config.set_value(0, Value::Bool(true)).is_err());
+    assert!(config.set_value(1000, Value::Hotkey(None)).is_err());
+    assert_eq!(config, HotkeyConfig::default());
+}
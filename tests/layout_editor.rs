@@ -0,0 +1,281 @@
+use livesplit_core::{
+    Layout, LayoutEditor,
+    component::{blank_space, separator, text},
+    layout::LayoutDirection,
+    settings::{Constraints, ImageCache, ValidationError, Value, ValueList},
+};
+
+fn editor() -> LayoutEditor {
+    let mut layout = Layout::new();
+    layout.push(text::Component::new());
+    layout.push(separator::Component::new());
+    layout.push(blank_space::Component::new());
+    layout.push(text::Component::new());
+    LayoutEditor::new(layout).unwrap()
+}
+
+fn names(editor: &LayoutEditor) -> Vec<String> {
+    editor.state(&mut ImageCache::new()).components
+}
+
+#[test]
+fn toggling_and_ranges_select_multiple_components() {
+    let mut editor = editor();
+    editor.toggle_selection(2);
+    assert_eq!(editor.selected_components(), [0, 2]);
+
+    editor.toggle_selection(2);
+    editor.toggle_selection(0);
+    assert_eq!(editor.selected_components(), [0]);
+
+    editor.select(3);
+    editor.select_range(1);
+    assert_eq!(editor.selected_components(), [1, 2, 3]);
+    assert_eq!(editor.state(&mut ImageCache::new()).selected_component, 3);
+}
+
+#[test]
+fn selected_components_move_as_a_group() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.toggle_selection(3);
+
+    editor.move_component_up();
+    assert_eq!(names(&editor), ["Separator", "Text", "Text", "Blank Space"]);
+    assert_eq!(editor.selected_components(), [0, 2]);
+    assert!(!editor.can_move_component_up());
+
+    editor.move_component(3);
+    assert_eq!(names(&editor), ["Text", "Separator", "Blank Space", "Text"]);
+    assert_eq!(editor.selected_components(), [1, 3]);
+}
+
+#[test]
+fn duplicating_copies_the_whole_selection() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_range(2);
+    editor.duplicate_component();
+
+    assert_eq!(
+        names(&editor),
+        [
+            "Text",
+            "Separator",
+            "Blank Space",
+            "Separator",
+            "Blank Space",
+            "Text"
+        ]
+    );
+    assert_eq!(editor.selected_components(), [3, 4]);
+}
+
+#[test]
+fn removing_keeps_at_least_one_component() {
+    let mut editor = editor();
+    editor.select_range(3);
+    assert!(!editor.can_remove_component());
+
+    editor.toggle_selection(0);
+    editor.remove_component();
+    assert_eq!(names(&editor), ["Text"]);
+    assert_eq!(editor.selected_components(), [0]);
+}
+
+#[test]
+fn changes_can_be_undone_and_redone() {
+    let mut editor = editor();
+    editor.select_range(1);
+    editor.remove_component();
+    editor.select(1);
+    editor
+        .set_component_settings_value(3, Value::String("A".into()))
+        .unwrap();
+    editor
+        .set_component_settings_value(3, Value::String("AB".into()))
+        .unwrap();
+    assert_eq!(names(&editor), ["Blank Space", "AB"]);
+
+    // Typing into the same setting is undone at once.
+    editor.undo();
+    assert_eq!(names(&editor), ["Blank Space", "Text"]);
+    assert!(editor.can_redo());
+
+    editor.undo();
+    assert_eq!(names(&editor), ["Text", "Separator", "Blank Space", "Text"]);
+    assert_eq!(editor.selected_components(), [0, 1]);
+    assert!(!editor.can_undo());
+
+    editor.redo();
+    assert_eq!(names(&editor), ["Blank Space", "Text"]);
+
+    editor.add_component(separator::Component::new());
+    assert!(!editor.can_redo());
+}
+
+#[test]
+fn invalid_setting_values_are_rejected() {
+    let mut editor = editor();
+    editor.select(2);
+
+    assert!(matches!(
+        editor.set_component_settings_value(1, Value::UInt(u64::from(u32::MAX) + 1)),
+        Err(ValidationError::TooLarge { .. }),
+    ));
+    assert!(matches!(
+        editor.set_component_settings_value(1, Value::Bool(true)),
+        Err(ValidationError::WrongType),
+    ));
+    assert!(matches!(
+        editor.set_component_settings_value(100, Value::UInt(1)),
+        Err(ValidationError::UnknownSetting),
+    ));
+    let image_cache = ImageCache::new();
+    assert!(matches!(
+        editor.set_component_appearance_value(0, Value::Bool(true), &image_cache),
+        Err(ValidationError::WrongType),
+    ));
+    assert!(matches!(
+        editor.set_general_settings_value(1000, Value::UInt(1), &image_cache),
+        Err(ValidationError::UnknownSetting),
+    ));
+    assert!(!editor.can_undo());
+
+    editor
+        .set_component_settings_value(1, Value::UInt(42))
+        .unwrap();
+    assert!(editor.can_undo());
+}
+
+#[test]
+fn string_constraints_are_checked() {
+    let constraints = Constraints {
+        max_length: Some(8),
+        file_extensions: vec!["wasm".into()],
+        ..Default::default()
+    };
+    assert!(
+        constraints
+            .validate(&Value::from("ga.WASM".to_owned()))
+            .is_ok()
+    );
+    assert!(
+        constraints
+            .validate(&Value::List(ValueList::new(
+                [String::from("ga.wasm"), String::from("ga.txt")],
+                String::new().into(),
+            )))
+            .is_err()
+    );
+    assert!(matches!(
+        constraints.validate(&Value::from("splitter.wasm".to_owned())),
+        Err(ValidationError::TooLong { max_length: 8 }),
+    ));
+    assert!(matches!(
+        constraints.validate(&Value::from("a.txt".to_owned())),
+        Err(ValidationError::WrongFileExtension),
+    ));
+}
+
+#[cfg(feature = "setting-patterns")]
+#[test]
+fn patterns_need_to_match_the_entire_string() {
+    use livesplit_core::settings::Pattern;
+
+    let constraints = Constraints {
+        pattern: Some(Pattern::new("[a-z]+_[0-9]+")),
+        ..Default::default()
+    };
+    assert!(
+        constraints
+            .validate(&Value::from("level_12".to_owned()))
+            .is_ok()
+    );
+    assert!(matches!(
+        constraints.validate(&Value::from("level_12 ".to_owned())),
+        Err(ValidationError::PatternMismatch { pattern }) if pattern == "[a-z]+_[0-9]+",
+    ));
+    assert!(
+        constraints
+            .validate(&Value::OptionalString(Some("Level_12".into())))
+            .is_err()
+    );
+
+    let json = serde_json::to_string(&constraints).unwrap();
+    assert!(json.contains(r#""pattern":"[a-z]+_[0-9]+""#));
+    assert_eq!(
+        serde_json::from_str::<Constraints>(&json).unwrap(),
+        constraints
+    );
+}
+
+#[test]
+fn settings_can_be_reset_to_their_defaults() {
+    let mut editor = editor();
+    editor.select(2);
+    editor
+        .set_component_settings_value(1, Value::UInt(42))
+        .unwrap();
+
+    let mut image_cache = ImageCache::new();
+    let state = editor.state(&mut image_cache);
+    let size = &state.component_settings.fields[1];
+    let default = size.default_value.clone().unwrap().into_uint().unwrap();
+    assert_ne!(default, 42);
+
+    editor.reset_component_settings_value(1);
+    let mut state = editor.state(&mut image_cache);
+    let size = state.component_settings.fields.swap_remove(1).value;
+    assert_eq!(size.into_uint().unwrap(), default);
+
+    editor
+        .set_general_settings_value(0, LayoutDirection::Horizontal.into(), &image_cache)
+        .unwrap();
+    editor.reset_general_settings_value(0);
+    let direction = |editor: &LayoutEditor| {
+        let state = editor.state(&mut ImageCache::new());
+        state.general_settings.fields[0]
+            .value
+            .clone()
+            .into_layout_direction()
+            .unwrap()
+    };
+    assert!(direction(&editor) == LayoutDirection::Vertical);
+
+    editor.undo();
+    assert!(direction(&editor) == LayoutDirection::Horizontal);
+}
+
+#[test]
+fn settings_changes_are_tracked() {
+    let mut editor = editor();
+    assert!(editor.take_settings_changes().components_rearranged);
+    assert!(editor.take_settings_changes().is_empty());
+    let generation = editor.settings_generation();
+
+    editor.select(2);
+    editor
+        .set_component_settings_value(1, Value::UInt(42))
+        .unwrap();
+    let image_cache = ImageCache::new();
+    editor
+        .set_general_settings_value(0, LayoutDirection::Horizontal.into(), &image_cache)
+        .unwrap();
+    assert!(editor.settings_generation() > generation);
+
+    let changes = editor.take_settings_changes();
+    assert!(changes.general_settings);
+    assert!(!changes.components_rearranged);
+    assert_eq!(changes.components, [2]);
+    assert!(changes.component_changed(2) && !changes.component_changed(1));
+
+    editor.move_component_up();
+    assert!(editor.take_settings_changes().component_changed(0));
+
+    let generation = editor.settings_generation();
+    editor.undo();
+    assert!(editor.settings_generation() > generation);
+    let changes = editor.take_settings_changes();
+    assert!(changes.general_settings && changes.components_rearranged);
+}
@@ -0,0 +1,65 @@
+use livesplit_core::{
+    HotkeyConfig, Layout, hotkey::KeyCode, layout::LayoutManager, settings::Value,
+};
+
+fn manager(count: usize) -> LayoutManager {
+    LayoutManager::from_layouts((0..count).map(|_| Layout::new()).collect()).unwrap()
+}
+
+#[test]
+fn cycles_through_layouts() {
+    let mut manager = manager(3);
+    assert_eq!(manager.current_index(), 0);
+    manager.next();
+    manager.next();
+    assert_eq!(manager.current_index(), 2);
+    manager.next();
+    assert_eq!(manager.current_index(), 0);
+    manager.previous();
+    assert_eq!(manager.current_index(), 2);
+    manager.select(1);
+    assert_eq!(manager.current_index(), 1);
+}
+
+#[test]
+fn removing_keeps_selection() {
+    let mut manager = manager(3);
+    manager.select(2);
+    manager.remove(0).unwrap();
+    assert_eq!(manager.current_index(), 1);
+    manager.remove(1).unwrap();
+    assert_eq!(manager.current_index(), 0);
+    assert!(manager.remove(0).is_none());
+    assert_eq!(manager.layouts().len(), 1);
+}
+
+#[test]
+fn removing_the_selected_layout_selects_the_previous_one() {
+    let mut manager = manager(3);
+    manager.select(1);
+    manager.remove(1).unwrap();
+    assert_eq!(manager.current_index(), 0);
+    manager.remove(0).unwrap();
+    assert_eq!(manager.current_index(), 0);
+    assert_eq!(manager.layouts().len(), 1);
+}
+
+#[test]
+fn no_layouts() {
+    assert!(LayoutManager::from_layouts(Vec::new()).is_none());
+}
+
+#[test]
+fn layout_hotkeys_conflict_with_other_actions() {
+    let mut config = HotkeyConfig::default();
+    let split = config.split;
+    assert!(config.set_value(10, split.into()).is_err());
+    config
+        .set_value(10, Value::Hotkey(Some(KeyCode::PageDown.into())))
+        .unwrap();
+    assert_eq!(config.next_layout, Some(KeyCode::PageDown.into()));
+    assert!(matches!(
+        config.settings_description().fields[10].value,
+        Value::Hotkey(Some(_))
+    ));
+}
@@ -46,14 +46,14 @@ mod parse {
     #[test]
     fn custom_variable_splits() {
         let l = livesplit(layout_files::CUSTOM_VARIABLE_SPLITS);
-        let Some(splits) = l.components.iter().find_map(|c| match c {
+        let Some(splits) = l.components().iter().find_map(|c| match c {
             Component::Splits(s) => Some(s),
             _ => None,
         }) else {
             panic!("Splits component not found");
         };
         let texts: Vec<_> = l
-            .components
+            .components()
             .iter()
             .filter_map(|c| match c {
                 Component::Text(t) => Some(t),
@@ -100,14 +100,14 @@ mod parse {
     #[test]
     fn custom_variable_subsplits() {
         let l = livesplit(layout_files::CUSTOM_VARIABLE_SUBSPLITS);
-        let Some(splits) = l.components.iter().find_map(|c| match c {
+        let Some(splits) = l.components().iter().find_map(|c| match c {
             Component::Splits(s) => Some(s),
             _ => None,
         }) else {
             panic!("Splits component not found");
         };
         let texts: Vec<_> = l
-            .components
+            .components()
             .iter()
             .filter_map(|c| match c {
                 Component::Text(t) => Some(t),
@@ -163,3 +163,75 @@ mod parse {
         assert_eq!(columns[1].name, "+/−");
     }
 }
+
+mod save {
+    use crate::layout_files;
+    use livesplit_core::layout::{Component, Layout, parser::parse, saver::save_layout};
+
+    #[track_caller]
+    fn assert_round_trip(data: &str) {
+        let layout = parse(data).unwrap();
+        let mut saved = String::new();
+        save_layout(&layout, &mut saved).unwrap();
+        let reparsed: Layout = parse(&saved).unwrap();
+        assert_eq!(
+            serde_json::to_string(&layout.settings()).ok(),
+            serde_json::to_string(&reparsed.settings()).ok(),
+        );
+    }
+
+    #[test]
+    fn all() {
+        assert_round_trip(layout_files::ALL);
+    }
+
+    #[test]
+    fn dark() {
+        assert_round_trip(layout_files::DARK);
+    }
+
+    #[test]
+    fn with_timer_delta_background() {
+        assert_round_trip(layout_files::WITH_TIMER_DELTA_BACKGROUND);
+    }
+
+    #[test]
+    fn custom_variable_splits() {
+        assert_round_trip(layout_files::CUSTOM_VARIABLE_SPLITS);
+    }
+
+    #[test]
+    fn unsupported_components_are_kept() {
+        let layout = parse(layout_files::ALL).unwrap();
+        let placeholder = layout
+            .components()
+            .iter()
+            .find_map(|c| match c {
+                Component::Placeholder(c) => Some(c),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            placeholder.settings().path,
+            "LiveSplit.ScriptableAutoSplit.dll"
+        );
+
+        let mut saved = String::new();
+        save_layout(&layout, &mut saved).unwrap();
+        assert!(saved.contains("<Path>LiveSplit.ScriptableAutoSplit.dll</Path>"));
+        assert!(
+            saved.contains(
+                r"<ScriptPath>C:\Projekte\a-hat-in-time-mock\AHatInTime.asl</ScriptPath>"
+            )
+        );
+    }
+
+    #[test]
+    fn default_layout() {
+        let layout = Layout::default_layout();
+        let mut saved = String::new();
+        save_layout(&layout, &mut saved).unwrap();
+        let reparsed = parse(&saved).unwrap();
+        assert_eq!(reparsed.components().len(), layout.components().len());
+    }
+}
@@ -0,0 +1,138 @@
+use livesplit_core::{
+    TimingMethod,
+    hotkey::{Hotkey, KeyCode, Modifiers},
+    livesplit_settings::parse,
+};
+
+const SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Settings version="1.8.29">
+  <HotkeyProfiles>
+    <HotkeyProfile name="Default">
+      <SplitKey>NumPad1</SplitKey>
+      <ResetKey>NumPad3</ResetKey>
+      <SkipKey>NumPad2</SkipKey>
+      <UndoKey>NumPad8</UndoKey>
+      <PauseKey />
+      <ToggleGlobalHotkeys />
+      <SwitchComparisonPrevious>NumPad4</SwitchComparisonPrevious>
+      <SwitchComparisonNext>NumPad6</SwitchComparisonNext>
+      <GlobalHotkeysEnabled>False</GlobalHotkeysEnabled>
+      <DeactivateHotkeysForOtherPrograms>False</DeactivateHotkeysForOtherPrograms>
+      <DoubleTapPrevention>True</DoubleTapPrevention>
+      <HotkeyDelay>0</HotkeyDelay>
+      <AllowGamepadsAsHotkeys>False</AllowGamepadsAsHotkeys>
+    </HotkeyProfile>
+    <HotkeyProfile name="Keyboard">
+      <SplitKey>Space</SplitKey>
+      <ResetKey>R, Shift, Control</ResetKey>
+      <SkipKey>Oemplus</SkipKey>
+      <UndoKey>D8</UndoKey>
+      <PauseKey>Xbox Controller Button 1</PauseKey>
+      <SwitchComparisonPrevious>Left</SwitchComparisonPrevious>
+      <SwitchComparisonNext>Right</SwitchComparisonNext>
+      <GlobalHotkeysEnabled>True</GlobalHotkeysEnabled>
+      <DoubleTapPrevention>False</DoubleTapPrevention>
+      <HotkeyDelay>0.25</HotkeyDelay>
+    </HotkeyProfile>
+  </HotkeyProfiles>
+  <WarnOnReset>False</WarnOnReset>
+  <RecentSplits>
+    <SplitsFile gameName="Super Mario Odyssey" categoryName="Any%" lastTimingMethod="RealTime" lastHotkeyProfile="Default">C:\Splits\smo.lss</SplitsFile>
+    <SplitsFile gameName="Celeste" categoryName="Any%" lastTimingMethod="GameTime" lastHotkeyProfile="Keyboard">C:\Splits\celeste.lss</SplitsFile>
+  </RecentSplits>
+  <RecentLayouts>
+    <LayoutPath>C:\Layouts\default.lsl</LayoutPath>
+  </RecentLayouts>
+  <LastComparison>Best Segments</LastComparison>
+  <SimpleSumOfBest>True</SimpleSumOfBest>
+  <ComparisonGeneratorStates>
+    <Generator name="Best Segments">True</Generator>
+    <Generator name="Worst Segments">False</Generator>
+  </ComparisonGeneratorStates>
+  <RefreshRate>40</RefreshRate>
+</Settings>"#;
+
+const LEGACY_SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Settings version="1.6">
+  <SplitKey>F1</SplitKey>
+  <ResetKey>F2</ResetKey>
+  <SkipKey />
+  <UndoKey>F3</UndoKey>
+  <RecentSplits>
+    <SplitsPath>C:\Splits\old.lss</SplitsPath>
+  </RecentSplits>
+  <LastTimingMethod>GameTime</LastTimingMethod>
+</Settings>"#;
+
+#[test]
+fn parses_hotkey_profiles_and_preferences() {
+    let settings = parse(SETTINGS).unwrap();
+
+    assert_eq!(settings.hotkey_profiles.len(), 2);
+    let default = &settings.hotkey_profiles[0];
+    assert_eq!(default.name, "Default");
+    assert_eq!(default.hotkeys.split, Some(KeyCode::Numpad1.into()));
+    assert_eq!(default.hotkeys.pause, None);
+
+    // The profile used with the most recent splits is the active one.
+    let keyboard = &settings.hotkey_profiles[1];
+    assert_eq!(settings.hotkeys, keyboard.hotkeys);
+    assert_eq!(settings.hotkeys.split, Some(KeyCode::Space.into()));
+    assert_eq!(
+        settings.hotkeys.reset,
+        Some(
+            Hotkey {
+                key_code: KeyCode::KeyR,
+                modifiers: Modifiers::SHIFT | Modifiers::CONTROL,
+            }
+            .into()
+        ),
+    );
+    assert_eq!(settings.hotkeys.skip, Some(KeyCode::Equal.into()));
+    assert_eq!(settings.hotkeys.undo, Some(KeyCode::Digit8.into()));
+    assert_eq!(settings.hotkeys.pause, None);
+    assert!(keyboard.global_hotkeys_enabled);
+    assert!(!keyboard.double_tap_prevention);
+    assert_eq!(keyboard.hotkey_delay.total_milliseconds(), 250.0);
+
+    assert!(!settings.warn_on_reset);
+    assert_eq!(settings.recent_splits.len(), 2);
+    assert_eq!(settings.recent_splits[1].path, r"C:\Splits\celeste.lss");
+    assert_eq!(settings.recent_splits[1].game_name, "Celeste");
+    assert_eq!(settings.recent_layouts, [r"C:\Layouts\default.lsl"]);
+    assert_eq!(settings.last_comparison.as_deref(), Some("Best Segments"));
+    assert_eq!(settings.last_timing_method, TimingMethod::GameTime);
+    assert!(settings.simple_sum_of_best);
+    assert_eq!(
+        settings.comparison_generators,
+        [
+            (String::from("Best Segments"), true),
+            (String::from("Worst Segments"), false),
+        ],
+    );
+}
+
+#[test]
+fn parses_hotkeys_of_old_versions() {
+    let settings = parse(LEGACY_SETTINGS).unwrap();
+
+    assert_eq!(settings.hotkey_profiles.len(), 1);
+    assert_eq!(settings.hotkeys.split, Some(KeyCode::F1.into()));
+    assert_eq!(settings.hotkeys.reset, Some(KeyCode::F2.into()));
+    assert_eq!(settings.hotkeys.skip, None);
+    assert_eq!(settings.hotkeys.undo, Some(KeyCode::F3.into()));
+    assert_eq!(settings.recent_splits[0].path, r"C:\Splits\old.lss");
+    assert_eq!(settings.last_timing_method, TimingMethod::GameTime);
+}
+
+#[test]
+fn skips_hotkey_delays_that_cant_be_represented() {
+    for delay in ["NaN", "Infinity", "1e30"] {
+        let settings = parse(&SETTINGS.replace(
+            "<HotkeyDelay>0.25</HotkeyDelay>",
+            &format!("<HotkeyDelay>{delay}</HotkeyDelay>"),
+        ))
+        .unwrap();
+        assert_eq!(settings.hotkey_profiles[1].hotkey_delay, Default::default());
+    }
+}
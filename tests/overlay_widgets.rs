@@ -0,0 +1,56 @@
+use livesplit_core::{
+    Run, Segment, Timer,
+    component::overlay_widgets,
+    event::CommandSink,
+    timing::{OverlayWidget, OverlayWidgetValue},
+};
+
+fn timer() -> Timer {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    Timer::new(run).unwrap()
+}
+
+fn widgets() -> Vec<OverlayWidget> {
+    vec![
+        OverlayWidget {
+            label: "Seed".into(),
+            value: OverlayWidgetValue::Text("1234".into()),
+        },
+        OverlayWidget {
+            label: "Boss HP".into(),
+            value: OverlayWidgetValue::Progress(0.25),
+        },
+    ]
+}
+
+#[test]
+fn state_contains_widgets() {
+    let mut timer = timer();
+    let component = overlay_widgets::Component::new();
+    let mut state = component.state(&timer);
+    assert!(state.widgets.is_empty());
+
+    timer.set_overlay_widgets(widgets());
+    component.update_state(&mut state, &timer);
+
+    assert_eq!(state.widgets.len(), 2);
+    assert_eq!(state.widgets[0].label, "Seed");
+    assert_eq!(state.widgets[0].value, "1234");
+    assert_eq!(state.widgets[0].progress, None);
+    assert_eq!(state.widgets[1].label, "Boss HP");
+    assert_eq!(state.widgets[1].value, "25%");
+    assert_eq!(state.widgets[1].progress, Some(0.25));
+
+    timer.set_overlay_widgets(Vec::new());
+    component.update_state(&mut state, &timer);
+    assert!(state.widgets.is_empty());
+}
+
+#[test]
+fn command_sink_sets_widgets() {
+    let timer = timer().into_shared();
+    // The shared timer applies the command right away.
+    drop(CommandSink::set_overlay_widgets(&timer, widgets()));
+    assert_eq!(timer.read().unwrap().overlay_widgets(), widgets());
+}
@@ -368,6 +368,486 @@ fn background_image() {
     );
 }
 
+#[cfg(all(feature = "software-rendering", feature = "svg-images"))]
+#[test]
+fn svg_icons_are_rasterized_at_the_needed_size() {
+    use livesplit_core::settings::Image;
+
+    // A 2x2 checkerboard of red and blue squares. If it got rasterized at its
+    // intrinsic size and then scaled up, most of the icon would be a blurry
+    // purple. Rasterized at the needed size, it stays crisp.
+    const CHECKERBOARD: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="2" height="2">
+<rect width="1" height="1" fill="red"/><rect x="1" y="1" width="1" height="1" fill="red"/>
+<rect x="1" width="1" height="1" fill="blue"/><rect y="1" width="1" height="1" fill="blue"/>
+</svg>"#;
+
+    let mut run = tests_helper::create_run(&["A"]);
+    let icon = Image::new(CHECKERBOARD.into(), Image::ICON);
+    assert!(icon.is_svg());
+    assert_eq!(icon.data(), CHECKERBOARD);
+    run.set_game_icon(icon);
+    let timer = Timer::new(run).unwrap();
+
+    let mut layout = Layout::new();
+    layout.push(component::title::Component::new());
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+
+    let mut renderer = rendering::software::Renderer::new();
+    renderer.render(&state, &image_cache, [600, 180]);
+
+    let (mut pure, mut purple) = (0, 0);
+    for &image::Rgba([r, g, b, _]) in renderer.image().pixels() {
+        if g == 0 && (r == 0) != (b == 0) && r.max(b) == 255 {
+            pure += 1;
+        } else if g == 0 && r > 0 && b > 0 {
+            purple += 1;
+        }
+    }
+    assert!(pure > 1000, "{pure}");
+    assert!(purple < pure / 10, "{purple} {pure}");
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn png_screenshots_are_not_premultiplied() {
+    use livesplit_core::settings::{Color, Gradient, LayoutBackground};
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+    let mut layout = Layout::new();
+    layout.general_settings_mut().background =
+        LayoutBackground::Gradient(Gradient::Plain(Color::rgba(1.0, 0.0, 0.0, 0.5)));
+
+    let png = rendering::software::render_png(&mut layout, &timer.snapshot(), [40, 30]);
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .unwrap()
+        .into_rgba8();
+
+    assert_eq!(image.dimensions(), (40, 30));
+    assert_eq!(image.get_pixel(20, 15).0, [255, 0, 0, 128]);
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn bgra_frames_match_rgba_frames() {
+    let mut timer = Timer::new(tests_helper::create_run(&["A", "B"])).unwrap();
+    let mut layout = Layout::default_layout();
+    let mut image_cache = ImageCache::new();
+    let (mut rgba_renderer, mut bgra_renderer) = (
+        rendering::software::BorrowedRenderer::new(),
+        rendering::software::BorrowedRenderer::new(),
+    );
+    let (width, height, stride) = (300, 500, 320);
+    let (mut rgba, mut bgra) = (vec![0; 4 * stride * height], vec![0; 4 * stride * height]);
+
+    // Once the run is started, the third frame only redraws the timer as the
+    // time passes, which also needs to be converted.
+    for frame in 0..3 {
+        match frame {
+            1 => tests_helper::start_run(&mut timer),
+            2 => {
+                timer.set_game_time(TimeSpan::from_seconds(1.5)).unwrap();
+            }
+            _ => {}
+        }
+
+        let state = layout.state(&mut image_cache, &timer.snapshot());
+        let dims = [width as u32, height as u32];
+        rgba_renderer.render(&state, &image_cache, &mut rgba, dims, stride as u32, false);
+        bgra_renderer.render_bgra(&state, &image_cache, &mut bgra, dims, stride as u32, false);
+
+        for (rgba, bgra) in rgba.chunks_exact(4).zip(bgra.chunks_exact(4)) {
+            assert_eq!([rgba[2], rgba[1], rgba[0], rgba[3]], bgra);
+        }
+    }
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn only_dirty_regions_change() {
+    use rendering::software::Region;
+
+    let mut timer = Timer::new(tests_helper::create_run(&["A", "B"])).unwrap();
+    let mut layout = Layout::default_layout();
+    let mut image_cache = ImageCache::new();
+    let mut renderer = rendering::software::Renderer::new();
+    let [width, height] = [300, 500];
+
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    renderer.render(&state, &image_cache, [width, height]);
+    assert_eq!(
+        renderer.dirty_regions(),
+        [Region {
+            x: 0,
+            y: 0,
+            width,
+            height
+        }],
+    );
+
+    tests_helper::start_run(&mut timer);
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    renderer.render(&state, &image_cache, [width, height]);
+    let before = renderer.image_data().to_vec();
+
+    // Only the timer changes as the time passes.
+    timer.set_game_time(TimeSpan::from_seconds(1.5)).unwrap();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    renderer.render(&state, &image_cache, [width, height]);
+    let regions = renderer.dirty_regions();
+    assert!(!regions.is_empty());
+
+    let mut dirty_pixels = 0;
+    for region in regions {
+        assert!(region.x + region.width <= width && region.y + region.height <= height);
+        dirty_pixels += region.width * region.height;
+    }
+    assert!(dirty_pixels < width * height / 4, "{regions:?}");
+
+    let after = renderer.image_data();
+    assert_ne!(before, after);
+    for (i, (before, after)) in before.chunks(4).zip(after.chunks(4)).enumerate() {
+        let [x, y] = [i as u32 % width, i as u32 / width];
+        let is_dirty = regions
+            .iter()
+            .any(|r| (r.x..r.x + r.width).contains(&x) && (r.y..r.y + r.height).contains(&y));
+        assert!(
+            is_dirty || before == after,
+            "The pixel at {x}, {y} changed."
+        );
+    }
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn embedded_fonts_are_preferred() {
+    use livesplit_core::settings::{Font, FontData};
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+
+    let render = |font: Font| {
+        let mut layout = Layout::new();
+        layout.push(component::text::Component::with_settings(
+            component::text::Settings {
+                text: component::text::Text::Center("0:12:34.56".into()),
+                ..Default::default()
+            },
+        ));
+        layout.general_settings_mut().text_font = Some(font);
+
+        // The embedded font needs to survive saving the layout.
+        let settings = serde_json::to_string(&layout.settings()).unwrap();
+        let mut layout = ls1l(&settings);
+
+        let mut image_cache = ImageCache::new();
+        let state = layout.state(&mut image_cache, &timer.snapshot());
+        let mut renderer = rendering::software::Renderer::new();
+        renderer.render(&state, &image_cache, [300, 60]);
+        renderer.into_image()
+    };
+
+    let installed = render(Font {
+        family: "LiveSplit Timer".into(),
+        ..Default::default()
+    });
+    let missing = render(Font {
+        family: "Some Font That Isn't Installed".into(),
+        ..Default::default()
+    });
+    let embedded = render(Font {
+        family: "Some Font That Isn't Installed".into(),
+        data: Some(FontData::new(
+            include_bytes!("../src/rendering/font/assets/Timer.ttf")
+                .as_slice()
+                .into(),
+        )),
+        ..Default::default()
+    });
+
+    assert!(installed != missing);
+    assert!(installed == embedded);
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn subpixel_antialiasing() {
+    use livesplit_core::{
+        rendering::software::{Renderer, TextAntialiasing},
+        settings::{Color, Gradient, LayoutBackground},
+    };
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+    let mut layout = Layout::new();
+    layout.push(component::text::Component::with_settings(
+        component::text::Settings {
+            text: component::text::Text::Center("Subpixels".into()),
+            ..Default::default()
+        },
+    ));
+    layout.general_settings_mut().background =
+        LayoutBackground::Gradient(Gradient::Plain(Color::rgba(0.2, 0.2, 0.2, 1.0)));
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+
+    // Switching an existing renderer needs to redraw the text.
+    let mut renderer = Renderer::new();
+    renderer.render(&state, &image_cache, [200, 40]);
+    let grayscale = renderer.image_data().to_vec();
+    renderer.set_text_antialiasing(TextAntialiasing::SubpixelRgb);
+    renderer.render(&state, &image_cache, [200, 40]);
+    let rgb = renderer.image_data().to_vec();
+
+    let mut renderer = Renderer::new();
+    renderer.set_text_antialiasing(TextAntialiasing::SubpixelBgr);
+    renderer.render(&state, &image_cache, [200, 40]);
+    let bgr = renderer.image_data();
+
+    let is_gray = |pixel: &[u8]| pixel[0] == pixel[1] && pixel[1] == pixel[2];
+    assert!(grayscale.chunks_exact(4).all(is_gray));
+    assert!(!rgb.chunks_exact(4).all(is_gray));
+
+    // The subpixels are in the opposite order, so red and blue are swapped.
+    assert!(
+        rgb.chunks_exact(4)
+            .zip(bgr.chunks_exact(4))
+            .all(|(rgb, bgr)| [rgb[2], rgb[1], rgb[0], rgb[3]] == bgr)
+    );
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn hinting_aligns_separators_to_the_pixel_grid() {
+    use livesplit_core::{
+        rendering::{Hinting, software::Renderer},
+        settings::{Color, Gradient, LayoutBackground},
+    };
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+    let mut layout = Layout::new();
+    layout.push(component::blank_space::Component::new());
+    layout.push(component::separator::Component::new());
+    layout.push(component::blank_space::Component::new());
+    let settings = layout.general_settings_mut();
+    settings.background = LayoutBackground::Gradient(Gradient::Plain(Color::black()));
+    settings.separators_color = Color::white();
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+
+    // The height is chosen such that the separator's edges don't line up with
+    // the pixel grid.
+    let column = |renderer: &Renderer| -> Vec<u8> {
+        renderer
+            .image_data()
+            .chunks_exact(4 * 100)
+            .map(|row| row[4 * 50])
+            .collect()
+    };
+    let mut renderer = Renderer::new();
+    renderer.render(&state, &image_cache, [100, 37]);
+    assert!(column(&renderer).iter().any(|&v| v != 0 && v != 255));
+
+    renderer.set_hinting(Hinting::Vertical);
+    renderer.render(&state, &image_cache, [100, 37]);
+    let hinted = column(&renderer);
+    assert!(hinted.iter().all(|&v| v == 0 || v == 255));
+    assert_eq!(hinted.iter().filter(|&&v| v == 255).count(), 1);
+
+    // Lines are at least one logical pixel thick.
+    renderer.set_device_pixel_ratio(2.0);
+    renderer.render(&state, &image_cache, [100, 37]);
+    assert_eq!(column(&renderer).iter().filter(|&&v| v == 255).count(), 2);
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn glyph_cache_stays_within_its_limits() {
+    use livesplit_core::{
+        rendering::software::Renderer,
+        settings::{CacheLimits, EvictionPolicy},
+    };
+
+    let mut run = tests_helper::create_run(&["A", "B", "C", "D"]);
+    run.set_game_name("Some Game Name");
+    run.set_category_name("Some Category Name");
+    let timer = Timer::new(run).unwrap();
+    let mut layout = Layout::default_layout();
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+
+    let mut renderer = Renderer::new();
+    renderer.render(&state, &image_cache, [300, 500]);
+    let unlimited = renderer.glyph_cache_usage().entries;
+    assert!(unlimited > 10);
+
+    let mut limited = Renderer::new();
+    limited.set_glyph_cache_limits(CacheLimits {
+        max_entries: Some(10),
+        max_bytes: None,
+        eviction_policy: EvictionPolicy::OverLimits,
+    });
+    limited.render(&state, &image_cache, [300, 500]);
+    assert_eq!(limited.glyph_cache_usage().entries, 10);
+
+    // The labels keep their glyphs, so evicting them doesn't change the image.
+    assert_eq!(renderer.image_data(), limited.image_data());
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn bitmap_glyphs_are_rendered_as_images() {
+    use livesplit_core::settings::{Font, FontData};
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+
+    // The font stores the glyph for "A" as a red square in the sbix table,
+    // which is how color emoji are usually stored.
+    let mut layout = Layout::new();
+    layout.push(component::text::Component::with_settings(
+        component::text::Settings {
+            text: component::text::Text::Center("A".into()),
+            ..Default::default()
+        },
+    ));
+    layout.general_settings_mut().text_font = Some(Font {
+        family: "Bitmap Glyphs".into(),
+        data: Some(FontData::new(
+            include_bytes!("fonts/BitmapGlyphs.ttf").as_slice().into(),
+        )),
+        ..Default::default()
+    });
+
+    let mut image_cache = ImageCache::new();
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    let mut renderer = rendering::software::Renderer::new();
+    renderer.render(&state, &image_cache, [300, 60]);
+
+    let red_pixels = renderer
+        .image_data()
+        .chunks_exact(4)
+        .filter(|pixel| pixel == &[0xFF, 0x00, 0x00, 0xFF])
+        .count();
+    assert!(
+        red_pixels > 100,
+        "Only {red_pixels} red pixels were rendered."
+    );
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn right_to_left_text_is_laid_out_visually() {
+    use livesplit_core::rendering::{
+        FontKind, Label, PathBuilder, default_text_engine::TextEngine,
+    };
+    use std::rc::Rc;
+
+    /// Records the points of the outlines, so the glyphs can be told apart.
+    #[derive(Default)]
+    struct Outline(Vec<[f32; 2]>);
+
+    impl PathBuilder for Outline {
+        type Path = Rc<Vec<[f32; 2]>>;
+
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.0.push([x, y]);
+        }
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.0.push([x, y]);
+        }
+        fn quad_to(&mut self, _: f32, _: f32, x: f32, y: f32) {
+            self.0.push([x, y]);
+        }
+        fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, x: f32, y: f32) {
+            self.0.push([x, y]);
+        }
+        fn close(&mut self) {}
+        fn finish(self) -> Self::Path {
+            Rc::new(self.0)
+        }
+    }
+
+    let mut engine = TextEngine::new();
+    let font = engine.create_font(None, FontKind::Text);
+    let mut layout = |text: &str, max_width: Option<f32>| {
+        let label = engine.create_label(Outline::default, text, &font, max_width);
+        let width = label.width(1.0);
+        let label = label.read().unwrap();
+        let glyphs: Vec<_> = label
+            .glyphs()
+            .iter()
+            .map(|g| (g.x, g.path.clone()))
+            .collect();
+        (glyphs, width)
+    };
+
+    let (latin, _) = layout("abc", None);
+    let (hebrew, hebrew_width) = layout("אבג", None);
+    let (ellipsis, ellipsis_width) = layout("…", None);
+    let is_latin = |path: &Rc<Vec<[f32; 2]>>| latin.iter().any(|(_, p)| p == path);
+
+    // The paragraph starts with Hebrew, so it's right-to-left and the Latin
+    // word that follows it ends up on the left.
+    let (mixed, width) = layout("אבג abc", None);
+    assert!(mixed.is_sorted_by(|(a, _), (b, _)| a <= b));
+    assert!(mixed[..3].iter().zip(&latin).all(|((_, a), (_, b))| a == b));
+    assert!(!mixed[3..].iter().any(|(_, p)| is_latin(p)));
+    assert_eq!(mixed.len(), latin.len() + hebrew.len());
+
+    // The end of the text is on the left, so that's where it gets cut off.
+    let max_width = hebrew_width + ellipsis_width + 0.01;
+    assert!(max_width < width);
+    let (truncated, truncated_width) = layout("אבג abc", Some(max_width));
+    assert!(truncated_width <= max_width);
+    assert_eq!(truncated[0], ellipsis[0]);
+    assert!(!truncated.iter().any(|(_, p)| is_latin(p)));
+    assert_eq!(truncated.len(), 1 + hebrew.len());
+}
+
+#[cfg(feature = "software-rendering")]
+#[test]
+fn radial_and_conic_gradients() {
+    use livesplit_core::settings::{Color, Gradient, LayoutBackground};
+
+    let timer = Timer::new(tests_helper::create_run(&["A"])).unwrap();
+    let (red, blue) = (
+        Color::rgba(1.0, 0.0, 0.0, 1.0),
+        Color::rgba(0.0, 0.0, 1.0, 1.0),
+    );
+
+    let render = |gradient: Gradient| {
+        let mut layout = Layout::new();
+        layout.push(component::blank_space::Component::new());
+        layout.general_settings_mut().background = LayoutBackground::Gradient(gradient);
+
+        let mut image_cache = ImageCache::new();
+        let state = layout.state(&mut image_cache, &timer.snapshot());
+        let mut renderer = rendering::software::Renderer::new();
+        renderer.render(&state, &image_cache, [200, 100]);
+        renderer.into_image()
+    };
+
+    let radial = render(Gradient::Radial(red, blue, [0.5, 0.5], 0.5));
+    let [r, _, b, _] = radial.get_pixel(100, 50).0;
+    assert!(r > 240 && b < 15);
+    assert_eq!(radial.get_pixel(0, 0).0, [0, 0, 255, 255]);
+    // The gradient is stretched into an ellipse, so the edge is reached at
+    // the left and right sides, but not any earlier.
+    let [r, _, b, _] = radial.get_pixel(60, 50).0;
+    assert!(r > b);
+
+    // The sweep starts at the top, so the start color is just right of the
+    // top center and the end color is just left of it.
+    let conic = render(Gradient::Conic(red, blue, [0.5, 0.5], 0.0));
+    let [r, _, b, _] = conic.get_pixel(103, 0).0;
+    assert!(r > 240 && b < 15);
+    let [r, _, b, _] = conic.get_pixel(97, 0).0;
+    assert!(r < 15 && b > 240);
+    let [r, _, b, _] = conic.get_pixel(100, 99).0;
+    assert!(r.abs_diff(b) < 10);
+}
+
 #[test]
 fn display_two_rows() {
     let timer = tests_helper::create_timer(&["A"]);
@@ -428,7 +908,7 @@ fn horizontal() {
     let mut timer = Timer::new(run).unwrap();
     let mut layout = Layout::default_layout();
     layout.general_settings_mut().direction = LayoutDirection::Horizontal;
-    match &mut layout.components[1] {
+    match layout.component_mut(1) {
         Component::Splits(splits) => splits.settings_mut().visual_split_count = 4,
         _ => unreachable!("We wanted to configure the splits"),
     }
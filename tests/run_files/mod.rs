@@ -20,8 +20,11 @@ pub const PORTAL2_LIVE_TIMER2: &str = include_str!("portal2_live_timer2.csv");
 pub const SOURCE_LIVE_TIMER: &str = include_str!("source_live_timer.json");
 pub const SOURCE_LIVE_TIMER2: &str = include_str!("source_live_timer2.json");
 pub const SPEEDRUN_IGT: &str = include_str!("speedrun_igt.json");
+pub const SPEEDRUN_IGT_ALL_ADVANCEMENTS: &str = include_str!("speedrun_igt_all_advancements.json");
 pub const SPLITTERINO: &str = include_str!("splitterino.splits");
+pub const SPLITTERINO_PAUSED: &str = include_str!("splitterino_paused.splits");
 pub const SPLITTERZ: &str = include_str!("splitterz");
+pub const THERUN_GG: &str = include_str!("therun_gg.json");
 pub const TIME_SPLIT_TRACKER_WITHOUT_ATTEMPT_COUNT: &str = include_str!("1734.timesplittracker");
 pub const TIME_SPLIT_TRACKER: &str = include_str!("timesplittracker.txt");
 pub const URN: &str = include_str!("urn.json");
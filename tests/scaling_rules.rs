@@ -0,0 +1,69 @@
+use livesplit_core::{
+    Layout, Run, Segment, Timer,
+    layout::{LayoutScaling, LayoutSettings, ScalingBreakpoint, ScalingRules},
+    settings::ImageCache,
+};
+
+fn rules() -> ScalingRules {
+    ScalingRules {
+        target_width: 300.0,
+        target_height: 500.0,
+        min_text_scale: 0.5,
+        max_text_scale: 1.5,
+        breakpoints: vec![
+            ScalingBreakpoint {
+                min_width: 1000.0,
+                row_height_scale: 1.5,
+            },
+            ScalingBreakpoint {
+                min_width: 600.0,
+                row_height_scale: 1.25,
+            },
+        ],
+    }
+}
+
+#[test]
+fn target_size_is_unscaled() {
+    assert_eq!(rules().resolve([300.0, 500.0]), LayoutScaling::default());
+}
+
+fn scaling(text_scale: f32, row_height_scale: f32) -> LayoutScaling {
+    LayoutScaling {
+        text_scale,
+        row_height_scale,
+    }
+}
+
+#[test]
+fn text_growth_is_limited() {
+    // The renderer would scale the text by 2, but it may only grow by 1.5.
+    assert_eq!(rules().resolve([1920.0, 1000.0]), scaling(0.75, 1.5));
+    assert_eq!(rules().resolve([800.0, 500.0]), scaling(1.0, 1.25));
+}
+
+#[test]
+fn narrow_layouts_shrink_text() {
+    assert_eq!(rules().resolve([150.0, 500.0]), scaling(0.5, 1.0));
+    assert_eq!(rules().resolve([30.0, 500.0]), scaling(0.5, 1.0));
+}
+
+#[test]
+fn layout_state_uses_viewport_size() {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    let timer = Timer::new(run).unwrap();
+
+    let mut layout = Layout::default_layout();
+    layout.set_viewport_size([1920.0, 1000.0]);
+    let state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+    assert_eq!(state.scaling, LayoutScaling::default());
+
+    layout.set_scaling_rules(Some(rules()));
+    let state = layout.state(&mut ImageCache::new(), &timer.snapshot());
+    assert_eq!(state.scaling, scaling(0.75, 1.5));
+
+    let json = serde_json::to_string(&layout.settings()).unwrap();
+    let settings: LayoutSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(settings.scaling_rules, Some(rules()));
+}
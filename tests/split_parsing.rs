@@ -5,10 +5,13 @@ mod parse {
     use livesplit_core::{
         Run, TimeSpan,
         analysis::total_playtime,
-        run::parser::{
-            TimerKind, composite, flitter, livesplit, llanfair, llanfair_gered, opensplit,
-            portal2_live_timer, source_live_timer, speedrun_igt, splitterino, splitterz,
-            time_split_tracker, urn, wsplit,
+        run::{
+            parser::{
+                TimerKind, composite, flitter, livesplit, llanfair, llanfair_gered, opensplit,
+                portal2_live_timer, source_live_timer, speedrun_igt, splitterino, splitterz,
+                therun_gg, time_split_tracker, urn, wsplit,
+            },
+            saver,
         },
     };
 
@@ -52,6 +55,74 @@ mod parse {
         livesplit(run_files::LIVESPLIT_1_6);
     }
 
+    #[test]
+    fn livesplit_lenient_keeps_valid_files_intact() {
+        let recovered = livesplit::parse_lenient(run_files::LIVESPLIT_1_6).unwrap();
+        assert!(recovered.warnings.is_empty());
+        assert_eq!(recovered.run, livesplit(run_files::LIVESPLIT_1_6));
+    }
+
+    #[test]
+    fn livesplit_lenient_drops_malformed_entries() {
+        let source = run_files::LIVESPLIT_1_6
+            .replacen(
+                r#"started="08/30/2015 19:36:33""#,
+                r#"started="yesterday""#,
+                1,
+            )
+            .replacen("00:00:37.5450000", "37 seconds", 1);
+
+        livesplit::parse(&source).unwrap_err();
+
+        let recovered = livesplit::parse_lenient(&source).unwrap();
+        assert_eq!(
+            recovered.warnings,
+            [
+                livesplit::Warning::DroppedAttempt { index: Some(2) },
+                livesplit::Warning::DroppedSegmentHistoryElement {
+                    segment_index: 0,
+                    index: Some(1),
+                },
+            ]
+        );
+
+        let run = livesplit(run_files::LIVESPLIT_1_6);
+        assert_eq!(
+            recovered.run.attempt_history().len(),
+            run.attempt_history().len() - 1
+        );
+        assert!(
+            !recovered
+                .run
+                .attempt_history()
+                .iter()
+                .any(|a| a.index() == 2)
+        );
+        assert!(recovered.run.segment(0).segment_history().get(1).is_none());
+        assert_eq!(recovered.run.len(), run.len());
+    }
+
+    #[test]
+    fn livesplit_lenient_recovers_truncated_files() {
+        let source = run_files::LIVESPLIT_1_6;
+        let source = &source[..source.find("<SegmentHistory>").unwrap() + 200];
+
+        livesplit::parse(source).unwrap_err();
+
+        let recovered = livesplit::parse_lenient(source).unwrap();
+        assert_eq!(recovered.warnings, [livesplit::Warning::Truncated]);
+        assert_eq!(
+            recovered.run.attempt_history().len(),
+            livesplit(run_files::LIVESPLIT_1_6).attempt_history().len()
+        );
+        assert_eq!(recovered.run.game_name(), "NES Open Tournament Golf");
+    }
+
+    #[test]
+    fn livesplit_lenient_rejects_other_files() {
+        livesplit::parse_lenient(run_files::URN).unwrap_err();
+    }
+
     #[test]
     fn livesplit_1_6_gametime() {
         livesplit(run_files::LIVESPLIT_1_6_GAMETIME);
@@ -132,7 +203,48 @@ mod parse {
 
     #[test]
     fn splitterino() {
-        splitterino::parse(run_files::SPLITTERINO).unwrap();
+        let run = splitterino::parse(run_files::SPLITTERINO).unwrap();
+        assert_eq!(run.attempt_history().len(), 1);
+        assert_eq!(
+            run.attempt_history()[0].time().real_time,
+            Some(TimeSpan::from_milliseconds(1889.0))
+        );
+        assert_eq!(run.attempt_history()[0].pause_time(), None);
+    }
+
+    #[test]
+    fn splitterino_pause_times() {
+        let run = splitterino::parse(run_files::SPLITTERINO_PAUSED).unwrap();
+        assert_eq!(run.offset(), TimeSpan::from_milliseconds(-1500.0));
+        assert_eq!(run.metadata().region_name(), "NTSC");
+
+        let attempt = &run.attempt_history()[0];
+        assert_eq!(
+            attempt.time().real_time,
+            Some(TimeSpan::from_milliseconds(26000.0))
+        );
+        assert_eq!(
+            attempt.time().game_time,
+            Some(TimeSpan::from_milliseconds(25000.0))
+        );
+        assert_eq!(
+            attempt.pause_time(),
+            Some(TimeSpan::from_milliseconds(2500.0))
+        );
+
+        assert_eq!(run.segment(1).personal_best_split_time().real_time, None);
+        assert_eq!(
+            run.segment(1).best_segment_time().real_time,
+            Some(TimeSpan::from_milliseconds(7500.0))
+        );
+        assert_eq!(
+            run.segment(1).segment_history().get(1).unwrap().real_time,
+            None
+        );
+        assert_eq!(
+            run.segment(2).segment_history().get(1).unwrap().real_time,
+            Some(TimeSpan::from_milliseconds(16000.0))
+        );
     }
 
     #[test]
@@ -167,7 +279,35 @@ mod parse {
 
     #[test]
     fn speedrun_igt() {
-        speedrun_igt::parse(run_files::SPEEDRUN_IGT).unwrap();
+        let run = speedrun_igt::parse(run_files::SPEEDRUN_IGT).unwrap();
+        assert_eq!(run.game_name(), "Minecraft: Java Edition");
+        assert_eq!(run.category_name(), "Any% Glitchless");
+        assert_eq!(run.len(), 9);
+        assert_eq!(run.segment(0).name(), "Enter Nether");
+        assert_eq!(run.segment(8).name(), "Finish");
+        assert_eq!(
+            run.segment(8).personal_best_split_time().game_time,
+            Some(TimeSpan::from_milliseconds(3_006_761.0))
+        );
+        assert_eq!(
+            run.attempt_history()[0].time().real_time,
+            Some(TimeSpan::from_milliseconds(3_427_897.0))
+        );
+    }
+
+    #[test]
+    fn speedrun_igt_all_advancements() {
+        let run = speedrun_igt::parse(run_files::SPEEDRUN_IGT_ALL_ADVANCEMENTS).unwrap();
+        assert_eq!(run.category_name(), "All Advancements");
+        let names = run.segments().iter().map(|s| s.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["Root", "Mine Stone", "Enter The Nether", "Finish"]);
+        assert_eq!(
+            run.segment(1).best_segment_time().game_time,
+            Some(TimeSpan::from_milliseconds(40_000.0))
+        );
+        assert_eq!(run.segment(3).personal_best_split_time().real_time, None);
+        assert!(run.segment(3).segment_history().get(1).is_none());
+        assert_eq!(run.attempt_history()[0].time().real_time, None);
     }
 
     #[test]
@@ -175,6 +315,80 @@ mod parse {
         opensplit::parse(run_files::OPENSPLIT).unwrap();
     }
 
+    #[test]
+    fn therun_gg() {
+        let run = therun_gg::parse(run_files::THERUN_GG).unwrap();
+        assert_eq!(run.game_name(), "Celeste");
+        assert_eq!(run.category_name(), "Any%");
+        assert_eq!(run.metadata().platform_name(), "PC");
+        assert_eq!(
+            run.metadata()
+                .speedrun_com_variables
+                .get("Version")
+                .map(|v| &**v),
+            Some("1.4")
+        );
+        assert_eq!(run.attempt_count(), 4);
+        assert_eq!(run.len(), 3);
+        assert_eq!(run.attempt_history().len(), 3);
+        assert_eq!(run.attempt_history()[0].time().real_time, None);
+        assert_eq!(
+            run.attempt_history()[2].time().real_time,
+            Some(TimeSpan::from_seconds(556.0))
+        );
+        assert_eq!(
+            run.segment(1).segment_history().get(2).unwrap().real_time,
+            Some(TimeSpan::from_milliseconds(190_200.0))
+        );
+        assert_eq!(
+            run.segment(2).personal_best_split_time().real_time,
+            Some(TimeSpan::from_seconds(556.0))
+        );
+    }
+
+    #[test]
+    fn composite_report_lists_all_rejections() {
+        let source = "{\n  \"game\": 5,\n  oops\n}";
+        let Err(report) = composite::parse_with_report(source.as_bytes(), None) else {
+            panic!("The file should not be parsed");
+        };
+
+        assert_eq!(report.invalid_utf8_offset, None);
+        assert_eq!(report.rejections[0].kind, TimerKind::LiveSplit);
+        assert_eq!(report.rejections.last().unwrap().kind, TimerKind::Llanfair);
+        assert!(report.rejections.iter().all(|r| !r.message.is_empty()));
+
+        let urn = report
+            .rejections
+            .iter()
+            .find(|r| r.kind == TimerKind::Urn)
+            .unwrap();
+        assert_eq!(urn.offset, Some(source.find("oops").unwrap()));
+    }
+
+    #[test]
+    fn composite_report_detects_invalid_utf8() {
+        let Err(report) = composite::parse_with_report(b"Name,\xFF\xFE", None) else {
+            panic!("The file should not be parsed");
+        };
+
+        assert_eq!(report.invalid_utf8_offset, Some(5));
+        assert_eq!(report.rejections.len(), 1);
+        assert_eq!(report.rejections[0].kind, TimerKind::Llanfair);
+    }
+
+    #[test]
+    fn composite_report_is_not_needed_for_valid_files() {
+        let run = composite::parse_with_report(run_files::URN.as_bytes(), None).unwrap();
+        assert_eq!(run.kind, TimerKind::Urn);
+    }
+
+    #[test]
+    fn therun_gg_prefers_parsing_as_itself() {
+        let run = composite::parse(run_files::THERUN_GG.as_bytes(), None).unwrap();
+        assert_eq!(run.kind, TimerKind::TheRunGg);
+    }
+
     #[test]
     fn speedrun_igt_prefers_parsing_as_itself() {
         let run = composite::parse(run_files::SPEEDRUN_IGT.as_bytes(), None).unwrap();
@@ -216,4 +430,12 @@ mod parse {
         let run = composite::parse(run_files::OPENSPLIT.as_bytes(), None).unwrap();
         assert_eq!(run.kind, TimerKind::OpenSplit);
     }
+
+    #[test]
+    fn binary_prefers_parsing_as_itself() {
+        let mut buf = Vec::new();
+        saver::binary::save_run(&livesplit(run_files::LIVESPLIT_1_6), &mut buf);
+        let run = composite::parse(&buf, None).unwrap();
+        assert_eq!(run.kind, TimerKind::Binary);
+    }
 }
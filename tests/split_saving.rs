@@ -0,0 +1,197 @@
+mod run_files;
+
+use livesplit_core::{
+    Run, Segment, Time, TimeSpan,
+    run::{parser, saver},
+};
+
+#[test]
+fn wsplit_round_trip() {
+    let run = parser::wsplit::parse(run_files::WSPLIT, false).unwrap();
+
+    let mut buf = String::new();
+    saver::wsplit::save_run(&run, &mut buf).unwrap();
+    let parsed = parser::wsplit::parse(&buf, false).unwrap();
+
+    assert_eq!(parsed.category_name(), run.category_name());
+    assert_eq!(parsed.attempt_count(), run.attempt_count());
+    assert_eq!(parsed.offset(), run.offset());
+    assert_eq!(parsed.len(), run.len());
+    for (parsed, original) in parsed.segments().iter().zip(run.segments()) {
+        assert_eq!(parsed.name(), original.name());
+        assert_eq!(
+            parsed.personal_best_split_time(),
+            original.personal_best_split_time()
+        );
+        assert_eq!(parsed.best_segment_time(), original.best_segment_time());
+    }
+}
+
+#[test]
+fn wsplit_replaces_commas_in_segment_names() {
+    let mut run = parser::wsplit::parse(run_files::WSPLIT, false).unwrap();
+    run.segment_mut(0).set_name("A, B");
+
+    let mut buf = String::new();
+    saver::wsplit::save_run(&run, &mut buf).unwrap();
+    let parsed = parser::wsplit::parse(&buf, false).unwrap();
+
+    assert_eq!(parsed.segment(0).name(), "A  B");
+    assert_eq!(parsed.len(), run.len());
+}
+
+#[test]
+fn urn_round_trip() {
+    let run = parser::urn::parse(run_files::URN).unwrap();
+
+    let mut buf = String::new();
+    saver::urn::save_run(&run, &mut buf).unwrap();
+    let parsed = parser::urn::parse(&buf).unwrap();
+
+    assert_eq!(parsed.category_name(), run.category_name());
+    assert_eq!(parsed.attempt_count(), run.attempt_count());
+    assert_eq!(parsed.offset(), run.offset());
+    assert_eq!(parsed.len(), run.len());
+    for (parsed, original) in parsed.segments().iter().zip(run.segments()) {
+        assert_eq!(parsed.name(), original.name());
+        assert_eq!(
+            parsed.personal_best_split_time(),
+            original.personal_best_split_time()
+        );
+        assert_eq!(parsed.best_segment_time(), original.best_segment_time());
+    }
+}
+
+#[test]
+fn splits_io_exchange_format() {
+    let run = parser::livesplit::parse(run_files::LIVESPLIT_1_6_GAMETIME).unwrap();
+
+    let mut buf = String::new();
+    saver::splits_io::save_run(&run, &mut buf).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&buf).unwrap();
+
+    assert_eq!(json["_schemaVersion"], "v1.0.1");
+    assert_eq!(json["game"]["longname"], run.game_name());
+    assert_eq!(json["category"]["longname"], run.category_name());
+    assert_eq!(json["attempts"]["total"], run.attempt_count());
+    assert_eq!(
+        json["attempts"]["histories"].as_array().unwrap().len(),
+        run.attempt_history().len()
+    );
+
+    let segments = json["segments"].as_array().unwrap();
+    assert_eq!(segments.len(), run.len());
+
+    let last = run.segments().last().unwrap();
+    let ended_at = &segments.last().unwrap()["endedAt"];
+    assert_eq!(
+        ended_at["gametimeMS"].as_i64().map(|ms| ms / 1000),
+        last.personal_best_split_time()
+            .game_time
+            .map(|t| (t.total_milliseconds() as i64) / 1000)
+    );
+    assert_eq!(
+        segments[0]["histories"].as_array().unwrap().len(),
+        run.segment(0).segment_history().iter_actual_runs().count()
+    );
+
+    let started_at = json["attempts"]["histories"][0]["startedAt"].as_str();
+    assert!(started_at.is_none_or(|s| s.ends_with('Z') && s.len() == 20));
+}
+
+#[test]
+fn binary_round_trip() {
+    for source in [run_files::LIVESPLIT_1_6_GAMETIME, run_files::CELESTE] {
+        let run = parser::livesplit::parse(source).unwrap();
+
+        let mut buf = Vec::new();
+        saver::binary::save_run(&run, &mut buf);
+        let parsed = parser::binary::parse(&buf).unwrap();
+
+        assert_eq!(parsed, run);
+
+        let (mut original_lss, mut parsed_lss) = (String::new(), String::new());
+        saver::livesplit::save_run(&run, &mut original_lss).unwrap();
+        saver::livesplit::save_run(&parsed, &mut parsed_lss).unwrap();
+        assert_eq!(parsed_lss, original_lss);
+    }
+}
+
+#[test]
+fn binary_rejects_truncated_and_future_files() {
+    let run = parser::livesplit::parse(run_files::LIVESPLIT_1_6).unwrap();
+
+    let mut buf = Vec::new();
+    saver::binary::save_run(&run, &mut buf);
+
+    for len in [0, 4, 6, buf.len() / 2, buf.len() - 1] {
+        assert!(parser::binary::parse(&buf[..len]).is_err());
+    }
+
+    buf[4] = 0xFF;
+    assert!(matches!(
+        parser::binary::parse(&buf),
+        Err(parser::binary::Error::UnsupportedVersion { .. })
+    ));
+}
+
+#[test]
+fn livesplit_chunked_matches_save_run() {
+    for source in [
+        run_files::LIVESPLIT_1_0,
+        run_files::LIVESPLIT_1_6_GAMETIME,
+        run_files::CELESTE,
+    ] {
+        let run = parser::livesplit::parse(source).unwrap();
+
+        let mut expected = String::new();
+        saver::livesplit::save_run(&run, &mut expected).unwrap();
+
+        let mut chunked = String::new();
+        let mut chunks = saver::livesplit::save_run_chunked(&run);
+        while let Some(chunk) = chunks.next_chunk() {
+            chunked.push_str(chunk);
+        }
+
+        assert_eq!(chunked, expected);
+    }
+}
+
+#[test]
+fn livesplit_chunked_splits_up_segment_histories() {
+    let mut run = Run::new();
+    let mut segment = Segment::new("A");
+    for index in 1..=1000 {
+        let time = Time::new().with_real_time(Some(TimeSpan::from_seconds(index as f64)));
+        segment.segment_history_mut().insert(index, time);
+    }
+    run.push_segment(segment);
+
+    let mut expected = String::new();
+    saver::livesplit::save_run(&run, &mut expected).unwrap();
+
+    let mut chunked = String::new();
+    let mut chunks = saver::livesplit::save_run_chunked(&run);
+    while let Some(chunk) = chunks.next_chunk() {
+        assert!(chunk.matches("<Time ").count() <= 256);
+        chunked.push_str(chunk);
+    }
+
+    assert_eq!(chunked, expected);
+}
+
+#[test]
+fn livesplit_chunked_handles_empty_runs() {
+    let run = Run::new();
+
+    let mut expected = String::new();
+    saver::livesplit::save_run(&run, &mut expected).unwrap();
+
+    let mut chunked = String::new();
+    let mut chunks = saver::livesplit::save_run_chunked(&run);
+    while let Some(chunk) = chunks.next_chunk() {
+        chunked.push_str(chunk);
+    }
+
+    assert_eq!(chunked, expected);
+}
@@ -0,0 +1,194 @@
+use livesplit_core::{
+    Layout, Run, Segment, Timer,
+    component::{
+        separator,
+        tabs::{self, Tab},
+        text, timer,
+    },
+    layout::{Component, ComponentSettings, ComponentState, LayoutSettings},
+    settings::{ImageCache, ValidationError, Value, ValueList},
+};
+
+fn tabs() -> tabs::Component {
+    let mut component = tabs::Component::new();
+    let mut splits = Tab::new("Splits".into());
+    splits.components.push(timer::Component::new().into());
+    let mut stats = Tab::new("Stats".into());
+    stats.components.push(text::Component::new().into());
+    stats.components.push(separator::Component::new().into());
+    component.push_tab(splits);
+    component.push_tab(stats);
+    component
+}
+
+fn timer() -> Timer {
+    let mut run = Run::new();
+    run.push_segment(Segment::new("A"));
+    Timer::new(run).unwrap()
+}
+
+#[test]
+fn switching_tabs_wraps_around() {
+    let mut component = tabs();
+    assert_eq!(component.selected_tab_index(), 0);
+    component.next_tab();
+    assert_eq!(component.selected_tab().unwrap().name, "Stats");
+    component.next_tab();
+    assert_eq!(component.selected_tab_index(), 0);
+    component.previous_tab();
+    assert_eq!(component.selected_tab_index(), 1);
+    component.select_tab(5);
+    assert_eq!(component.selected_tab_index(), 1);
+}
+
+#[test]
+fn state_contains_selected_tab_only() {
+    let mut layout = Layout::new();
+    layout.push(tabs());
+
+    let timer = timer();
+    let mut image_cache = ImageCache::new();
+    let mut state = layout.state(&mut image_cache, &timer.snapshot());
+
+    let ComponentState::Tabs(tabs) = &state.components[0] else {
+        panic!("not a tabs component state");
+    };
+    assert_eq!(tabs.tab_names, ["Splits", "Stats"]);
+    assert_eq!(tabs.selected_tab, 0);
+    assert!(matches!(tabs.components[..], [ComponentState::Timer(_)]));
+
+    layout.next_tab();
+    layout.update_state(&mut state, &mut image_cache, &timer.snapshot());
+
+    let ComponentState::Tabs(tabs) = &state.components[0] else {
+        panic!("not a tabs component state");
+    };
+    assert_eq!(tabs.selected_tab, 1);
+    assert!(matches!(
+        tabs.components[..],
+        [ComponentState::Text(_), ComponentState::Separator(_)]
+    ));
+}
+
+#[test]
+fn settings_round_trip() {
+    let mut component = tabs();
+    component.next_tab();
+
+    let mut layout = Layout::new();
+    layout.push(component);
+
+    let json = serde_json::to_string(&layout.settings()).unwrap();
+    let settings: LayoutSettings = serde_json::from_str(&json).unwrap();
+    let ComponentSettings::Tabs(settings) = &settings.components[0] else {
+        panic!("not the settings of a tabs component");
+    };
+    assert_eq!(settings.selected_tab, 1);
+    assert_eq!(settings.tabs[1].name, "Stats");
+    assert_eq!(settings.tabs[1].components.len(), 2);
+}
+
+#[test]
+fn tab_names_are_a_list_setting() {
+    let mut component = tabs();
+    component.next_tab();
+
+    let fields = component.settings_description().fields;
+    let Value::List(mut names) = fields[3].value.clone() else {
+        panic!("the tab names are not a list");
+    };
+    let current: Vec<String> = names.items.iter().cloned().map(String::from).collect();
+    assert_eq!(current, ["Splits", "Stats"]);
+
+    names.items[0] = Value::String("Timer".into());
+    names.items.pop();
+    names.items.push(Value::String("Graphs".into()));
+    names.items.push(*names.new_item.clone());
+    component.set_value(3, names.into());
+
+    let settings = component.settings();
+    assert_eq!(settings.tabs.len(), 3);
+    assert_eq!(settings.tabs[0].name, "Timer");
+    assert_eq!(settings.tabs[1].name, "Graphs");
+    assert_eq!(settings.tabs[1].components.len(), 2);
+    assert_eq!(settings.tabs[2].name, "");
+    assert!(settings.tabs[2].components.is_empty());
+
+    let names = ValueList::new([String::from("Only")], String::new().into());
+    component.set_value(3, names.into());
+    assert_eq!(component.settings().tabs.len(), 1);
+    assert_eq!(component.selected_tab_index(), 0);
+}
+
+#[test]
+fn components_move_with_the_tab_names() {
+    let mut component = tabs();
+    let mut notes = Tab::new("Notes".into());
+    notes.components.push(timer::Component::new().into());
+    component.push_tab(notes);
+    component.select_tab(2);
+
+    let names = |names: &[&str]| {
+        ValueList::new(
+            names.iter().map(|&name| String::from(name)),
+            String::new().into(),
+        )
+        .into()
+    };
+    let component_counts = |component: &tabs::Component| {
+        component
+            .tabs()
+            .iter()
+            .map(|tab| (tab.name.clone(), tab.components.len()))
+            .collect::<Vec<_>>()
+    };
+
+    component.set_value(3, names(&["Notes", "Splits", "Stats"]));
+    assert_eq!(
+        component_counts(&component),
+        [
+            ("Notes".into(), 1),
+            ("Splits".into(), 1),
+            ("Stats".into(), 2)
+        ],
+    );
+    assert_eq!(component.selected_tab().unwrap().name, "Notes");
+
+    component.set_value(3, names(&["Notes", "Stats"]));
+    assert_eq!(
+        component_counts(&component),
+        [("Notes".into(), 1), ("Stats".into(), 2)],
+    );
+}
+
+#[test]
+fn tab_names_that_are_not_strings_are_ignored() {
+    let mut component = tabs();
+    let mut names = ValueList::new([String::from("Splits")], String::new().into());
+    names.items.push(Value::Bool(true));
+    names.items.push(Value::String("Stats".into()));
+    component.set_value(3, names.into());
+
+    let names: Vec<&str> = component.tabs().iter().map(|tab| &*tab.name).collect();
+    assert_eq!(names, ["Splits", "Stats"]);
+    assert_eq!(component.tabs()[1].components.len(), 2);
+}
+
+#[test]
+fn tab_names_that_are_not_strings_are_rejected_by_the_layout() {
+    let mut component = Component::from(tabs());
+    let mut names = ValueList::new([String::from("Splits")], String::new().into());
+    names.items.push(Value::Bool(true));
+    assert!(matches!(
+        component.set_value(3, names.into()),
+        Err(ValidationError::WrongType),
+    ));
+
+    let names = ValueList::new([String::from("Notes")], String::new().into());
+    component.set_value(3, names.into()).unwrap();
+    let Component::Tabs(component) = component else {
+        unreachable!()
+    };
+    let names: Vec<&str> = component.tabs().iter().map(|tab| &*tab.name).collect();
+    assert_eq!(names, ["Notes"]);
+}
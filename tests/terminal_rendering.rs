@@ -0,0 +1,87 @@
+#![cfg(feature = "terminal-rendering")]
+
+mod run_files;
+#[path = "../src/util/tests_helper.rs"]
+mod tests_helper;
+
+use livesplit_core::{
+    Layout, Run, Segment, TimeSpan, Timer, TimingMethod,
+    rendering::terminal::{CONTINUATION, Renderer},
+    run::parser::livesplit,
+};
+
+fn text(renderer: &Renderer) -> String {
+    let [columns, _] = renderer.size();
+    renderer
+        .cells()
+        .chunks(columns)
+        .map(|row| {
+            let line: String = row
+                .iter()
+                .map(|cell| cell.symbol)
+                .filter(|&symbol| symbol != CONTINUATION)
+                .collect();
+            line.trim_end().to_owned() + "\n"
+        })
+        .collect()
+}
+
+#[test]
+fn default_layout() {
+    let run = livesplit::parse(run_files::LIVESPLIT_1_6_GAMETIME).unwrap();
+    let mut timer = Timer::new(run).unwrap();
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(&mut timer, &[Some(10.0), None, Some(20.0)]);
+
+    let mut layout = Layout::default_layout();
+    let state = layout.state(&mut Default::default(), &timer.snapshot());
+
+    let mut renderer = Renderer::new();
+    assert_eq!(renderer.render(&state, [32, 20]), 20);
+    assert_eq!(
+        text(&renderer),
+        " Dishonored
+ Any%                       1959
+ WatchYr<3Die        −3:01  0:10
+ Elevator Land           —     —
+ Gross, Rats         −6:09  0:20
+ Try Not Falling?          10:10
+ Men of the Faith          12:24
+ Fuckin Celia              14:12
+ Tr4p Haus                 16:51
+ Troubled Waters           21:39
+ RNG Roulette              24:55
+ Not At Fault              28:46
+ Amateur Work              34:42
+ Don't Mind Me             35:41
+ H9 U Havelock             37:37
+
+
+
+                           20.00
+ Previous Segment          −3:08
+"
+    );
+}
+
+#[test]
+fn wide_characters_cover_two_cells() {
+    let timer = Timer::new(tests_helper::create_run(&["日本語のセグメント", "B"])).unwrap();
+    let mut layout = Layout::default_layout();
+    let state = layout.state(&mut Default::default(), &timer.snapshot());
+
+    let mut renderer = Renderer::new();
+    renderer.render(&state, [16, 3]);
+
+    let cells = &renderer.cells()[16..];
+    assert_eq!(cells[1].symbol, '日');
+    assert_eq!(cells[2].symbol, CONTINUATION);
+    assert_eq!(cells[3].symbol, '本');
+
+    let mut ansi = String::new();
+    renderer.write_ansi(&mut ansi).unwrap();
+    assert_eq!(
+        ansi.split("\r\n").nth(1),
+        Some("\x1b[48;2;15;15;15m \x1b[38;2;255;255;255m日本語のセグ… —\x1b[0m"),
+    );
+}
@@ -0,0 +1,109 @@
+use livesplit_core::{
+    Layout, LayoutEditor,
+    component::graph,
+    layout::{Theme, ThemeColor, ThemedColor},
+    settings::{Color, ImageCache},
+};
+
+#[test]
+fn default_settings_use_dark_theme() {
+    let layout = Layout::default_layout();
+    assert_eq!(
+        Theme::from_general_settings("Dark".into(), layout.general_settings()),
+        Theme::dark(),
+    );
+}
+
+#[test]
+fn applying_theme_restyles_theme_colors() {
+    let mut layout = Layout::default_layout();
+    let light = Theme::light();
+    layout.general_settings_mut().apply_theme(&light);
+
+    for color in ThemeColor::ALL {
+        assert_eq!(color.resolve(layout.general_settings()), light.color(color));
+    }
+
+    let component = graph::Component::new();
+    let settings = component.settings();
+    assert_eq!(
+        settings
+            .graph_lines_color
+            .resolve(layout.general_settings()),
+        light.text_primary,
+    );
+    assert_eq!(
+        settings.grid_lines_color.resolve(layout.general_settings()),
+        Color::rgba(0.0, 0.0, 0.0, 0.15),
+    );
+}
+
+#[test]
+fn themed_color_accepts_plain_colors() {
+    let color: ThemedColor = serde_json::from_str("[1.0,0.5,0.25,1.0]").unwrap();
+    assert_eq!(color, ThemedColor::Custom(Color::rgba(1.0, 0.5, 0.25, 1.0)));
+    let color: ThemedColor = serde_json::from_str(r#""AheadGaining""#).unwrap();
+    assert_eq!(color, ThemedColor::Theme(ThemeColor::AheadGaining));
+}
+
+#[test]
+fn palette_colors_update_every_reference() {
+    let accent = ThemedColor::Palette {
+        name: "Accent".into(),
+    };
+    let mut component = graph::Component::new();
+    component.settings_mut().graph_lines_color = accent.clone();
+    component.settings_mut().complete_fill_color = accent.clone();
+    let mut layout = Layout::new();
+    layout.push(component);
+
+    let mut editor = LayoutEditor::new(layout).unwrap();
+    let red = Color::rgba(1.0, 0.0, 0.0, 1.0);
+    editor.set_palette_color("Accent", red);
+
+    let mut image_cache = ImageCache::new();
+    let state = editor.state(&mut image_cache);
+    let (index, field) = state
+        .general_settings
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(_, field)| field.key == "palette.Accent")
+        .unwrap();
+    assert_eq!(field.text, "Accent");
+    assert_eq!(
+        state.general_settings.groups.last().unwrap().fields(),
+        index..index + 1
+    );
+
+    let blue = Color::rgba(0.0, 0.0, 1.0, 1.0);
+    editor
+        .set_general_settings_value(index, blue.into(), &image_cache)
+        .unwrap();
+
+    let layout = editor.close();
+    let settings = layout.general_settings();
+    assert_eq!(accent.resolve(settings), blue);
+    assert_eq!(
+        ThemedColor::Palette {
+            name: "Missing".into()
+        }
+        .resolve(settings),
+        Color::transparent(),
+    );
+}
+
+#[test]
+fn palette_references_are_distinguishable_from_theme_colors() {
+    let color: ThemedColor = serde_json::from_str(r#"{"palette":"Paused"}"#).unwrap();
+    assert_eq!(
+        color,
+        ThemedColor::Palette {
+            name: "Paused".into()
+        }
+    );
+    assert_eq!(
+        serde_json::to_string(&color).unwrap(),
+        r#"{"palette":"Paused"}"#
+    );
+}
@@ -0,0 +1,221 @@
+#![cfg(feature = "wgpu-rendering")]
+
+mod layout_files;
+mod run_files;
+#[path = "../src/util/tests_helper.rs"]
+mod tests_helper;
+
+use livesplit_core::{
+    Layout, Run, Segment, TimeSpan, Timer, TimingMethod,
+    layout::{self, LayoutState},
+    rendering::{
+        software,
+        wgpu::{Renderer, wgpu},
+    },
+    run::parser::livesplit,
+    settings::{BlendMode, Color, Gradient, ImageCache, LayoutBackground},
+};
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use wgpu::{Device, Queue};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+/// Not every machine that runs the tests has a GPU, in which case the tests
+/// are skipped.
+fn device() -> Option<(Device, Queue)> {
+    let instance =
+        wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle_from_env());
+    let adapter = block_on(instance.request_adapter(&Default::default())).ok()?;
+    block_on(adapter.request_device(&Default::default())).ok()
+}
+
+fn render_gpu(
+    renderer: &mut Renderer,
+    (device, queue): &(Device, Queue),
+    state: &LayoutState,
+    image_cache: &ImageCache,
+    [width, height]: [u32; 2],
+) -> Vec<u8> {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    renderer.render(state, image_cache, &texture);
+
+    let bytes_per_row = (4 * width).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (bytes_per_row * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    queue.submit([encoder.finish()]);
+
+    buffer.map_async(wgpu::MapMode::Read, .., |result| result.unwrap());
+    device.poll(wgpu::PollType::wait_indefinitely()).unwrap();
+    let data = buffer.get_mapped_range(..).unwrap();
+    data.chunks(bytes_per_row as usize)
+        .flat_map(|row| &row[..4 * width as usize])
+        .copied()
+        .collect()
+}
+
+/// The renderers anti-alias differently, so the images are only compared by
+/// how different they are on average.
+#[track_caller]
+fn assert_similar(gpu: &[u8], cpu: &[u8]) {
+    assert_eq!(gpu.len(), cpu.len());
+    let difference: u64 = gpu
+        .iter()
+        .zip(cpu)
+        .map(|(a, b)| a.abs_diff(*b) as u64)
+        .sum();
+    let mean = difference as f64 / gpu.len() as f64;
+    assert!(mean < 1.5, "The images differ by {mean} on average.");
+}
+
+#[track_caller]
+fn check(gpu: &(Device, Queue), state: &LayoutState, image_cache: &ImageCache, dims: [u32; 2]) {
+    let mut renderer = Renderer::new(&gpu.0, &gpu.1);
+    let gpu_image = render_gpu(&mut renderer, gpu, state, image_cache, dims);
+
+    let mut software = software::Renderer::new();
+    software.render(state, image_cache, dims);
+
+    assert_similar(&gpu_image, software.image_data());
+}
+
+#[test]
+fn layouts_look_like_with_the_software_renderer() {
+    let Some(gpu) = device() else { return };
+
+    let run = livesplit::parse(run_files::LIVESPLIT_1_6_GAMETIME).unwrap();
+    let mut timer = Timer::new(run).unwrap();
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(
+        &mut timer,
+        &[Some(10.0), None, Some(20.0), Some(55.0)],
+    );
+
+    for (layout, dims) in [
+        (Layout::default_layout(), [300, 500]),
+        (
+            layout::parser::parse(layout_files::ALL).unwrap(),
+            [300, 800],
+        ),
+        (
+            layout::parser::parse(layout_files::DARK).unwrap(),
+            [300, 500],
+        ),
+        (
+            layout::parser::parse(layout_files::WITH_BACKGROUND_IMAGE).unwrap(),
+            [300, 300],
+        ),
+        (
+            Layout::from_settings(serde_json::from_str(layout_files::TEXT_SHADOW).unwrap()),
+            [300, 300],
+        ),
+    ] {
+        let mut layout = layout;
+        let mut image_cache = ImageCache::new();
+        let state = layout.state(&mut image_cache, &timer.snapshot());
+        check(&gpu, &state, &image_cache, dims);
+    }
+}
+
+#[test]
+fn gradients_and_blend_modes_are_evaluated_on_the_gpu() {
+    let Some(gpu) = device() else { return };
+
+    let timer = Timer::new(tests_helper::create_run(&["A", "B"])).unwrap();
+    let (red, blue) = (
+        Color::rgba(1.0, 0.0, 0.0, 1.0),
+        Color::rgba(0.0, 0.0, 1.0, 0.5),
+    );
+
+    for (background, blend_mode) in [
+        (
+            Gradient::Radial(red, blue, [0.3, 0.5], 0.6),
+            BlendMode::Multiply,
+        ),
+        (
+            Gradient::Conic(red, blue, [0.5, 0.5], 45.0),
+            BlendMode::Screen,
+        ),
+        (Gradient::Angled(red, blue, 30.0), BlendMode::Overlay),
+        (Gradient::Vertical(blue, red), BlendMode::Lighten),
+    ] {
+        let mut layout = Layout::default_layout();
+        layout.general_settings_mut().background = LayoutBackground::Gradient(background);
+        for index in 0..layout.components().len() {
+            let appearance = layout.component_appearance_mut(index);
+            appearance.opacity = 0.7;
+            appearance.blend_mode = blend_mode;
+        }
+
+        let mut image_cache = ImageCache::new();
+        let state = layout.state(&mut image_cache, &timer.snapshot());
+        check(&gpu, &state, &image_cache, [250, 400]);
+    }
+}
+
+#[test]
+fn only_the_top_layer_is_redrawn_between_frames() {
+    let Some(gpu) = device() else { return };
+
+    let mut timer = Timer::new(tests_helper::create_run(&["A", "B"])).unwrap();
+    let mut layout = Layout::default_layout();
+    let mut renderer = Renderer::new(&gpu.0, &gpu.1);
+    let mut image_cache = ImageCache::new();
+
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    render_gpu(&mut renderer, &gpu, &state, &image_cache, [300, 500]);
+
+    // The timer is on the top layer, so starting it only changes that layer,
+    // which still needs to end up on top of the bottom layer drawn before.
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(&mut timer, &[Some(5.0)]);
+    let state = layout.state(&mut image_cache, &timer.snapshot());
+    let gpu_image = render_gpu(&mut renderer, &gpu, &state, &image_cache, [300, 500]);
+
+    let mut software = software::Renderer::new();
+    software.render(&state, &image_cache, [300, 500]);
+    assert_similar(&gpu_image, software.image_data());
+}